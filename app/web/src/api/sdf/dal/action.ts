@@ -9,6 +9,13 @@ export enum ActionState {
   Running = "Running",
 }
 
+/** What to do with a pending action when its change set is applied */
+export enum ActionSelection {
+  Run = "run",
+  Hold = "hold",
+  Drop = "drop",
+}
+
 export enum ActionKind {
   Create = "Create",
   Destroy = "Destroy",
@@ -27,4 +34,40 @@ export enum ActionResultState {
   Success = "Success",
   Failure = "Failure",
   Unknown = "Unknown",
+  Timeout = "Timeout",
+}
+
+/** How a resource would change if its action ran, as described by the action's plan func */
+export enum PlannedChangeKind {
+  Create = "create",
+  NoOp = "noOp",
+  Replace = "replace",
+  UpdateInPlace = "updateInPlace",
+}
+
+export interface PlannedFieldChange {
+  path: string;
+  before?: unknown;
+  after?: unknown;
+  forcesReplacement: boolean;
+}
+
+export interface PlannedChange {
+  action: PlannedChangeKind;
+  changes: PlannedFieldChange[];
+  message?: string | null;
+}
+
+export interface ActionChangePlan {
+  actionId: ActionId;
+  funcRunId: string;
+  funcRunState: string;
+  plannedChange?: PlannedChange | null;
+}
+
+/** Set on a queued HEAD action while every maintenance window covering it is closed */
+export interface MaintenanceWindowHold {
+  windowIds: string[];
+  windowNames: string[];
+  nextOpenAt?: string | null;
 }
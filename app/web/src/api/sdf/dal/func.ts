@@ -19,6 +19,7 @@ export enum FuncKind {
   SchemaVariantDefinition = "SchemaVariantDefinition",
   Unknown = "Unknown",
   Management = "Management",
+  Plan = "Plan",
 }
 
 export enum CustomizableFuncKind {
@@ -109,16 +110,20 @@ export interface FuncArgument {
 }
 
 export enum FuncBackendKind {
+  Arithmetic = "Arithmetic",
   Array = "Array",
   Boolean = "Boolean",
+  Coalesce = "Coalesce",
   Diff = "Diff",
   Identity = "Identity",
   Float = "Float",
   Integer = "Integer",
+  JoinArray = "JoinArray",
   JsAction = "JsAction",
   JsAttribute = "JsAction",
   JsAuthentication = "JsAuthentication",
   Json = "Json",
+  JsonPointer = "JsonPointer",
   JsReconciliation = "JsReconciliation",
   JsSchemaVariantDefinition = "JsSchemaVariantDefinition",
   Map = "Map",
@@ -126,8 +131,10 @@ export enum FuncBackendKind {
   Object = "Object",
   ResourcePayloadToValue = "ResourcePayloadToValue",
   String = "String",
+  StringTemplate = "StringTemplate",
   Unset = "Unset",
   Validation = "Validation",
+  Wasm = "Wasm",
 }
 export interface FuncSummary {
   funcId: FuncId;
@@ -136,6 +143,7 @@ export interface FuncSummary {
   displayName: string | null;
   description: string | null;
   isLocked: boolean;
+  alwaysExecute: boolean;
   arguments: FuncArgument[];
   backendKind: FuncBackendKind;
   bindings: FuncBinding[];
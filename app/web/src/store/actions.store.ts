@@ -8,12 +8,14 @@ import { ChangeSetId } from "@/api/sdf/dal/change_set";
 import { ComponentId } from "@/api/sdf/dal/component";
 import { omit } from "@/utils/omit";
 import {
+  ActionChangePlan,
   ActionId,
   ActionKind,
   ActionPrototype,
   ActionPrototypeId,
   ActionState,
   ActionResultState,
+  MaintenanceWindowHold,
 } from "@/api/sdf/dal/action";
 import { useChangeSetsStore } from "./change_sets.store";
 import { useRealtimeStore } from "./realtime/realtime.store";
@@ -73,6 +75,7 @@ export interface ActionProposedView extends ActionView {
   myDependencies: ActionId[];
   dependentOn: ActionId[];
   holdStatusInfluencedBy: ActionId[];
+  maintenanceWindowHold?: MaintenanceWindowHold | null;
 }
 
 export interface ActionHistoryView extends ActionView {
@@ -86,6 +89,7 @@ export interface ActionHistoryView extends ActionView {
   arguments?: string;
   componentName: string;
   schemaName: string;
+  attempt: number;
 }
 
 export interface ChangeSetDetail {
@@ -119,6 +123,7 @@ export const useActionsStore = () => {
           rawActionsByComponentId: {} as Record<ComponentId, ActionPrototype[]>,
           actions: [] as ActionProposedView[],
           actionHistory: [] as ActionHistoryView[],
+          changePlansByActionId: {} as Record<ActionId, ActionChangePlan>,
         }),
         getters: {
           actionsAreInProgress(): boolean {
@@ -279,6 +284,19 @@ export const useActionsStore = () => {
               },
             });
           },
+          // What each queued action would change, computed by its plan func
+          async LOAD_ACTION_CHANGE_PLANS() {
+            return new ApiRequest<Array<ActionChangePlan>>({
+              url: "/action/change_plans",
+              headers: { accept: "application/json" },
+              params: {
+                visibility_change_set_pk: changeSetId,
+              },
+              onSuccess: (response) => {
+                this.changePlansByActionId = _.keyBy(response, "actionId");
+              },
+            });
+          },
           async LOAD_ACTION_HISTORY() {
             return new ApiRequest<Array<ActionHistoryView>>({
               url: "/action/history",
@@ -366,6 +384,22 @@ export const useActionsStore = () => {
             });
           },
 
+          // Requires the approval permission
+          async OVERRIDE_MAINTENANCE_WINDOW_HOLD(actionId: ActionId) {
+            return new ApiRequest<null>({
+              method: "post",
+              url: [
+                "v2",
+                "workspaces",
+                { workspaceId },
+                "maintenance-windows",
+                "overrides",
+                { actionId },
+              ],
+              keyRequestStatusBy: actionId,
+            });
+          },
+
           registerRequestsBegin(requestUlid: string, actionName: string) {
             realtimeStore.inflightRequests.set(requestUlid, actionName);
           },
@@ -380,6 +414,7 @@ export const useActionsStore = () => {
               callback: () => {
                 this.LOAD_ACTIONS();
                 this.LOAD_ACTION_HISTORY();
+                this.LOAD_ACTION_CHANGE_PLANS();
               },
             },
             {
@@ -11,6 +11,7 @@ import {
   ChangeSetStatus,
 } from "@/api/sdf/dal/change_set";
 import { WorkspaceMetadata } from "@/api/sdf/dal/workspace";
+import { ActionId, ActionSelection } from "@/api/sdf/dal/action";
 import router from "@/router";
 import { UserId, useAuthStore } from "@/store/auth.store";
 import IncomingChangesMerging from "@/components/toasts/IncomingChangesMerging.vue";
@@ -313,6 +314,19 @@ export function useChangeSetsStore() {
             url: BASE_API.concat([{ selectedChangeSetId }, "reopen"]),
           });
         },
+        // Decides what happens to pending actions when the change set is applied. This has to
+        // happen before approval, since approvers sign off on it along with everything else
+        async SELECT_ACTIONS_FOR_APPLY(
+          actionSelection: Record<ActionId, ActionSelection>,
+        ) {
+          if (!this.selectedChangeSet) throw new Error("Select a change set");
+          const selectedChangeSetId = this.selectedChangeSetId;
+          return new ApiRequest({
+            method: "post",
+            url: BASE_API.concat([{ selectedChangeSetId }, "action_selection"]),
+            params: { actionSelection },
+          });
+        },
         async APPLY_CHANGE_SET(username: string) {
           if (!this.selectedChangeSet) throw new Error("Select a change set");
           const selectedChangeSetId = this.selectedChangeSetId;
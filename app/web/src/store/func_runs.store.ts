@@ -27,6 +27,13 @@ export enum FuncRunState {
   Success = "Success",
 }
 
+export enum ExecutionPriority {
+  Action = "Action",
+  Background = "Background",
+  Dvu = "Dvu",
+  Interactive = "Interactive",
+}
+
 export enum FuncKind {
   Action = "action",
   Attribute = "attribute",
@@ -107,6 +114,7 @@ export interface FuncRun {
   actionDisplayName?: string;
   actionOriginatingChangeSetId?: ChangeSetId;
   actionResultState?: ActionResultState;
+  priority: ExecutionPriority;
   backendKind: FuncBackendKind;
   backendResponseType: FuncBackendResponseType;
   functionName: string;
@@ -130,6 +138,26 @@ export interface GetFuncRunResponse {
   funcRun?: FuncRun;
 }
 
+export type FuncRunReplayCode = "original" | "current";
+
+export interface FuncRunReplay {
+  originalFuncRunId: FuncRunId;
+  replayFuncRunId: FuncRunId;
+  code: FuncRunReplayCode;
+  codeChanged: boolean;
+  originalState: FuncRunState;
+  replayState: FuncRunState;
+  originalValue?: unknown;
+  replayValue?: unknown;
+}
+
+export interface ReplayFuncRunResponse {
+  replay: FuncRunReplay;
+  resultsMatch: boolean;
+  originalFuncRun: FuncRun;
+  replayFuncRun: FuncRun;
+}
+
 export const useFuncRunsStore = () => {
   const workspacesStore = useWorkspacesStore();
   const workspaceId = workspacesStore.selectedWorkspacePk;
@@ -147,6 +175,8 @@ export const useFuncRunsStore = () => {
       state: () => ({
         funcRuns: {} as Record<FuncRunId, FuncRun>,
         lastRuns: {} as Record<ActionId, Date>,
+        // keyed by the id of the func run that was replayed
+        replays: {} as Record<FuncRunId, ReplayFuncRunResponse>,
       }),
       actions: {
         async GET_FUNC_RUN(funcRunId: FuncRunId) {
@@ -162,6 +192,22 @@ export const useFuncRunsStore = () => {
           });
         },
 
+        async REPLAY_FUNC_RUN(
+          funcRunId: FuncRunId,
+          code: FuncRunReplayCode = "original",
+        ) {
+          return new ApiRequest<ReplayFuncRunResponse>({
+            method: "post",
+            url: `${API_PREFIX}/funcs/runs/${funcRunId}/replay`,
+            keyRequestStatusBy: funcRunId,
+            params: { code },
+            onSuccess: (response) => {
+              this.funcRuns[response.replayFuncRun.id] = response.replayFuncRun;
+              this.replays[funcRunId] = response;
+            },
+          });
+        },
+
         registerRequestsBegin(requestUlid: string, actionName: string) {
           realtimeStore.inflightRequests.set(requestUlid, actionName);
         },
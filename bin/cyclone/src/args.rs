@@ -1,7 +1,7 @@
 use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
 use clap::{ArgAction, Parser};
-use cyclone_server::{Config, ConfigError, IncomingStream};
+use cyclone_server::{Config, ConfigError, IncomingStream, WarmWorkersConfig, WasmConfig};
 
 const NAME: &str = "cyclone";
 
@@ -157,6 +157,28 @@ pub(crate) struct Args {
     /// Disables process gatherer.
     #[arg(long, group = "gatherer")]
     pub(crate) disable_process_gatherer: bool,
+
+    /// Serves executions from this many long-lived lang server processes, instead of a fresh
+    /// process per execution. Only for trusted workloads.
+    #[arg(long)]
+    pub(crate) warm_workers: Option<usize>,
+
+    /// Replaces a warm lang server process after it has served this many executions.
+    #[arg(long, default_value = "100", requires = "warm_workers")]
+    pub(crate) warm_worker_recycle_after: u32,
+
+    /// Replaces a warm lang server process once its resident memory exceeds this many megabytes.
+    #[arg(long, default_value = "512", requires = "warm_workers")]
+    pub(crate) warm_worker_memory_limit_mb: u64,
+
+    /// Allows WebAssembly functions to send HTTP requests to this host. A host starting with `*.`
+    /// allows all of its subdomains. May be given more than once.
+    #[arg(long = "wasm-http-allow", value_name = "HOST")]
+    pub(crate) wasm_http_allowlist: Vec<String>,
+
+    /// Limits the memory of a single WebAssembly function execution to this many megabytes.
+    #[arg(long, default_value = "256")]
+    pub(crate) wasm_memory_limit_mb: usize,
 }
 
 impl TryFrom<Args> for Config {
@@ -222,6 +244,20 @@ impl TryFrom<Args> for Config {
         } else if args.disable_process_gatherer {
             builder.enable_forwarder(false);
         }
+
+        if let Some(pool_size) = args.warm_workers {
+            builder.warm_workers(WarmWorkersConfig {
+                pool_size,
+                recycle_after: args.warm_worker_recycle_after,
+                memory_limit_bytes: Some(args.warm_worker_memory_limit_mb * 1024 * 1024),
+            });
+        }
+
+        builder.wasm(WasmConfig {
+            http_allowlist: args.wasm_http_allowlist,
+            memory_limit_bytes: args.wasm_memory_limit_mb * 1024 * 1024,
+        });
+
         builder.build().map_err(Into::into)
     }
 }
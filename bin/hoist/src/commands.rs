@@ -8,6 +8,7 @@ pub enum Commands {
     CompareSpecs(CompareSpecsArgs),
     GetDiffForAsset(GetDiffForAssetArgs),
     GetDiffSummary(GetDiffSummaryArgs),
+    RunFuncTests(RunFuncTestsArgs),
     UploadAllSpecs(UploadAllSpecsArgs),
     UploadSpec(UploadSpecArgs),
     WriteAllSpecs(WriteAllSpecsArgs),
@@ -77,6 +78,30 @@ pub struct GetDiffForAssetArgs {
     pub target_path: PathBuf,
 }
 
+#[derive(clap::Args, Debug)]
+#[command(about = "Run the func test cases in {change_set_id} and exit with an error if any fail")]
+pub struct RunFuncTestsArgs {
+    #[arg(long, env = "SI_SDF_URL", help = "Base URL of the SDF API")]
+    pub sdf_url: String,
+
+    #[arg(
+        long,
+        env = "SI_SDF_TOKEN",
+        hide_env_values(true),
+        help = "Token to authenticate with the SDF API"
+    )]
+    pub sdf_token: String,
+
+    #[arg(long, short = 'w', required = true)]
+    pub workspace_id: String,
+
+    #[arg(long, short = 'c', required = true)]
+    pub change_set_id: String,
+
+    #[arg(long, short = 'f', help = "Only run the test cases of this func")]
+    pub func_id: Option<String>,
+}
+
 #[derive(clap::Args, Debug)]
 #[command(about = "Upload all specs in {target_dir} to the module index")]
 pub struct UploadAllSpecsArgs {
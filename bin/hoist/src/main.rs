@@ -1,5 +1,5 @@
 use clap::CommandFactory;
-use commands::Commands;
+use commands::{Commands, RunFuncTestsArgs};
 use diff::{patch_list_to_changelog, rewrite_spec_for_diff};
 use futures::stream::StreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
@@ -14,9 +14,10 @@ use ulid::Ulid;
 
 use crate::diff::patch_list_to_summary;
 use clap::Parser;
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
 use json_patch::diff;
 use module_index_client::{ModuleDetailsResponse, ModuleIndexClient};
+use si_frontend_types::FuncTestReport;
 use si_pkg::{PkgSpec, SiPkg};
 use url::Url;
 
@@ -30,9 +31,9 @@ const CLOVER_DEFAULT_CREATOR: &str = "Clover";
 #[command(about = "Gets and puts cloud control assets from the module index")]
 struct Args {
     #[arg(long, short = 'e', env = "SI_MODULE_INDEX_URL")]
-    endpoint: String,
+    endpoint: Option<String>,
     #[arg(long, short = 't', env = "SI_BEARER_TOKEN", hide_env_values(true))]
-    token: String,
+    token: Option<String>,
 
     #[command(subcommand)]
     command: Option<Commands>,
@@ -43,11 +44,22 @@ async fn main() -> Result<()> {
     color_eyre::install()?;
     let args = Args::parse();
 
-    let endpoint = &args.endpoint;
-    let token = &args.token;
-    let client = ModuleIndexClient::new(Url::parse(endpoint)?, token);
+    // Func tests run against SDF rather than the module index, so they need neither its endpoint
+    // nor its token.
+    let command = match args.command {
+        Some(Commands::RunFuncTests(args)) => return run_func_tests(args).await,
+        command => command,
+    };
+
+    let endpoint = args
+        .endpoint
+        .ok_or_else(|| eyre!("the module index endpoint is required (SI_MODULE_INDEX_URL)"))?;
+    let token = args
+        .token
+        .ok_or_else(|| eyre!("the module index token is required (SI_BEARER_TOKEN)"))?;
+    let client = ModuleIndexClient::new(Url::parse(&endpoint)?, &token);
 
-    match args.command {
+    match command {
         Some(Commands::AnonymizeSpecs(args)) => anonymize_specs(args.target_dir, args.out).await?,
         Some(Commands::UploadAllSpecs(args)) => {
             upload_pkg_specs(
@@ -94,6 +106,54 @@ async fn main() -> Result<()> {
         Some(Commands::GetDiffForAsset(args)) => {
             detailed_diff_with_module_index(&client, args.target_path).await?
         }
+        Some(Commands::RunFuncTests(_)) => unreachable!("func tests are run before this"),
+    }
+
+    Ok(())
+}
+
+async fn run_func_tests(args: RunFuncTestsArgs) -> Result<()> {
+    let base = format!(
+        "{}/api/v2/workspaces/{}/change-sets/{}/funcs",
+        args.sdf_url.trim_end_matches('/'),
+        args.workspace_id,
+        args.change_set_id,
+    );
+    let url = match &args.func_id {
+        Some(func_id) => format!("{base}/{func_id}/test_cases/run"),
+        None => format!("{base}/test_cases/run"),
+    };
+
+    let report: FuncTestReport = reqwest::Client::new()
+        .post(url)
+        .bearer_auth(&args.sdf_token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    for result in &report.results {
+        println!(
+            "{} {} / {}",
+            if result.passed { "PASS" } else { "FAIL" },
+            result.func_name,
+            result.func_test_case_name,
+        );
+        if let Some(failure) = &result.failure {
+            println!("    {failure}");
+        } else if !result.passed {
+            println!(
+                "    got: {}",
+                result.output.as_ref().unwrap_or(&serde_json::Value::Null)
+            );
+        }
+    }
+
+    println!("{} passed, {} failed", report.passed, report.failed);
+
+    if !report.all_passed() {
+        std::process::exit(1);
     }
 
     Ok(())
@@ -2,7 +2,7 @@ import { SANDBOX_BUNDLE } from "./bundle.ts";
 import { FunctionKind } from "./function.ts";
 import { rawStorage, toJSON } from "./sandbox/requestStorage.ts";
 import { Debug } from "./debug.ts";
-import { join } from "https://deno.land/std/path/mod.ts";
+import { join, toFileUrl } from "https://deno.land/std/path/mod.ts";
 import { makeConsole } from "./sandbox/console.ts";
 import * as _ from "https://deno.land/x/lodash_es@v0.0.2/mod.ts";
 
@@ -12,6 +12,119 @@ const tempDirCache = new Map<string, string>();
 const sandboxBundleCache = new Map<string, string>();
 const textDecoder = new TextDecoder();
 
+// When warm, this process serves many requests, so functions run in a worker (a fresh V8 isolate)
+// instead of a fresh `deno` process, and per-request state is reset between requests.
+let warm = false;
+
+export function enableWarmMode() {
+  warm = true;
+}
+
+// The proxy cyclone started for the current request, if its workspace restricts where functions
+// may connect to. Every function run for the request, before functions included, goes through it.
+let egressProxy: string | undefined;
+
+const PROXY_ENV_VARS = [
+  "HTTP_PROXY",
+  "HTTPS_PROXY",
+  "ALL_PROXY",
+  "http_proxy",
+  "https_proxy",
+  "all_proxy",
+];
+
+export function setEgressProxy(url: string | undefined) {
+  egressProxy = url;
+}
+
+/**
+ * The address of the egress proxy, which is the only address functions may connect to while one is
+ * set.
+ */
+function egressProxyAddress(): string | undefined {
+  if (!egressProxy) return undefined;
+  const url = new URL(egressProxy);
+  const port = url.port || (url.protocol === "https:" ? "443" : "80");
+  return `${url.hostname}:${port}`;
+}
+
+/**
+ * The permissions a function is run with by `deno run`. Functions may do anything unless their
+ * workspace restricts egress, in which case the proxy is the only thing they may connect to, so
+ * neither unsetting the proxy env vars nor `Deno.connect` or `Deno.createHttpClient` get around
+ * it. FFI and running commands are refused too, since neither native code nor subprocesses are
+ * bound by Deno's permissions, and a subprocess can simply ignore the proxy env vars. Functions of
+ * such workspaces therefore can't use `siExec`.
+ */
+export function denoRunPermissions(): string[] {
+  const proxyAddress = egressProxyAddress();
+  if (!proxyAddress) return ["--allow-all"];
+  return [
+    "--allow-env",
+    "--allow-import",
+    "--allow-read",
+    "--allow-sys",
+    "--allow-write",
+    `--allow-net=${proxyAddress}`,
+  ];
+}
+
+/**
+ * The same permissions as {@link denoRunPermissions}, for functions run in a worker. Workers
+ * otherwise inherit the permissions of this process, which may do anything.
+ */
+export function workerPermissions(): Deno.PermissionOptions {
+  const proxyAddress = egressProxyAddress();
+  if (!proxyAddress) return "inherit";
+  return {
+    env: true,
+    ffi: false,
+    net: [proxyAddress],
+    read: true,
+    run: false,
+    sys: true,
+    write: true,
+  };
+}
+
+/**
+ * Points fetch at the egress proxy. This is applied after the env vars set by before functions, so
+ * they can't route around it.
+ */
+function applyEgressProxy() {
+  if (!egressProxy) return;
+  for (const key of PROXY_ENV_VARS) {
+    Deno.env.set(key, egressProxy);
+  }
+  Deno.env.delete("NO_PROXY");
+  Deno.env.delete("no_proxy");
+}
+
+/**
+ * Forgets everything a request left behind, so the next request served by a warm process starts
+ * from the same state as a fresh one: request storage, environment variables set for the
+ * function, and the temporary directories its code was written to.
+ */
+export async function resetExecutionState(baseEnv: Record<string, string>) {
+  const storage = rawStorage();
+  for (const key of Object.keys(storage.data)) delete storage.data[key];
+  for (const key of Object.keys(storage.env)) delete storage.env[key];
+
+  for (const key of Object.keys(Deno.env.toObject())) {
+    if (!(key in baseEnv)) Deno.env.delete(key);
+  }
+  for (const [key, value] of Object.entries(baseEnv)) {
+    Deno.env.set(key, value);
+  }
+
+  for (const tempDir of tempDirCache.values()) {
+    await Deno.remove(tempDir, { recursive: true }).catch(() => {});
+  }
+  tempDirCache.clear();
+  sandboxBundleCache.clear();
+  egressProxy = undefined;
+}
+
 class TimeoutError extends Error {
   constructor(seconds: number) {
     super(`function timed out after ${seconds} seconds`);
@@ -102,12 +215,18 @@ export async function runCode(
   for (const [key, value] of Object.entries(rawStorage().env || {})) {
     Deno.env.set(key, value);
   }
+  applyEgressProxy();
+
+  if (warm) {
+    const stdout = await runInWorker(tempDir, console, timeout);
+    return processExecutionOutput(stdout);
+  }
 
   const command = new Deno.Command("deno", {
     args: [
       "run",
       "--quiet",
-      "--allow-all",
+      ...denoRunPermissions(),
       "--unstable-node-globals",
       mainFile,
     ],
@@ -143,6 +262,84 @@ export async function runCode(
   return processExecutionOutput(stdout);
 }
 
+// Runs the execution code in a worker with its console redirected to the parent, returning what
+// it wrote to stdout. Mirrors running it with `deno run`, where anything on stderr is an error.
+function runInWorker(
+  tempDir: string,
+  console: ReturnType<typeof makeConsole>,
+  timeout: number,
+): Promise<string> {
+  const workerFile = join(tempDir, "worker.ts");
+  Deno.writeTextFileSync(
+    workerFile,
+    `${workerPrelude}\nawait import("./main.ts");\nself.postMessage({ done: true });\n`,
+  );
+
+  return new Promise((resolve, reject) => {
+    let stdout = "";
+    let stderr = "";
+    const worker = new Worker(toFileUrl(workerFile).href, {
+      type: "module",
+      deno: { permissions: workerPermissions() },
+    });
+
+    const timeoutId = setTimeout(() => {
+      worker.terminate();
+      reject(new TimeoutError(timeout));
+    }, timeout * 1000);
+
+    worker.onmessage = (event: MessageEvent) => {
+      const { stream, text, done } = event.data;
+      if (done) {
+        clearTimeout(timeoutId);
+        worker.terminate();
+        if (stderr.trim()) {
+          reject(new Error(stderr.trim()));
+        } else {
+          resolve(stdout);
+        }
+        return;
+      }
+
+      if (stream === "stdout") {
+        stdout += `${text}\n`;
+        // don't log the marker lines
+        if (
+          !text.includes("__STATE_MARKER__") &&
+          !text.includes("__RESULT_MARKER__")
+        ) {
+          console.forward("stdout", text);
+        }
+      } else {
+        stderr += `${text}\n`;
+        console.forward("stderr", text);
+      }
+    };
+
+    worker.onerror = (event: ErrorEvent) => {
+      event.preventDefault();
+      clearTimeout(timeoutId);
+      worker.terminate();
+      reject(new Error(event.message));
+    };
+  });
+}
+
+const workerPrelude = `
+const __post = (stream, args) => self.postMessage({
+  stream,
+  text: args.map((a) => typeof a === "string" ? a : JSON.stringify(a)).join(" "),
+});
+globalThis.console = {
+  ...globalThis.console,
+  debug: (...args) => __post("stdout", args),
+  info: (...args) => __post("stdout", args),
+  log: (...args) => __post("stdout", args),
+  warn: (...args) => __post("stderr", args),
+  error: (...args) => __post("stderr", args),
+};
+`;
+
 async function handleStream(
   reader: ReadableStreamDefaultReader<Uint8Array>,
   console: ReturnType<typeof makeConsole>,
@@ -170,7 +367,7 @@ async function handleStream(
             continue;
           }
 
-          type === "stdout" ? console.log(line) : console.error(line);
+          console.forward(type, line);
         }
       }
     }
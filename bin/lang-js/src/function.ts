@@ -13,6 +13,8 @@ import schema_variant_definition, {
 import management_run, { ManagementFunc } from "./function_kinds/management.ts";
 import action_run, { ActionRunFunc } from "./function_kinds/action_run.ts";
 import before from "./function_kinds/before.ts";
+import { setEgressProxy } from "./execution.ts";
+import { emitProtocolMessage } from "./protocol.ts";
 import { Debugger } from "./debug.ts";
 import { Debug } from "./debug.ts";
 import * as _ from "https://deno.land/x/lodash_es@v0.0.2/mod.ts";
@@ -89,12 +91,15 @@ export interface OutputLine {
   level: "debug" | "info" | "warn" | "error";
   group?: string;
   message: string;
+  fields?: Record<string, unknown>;
 }
 
 export async function executeFunction(
   request: Request,
   timeout: number,
 ) {
+  setEgressProxy(request.egressProxy);
+
   // Run Before Functions
   const ctx = ctxFromRequest(request);
 
@@ -171,7 +176,7 @@ export async function executeFunction(
       throw Error(`Unknown Kind variant: ${request.kind}`);
   }
 
-  console.log(JSON.stringify(result));
+  emitProtocolMessage(result);
 }
 
 export async function executor<F extends Func, Result>(
@@ -27,6 +27,7 @@ export enum FuncBackendResponseType {
   Object = "Object",
   Qualification = "Qualification",
   CodeGeneration = "CodeGeneration",
+  Plan = "Plan",
   String = "String",
   Unset = "Unset",
   Json = "Json",
@@ -160,6 +161,50 @@ const isQualification = (value: unknown): TypeCheckResult => {
   return { valid: true };
 };
 
+const planActions = ["create", "replace", "updateInPlace", "noOp"];
+const isPlan = (value: unknown): TypeCheckResult => {
+  if (typeof value !== "object" || !value) {
+    return { valid: false, message: "A plan must return an object." };
+  }
+
+  if (!("action" in value) || !planActions.includes(value.action as string)) {
+    return {
+      valid: false,
+      message:
+        "Plan action must be one of 'create' | 'replace' | 'updateInPlace' | 'noOp'",
+    };
+  }
+
+  if ("changes" in value && !_.isUndefined(value.changes)) {
+    if (!_.isArray(value.changes)) {
+      return { valid: false, message: "Plan changes must be an array" };
+    }
+    for (const change of value.changes) {
+      if (
+        typeof change !== "object" ||
+        !change ||
+        !("path" in change) ||
+        !_.isString(change.path)
+      ) {
+        return {
+          valid: false,
+          message: "Each plan change must be an object with a string path",
+        };
+      }
+    }
+  }
+
+  if (
+    "message" in value &&
+    !_.isUndefined(value.message) &&
+    !_.isString(value.message)
+  ) {
+    return { valid: false, message: "The plan message must be a string" };
+  }
+
+  return { valid: true };
+};
+
 const typeChecks: {
   [key in FuncBackendResponseType]?: (
     value: unknown,
@@ -174,6 +219,7 @@ const typeChecks: {
 
   [FuncBackendResponseType.CodeGeneration]: isCodeGeneration,
   [FuncBackendResponseType.Qualification]: isQualification,
+  [FuncBackendResponseType.Plan]: isPlan,
 };
 
 const nullables: { [key in FuncBackendResponseType]?: boolean } = {
@@ -187,6 +233,7 @@ const nullables: { [key in FuncBackendResponseType]?: boolean } = {
 
   [FuncBackendResponseType.CodeGeneration]: false,
   [FuncBackendResponseType.Qualification]: false,
+  [FuncBackendResponseType.Plan]: false,
 };
 
 async function execute(
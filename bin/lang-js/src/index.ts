@@ -11,6 +11,9 @@ import {
   functionKinds,
 } from "./function.ts";
 import { Debug } from "./debug.ts";
+import { enableWarmMode, resetExecutionState } from "./execution.ts";
+import { emitProtocolMessage, setProtocolToken } from "./protocol.ts";
+import { TextLineStream } from "https://deno.land/std/streams/mod.ts";
 
 // This is the default timeout for a function, in seconds.
 const defaultTimeout = 1800;
@@ -24,7 +27,7 @@ function onError(
 ) {
   debug(err);
   errorFn("StackTrace", err.stack);
-  console.log(JSON.stringify(failureExecution(err, executionId)));
+  emitProtocolMessage(failureExecution(err, executionId));
   process.exit(1);
 }
 
@@ -37,6 +40,10 @@ async function main() {
       `timeout for a function execution in seconds (default: ${defaultTimeout})`,
       { default: defaultTimeout },
     )
+    .option(
+      "--warm",
+      "serve requests one JSON line at a time until stdin closes, instead of a single request",
+    )
     .parse(Deno.args);
 
   let timeout: number = defaultTimeout;
@@ -50,6 +57,11 @@ async function main() {
     }
   }
 
+  if (options.warm) {
+    await serveWarm(timeout);
+    Deno.exit(0);
+  }
+
   let executionId = "<unset>";
   // We don't have the executionId yet, so this field will be empty
   let errorFn = makeConsole(executionId).error;
@@ -60,20 +72,8 @@ async function main() {
     for await (const chunk of Deno.stdin.readable) {
       requestJson += decoder.decode(chunk);
     }
-    debug({ request: requestJson });
-    const request: Request = JSON.parse(requestJson);
-
-    if (request.executionId) {
-      executionId = request.executionId;
-    } else {
-      throw Error("Request must have executionId field");
-    }
-
-    if (!request.kind) {
-      throw Error("Request must have a kind field");
-    }
-
-    debug({ request });
+    const request = parseRequest(requestJson);
+    executionId = request.executionId;
 
     // Now we have the executionId, so update our console.error() impl
     errorFn = makeConsole(executionId).error;
@@ -107,6 +107,62 @@ async function main() {
   Deno.exit(0);
 }
 
+function parseRequest(requestJson: string): Request {
+  debug({ request: requestJson });
+  return validateRequest(JSON.parse(requestJson));
+}
+
+function validateRequest(request: Request): Request {
+  if (!request.executionId) {
+    throw Error("Request must have executionId field");
+  }
+
+  if (!request.kind) {
+    throw Error("Request must have a kind field");
+  }
+
+  debug({ request });
+  return request;
+}
+
+// Serves one request per line of stdin. Every request ends with exactly one result line, which is
+// how cyclone knows the process is ready for the next one. Messages for a request carry the
+// protocol token cyclone sent with it, so that cyclone can tell them apart from anything a
+// function writes to stdout itself. Anything that escapes a request (like a timeout) still exits
+// the process, which cyclone treats as the worker being used up.
+async function serveWarm(timeout: number) {
+  enableWarmMode();
+  const baseEnv = Deno.env.toObject();
+  let executionId = "";
+
+  process.on("uncaughtException", (err) => {
+    onError(makeConsole(executionId).error, err, executionId);
+  });
+
+  const lines = Deno.stdin.readable
+    .pipeThrough(new TextDecoderStream())
+    .pipeThrough(new TextLineStream());
+
+  for await (const line of lines) {
+    if (!line.trim()) continue;
+    executionId = "";
+    try {
+      debug({ request: line });
+      const { protocolToken, ...request } = JSON.parse(line);
+      setProtocolToken(typeof protocolToken === "string" ? protocolToken : "");
+      executionId = validateRequest(request).executionId;
+      await executeFunction(request, timeout);
+    } catch (err) {
+      debug(err);
+      makeConsole(executionId).error("StackTrace", (err as Error).stack);
+      emitProtocolMessage(failureExecution(err as Error, executionId));
+    } finally {
+      await resetExecutionState(baseEnv);
+      setProtocolToken("");
+    }
+  }
+}
+
 // interface Errorable {
 //   name: string;
 //   message: string;
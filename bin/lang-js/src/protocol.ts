@@ -0,0 +1,13 @@
+// A warm process runs functions in workers that share its stdout, so a function could write
+// something that looks like a protocol message. Cyclone sends a fresh token with every warm
+// request and only reads lines carrying it; functions never see the token.
+let protocolToken = "";
+
+export function setProtocolToken(token: string) {
+  protocolToken = token;
+}
+
+// Writes a message for cyclone to stdout.
+export function emitProtocolMessage(message: unknown) {
+  console.log(`${protocolToken}${JSON.stringify(message)}`);
+}
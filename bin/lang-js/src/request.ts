@@ -23,6 +23,8 @@ export type Request =
     kind: FunctionKind;
     before?: BeforeFunc[];
     timeout?: number;
+    // Set by cyclone when the workspace restricts where functions may connect to.
+    egressProxy?: string;
   };
 
 export interface RequestCtx {
@@ -1,4 +1,5 @@
 import { OutputLine } from "../function.ts";
+import { emitProtocolMessage } from "../protocol.ts";
 
 const normalizeMessage = (msg: unknown[]): string => {
   return msg
@@ -9,6 +10,62 @@ const normalizeMessage = (msg: unknown[]): string => {
     .join(" ");
 };
 
+const LEVELS: Record<string, OutputLine["level"]> = {
+  trace: "debug",
+  debug: "debug",
+  info: "info",
+  warn: "warn",
+  warning: "warn",
+  error: "error",
+  fatal: "error",
+};
+
+export interface StructuredLine {
+  level?: OutputLine["level"];
+  message: string;
+  fields?: Record<string, unknown>;
+}
+
+// A line is structured if it is a JSON object with a string `message` (or `msg`). Its `level`
+// is used when it is one we know, and the rest of its keys become the line's fields.
+export const parseStructuredLine = (
+  line: string,
+): StructuredLine | undefined => {
+  const trimmed = line.trim();
+  if (!trimmed.startsWith("{")) return undefined;
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(trimmed);
+  } catch {
+    return undefined;
+  }
+  if (!parsed || typeof parsed !== "object" || Array.isArray(parsed)) {
+    return undefined;
+  }
+
+  const { ...fields } = parsed as Record<string, unknown>;
+  const messageKey = typeof fields.message === "string"
+    ? "message"
+    : typeof fields.msg === "string"
+    ? "msg"
+    : undefined;
+  if (!messageKey) return undefined;
+  const message = fields[messageKey] as string;
+  delete fields[messageKey];
+
+  const level = typeof fields.level === "string"
+    ? LEVELS[fields.level.toLowerCase()]
+    : undefined;
+  if (level) delete fields.level;
+
+  return {
+    level,
+    message,
+    fields: Object.keys(fields).length > 0 ? fields : undefined,
+  };
+};
+
 export const makeConsole = (executionId: string) => {
   function debug(...args: unknown[]): void {
     emitOutputLine({
@@ -43,9 +100,29 @@ export const makeConsole = (executionId: string) => {
     });
   }
 
+  // Forwards a line the function wrote to its stdout or stderr, keeping the level and fields of
+  // structured lines and logging anything else as is.
+  function forward(stream: OutputLine["stream"], line: string): void {
+    const structured = parseStructuredLine(line);
+    if (!structured) {
+      stream === "stdout" ? log(line) : error(line);
+      return;
+    }
+
+    emitOutputLine({
+      protocol: "output",
+      executionId,
+      stream,
+      level: structured.level ?? (stream === "stdout" ? "info" : "error"),
+      group: "log",
+      message: structured.message,
+      fields: structured.fields,
+    });
+  }
+
   function emitOutputLine(line: OutputLine): void {
-    console.log(JSON.stringify(line));
+    emitProtocolMessage(line);
   }
 
-  return { debug, error, log };
+  return { debug, error, forward, log };
 };
@@ -0,0 +1,39 @@
+import { assertEquals } from "https://deno.land/std@0.224.0/assert/mod.ts";
+import { parseStructuredLine } from "../src/sandbox/console.ts";
+
+Deno.test("parseStructuredLine", async (t) => {
+  await t.step("Plain lines are not structured", () => {
+    assertEquals(parseStructuredLine("refreshing instance i-1234"), undefined);
+    assertEquals(parseStructuredLine("{not json"), undefined);
+    assertEquals(parseStructuredLine('["message"]'), undefined);
+  });
+
+  await t.step("Objects without a message are not structured", () => {
+    assertEquals(parseStructuredLine('{"status": 403}'), undefined);
+  });
+
+  await t.step("Takes the level and message, leaving the rest as fields", () => {
+    assertEquals(
+      parseStructuredLine(
+        '{"level": "WARN", "message": "request failed", "status": 403}',
+      ),
+      { level: "warn", message: "request failed", fields: { status: 403 } },
+    );
+  });
+
+  await t.step("Accepts msg for the message", () => {
+    assertEquals(parseStructuredLine('{"msg": "done"}'), {
+      level: undefined,
+      message: "done",
+      fields: undefined,
+    });
+  });
+
+  await t.step("Keeps unknown levels as a field", () => {
+    assertEquals(parseStructuredLine('{"level": 30, "message": "done"}'), {
+      level: undefined,
+      message: "done",
+      fields: { level: 30 },
+    });
+  });
+});
@@ -0,0 +1,103 @@
+import {
+  assertEquals,
+  assertStringIncludes,
+} from "https://deno.land/std@0.224.0/assert/mod.ts";
+import {
+  denoRunPermissions,
+  resetExecutionState,
+  setEgressProxy,
+  workerPermissions,
+} from "../src/execution.ts";
+
+// Runs code in a worker with the permissions functions get, returning what it posted back.
+function runInWorker(code: string): Promise<string> {
+  const url = `data:application/javascript,${encodeURIComponent(code)}`;
+  const worker = new Worker(url, {
+    type: "module",
+    deno: { permissions: workerPermissions() },
+  });
+  return new Promise((resolve) => {
+    worker.onmessage = (event: MessageEvent) => {
+      worker.terminate();
+      resolve(event.data);
+    };
+  });
+}
+
+const runCommand = `
+try {
+  await new Deno.Command("true").output();
+  self.postMessage("ran");
+} catch (error) {
+  const denied = ["NotCapable", "PermissionDenied"].includes(error.name);
+  self.postMessage(denied ? "denied" : error.name);
+}
+`;
+
+const connectTo = (port: number) => `
+try {
+  const conn = await Deno.connect({ hostname: "127.0.0.1", port: ${port} });
+  conn.close();
+  self.postMessage("connected");
+} catch (error) {
+  // Deno 2 raises NotCapable where older versions raised PermissionDenied
+  const denied = ["NotCapable", "PermissionDenied"].includes(error.name);
+  self.postMessage(denied ? "denied" : error.name);
+}
+`;
+
+Deno.test("egress", async (t) => {
+  const baseEnv = Deno.env.toObject();
+
+  await t.step("Allows everything without a proxy", async () => {
+    await resetExecutionState(baseEnv);
+
+    assertEquals(denoRunPermissions(), ["--allow-all"]);
+    assertEquals(workerPermissions(), "inherit");
+  });
+
+  await t.step("Only allows connecting to the proxy", async () => {
+    await resetExecutionState(baseEnv);
+    setEgressProxy("http://127.0.0.1:3128");
+
+    const flags = denoRunPermissions();
+    assertEquals(flags.includes("--allow-all"), false);
+    assertEquals(flags.includes("--allow-ffi"), false);
+    assertEquals(flags.includes("--allow-run"), false);
+    assertStringIncludes(flags.join(" "), "--allow-net=127.0.0.1:3128");
+
+    await resetExecutionState(baseEnv);
+  });
+
+  await t.step("Stops workers from connecting around the proxy", async () => {
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 0 });
+    const { port } = listener.addr as Deno.NetAddr;
+    const accepted = (async () => {
+      for await (const conn of listener) conn.close();
+    })();
+
+    try {
+      await resetExecutionState(baseEnv);
+      setEgressProxy(`http://127.0.0.1:${port}`);
+      assertEquals(await runInWorker(connectTo(port)), "connected");
+
+      setEgressProxy(`http://127.0.0.1:${port + 1}`);
+      assertEquals(await runInWorker(connectTo(port)), "denied");
+    } finally {
+      await resetExecutionState(baseEnv);
+      listener.close();
+      await accepted.catch(() => {});
+    }
+  });
+
+  await t.step("Stops workers from running commands", async () => {
+    await resetExecutionState(baseEnv);
+    setEgressProxy("http://127.0.0.1:3128");
+
+    try {
+      assertEquals(await runInWorker(runCommand), "denied");
+    } finally {
+      await resetExecutionState(baseEnv);
+    }
+  });
+});
@@ -0,0 +1,33 @@
+import { assertEquals } from "https://deno.land/std@0.224.0/assert/mod.ts";
+import { emitProtocolMessage, setProtocolToken } from "../src/protocol.ts";
+
+function captureLog(fn: () => void): string[] {
+  const lines: string[] = [];
+  const log = console.log;
+  console.log = (...args: unknown[]) => lines.push(args.join(" "));
+  try {
+    fn();
+  } finally {
+    console.log = log;
+  }
+  return lines;
+}
+
+Deno.test("emitProtocolMessage", async (t) => {
+  await t.step("Writes plain JSON lines without a token", () => {
+    const lines = captureLog(() => emitProtocolMessage({ protocol: "result" }));
+
+    assertEquals(lines, ['{"protocol":"result"}']);
+  });
+
+  await t.step("Prefixes every line with the protocol token", () => {
+    setProtocolToken("sekrit");
+    try {
+      const lines = captureLog(() => emitProtocolMessage({ protocol: "result" }));
+
+      assertEquals(lines, ['sekrit{"protocol":"result"}']);
+    } finally {
+      setProtocolToken("");
+    }
+  });
+});
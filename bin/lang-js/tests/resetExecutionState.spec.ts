@@ -0,0 +1,32 @@
+import {
+  assertEquals,
+  assertFalse,
+} from "https://deno.land/std@0.224.0/assert/mod.ts";
+import { resetExecutionState } from "../src/execution.ts";
+import {
+  makeBeforeRequestStorage,
+  rawStorage,
+} from "../src/sandbox/requestStorage.ts";
+
+const EXEC_ID = "execId";
+
+Deno.test("resetExecutionState", async (t) => {
+  await t.step("Clears request storage", async () => {
+    const storage = makeBeforeRequestStorage(EXEC_ID);
+    storage.setItem("a", "value");
+    storage.setEnv("SECRET", "shh");
+
+    await resetExecutionState(Deno.env.toObject());
+
+    assertEquals(rawStorage(), { data: {}, env: {} });
+  });
+
+  await t.step("Restores environment variables", async () => {
+    const baseEnv = Deno.env.toObject();
+    Deno.env.set("LANG_JS_RESET_TEST", "leaked");
+
+    await resetExecutionState(baseEnv);
+
+    assertFalse(Deno.env.has("LANG_JS_RESET_TEST"));
+  });
+});
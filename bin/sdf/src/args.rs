@@ -122,6 +122,10 @@ pub(crate) struct Args {
     #[arg(long, value_parser = PossibleValuesParser::new(MigrationMode::variants()))]
     pub(crate) migration_mode: Option<String>,
 
+    /// Only report what func run garbage collection would delete
+    #[arg(long, default_value = "false")]
+    pub(crate) func_run_gc_dry_run: bool,
+
     /// Veritech encryption key file location [default: /run/sdf/veritech_encryption.key]
     #[arg(long)]
     pub(crate) veritech_encryption_key_path: Option<PathBuf>,
@@ -328,6 +332,9 @@ impl TryFrom<Args> for Config {
             if let Some(migration_mode) = args.migration_mode {
                 config_map.set("migration_mode", migration_mode);
             }
+            if args.func_run_gc_dry_run {
+                config_map.set("func_run_gc_dry_run", true);
+            }
             if let Some(url) = args.nats_url {
                 config_map.set("nats.url", url.clone());
                 config_map.set("layer_db_config.nats_config.url", url);
@@ -2,7 +2,9 @@
 
 use std::{path::PathBuf, time::Duration};
 
-use sdf_server::{key_generation, Config, Migrator, Server, SnapshotGarbageCollector};
+use sdf_server::{
+    key_generation, Config, FuncRunGarbageCollector, Migrator, Server, SnapshotGarbageCollector,
+};
 use si_service::{
     color_eyre,
     prelude::*,
@@ -104,6 +106,18 @@ async fn async_main() -> Result<()> {
                 telemetry_shutdown,
             )
             .await
+        } else if config.migration_mode().is_garbage_collect_func_runs() {
+            garbage_collect_func_runs(
+                config,
+                main_tracker,
+                main_token,
+                helping_tasks_tracker,
+                helping_tasks_token,
+                telemetry_tracker,
+                telemetry_token,
+                telemetry_shutdown,
+            )
+            .await
         } else if config.migration_mode().is_garbage_collect_snapshots() {
             garbage_collect_snapshots(
                 config,
@@ -236,6 +250,35 @@ async fn garbage_collect_snapshots(
         .map_err(Into::into)
 }
 
+#[inline]
+#[allow(clippy::too_many_arguments)]
+async fn garbage_collect_func_runs(
+    config: Config,
+    main_tracker: TaskTracker,
+    main_token: CancellationToken,
+    helping_tasks_tracker: TaskTracker,
+    helping_tasks_token: CancellationToken,
+    telemetry_tracker: TaskTracker,
+    telemetry_token: CancellationToken,
+    telemetry_shutdown: TelemetryShutdownGuard,
+) -> Result<()> {
+    let garbage_collector =
+        FuncRunGarbageCollector::new(config, &helping_tasks_tracker, helping_tasks_token.clone())
+            .await?;
+
+    let handle = main_tracker.spawn(garbage_collector.garbage_collect_func_runs());
+
+    shutdown::graceful_with_handle(handle)
+        .group(main_tracker, main_token)
+        .group(helping_tasks_tracker, helping_tasks_token)
+        .group(telemetry_tracker, telemetry_token)
+        .telemetry_guard(telemetry_shutdown.into_future())
+        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
+        .wait()
+        .await
+        .map_err(Into::into)
+}
+
 #[inline]
 async fn generate_veritech_key_pair(
     secret_key_path: PathBuf,
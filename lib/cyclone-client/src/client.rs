@@ -449,10 +449,11 @@ mod tests {
     use buck2_resources::Buck2Resources;
     use cyclone_core::{
         ActionRunRequest, ComponentKind, ComponentView, ComponentViewWithGeometry, FunctionResult,
-        ManagementRequest, ProgressMessage, ResolverFunctionComponent, ResolverFunctionRequest,
+        FunctionResultFailureErrorKind, FunctionRuntime, ManagementRequest, ProgressMessage,
+        ResolverFunctionComponent, ResolverFunctionRequest, ResolverFunctionResultSuccess,
         SchemaVariantDefinitionRequest, ThisComponent, ValidationRequest,
     };
-    use cyclone_server::{Config, ConfigBuilder, Runnable as _, Server};
+    use cyclone_server::{Config, ConfigBuilder, Runnable as _, Server, WasmConfig};
     use futures::StreamExt;
     use serde_json::json;
     use tempfile::{NamedTempFile, TempPath};
@@ -766,6 +767,10 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            runtime: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -876,6 +881,10 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            runtime: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -959,6 +968,9 @@ mod tests {
             validation_format: r#"{"type":"number","flags":{"presence":"required"},"rules":[{"name":"integer"},{"name":"min","args":{"limit":33}},{"name":"max","args":{"limit":33}}]}"#.to_string(),
             code_base64: "".to_string(),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            egress_policy: None,
         };
         let mut progress = client
             .prepare_execution(CycloneRequest::from_parts(req, Default::default()))
@@ -1027,6 +1039,10 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            runtime: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -1102,6 +1118,54 @@ mod tests {
         }
     }
 
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_action_run_timeout() {
+        let mut builder = Config::builder();
+        let mut client = http_client_for_running_server(builder.enable_action_run(true)).await;
+
+        let req = ActionRunRequest {
+            execution_id: "1234".to_string(),
+            handler: "workit".to_string(),
+            args: Default::default(),
+            code_base64: base64_encode(
+                r#"async function workit() {
+                    while (true) {
+                        await new Promise((resolve) => setTimeout(resolve, 100));
+                    }
+                }"#,
+            ),
+            before: vec![],
+            timeout_secs: Some(1),
+            priority: Default::default(),
+            runtime: Default::default(),
+            egress_policy: None,
+        };
+
+        let mut progress = client
+            .prepare_execution(CycloneRequest::from_parts(req, Default::default()))
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+        while let Some(msg) = progress.next().await {
+            msg.expect("failed to receive progress message");
+        }
+
+        match progress.finish().await.expect("failed to return result") {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => {
+                assert_eq!(
+                    failure.error().kind,
+                    FunctionResultFailureErrorKind::Timeout
+                );
+            }
+        }
+    }
+
     #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
     #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
     async fn uds_execute_action_run() {
@@ -1122,6 +1186,10 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            runtime: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -1219,6 +1287,9 @@ mod tests {
                     return new AssetBuilder().build();
                 }"#,
             ),
+            timeout_secs: None,
+            priority: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -1302,6 +1373,9 @@ mod tests {
                     return new AssetBuilder().build();
                 }"#,
             ),
+            timeout_secs: None,
+            priority: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -1397,6 +1471,9 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -1492,6 +1569,9 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            egress_policy: None,
         };
 
         // Start the protocol
@@ -1553,4 +1633,152 @@ mod tests {
             }
         }
     }
+
+    /// A component for the `function` world whose `run` export runs `body`, then returns
+    /// `{"answer":42}`.
+    fn wasm_component(body: &str) -> String {
+        let component = wat::parse_str(format!(
+            r#"
+(component
+  (core module $m
+    (memory (export "memory") 1)
+    (global $next (mut i32) (i32.const 1024))
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $ptr i32)
+      (local.set $ptr (global.get $next))
+      (global.set $next (i32.add (global.get $next) (local.get 3)))
+      (local.get $ptr))
+    ;; The result: ok, followed by the address and length of the JSON it holds
+    (data (i32.const 0) "\00\00\00\00\10\00\00\00\0d\00\00\00")
+    (data (i32.const 16) "{{\"answer\":42}}")
+    (func (export "run") (param i32 i32 i32 i32) (result i32)
+      {body}
+      (i32.const 0)))
+  (core instance $i (instantiate $m))
+  (func (export "run") (param "kind" string) (param "request" string)
+    (result (result string (error string)))
+    (canon lift (core func $i "run") (memory $i "memory") (realloc (func $i "realloc")))))
+"#
+        ))
+        .expect("failed to assemble component");
+
+        base64_encode(component)
+    }
+
+    async fn execute_wasm(
+        builder: &mut ConfigBuilder,
+        body: &str,
+        timeout_secs: Option<u64>,
+    ) -> FunctionResult<ResolverFunctionResultSuccess> {
+        let mut client = http_client_for_running_server(builder.enable_resolver(true)).await;
+
+        let req = ResolverFunctionRequest {
+            execution_id: "1234".to_string(),
+            handler: "".to_string(),
+            component: ResolverFunctionComponent {
+                data: ComponentView {
+                    properties: serde_json::json!({}),
+                    kind: ComponentKind::Standard,
+                },
+                parents: vec![],
+            },
+            response_type: cyclone_core::ResolverFunctionResponseType::Object,
+            code_base64: wasm_component(body),
+            before: vec![],
+            timeout_secs,
+            priority: Default::default(),
+            runtime: FunctionRuntime::Wasm,
+            egress_policy: None,
+        };
+
+        let mut progress = client
+            .prepare_execution(CycloneRequest::from_parts(req, Default::default()))
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+        while let Some(msg) = progress.next().await {
+            msg.expect("failed to receive progress message");
+        }
+
+        progress.finish().await.expect("failed to return result")
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_wasm_resolver() {
+        let mut builder = Config::builder();
+
+        match execute_wasm(&mut builder, "", None).await {
+            FunctionResult::Success(success) => {
+                assert!(!success.unset);
+                assert_eq!(success.data, json!({"answer": 42}));
+            }
+            FunctionResult::Failure(failure) => {
+                panic!("result should be success; failure={failure:?}")
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_wasm_trap() {
+        let mut builder = Config::builder();
+
+        match execute_wasm(&mut builder, "unreachable", None).await {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => {
+                assert_eq!(
+                    failure.error().kind,
+                    FunctionResultFailureErrorKind::UserCodeException("Trap".to_string())
+                );
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_wasm_timeout() {
+        let mut builder = Config::builder();
+
+        match execute_wasm(&mut builder, "(loop $forever (br $forever))", Some(1)).await {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => {
+                assert_eq!(
+                    failure.error().kind,
+                    FunctionResultFailureErrorKind::Timeout
+                );
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_wasm_memory_limit() {
+        let mut builder = Config::builder();
+        builder.wasm(WasmConfig {
+            memory_limit_bytes: 1024 * 1024,
+            ..Default::default()
+        });
+
+        // Grows by a page at a time until it can't
+        let body = "(loop $grow
+            (br_if $grow (i32.ne (memory.grow (i32.const 1)) (i32.const -1))))";
+        match execute_wasm(&mut builder, body, None).await {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => {
+                assert_eq!(
+                    failure.error().kind,
+                    FunctionResultFailureErrorKind::MemoryLimitExceeded
+                );
+            }
+        }
+    }
 }
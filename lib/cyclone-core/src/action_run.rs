@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 use telemetry_utils::metric;
 
-use crate::{BeforeFunction, CycloneRequestable};
+use crate::{BeforeFunction, CycloneRequestable, EgressPolicy, ExecutionPriority, FunctionRuntime};
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +12,18 @@ pub struct ActionRunRequest {
     pub code_base64: String,
     pub args: serde_json::Value,
     pub before: Vec<BeforeFunction>,
+    /// Kill the function if it runs longer than this, instead of after the server-wide limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Where the request waits in line while every execution slot is taken.
+    #[serde(default)]
+    pub priority: ExecutionPriority,
+    /// What the code is run with. Requests that predate WebAssembly functions are JavaScript.
+    #[serde(default)]
+    pub runtime: FunctionRuntime,
+    /// Where the function may open network connections to. Without one it may reach anything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress_policy: Option<EgressPolicy>,
 }
 
 #[remain::sorted]
@@ -42,6 +54,22 @@ impl CycloneRequestable for ActionRunRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
+    fn priority(&self) -> ExecutionPriority {
+        self.priority
+    }
+
+    fn runtime(&self) -> FunctionRuntime {
+        self.runtime
+    }
+
+    fn egress_policy(&self) -> Option<&EgressPolicy> {
+        self.egress_policy.as_ref()
+    }
+
     fn kind(&self) -> &str {
         "actionRun"
     }
@@ -0,0 +1,294 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("failed to parse '{0}' into EgressRule: expected a hostname, a *.domain, or a CIDR block")]
+pub struct EgressRuleParseError(String);
+
+/// Where a function may open network connections to.
+///
+/// Requests without a policy may reach anything.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EgressPolicy {
+    /// The [`EgressRule`]s a destination has to match one of. Empty allows nothing at all.
+    pub allow: Vec<String>,
+}
+
+impl EgressPolicy {
+    /// The policy's rules, skipping any that fail to parse.
+    pub fn rules(&self) -> impl Iterator<Item = EgressRule> + '_ {
+        self.allow.iter().filter_map(|rule| rule.parse().ok())
+    }
+
+    /// Whether `host` (a hostname or an IP address) is allowed without having to resolve it.
+    #[must_use]
+    pub fn allows_host(&self, host: &str) -> bool {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        match host.parse::<IpAddr>() {
+            Ok(ip) => self.allows_ip(ip),
+            Err(_) => self.rules().any(|rule| rule.matches_hostname(host)),
+        }
+    }
+
+    /// Whether connections to `ip` are allowed by one of the policy's CIDR blocks.
+    #[must_use]
+    pub fn allows_ip(&self, ip: IpAddr) -> bool {
+        self.rules().any(|rule| rule.matches_ip(ip))
+    }
+}
+
+/// One entry in an [`EgressPolicy`]'s allow list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EgressRule {
+    /// Any subdomain of the domain, written as `*.example.com`. The domain itself is not included.
+    Domain(String),
+    /// Exactly this hostname, such as `ec2.us-east-1.amazonaws.com`.
+    Hostname(String),
+    /// Any address in the block, such as `10.0.0.0/8`. A bare address is a block of one.
+    Network(IpAddr, u8),
+}
+
+impl EgressRule {
+    #[must_use]
+    pub fn matches_hostname(&self, hostname: &str) -> bool {
+        let hostname = hostname.trim_end_matches('.').to_lowercase();
+        match self {
+            Self::Domain(domain) => hostname
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')),
+            Self::Hostname(allowed) => *allowed == hostname,
+            Self::Network(..) => false,
+        }
+    }
+
+    #[must_use]
+    pub fn matches_ip(&self, ip: IpAddr) -> bool {
+        let Self::Network(network, prefix_len) = self else {
+            return false;
+        };
+        match (network, to_canonical(ip)) {
+            (IpAddr::V4(_), ip @ IpAddr::V4(_)) | (IpAddr::V6(_), ip @ IpAddr::V6(_)) => {
+                to_canonical_network(ip, *prefix_len) == *network
+            }
+            _ => false,
+        }
+    }
+}
+
+/// IPv4 addresses mapped into IPv6 are matched against IPv4 blocks.
+fn to_canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(IpAddr::V6(v6), IpAddr::V4),
+        ip => ip,
+    }
+}
+
+fn is_valid_hostname(hostname: &str) -> bool {
+    !hostname.is_empty()
+        && hostname.len() <= 253
+        && hostname.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+impl FromStr for EgressRule {
+    type Err = EgressRuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rule = s.trim().trim_end_matches('.').to_lowercase();
+        let invalid = || EgressRuleParseError(s.to_string());
+
+        if let Some((network, prefix_len)) = rule.split_once('/') {
+            let network: IpAddr = network.parse().map_err(|_| invalid())?;
+            let prefix_len: u8 = prefix_len.parse().map_err(|_| invalid())?;
+            let max_len = match network {
+                IpAddr::V4(_) => 32,
+                IpAddr::V6(_) => 128,
+            };
+            if prefix_len > max_len {
+                return Err(invalid());
+            }
+            Ok(Self::Network(
+                to_canonical_network(network, prefix_len),
+                prefix_len,
+            ))
+        } else if let Ok(ip) = rule.parse::<IpAddr>() {
+            let prefix_len = match ip {
+                IpAddr::V4(_) => 32,
+                IpAddr::V6(_) => 128,
+            };
+            Ok(Self::Network(ip, prefix_len))
+        } else if let Some(domain) = rule.strip_prefix("*.") {
+            if is_valid_hostname(domain) {
+                Ok(Self::Domain(domain.to_string()))
+            } else {
+                Err(invalid())
+            }
+        } else if is_valid_hostname(&rule) {
+            Ok(Self::Hostname(rule))
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+/// Clears the host bits of a block, so `10.1.2.3/8` means `10.0.0.0/8`.
+fn to_canonical_network(network: IpAddr, prefix_len: u8) -> IpAddr {
+    match network {
+        IpAddr::V4(v4) => {
+            let mask = u32::MAX
+                .checked_shl(32 - u32::from(prefix_len))
+                .unwrap_or(0);
+            IpAddr::V4(Ipv4Addr::from(u32::from(v4) & mask))
+        }
+        IpAddr::V6(v6) => {
+            let mask = u128::MAX
+                .checked_shl(128 - u32::from(prefix_len))
+                .unwrap_or(0);
+            IpAddr::V6(Ipv6Addr::from(u128::from(v6) & mask))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(s: &str) -> EgressRule {
+        s.parse().expect("failed to parse rule")
+    }
+
+    fn policy(allow: &[&str]) -> EgressPolicy {
+        EgressPolicy {
+            allow: allow.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(
+            EgressRule::Hostname("ec2.us-east-1.amazonaws.com".to_owned()),
+            rule(" EC2.us-east-1.amazonaws.com. ")
+        );
+        assert_eq!(
+            EgressRule::Domain("amazonaws.com".to_owned()),
+            rule("*.amazonaws.com")
+        );
+        assert_eq!(
+            EgressRule::Network(IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3)), 32),
+            rule("10.1.2.3")
+        );
+        assert_eq!(
+            EgressRule::Network(IpAddr::V6(Ipv6Addr::LOCALHOST), 128),
+            rule("::1")
+        );
+
+        for invalid in [
+            "",
+            "*.",
+            "*",
+            "-example.com",
+            "exa mple.com",
+            "example..com",
+            "10.0.0.0/33",
+            "::/129",
+            "10.0.0.0/",
+            "not-an-ip/8",
+            "*.*.example.com",
+        ] {
+            assert!(
+                invalid.parse::<EgressRule>().is_err(),
+                "'{invalid}' should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalizes_networks() {
+        assert_eq!(
+            EgressRule::Network(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8),
+            rule("10.1.2.3/8")
+        );
+        assert_eq!(
+            EgressRule::Network(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+            rule("192.168.1.1/0")
+        );
+        assert_eq!(
+            EgressRule::Network("fd00::".parse().expect("valid ip"), 8),
+            rule("fd12:3456::1/8")
+        );
+        assert_eq!(rule("10.0.0.0/8"), rule("10.255.255.255/8"));
+    }
+
+    #[test]
+    fn matches_networks() {
+        let network = rule("10.0.0.0/8");
+        assert!(network.matches_ip("10.0.0.1".parse().expect("valid ip")));
+        assert!(network.matches_ip("10.255.255.255".parse().expect("valid ip")));
+        assert!(!network.matches_ip("11.0.0.1".parse().expect("valid ip")));
+        assert!(!network.matches_hostname("10.0.0.1"));
+
+        let everything = rule("0.0.0.0/0");
+        assert!(everything.matches_ip("203.0.113.9".parse().expect("valid ip")));
+        assert!(!everything.matches_ip("2001:db8::1".parse().expect("valid ip")));
+
+        let v6 = rule("2001:db8::/32");
+        assert!(v6.matches_ip("2001:db8:1::1".parse().expect("valid ip")));
+        assert!(!v6.matches_ip("2001:db9::1".parse().expect("valid ip")));
+    }
+
+    #[test]
+    fn matches_ipv4_mapped_ipv6_as_ipv4() {
+        let network = rule("10.0.0.0/8");
+        assert!(network.matches_ip("::ffff:10.1.2.3".parse().expect("valid ip")));
+        assert!(!network.matches_ip("::ffff:11.1.2.3".parse().expect("valid ip")));
+
+        // A mapped address can't sneak past an IPv6 block either
+        let v6 = rule("::ffff:0:0/96");
+        assert!(!v6.matches_ip("::ffff:10.1.2.3".parse().expect("valid ip")));
+
+        let policy = policy(&["127.0.0.1"]);
+        assert!(policy.allows_host("[::ffff:127.0.0.1]"));
+        assert!(!policy.allows_host("[::1]"));
+    }
+
+    #[test]
+    fn matches_domains() {
+        let domain = rule("*.example.com");
+        assert!(domain.matches_hostname("api.example.com"));
+        assert!(domain.matches_hostname("a.b.example.com"));
+        assert!(domain.matches_hostname("API.Example.com."));
+        assert!(!domain.matches_hostname("example.com"));
+        assert!(!domain.matches_hostname("badexample.com"));
+        assert!(!domain.matches_hostname("example.com.evil.net"));
+        assert!(!domain.matches_hostname(".example.com"));
+
+        let hostname = rule("example.com");
+        assert!(hostname.matches_hostname("example.com"));
+        assert!(hostname.matches_hostname("EXAMPLE.com."));
+        assert!(!hostname.matches_hostname("api.example.com"));
+    }
+
+    #[test]
+    fn policies_allow_any_of_their_rules() {
+        let policy = policy(&["*.amazonaws.com", "10.0.0.0/8", "not a rule"]);
+        assert!(policy.allows_host("s3.amazonaws.com"));
+        assert!(policy.allows_host("10.2.3.4"));
+        assert!(!policy.allows_host("example.com"));
+        assert!(!policy.allows_host("192.168.0.1"));
+
+        assert!(!EgressPolicy::default().allows_host("example.com"));
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::CycloneRequestable;
+use crate::{CycloneRequestable, EgressPolicy, ExecutionPriority, FunctionRuntime};
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +15,22 @@ impl CycloneRequestable for KillExecutionRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        None
+    }
+
+    fn priority(&self) -> ExecutionPriority {
+        ExecutionPriority::Interactive
+    }
+
+    fn runtime(&self) -> FunctionRuntime {
+        FunctionRuntime::Js
+    }
+
+    fn egress_policy(&self) -> Option<&EgressPolicy> {
+        None
+    }
+
     fn kind(&self) -> &str {
         ""
     }
@@ -15,14 +15,17 @@ mod action_run;
 mod before;
 mod canonical_command;
 mod component_view;
+mod egress;
 mod kill_execution;
 mod liveness;
 mod management;
+mod priority;
 pub mod process;
 mod progress;
 mod readiness;
 mod request;
 mod resolver_function;
+mod runtime;
 mod schema_variant_definition;
 mod sensitive_container;
 mod validation;
@@ -33,9 +36,11 @@ pub use action_run::{ActionRunRequest, ActionRunResultSuccess, ResourceStatus};
 pub use before::BeforeFunction;
 pub use canonical_command::{CanonicalCommand, CanonicalCommandError};
 pub use component_view::{ComponentKind, ComponentView, ComponentViewWithGeometry, ThisComponent};
+pub use egress::{EgressPolicy, EgressRule, EgressRuleParseError};
 pub use kill_execution::KillExecutionRequest;
 pub use liveness::{LivenessStatus, LivenessStatusParseError};
 pub use management::{ManagementFuncStatus, ManagementRequest, ManagementResultSuccess};
+pub use priority::ExecutionPriority;
 pub use progress::{
     FunctionResult, FunctionResultFailure, FunctionResultFailureError,
     FunctionResultFailureErrorKind, Message, OutputStream, ProgressMessage,
@@ -46,6 +51,7 @@ pub use resolver_function::{
     ResolverFunctionComponent, ResolverFunctionRequest, ResolverFunctionResponseType,
     ResolverFunctionResultSuccess,
 };
+pub use runtime::FunctionRuntime;
 pub use schema_variant_definition::{
     SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess,
 };
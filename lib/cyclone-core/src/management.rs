@@ -6,7 +6,7 @@ use telemetry_utils::metric;
 
 use crate::{
     component_view::{ComponentViewWithGeometry, ThisComponent},
-    BeforeFunction, CycloneRequestable,
+    BeforeFunction, CycloneRequestable, EgressPolicy, ExecutionPriority, FunctionRuntime,
 };
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -20,6 +20,15 @@ pub struct ManagementRequest {
     pub components: HashMap<String, ComponentViewWithGeometry>,
     pub variant_socket_map: HashMap<String, usize>,
     pub before: Vec<BeforeFunction>,
+    /// Kill the function if it runs longer than this, instead of after the server-wide limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Where the request waits in line while every execution slot is taken.
+    #[serde(default)]
+    pub priority: ExecutionPriority,
+    /// Where the function may open network connections to. Without one it may reach anything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress_policy: Option<EgressPolicy>,
 }
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -46,6 +55,22 @@ impl CycloneRequestable for ManagementRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
+    fn priority(&self) -> ExecutionPriority {
+        self.priority
+    }
+
+    fn runtime(&self) -> FunctionRuntime {
+        FunctionRuntime::Js
+    }
+
+    fn egress_policy(&self) -> Option<&EgressPolicy> {
+        self.egress_policy.as_ref()
+    }
+
     fn kind(&self) -> &str {
         "management"
     }
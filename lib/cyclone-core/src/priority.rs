@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumIter, EnumString};
+
+/// How urgently a request should be executed. When every execution slot is taken, waiting requests
+/// with a higher priority are granted a slot first.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ExecutionPriority {
+    /// Action functions being dispatched from the action queue.
+    Action,
+    /// Work that nobody is waiting on, like refreshing resources.
+    Background,
+    /// Attribute and validation functions run as part of a dependent values update. This is the
+    /// bulk of all traffic, so requests that don't say otherwise are treated the same way.
+    #[default]
+    Dvu,
+    /// Work a user is actively waiting on, like a test run in the func editor.
+    Interactive,
+}
+
+impl ExecutionPriority {
+    /// All priorities, from the one that is granted slots first to the one granted slots last.
+    pub const HIGHEST_FIRST: [Self; 4] =
+        [Self::Interactive, Self::Action, Self::Dvu, Self::Background];
+}
@@ -33,6 +33,13 @@ pub struct OutputStream {
     ///
     /// The timestamp generated locally when the message was created.
     pub timestamp: u64,
+    /// Key/value fields of a structured log line.
+    ///
+    /// Set when the function wrote a JSON object with a `message` (or `msg`) to its output, in
+    /// which case `message` and `level` are taken from the object and the rest of its keys end up
+    /// here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 /// A message produced as a function is executing.
@@ -148,8 +155,16 @@ impl FunctionResultFailure {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Display)]
 pub enum FunctionResultFailureErrorKind {
     ActionFieldWrongType,
+    /// The function was throttled by its CPU limit and ran out of time.
+    CpuLimitExceeded,
+    /// The function tried to reach a network destination its egress policy does not allow.
+    EgressDenied,
     InvalidReturnType,
     KilledExecution,
+    /// The function used more memory than its limit allows and was killed.
+    MemoryLimitExceeded,
+    /// The function ran for longer than its timeout and was killed.
+    Timeout,
     UserCodeException(String),
     VeritechServer,
 }
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use si_crypto::SensitiveStrings;
 use si_std::SensitiveString;
 
+use crate::{EgressPolicy, ExecutionPriority, FunctionRuntime};
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CycloneRequest<R>
@@ -38,6 +40,14 @@ pub trait CycloneRequestable {
     type Response;
 
     fn execution_id(&self) -> &str;
+    /// How long the function may run before it is killed, if it overrides the server default.
+    fn timeout_secs(&self) -> Option<u64>;
+    /// Where the request waits in line while every execution slot is taken.
+    fn priority(&self) -> ExecutionPriority;
+    /// What the request's code is run with.
+    fn runtime(&self) -> FunctionRuntime;
+    /// Where the function may open network connections to, if it is restricted at all.
+    fn egress_policy(&self) -> Option<&EgressPolicy>;
     fn kind(&self) -> &str;
     fn websocket_path(&self) -> &str;
     fn inc_run_metric(&self);
@@ -1,4 +1,7 @@
-use crate::{before::BeforeFunction, request::CycloneRequestable};
+use crate::{
+    before::BeforeFunction, request::CycloneRequestable, EgressPolicy, ExecutionPriority,
+    FunctionRuntime,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use telemetry::prelude::*;
@@ -15,6 +18,18 @@ pub struct ResolverFunctionRequest {
     pub response_type: ResolverFunctionResponseType,
     pub code_base64: String,
     pub before: Vec<BeforeFunction>,
+    /// Kill the function if it runs longer than this, instead of after the server-wide limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Where the request waits in line while every execution slot is taken.
+    #[serde(default)]
+    pub priority: ExecutionPriority,
+    /// What the code is run with. Requests that predate WebAssembly functions are JavaScript.
+    #[serde(default)]
+    pub runtime: FunctionRuntime,
+    /// Where the function may open network connections to. Without one it may reach anything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress_policy: Option<EgressPolicy>,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
@@ -40,6 +55,7 @@ pub enum ResolverFunctionResponseType {
     Management,
     Map,
     Object,
+    Plan,
     Qualification,
     String,
     #[default]
@@ -63,6 +79,22 @@ impl CycloneRequestable for ResolverFunctionRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
+    fn priority(&self) -> ExecutionPriority {
+        self.priority
+    }
+
+    fn runtime(&self) -> FunctionRuntime {
+        self.runtime
+    }
+
+    fn egress_policy(&self) -> Option<&EgressPolicy> {
+        self.egress_policy.as_ref()
+    }
+
     fn kind(&self) -> &str {
         "resolverfunction"
     }
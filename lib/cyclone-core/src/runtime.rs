@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumIter, EnumString};
+
+/// What a request's code is, and so what cyclone runs it with.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum FunctionRuntime {
+    /// JavaScript or TypeScript, run by a lang server process.
+    #[default]
+    Js,
+    /// A WebAssembly component targeting WASI preview 2, run inside cyclone itself.
+    Wasm,
+}
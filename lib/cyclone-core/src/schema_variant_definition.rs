@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 use telemetry_utils::metric;
 
-use crate::request::CycloneRequestable;
+use crate::{request::CycloneRequestable, EgressPolicy, ExecutionPriority, FunctionRuntime};
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +10,15 @@ pub struct SchemaVariantDefinitionRequest {
     pub execution_id: String,
     pub handler: String,
     pub code_base64: String,
+    /// Kill the function if it runs longer than this, instead of after the server-wide limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Where the request waits in line while every execution slot is taken.
+    #[serde(default)]
+    pub priority: ExecutionPriority,
+    /// Where the function may open network connections to. Without one it may reach anything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress_policy: Option<EgressPolicy>,
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -29,6 +38,22 @@ impl CycloneRequestable for SchemaVariantDefinitionRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
+    fn priority(&self) -> ExecutionPriority {
+        self.priority
+    }
+
+    fn runtime(&self) -> FunctionRuntime {
+        FunctionRuntime::Js
+    }
+
+    fn egress_policy(&self) -> Option<&EgressPolicy> {
+        self.egress_policy.as_ref()
+    }
+
     fn kind(&self) -> &str {
         "schemaVariantDefinition"
     }
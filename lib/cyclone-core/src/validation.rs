@@ -1,4 +1,6 @@
-use crate::{request::CycloneRequestable, BeforeFunction};
+use crate::{
+    request::CycloneRequestable, BeforeFunction, EgressPolicy, ExecutionPriority, FunctionRuntime,
+};
 use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 use telemetry_utils::metric;
@@ -12,6 +14,15 @@ pub struct ValidationRequest {
     pub value: Option<serde_json::Value>,
     pub validation_format: String,
     pub before: Vec<BeforeFunction>,
+    /// Kill the function if it runs longer than this, instead of after the server-wide limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Where the request waits in line while every execution slot is taken.
+    #[serde(default)]
+    pub priority: ExecutionPriority,
+    /// Where the function may open network connections to. Without one it may reach anything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress_policy: Option<EgressPolicy>,
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -28,6 +39,22 @@ impl CycloneRequestable for ValidationRequest {
         &self.execution_id
     }
 
+    fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+
+    fn priority(&self) -> ExecutionPriority {
+        self.priority
+    }
+
+    fn runtime(&self) -> FunctionRuntime {
+        FunctionRuntime::Js
+    }
+
+    fn egress_policy(&self) -> Option<&EgressPolicy> {
+        self.egress_policy.as_ref()
+    }
+
     fn kind(&self) -> &str {
         "validation"
     }
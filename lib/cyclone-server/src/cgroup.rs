@@ -0,0 +1,227 @@
+//! Resource limit accounting for the cgroup cyclone runs in.
+//!
+//! When cyclone is spawned into its own cgroup v2 (see `si-pool-noodle`'s `CgroupLimits`), its
+//! lang server children share that cgroup and its `memory.max`, `cpu.max`, and `pids.max` limits.
+//! The kernel only tells us about a limit being hit through the cgroup's event counters, so we
+//! snapshot them when an execution starts and compare once it fails, to tell a function that ran
+//! out of memory or CPU apart from one that failed on its own.
+//!
+//! The counters are for the whole cgroup, so they can only be pinned on an execution that had
+//! the cgroup to itself. Limits hit while executions overlapped are not reported at all.
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use cyclone_core::FunctionResultFailureErrorKind;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Executions currently running in this cyclone, which all share its cgroup.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+/// Executions started by this cyclone so far.
+static STARTED: AtomicU64 = AtomicU64::new(0);
+
+/// A resource limit of the cgroup that an execution ran into.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExceededLimit {
+    /// The execution was throttled by the cgroup's `cpu.max` for much of the time it ran.
+    Cpu,
+    /// A process of the execution was OOM-killed by the cgroup's `memory.max`.
+    Memory,
+}
+
+impl ExceededLimit {
+    pub(crate) fn kind(self) -> FunctionResultFailureErrorKind {
+        match self {
+            Self::Cpu => FunctionResultFailureErrorKind::CpuLimitExceeded,
+            Self::Memory => FunctionResultFailureErrorKind::MemoryLimitExceeded,
+        }
+    }
+
+    pub(crate) fn message(self) -> &'static str {
+        match self {
+            Self::Cpu => "function exceeded its cpu limit and was throttled until it timed out",
+            Self::Memory => "function exceeded memory limit",
+        }
+    }
+}
+
+/// Marks an execution as running in cyclone's cgroup for as long as it is held.
+#[derive(Debug)]
+pub(crate) struct RunningExecution {
+    counters: Option<CgroupCounters>,
+}
+
+impl RunningExecution {
+    pub(crate) fn start() -> Self {
+        let others_running = RUNNING.fetch_add(1, Ordering::SeqCst) > 0;
+        let started = STARTED.fetch_add(1, Ordering::SeqCst) + 1;
+
+        Self {
+            counters: if others_running {
+                None
+            } else {
+                CgroupCounters::read(started)
+            },
+        }
+    }
+
+    /// The counters as of the start of the execution, or `None` if they can't be pinned on it
+    /// because another execution was already running.
+    pub(crate) fn counters(&self) -> Option<CgroupCounters> {
+        self.counters
+    }
+}
+
+impl Drop for RunningExecution {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A snapshot of the event counters of the cgroup cyclone runs in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct CgroupCounters {
+    oom_kills: u64,
+    throttled_usecs: u64,
+    read_at: Instant,
+    started: u64,
+}
+
+impl CgroupCounters {
+    /// Reads the counters, or returns `None` if cyclone isn't running in a cgroup v2 hierarchy.
+    fn read(started: u64) -> Option<Self> {
+        let path = current_cgroup_path()?;
+
+        Some(Self {
+            oom_kills: read_counter(path.join("memory.events"), "oom_kill").unwrap_or_default(),
+            throttled_usecs: read_counter(path.join("cpu.stat"), "throttled_usec")
+                .unwrap_or_default(),
+            read_at: Instant::now(),
+            started,
+        })
+    }
+
+    /// Returns the limit that was hit since this snapshot was taken, if any, as long as no other
+    /// execution has started since.
+    pub(crate) fn exceeded_since(&self) -> Option<ExceededLimit> {
+        if STARTED.load(Ordering::SeqCst) != self.started {
+            return None;
+        }
+        let now = Self::read(self.started)?;
+
+        exceeded_between(
+            self.oom_kills,
+            now.oom_kills,
+            Duration::from_micros(now.throttled_usecs.saturating_sub(self.throttled_usecs)),
+            now.read_at.duration_since(self.read_at),
+        )
+    }
+}
+
+/// Running out of memory takes precedence, as it is what actually killed the function. Being
+/// throttled only counts as hitting the cpu limit when it took up at least half of the time the
+/// function ran, since a busy function gets throttled now and then without that being why it
+/// failed.
+fn exceeded_between(
+    oom_kills_before: u64,
+    oom_kills_after: u64,
+    throttled: Duration,
+    elapsed: Duration,
+) -> Option<ExceededLimit> {
+    if oom_kills_after > oom_kills_before {
+        Some(ExceededLimit::Memory)
+    } else if !throttled.is_zero() && throttled * 2 >= elapsed {
+        Some(ExceededLimit::Cpu)
+    } else {
+        None
+    }
+}
+
+/// Finds the unified hierarchy entry (`0::<path>`) in `/proc/self/cgroup`.
+fn current_cgroup_path() -> Option<PathBuf> {
+    let cgroups = fs::read_to_string("/proc/self/cgroup").ok()?;
+    let relative = cgroups
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))?
+        .trim_start_matches('/');
+
+    Some(PathBuf::from(CGROUP_ROOT).join(relative))
+}
+
+/// Reads a `key value` line from a flat keyed cgroup file such as `memory.events`.
+fn read_counter(path: PathBuf, key: &str) -> Option<u64> {
+    parse_counter(&fs::read_to_string(path).ok()?, key)
+}
+
+fn parse_counter(contents: &str, key: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        (name == key).then(|| value.trim().parse().ok()).flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CPU_STAT: &str = "usage_usec 1500000
+user_usec 1200000
+system_usec 300000
+nr_periods 40
+nr_throttled 12
+throttled_usec 900000
+";
+
+    #[test]
+    fn parses_counters() {
+        assert_eq!(Some(12), parse_counter(CPU_STAT, "nr_throttled"));
+        assert_eq!(Some(900_000), parse_counter(CPU_STAT, "throttled_usec"));
+        assert_eq!(
+            Some(1),
+            parse_counter("low 0\nhigh 0\nmax 3\noom 1\noom_kill 1\n", "oom_kill")
+        );
+    }
+
+    #[test]
+    fn does_not_match_key_prefixes() {
+        assert_eq!(None, parse_counter("oom_kill_group 3\n", "oom_kill"));
+        assert_eq!(None, parse_counter(CPU_STAT, "throttled"));
+    }
+
+    #[test]
+    fn ignores_malformed_counters() {
+        assert_eq!(None, parse_counter("oom_kill lots\n", "oom_kill"));
+        assert_eq!(None, parse_counter("oom_kill\n", "oom_kill"));
+        assert_eq!(None, parse_counter("", "oom_kill"));
+    }
+
+    #[test]
+    fn oom_kill_is_memory_limit() {
+        assert_eq!(
+            Some(ExceededLimit::Memory),
+            exceeded_between(0, 1, Duration::from_secs(10), Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn mostly_throttled_is_cpu_limit() {
+        assert_eq!(
+            Some(ExceededLimit::Cpu),
+            exceeded_between(1, 1, Duration::from_secs(5), Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn briefly_throttled_is_no_limit() {
+        assert_eq!(
+            None,
+            exceeded_between(0, 0, Duration::from_millis(200), Duration::from_secs(10))
+        );
+        assert_eq!(None, exceeded_between(0, 0, Duration::ZERO, Duration::ZERO));
+    }
+}
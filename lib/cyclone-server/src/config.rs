@@ -24,6 +24,7 @@ pub enum ConfigError {
 type Result<T> = std::result::Result<T, ConfigError>;
 
 #[derive(Debug, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct Config {
     #[builder(default)]
     watch: Option<Duration>,
@@ -66,6 +67,12 @@ pub struct Config {
 
     #[builder(setter(into), default = "false")]
     enable_process_gatherer: bool,
+
+    #[builder(setter(into, strip_option), default)]
+    warm_workers: Option<WarmWorkersConfig>,
+
+    #[builder(default)]
+    wasm: WasmConfig,
 }
 
 impl Config {
@@ -158,9 +165,73 @@ impl Config {
     pub fn enable_process_gatherer(&self) -> bool {
         self.enable_process_gatherer
     }
+
+    /// Gets a reference to the config's warm workers, if warm mode is enabled.
+    #[must_use]
+    pub fn warm_workers(&self) -> Option<&WarmWorkersConfig> {
+        self.warm_workers.as_ref()
+    }
+
+    /// Gets a reference to the config's settings for WebAssembly funcs.
+    #[must_use]
+    pub fn wasm(&self) -> &WasmConfig {
+        &self.wasm
+    }
+}
+
+/// Settings for serving executions from a pool of long-lived lang server processes, instead of
+/// a fresh process per execution. Executions share processes, so this is only for trusted
+/// workloads.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WarmWorkersConfig {
+    /// How many lang server processes to keep warm.
+    pub pool_size: usize,
+    /// Replace a process once it has served this many executions.
+    pub recycle_after: u32,
+    /// Replace a process once its resident memory grows past this many bytes.
+    pub memory_limit_bytes: Option<u64>,
+}
+
+impl Default for WarmWorkersConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: 4,
+            recycle_after: 100,
+            memory_limit_bytes: Some(512 * 1024 * 1024),
+        }
+    }
+}
+
+/// Settings for running WebAssembly funcs, which cyclone executes itself rather than handing them
+/// to the lang server.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WasmConfig {
+    /// Hosts that funcs may send HTTP requests to. An entry starting with `*.` allows every
+    /// subdomain of what follows it. No requests are allowed when this is empty.
+    pub http_allowlist: Vec<String>,
+    /// How much linear memory a single execution may grow to, in bytes.
+    pub memory_limit_bytes: usize,
+}
+
+impl Default for WasmConfig {
+    fn default() -> Self {
+        Self {
+            http_allowlist: Vec::new(),
+            memory_limit_bytes: 256 * 1024 * 1024,
+        }
+    }
 }
 
 impl ConfigBuilder {
+    fn validate(&self) -> std::result::Result<(), String> {
+        // A server that exits after one request would throw its warm workers away before any of
+        // them is reused
+        if matches!(self.warm_workers, Some(Some(_))) && self.limit_requests == Some(Some(1)) {
+            return Err("warm workers cannot be used when limiting cyclone to one request".into());
+        }
+        Ok(())
+    }
+
     pub fn http_socket(&mut self, socket_addrs: impl ToSocketAddrs) -> Result<&mut Self> {
         Ok(self.incoming_stream(IncomingStream::http_socket(socket_addrs)?))
     }
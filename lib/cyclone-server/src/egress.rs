@@ -0,0 +1,421 @@
+//! Egress policies for lang server executions.
+//!
+//! When a request carries an [`EgressPolicy`], cyclone starts a small HTTP proxy on the loopback
+//! interface for just that execution and hands its address to the lang server, which points the
+//! function's `HTTP_PROXY` and `HTTPS_PROXY` at it. The proxy tunnels `CONNECT` requests and
+//! forwards plain HTTP ones, but only to destinations the policy allows; anything else gets a
+//! `403 Forbidden` and is remembered, so the execution can be reported as
+//! [`EgressDenied`](cyclone_core::FunctionResultFailureErrorKind::EgressDenied).
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex},
+};
+
+use cyclone_core::EgressPolicy;
+use telemetry::prelude::*;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{lookup_host, TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+/// Request heads larger than this are rejected rather than buffered.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Headers that only concern the hop between the function and the proxy.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authorization",
+    "proxy-connection",
+];
+
+#[derive(Debug)]
+pub(crate) struct EgressProxy {
+    addr: SocketAddr,
+    denied: Arc<Mutex<Vec<String>>>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for EgressProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl EgressProxy {
+    pub(crate) async fn start(policy: EgressPolicy) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let addr = listener.local_addr()?;
+        let denied = Arc::new(Mutex::new(Vec::new()));
+        let policy = Arc::new(policy);
+
+        let task = tokio::spawn({
+            let denied = denied.clone();
+            async move {
+                loop {
+                    let stream = match listener.accept().await {
+                        Ok((stream, _)) => stream,
+                        Err(err) => {
+                            warn!(si.error.message = ?err, "egress proxy failed to accept");
+                            continue;
+                        }
+                    };
+                    let policy = policy.clone();
+                    let denied = denied.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = handle_connection(stream, &policy, &denied).await {
+                            debug!(si.error.message = ?err, "egress proxy connection failed");
+                        }
+                    });
+                }
+            }
+        });
+
+        Ok(Self { addr, denied, task })
+    }
+
+    /// The proxy's URL, for `HTTP_PROXY` and friends.
+    pub(crate) fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// The destinations the function tried to reach and was denied, as `host:port`.
+    pub(crate) fn denied(&self) -> Vec<String> {
+        match self.denied.lock() {
+            Ok(denied) => denied.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+async fn handle_connection(
+    mut client: TcpStream,
+    policy: &EgressPolicy,
+    denied: &Mutex<Vec<String>>,
+) -> io::Result<()> {
+    let (head, rest) = match read_head(&mut client).await? {
+        Some(parts) => parts,
+        None => return respond(&mut client, "400 Bad Request", "malformed request").await,
+    };
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split(' ');
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return respond(&mut client, "400 Bad Request", "malformed request line").await;
+    };
+
+    let is_connect = method.eq_ignore_ascii_case("CONNECT");
+    let (authority, path) = if is_connect {
+        (target, None)
+    } else if let Some(without_scheme) = target.strip_prefix("http://") {
+        match without_scheme.find('/') {
+            Some(index) => (&without_scheme[..index], Some(&without_scheme[index..])),
+            None => (without_scheme, Some("/")),
+        }
+    } else {
+        return respond(
+            &mut client,
+            "400 Bad Request",
+            "only CONNECT and absolute http:// requests can be proxied",
+        )
+        .await;
+    };
+    let Some((host, port)) = split_authority(authority, if is_connect { 443 } else { 80 }) else {
+        return respond(&mut client, "400 Bad Request", "malformed destination").await;
+    };
+
+    let addrs = match allowed_addrs(policy, host, port).await {
+        Ok(addrs) => addrs,
+        Err(err) => return respond(&mut client, "502 Bad Gateway", &err.to_string()).await,
+    };
+    if addrs.is_empty() {
+        let destination = format!("{host}:{port}");
+        info!(%destination, "egress proxy denied connection");
+        match denied.lock() {
+            Ok(mut denied) => denied.push(destination.clone()),
+            Err(poisoned) => poisoned.into_inner().push(destination.clone()),
+        }
+        return respond(
+            &mut client,
+            "403 Forbidden",
+            &format!("{destination} is not allowed by the egress policy"),
+        )
+        .await;
+    }
+
+    let mut upstream = match TcpStream::connect(&addrs[..]).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            return respond(&mut client, "502 Bad Gateway", &err.to_string()).await;
+        }
+    };
+
+    if is_connect {
+        client
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await?;
+    } else {
+        // Forward the request in origin form, closing the connection afterwards so the next
+        // request has to come back through the proxy and get checked too
+        let mut forwarded = format!("{method} {} {version}\r\n", path.unwrap_or("/"));
+        for header in lines.filter(|line| !line.is_empty()) {
+            let name = header.split(':').next().unwrap_or_default().trim();
+            if !HOP_BY_HOP_HEADERS
+                .iter()
+                .any(|hop| name.eq_ignore_ascii_case(hop))
+            {
+                forwarded.push_str(header);
+                forwarded.push_str("\r\n");
+            }
+        }
+        forwarded.push_str("Connection: close\r\n\r\n");
+        upstream.write_all(forwarded.as_bytes()).await?;
+    }
+    upstream.write_all(&rest).await?;
+
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Reads up to the end of the request head, returning it along with anything read past it.
+async fn read_head(client: &mut TcpStream) -> io::Result<Option<(String, Vec<u8>)>> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0; 1024];
+    loop {
+        if let Some(end) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            let rest = buf.split_off(end + 4);
+            buf.truncate(end);
+            return Ok(String::from_utf8(buf).ok().map(|head| (head, rest)));
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Ok(None);
+        }
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn split_authority(authority: &str, default_port: u16) -> Option<(&str, u16)> {
+    // Drop any userinfo, which is no business of the policy's
+    let authority = authority.rsplit('@').next()?;
+    let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
+        // An IPv6 literal, such as [::1]:443
+        let (host, after) = rest.split_once(']')?;
+        (host, after.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port,
+    };
+    (!host.is_empty()).then_some((host, port))
+}
+
+/// The addresses the function may connect to for `host`, which is empty when it may not connect
+/// at all. A host allowed by name may use whatever it resolves to, while any other host only gets
+/// the addresses that fall within one of the policy's CIDR blocks.
+async fn allowed_addrs(
+    policy: &EgressPolicy,
+    host: &str,
+    port: u16,
+) -> io::Result<Vec<SocketAddr>> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(if policy.allows_ip(ip) {
+            vec![SocketAddr::new(ip, port)]
+        } else {
+            Vec::new()
+        });
+    }
+
+    let allowed_by_name = policy.allows_host(host);
+    match lookup_host((host, port)).await {
+        Ok(addrs) => Ok(addrs
+            .filter(|addr| allowed_by_name || policy.allows_ip(addr.ip()))
+            .collect()),
+        // Only an allowed host failing to resolve is worth telling the function about
+        Err(err) if allowed_by_name => Err(err),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+async fn respond(client: &mut TcpStream, status: &str, message: &str) -> io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{message}",
+        message.len()
+    );
+    client.write_all(response.as_bytes()).await?;
+    client.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn proxy(allow: &[&str]) -> EgressProxy {
+        EgressProxy::start(EgressPolicy {
+            allow: allow.iter().map(ToString::to_string).collect(),
+        })
+        .await
+        .expect("failed to start proxy")
+    }
+
+    /// Sends a request to the proxy, returning everything it sent back before closing.
+    async fn send(proxy: &EgressProxy, request: &str) -> String {
+        let mut stream = TcpStream::connect(proxy.addr)
+            .await
+            .expect("failed to connect to proxy");
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .expect("failed to send request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("failed to read response");
+        response
+    }
+
+    #[test]
+    fn splits_authorities() {
+        assert_eq!(
+            Some(("example.com", 443)),
+            split_authority("example.com", 443)
+        );
+        assert_eq!(
+            Some(("example.com", 8080)),
+            split_authority("example.com:8080", 443)
+        );
+        assert_eq!(
+            Some(("10.0.0.1", 80)),
+            split_authority("user:secret@10.0.0.1", 80)
+        );
+        assert_eq!(
+            Some(("evil.net", 80)),
+            split_authority("allowed.com:80@evil.net", 80)
+        );
+        assert_eq!(Some(("::1", 443)), split_authority("[::1]:443", 80));
+        assert_eq!(Some(("::1", 80)), split_authority("[::1]", 80));
+        assert_eq!(
+            Some(("::ffff:10.0.0.1", 8443)),
+            split_authority("user@[::ffff:10.0.0.1]:8443", 443)
+        );
+
+        assert_eq!(None, split_authority("", 443));
+        assert_eq!(None, split_authority(":443", 443));
+        assert_eq!(None, split_authority("example.com:http", 443));
+        assert_eq!(None, split_authority("example.com:65536", 443));
+        assert_eq!(None, split_authority("[::1", 443));
+    }
+
+    #[tokio::test]
+    async fn refuses_connect_to_denied_hosts() {
+        let proxy = proxy(&["127.0.0.1"]).await;
+
+        let response = send(
+            &proxy,
+            "CONNECT 10.9.9.9:443 HTTP/1.1\r\nHost: 10.9.9.9:443\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"), "{response}");
+
+        // Names that aren't allowed are refused whether they resolve or not
+        let response = send(
+            &proxy,
+            "CONNECT denied.invalid:443 HTTP/1.1\r\nHost: denied.invalid:443\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"), "{response}");
+
+        assert_eq!(
+            vec!["10.9.9.9:443".to_owned(), "denied.invalid:443".to_owned()],
+            proxy.denied()
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_absolute_form_requests_to_denied_hosts() {
+        let proxy = proxy(&["*.allowed.invalid"]).await;
+
+        let response = send(
+            &proxy,
+            "GET http://10.9.9.9/metadata HTTP/1.1\r\nHost: 10.9.9.9\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"), "{response}");
+
+        // Userinfo doesn't change where the request goes
+        let response = send(
+            &proxy,
+            "GET http://api.allowed.invalid@10.9.9.9:8080/ HTTP/1.1\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"), "{response}");
+
+        assert_eq!(
+            vec!["10.9.9.9:80".to_owned(), "10.9.9.9:8080".to_owned()],
+            proxy.denied()
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_requests_it_cannot_check() {
+        let proxy = proxy(&["127.0.0.1"]).await;
+
+        // Origin form requests don't say where they're going, and https can only be tunneled
+        for request in [
+            "GET / HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n",
+            "GET https://127.0.0.1/ HTTP/1.1\r\n\r\n",
+            "nonsense\r\n\r\n",
+        ] {
+            let response = send(&proxy, request).await;
+            assert!(
+                response.starts_with("HTTP/1.1 400 Bad Request"),
+                "{response}"
+            );
+        }
+        assert!(proxy.denied().is_empty());
+    }
+
+    #[tokio::test]
+    async fn tunnels_connect_to_allowed_hosts() {
+        let upstream = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("failed to bind upstream");
+        let upstream_addr = upstream.local_addr().expect("no upstream address");
+        tokio::spawn(async move {
+            let (mut stream, _) = upstream.accept().await.expect("failed to accept");
+            let mut buf = [0; 4];
+            stream.read_exact(&mut buf).await.expect("failed to read");
+            stream.write_all(&buf).await.expect("failed to write");
+        });
+        let proxy = proxy(&["127.0.0.1"]).await;
+
+        let mut stream = TcpStream::connect(proxy.addr)
+            .await
+            .expect("failed to connect to proxy");
+        stream
+            .write_all(format!("CONNECT {upstream_addr} HTTP/1.1\r\n\r\nping").as_bytes())
+            .await
+            .expect("failed to send request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("failed to read response");
+
+        assert_eq!("HTTP/1.1 200 Connection Established\r\n\r\nping", response);
+        assert!(proxy.denied().is_empty());
+    }
+}
@@ -12,11 +12,12 @@ use bytes_lines_codec::BytesLinesCodec;
 use cyclone_core::{
     process::{self, ShutdownError},
     CycloneRequest, CycloneRequestable, FunctionResult, FunctionResultFailure,
-    FunctionResultFailureError, FunctionResultFailureErrorKind, Message, OutputStream,
+    FunctionResultFailureError, FunctionResultFailureErrorKind, FunctionRuntime, Message,
+    OutputStream,
 };
 use futures::{SinkExt, StreamExt, TryStreamExt};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use si_crypto::SensitiveStrings;
 use telemetry::prelude::*;
 use thiserror::Error;
@@ -28,9 +29,16 @@ use tokio::{
 use tokio_serde::{formats::SymmetricalJson, Deserializer, Framed, SymmetricallyFramed};
 use tokio_util::codec::{Decoder, FramedRead, FramedWrite};
 
-use crate::{state::LangServerChild, WebSocketMessage};
+use crate::{
+    cgroup::{CgroupCounters, ExceededLimit, RunningExecution},
+    egress::EgressProxy,
+    state::{LangServer, LangServerChild},
+    warm::WarmWorkerGuard,
+    wasm::{WasmExecution, WasmRuntime},
+    WebSocketMessage,
+};
 
-const TX_TIMEOUT_SECS: Duration = Duration::from_secs(5);
+pub(crate) const TX_TIMEOUT_SECS: Duration = Duration::from_secs(5);
 const DEFAULT_LANG_SERVER_PROCESS_TIMEOUT: Duration = Duration::from_secs(32 * 60);
 
 pub fn new<Request, LangServerSuccess, Success>(
@@ -63,18 +71,36 @@ pub enum ExecutionError {
     ChildShutdown(#[from] ShutdownError),
     #[error("failed to spawn child process; program={0}")]
     ChildSpawn(#[source] io::Error, PathBuf),
-    #[error("child process timed out: {0:?}")]
-    ChildTimeout(Duration),
+    #[error("child process timed out after {timeout:?}; execution_id={execution_id}")]
+    ChildTimeout {
+        execution_id: String,
+        timeout: Duration,
+    },
+    #[error("failed to start egress proxy")]
+    EgressProxy(#[source] io::Error),
     #[error("failed to decode string as utf8")]
     FromUtf8(#[from] FromUtf8Error),
     #[error("failed to deserialize json message")]
     JSONDeserialize(#[source] serde_json::Error),
     #[error("failed to serialize json message")]
     JSONSerialize(#[source] serde_json::Error),
+    #[error("execution exceeded its {limit:?} limit; execution_id={execution_id}")]
+    ResourceLimitExceeded {
+        execution_id: String,
+        limit: ExceededLimit,
+    },
     #[error("send timeout")]
     SendTimeout(#[source] tokio::time::error::Elapsed),
     #[error("unexpected websocket message type: {0:?}")]
     UnexpectedMessageType(WebSocketMessage),
+    #[error("warm worker pool is closed")]
+    WarmPoolClosed,
+    #[error("warm worker exited before sending a result")]
+    WarmWorkerExited,
+    #[error("webassembly execution ended before sending a result")]
+    WasmExecutionExited,
+    #[error("failed to set up the webassembly runtime")]
+    WasmRuntime(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error("failed to close websocket")]
     WSClose(#[source] axum::Error),
     #[error("failed to receive websocket message--stream is closed")]
@@ -85,6 +111,12 @@ pub enum ExecutionError {
     WSSendIO(#[source] axum::Error),
 }
 
+impl ExecutionError {
+    pub(crate) fn wasm_runtime(err: wasmtime::Error) -> Self {
+        Self::WasmRuntime(err.into())
+    }
+}
+
 type Result<T> = std::result::Result<T, ExecutionError>;
 
 #[derive(Debug)]
@@ -106,7 +138,8 @@ where
 {
     pub async fn start(
         self,
-        child: LangServerChild,
+        lang_server: LangServer,
+        wasm_runtime: WasmRuntime,
         ws: &mut WebSocket,
     ) -> Result<ExecutionStarted<LangServerSuccess, Success>> {
         // Send start is the initial communication before we read the request.
@@ -114,39 +147,93 @@ where
         // Read the request message from the web socket
         let cyclone_request = Self::read_request(ws).await?;
         let (request, sensitive_strings) = cyclone_request.into_parts();
-        let inner = child.inner();
-        let mut child_lock = inner.lock().await;
-
-        let stdin = child_lock
-            .stdin
-            .take()
-            .ok_or(ExecutionError::ChildIO("stdin"))?;
-        Self::child_send_function_request(stdin, request).await?;
-
-        let stderr = {
-            let stderr = child_lock
-                .stderr
-                .take()
-                .ok_or(ExecutionError::ChildIO("stderr"))?;
-            FramedRead::new(stderr, BytesLinesCodec::new())
+        let execution_id = request.execution_id().to_owned();
+        // A request can only shorten the server's timeout, never extend it
+        let lang_server_process_timeout = match request.timeout_secs() {
+            Some(timeout_secs) => {
+                Duration::from_secs(timeout_secs).min(self.lang_server_process_timeout)
+            }
+            None => self.lang_server_process_timeout,
+        };
+        let sensitive_strings = Arc::new(sensitive_strings);
+        let running = RunningExecution::start();
+        let cgroup_counters = running.counters();
+        // WebAssembly funcs make their requests through cyclone, which applies the policy itself
+        let egress_proxy = match (request.runtime(), request.egress_policy()) {
+            (FunctionRuntime::Js, Some(policy)) => Some(
+                EgressProxy::start(policy.clone())
+                    .await
+                    .map_err(ExecutionError::EgressProxy)?,
+            ),
+            _ => None,
         };
 
-        let stdout = {
-            let stdout = child_lock
-                .stdout
-                .take()
-                .ok_or(ExecutionError::ChildIO("stdout"))?;
-            let codec = FramedRead::new(stdout, BytesLinesCodec::new());
-            SymmetricallyFramed::new(codec, SymmetricalJson::default())
+        let lang_server = match (request.runtime(), lang_server) {
+            // WebAssembly funcs are run by cyclone itself, leaving the lang server untouched
+            (FunctionRuntime::Wasm, _) => StartedLangServer::Wasm(wasm_runtime.start(
+                execution_id.clone(),
+                Self::request_value(&request, None)?,
+                request.egress_policy().cloned(),
+            )),
+            (FunctionRuntime::Js, LangServer::Child(child)) => {
+                let inner = child.inner();
+                let mut child_lock = inner.lock().await;
+
+                let stdin = child_lock
+                    .stdin
+                    .take()
+                    .ok_or(ExecutionError::ChildIO("stdin"))?;
+                Self::child_send_function_request(
+                    stdin,
+                    Self::request_value(&request, egress_proxy.as_ref())?,
+                )
+                .await?;
+
+                let stderr = {
+                    let stderr = child_lock
+                        .stderr
+                        .take()
+                        .ok_or(ExecutionError::ChildIO("stderr"))?;
+                    FramedRead::new(stderr, BytesLinesCodec::new())
+                };
+
+                let stdout = {
+                    let stdout = child_lock
+                        .stdout
+                        .take()
+                        .ok_or(ExecutionError::ChildIO("stdout"))?;
+                    let codec = FramedRead::new(stdout, BytesLinesCodec::new());
+                    SymmetricallyFramed::new(codec, SymmetricalJson::default())
+                };
+
+                drop(child_lock);
+                StartedLangServer::Child {
+                    child,
+                    stdout,
+                    stderr,
+                }
+            }
+            (FunctionRuntime::Js, LangServer::Warm(pool)) => {
+                let mut worker = pool.checkout().await?;
+                worker
+                    .send_request(
+                        Self::request_value(&request, egress_proxy.as_ref())?,
+                        sensitive_strings.clone(),
+                    )
+                    .await?;
+                StartedLangServer::Warm(worker)
+            }
         };
 
         Ok(ExecutionStarted {
-            child,
-            stdout,
-            stderr,
-            sensitive_strings: Arc::new(sensitive_strings),
+            lang_server,
+            sensitive_strings,
             success_marker: self.success_marker,
-            lang_server_process_timeout: self.lang_server_process_timeout,
+            execution_id,
+            lang_server_process_timeout,
+            cgroup_counters,
+            _running: running,
+            egress_proxy,
         })
     }
 
@@ -174,16 +261,27 @@ where
         Ok(())
     }
 
-    async fn child_send_function_request(stdin: ChildStdin, request: Request) -> Result<()> {
-        let mut value = serde_json::to_value(&request).map_err(ExecutionError::JSONSerialize)?;
+    fn request_value(request: &Request, egress_proxy: Option<&EgressProxy>) -> Result<Value> {
+        let mut value = serde_json::to_value(request).map_err(ExecutionError::JSONSerialize)?;
 
         if let serde_json::Value::Object(ref mut map) = value {
             map.insert(
                 "kind".to_string(),
                 serde_json::Value::String(request.kind().to_string()),
             );
+            // The lang server sends the function's traffic through the proxy
+            if let Some(egress_proxy) = egress_proxy {
+                map.insert(
+                    "egressProxy".to_string(),
+                    serde_json::Value::String(egress_proxy.url()),
+                );
+            }
         }
 
+        Ok(value)
+    }
+
+    async fn child_send_function_request(stdin: ChildStdin, value: Value) -> Result<()> {
         let codec = FramedWrite::new(stdin, BytesLinesCodec::new());
         let mut stdin = SymmetricallyFramed::new(codec, SymmetricalJson::default());
 
@@ -207,12 +305,34 @@ type SiJsonError<S> = <SymmetricalJson<SiMessage<S>> as Deserializer<SiMessage<S
 
 #[derive(Debug)]
 pub struct ExecutionStarted<LangServerSuccess, Success> {
-    child: LangServerChild,
-    stdout: SiFramed<SiMessage<LangServerSuccess>>,
-    stderr: FramedRead<ChildStderr, BytesLinesCodec>,
+    lang_server: StartedLangServer<LangServerSuccess>,
     sensitive_strings: Arc<SensitiveStrings>,
     success_marker: PhantomData<Success>,
+    execution_id: String,
     lang_server_process_timeout: Duration,
+    cgroup_counters: Option<CgroupCounters>,
+    /// Kept for as long as the execution runs, so that limits hit by others aren't blamed on it.
+    _running: RunningExecution,
+    /// Kept for as long as the execution runs, so the proxy stays up until it's done.
+    egress_proxy: Option<EgressProxy>,
+}
+
+#[derive(Debug)]
+enum StartedLangServer<LangServerSuccess> {
+    Child {
+        child: LangServerChild,
+        stdout: SiFramed<SiMessage<LangServerSuccess>>,
+        stderr: FramedRead<ChildStderr, BytesLinesCodec>,
+    },
+    Warm(WarmWorkerGuard),
+    Wasm(WasmExecution),
+}
+
+#[derive(Debug)]
+enum ClosingLangServer {
+    Child(LangServerChild),
+    Warm(WarmWorkerGuard),
+    Wasm,
 }
 
 // TODO: implement shutdown oneshot
@@ -246,63 +366,300 @@ where
     SiDecoderError: From<SiJsonError<LangServerSuccess>>,
 {
     pub async fn process(self, ws: &mut WebSocket) -> Result<ExecutionClosing<Success>> {
-        tokio::spawn(handle_stderr(self.stderr, self.sensitive_strings.clone()));
-
-        let mut stream = self
-            .stdout
-            .map(|ls_result| match ls_result {
-                Ok(ls_msg) => match ls_msg {
-                    LangServerMessage::Output(mut output) => {
-                        Self::filter_output(&mut output, &self.sensitive_strings)?;
-                        Ok(Message::OutputStream(output.into()))
+        match self.lang_server {
+            StartedLangServer::Child {
+                child,
+                stdout,
+                stderr,
+            } => {
+                tokio::spawn(handle_stderr(stderr, self.sensitive_strings.clone()));
+
+                let cgroup_counters = self.cgroup_counters;
+                let execution_id = self.execution_id.clone();
+                let mut stream = stdout.map(|ls_result| match ls_result {
+                    Ok(ls_msg) => {
+                        let is_result = matches!(ls_msg, LangServerMessage::Result(_));
+                        Self::to_ws_messages(
+                            ls_msg,
+                            &execution_id,
+                            &self.sensitive_strings,
+                            cgroup_counters,
+                            self.egress_proxy.as_ref(),
+                        )
+                        .map(|msgs| (is_result, msgs))
                     }
-                    LangServerMessage::Result(mut result) => {
-                        Self::filter_result(&mut result, &self.sensitive_strings)?;
-                        Ok(Message::Result(result.into()))
+                    Err(err) => Err(ExecutionError::ChildRecvIO(err)),
+                });
+
+                let receive_loop = async {
+                    let mut received_result = false;
+                    while let Some((is_result, msgs)) = stream.try_next().await? {
+                        received_result |= is_result;
+                        for msg in msgs {
+                            ws.send(msg).await.map_err(ExecutionError::WSSendIO)?;
+                        }
                     }
-                },
-                Err(err) => Err(ExecutionError::ChildRecvIO(err)),
-            })
-            .map(|msg_result: Result<_>| match msg_result {
-                Ok(msg) => match msg
-                    .serialize_to_string()
-                    .map_err(ExecutionError::JSONSerialize)
-                {
-                    Ok(json_str) => Ok(WebSocketMessage::Text(json_str)),
-                    Err(err) => Err(err),
-                },
-                Err(err) => Err(err),
-            });
+                    // The lang server itself may have been OOM-killed before it could report a
+                    // result
+                    if !received_result {
+                        if let Some(err) = Self::oom_error(&self.execution_id, cgroup_counters) {
+                            return Err(err);
+                        }
+                    }
+
+                    Result::<_>::Ok(())
+                };
+
+                let inner = child.inner();
+                let mut child_lock = inner.lock().await;
+                match timeout(self.lang_server_process_timeout, receive_loop).await {
+                    Ok(execution) => execution?,
+                    Err(err) => {
+                        // Exceeded timeout, shutdown child process
+                        process::child_shutdown(
+                            &mut child_lock,
+                            Some(process::Signal::SIGTERM),
+                            None,
+                        )
+                        .await?;
+                        drop(child);
+
+                        error!(
+                            ?err,
+                            execution_id = %self.execution_id,
+                            timeout = ?self.lang_server_process_timeout,
+                            "shutdown child process due to timeout"
+                        );
+                        return Err(Self::timeout_error(
+                            self.execution_id,
+                            self.lang_server_process_timeout,
+                            self.cgroup_counters,
+                        ));
+                    }
+                };
+                drop(child_lock);
+
+                Ok(ExecutionClosing {
+                    lang_server: ClosingLangServer::Child(child),
+                    success_marker: PhantomData,
+                })
+            }
+            StartedLangServer::Warm(mut worker) => {
+                // A warm worker keeps running after the execution, so the result is the last
+                // message that belongs to it.
+                let cgroup_counters = self.cgroup_counters;
+                let receive_loop = async {
+                    loop {
+                        let ls_msg = match worker.next_message().await {
+                            Some(ls_msg) => ls_msg?,
+                            None => {
+                                return Err(Self::oom_error(&self.execution_id, cgroup_counters)
+                                    .unwrap_or(ExecutionError::WarmWorkerExited));
+                            }
+                        };
+                        let is_result = matches!(ls_msg, LangServerMessage::Result(_));
+                        for msg in Self::to_ws_messages(
+                            ls_msg,
+                            &self.execution_id,
+                            &self.sensitive_strings,
+                            cgroup_counters,
+                            self.egress_proxy.as_ref(),
+                        )? {
+                            ws.send(msg).await.map_err(ExecutionError::WSSendIO)?;
+                        }
+                        if is_result {
+                            return Ok(());
+                        }
+                    }
+                };
+
+                let execution = timeout(self.lang_server_process_timeout, receive_loop).await;
+                match execution {
+                    Ok(execution) => execution?,
+                    Err(err) => {
+                        // Dropping the worker without finishing it kills it
+                        drop(worker);
+
+                        error!(
+                            ?err,
+                            execution_id = %self.execution_id,
+                            timeout = ?self.lang_server_process_timeout,
+                            "killed warm worker due to timeout"
+                        );
+                        return Err(Self::timeout_error(
+                            self.execution_id,
+                            self.lang_server_process_timeout,
+                            self.cgroup_counters,
+                        ));
+                    }
+                };
 
-        let receive_loop = async {
-            while let Some(msg) = stream.try_next().await? {
-                ws.send(msg).await.map_err(ExecutionError::WSSendIO)?;
+                Ok(ExecutionClosing {
+                    lang_server: ClosingLangServer::Warm(worker),
+                    success_marker: PhantomData,
+                })
             }
+            StartedLangServer::Wasm(mut wasm) => {
+                // The component runs inside of cyclone, so its memory is accounted for by the
+                // store's limits rather than the cgroup
+                let receive_loop = async {
+                    loop {
+                        let ls_msg = match wasm.next_message().await {
+                            Some(ls_msg) => ls_msg?,
+                            None => return Err(ExecutionError::WasmExecutionExited),
+                        };
+                        let is_result = matches!(ls_msg, LangServerMessage::Result(_));
+                        for msg in Self::to_ws_messages(
+                            ls_msg,
+                            &self.execution_id,
+                            &self.sensitive_strings,
+                            None,
+                            None,
+                        )? {
+                            ws.send(msg).await.map_err(ExecutionError::WSSendIO)?;
+                        }
+                        if is_result {
+                            return Ok(());
+                        }
+                    }
+                };
+
+                let execution = timeout(self.lang_server_process_timeout, receive_loop).await;
+                match execution {
+                    Ok(execution) => execution?,
+                    Err(err) => {
+                        // Dropping the execution stops the component at its next epoch
+                        drop(wasm);
+
+                        error!(
+                            ?err,
+                            execution_id = %self.execution_id,
+                            timeout = ?self.lang_server_process_timeout,
+                            "stopped webassembly execution due to timeout"
+                        );
+                        return Err(ExecutionError::ChildTimeout {
+                            execution_id: self.execution_id,
+                            timeout: self.lang_server_process_timeout,
+                        });
+                    }
+                };
 
-            Result::<_>::Ok(())
-        };
+                Ok(ExecutionClosing {
+                    lang_server: ClosingLangServer::Wasm,
+                    success_marker: PhantomData,
+                })
+            }
+        }
+    }
 
-        let inner = self.child.inner();
-        let mut child_lock = inner.lock().await;
-        match timeout(self.lang_server_process_timeout, receive_loop).await {
-            Ok(execution) => execution?,
-            Err(err) => {
-                // Exceeded timeout, shutdown child process
-                process::child_shutdown(&mut child_lock, Some(process::Signal::SIGTERM), None)
-                    .await?;
-                drop(self.child);
+    /// A timed out execution that was throttled along the way is reported as having hit its cpu
+    /// limit (or its memory limit, if something got OOM-killed) rather than as a plain timeout.
+    fn timeout_error(
+        execution_id: String,
+        timeout: Duration,
+        cgroup_counters: Option<CgroupCounters>,
+    ) -> ExecutionError {
+        match cgroup_counters.and_then(|counters| counters.exceeded_since()) {
+            Some(limit) => ExecutionError::ResourceLimitExceeded {
+                execution_id,
+                limit,
+            },
+            None => ExecutionError::ChildTimeout {
+                execution_id,
+                timeout,
+            },
+        }
+    }
+
+    /// Returns an error for the execution if one of its processes was OOM-killed since it started.
+    fn oom_error(
+        execution_id: &str,
+        cgroup_counters: Option<CgroupCounters>,
+    ) -> Option<ExecutionError> {
+        match cgroup_counters.and_then(|counters| counters.exceeded_since()) {
+            Some(limit @ ExceededLimit::Memory) => Some(ExecutionError::ResourceLimitExceeded {
+                execution_id: execution_id.to_owned(),
+                limit,
+            }),
+            Some(ExceededLimit::Cpu) | None => None,
+        }
+    }
 
-                error!(?err, "shutdown child process due to timeout");
-                return Err(ExecutionError::ChildTimeout(
-                    self.lang_server_process_timeout,
-                ));
+    /// Converts a lang server message into the messages sent over the websocket, which is more
+    /// than one when the function was denied network access: each denied destination is logged
+    /// before the result.
+    fn to_ws_messages(
+        ls_msg: LangServerMessage<LangServerSuccess>,
+        execution_id: &str,
+        sensitive_strings: &SensitiveStrings,
+        cgroup_counters: Option<CgroupCounters>,
+        egress_proxy: Option<&EgressProxy>,
+    ) -> Result<Vec<WebSocketMessage>> {
+        let mut msgs = Vec::new();
+        let msg = match ls_msg {
+            LangServerMessage::Output(mut output) => {
+                Self::filter_output(&mut output, sensitive_strings)?;
+                Message::OutputStream(output.into())
+            }
+            LangServerMessage::Result(mut result) => {
+                Self::filter_result(&mut result, sensitive_strings)?;
+                // Even a function that caught the proxy's refusal fails, since what it did
+                // without the connection can't be trusted
+                let denied = egress_proxy.map(EgressProxy::denied).unwrap_or_default();
+                if !denied.is_empty() {
+                    for destination in &denied {
+                        msgs.push(Self::to_ws_message(Message::OutputStream(OutputStream {
+                            execution_id: execution_id.to_owned(),
+                            stream: "stderr".to_owned(),
+                            level: "error".to_owned(),
+                            group: Some("egress".to_owned()),
+                            message: format!(
+                                "Connection to {destination} denied by the workspace's egress policy"
+                            ),
+                            timestamp: crate::timestamp(),
+                            fields: Some(serde_json::Map::from_iter([(
+                                "destination".to_owned(),
+                                Value::String(destination.clone()),
+                            )])),
+                        }))?);
+                    }
+                    result = LangServerResult::Failure(LangServerFailure {
+                        execution_id: execution_id.to_owned(),
+                        error: LangServerFailureError {
+                            kind: FunctionResultFailureErrorKind::EgressDenied,
+                            message: format!(
+                                "function tried to reach {}, which the workspace's egress policy does not allow",
+                                denied.join(", ")
+                            ),
+                        },
+                    });
+                }
+                // When the function's process was OOM-killed, the lang server only sees it die,
+                // so tell the user what actually happened
+                if let LangServerResult::Failure(failure) = &mut result {
+                    if let Some(limit @ ExceededLimit::Memory) =
+                        cgroup_counters.and_then(|counters| counters.exceeded_since())
+                    {
+                        failure.error = LangServerFailureError {
+                            kind: limit.kind(),
+                            message: limit.message().to_owned(),
+                        };
+                    }
+                }
+                Message::Result(result.into())
             }
         };
+        msgs.push(Self::to_ws_message(msg)?);
 
-        Ok(ExecutionClosing {
-            child: self.child,
-            success_marker: PhantomData,
-        })
+        Ok(msgs)
+    }
+
+    fn to_ws_message(msg: Message<Success>) -> Result<WebSocketMessage> {
+        let json_str = msg
+            .serialize_to_string()
+            .map_err(ExecutionError::JSONSerialize)?;
+
+        Ok(WebSocketMessage::Text(json_str))
     }
 
     fn filter_output(
@@ -312,6 +669,12 @@ where
         if sensitive_strings.has_sensitive(&output.message) {
             output.message = sensitive_strings.redact(&output.message);
         }
+        if let Some(fields) = &mut output.fields {
+            redact_keys(fields, sensitive_strings);
+            fields
+                .values_mut()
+                .for_each(|value| redact_value(value, sensitive_strings));
+        }
 
         Ok(())
     }
@@ -321,18 +684,7 @@ where
         sensitive_strings: &SensitiveStrings,
     ) -> Result<()> {
         let mut value = serde_json::to_value(&result).map_err(ExecutionError::JSONSerialize)?;
-
-        let mut work_queue = vec![&mut value];
-        while let Some(work) = work_queue.pop() {
-            match work {
-                Value::Array(values) => work_queue.extend(values),
-                Value::Object(object) => object.values_mut().for_each(|v| work_queue.push(v)),
-                Value::String(string) if sensitive_strings.has_sensitive(string) => {
-                    *string = sensitive_strings.redact(string);
-                }
-                Value::String(_) | Value::Null | Value::Number(_) | Value::Bool(_) => {}
-            }
-        }
+        redact_value(&mut value, sensitive_strings);
 
         let mut filtered_result: LangServerResult<LangServerSuccess> =
             serde_json::from_value(value).map_err(ExecutionError::JSONDeserialize)?;
@@ -341,9 +693,48 @@ where
     }
 }
 
+/// Redacts sensitive strings wherever they appear within `value`.
+fn redact_value(value: &mut Value, sensitive_strings: &SensitiveStrings) {
+    let mut work_queue = vec![value];
+    while let Some(work) = work_queue.pop() {
+        match work {
+            Value::Array(values) => work_queue.extend(values),
+            Value::Object(object) => {
+                redact_keys(object, sensitive_strings);
+                object.values_mut().for_each(|v| work_queue.push(v));
+            }
+            Value::String(string) if sensitive_strings.has_sensitive(string) => {
+                *string = sensitive_strings.redact(string);
+            }
+            Value::String(_) | Value::Null | Value::Number(_) | Value::Bool(_) => {}
+        }
+    }
+}
+
+/// Redacts sensitive strings in the keys of `object`, which a function can log as easily as its
+/// values.
+fn redact_keys(object: &mut Map<String, Value>, sensitive_strings: &SensitiveStrings) {
+    if !object
+        .keys()
+        .any(|key| sensitive_strings.has_sensitive(key))
+    {
+        return;
+    }
+    *object = std::mem::take(object)
+        .into_iter()
+        .map(|(key, value)| {
+            if sensitive_strings.has_sensitive(&key) {
+                (sensitive_strings.redact(&key), value)
+            } else {
+                (key, value)
+            }
+        })
+        .collect();
+}
+
 #[derive(Debug)]
 pub struct ExecutionClosing<Success> {
-    child: LangServerChild,
+    lang_server: ClosingLangServer,
     success_marker: PhantomData<Success>,
 }
 
@@ -352,7 +743,22 @@ where
     Success: Serialize,
 {
     pub async fn finish(self, mut ws: WebSocket) -> Result<()> {
-        let inner = self.child.inner();
+        let child = match self.lang_server {
+            ClosingLangServer::Child(child) => child,
+            ClosingLangServer::Warm(worker) => {
+                // The worker is done with this execution whether or not the client hears about
+                // it, so it goes back to the pool before the websocket is wrapped up.
+                worker.finished();
+                Self::ws_send_finish(&mut ws).await?;
+                return Self::ws_close(ws).await;
+            }
+            ClosingLangServer::Wasm => {
+                Self::ws_send_finish(&mut ws).await?;
+                return Self::ws_close(ws).await;
+            }
+        };
+
+        let inner = child.inner();
         let mut child_lock = inner.lock().await;
         let finished = Self::ws_send_finish(&mut ws).await;
         let closed = Self::ws_close(ws).await;
@@ -360,7 +766,8 @@ where
             process::child_shutdown(&mut child_lock, Some(process::Signal::SIGTERM), None)
                 .await
                 .map_err(Into::into);
-        drop(self.child);
+        drop(child_lock);
+        drop(child);
 
         match (finished, closed, shutdown) {
             // Everything succeeds, great!
@@ -428,6 +835,8 @@ pub struct LangServerOutput {
     level: String,
     group: Option<String>,
     message: String,
+    #[serde(default)]
+    fields: Option<serde_json::Map<String, Value>>,
 }
 
 impl From<LangServerOutput> for OutputStream {
@@ -439,6 +848,7 @@ impl From<LangServerOutput> for OutputStream {
             group: value.group,
             message: value.message,
             timestamp: crate::timestamp(),
+            fields: value.fields,
         }
     }
 }
@@ -485,3 +895,51 @@ struct LangServerFailureError {
     kind: FunctionResultFailureErrorKind,
     message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use cyclone_core::{ActionRunRequest, ActionRunResultSuccess};
+    use serde_json::json;
+
+    use super::*;
+    use crate::result::LangServerActionRunResultSuccess;
+
+    fn sensitive_strings() -> SensitiveStrings {
+        let mut sensitive_strings = SensitiveStrings::default();
+        sensitive_strings.insert("hunter2");
+        sensitive_strings
+    }
+
+    #[test]
+    fn redacts_output_field_keys_and_values() {
+        let mut output: LangServerOutput = serde_json::from_value(json!({
+            "executionId": "1234",
+            "stream": "stdout",
+            "level": "info",
+            "message": "logging in with hunter2",
+            "fields": {
+                "hunter2": "password",
+                "user": "hunter2",
+                "nested": { "token-hunter2": ["hunter2"] },
+            },
+        }))
+        .expect("failed to deserialize output");
+
+        Execution::<
+            ActionRunRequest,
+            LangServerActionRunResultSuccess,
+            ActionRunResultSuccess,
+        >::filter_output(&mut output, &sensitive_strings())
+            .expect("failed to filter output");
+
+        assert_eq!("logging in with [redacted]", output.message);
+        assert_eq!(
+            Some(json!({
+                "[redacted]": "password",
+                "user": "[redacted]",
+                "nested": { "token-[redacted]": ["[redacted]"] },
+            })),
+            output.fields.map(Value::Object)
+        );
+    }
+}
@@ -12,9 +12,10 @@ use axum::{
     response::IntoResponse,
 };
 use cyclone_core::{
-    ActionRunRequest, ActionRunResultSuccess, CycloneRequestable, LivenessStatus,
-    ManagementRequest, ManagementResultSuccess, Message, ReadinessStatus, ResolverFunctionRequest,
-    ResolverFunctionResultSuccess, SchemaVariantDefinitionRequest,
+    ActionRunRequest, ActionRunResultSuccess, CycloneRequestable, FunctionResult,
+    FunctionResultFailure, FunctionResultFailureError, FunctionResultFailureErrorKind,
+    LivenessStatus, ManagementRequest, ManagementResultSuccess, Message, ReadinessStatus,
+    ResolverFunctionRequest, ResolverFunctionResultSuccess, SchemaVariantDefinitionRequest,
     SchemaVariantDefinitionResultSuccess, ValidationRequest, ValidationResultSuccess,
 };
 use hyper::StatusCode;
@@ -24,12 +25,13 @@ use telemetry_http::ParentSpan;
 
 use super::extract::LimitRequestGuard;
 use crate::{
-    execution::{self, Execution},
+    execution::{self, Execution, ExecutionError},
     result::{
         LangServerActionRunResultSuccess, LangServerResolverFunctionResultSuccess,
         LangServerValidationResultSuccess,
     },
-    state::{LangServerChild, LangServerProcessTimeout, WatchKeepalive},
+    state::{LangServer, LangServerProcessTimeout, WatchKeepalive},
+    wasm::WasmRuntime,
     watch,
 };
 
@@ -97,7 +99,8 @@ pub async fn ws_execute_ping(
 pub async fn ws_execute_resolver(
     wsu: WebSocketUpgrade,
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
-    State(child): State<LangServerChild>,
+    State(lang_server): State<LangServer>,
+    State(wasm_runtime): State<WasmRuntime>,
     limit_request_guard: LimitRequestGuard,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
@@ -113,7 +116,8 @@ pub async fn ws_execute_resolver(
             lang_server_success,
             success,
             request_span.into_inner(),
-            child,
+            lang_server,
+            wasm_runtime,
         )
     })
 }
@@ -121,7 +125,8 @@ pub async fn ws_execute_resolver(
 pub async fn ws_execute_validation(
     wsu: WebSocketUpgrade,
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
-    State(child): State<LangServerChild>,
+    State(lang_server): State<LangServer>,
+    State(wasm_runtime): State<WasmRuntime>,
     limit_request_guard: LimitRequestGuard,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
@@ -137,7 +142,8 @@ pub async fn ws_execute_validation(
             lang_server_success,
             success,
             request_span.into_inner(),
-            child,
+            lang_server,
+            wasm_runtime,
         )
     })
 }
@@ -145,7 +151,8 @@ pub async fn ws_execute_validation(
 pub async fn ws_execute_action_run(
     wsu: WebSocketUpgrade,
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
-    State(child): State<LangServerChild>,
+    State(lang_server): State<LangServer>,
+    State(wasm_runtime): State<WasmRuntime>,
     limit_request_guard: LimitRequestGuard,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
@@ -161,7 +168,8 @@ pub async fn ws_execute_action_run(
             lang_server_success,
             success,
             request_span.into_inner(),
-            child,
+            lang_server,
+            wasm_runtime,
         )
     })
 }
@@ -169,7 +177,8 @@ pub async fn ws_execute_action_run(
 pub async fn ws_execute_schema_variant_definition(
     wsu: WebSocketUpgrade,
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
-    State(child): State<LangServerChild>,
+    State(lang_server): State<LangServer>,
+    State(wasm_runtime): State<WasmRuntime>,
     limit_request_guard: LimitRequestGuard,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
@@ -185,7 +194,8 @@ pub async fn ws_execute_schema_variant_definition(
             lang_server_success,
             success,
             request_span.into_inner(),
-            child,
+            lang_server,
+            wasm_runtime,
         )
     })
 }
@@ -193,7 +203,8 @@ pub async fn ws_execute_schema_variant_definition(
 pub async fn ws_execute_management(
     wsu: WebSocketUpgrade,
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
-    State(child): State<LangServerChild>,
+    State(lang_server): State<LangServer>,
+    State(wasm_runtime): State<WasmRuntime>,
     limit_request_guard: LimitRequestGuard,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
@@ -209,7 +220,8 @@ pub async fn ws_execute_management(
             lang_server_success,
             success,
             request_span.into_inner(),
-            child,
+            lang_server,
+            wasm_runtime,
         )
     })
 }
@@ -230,7 +242,8 @@ async fn handle_socket<Request, LangServerSuccess, Success>(
     _lang_server_success_marker: PhantomData<LangServerSuccess>,
     success_marker: PhantomData<Success>,
     request_span: Span,
-    child: LangServerChild,
+    lang_server: LangServer,
+    wasm_runtime: WasmRuntime,
 ) where
     Request: Serialize + DeserializeOwned + Unpin + fmt::Debug + CycloneRequestable,
     Success: Serialize + Unpin + fmt::Debug,
@@ -239,7 +252,10 @@ async fn handle_socket<Request, LangServerSuccess, Success>(
     let proto = {
         let execution: Execution<Request, LangServerSuccess, Success> =
             execution::new(lang_server_process_timeout);
-        match execution.start(child, &mut socket).await {
+        match execution
+            .start(lang_server, wasm_runtime, &mut socket)
+            .await
+        {
             Ok(started) => started,
             Err(err) => {
                 warn!(si.error.message = ?err, "failed to start protocol");
@@ -259,6 +275,58 @@ async fn handle_socket<Request, LangServerSuccess, Success>(
     };
     let proto = match proto.process(&mut socket).await {
         Ok(processed) => processed,
+        Err(ExecutionError::ChildTimeout {
+            execution_id,
+            timeout,
+        }) => {
+            warn!(
+                si.func_run.id = %execution_id,
+                ?timeout,
+                "function exceeded its timeout and was killed"
+            );
+            request_span.record_err(ExecutionError::ChildTimeout {
+                execution_id: execution_id.clone(),
+                timeout,
+            });
+            let error = FunctionResultFailureError {
+                kind: FunctionResultFailureErrorKind::Timeout,
+                message: format!("function timed out after {} seconds", timeout.as_secs()),
+            };
+            if let Err(err) = fail_with_result(socket, execution_id, error, success_marker).await {
+                warn!(
+                    error = ?err,
+                    kind = std::any::type_name::<Request>(),
+                    "failed to report function timeout",
+                );
+            };
+            return;
+        }
+        Err(ExecutionError::ResourceLimitExceeded {
+            execution_id,
+            limit,
+        }) => {
+            warn!(
+                si.func_run.id = %execution_id,
+                ?limit,
+                "function exceeded a resource limit of its cgroup"
+            );
+            request_span.record_err(ExecutionError::ResourceLimitExceeded {
+                execution_id: execution_id.clone(),
+                limit,
+            });
+            let error = FunctionResultFailureError {
+                kind: limit.kind(),
+                message: limit.message().to_owned(),
+            };
+            if let Err(err) = fail_with_result(socket, execution_id, error, success_marker).await {
+                warn!(
+                    error = ?err,
+                    kind = std::any::type_name::<Request>(),
+                    "failed to report function resource limit",
+                );
+            };
+            return;
+        }
         Err(err) => {
             warn!(si.error.message = ?err, "failed to process protocol");
             request_span.record_err(&err);
@@ -287,6 +355,28 @@ async fn handle_socket<Request, LangServerSuccess, Success>(
     request_span.record_ok();
 }
 
+/// Reports a failure as the result of the function, so that a timeout or an exceeded resource
+/// limit reaches the caller with its own [`FunctionResultFailureErrorKind`] rather than as a
+/// generic protocol failure.
+async fn fail_with_result<Success: Serialize>(
+    mut socket: WebSocket,
+    execution_id: String,
+    error: FunctionResultFailureError,
+    _success_marker: PhantomData<Success>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let result = Message::<Success>::Result(FunctionResult::Failure(FunctionResultFailure::new(
+        execution_id,
+        error,
+        crate::timestamp(),
+    )))
+    .serialize_to_string()?;
+    socket.send(ws::Message::Text(result)).await?;
+    let finish = Message::<Success>::Finish.serialize_to_string()?;
+    socket.send(ws::Message::Text(finish)).await?;
+    socket.close().await?;
+    Ok(())
+}
+
 async fn fail_to_process<Success: Serialize>(
     mut socket: WebSocket,
     message: impl Into<String>,
@@ -1,4 +1,6 @@
+mod cgroup;
 mod config;
+mod egress;
 mod execution;
 mod extract;
 mod handlers;
@@ -13,10 +15,14 @@ mod tower;
 mod uds;
 #[cfg(target_os = "linux")]
 mod vsock;
+mod warm;
+mod wasm;
 mod watch;
 
 pub use axum::extract::ws::Message as WebSocketMessage;
-pub use config::{Config, ConfigBuilder, ConfigError, IncomingStream};
+pub use config::{
+    Config, ConfigBuilder, ConfigError, IncomingStream, WarmWorkersConfig, WasmConfig,
+};
 #[cfg(target_os = "linux")]
 pub use process_gatherer::init;
 pub use server::{Runnable, Server, ShutdownSource};
@@ -221,6 +221,8 @@ async fn build_service(
         telemetry_level,
         config.lang_server_function_timeout(),
         config.lang_server_process_timeout(),
+        config.warm_workers().cloned(),
+        config.wasm().clone(),
     )
     .await?;
 
@@ -13,14 +13,20 @@ use tokio::{
     sync::{mpsc, Mutex},
 };
 
-use crate::execution::ExecutionError;
+use crate::{
+    config::{WarmWorkersConfig, WasmConfig},
+    execution::ExecutionError,
+    warm::WarmPool,
+    wasm::WasmRuntime,
+};
 type Result<T> = std::result::Result<T, ExecutionError>;
 
 #[derive(Clone, FromRef)]
 pub struct AppState {
-    child: LangServerChild,
+    lang_server: LangServer,
     lang_server_process_timeout: LangServerProcessTimeout,
     telemetry_level: TelemetryLevel,
+    wasm_runtime: WasmRuntime,
 }
 
 impl AppState {
@@ -29,7 +35,39 @@ impl AppState {
         telemetry_level: Box<dyn telemetry::TelemetryLevel>,
         lang_server_function_timeout: Option<usize>,
         lang_server_process_timeout: Option<u64>,
+        warm_workers: Option<WarmWorkersConfig>,
+        wasm: WasmConfig,
     ) -> Result<Self> {
+        let debug = telemetry_level.is_debug_or_lower().await;
+        let lang_server = match warm_workers {
+            Some(warm_workers) => LangServer::Warm(WarmPool::new(
+                warm_workers,
+                lang_server_path,
+                lang_server_function_timeout,
+                debug,
+            )?),
+            None => LangServer::Child(Self::spawn_child(
+                lang_server_path,
+                lang_server_function_timeout,
+                debug,
+            )?),
+        };
+
+        Ok(Self {
+            lang_server,
+            lang_server_process_timeout: LangServerProcessTimeout(Arc::new(
+                lang_server_process_timeout,
+            )),
+            telemetry_level: TelemetryLevel(Arc::new(telemetry_level)),
+            wasm_runtime: WasmRuntime::new(wasm)?,
+        })
+    }
+
+    fn spawn_child(
+        lang_server_path: impl Into<PathBuf> + std::convert::AsRef<std::ffi::OsStr>,
+        lang_server_function_timeout: Option<usize>,
+        debug: bool,
+    ) -> Result<LangServerChild> {
         let mut cmd = Command::new(&lang_server_path);
         cmd.stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -38,7 +76,7 @@ impl AppState {
         if let Some(timeout) = lang_server_function_timeout {
             cmd.arg("--timeout").arg(timeout.to_string());
         }
-        if telemetry_level.is_debug_or_lower().await {
+        if debug {
             cmd.env("SI_LANG_JS_LOG", "*");
         }
 
@@ -47,16 +85,19 @@ impl AppState {
             .spawn()
             .map_err(|err| ExecutionError::ChildSpawn(err, lang_server_path.into()))?;
 
-        Ok(Self {
-            child: LangServerChild(Arc::new(Mutex::new(child))),
-            lang_server_process_timeout: LangServerProcessTimeout(Arc::new(
-                lang_server_process_timeout,
-            )),
-            telemetry_level: TelemetryLevel(Arc::new(telemetry_level)),
-        })
+        Ok(LangServerChild(Arc::new(Mutex::new(child))))
     }
 }
 
+/// Where executions are sent to be run by the lang server.
+#[derive(Clone, Debug)]
+pub enum LangServer {
+    /// A lang server process spawned ahead of time, which serves a single execution.
+    Child(LangServerChild),
+    /// A pool of warm lang server processes, which each serve many executions.
+    Warm(WarmPool),
+}
+
 #[derive(Clone, Debug, FromRef)]
 pub struct LangServerPath(Arc<PathBuf>);
 
@@ -0,0 +1,544 @@
+//! Warm lang server workers.
+//!
+//! By default, a lang server process is spawned for a single request and exits once it is done,
+//! so every execution pays for the lang server starting up and loading its modules. In warm mode,
+//! a pool of long-lived lang server processes each serve many requests, one at a time, over the
+//! same JSON lines protocol. The lang server runs each function in a fresh V8 context and resets
+//! its own state between requests, but the process itself is shared, so this is only meant for
+//! trusted workloads.
+//!
+//! A worker is retired once it has served `recycle_after` requests, once it grows past the memory
+//! ceiling, or as soon as an execution does not finish cleanly.
+//!
+//! Functions run in workers of the lang server process, which share its stdout, so a function
+//! could write lines that look like protocol messages. Every request carries a fresh random
+//! token, which the lang server prefixes its own messages with and never hands to the function,
+//! and only lines carrying the token are read as messages.
+
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    process::Stdio,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use bytes_lines_codec::BytesLinesCodec;
+use futures::{SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use si_crypto::SensitiveStrings;
+use telemetry::prelude::*;
+use telemetry_utils::metric;
+use tokio::{
+    process::{Child, ChildStdin, ChildStdout, Command},
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time,
+};
+use tokio_serde::{formats::SymmetricalJson, SymmetricallyFramed};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::{
+    config::WarmWorkersConfig,
+    execution::{ExecutionError, TX_TIMEOUT_SECS},
+};
+
+type Result<T> = std::result::Result<T, ExecutionError>;
+
+type WorkerStdin =
+    SymmetricallyFramed<FramedWrite<ChildStdin, BytesLinesCodec>, Value, SymmetricalJson<Value>>;
+
+/// A pool of warm lang server processes, which hands out one worker per execution.
+#[derive(Clone, Debug)]
+pub struct WarmPool {
+    inner: Arc<WarmPoolInner>,
+}
+
+#[derive(Debug)]
+struct WarmPoolInner {
+    config: WarmWorkersConfig,
+    lang_server_path: PathBuf,
+    lang_server_function_timeout: Option<usize>,
+    debug: bool,
+    idle: Mutex<Vec<WarmWorker>>,
+    permits: Arc<Semaphore>,
+}
+
+impl WarmPool {
+    /// Creates a new [`WarmPool`] and spawns all of its workers up front, so that the first
+    /// requests don't pay for starting them.
+    pub fn new(
+        config: WarmWorkersConfig,
+        lang_server_path: impl Into<PathBuf>,
+        lang_server_function_timeout: Option<usize>,
+        debug: bool,
+    ) -> Result<Self> {
+        let pool = Self {
+            inner: Arc::new(WarmPoolInner {
+                permits: Arc::new(Semaphore::new(config.pool_size.max(1))),
+                config,
+                lang_server_path: lang_server_path.into(),
+                lang_server_function_timeout,
+                debug,
+                idle: Mutex::new(Vec::new()),
+            }),
+        };
+
+        let workers = (0..pool.inner.config.pool_size.max(1))
+            .map(|_| pool.spawn_worker())
+            .collect::<Result<Vec<_>>>()?;
+        pool.idle().extend(workers);
+
+        Ok(pool)
+    }
+
+    /// Waits for a worker to be free and checks it out for a single execution.
+    pub async fn checkout(&self) -> Result<WarmWorkerGuard> {
+        let permit = self
+            .inner
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| ExecutionError::WarmPoolClosed)?;
+
+        let worker = match self.idle().pop() {
+            Some(worker) => worker,
+            None => self.spawn_worker()?,
+        };
+
+        Ok(WarmWorkerGuard {
+            worker: Some(worker),
+            pool: self.clone(),
+            _permit: permit,
+        })
+    }
+
+    fn spawn_worker(&self) -> Result<WarmWorker> {
+        let mut cmd = Command::new(&self.inner.lang_server_path);
+        cmd.arg("--warm")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(timeout) = self.inner.lang_server_function_timeout {
+            cmd.arg("--timeout").arg(timeout.to_string());
+        }
+        if self.inner.debug {
+            cmd.env("SI_LANG_JS_LOG", "*");
+        }
+
+        debug!(cmd = ?cmd, "spawning warm worker process");
+        let mut child = cmd
+            .spawn()
+            .map_err(|err| ExecutionError::ChildSpawn(err, self.inner.lang_server_path.clone()))?;
+
+        let stdin = child.stdin.take().ok_or(ExecutionError::ChildIO("stdin"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or(ExecutionError::ChildIO("stdout"))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or(ExecutionError::ChildIO("stderr"))?;
+
+        // The worker outlives any one request and its stderr is read concurrently, so a line
+        // may only be read once the next request was sent. Redact it with the sensitive strings
+        // of every request the worker has served, rather than those of the current one.
+        let sensitive_strings = Arc::new(RwLock::new(SensitiveStrings::default()));
+        tokio::spawn(forward_stderr(
+            FramedRead::new(stderr, BytesLinesCodec::new()),
+            sensitive_strings.clone(),
+        ));
+
+        metric!(counter.cyclone.warm_workers = 1);
+
+        Ok(WarmWorker {
+            child,
+            stdin: SymmetricallyFramed::new(
+                FramedWrite::new(stdin, BytesLinesCodec::new()),
+                SymmetricalJson::default(),
+            ),
+            stdout: FramedRead::new(stdout, BytesLinesCodec::new()),
+            sensitive_strings,
+            protocol_token: String::new(),
+            executions: 0,
+        })
+    }
+
+    fn check_in(&self, worker: WarmWorker) {
+        if worker.executions >= self.inner.config.recycle_after {
+            debug!(
+                executions = worker.executions,
+                "recycling warm worker after reaching its execution limit"
+            );
+            worker.kill();
+            return;
+        }
+        if let (Some(limit), Some(used)) = (
+            self.inner.config.memory_limit_bytes,
+            worker.resident_memory_bytes(),
+        ) {
+            if used > limit {
+                debug!(used, limit, "recycling warm worker over its memory limit");
+                worker.kill();
+                return;
+            }
+        }
+
+        self.idle().push(worker);
+    }
+
+    fn idle(&self) -> std::sync::MutexGuard<'_, Vec<WarmWorker>> {
+        self.inner
+            .idle
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[derive(Debug)]
+struct WarmWorker {
+    child: Child,
+    stdin: WorkerStdin,
+    stdout: FramedRead<ChildStdout, BytesLinesCodec>,
+    sensitive_strings: Arc<RwLock<SensitiveStrings>>,
+    /// The token the lang server prefixes its messages for the current request with.
+    protocol_token: String,
+    executions: u32,
+}
+
+impl WarmWorker {
+    fn kill(mut self) {
+        if let Err(err) = self.child.start_kill() {
+            warn!(si.error.message = ?err, "failed to kill warm worker");
+        }
+        metric!(counter.cyclone.warm_workers = -1);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn resident_memory_bytes(&self) -> Option<u64> {
+        let pid = i32::try_from(self.child.id()?).ok()?;
+        let status = procfs::process::Process::new(pid).ok()?.status().ok()?;
+        status.vmrss.map(|kilobytes| kilobytes * 1024)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn resident_memory_bytes(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// A warm worker checked out for a single execution. If the execution does not call
+/// [`finished`](Self::finished), the worker may be in any state, so it is killed rather than
+/// returned to the pool.
+#[derive(Debug)]
+pub struct WarmWorkerGuard {
+    worker: Option<WarmWorker>,
+    pool: WarmPool,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl WarmWorkerGuard {
+    /// Sends a request to the worker, along with a fresh protocol token.
+    pub async fn send_request(
+        &mut self,
+        mut request: Value,
+        sensitive_strings: Arc<SensitiveStrings>,
+    ) -> Result<()> {
+        let worker = self.worker_mut()?;
+        worker
+            .sensitive_strings
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .extend(HashSet::from(SensitiveStrings::clone(&sensitive_strings)));
+        worker.executions += 1;
+
+        worker.protocol_token = format!("{:032x}", rand::random::<u128>());
+        if let Value::Object(fields) = &mut request {
+            fields.insert(
+                "protocolToken".to_string(),
+                Value::String(worker.protocol_token.clone()),
+            );
+        }
+
+        time::timeout(TX_TIMEOUT_SECS, worker.stdin.send(request))
+            .await
+            .map_err(ExecutionError::SendTimeout)?
+            .map_err(ExecutionError::ChildSendIO)
+    }
+
+    /// Reads the next message the worker wrote to stdout for the current request, or `None` if
+    /// it exited. Lines without the request's protocol token were written by the function itself
+    /// and are skipped.
+    pub async fn next_message<T: DeserializeOwned>(&mut self) -> Option<Result<T>> {
+        let worker = self.worker.as_mut()?;
+        loop {
+            let line = match worker.stdout.next().await? {
+                Ok(line) => line,
+                Err(err) => return Some(Err(ExecutionError::ChildRecvIO(err))),
+            };
+            match strip_protocol_token(&line, &worker.protocol_token) {
+                Some(message) => {
+                    return Some(
+                        serde_json::from_slice(message).map_err(ExecutionError::JSONDeserialize),
+                    );
+                }
+                None => warn!("skipping stdout line a function wrote outside of the protocol"),
+            }
+        }
+    }
+
+    /// Returns the worker to the pool once its execution is done, unless it is due to be recycled.
+    pub fn finished(mut self) {
+        if let Some(worker) = self.worker.take() {
+            self.pool.check_in(worker);
+        }
+    }
+
+    fn worker_mut(&mut self) -> Result<&mut WarmWorker> {
+        self.worker.as_mut().ok_or(ExecutionError::WarmWorkerExited)
+    }
+}
+
+impl Drop for WarmWorkerGuard {
+    fn drop(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            worker.kill();
+        }
+    }
+}
+
+/// Returns what follows the protocol token in a line, if the token is in it. A function may have
+/// written a partial line right before the message, so the token does not have to come first.
+fn strip_protocol_token<'a>(line: &'a [u8], token: &str) -> Option<&'a [u8]> {
+    let token = token.as_bytes();
+    if token.is_empty() {
+        return None;
+    }
+    line.windows(token.len())
+        .position(|window| window == token)
+        .map(|start| &line[start + token.len()..])
+}
+
+async fn forward_stderr(
+    mut stderr: FramedRead<tokio::process::ChildStderr, BytesLinesCodec>,
+    sensitive_strings: Arc<RwLock<SensitiveStrings>>,
+) {
+    while let Some(line) = stderr.next().await {
+        let line = match line {
+            Ok(line) => String::from_utf8_lossy(&line).into_owned(),
+            Err(err) => {
+                error!("Unable to collect stderr: {}", err);
+                return;
+            }
+        };
+        let line = sensitive_strings
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .redact(&line);
+
+        eprintln!("{line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{os::unix::fs::PermissionsExt, path::Path, time::Duration};
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// A stand-in lang server that answers every request with a stray line, as a function writing
+    /// to stdout would, followed by a result carrying the request's protocol token.
+    const FAKE_LANG_SERVER: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  token=$(printf '%s' "$line" | sed -n 's/.*"protocolToken":"\([0-9a-f]*\)".*/\1/p')
+  execution_id=$(printf '%s' "$line" | sed -n 's/.*"executionId":"\([^"]*\)".*/\1/p')
+  echo '{"protocol":"result","executionId":"not-from-the-lang-server"}'
+  printf 'partial line'
+  echo "${token}{\"protocol\":\"result\",\"executionId\":\"${execution_id}\"}"
+done
+"#;
+
+    struct FakeLangServer(PathBuf);
+
+    impl FakeLangServer {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "cyclone-warm-test-{:016x}.sh",
+                rand::random::<u64>()
+            ));
+            std::fs::write(&path, FAKE_LANG_SERVER).expect("failed to write fake lang server");
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+                .expect("failed to make fake lang server executable");
+            Self(path)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for FakeLangServer {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn pool(lang_server: &FakeLangServer, pool_size: usize, recycle_after: u32) -> WarmPool {
+        WarmPool::new(
+            WarmWorkersConfig {
+                pool_size,
+                recycle_after,
+                memory_limit_bytes: None,
+            },
+            lang_server.path(),
+            None,
+            false,
+        )
+        .expect("failed to create pool")
+    }
+
+    fn sensitive(value: &str) -> Arc<SensitiveStrings> {
+        let mut sensitive_strings = SensitiveStrings::default();
+        sensitive_strings.insert(value);
+        Arc::new(sensitive_strings)
+    }
+
+    async fn execute(worker: &mut WarmWorkerGuard, execution_id: &str) -> Value {
+        worker
+            .send_request(
+                json!({ "executionId": execution_id, "kind": "resolverfunction" }),
+                sensitive(execution_id),
+            )
+            .await
+            .expect("failed to send request");
+        worker
+            .next_message::<Value>()
+            .await
+            .expect("worker exited")
+            .expect("failed to read message")
+    }
+
+    #[test]
+    fn strips_protocol_tokens() {
+        assert_eq!(
+            Some(&b"{}"[..]),
+            strip_protocol_token(b"abc123{}", "abc123")
+        );
+        assert_eq!(
+            Some(&b"{}"[..]),
+            strip_protocol_token(b"partial lineabc123{}", "abc123")
+        );
+        assert_eq!(None, strip_protocol_token(b"{}", "abc123"));
+        assert_eq!(None, strip_protocol_token(b"{}", ""));
+    }
+
+    #[tokio::test]
+    async fn skips_lines_without_the_protocol_token() {
+        let lang_server = FakeLangServer::new();
+        let pool = pool(&lang_server, 1, 100);
+
+        let mut worker = pool.checkout().await.expect("failed to check out worker");
+        let message = execute(&mut worker, "first").await;
+
+        assert_eq!(
+            json!({ "protocol": "result", "executionId": "first" }),
+            message
+        );
+    }
+
+    #[tokio::test]
+    async fn reuses_workers_until_they_are_recycled() {
+        let lang_server = FakeLangServer::new();
+        let pool = pool(&lang_server, 1, 2);
+
+        let mut worker = pool.checkout().await.expect("failed to check out worker");
+        execute(&mut worker, "first").await;
+        let pid = worker.worker.as_ref().and_then(|worker| worker.child.id());
+        worker.finished();
+        assert_eq!(1, pool.idle().len());
+
+        let mut worker = pool.checkout().await.expect("failed to check out worker");
+        assert_eq!(
+            pid,
+            worker.worker.as_ref().and_then(|worker| worker.child.id())
+        );
+        assert_eq!(
+            json!({ "protocol": "result", "executionId": "second" }),
+            execute(&mut worker, "second").await
+        );
+        // The worker served its second execution, so it is retired rather than checked in
+        worker.finished();
+        assert!(pool.idle().is_empty());
+
+        let worker = pool.checkout().await.expect("failed to check out worker");
+        assert_ne!(
+            pid,
+            worker.worker.as_ref().and_then(|worker| worker.child.id())
+        );
+    }
+
+    #[tokio::test]
+    async fn kills_workers_that_do_not_finish() {
+        let lang_server = FakeLangServer::new();
+        let pool = pool(&lang_server, 1, 100);
+
+        let mut worker = pool.checkout().await.expect("failed to check out worker");
+        worker
+            .send_request(
+                json!({ "executionId": "unfinished" }),
+                sensitive("unfinished"),
+            )
+            .await
+            .expect("failed to send request");
+        drop(worker);
+
+        assert!(pool.idle().is_empty());
+    }
+
+    #[tokio::test]
+    async fn checkouts_wait_for_a_free_worker() {
+        let lang_server = FakeLangServer::new();
+        let pool = pool(&lang_server, 1, 100);
+
+        let worker = pool.checkout().await.expect("failed to check out worker");
+        assert!(
+            time::timeout(Duration::from_millis(100), pool.checkout())
+                .await
+                .is_err(),
+            "a second worker was checked out of a pool of one"
+        );
+
+        worker.finished();
+        time::timeout(Duration::from_secs(5), pool.checkout())
+            .await
+            .expect("worker was not released")
+            .expect("failed to check out worker");
+    }
+
+    #[tokio::test]
+    async fn redacts_stderr_with_every_request_served() {
+        let lang_server = FakeLangServer::new();
+        let pool = pool(&lang_server, 1, 100);
+
+        let mut worker = pool.checkout().await.expect("failed to check out worker");
+        execute(&mut worker, "first-secret").await;
+        execute(&mut worker, "second-secret").await;
+
+        let sensitive_strings = worker
+            .worker
+            .as_ref()
+            .expect("worker is checked out")
+            .sensitive_strings
+            .read()
+            .expect("lock is poisoned")
+            .clone();
+        assert_eq!(
+            "[redacted] [redacted]",
+            sensitive_strings.redact("first-secret second-secret")
+        );
+    }
+}
@@ -0,0 +1,647 @@
+//! WebAssembly funcs.
+//!
+//! A request for the [`FunctionRuntime::Wasm`](cyclone_core::FunctionRuntime) runtime carries a
+//! WebAssembly component targeting WASI preview 2 instead of JavaScript, which cyclone runs itself
+//! with wasmtime rather than handing it to the lang server. Every execution gets its own store, so
+//! nothing is shared between executions, and a component can't reach anything outside of itself
+//! except through the host interface described in `wit/function.wit`: logging, clocks and
+//! randomness, and HTTP requests to the hosts in the [`WasmConfig`]'s allowlist. When the request
+//! carries an [`EgressPolicy`], HTTP requests also have to be allowed by it, the same as a
+//! JavaScript func's connections through the egress proxy, and a func that was denied fails with
+//! [`EgressDenied`](FunctionResultFailureErrorKind::EgressDenied).
+//!
+//! A running component reports back with the same messages as the lang server does, so the rest
+//! of an execution (redacting secrets, timeouts, the websocket protocol) works the same for both.
+//!
+//! Compiling a component takes far longer than running most funcs, so compiled components are
+//! kept, by the hash of their code, for the next execution of the same func.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose, Engine as _};
+use cyclone_core::{EgressPolicy, FunctionResultFailureErrorKind, ResourceStatus};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use telemetry::prelude::*;
+use tokio::{sync::mpsc, task::JoinHandle, time};
+use wasmtime::{
+    component::{Component, Linker, ResourceTable},
+    Config, Engine, ResourceLimiter, Store,
+};
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiView};
+use wasmtime_wasi_http::{
+    bindings::http::types::ErrorCode,
+    body::HyperOutgoingBody,
+    types::{default_send_request, HostFutureIncomingResponse, OutgoingRequestConfig},
+    HttpResult, WasiHttpCtx, WasiHttpView,
+};
+
+use crate::{config::WasmConfig, execution::ExecutionError};
+
+type Result<T> = std::result::Result<T, ExecutionError>;
+
+wasmtime::component::bindgen!({
+    path: "wit",
+    world: "function",
+    async: {
+        only_imports: [],
+    },
+});
+
+/// How often running components give control back to the async runtime, which is what lets an
+/// execution that has timed out be stopped.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// How many compiled components to keep. Funcs are mostly small, and a cyclone instance usually
+/// only runs the funcs of a handful of workspaces.
+const COMPONENT_CACHE_SIZE: usize = 64;
+
+/// Compiles and runs the components of WebAssembly funcs.
+#[derive(Clone)]
+pub struct WasmRuntime {
+    inner: Arc<WasmRuntimeInner>,
+}
+
+struct WasmRuntimeInner {
+    engine: Engine,
+    linker: Linker<WasmState>,
+    components: Mutex<ComponentCache>,
+    http_allowlist: Arc<[String]>,
+    memory_limit_bytes: usize,
+}
+
+impl fmt::Debug for WasmRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmRuntime")
+            .field("http_allowlist", &self.inner.http_allowlist)
+            .field("memory_limit_bytes", &self.inner.memory_limit_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasmRuntime {
+    /// Creates a new [`WasmRuntime`]. Must be called from within a tokio runtime.
+    pub fn new(config: WasmConfig) -> Result<Self> {
+        let mut engine_config = Config::new();
+        engine_config
+            .async_support(true)
+            .wasm_component_model(true)
+            .epoch_interruption(true);
+        let engine = Engine::new(&engine_config).map_err(ExecutionError::wasm_runtime)?;
+
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_async(&mut linker).map_err(ExecutionError::wasm_runtime)?;
+        wasmtime_wasi_http::add_only_http_to_linker_async(&mut linker)
+            .map_err(ExecutionError::wasm_runtime)?;
+        Function::add_to_linker(&mut linker, |state: &mut WasmState| state)
+            .map_err(ExecutionError::wasm_runtime)?;
+
+        let ticker = engine.weak();
+        tokio::spawn(async move {
+            let mut interval = time::interval(EPOCH_TICK);
+            loop {
+                interval.tick().await;
+                match ticker.upgrade() {
+                    Some(engine) => engine.increment_epoch(),
+                    None => return,
+                }
+            }
+        });
+
+        Ok(Self {
+            inner: Arc::new(WasmRuntimeInner {
+                engine,
+                linker,
+                components: Mutex::new(ComponentCache::default()),
+                http_allowlist: config.http_allowlist.into(),
+                memory_limit_bytes: config.memory_limit_bytes,
+            }),
+        })
+    }
+
+    /// Starts running a request's component in the background.
+    ///
+    /// The request is the one the lang server would have been sent, `kind` and all. Whatever
+    /// goes wrong from here on, including the component failing to compile, is reported as the
+    /// result of the function.
+    pub fn start(
+        &self,
+        execution_id: String,
+        mut request: Value,
+        egress_policy: Option<EgressPolicy>,
+    ) -> WasmExecution {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let runtime = self.clone();
+
+        let task = tokio::spawn(async move {
+            let result = runtime
+                .run(&execution_id, &mut request, egress_policy, tx.clone())
+                .await;
+            let message = match result {
+                Ok(success) => success,
+                Err(failure) => failure.into_message(&execution_id),
+            };
+            // The execution is gone if nobody is listening anymore
+            let _ = tx.send(message);
+        });
+
+        WasmExecution { rx, task }
+    }
+
+    async fn run(
+        &self,
+        execution_id: &str,
+        request: &mut Value,
+        egress_policy: Option<EgressPolicy>,
+        output_tx: mpsc::UnboundedSender<Value>,
+    ) -> std::result::Result<Value, WasmFailure> {
+        let kind = request
+            .get("kind")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        if !matches!(kind.as_str(), "resolverfunction" | "actionRun") {
+            return Err(WasmFailure::new(
+                FunctionResultFailureErrorKind::VeritechServer,
+                format!("{kind} functions can't be WebAssembly components"),
+            ));
+        }
+
+        // The component is the code, so there's no need to hand it a copy of itself
+        let code_base64 = request
+            .as_object_mut()
+            .and_then(|fields| {
+                fields.remove("before");
+                fields.remove("codeBase64")
+            })
+            .and_then(|code| code.as_str().map(ToOwned::to_owned))
+            .unwrap_or_default();
+        let code = general_purpose::STANDARD_NO_PAD
+            .decode(code_base64.trim_end_matches('='))
+            .map_err(|err| {
+                WasmFailure::new(
+                    FunctionResultFailureErrorKind::VeritechServer,
+                    format!("function code is not valid base64: {err}"),
+                )
+            })?;
+
+        let component = self.component(code).await?;
+
+        let egress_denied = Arc::new(Mutex::new(Vec::new()));
+        let mut store = Store::new(
+            &self.inner.engine,
+            WasmState {
+                wasi: WasiCtxBuilder::new().build(),
+                http: WasiHttpCtx::new(),
+                table: ResourceTable::new(),
+                limits: WasmLimits {
+                    memory_limit_bytes: self.inner.memory_limit_bytes,
+                    memory_exceeded: false,
+                },
+                http_allowlist: self.inner.http_allowlist.clone(),
+                egress_policy,
+                egress_denied: egress_denied.clone(),
+                execution_id: execution_id.to_owned(),
+                output_tx,
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        store.set_epoch_deadline(1);
+        store.epoch_deadline_async_yield_and_update(1);
+
+        let output =
+            match Function::instantiate_async(&mut store, &component, &self.inner.linker).await {
+                Ok(function) => {
+                    function
+                        .call_run(&mut store, &kind, &request.to_string())
+                        .await
+                }
+                Err(err) if !store.data().limits.memory_exceeded => {
+                    return Err(WasmFailure::user("LinkError", err));
+                }
+                Err(err) => Err(err),
+            };
+        if store.data().limits.memory_exceeded {
+            return Err(WasmFailure::new(
+                FunctionResultFailureErrorKind::MemoryLimitExceeded,
+                format!(
+                    "function tried to use more than {} bytes of memory",
+                    self.inner.memory_limit_bytes
+                ),
+            ));
+        }
+
+        // Even a func that carried on without the connection fails, since what it did without it
+        // can't be trusted
+        let denied = match egress_denied.lock() {
+            Ok(denied) => denied.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        if !denied.is_empty() {
+            return Err(WasmFailure::new(
+                FunctionResultFailureErrorKind::EgressDenied,
+                format!(
+                    "function tried to reach {}, which the workspace's egress policy does not allow",
+                    denied.join(", ")
+                ),
+            ));
+        }
+
+        let output = output
+            .map_err(|err| WasmFailure::user("Trap", err))?
+            .map_err(|message| WasmFailure::user("Error", message))?;
+
+        let value: Value = serde_json::from_str(&output).map_err(|err| {
+            WasmFailure::new(
+                FunctionResultFailureErrorKind::InvalidReturnType,
+                format!("function returned invalid JSON: {err}"),
+            )
+        })?;
+
+        match kind.as_str() {
+            "actionRun" => action_run_success(execution_id, value),
+            _ => Ok(json!({
+                "protocol": "result",
+                "status": "success",
+                "executionId": execution_id,
+                "data": value,
+                "unset": false,
+            })),
+        }
+    }
+}
+
+impl WasmRuntime {
+    /// Returns the compiled component for the code, compiling it if it isn't cached yet.
+    async fn component(&self, code: Vec<u8>) -> std::result::Result<Component, WasmFailure> {
+        let hash = blake3::hash(&code);
+        if let Some(component) = self.components().get(&hash) {
+            return Ok(component);
+        }
+
+        // Compiling is cpu bound, so keep it off of the threads serving websockets
+        let engine = self.inner.engine.clone();
+        let component = tokio::task::spawn_blocking(move || Component::new(&engine, code))
+            .await
+            .map_err(|err| WasmFailure::new(FunctionResultFailureErrorKind::VeritechServer, err))?
+            .map_err(|err| WasmFailure::user("CompileError", err))?;
+        self.components().insert(hash, component.clone());
+
+        Ok(component)
+    }
+
+    fn components(&self) -> std::sync::MutexGuard<'_, ComponentCache> {
+        self.inner
+            .components
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Compiled components by the hash of their code, forgetting the oldest once it is full.
+#[derive(Default)]
+struct ComponentCache {
+    components: HashMap<blake3::Hash, Component>,
+    order: VecDeque<blake3::Hash>,
+}
+
+impl ComponentCache {
+    fn get(&self, hash: &blake3::Hash) -> Option<Component> {
+        self.components.get(hash).cloned()
+    }
+
+    fn insert(&mut self, hash: blake3::Hash, component: Component) {
+        if self.components.insert(hash, component).is_some() {
+            return;
+        }
+        self.order.push_back(hash);
+        while self.order.len() > COMPONENT_CACHE_SIZE {
+            if let Some(oldest) = self.order.pop_front() {
+                self.components.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Limits how much memory a single execution's component may grow to, remembering whether it
+/// tried to go past the limit so that the failure can be reported as such rather than as a trap.
+struct WasmLimits {
+    memory_limit_bytes: usize,
+    memory_exceeded: bool,
+}
+
+impl ResourceLimiter for WasmLimits {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        if desired > self.memory_limit_bytes {
+            self.memory_exceeded = true;
+            return Err(wasmtime::Error::msg(format!(
+                "memory limit of {} bytes exceeded",
+                self.memory_limit_bytes
+            )));
+        }
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        Ok(true)
+    }
+}
+
+/// A component running in the background. Dropping it stops the component.
+#[derive(Debug)]
+pub struct WasmExecution {
+    rx: mpsc::UnboundedReceiver<Value>,
+    task: JoinHandle<()>,
+}
+
+impl WasmExecution {
+    /// Reads the next message of the execution, or `None` if it ended without a result.
+    pub async fn next_message<T: DeserializeOwned>(&mut self) -> Option<Result<T>> {
+        let message = self.rx.recv().await?;
+        Some(serde_json::from_value(message).map_err(ExecutionError::JSONDeserialize))
+    }
+}
+
+impl Drop for WasmExecution {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Everything a single execution's component can reach.
+struct WasmState {
+    wasi: WasiCtx,
+    http: WasiHttpCtx,
+    table: ResourceTable,
+    limits: WasmLimits,
+    http_allowlist: Arc<[String]>,
+    egress_policy: Option<EgressPolicy>,
+    /// The destinations the workspace's egress policy denied, as `host:port`.
+    egress_denied: Arc<Mutex<Vec<String>>>,
+    execution_id: String,
+    output_tx: mpsc::UnboundedSender<Value>,
+}
+
+impl WasmState {
+    fn output(&self, level: LogLevel, message: String) {
+        let (stream, level) = match level {
+            LogLevel::Debug => ("stdout", "debug"),
+            LogLevel::Info => ("stdout", "info"),
+            LogLevel::Warn => ("stderr", "warn"),
+            LogLevel::Error => ("stderr", "error"),
+        };
+        // Output is redacted along with everything else the execution sends
+        let _ = self.output_tx.send(json!({
+            "protocol": "output",
+            "executionId": self.execution_id,
+            "stream": stream,
+            "level": level,
+            "group": "log",
+            "message": message,
+        }));
+    }
+}
+
+impl FunctionImports for WasmState {
+    fn log(&mut self, level: LogLevel, message: String) {
+        self.output(level, message);
+    }
+}
+
+impl WasiView for WasmState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+impl WasiHttpView for WasmState {
+    fn ctx(&mut self) -> &mut WasiHttpCtx {
+        &mut self.http
+    }
+
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn send_request(
+        &mut self,
+        request: http_1::Request<HyperOutgoingBody>,
+        config: OutgoingRequestConfig,
+    ) -> HttpResult<HostFutureIncomingResponse> {
+        let host = request
+            .uri()
+            .host()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if !is_allowed(&self.http_allowlist, &host) {
+            debug!(
+                si.func_run.id = %self.execution_id,
+                host,
+                "denied http request to a host outside of the allowlist"
+            );
+            self.output(
+                LogLevel::Error,
+                format!("http request to {host} denied: host is not allowed"),
+            );
+            return Err(ErrorCode::HttpRequestDenied.into());
+        }
+
+        // Hosts the policy only allows through a CIDR block are denied, since the address the
+        // request ends up connecting to isn't known here
+        if let Some(policy) = &self.egress_policy {
+            if !policy.allows_host(&host) {
+                let port =
+                    request
+                        .uri()
+                        .port_u16()
+                        .unwrap_or(if config.use_tls { 443 } else { 80 });
+                let destination = format!("{host}:{port}");
+                info!(
+                    si.func_run.id = %self.execution_id,
+                    %destination,
+                    "denied http request by the workspace's egress policy"
+                );
+                let _ = self.output_tx.send(json!({
+                    "protocol": "output",
+                    "executionId": self.execution_id,
+                    "stream": "stderr",
+                    "level": "error",
+                    "group": "egress",
+                    "message": format!(
+                        "Connection to {destination} denied by the workspace's egress policy"
+                    ),
+                    "fields": { "destination": destination },
+                }));
+                match self.egress_denied.lock() {
+                    Ok(mut denied) => denied.push(destination),
+                    Err(poisoned) => poisoned.into_inner().push(destination),
+                }
+                return Err(ErrorCode::HttpRequestDenied.into());
+            }
+        }
+
+        Ok(default_send_request(request, config))
+    }
+}
+
+/// Whether the allowlist covers the (lowercase) host.
+fn is_allowed(allowlist: &[String], host: &str) -> bool {
+    !host.is_empty()
+        && allowlist.iter().any(|allowed| {
+            let allowed = allowed.to_ascii_lowercase();
+            match allowed.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|subdomain| subdomain.ends_with('.')),
+                None => allowed == host,
+            }
+        })
+}
+
+/// Turns what an action returned into the lang server's result, which calls the action's
+/// `status` its `health`.
+fn action_run_success(execution_id: &str, value: Value) -> std::result::Result<Value, WasmFailure> {
+    let Value::Object(mut fields) = value else {
+        return Err(WasmFailure::new(
+            FunctionResultFailureErrorKind::InvalidReturnType,
+            "action functions must return an object",
+        ));
+    };
+    let health = fields
+        .remove("status")
+        .filter(|status| serde_json::from_value::<ResourceStatus>(status.clone()).is_ok())
+        .ok_or_else(|| {
+            WasmFailure::new(
+                FunctionResultFailureErrorKind::ActionFieldWrongType,
+                "action functions must return a status of \"ok\", \"warning\", or \"error\"",
+            )
+        })?;
+
+    fields.insert("health".to_string(), health);
+    fields.insert("executionId".to_string(), execution_id.into());
+    fields.insert("protocol".to_string(), "result".into());
+    fields.insert("status".to_string(), "success".into());
+    Ok(Value::Object(fields))
+}
+
+/// Why a component didn't produce a result.
+struct WasmFailure {
+    kind: FunctionResultFailureErrorKind,
+    message: String,
+}
+
+impl WasmFailure {
+    fn new(kind: FunctionResultFailureErrorKind, message: impl fmt::Display) -> Self {
+        Self {
+            kind,
+            message: message.to_string(),
+        }
+    }
+
+    /// A failure of the func's own making, named like the error a JavaScript func would throw.
+    fn user(name: &str, message: impl fmt::Display) -> Self {
+        Self {
+            kind: FunctionResultFailureErrorKind::UserCodeException(name.to_owned()),
+            // Alternate formatting includes the causes of wasmtime's errors, such as the trap
+            message: format!("{message:#}"),
+        }
+    }
+
+    fn into_message(self, execution_id: &str) -> Value {
+        json!({
+            "protocol": "result",
+            "status": "failure",
+            "executionId": execution_id,
+            "error": {
+                "kind": self.kind,
+                "message": self.message,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowlist(hosts: &[&str]) -> Vec<String> {
+        hosts.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn allows_exact_hosts() {
+        let allowlist = allowlist(&["api.example.com"]);
+
+        assert!(is_allowed(&allowlist, "api.example.com"));
+        assert!(!is_allowed(&allowlist, "example.com"));
+        assert!(!is_allowed(&allowlist, "www.api.example.com"));
+    }
+
+    #[test]
+    fn allows_subdomains_of_wildcards() {
+        let allowlist = allowlist(&["*.example.com"]);
+
+        assert!(is_allowed(&allowlist, "api.example.com"));
+        assert!(is_allowed(&allowlist, "a.b.example.com"));
+        assert!(!is_allowed(&allowlist, "example.com"));
+        assert!(!is_allowed(&allowlist, "badexample.com"));
+    }
+
+    #[test]
+    fn ignores_case_of_allowlist() {
+        assert!(is_allowed(
+            &allowlist(&["API.Example.com"]),
+            "api.example.com"
+        ));
+        assert!(is_allowed(
+            &allowlist(&["*.EXAMPLE.COM"]),
+            "api.example.com"
+        ));
+    }
+
+    #[test]
+    fn rejects_empty_host() {
+        assert!(!is_allowed(&allowlist(&[""]), ""));
+        assert!(!is_allowed(&allowlist(&["*."]), ""));
+    }
+
+    #[test]
+    fn component_cache_forgets_oldest() {
+        // The smallest valid component: the preamble and nothing else
+        let component = Component::new(&Engine::default(), b"\0asm\x0d\0\x01\0")
+            .expect("failed to compile component");
+        let hashes: Vec<_> = (0..=COMPONENT_CACHE_SIZE)
+            .map(|i| blake3::hash(&i.to_le_bytes()))
+            .collect();
+
+        let mut cache = ComponentCache::default();
+        for hash in &hashes {
+            cache.insert(*hash, component.clone());
+        }
+        // Inserting what is already cached doesn't push anything out
+        cache.insert(hashes[COMPONENT_CACHE_SIZE], component.clone());
+
+        assert!(cache.get(&hashes[0]).is_none());
+        assert!(hashes[1..].iter().all(|hash| cache.get(hash).is_some()));
+        assert_eq!(COMPONENT_CACHE_SIZE, cache.order.len());
+    }
+}
@@ -1,5 +1,6 @@
 //! This module contains helpers for use when authoring dal integration tests.
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use audit_database::{AuditDatabaseContext, AuditLogRow};
@@ -15,6 +16,7 @@ use dal::{
 use itertools::Itertools;
 use names::{Generator, Name};
 use si_data_nats::async_nats::jetstream::stream::Stream;
+use si_events::{FuncRun, FuncRunId, FuncRunState};
 use tokio::time::Instant;
 
 mod change_set;
@@ -462,3 +464,34 @@ pub async fn list_audit_logs_until_expected_number_of_rows(
         "hit timeout before audit logs query returns expected number of rows (expected: {expected_number_of_rows}, actual: {actual_number_of_rows})"
     ))
 }
+
+/// Retries reading the [`FuncRun`] until it has succeeded, since func runs are recorded as
+/// veritech reports back.
+pub async fn wait_for_func_run_with_success_state(
+    ctx: &DalContext,
+    func_run_id: FuncRunId,
+) -> Result<FuncRun> {
+    let timeout = Duration::from_secs(15);
+    let interval = Duration::from_millis(100);
+
+    let start = Instant::now();
+    let mut state = None;
+
+    while start.elapsed() < timeout {
+        let func_run = ctx
+            .layer_db()
+            .func_run()
+            .read(func_run_id)
+            .await?
+            .ok_or(eyre!("func run not found: {func_run_id}"))?;
+        if func_run.state() == FuncRunState::Success {
+            return Ok(Arc::unwrap_or_clone(func_run));
+        }
+        state = Some(func_run.state());
+        tokio::time::sleep(interval).await;
+    }
+
+    Err(eyre!(
+        "hit timeout before func run succeeded (func run: {func_run_id}, state: {state:?})"
+    ))
+}
@@ -0,0 +1,340 @@
+//! An in-process stand-in for veritech, for tests that exercise the dal without running functions
+//! in cyclone.
+//!
+//! Intrinsic functions never reach veritech, as the dal evaluates them itself. Of the rest, the
+//! stand-in answers:
+//!
+//! * requests for a function with a [`CannedResponse`], keyed by the function's name or code hash
+//! * attribute functions whose handler does nothing but return a JSON literal or (part of) its
+//!   input, such as `function main(input) { return input.name; }`, by evaluating them natively
+//! * validations, which always pass unless given a [`CannedResponse`]
+//!
+//! Any other request fails, pointing at the function it was for. Every request is recorded, so
+//! that tests can assert on what the dal asked for.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine};
+use dal::{layer_db_types::ContentTypes, DalContext, Func};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use si_events::{CasValue, ContentHash};
+use tokio::sync::mpsc;
+use veritech_client::{
+    ActionRunRequest, ActionRunResultSuccess, ClientError, ClientResult, FunctionExecutor,
+    FunctionResult, FunctionResultFailure, FunctionResultFailureError,
+    FunctionResultFailureErrorKind, KillExecutionRequest, ManagementRequest,
+    ManagementResultSuccess, OutputStream, ResolverFunctionRequest, ResolverFunctionResultSuccess,
+    SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess, ValidationRequest,
+    ValidationResultSuccess,
+};
+
+use crate::{eyre, Result};
+
+/// A [`FunctionExecutor`] that answers requests in-process instead of sending them to veritech.
+///
+/// Clones share their canned responses and recorded requests, so a test can hold onto one while
+/// the dal uses another. Use it with `#[dal_test::test(in_process_veritech)]`, which hands it to
+/// any test argument of this type.
+#[derive(Clone, Debug, Default)]
+pub struct InProcessVeritech {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    responses: HashMap<ContentHash, CannedResponse>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// What a request for a function should be answered with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CannedResponse {
+    /// Fails the function with this error.
+    Failure {
+        /// The kind of failure.
+        kind: FunctionResultFailureErrorKind,
+        /// The failure's message.
+        message: String,
+    },
+    /// Succeeds with this result, given as the camelCased fields of the request's success type
+    /// except for `executionId`, which is filled in from the request.
+    Success(Value),
+}
+
+impl CannedResponse {
+    /// Succeeds an attribute, qualification, or other resolver function with this value.
+    pub fn resolver_value(data: Value) -> Self {
+        Self::Success(json!({ "data": data, "unset": false, "timestamp": 0 }))
+    }
+
+    /// Succeeds an action with an `ok` status and this resource payload.
+    pub fn action_ok(payload: Value) -> Self {
+        Self::Success(json!({
+            "resourceId": null,
+            "payload": payload,
+            "status": "ok",
+            "message": null,
+            "error": null,
+        }))
+    }
+
+    /// Fails the function as if it had thrown an error with this message.
+    pub fn exception(message: impl Into<String>) -> Self {
+        Self::Failure {
+            kind: FunctionResultFailureErrorKind::UserCodeException("Error".to_string()),
+            message: message.into(),
+        }
+    }
+}
+
+/// A request that the dal sent to an [`InProcessVeritech`].
+#[remain::sorted]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecordedRequest {
+    /// A request to run an action function.
+    ActionRun(ActionRunRequest),
+    /// A request to kill a running function.
+    KillExecution(KillExecutionRequest),
+    /// A request to run a management function.
+    Management(ManagementRequest),
+    /// A request to run an attribute, qualification, or other resolver function.
+    ResolverFunction(ResolverFunctionRequest),
+    /// A request to run an asset definition function.
+    SchemaVariantDefinition(SchemaVariantDefinitionRequest),
+    /// A request to validate a value.
+    Validation(ValidationRequest),
+}
+
+impl InProcessVeritech {
+    /// Creates a stand-in without any canned responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers requests for the [`Func`] with this name with `response`.
+    ///
+    /// Responses are matched by code, so this needs to be called again if the func's code changes.
+    pub async fn respond_to_func(
+        &self,
+        ctx: &DalContext,
+        func_name: impl AsRef<str>,
+        response: CannedResponse,
+    ) -> Result<()> {
+        let func_name = func_name.as_ref();
+        let func_id = Func::find_id_by_name(ctx, func_name)
+            .await?
+            .ok_or_else(|| eyre!("no func named {func_name}"))?;
+        let func = Func::get_by_id(ctx, func_id).await?;
+
+        self.respond_to_code_hash(func.code_blake3, response);
+        Ok(())
+    }
+
+    /// Answers requests for functions whose code hashes to `code_hash` (see
+    /// [`Func::code_blake3`]) with `response`.
+    pub fn respond_to_code_hash(&self, code_hash: ContentHash, response: CannedResponse) {
+        self.lock().responses.insert(code_hash, response);
+    }
+
+    /// Returns every request received so far, in the order they arrived.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // A test that panics while holding the lock has already failed
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn record(&self, request: RecordedRequest) {
+        self.lock().requests.push(request);
+    }
+
+    fn respond<T: DeserializeOwned>(
+        &self,
+        execution_id: &str,
+        code_base64: &str,
+        evaluate_natively: impl FnOnce() -> Option<Value>,
+    ) -> ClientResult<FunctionResult<T>> {
+        let code_hash = code_hash(code_base64)?;
+        let canned = self.lock().responses.get(&code_hash).cloned();
+
+        match canned.or_else(|| evaluate_natively().map(CannedResponse::Success)) {
+            Some(CannedResponse::Success(body)) => success(execution_id, body),
+            Some(CannedResponse::Failure { kind, message }) => {
+                Ok(failure(execution_id, kind, message))
+            }
+            None => Ok(failure(
+                execution_id,
+                FunctionResultFailureErrorKind::VeritechServer,
+                format!(
+                    "no canned response for function with code hash {code_hash} and it is too \
+                    complex to evaluate in-process"
+                ),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl FunctionExecutor for InProcessVeritech {
+    async fn execute_action_run(
+        &self,
+        _output_tx: mpsc::Sender<OutputStream>,
+        request: &ActionRunRequest,
+        _workspace_id: &str,
+        _change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ActionRunResultSuccess>> {
+        self.record(RecordedRequest::ActionRun(request.clone()));
+        self.respond(&request.execution_id, &request.code_base64, || None)
+    }
+
+    async fn execute_resolver_function(
+        &self,
+        _output_tx: mpsc::Sender<OutputStream>,
+        request: &ResolverFunctionRequest,
+        _workspace_id: &str,
+        _change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ResolverFunctionResultSuccess>> {
+        self.record(RecordedRequest::ResolverFunction(request.clone()));
+        self.respond(&request.execution_id, &request.code_base64, || {
+            evaluate(
+                &request.code_base64,
+                &request.handler,
+                &request.component.data.properties,
+            )
+            .map(|data| json!({ "data": data, "unset": false, "timestamp": timestamp() }))
+        })
+    }
+
+    async fn execute_schema_variant_definition(
+        &self,
+        _output_tx: mpsc::Sender<OutputStream>,
+        request: &SchemaVariantDefinitionRequest,
+        _workspace_id: &str,
+        _change_set_id: &str,
+    ) -> ClientResult<FunctionResult<SchemaVariantDefinitionResultSuccess>> {
+        self.record(RecordedRequest::SchemaVariantDefinition(request.clone()));
+        self.respond(&request.execution_id, &request.code_base64, || None)
+    }
+
+    async fn execute_validation(
+        &self,
+        _output_tx: mpsc::Sender<OutputStream>,
+        request: &ValidationRequest,
+        _workspace_id: &str,
+        _change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ValidationResultSuccess>> {
+        self.record(RecordedRequest::Validation(request.clone()));
+        self.respond(&request.execution_id, &request.code_base64, || {
+            Some(json!({ "error": null }))
+        })
+    }
+
+    async fn execute_management(
+        &self,
+        _output_tx: mpsc::Sender<OutputStream>,
+        request: &ManagementRequest,
+        _workspace_id: &str,
+        _change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ManagementResultSuccess>> {
+        self.record(RecordedRequest::Management(request.clone()));
+        self.respond(&request.execution_id, &request.code_base64, || None)
+    }
+
+    async fn kill_execution(
+        &self,
+        request: &KillExecutionRequest,
+    ) -> ClientResult<FunctionResult<()>> {
+        self.record(RecordedRequest::KillExecution(request.clone()));
+        Ok(FunctionResult::Success(()))
+    }
+}
+
+/// Hashes code the same way as [`Func::code_blake3`], i.e. as the content store address of the
+/// base64 encoded code.
+fn code_hash(code_base64: &str) -> ClientResult<ContentHash> {
+    let value: ContentTypes = CasValue::from(Value::String(code_base64.to_owned())).into();
+    let (bytes, _) = si_layer_cache::db::serialize::to_vec(&value)
+        .map_err(|err| ClientError::Transport(Box::new(err)))?;
+
+    Ok(ContentHash::new(&bytes))
+}
+
+fn success<T: DeserializeOwned>(
+    execution_id: &str,
+    mut body: Value,
+) -> ClientResult<FunctionResult<T>> {
+    if let Value::Object(fields) = &mut body {
+        fields.insert("executionId".to_string(), execution_id.into());
+    }
+
+    serde_json::from_value(body)
+        .map(FunctionResult::Success)
+        .map_err(ClientError::JSONSerialize)
+}
+
+fn failure<T>(
+    execution_id: &str,
+    kind: FunctionResultFailureErrorKind,
+    message: String,
+) -> FunctionResult<T> {
+    FunctionResult::Failure(FunctionResultFailure::new(
+        execution_id,
+        FunctionResultFailureError { kind, message },
+        timestamp(),
+    ))
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Evaluates a handler whose body is a single `return` of a JSON literal, its input, or a
+/// property path into its input. Returns `None` for anything more involved.
+fn evaluate(code_base64: &str, handler: &str, input: &Value) -> Option<Value> {
+    let code = general_purpose::STANDARD_NO_PAD
+        .decode(code_base64.trim_end_matches('='))
+        .ok()?;
+    let code = String::from_utf8(code).ok()?;
+
+    let signature = format!("function {handler}(");
+    let after_name = &code[code.find(&signature)? + signature.len()..];
+    let (params, rest) = after_name.split_once(')')?;
+    let param = params.split([',', ':']).next()?.trim();
+    let body = rest.get(rest.find('{')? + 1..rest.rfind('}')?)?.trim();
+
+    let expression = body
+        .strip_prefix("return")?
+        .trim()
+        .trim_end_matches(';')
+        .trim();
+    if expression.contains(';') {
+        return None;
+    }
+
+    if !param.is_empty() {
+        if expression == param {
+            return Some(input.clone());
+        }
+        if let Some(path) = expression
+            .strip_prefix(param)
+            .and_then(|p| p.strip_prefix('.'))
+        {
+            let pointer = format!("/{}", path.replace('.', "/"));
+            return Some(input.pointer(&pointer).cloned().unwrap_or(Value::Null));
+        }
+    }
+    serde_json::from_str(expression).ok()
+}
@@ -70,6 +70,7 @@ pub mod prelude {
     pub use color_eyre::eyre::OptionExt;
     pub use color_eyre::Result;
 }
+mod in_process_veritech;
 mod signup;
 mod test_exclusive_schemas;
 
@@ -77,6 +78,7 @@ pub use color_eyre::{
     self,
     eyre::{eyre, Result, WrapErr},
 };
+pub use in_process_veritech::{CannedResponse, InProcessVeritech, RecordedRequest};
 pub use si_test_macros::{dal_test as test, sdf_test};
 pub use signup::WorkspaceSignup;
 pub use telemetry;
@@ -405,16 +407,28 @@ impl TestContext {
     }
 
     /// Creates a new [`ServicesContext`].
-    #[allow(clippy::expect_used, clippy::panic)]
     pub async fn create_services_context(
         &self,
         token: CancellationToken,
         tracker: TaskTracker,
+    ) -> ServicesContext {
+        let veritech = veritech_client::Client::new(self.nats_conn.clone());
+        self.create_services_context_with_veritech(token, tracker, veritech)
+            .await
+    }
+
+    /// Creates a new [`ServicesContext`] which executes functions with `veritech`, such as an
+    /// [`InProcessVeritech`], rather than a Veritech client.
+    #[allow(clippy::expect_used, clippy::panic)]
+    pub async fn create_services_context_with_veritech(
+        &self,
+        token: CancellationToken,
+        tracker: TaskTracker,
+        veritech: impl veritech_client::FunctionExecutor,
     ) -> ServicesContext {
         let rebaser = rebaser_client::Client::new(self.nats_conn.clone())
             .await
             .expect("failed to create rebaser client");
-        let veritech = veritech_client::Client::new(self.nats_conn.clone());
 
         let (layer_db, layer_db_graceful_shutdown) = DalLayerDb::from_services(
             self.layer_db_pg_pool.clone(),
@@ -906,7 +920,7 @@ async fn global_setup(test_context_builer: TestContextBuilder) -> Result<()> {
         services_ctx.jetstream_streams(),
         services_ctx.job_processor(),
         services_ctx.rebaser().clone(),
-        services_ctx.veritech().clone(),
+        veritech_client::Client::new(services_ctx.nats_conn().clone()),
         &services_ctx.encryption_key(),
         pkgs_path,
         test_context.config.module_index_url.clone(),
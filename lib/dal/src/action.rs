@@ -3,6 +3,7 @@ use std::collections::{HashSet, VecDeque};
 use petgraph::prelude::*;
 use postgres_types::{FromSql, ToSql};
 use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
 use si_events::ulid::Ulid;
 use si_layer_cache::LayerDbError;
 use strum::{AsRefStr, Display, EnumDiscriminants, EnumIter, EnumString};
@@ -11,12 +12,16 @@ use thiserror::Error;
 
 use crate::{
     action::{
+        concurrency_group::{ActionConcurrencyGroupError, ActionConcurrencyLimiter},
         dependency_graph::ActionDependencyGraph,
+        maintenance_window::{
+            MaintenanceWindowError, MaintenanceWindowGate, MaintenanceWindowOverride,
+        },
         prototype::{ActionKind, ActionPrototype, ActionPrototypeError},
     },
     attribute::value::{AttributeValueError, DependentValueGraph},
     component::inferred_connection_graph::InferredConnectionGraphError,
-    func::FuncExecutionPk,
+    func::{runner::FuncRunnerError, FuncExecutionPk},
     implement_add_edge_to,
     job::definition::ActionJob,
     workspace_snapshot::node_weight::{
@@ -27,18 +32,33 @@ use crate::{
     WorkspaceSnapshotError, WsEvent, WsEventError, WsEventResult, WsPayload,
 };
 
+pub mod change_plan;
+pub mod concurrency_group;
 pub mod dependency_graph;
+pub mod maintenance_window;
+pub mod plan;
 pub mod prototype;
+pub mod refresh_schedule;
+pub mod retry_policy;
+pub mod selection;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum ActionError {
+    #[error("action concurrency group error: {0}")]
+    ActionConcurrencyGroup(#[from] Box<ActionConcurrencyGroupError>),
+    #[error("action {0} is not pending in this change set")]
+    ActionNotPendingInChangeSet(ActionId),
     #[error("action prototype error: {0}")]
     ActionPrototype(#[from] ActionPrototypeError),
     #[error("AttributeValue error: {0}")]
     AttributeValue(#[from] AttributeValueError),
     #[error("Change Set error: {0}")]
     ChangeSet(#[from] ChangeSetError),
+    #[error("func runner error while computing change plan: {0}")]
+    ChangePlanFuncRunner(#[source] Box<FuncRunnerError>),
+    #[error("func runner has failed to send a change plan value and exited")]
+    ChangePlanFuncRunnerSend,
     #[error("Component error: {0}")]
     Component(#[from] ComponentError),
     #[error("component not found for action: {0}")]
@@ -49,10 +69,21 @@ pub enum ActionError {
     InferredConnectionGraph(#[from] InferredConnectionGraphError),
     #[error("Layer DB error: {0}")]
     LayerDb(#[from] LayerDbError),
+    #[error("maintenance window error: {0}")]
+    MaintenanceWindow(#[from] Box<MaintenanceWindowError>),
     #[error("Node Weight error: {0}")]
     NodeWeight(#[from] NodeWeightError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
     #[error("prototype not found for action: {0}")]
     PrototypeNotFoundForAction(ActionId),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("action {action_id} was selected to run but depends on held action {held_action_id}")]
+    SelectedActionDependsOnHeldAction {
+        action_id: ActionId,
+        held_action_id: ActionId,
+    },
     #[error("Transactions error: {0}")]
     Transactions(#[from] TransactionsError),
     #[error("Unable to determine kind for action: {0}")]
@@ -567,6 +598,10 @@ impl Action {
     ///   * The graph of values for `DependentValuesUpdate` does *NOT* include
     ///     *ANY* [`AttributeValue`s](AttributeValue) for the same
     ///     [`Component`](crate::Component) as the [`Action`].
+    ///   * On HEAD, one of the [`MaintenanceWindows`](maintenance_window::MaintenanceWindow)
+    ///     covering it (if any) is open, or its hold has been overridden.
+    ///   * Dispatching it does not take its
+    ///     [`ActionConcurrencyGroup`](concurrency_group::ActionConcurrencyGroup) over its limit.
     pub async fn eligible_to_dispatch(ctx: &DalContext) -> ActionResult<Vec<ActionId>> {
         let action_dependency_graph = ActionDependencyGraph::for_workspace(ctx).await?;
         let mut result = Vec::new();
@@ -587,6 +622,17 @@ impl Action {
             dvu_component_ids.insert(AttributeValue::component_id(ctx, *av_id).await?);
         }
 
+        // Dispatched actions count against their concurrency group too, since they will be
+        // running as soon as the job system picks them up.
+        let mut concurrency_limiter = ActionConcurrencyLimiter::new(
+            ctx,
+            &[ActionState::Dispatched, ActionState::Running],
+            None,
+        )
+        .await
+        .map_err(Box::new)?;
+        let maintenance_window_gate = MaintenanceWindowGate::new(ctx).await.map_err(Box::new)?;
+
         for possible_action_id in action_dependency_graph.independent_actions() {
             let action = Action::get_by_id(ctx, possible_action_id).await?;
 
@@ -600,6 +646,25 @@ impl Action {
                         continue;
                     }
                 }
+                if maintenance_window_gate
+                    .hold_for(ctx, possible_action_id)
+                    .await
+                    .map_err(Box::new)?
+                    .is_some()
+                {
+                    // Every maintenance window covering the action is closed, so it stays queued
+                    // until one of them opens.
+                    continue;
+                }
+                if !concurrency_limiter
+                    .try_acquire(ctx, possible_action_id)
+                    .await
+                    .map_err(Box::new)?
+                {
+                    // The action's concurrency group is full, so it stays queued until one of the
+                    // actions in flight for the group finishes.
+                    continue;
+                }
                 result.push(possible_action_id);
             }
         }
@@ -612,6 +677,10 @@ impl Action {
     ))]
     pub async fn dispatch_action(ctx: &DalContext, action_id: ActionId) -> ActionResult<()> {
         Action::set_state(ctx, action_id, ActionState::Dispatched).await?;
+        // An override only lets the action through once, so it is held again if it is queued again
+        MaintenanceWindowOverride::delete(ctx, action_id)
+            .await
+            .map_err(Box::new)?;
 
         ctx.enqueue_action(ActionJob::new(ctx, action_id)).await?;
 
@@ -0,0 +1,320 @@
+//! Describes what each pending [`Action`] would change in the "outside world", by running the
+//! plan func bound to its [`ActionPrototype`] instead of the action itself. Plans are stored as
+//! [`FuncRuns`](si_events::FuncRun) of kind [`Plan`](crate::func::FuncKind::Plan), so the latest
+//! plan for an action is always the most recent plan func run for it.
+//!
+//! Plans are requested whenever a change set's values may have changed, which happens in bursts,
+//! so requests are debounced: pinga computes them once the change set has gone
+//! [`CHANGE_PLAN_QUIET_PERIOD`] without another request.
+
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgRow;
+use si_events::{CasValue, FuncRunId, FuncRunState};
+use telemetry::prelude::*;
+
+use crate::{
+    action::{
+        prototype::ActionPrototype, Action, ActionError, ActionId, ActionResult, ActionState,
+    },
+    func::runner::FuncRunner,
+    layer_db_types::ContentTypes,
+    ChangeSetId, Component, DalContext, WorkspacePk,
+};
+
+/// How long a change set has to go without new plan requests before its plans are computed.
+pub const CHANGE_PLAN_QUIET_PERIOD: Duration = Duration::from_secs(10);
+
+/// How long plans are put off at most for a change set that keeps changing.
+pub const CHANGE_PLAN_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// How the "outside world" version of a component would change if its [`Action`] ran.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlannedChangeKind {
+    /// A new resource would be created.
+    Create,
+    /// Nothing would change.
+    NoOp,
+    /// The existing resource would be destroyed and created again.
+    Replace,
+    /// The existing resource would be modified without being replaced.
+    UpdateInPlace,
+}
+
+/// A single field that would change, addressed by its path in the resource.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedFieldChange {
+    pub path: String,
+    #[serde(default)]
+    pub before: Option<serde_json::Value>,
+    #[serde(default)]
+    pub after: Option<serde_json::Value>,
+    /// Whether changing this field is the reason the resource has to be replaced.
+    #[serde(default)]
+    pub forces_replacement: bool,
+}
+
+/// The value returned by a plan func.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedChange {
+    #[serde(rename = "action")]
+    pub kind: PlannedChangeKind,
+    #[serde(default)]
+    pub changes: Vec<PlannedFieldChange>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// The latest plan computed for a pending [`Action`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionChangePlan {
+    pub action_id: ActionId,
+    pub func_run_id: FuncRunId,
+    pub func_run_state: FuncRunState,
+    /// Only set once the plan func has succeeded.
+    pub planned_change: Option<PlannedChange>,
+}
+
+impl ActionChangePlan {
+    /// Asks for the plans of the current change set to be computed once it settles. Nothing is
+    /// requested for HEAD, whose actions are dispatched rather than reviewed, or when none of the
+    /// pending actions have a plan func.
+    #[instrument(name = "action.change_plan.request", level = "debug", skip(ctx))]
+    pub async fn request(ctx: &DalContext) -> ActionResult<()> {
+        if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await?
+            || !Self::any_planned(ctx).await?
+        {
+            return Ok(());
+        }
+
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "INSERT INTO action_change_plan_requests (workspace_pk, change_set_id)
+                    VALUES ($1, $2)
+                    ON CONFLICT (workspace_pk, change_set_id) DO UPDATE
+                    SET requested_at = CLOCK_TIMESTAMP()",
+                &[&ctx.workspace_pk()?, &ctx.change_set_id()],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Runs the plan func for the given [`Action`], if its prototype has one, and returns the
+    /// [`FuncRunId`] the plan was stored under.
+    #[instrument(name = "action.change_plan.compute", level = "info", skip(ctx))]
+    pub async fn compute(ctx: &DalContext, action_id: ActionId) -> ActionResult<Option<FuncRunId>> {
+        let prototype_id = Action::prototype_id(ctx, action_id).await?;
+        let Some(plan_func_id) = ActionPrototype::plan_func_id(ctx, prototype_id).await? else {
+            return Ok(None);
+        };
+        let Some(component_id) = Action::component_id(ctx, action_id).await? else {
+            return Err(ActionError::ComponentNotFoundForAction(action_id));
+        };
+        let component = Component::get_by_id(ctx, component_id).await?;
+        let component_view = component.view(ctx).await?;
+
+        let result_channel = FuncRunner::run_plan(
+            ctx,
+            action_id,
+            prototype_id,
+            component_id,
+            plan_func_id,
+            serde_json::json!({ "properties" : component_view }),
+        )
+        .await
+        .map_err(|err| ActionError::ChangePlanFuncRunner(Box::new(err)))?;
+        let func_run_value = result_channel
+            .await
+            .map_err(|_| ActionError::ChangePlanFuncRunnerSend)?
+            .map_err(|err| ActionError::ChangePlanFuncRunner(Box::new(err)))?;
+
+        let value_address = match func_run_value.value().cloned() {
+            Some(value) => Some(
+                ctx.layer_db()
+                    .cas()
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value.into())),
+                        None,
+                        ctx.events_tenancy(),
+                        ctx.events_actor(),
+                    )?
+                    .0,
+            ),
+            None => None,
+        };
+        let unprocessed_value_address = match func_run_value.unprocessed_value().cloned() {
+            Some(value) => Some(
+                ctx.layer_db()
+                    .cas()
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value.into())),
+                        None,
+                        ctx.events_tenancy(),
+                        ctx.events_actor(),
+                    )?
+                    .0,
+            ),
+            None => None,
+        };
+
+        ctx.layer_db()
+            .func_run()
+            .set_values_and_set_state_to_success(
+                func_run_value.func_run_id(),
+                unprocessed_value_address,
+                value_address,
+                ctx.events_tenancy(),
+                ctx.events_actor(),
+            )
+            .await?;
+
+        Ok(Some(func_run_value.func_run_id()))
+    }
+
+    /// Computes a plan for every [`Action`] enqueued in the current change set that hasn't been
+    /// dispatched yet. A plan func that fails only affects the plan for its own action.
+    #[instrument(name = "action.change_plan.compute_all", level = "info", skip(ctx))]
+    pub async fn compute_all(ctx: &DalContext) -> ActionResult<Vec<FuncRunId>> {
+        let mut func_run_ids = Vec::new();
+        for action_id in Self::pending_action_ids(ctx).await? {
+            match Self::compute(ctx, action_id).await {
+                Ok(Some(func_run_id)) => func_run_ids.push(func_run_id),
+                Ok(None) => {}
+                Err(err) => {
+                    warn!(si.error.message = ?err, %action_id, "unable to compute change plan for action");
+                }
+            }
+        }
+
+        Ok(func_run_ids)
+    }
+
+    /// Returns the latest plan for every [`Action`] enqueued in the current change set that hasn't
+    /// been dispatched yet. Actions without a plan func, or whose plan hasn't been computed yet,
+    /// are left out.
+    #[instrument(name = "action.change_plan.list", level = "debug", skip(ctx))]
+    pub async fn list(ctx: &DalContext) -> ActionResult<Vec<Self>> {
+        let workspace_pk = ctx.events_tenancy().workspace_pk;
+        let mut plans = Vec::new();
+        for action_id in Self::pending_action_ids(ctx).await? {
+            let Some(func_run) = ctx
+                .layer_db()
+                .func_run()
+                .get_last_plan_run_for_action_id(workspace_pk, action_id)
+                .await?
+            else {
+                continue;
+            };
+
+            let planned_change = match (func_run.state(), func_run.result_value_cas_address()) {
+                (FuncRunState::Success, Some(address)) => {
+                    let value: Option<CasValue> =
+                        ctx.layer_db().cas().try_read_as(&address).await?;
+                    match value {
+                        Some(value) => Some(serde_json::from_value(value.into())?),
+                        None => None,
+                    }
+                }
+                _ => None,
+            };
+
+            plans.push(Self {
+                action_id,
+                func_run_id: func_run.id(),
+                func_run_state: func_run.state(),
+                planned_change,
+            });
+        }
+
+        Ok(plans)
+    }
+
+    /// Whether any pending [`Action`] has a plan func to run.
+    async fn any_planned(ctx: &DalContext) -> ActionResult<bool> {
+        for action_id in Self::pending_action_ids(ctx).await? {
+            let prototype_id = Action::prototype_id(ctx, action_id).await?;
+            if ActionPrototype::plan_func_id(ctx, prototype_id)
+                .await?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    async fn pending_action_ids(ctx: &DalContext) -> ActionResult<Vec<ActionId>> {
+        let change_set_id = ctx.change_set_id();
+        let mut action_ids = Vec::new();
+        for action_id in Action::all_ids(ctx).await? {
+            let action = Action::get_by_id(ctx, action_id).await?;
+            if matches!(action.state(), ActionState::Queued | ActionState::OnHold)
+                && action.originating_changeset_id() == change_set_id
+            {
+                action_ids.push(action_id);
+            }
+        }
+
+        Ok(action_ids)
+    }
+}
+
+/// A change set whose plans should be computed once it settles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionChangePlanRequest {
+    workspace_pk: WorkspacePk,
+    change_set_id: ChangeSetId,
+}
+
+impl TryFrom<PgRow> for ActionChangePlanRequest {
+    type Error = ActionError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            workspace_pk: row.try_get("workspace_pk")?,
+            change_set_id: row.try_get("change_set_id")?,
+        })
+    }
+}
+
+impl ActionChangePlanRequest {
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn change_set_id(&self) -> ChangeSetId {
+        self.change_set_id
+    }
+
+    /// Claims every request, across all workspaces, whose change set has settled or has waited
+    /// for [`CHANGE_PLAN_MAX_DELAY`]. Claiming deletes the request in one statement, so each one
+    /// is only claimed by one pinga instance, and a request made afterwards starts over.
+    pub async fn claim_due(ctx: &DalContext) -> ActionResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "DELETE FROM action_change_plan_requests
+                    WHERE requested_at <= CLOCK_TIMESTAMP() - make_interval(secs => $1)
+                        OR created_at <= CLOCK_TIMESTAMP() - make_interval(secs => $2)
+                    RETURNING workspace_pk, change_set_id",
+                &[
+                    &CHANGE_PLAN_QUIET_PERIOD.as_secs_f64(),
+                    &CHANGE_PLAN_MAX_DELAY.as_secs_f64(),
+                ],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+}
@@ -0,0 +1,370 @@
+//! Concurrency groups cap how many [`Actions`](Action) of a kind can be in flight at once, for
+//! example to stay under the rate limit of a cloud provider's API.
+//!
+//! Groups belong to the workspace rather than to a change set, since they describe limits of the
+//! outside world. An action belongs to the most specific group that claims it: a group listing
+//! its [`ActionPrototype`] wins over one listing its [`Schema`](crate::Schema), which wins over one
+//! listing its [`SchemaVariant`] category. Actions that would go over their group's limit stay
+//! [`Queued`](ActionState::Queued) until one of the actions in flight finishes.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    action::{
+        prototype::{ActionPrototype, ActionPrototypeError},
+        Action, ActionError, ActionId, ActionState,
+    },
+    ActionPrototypeId, DalContext, SchemaId, SchemaVariant, SchemaVariantError, TransactionsError,
+    WorkspacePk,
+};
+
+pub use si_id::ActionConcurrencyGroupId;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ActionConcurrencyGroupError {
+    #[error("action error: {0}")]
+    Action(#[from] Box<ActionError>),
+    #[error("action prototype error: {0}")]
+    ActionPrototype(#[from] Box<ActionPrototypeError>),
+    #[error("concurrency limit must be at least 1")]
+    InvalidLimit,
+    #[error("concurrency group key cannot be empty")]
+    KeyEmpty,
+    #[error("concurrency group key already in use: {0}")]
+    KeyTaken(String),
+    #[error("concurrency group not found: {0}")]
+    NotFound(ActionConcurrencyGroupId),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] Box<SchemaVariantError>),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ActionConcurrencyGroupResult<T> = Result<T, ActionConcurrencyGroupError>;
+
+/// Which [`Actions`](Action) belong to an [`ActionConcurrencyGroup`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ActionConcurrencyGroupMember {
+    /// The actions for one [`ActionPrototype`].
+    #[serde(rename_all = "camelCase")]
+    ActionPrototype {
+        action_prototype_id: ActionPrototypeId,
+    },
+    /// The actions for every variant of a [`Schema`](crate::Schema).
+    #[serde(rename_all = "camelCase")]
+    Schema { schema_id: SchemaId },
+    /// The actions for every [`SchemaVariant`] in a category, such as "AWS IAM".
+    #[serde(rename_all = "camelCase")]
+    Category { category: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionConcurrencyGroup {
+    pk: ActionConcurrencyGroupId,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    workspace_pk: WorkspacePk,
+    key: String,
+    concurrency_limit: i32,
+    members: Vec<ActionConcurrencyGroupMember>,
+}
+
+impl TryFrom<PgRow> for ActionConcurrencyGroup {
+    type Error = ActionConcurrencyGroupError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let members: serde_json::Value = row.try_get("members")?;
+        Ok(Self {
+            pk: row.try_get("pk")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            workspace_pk: row.try_get("workspace_pk")?,
+            key: row.try_get("key")?,
+            concurrency_limit: row.try_get("concurrency_limit")?,
+            members: serde_json::from_value(members)?,
+        })
+    }
+}
+
+impl ActionConcurrencyGroup {
+    pub fn pk(&self) -> ActionConcurrencyGroupId {
+        self.pk
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn concurrency_limit(&self) -> i32 {
+        self.concurrency_limit
+    }
+
+    pub fn members(&self) -> &[ActionConcurrencyGroupMember] {
+        &self.members
+    }
+
+    #[instrument(name = "action.concurrency_group.new", level = "info", skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        key: String,
+        concurrency_limit: i32,
+        members: Vec<ActionConcurrencyGroupMember>,
+    ) -> ActionConcurrencyGroupResult<Self> {
+        let key = key.trim().to_owned();
+        Self::validate(&key, concurrency_limit)?;
+        if Self::list(ctx).await?.iter().any(|group| group.key == key) {
+            return Err(ActionConcurrencyGroupError::KeyTaken(key));
+        }
+
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO action_concurrency_groups (workspace_pk, key, concurrency_limit, members)
+                    VALUES ($1, $2, $3, $4) RETURNING *",
+                &[
+                    &workspace_pk,
+                    &key,
+                    &concurrency_limit,
+                    &serde_json::to_value(&members)?,
+                ],
+            )
+            .await?;
+
+        Self::try_from(row)
+    }
+
+    pub async fn get_by_pk(
+        ctx: &DalContext,
+        pk: ActionConcurrencyGroupId,
+    ) -> ActionConcurrencyGroupResult<Self> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM action_concurrency_groups WHERE pk = $1 AND workspace_pk = $2",
+                &[&pk, &workspace_pk],
+            )
+            .await?
+            .ok_or(ActionConcurrencyGroupError::NotFound(pk))?;
+
+        Self::try_from(row)
+    }
+
+    /// Lists the groups for the current workspace, ordered by key.
+    pub async fn list(ctx: &DalContext) -> ActionConcurrencyGroupResult<Vec<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM action_concurrency_groups WHERE workspace_pk = $1 ORDER BY key",
+                &[&workspace_pk],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    pub async fn update(
+        &mut self,
+        ctx: &DalContext,
+        concurrency_limit: i32,
+        members: Vec<ActionConcurrencyGroupMember>,
+    ) -> ActionConcurrencyGroupResult<()> {
+        Self::validate(&self.key, concurrency_limit)?;
+
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "UPDATE action_concurrency_groups
+                    SET concurrency_limit = $3, members = $4, updated_at = CLOCK_TIMESTAMP()
+                    WHERE pk = $1 AND workspace_pk = $2 RETURNING *",
+                &[
+                    &self.pk,
+                    &workspace_pk,
+                    &concurrency_limit,
+                    &serde_json::to_value(&members)?,
+                ],
+            )
+            .await?
+            .ok_or(ActionConcurrencyGroupError::NotFound(self.pk))?;
+        *self = Self::try_from(row)?;
+
+        Ok(())
+    }
+
+    pub async fn delete(
+        ctx: &DalContext,
+        pk: ActionConcurrencyGroupId,
+    ) -> ActionConcurrencyGroupResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "DELETE FROM action_concurrency_groups WHERE pk = $1 AND workspace_pk = $2",
+                &[&pk, &workspace_pk],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn validate(key: &str, concurrency_limit: i32) -> ActionConcurrencyGroupResult<()> {
+        if key.is_empty() {
+            return Err(ActionConcurrencyGroupError::KeyEmpty);
+        }
+        if concurrency_limit < 1 {
+            return Err(ActionConcurrencyGroupError::InvalidLimit);
+        }
+        Ok(())
+    }
+}
+
+/// Counts the [`Actions`](Action) in flight for each [`ActionConcurrencyGroup`], to decide which
+/// actions can start.
+#[derive(Debug)]
+pub struct ActionConcurrencyLimiter {
+    groups: Vec<ActionConcurrencyGroup>,
+    in_flight: HashMap<ActionConcurrencyGroupId, i32>,
+    group_for_prototype: HashMap<ActionPrototypeId, Option<usize>>,
+}
+
+impl ActionConcurrencyLimiter {
+    /// Builds a limiter that counts every [`Action`] in one of `in_flight_states` against its
+    /// group, except for `exclude`.
+    pub async fn new(
+        ctx: &DalContext,
+        in_flight_states: &[ActionState],
+        exclude: Option<ActionId>,
+    ) -> ActionConcurrencyGroupResult<Self> {
+        let mut limiter = Self {
+            groups: ActionConcurrencyGroup::list(ctx).await?,
+            in_flight: HashMap::new(),
+            group_for_prototype: HashMap::new(),
+        };
+        if limiter.groups.is_empty() {
+            return Ok(limiter);
+        }
+
+        for action_id in Action::all_ids(ctx).await.map_err(Box::new)? {
+            if Some(action_id) == exclude {
+                continue;
+            }
+            let action = Action::get_by_id(ctx, action_id).await.map_err(Box::new)?;
+            if !in_flight_states.contains(&action.state()) {
+                continue;
+            }
+            if let Some(group) = limiter.group_for_action(ctx, action_id).await? {
+                let pk = group.pk;
+                *limiter.in_flight.entry(pk).or_default() += 1;
+            }
+        }
+
+        Ok(limiter)
+    }
+
+    /// The group the [`Action`] belongs to, if any.
+    pub async fn group_for_action(
+        &mut self,
+        ctx: &DalContext,
+        action_id: ActionId,
+    ) -> ActionConcurrencyGroupResult<Option<&ActionConcurrencyGroup>> {
+        if self.groups.is_empty() {
+            return Ok(None);
+        }
+
+        let prototype_id = Action::prototype_id(ctx, action_id)
+            .await
+            .map_err(Box::new)?;
+        let index = match self.group_for_prototype.get(&prototype_id) {
+            Some(index) => *index,
+            None => {
+                let index = self.find_group_index(ctx, prototype_id).await?;
+                self.group_for_prototype.insert(prototype_id, index);
+                index
+            }
+        };
+
+        Ok(index.map(|index| &self.groups[index]))
+    }
+
+    /// Whether the [`Action`] can start without its group going over its limit. If it can, it is
+    /// counted as in flight from now on.
+    pub async fn try_acquire(
+        &mut self,
+        ctx: &DalContext,
+        action_id: ActionId,
+    ) -> ActionConcurrencyGroupResult<bool> {
+        let Some(group) = self.group_for_action(ctx, action_id).await? else {
+            return Ok(true);
+        };
+        let (pk, concurrency_limit) = (group.pk, group.concurrency_limit);
+
+        let in_flight = self.in_flight.entry(pk).or_default();
+        if *in_flight >= concurrency_limit {
+            debug!(%action_id, group = %pk, in_flight = *in_flight, "action concurrency group is full");
+            return Ok(false);
+        }
+        *in_flight += 1;
+
+        Ok(true)
+    }
+
+    async fn find_group_index(
+        &self,
+        ctx: &DalContext,
+        prototype_id: ActionPrototypeId,
+    ) -> ActionConcurrencyGroupResult<Option<usize>> {
+        let find = |wanted: &ActionConcurrencyGroupMember| {
+            self.groups
+                .iter()
+                .position(|group| group.members.contains(wanted))
+        };
+
+        if let Some(index) = find(&ActionConcurrencyGroupMember::ActionPrototype {
+            action_prototype_id: prototype_id,
+        }) {
+            return Ok(Some(index));
+        }
+
+        let schema_variant_id = ActionPrototype::schema_variant_id(ctx, prototype_id)
+            .await
+            .map_err(Box::new)?;
+        let schema_id = SchemaVariant::schema_id_for_schema_variant_id(ctx, schema_variant_id)
+            .await
+            .map_err(Box::new)?;
+        if let Some(index) = find(&ActionConcurrencyGroupMember::Schema { schema_id }) {
+            return Ok(Some(index));
+        }
+
+        let category = SchemaVariant::get_by_id(ctx, schema_variant_id)
+            .await
+            .map_err(Box::new)?
+            .category()
+            .to_owned();
+        Ok(find(&ActionConcurrencyGroupMember::Category { category }))
+    }
+}
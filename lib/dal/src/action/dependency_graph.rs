@@ -3,13 +3,14 @@ use petgraph::{
     prelude::*,
     visit::{Control, DfsEvent},
 };
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use telemetry::prelude::*;
 
 use crate::{
     action::{Action, ActionId},
     dependency_graph::DependencyGraph,
-    Component, ComponentId, DalContext,
+    Component, ComponentId, DalContext, InputSocketId, OutputSocketId,
 };
 
 use super::{
@@ -20,6 +21,33 @@ use super::{
 #[derive(Debug, Clone)]
 pub struct ActionDependencyGraph {
     inner: DependencyGraph<ActionId>,
+    // The socket connections between each pair of components, keyed by `(source, destination)`.
+    socket_edges: HashMap<(ComponentId, ComponentId), Vec<ActionDependencySocketEdge>>,
+    // The components (in data flow order) that caused an action to depend on another, keyed by
+    // `(action, depends_on)`.
+    dependency_paths: HashMap<(ActionId, ActionId), Vec<ComponentId>>,
+}
+
+/// A socket connection between two [`Components`](Component) that data flows along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionDependencySocketEdge {
+    pub from_component_id: ComponentId,
+    pub from_output_socket_id: OutputSocketId,
+    pub to_component_id: ComponentId,
+    pub to_input_socket_id: InputSocketId,
+    /// Whether the connection is inferred from the component tree rather than drawn explicitly.
+    pub inferred: bool,
+}
+
+/// Why one [`Action`] depends on another: the chain of [`Components`](Component) the data flows
+/// through, and the socket connections along that chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionDependencyReason {
+    /// Components in data flow order, from the first component to the last.
+    pub component_path: Vec<ComponentId>,
+    pub socket_edges: Vec<ActionDependencySocketEdge>,
 }
 
 impl Default for ActionDependencyGraph {
@@ -32,6 +60,8 @@ impl ActionDependencyGraph {
     pub fn new() -> Self {
         Self {
             inner: DependencyGraph::new(),
+            socket_edges: HashMap::new(),
+            dependency_paths: HashMap::new(),
         }
     }
 
@@ -112,6 +142,17 @@ impl ActionDependencyGraph {
                     // components.
                     component_dependencies.update_edge(source_component_index, component_index, ());
                 }
+                action_dependency_graph
+                    .socket_edges
+                    .entry((incoming_connection.from_component_id, component_id))
+                    .or_default()
+                    .push(ActionDependencySocketEdge {
+                        from_component_id: incoming_connection.from_component_id,
+                        from_output_socket_id: incoming_connection.from_output_socket_id,
+                        to_component_id: component_id,
+                        to_input_socket_id: incoming_connection.to_input_socket_id,
+                        inferred: false,
+                    });
                 if seen_list.insert(incoming_connection.from_component_id) {
                     components_to_process.push_back(incoming_connection.from_component_id);
                 }
@@ -133,6 +174,17 @@ impl ActionDependencyGraph {
                     // components.
                     component_dependencies.update_edge(source_component_index, component_index, ());
                 }
+                action_dependency_graph
+                    .socket_edges
+                    .entry((inferred_connection.source_component_id, component_id))
+                    .or_default()
+                    .push(ActionDependencySocketEdge {
+                        from_component_id: inferred_connection.source_component_id,
+                        from_output_socket_id: inferred_connection.output_socket_id,
+                        to_component_id: component_id,
+                        to_input_socket_id: inferred_connection.input_socket_id,
+                        inferred: true,
+                    });
                 if seen_list.insert(inferred_connection.source_component_id) {
                     components_to_process.push_back(inferred_connection.source_component_id);
                 }
@@ -171,36 +223,83 @@ impl ActionDependencyGraph {
                     // Depending on the direction above, we reverse the component dependencies graph to traverse accordingly
                     match dependency_direction {
                         Outgoing => {
+                            let mut predecessors = HashMap::new();
                             petgraph::visit::depth_first_search(
                                 &component_dependencies,
                                 Some(component_index),
                                 |event| {
-                                    Self::calculate_dependencies_dfs_event(
+                                    if let DfsEvent::TreeEdge(parent, child) = event {
+                                        predecessors.insert(child, parent);
+                                    }
+                                    let control = Self::calculate_dependencies_dfs_event(
                                         event,
                                         &mut action_dependency_graph,
                                         &component_dependencies,
                                         &actions_by_component_id,
                                         component_index,
                                         component_action_id,
-                                    )
+                                    );
+                                    if let (DfsEvent::Discover(node, _), Control::Prune) =
+                                        (event, &control)
+                                    {
+                                        // Data flows from this component to the dependency.
+                                        let path = Self::dfs_component_path(
+                                            &component_dependencies,
+                                            &predecessors,
+                                            component_index,
+                                            node,
+                                        );
+                                        action_dependency_graph.record_dependency_path(
+                                            component_action_id,
+                                            component_dependencies
+                                                .node_weight(node)
+                                                .and_then(|id| actions_by_component_id.get(id)),
+                                            path,
+                                        );
+                                    }
+                                    control
                                 },
                             );
                         }
                         Incoming => {
                             // For Incoming, reverse the view so that the DFS still follows outgoing edges.
                             let reversed = petgraph::visit::Reversed(&component_dependencies);
+                            let mut predecessors = HashMap::new();
                             petgraph::visit::depth_first_search(
                                 &reversed,
                                 Some(component_index),
                                 |event| {
-                                    Self::calculate_dependencies_dfs_event(
+                                    if let DfsEvent::TreeEdge(parent, child) = event {
+                                        predecessors.insert(child, parent);
+                                    }
+                                    let control = Self::calculate_dependencies_dfs_event(
                                         event,
                                         &mut action_dependency_graph,
                                         &component_dependencies,
                                         &actions_by_component_id,
                                         component_index,
                                         component_action_id,
-                                    )
+                                    );
+                                    if let (DfsEvent::Discover(node, _), Control::Prune) =
+                                        (event, &control)
+                                    {
+                                        // We walked against the data flow, so flip the path.
+                                        let mut path = Self::dfs_component_path(
+                                            &component_dependencies,
+                                            &predecessors,
+                                            component_index,
+                                            node,
+                                        );
+                                        path.reverse();
+                                        action_dependency_graph.record_dependency_path(
+                                            component_action_id,
+                                            component_dependencies
+                                                .node_weight(node)
+                                                .and_then(|id| actions_by_component_id.get(id)),
+                                            path,
+                                        );
+                                    }
+                                    control
                                 },
                             );
                         }
@@ -226,6 +325,52 @@ impl ActionDependencyGraph {
         self.inner.direct_dependencies_of(action_id)
     }
 
+    /// Explains why `action_id` depends on `depends_on_id`, if the dependency came from the data
+    /// flow between their [`Components`](Component).
+    pub fn dependency_reason(
+        &self,
+        action_id: ActionId,
+        depends_on_id: ActionId,
+    ) -> Option<ActionDependencyReason> {
+        let component_path = self.dependency_paths.get(&(action_id, depends_on_id))?;
+        let socket_edges = component_path
+            .iter()
+            .tuple_windows()
+            .flat_map(|(&source, &destination)| {
+                self.socket_edges
+                    .get(&(source, destination))
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        Some(ActionDependencyReason {
+            component_path: component_path.clone(),
+            socket_edges,
+        })
+    }
+
+    /// Returns each group of actions that depend on each other in a cycle. Actions in a cycle can
+    /// never be dispatched.
+    pub fn cycles(&self) -> Vec<Vec<ActionId>> {
+        let graph = self.inner.graph();
+        petgraph::algo::tarjan_scc(graph)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || component
+                        .first()
+                        .is_some_and(|&index| graph.contains_edge(index, index))
+            })
+            .map(|component| {
+                component
+                    .into_iter()
+                    .filter_map(|index| graph.node_weight(index).copied())
+                    .collect()
+            })
+            .collect()
+    }
+
     pub fn remove_action(&mut self, action_id: ActionId) {
         self.inner.remove_id(action_id);
     }
@@ -261,6 +406,43 @@ impl ActionDependencyGraph {
         all_dependencies.into_iter().collect_vec()
     }
 
+    /// Walks the DFS tree back from `end` to `start`, returning the components in traversal order.
+    fn dfs_component_path(
+        component_dependencies: &StableDiGraph<ComponentId, ()>,
+        predecessors: &HashMap<NodeIndex, NodeIndex>,
+        start: NodeIndex,
+        end: NodeIndex,
+    ) -> Vec<ComponentId> {
+        let mut path = vec![end];
+        let mut current = end;
+        while current != start {
+            match predecessors.get(&current) {
+                Some(&parent) => {
+                    path.push(parent);
+                    current = parent;
+                }
+                None => break,
+            }
+        }
+        path.into_iter()
+            .rev()
+            .filter_map(|index| component_dependencies.node_weight(index).copied())
+            .collect()
+    }
+
+    /// Records the component path that made `action_id` depend on each of `dependency_action_ids`.
+    fn record_dependency_path(
+        &mut self,
+        action_id: ActionId,
+        dependency_action_ids: Option<&HashSet<ActionId>>,
+        path: Vec<ComponentId>,
+    ) {
+        for &dependency_action_id in dependency_action_ids.into_iter().flatten() {
+            self.dependency_paths
+                .insert((action_id, dependency_action_id), path.clone());
+        }
+    }
+
     /// For each event, if we find a component with an action, add the dependency and prune (as we don't need to keep traversing)
     fn calculate_dependencies_dfs_event(
         event: DfsEvent<NodeIndex>,
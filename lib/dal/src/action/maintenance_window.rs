@@ -0,0 +1,760 @@
+//! Maintenance windows restrict when [`Actions`](Action) on HEAD can be dispatched, for
+//! workspaces that may only change their infrastructure during approved periods.
+//!
+//! A window opens whenever its cron schedule matches, in its own timezone, and stays open for
+//! `duration_minutes`. A window covers every [`Component`] in the workspace, or only those of one
+//! [`Schema`](crate::Schema), or only those whose [`SchemaVariant`] has a given label (its
+//! category, such as "AWS EC2"). An action whose component is covered by at least one enabled
+//! window can only be dispatched while one of those windows is open; until then it stays
+//! [`Queued`](crate::action::ActionState::Queued), unless a user with the approval permission
+//! overrides the hold for it. Windows are stored per workspace rather than per change set, since
+//! they only ever apply to HEAD. Pinga wakes dispatch up when a window opens.
+
+use std::{collections::HashSet, str::FromStr};
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeDelta, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    action::{Action, ActionError, ActionId},
+    workspace_snapshot::graph::RebaseBatch,
+    Component, ComponentError, DalContext, HistoryActor, SchemaId, TransactionsError, UserPk,
+    WorkspacePk,
+};
+
+pub use si_id::MaintenanceWindowId;
+
+/// A window longer than a week would never close for a weekly schedule.
+pub const MAX_DURATION_MINUTES: i32 = 7 * 24 * 60;
+
+/// How far ahead to look for the next time a window opens, long enough to find the next leap
+/// day. Schedules that never match (such as "February 30th") are reported as not opening again.
+const NEXT_OPEN_LOOKAHEAD_DAYS: i64 = 4 * 366;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum MaintenanceWindowError {
+    #[error("action error: {0}")]
+    Action(#[from] Box<ActionError>),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
+    #[error("window duration must be between 1 and {MAX_DURATION_MINUTES} minutes, got {0}")]
+    InvalidDuration(i32),
+    #[error("invalid schedule {0:?}: {1}")]
+    InvalidSchedule(String, String),
+    #[error("unknown timezone: {0}")]
+    InvalidTimezone(String),
+    #[error("maintenance window name cannot be empty")]
+    NameEmpty,
+    #[error("maintenance window not found: {0}")]
+    NotFound(MaintenanceWindowId),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type MaintenanceWindowResult<T> = Result<T, MaintenanceWindowError>;
+
+/// A five field cron expression (minute, hour, day of month, month, day of week), supporting
+/// `*`, lists, ranges and steps. As with cron, when both the day of month and the day of week are
+/// restricted, a day matches if either of them does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = MaintenanceWindowError;
+
+    fn from_str(schedule: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            MaintenanceWindowError::InvalidSchedule(schedule.to_string(), reason.to_string())
+        };
+
+        let fields: Vec<&str> = schedule.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(invalid("expected 5 fields"));
+        };
+
+        let (minutes, _) = parse_field(minute, 0, 59).map_err(|reason| invalid(&reason))?;
+        let (hours, _) = parse_field(hour, 0, 23).map_err(|reason| invalid(&reason))?;
+        let (days_of_month, any_day_of_month) =
+            parse_field(day_of_month, 1, 31).map_err(|reason| invalid(&reason))?;
+        let (months, _) = parse_field(month, 1, 12).map_err(|reason| invalid(&reason))?;
+        let (mut days_of_week, any_day_of_week) =
+            parse_field(day_of_week, 0, 7).map_err(|reason| invalid(&reason))?;
+        // Both 0 and 7 are Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            any_day_of_month,
+            any_day_of_week,
+        })
+    }
+}
+
+/// Parses one cron field into a bit set of the values it matches, and whether it is a bare `*`.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<(u64, bool), String> {
+    let mut bits = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid step in {part:?}"))?;
+                if step == 0 {
+                    return Err(format!("invalid step in {part:?}"));
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let parse_value = |value: &str| -> Result<u32, String> {
+            match value.parse() {
+                Ok(value) if (min..=max).contains(&value) => Ok(value),
+                _ => Err(format!("{value:?} is not between {min} and {max}")),
+            }
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                // "5/15" means every 15 starting at 5
+                None if step.is_some() => (parse_value(range)?, max),
+                None => {
+                    let value = parse_value(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("invalid range in {part:?}"));
+        }
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << value;
+        }
+    }
+
+    Ok((bits, field == "*"))
+}
+
+impl CronSchedule {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.days_of_month & (1 << date.day()) != 0;
+        let day_of_week = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => day_of_week,
+            (false, true) => day_of_month,
+            (false, false) => day_of_month || day_of_week,
+        }
+    }
+
+    /// Whether the schedule matches the minute `time` falls in.
+    pub fn matches(&self, time: NaiveDateTime) -> bool {
+        self.matches_day(time.date())
+            && self.hours & (1 << time.hour()) != 0
+            && self.minutes & (1 << time.minute()) != 0
+    }
+
+    /// The latest minute matching the schedule in the `duration_minutes` up to and including
+    /// `time`, which is when the window containing `time` opened.
+    pub fn last_match_within(
+        &self,
+        time: NaiveDateTime,
+        duration_minutes: i32,
+    ) -> Option<NaiveDateTime> {
+        let time = truncate_to_minute(time);
+        (0..i64::from(duration_minutes))
+            .map(|minutes| time - TimeDelta::minutes(minutes))
+            .find(|candidate| self.matches(*candidate))
+    }
+
+    /// The first minute after `time` matching the schedule.
+    pub fn next_match_after(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let limit = time + TimeDelta::days(NEXT_OPEN_LOOKAHEAD_DAYS);
+        let mut candidate = truncate_to_minute(time) + TimeDelta::minutes(1);
+        while candidate <= limit {
+            if !self.matches_day(candidate.date()) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if self.hours & (1 << candidate.hour()) == 0 {
+                candidate =
+                    candidate.date().and_hms_opt(candidate.hour(), 0, 0)? + TimeDelta::hours(1);
+            } else if self.minutes & (1 << candidate.minute()) == 0 {
+                candidate += TimeDelta::minutes(1);
+            } else {
+                return Some(candidate);
+            }
+        }
+
+        None
+    }
+}
+
+fn truncate_to_minute(time: NaiveDateTime) -> NaiveDateTime {
+    time.with_second(0)
+        .and_then(|time| time.with_nanosecond(0))
+        .unwrap_or(time)
+}
+
+/// The user provided parts of a [`MaintenanceWindow`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindowDefinition {
+    pub name: String,
+    /// A five field cron expression for when the window opens, such as "0 2 * * 6".
+    pub schedule: String,
+    /// An IANA timezone name, such as "Europe/Lisbon", the schedule is evaluated in.
+    pub timezone: String,
+    pub duration_minutes: i32,
+    /// Only cover the components of this schema.
+    pub schema_id: Option<SchemaId>,
+    /// Only cover the components whose schema variant has this label (its category).
+    pub label: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindow {
+    pk: MaintenanceWindowId,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    workspace_pk: WorkspacePk,
+    name: String,
+    schedule: String,
+    timezone: String,
+    duration_minutes: i32,
+    schema_id: Option<SchemaId>,
+    label: Option<String>,
+    enabled: bool,
+    /// When pinga last noticed the window opening.
+    last_opened_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PgRow> for MaintenanceWindow {
+    type Error = MaintenanceWindowError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pk: row.try_get("pk")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            workspace_pk: row.try_get("workspace_pk")?,
+            name: row.try_get("name")?,
+            schedule: row.try_get("schedule")?,
+            timezone: row.try_get("timezone")?,
+            duration_minutes: row.try_get("duration_minutes")?,
+            schema_id: row.try_get("schema_id")?,
+            label: row.try_get("label")?,
+            enabled: row.try_get("enabled")?,
+            last_opened_at: row.try_get("last_opened_at")?,
+        })
+    }
+}
+
+/// Whether a [`MaintenanceWindow`] is open, as of when it was checked.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindowStatus {
+    /// When the window opened, if it is open.
+    pub opened_at: Option<DateTime<Utc>>,
+    /// When the window opens next, if it ever does.
+    pub next_open_at: Option<DateTime<Utc>>,
+}
+
+impl MaintenanceWindowStatus {
+    pub fn is_open(&self) -> bool {
+        self.opened_at.is_some()
+    }
+}
+
+impl MaintenanceWindow {
+    pub fn pk(&self) -> MaintenanceWindowId {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn schedule(&self) -> &str {
+        &self.schedule
+    }
+
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+
+    pub fn duration_minutes(&self) -> i32 {
+        self.duration_minutes
+    }
+
+    pub fn schema_id(&self) -> Option<SchemaId> {
+        self.schema_id
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn last_opened_at(&self) -> Option<DateTime<Utc>> {
+        self.last_opened_at
+    }
+
+    #[instrument(name = "action.maintenance_window.new", level = "info", skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        definition: MaintenanceWindowDefinition,
+    ) -> MaintenanceWindowResult<Self> {
+        Self::validate(ctx, &definition).await?;
+
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO maintenance_windows (
+                    workspace_pk, name, schedule, timezone, duration_minutes, schema_id, label,
+                    enabled
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
+                &[
+                    &workspace_pk,
+                    &definition.name,
+                    &definition.schedule,
+                    &definition.timezone,
+                    &definition.duration_minutes,
+                    &definition.schema_id,
+                    &definition.label,
+                    &definition.enabled,
+                ],
+            )
+            .await?;
+
+        Self::try_from(row)
+    }
+
+    pub async fn get_by_pk(
+        ctx: &DalContext,
+        pk: MaintenanceWindowId,
+    ) -> MaintenanceWindowResult<Self> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM maintenance_windows WHERE pk = $1 AND workspace_pk = $2",
+                &[&pk, &workspace_pk],
+            )
+            .await?
+            .ok_or(MaintenanceWindowError::NotFound(pk))?;
+
+        Self::try_from(row)
+    }
+
+    /// Lists the windows for the current workspace, by name.
+    pub async fn list(ctx: &DalContext) -> MaintenanceWindowResult<Vec<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM maintenance_windows WHERE workspace_pk = $1 ORDER BY name",
+                &[&workspace_pk],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    pub async fn update(
+        &mut self,
+        ctx: &DalContext,
+        definition: MaintenanceWindowDefinition,
+    ) -> MaintenanceWindowResult<()> {
+        Self::validate(ctx, &definition).await?;
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "UPDATE maintenance_windows
+                    SET name = $2, schedule = $3, timezone = $4, duration_minutes = $5,
+                        schema_id = $6, label = $7, enabled = $8, updated_at = CLOCK_TIMESTAMP()
+                    WHERE pk = $1 RETURNING *",
+                &[
+                    &self.pk,
+                    &definition.name,
+                    &definition.schedule,
+                    &definition.timezone,
+                    &definition.duration_minutes,
+                    &definition.schema_id,
+                    &definition.label,
+                    &definition.enabled,
+                ],
+            )
+            .await?;
+        *self = Self::try_from(row)?;
+
+        Ok(())
+    }
+
+    pub async fn delete(ctx: &DalContext, pk: MaintenanceWindowId) -> MaintenanceWindowResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "DELETE FROM maintenance_windows WHERE pk = $1 AND workspace_pk = $2",
+                &[&pk, &workspace_pk],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Whether the window covers components of the given schema and label.
+    pub fn covers(&self, schema_id: Option<SchemaId>, label: Option<&str>) -> bool {
+        self.schema_id.is_none_or(|id| Some(id) == schema_id)
+            && self.label.as_deref().is_none_or(|l| Some(l) == label)
+    }
+
+    /// Checks whether the window is open right now, in its own timezone.
+    pub async fn status(
+        &self,
+        ctx: &DalContext,
+    ) -> MaintenanceWindowResult<MaintenanceWindowStatus> {
+        let schedule: CronSchedule = self.schedule.parse()?;
+        let local_now = Self::local_now(ctx, &self.timezone).await?;
+
+        let opened_at = match schedule.last_match_within(local_now, self.duration_minutes) {
+            Some(start) => Some(Self::to_utc(ctx, start, &self.timezone).await?),
+            None => None,
+        };
+        let next_open_at = match schedule.next_match_after(local_now) {
+            Some(start) => Some(Self::to_utc(ctx, start, &self.timezone).await?),
+            None => None,
+        };
+
+        Ok(MaintenanceWindowStatus {
+            opened_at,
+            next_open_at,
+        })
+    }
+
+    /// Claims every enabled window, across all workspaces, that has opened since pinga last
+    /// noticed, recording when it opened. As with
+    /// [`RefreshSchedule::claim_due`](crate::action::refresh_schedule::RefreshSchedule::claim_due),
+    /// each opening is only claimed by one pinga instance.
+    pub async fn claim_opened(ctx: &DalContext) -> MaintenanceWindowResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query("SELECT * FROM maintenance_windows WHERE enabled", &[])
+            .await?;
+
+        let mut claimed = Vec::new();
+        for row in rows {
+            let window = Self::try_from(row)?;
+            let opened_at = match window.status(ctx).await {
+                Ok(status) => status.opened_at,
+                Err(err) => {
+                    warn!(si.error.message = ?err, window = %window.pk, "unable to check maintenance window");
+                    continue;
+                }
+            };
+            let Some(opened_at) = opened_at else {
+                continue;
+            };
+            if window.last_opened_at.is_some_and(|last| last >= opened_at) {
+                continue;
+            }
+
+            if let Some(row) = ctx
+                .txns()
+                .await?
+                .pg()
+                .query_opt(
+                    "UPDATE maintenance_windows SET last_opened_at = $2
+                        WHERE pk = $1 AND (last_opened_at IS NULL OR last_opened_at < $2)
+                        RETURNING *",
+                    &[&window.pk, &opened_at],
+                )
+                .await?
+            {
+                claimed.push(Self::try_from(row)?);
+            }
+        }
+
+        Ok(claimed)
+    }
+
+    /// Asks the rebaser to dispatch the eligible [`Actions`](Action) on HEAD, by sending it an
+    /// empty set of updates. The context must be for HEAD of the window's workspace.
+    pub async fn wake_dispatch(ctx: &DalContext) -> MaintenanceWindowResult<()> {
+        let updates_address = ctx.write_rebase_batch(RebaseBatch::new(vec![])).await?;
+        ctx.run_async_rebase(ctx.workspace_pk()?, ctx.change_set_id(), updates_address)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn local_now(ctx: &DalContext, timezone: &str) -> MaintenanceWindowResult<NaiveDateTime> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT CLOCK_TIMESTAMP() AT TIME ZONE $1 AS local_now",
+                &[&timezone],
+            )
+            .await?;
+
+        Ok(row.try_get("local_now")?)
+    }
+
+    async fn to_utc(
+        ctx: &DalContext,
+        local: NaiveDateTime,
+        timezone: &str,
+    ) -> MaintenanceWindowResult<DateTime<Utc>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT $1::timestamp AT TIME ZONE $2 AS at",
+                &[&local, &timezone],
+            )
+            .await?;
+
+        Ok(row.try_get("at")?)
+    }
+
+    async fn validate(
+        ctx: &DalContext,
+        definition: &MaintenanceWindowDefinition,
+    ) -> MaintenanceWindowResult<()> {
+        if definition.name.trim().is_empty() {
+            return Err(MaintenanceWindowError::NameEmpty);
+        }
+        CronSchedule::from_str(&definition.schedule)?;
+        if !(1..=MAX_DURATION_MINUTES).contains(&definition.duration_minutes) {
+            return Err(MaintenanceWindowError::InvalidDuration(
+                definition.duration_minutes,
+            ));
+        }
+        if ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT name FROM pg_timezone_names WHERE name = $1",
+                &[&definition.timezone],
+            )
+            .await?
+            .is_none()
+        {
+            return Err(MaintenanceWindowError::InvalidTimezone(
+                definition.timezone.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Lets an [`Action`] be dispatched while the maintenance windows covering it are closed. Only
+/// users with the approval permission should be able to create one. The override is removed when
+/// the action is dispatched, so it only lets the action through once.
+pub struct MaintenanceWindowOverride;
+
+impl MaintenanceWindowOverride {
+    #[instrument(
+        name = "action.maintenance_window_override.new",
+        level = "info",
+        skip(ctx)
+    )]
+    pub async fn new(ctx: &DalContext, action_id: ActionId) -> MaintenanceWindowResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let approved_by: Option<UserPk> = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => Some(*user_pk),
+            HistoryActor::SystemInit => None,
+        };
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "INSERT INTO maintenance_window_overrides (workspace_pk, action_id, approved_by)
+                    VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                &[&workspace_pk, &action_id, &approved_by],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(ctx: &DalContext, action_id: ActionId) -> MaintenanceWindowResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "DELETE FROM maintenance_window_overrides
+                    WHERE workspace_pk = $1 AND action_id = $2",
+                &[&workspace_pk, &action_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn list_action_ids(ctx: &DalContext) -> MaintenanceWindowResult<HashSet<ActionId>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT action_id FROM maintenance_window_overrides WHERE workspace_pk = $1",
+                &[&workspace_pk],
+            )
+            .await?;
+
+        let mut action_ids = HashSet::new();
+        for row in rows {
+            action_ids.insert(row.try_get("action_id")?);
+        }
+
+        Ok(action_ids)
+    }
+}
+
+/// Why an [`Action`] is being kept [`Queued`](crate::action::ActionState::Queued).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindowHold {
+    /// The windows covering the action, all of which are closed.
+    pub window_ids: Vec<MaintenanceWindowId>,
+    pub window_names: Vec<String>,
+    /// The earliest time one of the windows opens again.
+    pub next_open_at: Option<DateTime<Utc>>,
+}
+
+/// Decides which [`Actions`](Action) on HEAD have to wait for a maintenance window. Outside of
+/// HEAD, nothing is held.
+#[derive(Debug)]
+pub struct MaintenanceWindowGate {
+    windows: Vec<(MaintenanceWindow, MaintenanceWindowStatus)>,
+    overrides: HashSet<ActionId>,
+}
+
+impl MaintenanceWindowGate {
+    pub async fn new(ctx: &DalContext) -> MaintenanceWindowResult<Self> {
+        let mut gate = Self {
+            windows: Vec::new(),
+            overrides: HashSet::new(),
+        };
+        if ctx.get_workspace_default_change_set_id().await? != ctx.change_set_id() {
+            return Ok(gate);
+        }
+
+        for window in MaintenanceWindow::list(ctx).await? {
+            if !window.enabled {
+                continue;
+            }
+            let status = window.status(ctx).await?;
+            gate.windows.push((window, status));
+        }
+        if !gate.windows.is_empty() {
+            gate.overrides = MaintenanceWindowOverride::list_action_ids(ctx).await?;
+        }
+
+        Ok(gate)
+    }
+
+    /// Returns why the [`Action`] has to wait, if every window covering it is closed and its hold
+    /// hasn't been overridden.
+    pub async fn hold_for(
+        &self,
+        ctx: &DalContext,
+        action_id: ActionId,
+    ) -> MaintenanceWindowResult<Option<MaintenanceWindowHold>> {
+        if self.windows.is_empty() || self.overrides.contains(&action_id) {
+            return Ok(None);
+        }
+
+        let (schema_id, label) = match Action::component_id(ctx, action_id)
+            .await
+            .map_err(Box::new)?
+        {
+            Some(component_id) => {
+                let schema_id = Component::schema_for_component_id(ctx, component_id)
+                    .await
+                    .map_err(Box::new)?
+                    .id();
+                let label = Component::schema_variant_for_component_id(ctx, component_id)
+                    .await
+                    .map_err(Box::new)?
+                    .category()
+                    .to_owned();
+                (Some(schema_id), Some(label))
+            }
+            None => (None, None),
+        };
+
+        let covering: Vec<_> = self
+            .windows
+            .iter()
+            .filter(|(window, _)| window.covers(schema_id, label.as_deref()))
+            .collect();
+        if covering.is_empty() || covering.iter().any(|(_, status)| status.is_open()) {
+            return Ok(None);
+        }
+
+        Ok(Some(MaintenanceWindowHold {
+            window_ids: covering.iter().map(|(window, _)| window.pk).collect(),
+            window_names: covering
+                .iter()
+                .map(|(window, _)| window.name.clone())
+                .collect(),
+            next_open_at: covering
+                .iter()
+                .filter_map(|(_, status)| status.next_open_at)
+                .min(),
+        }))
+    }
+}
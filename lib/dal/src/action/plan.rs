@@ -0,0 +1,313 @@
+//! A preview of how the queued [`Actions`](Action) for a workspace will be dispatched: in which
+//! order, with how much parallelism, and why.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::{
+    action::{
+        dependency_graph::{ActionDependencyGraph, ActionDependencyReason},
+        prototype::{ActionKind, ActionPrototype},
+        Action, ActionId, ActionPrototypeId, ActionResult, ActionState,
+    },
+    Component, ComponentId, DalContext,
+};
+
+/// An [`Action`] as it appears in an [`ActionPlan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlanAction {
+    pub id: ActionId,
+    pub prototype_id: ActionPrototypeId,
+    pub name: String,
+    pub kind: ActionKind,
+    pub state: ActionState,
+    pub component_id: Option<ComponentId>,
+    pub component_name: Option<String>,
+}
+
+/// An edge in an [`ActionPlan`]: `action_id` will not run until `depends_on` has succeeded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlanDependency {
+    pub action_id: ActionId,
+    pub depends_on: ActionId,
+    /// The components and sockets that created the dependency, if it came from data flow.
+    pub reason: Option<ActionDependencyReason>,
+}
+
+/// An [`Action`] that will not be dispatched until someone intervenes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlanHeldAction {
+    pub action_id: ActionId,
+    /// The `OnHold` or `Failed` actions this action (transitively) depends on. Empty if the action
+    /// is itself `OnHold` or `Failed`.
+    pub held_by: Vec<ActionId>,
+}
+
+/// The order and parallelism with which the queued [`Actions`](Action) will be dispatched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlan {
+    pub actions: Vec<ActionPlanAction>,
+    /// Groups of actions that can run in parallel. Every action in a wave only depends on actions
+    /// in earlier waves.
+    pub waves: Vec<Vec<ActionId>>,
+    pub dependencies: Vec<ActionPlanDependency>,
+    pub held: Vec<ActionPlanHeldAction>,
+    /// Groups of actions that depend on each other, and so can never run.
+    pub cycles: Vec<Vec<ActionId>>,
+    /// Actions that are not held, but can never run because they are in, or depend on, a cycle.
+    pub blocked_by_cycle: Vec<ActionId>,
+}
+
+impl ActionPlan {
+    /// Build the [`ActionPlan`] for all of the [`Actions`](Action) in the current
+    /// [`WorkspaceSnapshot`](crate::WorkspaceSnapshot).
+    #[instrument(level = "info", name = "action.plan.for_workspace", skip(ctx))]
+    pub async fn for_workspace(ctx: &DalContext) -> ActionResult<Self> {
+        let action_dependency_graph = ActionDependencyGraph::for_workspace(ctx).await?;
+        Self::for_dependency_graph(ctx, &action_dependency_graph).await
+    }
+
+    pub async fn for_dependency_graph(
+        ctx: &DalContext,
+        action_dependency_graph: &ActionDependencyGraph,
+    ) -> ActionResult<Self> {
+        let mut action_ids = action_dependency_graph.remaining_actions();
+        action_ids.sort();
+
+        let mut actions = Vec::with_capacity(action_ids.len());
+        let mut dependencies = Vec::new();
+        let mut held = Vec::new();
+        for &action_id in &action_ids {
+            let action = Action::get_by_id(ctx, action_id).await?;
+            let prototype =
+                ActionPrototype::get_by_id(ctx, Action::prototype_id(ctx, action_id).await?)
+                    .await?;
+            let component_id = Action::component_id(ctx, action_id).await?;
+            let component_name = match component_id {
+                Some(component_id) => Some(Component::name_by_id(ctx, component_id).await?),
+                None => None,
+            };
+
+            let mut depends_on = action_dependency_graph.direct_dependencies_of(action_id);
+            depends_on.sort();
+            for depends_on in depends_on {
+                dependencies.push(ActionPlanDependency {
+                    action_id,
+                    depends_on,
+                    reason: action_dependency_graph.dependency_reason(action_id, depends_on),
+                });
+            }
+
+            let mut held_by =
+                Action::get_hold_status_influenced_by(ctx, action_dependency_graph, action_id)
+                    .await?;
+            held_by.sort();
+            if !held_by.is_empty()
+                || matches!(action.state(), ActionState::OnHold | ActionState::Failed)
+            {
+                held.push(ActionPlanHeldAction { action_id, held_by });
+            }
+
+            actions.push(ActionPlanAction {
+                id: action_id,
+                prototype_id: prototype.id(),
+                name: prototype.name().to_owned(),
+                kind: prototype.kind,
+                state: action.state(),
+                component_id,
+                component_name,
+            });
+        }
+
+        // Held actions are never dispatched, and everything depending on them is held too, so
+        // taking them out of the graph doesn't unblock anything that would otherwise wait.
+        let mut remaining = action_dependency_graph.clone();
+        for held_action in &held {
+            remaining.remove_action(held_action.action_id);
+        }
+
+        let mut waves = Vec::new();
+        loop {
+            let mut wave = remaining.independent_actions();
+            if wave.is_empty() {
+                break;
+            }
+            wave.sort();
+            for &action_id in &wave {
+                remaining.remove_action(action_id);
+            }
+            waves.push(wave);
+        }
+
+        let cycles = action_dependency_graph.cycles();
+        if !cycles.is_empty() {
+            warn!("action plan has {} cycle(s): {:?}", cycles.len(), cycles);
+        }
+        let mut blocked_by_cycle = remaining.remaining_actions();
+        blocked_by_cycle.sort();
+
+        Ok(Self {
+            actions,
+            waves,
+            dependencies,
+            held,
+            cycles,
+            blocked_by_cycle,
+        })
+    }
+
+    /// Whether every action that isn't held will eventually be dispatched.
+    pub fn is_acyclic(&self) -> bool {
+        self.cycles.is_empty()
+    }
+
+    /// Renders the plan as a Graphviz DOT digraph. Edges point in execution order, from each
+    /// action to the actions waiting on it.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph action_plan {\n    rankdir=LR;\n");
+        let labels = self.labels();
+        let mut placed = HashSet::new();
+
+        for (index, wave) in self.waves.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_wave_{index} {{");
+            let _ = writeln!(dot, "        label=\"Wave {}\";", index + 1);
+            for action_id in wave {
+                placed.insert(*action_id);
+                let _ = writeln!(
+                    dot,
+                    "        \"{action_id}\" [label=\"{}\"];",
+                    escape_dot(&labels[action_id])
+                );
+            }
+            dot.push_str("    }\n");
+        }
+        for (action_id, label) in &labels {
+            if placed.contains(action_id) {
+                continue;
+            }
+            let style = if self.is_held(*action_id) {
+                "dashed"
+            } else {
+                "bold"
+            };
+            let color = if self.is_held(*action_id) {
+                "gray"
+            } else {
+                "red"
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{action_id}\" [label=\"{}\", style={style}, color={color}];",
+                escape_dot(label)
+            );
+        }
+        for dependency in &self.dependencies {
+            let inferred = dependency.reason.as_ref().is_some_and(|reason| {
+                !reason.socket_edges.is_empty()
+                    && reason.socket_edges.iter().all(|edge| edge.inferred)
+            });
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"{}\"{};",
+                dependency.depends_on,
+                dependency.action_id,
+                if inferred { " [style=dashed]" } else { "" }
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the plan as a Mermaid flowchart. Edges point in execution order, from each action
+    /// to the actions waiting on it.
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+        let labels = self.labels();
+        let mut placed = HashSet::new();
+
+        for (index, wave) in self.waves.iter().enumerate() {
+            let _ = writeln!(
+                mermaid,
+                "    subgraph wave_{index} [\"Wave {}\"]",
+                index + 1
+            );
+            for action_id in wave {
+                placed.insert(*action_id);
+                let _ = writeln!(
+                    mermaid,
+                    "        a{action_id}[\"{}\"]",
+                    escape_mermaid(&labels[action_id])
+                );
+            }
+            mermaid.push_str("    end\n");
+        }
+        let mut held = Vec::new();
+        let mut blocked = Vec::new();
+        for (action_id, label) in &labels {
+            if placed.contains(action_id) {
+                continue;
+            }
+            let _ = writeln!(mermaid, "    a{action_id}[\"{}\"]", escape_mermaid(label));
+            if self.is_held(*action_id) {
+                held.push(format!("a{action_id}"));
+            } else {
+                blocked.push(format!("a{action_id}"));
+            }
+        }
+        for dependency in &self.dependencies {
+            let _ = writeln!(
+                mermaid,
+                "    a{} --> a{}",
+                dependency.depends_on, dependency.action_id
+            );
+        }
+        if !held.is_empty() {
+            mermaid.push_str("    classDef held stroke-dasharray: 5 5,color:gray\n");
+            let _ = writeln!(mermaid, "    class {} held", held.join(","));
+        }
+        if !blocked.is_empty() {
+            mermaid.push_str("    classDef blocked stroke:red,stroke-width:2px\n");
+            let _ = writeln!(mermaid, "    class {} blocked", blocked.join(","));
+        }
+
+        mermaid
+    }
+
+    fn is_held(&self, action_id: ActionId) -> bool {
+        self.held
+            .iter()
+            .any(|held_action| held_action.action_id == action_id)
+    }
+
+    // e.g. "Create ec2 instance (web server)"
+    fn labels(&self) -> BTreeMap<ActionId, String> {
+        self.actions
+            .iter()
+            .map(|action| {
+                let label = match &action.component_name {
+                    Some(component_name) => {
+                        format!("{} {} ({component_name})", action.kind, action.name)
+                    }
+                    None => format!("{} {}", action.kind, action.name),
+                };
+                (action.id, label)
+            })
+            .collect()
+    }
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
@@ -15,9 +15,11 @@ use crate::{
     diagram::DiagramError,
     func::{
         runner::{FuncRunner, FuncRunnerError},
-        FuncId,
+        timeout::{FuncTimeout, FuncTimeoutError, FuncTimeoutTarget},
+        FuncId, FuncKind,
     },
     implement_add_edge_to,
+    layer_db_types::ContentTypes,
     workspace_snapshot::{
         content_address::ContentAddressDiscriminants,
         node_weight::{
@@ -46,12 +48,16 @@ pub enum ActionPrototypeError {
     FuncRunner(#[from] FuncRunnerError),
     #[error("func runner has failed to send a value and exited")]
     FuncRunnerSend,
+    #[error("func timeout error: {0}")]
+    FuncTimeout(#[from] Box<FuncTimeoutError>),
     #[error("Helper error: {0}")]
     Helper(#[from] HelperError),
     #[error("Layer DB Error: {0}")]
     LayerDb(#[from] LayerDbError),
     #[error("Node Weight error: {0}")]
     NodeWeight(#[from] NodeWeightError),
+    #[error("func {0} is a {1} func, not a plan func")]
+    NotAPlanFunc(FuncId, FuncKind),
     #[error("schema variant error: {0}")]
     SchemaVariant(#[from] SchemaVariantError),
     #[error("schema variant not found for prototype: {0}")]
@@ -228,13 +234,74 @@ impl ActionPrototype {
             if let NodeWeight::Func(node_weight) =
                 ctx.workspace_snapshot()?.get_node_weight(target_id).await?
             {
-                return Ok(node_weight.id().into());
+                if node_weight.func_kind() != FuncKind::Plan {
+                    return Ok(node_weight.id().into());
+                }
             }
         }
 
         Err(ActionPrototypeError::FuncNotFoundForPrototype(id))
     }
 
+    /// Returns the [`FuncId`] of the plan func bound to this prototype, if there is one. The plan
+    /// func describes what running the action would change, without changing anything.
+    pub async fn plan_func_id(
+        ctx: &DalContext,
+        id: ActionPrototypeId,
+    ) -> ActionPrototypeResult<Option<FuncId>> {
+        for (_, _, target_id) in ctx
+            .workspace_snapshot()?
+            .edges_directed_for_edge_weight_kind(id, Outgoing, EdgeWeightKindDiscriminants::Use)
+            .await?
+        {
+            if let NodeWeight::Func(node_weight) =
+                ctx.workspace_snapshot()?.get_node_weight(target_id).await?
+            {
+                if node_weight.func_kind() == FuncKind::Plan {
+                    return Ok(Some(node_weight.id().into()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Binds a plan func to this prototype, replacing the one already bound.
+    pub async fn set_plan_func(
+        ctx: &DalContext,
+        id: ActionPrototypeId,
+        func_id: FuncId,
+    ) -> ActionPrototypeResult<()> {
+        let func_kind = ctx
+            .workspace_snapshot()?
+            .get_node_weight(func_id)
+            .await?
+            .get_func_node_weight()?
+            .func_kind();
+        if func_kind != FuncKind::Plan {
+            return Err(ActionPrototypeError::NotAPlanFunc(func_id, func_kind));
+        }
+
+        Self::remove_plan_func(ctx, id).await?;
+        Self::add_edge_to_func(ctx, id, func_id, EdgeWeightKind::new_use()).await?;
+
+        Ok(())
+    }
+
+    /// Unbinds the plan func from this prototype, if there is one.
+    pub async fn remove_plan_func(
+        ctx: &DalContext,
+        id: ActionPrototypeId,
+    ) -> ActionPrototypeResult<()> {
+        if let Some(plan_func_id) = Self::plan_func_id(ctx, id).await? {
+            ctx.workspace_snapshot()?
+                .remove_edge(id, plan_func_id, EdgeWeightKindDiscriminants::Use)
+                .await?;
+        }
+
+        Ok(())
+    }
+
     /// Lists all [`ActionPrototypes`](ActionPrototype) for a given
     /// [`FuncId`](Func).
     pub async fn list_for_func_id(
@@ -321,8 +388,8 @@ impl ActionPrototype {
             Some(value) => Some(
                 ctx.layer_db()
                     .cas()
-                    .write(
-                        Arc::new(value.into()),
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -336,8 +403,8 @@ impl ActionPrototype {
             Some(value) => Some(
                 ctx.layer_db()
                     .cas()
-                    .write(
-                        Arc::new(value.into()),
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -478,6 +545,9 @@ impl ActionPrototype {
                 .publish_on_commit(ctx)
                 .await?;
         }
+        FuncTimeout::remove_for_target(ctx, FuncTimeoutTarget::ActionPrototype(id))
+            .await
+            .map_err(Box::new)?;
         ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
 
         Ok(())
@@ -0,0 +1,322 @@
+//! Refresh schedules periodically enqueue the refresh [`Actions`](Action) for every
+//! [`Component`] with a resource on HEAD, so that changes made outside of System Initiative are
+//! noticed (see [`ResourceDrift`](crate::component::drift::ResourceDrift)).
+//!
+//! A workspace has at most one schedule without a schema, which applies to every component, and
+//! at most one schedule per [`Schema`](crate::Schema), which overrides it for the components of
+//! that schema. Schedules are stored per workspace rather than per change set, since they only
+//! ever apply to HEAD. Pinga checks for schedules that are due and enqueues the actions.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    action::{
+        prototype::{ActionKind, ActionPrototype, ActionPrototypeError},
+        Action, ActionError,
+    },
+    Component, ComponentError, DalContext, SchemaId, TransactionsError, WorkspacePk, WsEvent,
+    WsEventError,
+};
+
+pub use si_id::RefreshScheduleId;
+
+/// Refreshing more often than this would mostly burn through provider rate limits.
+pub const MIN_INTERVAL_SECONDS: i32 = 300;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum RefreshScheduleError {
+    #[error("action error: {0}")]
+    Action(#[from] Box<ActionError>),
+    #[error("action prototype error: {0}")]
+    ActionPrototype(#[from] Box<ActionPrototypeError>),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
+    #[error("refresh interval must be at least {MIN_INTERVAL_SECONDS} seconds, got {0}")]
+    IntervalTooShort(i32),
+    #[error("refresh schedule not found: {0}")]
+    NotFound(RefreshScheduleId),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("a refresh schedule already exists for schema {0:?}")]
+    ScheduleExists(Option<SchemaId>),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type RefreshScheduleResult<T> = Result<T, RefreshScheduleError>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshSchedule {
+    pk: RefreshScheduleId,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    workspace_pk: WorkspacePk,
+    /// The schema this schedule overrides the workspace schedule for, if any.
+    schema_id: Option<SchemaId>,
+    interval_seconds: i32,
+    enabled: bool,
+    last_run_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PgRow> for RefreshSchedule {
+    type Error = RefreshScheduleError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pk: row.try_get("pk")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            workspace_pk: row.try_get("workspace_pk")?,
+            schema_id: row.try_get("schema_id")?,
+            interval_seconds: row.try_get("interval_seconds")?,
+            enabled: row.try_get("enabled")?,
+            last_run_at: row.try_get("last_run_at")?,
+        })
+    }
+}
+
+impl RefreshSchedule {
+    pub fn pk(&self) -> RefreshScheduleId {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn schema_id(&self) -> Option<SchemaId> {
+        self.schema_id
+    }
+
+    pub fn interval_seconds(&self) -> i32 {
+        self.interval_seconds
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn last_run_at(&self) -> Option<DateTime<Utc>> {
+        self.last_run_at
+    }
+
+    /// Creates the schedule for the workspace, or for one schema in it. A disabled schedule for
+    /// a schema turns off scheduled refreshes for that schema.
+    #[instrument(name = "action.refresh_schedule.new", level = "info", skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        schema_id: Option<SchemaId>,
+        interval_seconds: i32,
+        enabled: bool,
+    ) -> RefreshScheduleResult<Self> {
+        Self::validate(interval_seconds)?;
+        if Self::list(ctx)
+            .await?
+            .iter()
+            .any(|schedule| schedule.schema_id == schema_id)
+        {
+            return Err(RefreshScheduleError::ScheduleExists(schema_id));
+        }
+
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO refresh_schedules (workspace_pk, schema_id, interval_seconds, enabled)
+                    VALUES ($1, $2, $3, $4) RETURNING *",
+                &[&workspace_pk, &schema_id, &interval_seconds, &enabled],
+            )
+            .await?;
+
+        Self::try_from(row)
+    }
+
+    pub async fn get_by_pk(ctx: &DalContext, pk: RefreshScheduleId) -> RefreshScheduleResult<Self> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM refresh_schedules WHERE pk = $1 AND workspace_pk = $2",
+                &[&pk, &workspace_pk],
+            )
+            .await?
+            .ok_or(RefreshScheduleError::NotFound(pk))?;
+
+        Self::try_from(row)
+    }
+
+    /// Lists the schedules for the current workspace, with the workspace schedule first.
+    pub async fn list(ctx: &DalContext) -> RefreshScheduleResult<Vec<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM refresh_schedules
+                    WHERE workspace_pk = $1
+                    ORDER BY schema_id NULLS FIRST",
+                &[&workspace_pk],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    pub async fn update(
+        &mut self,
+        ctx: &DalContext,
+        interval_seconds: i32,
+        enabled: bool,
+    ) -> RefreshScheduleResult<()> {
+        Self::validate(interval_seconds)?;
+
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "UPDATE refresh_schedules
+                    SET interval_seconds = $3, enabled = $4, updated_at = CLOCK_TIMESTAMP()
+                    WHERE pk = $1 AND workspace_pk = $2 RETURNING *",
+                &[&self.pk, &workspace_pk, &interval_seconds, &enabled],
+            )
+            .await?
+            .ok_or(RefreshScheduleError::NotFound(self.pk))?;
+        *self = Self::try_from(row)?;
+
+        Ok(())
+    }
+
+    pub async fn delete(ctx: &DalContext, pk: RefreshScheduleId) -> RefreshScheduleResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "DELETE FROM refresh_schedules WHERE pk = $1 AND workspace_pk = $2",
+                &[&pk, &workspace_pk],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Claims every enabled schedule, across all workspaces, whose interval has passed since it
+    /// last ran, marking it as run now. Claiming and marking happen in one statement, so when
+    /// several pinga instances check at once each schedule is only claimed by one of them (once
+    /// the transaction commits).
+    pub async fn claim_due(ctx: &DalContext) -> RefreshScheduleResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "UPDATE refresh_schedules SET last_run_at = CLOCK_TIMESTAMP()
+                    WHERE enabled
+                    AND (
+                        last_run_at IS NULL
+                        OR last_run_at + make_interval(secs => interval_seconds) <= CLOCK_TIMESTAMP()
+                    )
+                    RETURNING *",
+                &[],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    /// Enqueues the refresh actions for every [`Component`] the schedule covers that has a
+    /// resource, skipping components that already have one queued. Returns how many actions
+    /// were enqueued. The context must be for HEAD of the schedule's workspace.
+    #[instrument(
+        name = "action.refresh_schedule.enqueue_refreshes",
+        level = "info",
+        skip_all,
+        fields(si.workspace.id = %self.workspace_pk, si.schema.id = ?self.schema_id)
+    )]
+    pub async fn enqueue_refreshes(&self, ctx: &DalContext) -> RefreshScheduleResult<usize> {
+        // The workspace schedule does not cover schemas that have a schedule of their own
+        let overridden: HashSet<SchemaId> = match self.schema_id {
+            Some(_) => HashSet::new(),
+            None => Self::list(ctx)
+                .await?
+                .into_iter()
+                .filter_map(|schedule| schedule.schema_id)
+                .collect(),
+        };
+
+        let mut enqueued = 0;
+        for component in Component::list(ctx).await.map_err(Box::new)? {
+            if component.to_delete() || component.resource(ctx).await.map_err(Box::new)?.is_none() {
+                continue;
+            }
+            let schema_id = Component::schema_for_component_id(ctx, component.id())
+                .await
+                .map_err(Box::new)?
+                .id();
+            let covered = match self.schema_id {
+                Some(schedule_schema_id) => schedule_schema_id == schema_id,
+                None => !overridden.contains(&schema_id),
+            };
+            if !covered {
+                continue;
+            }
+
+            let schema_variant_id = Component::schema_variant_id(ctx, component.id())
+                .await
+                .map_err(Box::new)?;
+            for prototype in ActionPrototype::for_variant(ctx, schema_variant_id)
+                .await
+                .map_err(Box::new)?
+            {
+                if prototype.kind != ActionKind::Refresh {
+                    continue;
+                }
+                if Action::find_equivalent(ctx, prototype.id(), Some(component.id()))
+                    .await
+                    .map_err(Box::new)?
+                    .is_some()
+                {
+                    continue;
+                }
+                Action::new(ctx, prototype.id(), Some(component.id()))
+                    .await
+                    .map_err(Box::new)?;
+                enqueued += 1;
+            }
+        }
+
+        if enqueued > 0 {
+            info!(enqueued, "enqueued scheduled refresh actions");
+            WsEvent::action_list_updated(ctx)
+                .await?
+                .publish_on_commit(ctx)
+                .await?;
+        }
+
+        Ok(enqueued)
+    }
+
+    fn validate(interval_seconds: i32) -> RefreshScheduleResult<()> {
+        if interval_seconds < MIN_INTERVAL_SECONDS {
+            return Err(RefreshScheduleError::IntervalTooShort(interval_seconds));
+        }
+        Ok(())
+    }
+}
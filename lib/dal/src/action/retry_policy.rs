@@ -0,0 +1,591 @@
+//! Retry policies let failed [`Actions`](Action) be retried automatically instead of sitting in
+//! [`ActionState::Failed`](super::ActionState::Failed) until someone retries them by hand.
+//!
+//! A policy targets either a single [`ActionPrototype`] or every prototype of a
+//! [`SchemaVariant`]. When both exist, the policy for the prototype wins.
+//!
+//! When an attempt fails and the policy allows another one, the
+//! [`ActionJob`](crate::job::definition::ActionJob) records an [`ActionRetry`] with the time of
+//! the next attempt and leaves the action failed. Pinga claims retries that are due and queues
+//! their actions again, so they go through the dispatcher (and its concurrency groups and
+//! maintenance windows) like any other action, and pending retries survive restarts. Each attempt
+//! is a separate execution of the action function with its own [`FuncRun`](si_events::FuncRun).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use si_events::ulid::Ulid;
+use si_layer_cache::LayerDbError;
+use telemetry::prelude::*;
+use thiserror::Error;
+use veritech_client::{FunctionResultFailureErrorKind, ResourceStatus};
+
+use crate::{
+    action::{
+        prototype::{ActionPrototype, ActionPrototypeError},
+        Action, ActionError, ActionId, ActionState,
+    },
+    layer_db_types::{ActionRetryPolicyContent, ActionRetryPolicyContentV1},
+    workspace_snapshot::{
+        content_address::{ContentAddress, ContentAddressDiscriminants},
+        edge_weight::{EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants},
+        node_weight::{category_node_weight::CategoryNodeKind, NodeWeight, NodeWeightError},
+        WorkspaceSnapshotError,
+    },
+    ActionPrototypeId, ChangeSetId, DalContext, SchemaVariant, SchemaVariantError, SchemaVariantId,
+    Timestamp, TransactionsError, WorkspacePk, WsEvent, WsEventError,
+};
+
+pub use si_id::ActionRetryPolicyId;
+
+/// The longest a policy may wait between attempts. Actions waiting to be retried stay failed, so
+/// longer delays would mostly hide the failure from the people who could fix it.
+pub const MAX_DELAY_MS: u64 = 60 * 60 * 1000;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ActionRetryPolicyError {
+    #[error("action error: {0}")]
+    Action(#[from] Box<ActionError>),
+    #[error("action prototype error: {0}")]
+    ActionPrototype(#[from] Box<ActionPrototypeError>),
+    #[error("max delay cannot be greater than {MAX_DELAY_MS}ms, got {0}ms")]
+    DelayTooLong(u64),
+    #[error("initial delay ({0}ms) cannot be greater than the max delay ({1}ms)")]
+    InvalidDelay(u64, u64),
+    #[error("max attempts must be at least 1")]
+    InvalidMaxAttempts,
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] Box<SchemaVariantError>),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type ActionRetryPolicyResult<T> = Result<T, ActionRetryPolicyError>;
+
+/// What an [`ActionRetryPolicy`] applies to.
+///
+/// NOTE: this is stored in the content store, so only ever add new variants to the end.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ActionRetryPolicyTarget {
+    ActionPrototype(ActionPrototypeId),
+    SchemaVariant(SchemaVariantId),
+}
+
+/// Why an [`Action`] failed, used to decide whether an [`ActionRetryPolicy`] retries it.
+///
+/// NOTE: this is stored in the content store, so only ever add new variants to the end.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ActionFailureKind {
+    /// The function ran, but reported the resource with an error status.
+    ResourceError,
+    /// The function ran, but reported the resource with a warning status.
+    ResourceWarning,
+    ActionFieldWrongType,
+    InvalidReturnType,
+    KilledExecution,
+    UserCodeException,
+    VeritechServer,
+    /// Anything else, such as the function not returning a result at all.
+    Other,
+    /// The function ran for longer than its timeout (see
+    /// [`FuncTimeout`](crate::func::timeout::FuncTimeout)) and was killed.
+    Timeout,
+    /// The function was throttled by the CPU limit of its execution environment and timed out.
+    CpuLimitExceeded,
+    /// The function used more memory than its execution environment allows and was killed.
+    MemoryLimitExceeded,
+    /// The function tried to reach a network destination its workspace's egress policy does not
+    /// allow (see [`FuncEgressPolicy`](crate::func::egress_policy::FuncEgressPolicy)).
+    EgressDenied,
+}
+
+impl ActionFailureKind {
+    /// The failure kind for the status of a resource returned by an action function, if that
+    /// status is a failure.
+    pub fn from_resource_status(status: ResourceStatus) -> Option<Self> {
+        match status {
+            ResourceStatus::Error => Some(Self::ResourceError),
+            ResourceStatus::Ok => None,
+            ResourceStatus::Warning => Some(Self::ResourceWarning),
+        }
+    }
+}
+
+impl From<&FunctionResultFailureErrorKind> for ActionFailureKind {
+    fn from(value: &FunctionResultFailureErrorKind) -> Self {
+        match value {
+            FunctionResultFailureErrorKind::ActionFieldWrongType => Self::ActionFieldWrongType,
+            FunctionResultFailureErrorKind::CpuLimitExceeded => Self::CpuLimitExceeded,
+            FunctionResultFailureErrorKind::EgressDenied => Self::EgressDenied,
+            FunctionResultFailureErrorKind::InvalidReturnType => Self::InvalidReturnType,
+            FunctionResultFailureErrorKind::KilledExecution => Self::KilledExecution,
+            FunctionResultFailureErrorKind::MemoryLimitExceeded => Self::MemoryLimitExceeded,
+            FunctionResultFailureErrorKind::Timeout => Self::Timeout,
+            FunctionResultFailureErrorKind::UserCodeException(_) => Self::UserCodeException,
+            FunctionResultFailureErrorKind::VeritechServer => Self::VeritechServer,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionRetryPolicy {
+    id: ActionRetryPolicyId,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    target: ActionRetryPolicyTarget,
+    /// Total number of times the action may run, including the first attempt.
+    max_attempts: u32,
+    /// Delay before the first retry. Each following retry doubles it, up to `max_delay_ms`.
+    initial_delay_ms: u64,
+    max_delay_ms: u64,
+    /// The failures that are retried. If empty, every failure is retried.
+    retry_on: Vec<ActionFailureKind>,
+}
+
+impl ActionRetryPolicy {
+    fn assemble(id: ActionRetryPolicyId, inner: ActionRetryPolicyContentV1) -> Self {
+        Self {
+            id,
+            timestamp: inner.timestamp,
+            target: inner.target,
+            max_attempts: inner.max_attempts,
+            initial_delay_ms: inner.initial_delay_ms,
+            max_delay_ms: inner.max_delay_ms,
+            retry_on: inner.retry_on,
+        }
+    }
+
+    fn content(&self) -> ActionRetryPolicyContentV1 {
+        ActionRetryPolicyContentV1 {
+            timestamp: self.timestamp,
+            target: self.target,
+            max_attempts: self.max_attempts,
+            initial_delay_ms: self.initial_delay_ms,
+            max_delay_ms: self.max_delay_ms,
+            retry_on: self.retry_on.clone(),
+        }
+    }
+
+    pub fn id(&self) -> ActionRetryPolicyId {
+        self.id
+    }
+
+    pub fn target(&self) -> ActionRetryPolicyTarget {
+        self.target
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn initial_delay_ms(&self) -> u64 {
+        self.initial_delay_ms
+    }
+
+    pub fn max_delay_ms(&self) -> u64 {
+        self.max_delay_ms
+    }
+
+    pub fn retry_on(&self) -> &[ActionFailureKind] {
+        &self.retry_on
+    }
+
+    /// Creates the [`ActionRetryPolicy`] for the target, or replaces the existing one.
+    pub async fn upsert(
+        ctx: &DalContext,
+        target: ActionRetryPolicyTarget,
+        max_attempts: u32,
+        initial_delay_ms: u64,
+        max_delay_ms: u64,
+        retry_on: Vec<ActionFailureKind>,
+    ) -> ActionRetryPolicyResult<Self> {
+        if max_attempts == 0 {
+            return Err(ActionRetryPolicyError::InvalidMaxAttempts);
+        }
+        if initial_delay_ms > max_delay_ms {
+            return Err(ActionRetryPolicyError::InvalidDelay(
+                initial_delay_ms,
+                max_delay_ms,
+            ));
+        }
+        if max_delay_ms > MAX_DELAY_MS {
+            return Err(ActionRetryPolicyError::DelayTooLong(max_delay_ms));
+        }
+        // Make sure the target exists
+        match target {
+            ActionRetryPolicyTarget::ActionPrototype(id) => {
+                ActionPrototype::get_by_id(ctx, id)
+                    .await
+                    .map_err(Box::new)?;
+            }
+            ActionRetryPolicyTarget::SchemaVariant(id) => {
+                SchemaVariant::get_by_id(ctx, id).await.map_err(Box::new)?;
+            }
+        }
+
+        if let Some(mut existing) = Self::find_for_target(ctx, target).await? {
+            existing.max_attempts = max_attempts;
+            existing.initial_delay_ms = initial_delay_ms;
+            existing.max_delay_ms = max_delay_ms;
+            existing.retry_on = retry_on;
+            existing.write(ctx).await?;
+            return Ok(existing);
+        }
+
+        let content = ActionRetryPolicyContentV1 {
+            timestamp: Timestamp::now(),
+            target,
+            max_attempts,
+            initial_delay_ms,
+            max_delay_ms,
+            retry_on,
+        };
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(ActionRetryPolicyContent::V1(content.clone()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let id = workspace_snapshot.generate_ulid().await?;
+        let lineage_id = workspace_snapshot.generate_ulid().await?;
+        let node_weight =
+            NodeWeight::new_content(id, lineage_id, ContentAddress::ActionRetryPolicy(hash));
+        workspace_snapshot.add_or_replace_node(node_weight).await?;
+
+        let category_id = workspace_snapshot
+            .get_or_create_category_node(CategoryNodeKind::ActionRetryPolicy)
+            .await?;
+        workspace_snapshot
+            .add_edge(category_id, EdgeWeight::new(EdgeWeightKind::new_use()), id)
+            .await?;
+
+        Ok(Self::assemble(id.into(), content))
+    }
+
+    pub async fn get_by_id(
+        ctx: &DalContext,
+        id: ActionRetryPolicyId,
+    ) -> ActionRetryPolicyResult<Self> {
+        let ulid: Ulid = id.into();
+        let node_weight = ctx
+            .workspace_snapshot()?
+            .get_node_weight(ulid)
+            .await?
+            .get_content_node_weight_of_kind(ContentAddressDiscriminants::ActionRetryPolicy)?;
+        let hash = node_weight.content_hash();
+
+        let content: ActionRetryPolicyContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&hash)
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(ulid))?;
+
+        let ActionRetryPolicyContent::V1(inner) = content;
+
+        Ok(Self::assemble(id, inner))
+    }
+
+    pub async fn list(ctx: &DalContext) -> ActionRetryPolicyResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let Some(category_id) = workspace_snapshot
+            .get_category_node(None, CategoryNodeKind::ActionRetryPolicy)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut policies = vec![];
+        for policy_idx in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(category_id, EdgeWeightKindDiscriminants::Use)
+            .await?
+        {
+            let id = workspace_snapshot.get_node_weight(policy_idx).await?.id();
+            policies.push(Self::get_by_id(ctx, id.into()).await?);
+        }
+        policies.sort_by_key(|policy| policy.id);
+
+        Ok(policies)
+    }
+
+    pub async fn find_for_target(
+        ctx: &DalContext,
+        target: ActionRetryPolicyTarget,
+    ) -> ActionRetryPolicyResult<Option<Self>> {
+        Ok(Self::list(ctx)
+            .await?
+            .into_iter()
+            .find(|policy| policy.target == target))
+    }
+
+    /// Finds the policy that applies to the [`Action`]: the one for its [`ActionPrototype`] if
+    /// there is one, otherwise the one for the prototype's [`SchemaVariant`].
+    pub async fn find_for_action(
+        ctx: &DalContext,
+        action_id: ActionId,
+    ) -> ActionRetryPolicyResult<Option<Self>> {
+        let policies = Self::list(ctx).await?;
+        if policies.is_empty() {
+            return Ok(None);
+        }
+
+        let prototype_id = Action::prototype_id(ctx, action_id)
+            .await
+            .map_err(Box::new)?;
+        if let Some(policy) = policies
+            .iter()
+            .find(|policy| policy.target == ActionRetryPolicyTarget::ActionPrototype(prototype_id))
+        {
+            return Ok(Some(policy.clone()));
+        }
+
+        let schema_variant_id = ActionPrototype::schema_variant_id(ctx, prototype_id)
+            .await
+            .map_err(Box::new)?;
+        Ok(policies.into_iter().find(|policy| {
+            policy.target == ActionRetryPolicyTarget::SchemaVariant(schema_variant_id)
+        }))
+    }
+
+    pub async fn remove(ctx: &DalContext, id: ActionRetryPolicyId) -> ActionRetryPolicyResult<()> {
+        ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
+
+        Ok(())
+    }
+
+    /// Whether an action that just failed `attempt` times (counting from 1) should be retried
+    /// after a failure of the given kind.
+    pub fn should_retry(&self, attempt: u32, failure: ActionFailureKind) -> bool {
+        attempt < self.max_attempts
+            && (self.retry_on.is_empty() || self.retry_on.contains(&failure))
+    }
+
+    /// How long to wait before the retry that follows the given attempt. The delay grows
+    /// exponentially and is capped at the max delay (and [`MAX_DELAY_MS`]), then jittered so that
+    /// actions which failed together do not all retry at the same moment.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(63);
+        let delay_ms = self
+            .initial_delay_ms
+            .saturating_mul(1u64 << exponent)
+            .min(self.max_delay_ms)
+            .min(MAX_DELAY_MS);
+
+        // "Equal" jitter: wait at least half of the delay, and a random amount of the rest
+        let half = delay_ms / 2;
+        let jitter = rand::thread_rng().gen_range(0..=delay_ms - half);
+        Duration::from_millis(half + jitter)
+    }
+
+    async fn write(&mut self, ctx: &DalContext) -> ActionRetryPolicyResult<()> {
+        self.timestamp.updated_at = chrono::Utc::now();
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(ActionRetryPolicyContent::V1(self.content()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        ctx.workspace_snapshot()?
+            .update_content(self.id.into(), hash)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// A pending or claimed retry of a failed [`Action`], stored per change set since the action only
+/// exists in one. The row also remembers how many attempts have been made, so that the next
+/// [`ActionJob`](crate::job::definition::ActionJob) knows which attempt it is running.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionRetry {
+    workspace_pk: WorkspacePk,
+    change_set_id: ChangeSetId,
+    action_id: ActionId,
+    attempts: u32,
+    next_attempt_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PgRow> for ActionRetry {
+    type Error = ActionRetryPolicyError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let attempts: i32 = row.try_get("attempts")?;
+        Ok(Self {
+            workspace_pk: row.try_get("workspace_pk")?,
+            change_set_id: row.try_get("change_set_id")?,
+            action_id: row.try_get("action_id")?,
+            attempts: attempts.max(0) as u32,
+            next_attempt_at: row.try_get("next_attempt_at")?,
+        })
+    }
+}
+
+impl ActionRetry {
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn change_set_id(&self) -> ChangeSetId {
+        self.change_set_id
+    }
+
+    pub fn action_id(&self) -> ActionId {
+        self.action_id
+    }
+
+    /// How many attempts have been made so far.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// When the next attempt is due, or `None` once the retry has been claimed.
+    pub fn next_attempt_at(&self) -> Option<DateTime<Utc>> {
+        self.next_attempt_at
+    }
+
+    /// Records that the action should be attempted again once the delay has passed, after
+    /// `attempts` attempts.
+    #[instrument(name = "action.retry.schedule", level = "info", skip(ctx))]
+    pub async fn schedule(
+        ctx: &DalContext,
+        action_id: ActionId,
+        attempts: u32,
+        delay: Duration,
+    ) -> ActionRetryPolicyResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let change_set_id = ctx.change_set_id();
+        let attempts = attempts.min(i32::MAX as u32) as i32;
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "INSERT INTO action_retries
+                    (workspace_pk, change_set_id, action_id, attempts, next_attempt_at)
+                    VALUES ($1, $2, $3, $4, CLOCK_TIMESTAMP() + make_interval(secs => $5))
+                    ON CONFLICT (workspace_pk, change_set_id, action_id) DO UPDATE
+                    SET attempts = EXCLUDED.attempts,
+                        next_attempt_at = EXCLUDED.next_attempt_at,
+                        updated_at = CLOCK_TIMESTAMP()",
+                &[
+                    &workspace_pk,
+                    &change_set_id,
+                    &action_id,
+                    &attempts,
+                    &delay.as_secs_f64(),
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// How many attempts have been made so far for the action, if it has been retried.
+    pub async fn attempts_for(
+        ctx: &DalContext,
+        action_id: ActionId,
+    ) -> ActionRetryPolicyResult<Option<u32>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM action_retries
+                    WHERE workspace_pk = $1 AND change_set_id = $2 AND action_id = $3",
+                &[&workspace_pk, &ctx.change_set_id(), &action_id],
+            )
+            .await?;
+
+        Ok(match maybe_row {
+            Some(row) => Some(Self::try_from(row)?.attempts),
+            None => None,
+        })
+    }
+
+    /// Forgets the retries of the action, once it succeeded or will not be retried anymore.
+    pub async fn delete(ctx: &DalContext, action_id: ActionId) -> ActionRetryPolicyResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "DELETE FROM action_retries
+                    WHERE workspace_pk = $1 AND change_set_id = $2 AND action_id = $3",
+                &[&workspace_pk, &ctx.change_set_id(), &action_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Claims every retry, across all workspaces, whose next attempt is due. As with
+    /// [`RefreshSchedule::claim_due`](crate::action::refresh_schedule::RefreshSchedule::claim_due),
+    /// claiming happens in one statement, so each retry is only claimed by one pinga instance.
+    pub async fn claim_due(ctx: &DalContext) -> ActionRetryPolicyResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "UPDATE action_retries
+                    SET next_attempt_at = NULL, updated_at = CLOCK_TIMESTAMP()
+                    WHERE next_attempt_at <= CLOCK_TIMESTAMP()
+                    RETURNING *",
+                &[],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    /// Queues the action again so that the dispatcher picks it up. Actions that were removed,
+    /// or are no longer failed (such as ones retried by hand in the meantime), are left alone.
+    /// The context must be for the retry's change set. Returns whether the action was queued.
+    pub async fn requeue(&self, ctx: &DalContext) -> ActionRetryPolicyResult<bool> {
+        let snapshot = ctx.workspace_snapshot()?;
+        if !snapshot.node_exists(self.action_id).await {
+            Self::delete(ctx, self.action_id).await?;
+            return Ok(false);
+        }
+        let action = Action::get_by_id(ctx, self.action_id)
+            .await
+            .map_err(Box::new)?;
+        if action.state() != ActionState::Failed {
+            return Ok(false);
+        }
+
+        Action::set_state(ctx, self.action_id, ActionState::Queued)
+            .await
+            .map_err(Box::new)?;
+        WsEvent::action_list_updated(ctx)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,152 @@
+//! Lets whoever applies a change set decide what happens to each of the [`Actions`](Action) it
+//! contains, instead of running all of them as soon as the change set is applied. For example, a
+//! resize can be applied to the model now, and its action held until the maintenance window.
+//!
+//! The selection is made in the change set itself, before it is approved, so approvers sign off on
+//! what will run along with everything else, and it is merged to HEAD with the rest of the change
+//! set.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use telemetry::prelude::*;
+
+use crate::{
+    action::{
+        dependency_graph::ActionDependencyGraph, Action, ActionError, ActionId, ActionResult,
+        ActionState,
+    },
+    DalContext, WsEvent,
+};
+
+/// What to do with a pending [`Action`] when its change set is applied.
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Display)]
+#[serde(rename_all = "camelCase")]
+pub enum ActionSelection {
+    /// Run the action as soon as the change set is applied.
+    Run,
+    /// Put the action on hold, so it only runs once someone releases it.
+    Hold,
+    /// Remove the action, so it never runs.
+    Drop,
+}
+
+/// The actions each [`ActionSelection`] was applied to, for the audit log.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionSelectionSummary {
+    pub run: Vec<ActionId>,
+    pub held: Vec<ActionId>,
+    pub dropped: Vec<ActionId>,
+}
+
+impl ActionSelectionSummary {
+    pub fn is_empty(&self) -> bool {
+        self.run.is_empty() && self.held.is_empty() && self.dropped.is_empty()
+    }
+}
+
+impl ActionSelection {
+    /// Applies the selections to the pending [`Actions`](Action) of the current change set.
+    /// Actions without a selection are left as they are.
+    ///
+    /// Every selected action must have been enqueued in the current change set and not have
+    /// started yet, and an action selected to run can't depend on one that will be on hold, since
+    /// it would never be dispatched. Nothing is changed if any selection is invalid.
+    #[instrument(name = "action.selection.apply", level = "info", skip(ctx))]
+    pub async fn apply(
+        ctx: &DalContext,
+        selections: &HashMap<ActionId, ActionSelection>,
+    ) -> ActionResult<ActionSelectionSummary> {
+        let change_set_id = ctx.change_set_id();
+        let mut on_hold = HashSet::new();
+        let mut pending = HashSet::new();
+        for action_id in Action::all_ids(ctx).await? {
+            let action = Action::get_by_id(ctx, action_id).await?;
+            match action.state() {
+                ActionState::OnHold => {
+                    on_hold.insert(action_id);
+                }
+                ActionState::Queued => {}
+                _ => continue,
+            }
+            if action.originating_changeset_id() == change_set_id {
+                pending.insert(action_id);
+            }
+        }
+
+        let mut summary = ActionSelectionSummary::default();
+        for (&action_id, &selection) in selections {
+            if !pending.contains(&action_id) {
+                return Err(ActionError::ActionNotPendingInChangeSet(action_id));
+            }
+            match selection {
+                Self::Run => summary.run.push(action_id),
+                Self::Hold => summary.held.push(action_id),
+                Self::Drop => summary.dropped.push(action_id),
+            }
+        }
+        summary.run.sort();
+        summary.held.sort();
+        summary.dropped.sort();
+
+        // Actions already on hold stay that way unless they were selected
+        let held: HashSet<ActionId> = on_hold
+            .into_iter()
+            .filter(|action_id| !selections.contains_key(action_id))
+            .chain(summary.held.iter().copied())
+            .collect();
+        let action_dependency_graph = ActionDependencyGraph::for_workspace(ctx).await?;
+        for &action_id in &summary.run {
+            if let Some(held_action_id) =
+                Self::held_dependency(&action_dependency_graph, action_id, &held)
+            {
+                return Err(ActionError::SelectedActionDependsOnHeldAction {
+                    action_id,
+                    held_action_id,
+                });
+            }
+        }
+
+        for &action_id in &summary.run {
+            Action::set_state(ctx, action_id, ActionState::Queued).await?;
+        }
+        for &action_id in &summary.held {
+            Action::set_state(ctx, action_id, ActionState::OnHold).await?;
+        }
+        for &action_id in &summary.dropped {
+            Action::remove_by_id(ctx, action_id).await?;
+        }
+
+        if !summary.is_empty() {
+            WsEvent::action_list_updated(ctx)
+                .await?
+                .publish_on_commit(ctx)
+                .await?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Finds a held action that `action_id` depends on, directly or transitively.
+    fn held_dependency(
+        action_dependency_graph: &ActionDependencyGraph,
+        action_id: ActionId,
+        held: &HashSet<ActionId>,
+    ) -> Option<ActionId> {
+        let mut seen = HashSet::new();
+        let mut work_queue =
+            VecDeque::from(action_dependency_graph.direct_dependencies_of(action_id));
+        while let Some(dependency_id) = work_queue.pop_front() {
+            if held.contains(&dependency_id) {
+                return Some(dependency_id);
+            }
+            if seen.insert(dependency_id) {
+                work_queue.extend(action_dependency_graph.direct_dependencies_of(dependency_id));
+            }
+        }
+
+        None
+    }
+}
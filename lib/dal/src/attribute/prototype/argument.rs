@@ -26,11 +26,14 @@ use crate::{
         WorkspaceSnapshotError,
     },
     AttributePrototype, AttributePrototypeId, AttributeValue, ComponentId, DalContext, HelperError,
-    OutputSocketId, PropId, SecretId, Timestamp, TransactionsError,
+    OutputSocketId, PropId, SecretId, Timestamp, TransactionsError, WorkspaceVariableId,
 };
 
 use self::{
     static_value::{StaticArgumentValue, StaticArgumentValueId},
+    subscription::{
+        AttributeSubscription, AttributeSubscriptionError, AttributeSubscriptionId, ComponentFilter,
+    },
     value_source::ValueSource,
 };
 
@@ -38,6 +41,7 @@ use super::AttributePrototypeError;
 pub use crate::workspace_snapshot::node_weight::attribute_prototype_argument_node_weight::ArgumentTargets;
 
 pub mod static_value;
+pub mod subscription;
 pub mod value_source;
 
 pub use si_id::AttributePrototypeArgumentId;
@@ -47,6 +51,8 @@ pub use si_id::AttributePrototypeArgumentId;
 pub enum AttributePrototypeArgumentError {
     #[error("attribute prototype error: {0}")]
     AttributePrototype(#[from] AttributePrototypeError),
+    #[error("attribute subscription error: {0}")]
+    AttributeSubscription(#[from] Box<AttributeSubscriptionError>),
     #[error("attribute value error: {0}")]
     AttributeValue(String),
     #[error("change set error: {0}")]
@@ -382,6 +388,12 @@ impl AttributePrototypeArgument {
                         ContentAddressDiscriminants::StaticArgumentValue => {
                             ValueSource::StaticArgumentValue(inner.id().into())
                         }
+                        ContentAddressDiscriminants::AttributeSubscription => {
+                            ValueSource::Subscription(inner.id().into())
+                        }
+                        ContentAddressDiscriminants::WorkspaceVariable => {
+                            ValueSource::WorkspaceVariable(inner.id().into())
+                        }
                         other => {
                             return Err(
                                 AttributePrototypeArgumentError::UnexpectedValueSourceContent(
@@ -412,6 +424,7 @@ impl AttributePrototypeArgument {
         value_source: ValueSource,
     ) -> AttributePrototypeArgumentResult<()> {
         let workspace_snapshot = ctx.workspace_snapshot()?;
+        let previous_value_source = Self::value_source_by_id(ctx, apa_id).await?;
 
         for existing_value_source in workspace_snapshot
             .outgoing_targets_for_edge_weight_kind(
@@ -437,6 +450,12 @@ impl AttributePrototypeArgument {
         )
         .await?;
 
+        if let Some(ValueSource::Subscription(subscription_id)) = previous_value_source {
+            AttributeSubscription::remove_if_unused(ctx, subscription_id)
+                .await
+                .map_err(Box::new)?;
+        }
+
         Ok(())
     }
 
@@ -540,6 +559,41 @@ impl AttributePrototypeArgument {
             .await
     }
 
+    pub async fn set_value_from_subscription_id(
+        self,
+        ctx: &DalContext,
+        subscription_id: AttributeSubscriptionId,
+    ) -> AttributePrototypeArgumentResult<Self> {
+        Self::set_value_source(ctx, self.id, subscription_id.into())
+            .await
+            .and(Ok(self))
+    }
+
+    /// Creates a new [`AttributeSubscription`] and uses it as the value source for this argument.
+    pub async fn set_value_from_subscription(
+        self,
+        ctx: &DalContext,
+        filters: Vec<ComponentFilter>,
+        select: Vec<String>,
+    ) -> AttributePrototypeArgumentResult<Self> {
+        let subscription = AttributeSubscription::new(ctx, filters, select)
+            .await
+            .map_err(Box::new)?;
+
+        self.set_value_from_subscription_id(ctx, subscription.id())
+            .await
+    }
+
+    pub async fn set_value_from_workspace_variable_id(
+        self,
+        ctx: &DalContext,
+        workspace_variable_id: WorkspaceVariableId,
+    ) -> AttributePrototypeArgumentResult<Self> {
+        Self::set_value_source(ctx, self.id, workspace_variable_id.into())
+            .await
+            .and(Ok(self))
+    }
+
     pub async fn list_ids_for_prototype(
         ctx: &DalContext,
         prototype_id: AttributePrototypeId,
@@ -681,9 +735,17 @@ impl AttributePrototypeArgument {
             avs_to_update.retain(|av_id| av_ids_to_keep.contains(av_id));
         }
 
+        let value_source = Self::value_source_by_id(ctx, self.id).await?;
+
         // Remove the argument
         ctx.workspace_snapshot()?.remove_node_by_id(self.id).await?;
 
+        if let Some(ValueSource::Subscription(subscription_id)) = value_source {
+            AttributeSubscription::remove_if_unused(ctx, subscription_id)
+                .await
+                .map_err(Box::new)?;
+        }
+
         // Enqueue a dependent values update with the destination attribute values
         ctx.add_dependent_values_and_enqueue(avs_to_update).await?;
 
@@ -0,0 +1,561 @@
+//! An [`AttributeSubscription`] is a [`ValueSource`](super::value_source::ValueSource) that,
+//! rather than pointing at a single prop or socket, queries across every [`Component`] in the
+//! workspace. Each matching [`Component`] contributes the value found at the "select" path, and
+//! the subscribing function argument receives all of them as an array.
+//!
+//! Because the set of matching [`Components`](Component) can change without any explicit
+//! connection being made, subscribers are tracked as dynamic dependencies by the
+//! [`DependentValueGraph`](crate::attribute::value::DependentValueGraph).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_events::ulid::Ulid;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    attribute::prototype::AttributePrototypeError,
+    attribute::value::AttributeValueError,
+    layer_db_types::{
+        AttributeSubscriptionContent, AttributeSubscriptionContentV1, ComponentFilterContentV1,
+    },
+    prop::{PropError, PropPath},
+    workspace_snapshot::{
+        content_address::ContentAddress,
+        edge_weight::{EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants},
+        node_weight::{category_node_weight::CategoryNodeKind, NodeWeight, NodeWeightError},
+        WorkspaceSnapshotError,
+    },
+    AttributePrototype, AttributeValue, AttributeValueId, Component, ComponentError, ComponentId,
+    DalContext, Prop, SchemaId, Timestamp, TransactionsError,
+};
+
+use super::AttributePrototypeArgument;
+
+pub use si_id::AttributeSubscriptionId;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum AttributeSubscriptionError {
+    #[error("attribute prototype error: {0}")]
+    AttributePrototype(#[from] AttributePrototypeError),
+    #[error("attribute prototype argument error: {0}")]
+    AttributePrototypeArgument(String),
+    #[error("attribute value error: {0}")]
+    AttributeValue(String),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] si_layer_cache::LayerDbError),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("prop error: {0}")]
+    Prop(#[from] PropError),
+    #[error("subscription select path cannot be empty")]
+    SelectPathEmpty,
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+impl From<ComponentError> for AttributeSubscriptionError {
+    fn from(value: ComponentError) -> Self {
+        Self::Component(Box::new(value))
+    }
+}
+
+impl From<AttributeValueError> for AttributeSubscriptionError {
+    fn from(value: AttributeValueError) -> Self {
+        Self::AttributeValue(value.to_string())
+    }
+}
+
+pub type AttributeSubscriptionResult<T> = Result<T, AttributeSubscriptionError>;
+
+/// A single condition a [`Component`] must satisfy to be matched by an [`AttributeSubscription`].
+/// All filters on a subscription must match.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ComponentFilter {
+    /// Matches [`Components`](Component) nested (at any depth) inside the given frame.
+    DescendantOf(ComponentId),
+    /// Matches [`Components`](Component) nested (at any depth) inside the subscribing
+    /// [`Component`], which is expected to be a frame.
+    DescendantOfSubscriber,
+    /// Matches [`Components`](Component) whose value at `path` is exactly `value`.
+    PropValue { path: Vec<String>, value: Value },
+    /// Matches [`Components`](Component) whose schema variant belongs to the given schema.
+    Schema(SchemaId),
+}
+
+impl From<ComponentFilterContentV1> for ComponentFilter {
+    fn from(value: ComponentFilterContentV1) -> Self {
+        match value {
+            ComponentFilterContentV1::Schema(schema_id) => Self::Schema(schema_id),
+            ComponentFilterContentV1::DescendantOf(component_id) => {
+                Self::DescendantOf(component_id)
+            }
+            ComponentFilterContentV1::DescendantOfSubscriber => Self::DescendantOfSubscriber,
+            ComponentFilterContentV1::PropValue { path, value } => Self::PropValue {
+                path,
+                value: value.into(),
+            },
+        }
+    }
+}
+
+impl From<ComponentFilter> for ComponentFilterContentV1 {
+    fn from(value: ComponentFilter) -> Self {
+        match value {
+            ComponentFilter::Schema(schema_id) => Self::Schema(schema_id),
+            ComponentFilter::DescendantOf(component_id) => Self::DescendantOf(component_id),
+            ComponentFilter::DescendantOfSubscriber => Self::DescendantOfSubscriber,
+            ComponentFilter::PropValue { path, value } => Self::PropValue {
+                path,
+                value: value.into(),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AttributeSubscription {
+    pub id: AttributeSubscriptionId,
+    pub timestamp: Timestamp,
+    /// The conditions a [`Component`] must satisfy to be included.
+    pub filters: Vec<ComponentFilter>,
+    /// The prop path (e.g. `["root", "domain", "subnetId"]`) whose value is yielded for every
+    /// matching [`Component`].
+    pub select: Vec<String>,
+}
+
+impl AttributeSubscription {
+    pub fn assemble(id: AttributeSubscriptionId, inner: AttributeSubscriptionContentV1) -> Self {
+        Self {
+            id,
+            timestamp: inner.timestamp,
+            filters: inner.filters.into_iter().map(Into::into).collect(),
+            select: inner.select,
+        }
+    }
+
+    pub fn id(&self) -> AttributeSubscriptionId {
+        self.id
+    }
+
+    pub async fn new(
+        ctx: &DalContext,
+        filters: Vec<ComponentFilter>,
+        select: Vec<String>,
+    ) -> AttributeSubscriptionResult<Self> {
+        if select.is_empty() {
+            return Err(AttributeSubscriptionError::SelectPathEmpty);
+        }
+
+        let timestamp = Timestamp::now();
+        let content = AttributeSubscriptionContentV1 {
+            timestamp,
+            filters: filters.into_iter().map(Into::into).collect(),
+            select,
+        };
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(AttributeSubscriptionContent::V1(content.clone()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let id = workspace_snapshot.generate_ulid().await?;
+        let lineage_id = workspace_snapshot.generate_ulid().await?;
+        let node_weight =
+            NodeWeight::new_content(id, lineage_id, ContentAddress::AttributeSubscription(hash));
+        workspace_snapshot.add_or_replace_node(node_weight).await?;
+
+        let category_id = workspace_snapshot
+            .get_or_create_category_node(CategoryNodeKind::AttributeSubscription)
+            .await?;
+        workspace_snapshot
+            .add_edge(category_id, EdgeWeight::new(EdgeWeightKind::new_use()), id)
+            .await?;
+
+        Ok(Self::assemble(id.into(), content))
+    }
+
+    pub async fn get_by_id(
+        ctx: &DalContext,
+        id: AttributeSubscriptionId,
+    ) -> AttributeSubscriptionResult<Self> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        let ulid: Ulid = id.into();
+        let node_weight = workspace_snapshot.get_node_weight(ulid).await?;
+        let hash = node_weight.content_hash();
+
+        let content: AttributeSubscriptionContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&hash)
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(ulid))?;
+
+        let AttributeSubscriptionContent::V1(inner) = content;
+
+        Ok(Self::assemble(id, inner))
+    }
+
+    /// Lists every [`AttributeSubscription`] in the workspace.
+    pub async fn list(ctx: &DalContext) -> AttributeSubscriptionResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let Some(category_id) = workspace_snapshot
+            .get_category_node(None, CategoryNodeKind::AttributeSubscription)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut subscriptions = vec![];
+        for subscription_idx in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(category_id, EdgeWeightKindDiscriminants::Use)
+            .await?
+        {
+            let id = workspace_snapshot
+                .get_node_weight(subscription_idx)
+                .await?
+                .id();
+            subscriptions.push(Self::get_by_id(ctx, id.into()).await?);
+        }
+
+        Ok(subscriptions)
+    }
+
+    /// Removes the [`AttributeSubscription`] if no argument uses it as its value source anymore.
+    /// Unlike other value sources, subscriptions are kept in the graph by their category node, so
+    /// they have to be removed along with their last argument.
+    pub async fn remove_if_unused(
+        ctx: &DalContext,
+        id: AttributeSubscriptionId,
+    ) -> AttributeSubscriptionResult<()> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        if workspace_snapshot
+            .incoming_sources_for_edge_weight_kind(
+                id,
+                EdgeWeightKindDiscriminants::PrototypeArgumentValue,
+            )
+            .await?
+            .is_empty()
+        {
+            workspace_snapshot.remove_node_by_id(id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the [`AttributeValueIds`](AttributeValue) whose prototypes take this subscription
+    /// as an argument (i.e. the values that must be recomputed when the subscription's results
+    /// change).
+    pub async fn subscriber_attribute_value_ids(
+        ctx: &DalContext,
+        id: AttributeSubscriptionId,
+    ) -> AttributeSubscriptionResult<Vec<AttributeValueId>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let apa_idxs = workspace_snapshot
+            .incoming_sources_for_edge_weight_kind(
+                id,
+                EdgeWeightKindDiscriminants::PrototypeArgumentValue,
+            )
+            .await?;
+
+        let mut attribute_value_ids = Vec::new();
+        for apa_idx in apa_idxs {
+            let apa = workspace_snapshot
+                .get_node_weight(apa_idx)
+                .await?
+                .get_attribute_prototype_argument_node_weight()?;
+            let prototype_id =
+                AttributePrototypeArgument::prototype_id_for_argument_id(ctx, apa.id().into())
+                    .await
+                    .map_err(|e| {
+                        AttributeSubscriptionError::AttributePrototypeArgument(e.to_string())
+                    })?;
+            for attribute_value_id in
+                AttributePrototype::attribute_value_ids(ctx, prototype_id).await?
+            {
+                // Component specific arguments only apply to their destination component.
+                if let Some(targets) = apa.targets() {
+                    if AttributeValue::component_id(ctx, attribute_value_id).await?
+                        != targets.destination_component_id
+                    {
+                        continue;
+                    }
+                }
+                attribute_value_ids.push(attribute_value_id);
+            }
+        }
+
+        Ok(attribute_value_ids)
+    }
+
+    /// Returns the ids of the [`AttributeSubscriptions`](AttributeSubscription) whose results could
+    /// change when the given [`Component`] is removed, moved in the frame tree, or enters or leaves
+    /// the `to_delete` state. Moving or removing a frame moves its descendants too, so they are
+    /// checked along with it.
+    pub async fn list_ids_affected_by_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> AttributeSubscriptionResult<Vec<AttributeSubscriptionId>> {
+        let subscriptions = Self::list(ctx).await?;
+        if subscriptions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut component_ids = vec![component_id];
+        component_ids.extend(Component::get_all_descendants_for_id(ctx, component_id).await?);
+
+        let mut affected_ids = vec![];
+        for subscription in subscriptions {
+            for &candidate_component_id in &component_ids {
+                if subscription
+                    .could_match(ctx, candidate_component_id)
+                    .await?
+                {
+                    affected_ids.push(subscription.id);
+                    break;
+                }
+            }
+        }
+
+        Ok(affected_ids)
+    }
+
+    /// Adds the subscribers of the given [`AttributeSubscriptions`](AttributeSubscription) to the
+    /// dependent values update. This is needed when the set of [`Components`](Component) a query
+    /// matches changes since no attribute value on the subscriber side has changed. Subscriptions
+    /// that have been removed in the meantime are skipped.
+    pub async fn enqueue_subscribers(
+        ctx: &DalContext,
+        ids: Vec<AttributeSubscriptionId>,
+    ) -> AttributeSubscriptionResult<()> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        let mut subscriber_ids = HashSet::new();
+        for id in ids {
+            if !workspace_snapshot.node_exists(id).await {
+                continue;
+            }
+            subscriber_ids.extend(Self::subscriber_attribute_value_ids(ctx, id).await?);
+        }
+
+        if subscriber_ids.is_empty() {
+            return Ok(());
+        }
+
+        ctx.add_dependent_values_and_enqueue(subscriber_ids.into_iter().collect())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Adds the subscribers of every [`AttributeSubscription`] that could match the given
+    /// [`Component`] (or its descendants) to the dependent values update. See
+    /// [`Self::list_ids_affected_by_component`].
+    pub async fn enqueue_subscribers_affected_by_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> AttributeSubscriptionResult<()> {
+        let ids = Self::list_ids_affected_by_component(ctx, component_id).await?;
+        Self::enqueue_subscribers(ctx, ids).await
+    }
+
+    /// Whether a change to a value at the given prop path could change the results of this
+    /// subscription, either because it is the selected value or because a filter reads it.
+    pub fn watches_path(&self, path: &[String]) -> bool {
+        if self.select == path {
+            return true;
+        }
+
+        self.filters.iter().any(|filter| match filter {
+            ComponentFilter::PropValue {
+                path: filter_path, ..
+            } => filter_path == path,
+            _ => false,
+        })
+    }
+
+    /// Resolves the subscription from the point of view of the subscribing [`Component`],
+    /// returning the selected [`AttributeValueIds`](AttributeValue) of every matching
+    /// [`Component`], ordered by [`ComponentId`].
+    #[instrument(level = "debug", skip(ctx))]
+    pub async fn resolve(
+        &self,
+        ctx: &DalContext,
+        subscriber_component_id: ComponentId,
+    ) -> AttributeSubscriptionResult<Vec<AttributeValueId>> {
+        let select_path = PropPath::new(&self.select);
+
+        let mut component_ids = Component::list_ids(ctx).await?;
+        component_ids.sort();
+
+        let mut attribute_value_ids = vec![];
+        for component_id in component_ids {
+            // A subscriber never matches itself; otherwise a subscription on a prop the
+            // subscriber also has would depend on its own output.
+            if component_id == subscriber_component_id {
+                continue;
+            }
+
+            // Both "deleted" and not deleted Components can feed data into "deleted" Components.
+            // **ONLY** not deleted Components can feed data into not deleted Components.
+            if !Component::should_data_flow_between_components(
+                ctx,
+                subscriber_component_id,
+                component_id,
+            )
+            .await?
+            {
+                continue;
+            }
+
+            if !self
+                .matches(ctx, subscriber_component_id, component_id)
+                .await?
+            {
+                continue;
+            }
+
+            let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
+            let Some(prop_id) =
+                Prop::find_prop_id_by_path_opt(ctx, schema_variant_id, &select_path).await?
+            else {
+                continue;
+            };
+
+            attribute_value_ids
+                .extend(Component::attribute_values_for_prop_id(ctx, component_id, prop_id).await?);
+        }
+
+        Ok(attribute_value_ids)
+    }
+
+    /// Returns true if the candidate [`Component`] satisfies every filter on this subscription.
+    pub async fn matches(
+        &self,
+        ctx: &DalContext,
+        subscriber_component_id: ComponentId,
+        candidate_component_id: ComponentId,
+    ) -> AttributeSubscriptionResult<bool> {
+        for filter in &self.filters {
+            let matched = match filter {
+                ComponentFilter::Schema(schema_id) => {
+                    Self::schema_matches(ctx, candidate_component_id, *schema_id).await?
+                }
+                ComponentFilter::DescendantOf(frame_id) => {
+                    Self::is_descendant_of(ctx, candidate_component_id, *frame_id).await?
+                }
+                ComponentFilter::DescendantOfSubscriber => {
+                    Self::is_descendant_of(ctx, candidate_component_id, subscriber_component_id)
+                        .await?
+                }
+                ComponentFilter::PropValue { path, value } => {
+                    Self::prop_value_matches(ctx, candidate_component_id, path, value).await?
+                }
+            };
+
+            if !matched {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns true if the candidate [`Component`] satisfies every filter on this subscription
+    /// that does not depend on the frame tree, i.e. if it matches for some subscriber and some
+    /// placement of the candidate in the frame tree.
+    async fn could_match(
+        &self,
+        ctx: &DalContext,
+        candidate_component_id: ComponentId,
+    ) -> AttributeSubscriptionResult<bool> {
+        for filter in &self.filters {
+            let matched = match filter {
+                ComponentFilter::Schema(schema_id) => {
+                    Self::schema_matches(ctx, candidate_component_id, *schema_id).await?
+                }
+                ComponentFilter::DescendantOf(_) | ComponentFilter::DescendantOfSubscriber => true,
+                ComponentFilter::PropValue { path, value } => {
+                    Self::prop_value_matches(ctx, candidate_component_id, path, value).await?
+                }
+            };
+
+            if !matched {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn schema_matches(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        schema_id: SchemaId,
+    ) -> AttributeSubscriptionResult<bool> {
+        let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
+        Ok(ctx
+            .workspace_snapshot()?
+            .schema_id_for_schema_variant_id(schema_variant_id)
+            .await?
+            == schema_id)
+    }
+
+    async fn prop_value_matches(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        path: &[String],
+        value: &Value,
+    ) -> AttributeSubscriptionResult<bool> {
+        let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
+        let Some(prop_id) =
+            Prop::find_prop_id_by_path_opt(ctx, schema_variant_id, &PropPath::new(path)).await?
+        else {
+            return Ok(false);
+        };
+
+        for attribute_value_id in
+            Component::attribute_values_for_prop_id(ctx, component_id, prop_id).await?
+        {
+            let view = AttributeValue::get_by_id(ctx, attribute_value_id)
+                .await?
+                .view(ctx)
+                .await?;
+            if view.as_ref() == Some(value) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    async fn is_descendant_of(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        frame_id: ComponentId,
+    ) -> AttributeSubscriptionResult<bool> {
+        let mut cursor = component_id;
+        while let Some(parent_id) = Component::get_parent_by_id(ctx, cursor).await? {
+            if parent_id == frame_id {
+                return Ok(true);
+            }
+            cursor = parent_id;
+        }
+
+        Ok(false)
+    }
+}
@@ -11,10 +11,10 @@ use crate::{
         output::OutputSocketError,
     },
     AttributeValue, AttributeValueId, ComponentError, ComponentId, DalContext, InputSocket,
-    OutputSocket, OutputSocketId, Prop, PropId, SecretId,
+    OutputSocket, OutputSocketId, Prop, PropId, SecretId, WorkspaceVariableId,
 };
 
-use super::static_value::StaticArgumentValueId;
+use super::{static_value::StaticArgumentValueId, subscription::AttributeSubscriptionId};
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -47,6 +47,8 @@ pub enum ValueSource {
     Prop(PropId),
     Secret(SecretId),
     StaticArgumentValue(StaticArgumentValueId),
+    Subscription(AttributeSubscriptionId),
+    WorkspaceVariable(WorkspaceVariableId),
 }
 
 impl From<ValueSource> for si_events::ulid::Ulid {
@@ -80,6 +82,16 @@ impl From<StaticArgumentValueId> for ValueSource {
         Self::StaticArgumentValue(id)
     }
 }
+impl From<AttributeSubscriptionId> for ValueSource {
+    fn from(id: AttributeSubscriptionId) -> Self {
+        Self::Subscription(id)
+    }
+}
+impl From<WorkspaceVariableId> for ValueSource {
+    fn from(id: WorkspaceVariableId) -> Self {
+        Self::WorkspaceVariable(id)
+    }
+}
 
 impl ValueSource {
     async fn all_attribute_values_everywhere(
@@ -99,7 +111,7 @@ impl ValueSource {
                     .await?
             }
             Self::Secret(_) => return Err(ValueSourceError::SourceHasNoValues(*self)),
-            Self::StaticArgumentValue(_) => {
+            Self::StaticArgumentValue(_) | Self::Subscription(_) | Self::WorkspaceVariable(_) => {
                 return Err(ValueSourceError::SourceHasNoValues(*self));
             }
         })
@@ -112,6 +124,8 @@ impl ValueSource {
             ValueSource::Prop(id) => id.into(),
             ValueSource::Secret(id) => id.into(),
             ValueSource::StaticArgumentValue(id) => id.into(),
+            ValueSource::Subscription(id) => id.into(),
+            ValueSource::WorkspaceVariable(id) => id.into(),
         }
     }
 
@@ -184,6 +198,12 @@ impl fmt::Display for ValueSource {
             ValueSource::StaticArgumentValue(id) => {
                 write!(f, "ValueSource::StaticArgumentValue({id})")
             }
+            ValueSource::Subscription(id) => {
+                write!(f, "ValueSource::Subscription({id})")
+            }
+            ValueSource::WorkspaceVariable(id) => {
+                write!(f, "ValueSource::WorkspaceVariable({id})")
+            }
         }
     }
 }
@@ -14,9 +14,11 @@ use crate::socket::input::InputSocketError;
 use crate::socket::output::OutputSocketError;
 use crate::workspace_snapshot::node_weight::NodeWeightError;
 use crate::workspace_snapshot::WorkspaceSnapshotError;
+use crate::workspace_variable::{WorkspaceVariable, WorkspaceVariableError};
 use crate::{
     attribute::prototype::argument::{
         static_value::StaticArgumentValue,
+        subscription::{AttributeSubscription, AttributeSubscriptionError},
         value_source::{ValueSource, ValueSourceError},
         AttributePrototypeArgument, AttributePrototypeArgumentError,
     },
@@ -36,6 +38,8 @@ pub enum AttributePrototypeDebugViewError {
     AttributePrototypeArgumentError(#[from] AttributePrototypeArgumentError),
     #[error("attribute prototype error: {0}")]
     AttributePrototypeError(#[from] AttributePrototypeError),
+    #[error("attribute subscription error: {0}")]
+    AttributeSubscription(#[from] AttributeSubscriptionError),
     #[error("attribute value error: {0}")]
     AttributeValue(#[from] AttributeValueError),
     #[error("component error: {0}")]
@@ -60,6 +64,8 @@ pub enum AttributePrototypeDebugViewError {
     ValueSourceError(#[from] ValueSourceError),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshotError(#[from] WorkspaceSnapshotError),
+    #[error("workspace variable error: {0}")]
+    WorkspaceVariable(#[from] WorkspaceVariableError),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -171,6 +177,45 @@ impl AttributePrototypeDebugView {
                             is_used: arg_used,
                         }]
                     }
+                    ValueSource::WorkspaceVariable(workspace_variable_id) => {
+                        let workspace_variable =
+                            WorkspaceVariable::get_by_id(ctx, workspace_variable_id).await?;
+                        vec![FuncArgDebugView {
+                            value: workspace_variable.value().cloned().unwrap_or(Value::Null),
+                            name: func_arg_name.clone(),
+                            value_source: value_source.to_string(),
+                            value_source_id: workspace_variable_id.into(),
+                            path: None,
+                            socket_source_kind: None,
+                            is_used: arg_used,
+                        }]
+                    }
+                    ValueSource::Subscription(subscription_id) => {
+                        let mut values = vec![];
+
+                        let subscription =
+                            AttributeSubscription::get_by_id(ctx, subscription_id).await?;
+                        for attribute_value_id in
+                            subscription.resolve(ctx, destination_component_id).await?
+                        {
+                            let attribute_value =
+                                AttributeValue::get_by_id(ctx, attribute_value_id).await?;
+                            let prop_path =
+                                AttributeValue::get_path_for_id(ctx, attribute_value_id).await?;
+                            let view = attribute_value.view(ctx).await?.unwrap_or(Value::Null);
+                            values.push(FuncArgDebugView {
+                                value: view,
+                                name: func_arg_name.clone(),
+                                value_source: value_source.to_string(),
+                                value_source_id: subscription_id.into(),
+                                socket_source_kind: None,
+                                path: prop_path,
+                                is_used: arg_used,
+                            });
+                        }
+
+                        values
+                    }
                     ValueSource::Prop(prop_id) => {
                         let mut values = vec![];
 
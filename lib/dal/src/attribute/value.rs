@@ -47,11 +47,11 @@ use petgraph::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use si_events::ulid::Ulid;
-use si_events::FuncRunValue;
+use si_events::{ContentHash, FuncRunValue};
 use si_pkg::{AttributeValuePath, KeyOrIndex};
 use telemetry::prelude::*;
 use thiserror::Error;
-use tokio::sync::{RwLock, TryLockError};
+use tokio::sync::{RwLock, RwLockReadGuard, TryLockError};
 
 pub use dependent_value_graph::DependentValueGraph;
 
@@ -62,6 +62,7 @@ use crate::func::argument::{FuncArgument, FuncArgumentError};
 use crate::func::intrinsics::IntrinsicFunc;
 use crate::func::runner::{FuncRunner, FuncRunnerError};
 use crate::func::FuncExecutionPk;
+use crate::layer_db_types::{ComputedValueContent, ComputedValueContentV1, ContentTypes};
 use crate::prop::PropError;
 use crate::socket::input::InputSocketError;
 use crate::socket::output::OutputSocketError;
@@ -72,6 +73,7 @@ use crate::workspace_snapshot::node_weight::{
     AttributeValueNodeWeight, NodeWeight, NodeWeightDiscriminants, NodeWeightError,
 };
 use crate::workspace_snapshot::{serde_value_to_string_type, WorkspaceSnapshotError};
+use crate::workspace_variable::{WorkspaceVariable, WorkspaceVariableError};
 use crate::{
     implement_add_edge_to, AttributePrototype, AttributePrototypeId, Component, ComponentError,
     ComponentId, DalContext, Func, FuncError, FuncId, HelperError, InputSocket, InputSocketId,
@@ -79,6 +81,7 @@ use crate::{
 };
 
 use super::prototype::argument::static_value::StaticArgumentValue;
+use super::prototype::argument::subscription::{AttributeSubscription, AttributeSubscriptionError};
 use super::prototype::argument::value_source::ValueSourceError;
 use super::prototype::argument::{
     value_source::ValueSource, AttributePrototypeArgument, AttributePrototypeArgumentError,
@@ -108,6 +111,8 @@ pub enum AttributeValueError {
     ),
     #[error("attribute prototype argument {0} has no value source")]
     AttributePrototypeArgumentMissingValueSource(AttributePrototypeArgumentId),
+    #[error("attribute subscription error: {0}")]
+    AttributeSubscription(#[from] Box<AttributeSubscriptionError>),
     #[error("attribute value {0} has no prototype")]
     AttributeValueMissingPrototype(AttributeValueId),
     #[error("attribute value {0} has more than one edge to a prop")]
@@ -222,6 +227,8 @@ pub enum AttributeValueError {
     Workspace(String),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+    #[error("workspace variable error: {0}")]
+    WorkspaceVariable(#[from] Box<WorkspaceVariableError>),
 }
 
 impl From<ComponentError> for AttributeValueError {
@@ -234,6 +241,46 @@ pub type AttributeValueResult<T> = Result<T, AttributeValueError>;
 
 pub use si_id::AttributeValueId;
 
+/// What executing an [`AttributeValue`]'s prototype function produced: the func run's values, the
+/// func, the values that were read to build its arguments, and what it ran with (for funcs that
+/// are not intrinsics).
+pub type PrototypeFunctionExecution = (
+    FuncRunValue,
+    Func,
+    Vec<AttributeValueId>,
+    Option<PrototypeFunctionInputs>,
+);
+
+/// Hashes of the code and arguments a prototype function ran with, which are stored with the
+/// values it produced (see [`ComputedValueContent`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrototypeFunctionInputs {
+    pub code_hash: ContentHash,
+    pub args_hash: ContentHash,
+}
+
+impl PrototypeFunctionInputs {
+    /// Hashes what executing the func with the given arguments depends on. Intrinsics have no
+    /// code, so there is nothing to record for them.
+    pub fn for_execution(func: &Func, args: &Value) -> AttributeValueResult<Option<Self>> {
+        if func.is_intrinsic() {
+            return Ok(None);
+        }
+
+        // The handler and response type change the result as much as the code does
+        let code = serde_json::to_vec(&(
+            func.code_blake3,
+            &func.handler,
+            func.backend_kind,
+            func.backend_response_type,
+        ))?;
+        Ok(Some(Self {
+            code_hash: ContentHash::new(&code),
+            args_hash: ContentHash::new(&serde_json::to_vec(args)?),
+        }))
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct AttributeValue {
     pub id: AttributeValueId,
@@ -525,7 +572,7 @@ impl AttributeValue {
         ctx: &DalContext,
         attribute_value_id: AttributeValueId,
         read_lock: Arc<RwLock<()>>,
-    ) -> AttributeValueResult<(FuncRunValue, Func, Vec<AttributeValueId>)> {
+    ) -> AttributeValueResult<PrototypeFunctionExecution> {
         // When functions are being executed in the dependent values update job,
         // we need to ensure we are not reading our input sources from a graph
         // that is in the process of being mutated on another thread, since it
@@ -537,12 +584,138 @@ impl AttributeValue {
 
         let read_guard = read_lock.read().await;
 
-        // Prepare arguments for prototype function execution.
-        let value_is_for = Self::is_for(ctx, attribute_value_id).await?;
         let (prototype_func_id, prepared_args, input_attribute_value_ids) =
             Self::prepare_arguments_for_prototype_function_execution(ctx, attribute_value_id)
                 .await?;
 
+        Self::execute_prepared_prototype_function(
+            ctx,
+            attribute_value_id,
+            prototype_func_id,
+            prepared_args,
+            input_attribute_value_ids,
+            read_guard,
+        )
+        .await
+    }
+
+    /// Like [`Self::execute_prototype_function`], but returns [`None`] without executing anything
+    /// when the [`AttributeValue`] still holds what the prototype function produced from the same
+    /// code and arguments. See [`Self::prototype_function_inputs_unchanged`] for the conditions
+    /// under which execution is skipped.
+    #[instrument(
+        name = "attribute_value.execute_prototype_function_unless_inputs_unchanged",
+        level = "info",
+        skip_all,
+        fields(
+            si.attribute_value.id = %attribute_value_id,
+        ),
+    )]
+    pub async fn execute_prototype_function_unless_inputs_unchanged(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        read_lock: Arc<RwLock<()>>,
+    ) -> AttributeValueResult<Option<PrototypeFunctionExecution>> {
+        // See the note on locking in "execute_prototype_function".
+        let read_guard = read_lock.read().await;
+
+        let (prototype_func_id, prepared_args, input_attribute_value_ids) =
+            Self::prepare_arguments_for_prototype_function_execution(ctx, attribute_value_id)
+                .await?;
+
+        if Self::prototype_function_inputs_unchanged(
+            ctx,
+            attribute_value_id,
+            prototype_func_id,
+            &prepared_args,
+        )
+        .await?
+        {
+            return Ok(None);
+        }
+
+        Self::execute_prepared_prototype_function(
+            ctx,
+            attribute_value_id,
+            prototype_func_id,
+            prepared_args,
+            input_attribute_value_ids,
+            read_guard,
+        )
+        .await
+        .map(Some)
+    }
+
+    /// Determines whether executing the prototype function with the prepared arguments would be
+    /// redundant. Values computed by a prototype function are stored with the hashes of the code
+    /// and arguments it ran with (see [`ComputedValueContent`]), so we compare against those; a
+    /// value set any other way never matches. Execution is never skipped for intrinsics (they are
+    /// cheap and their values are stored as is), for funcs that opt out via
+    /// [`Func::always_execute`] and for components with before funcs, whose secrets are not part of
+    /// the arguments.
+    async fn prototype_function_inputs_unchanged(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        prototype_func_id: FuncId,
+        prepared_args: &Value,
+    ) -> AttributeValueResult<bool> {
+        let func = Func::get_by_id(ctx, prototype_func_id).await?;
+        let Some(inputs) = PrototypeFunctionInputs::for_execution(&func, prepared_args)? else {
+            return Ok(false);
+        };
+        if func.always_execute {
+            return Ok(false);
+        }
+
+        let component_id = Self::component_id(ctx, attribute_value_id).await?;
+        if FuncRunner::component_has_before_funcs(ctx, component_id)
+            .await
+            .map_err(Box::new)?
+        {
+            return Ok(false);
+        }
+
+        let av_node_weight = ctx
+            .workspace_snapshot()?
+            .get_node_weight(attribute_value_id)
+            .await?
+            .get_attribute_value_node_weight()?;
+        let (Some(value), Some(unprocessed_value)) =
+            (av_node_weight.value(), av_node_weight.unprocessed_value())
+        else {
+            return Ok(false);
+        };
+
+        for address in [value, unprocessed_value] {
+            match ctx
+                .layer_db()
+                .cas()
+                .read(&address.content_hash())
+                .await?
+                .as_deref()
+            {
+                Some(ContentTypes::ComputedValue(ComputedValueContent::V1(content)))
+                    if content.code_hash == inputs.code_hash
+                        && content.args_hash == inputs.args_hash => {}
+                _ => return Ok(false),
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn execute_prepared_prototype_function(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        prototype_func_id: FuncId,
+        prepared_args: Value,
+        input_attribute_value_ids: Vec<AttributeValueId>,
+        read_guard: RwLockReadGuard<'_, ()>,
+    ) -> AttributeValueResult<PrototypeFunctionExecution> {
+        let value_is_for = Self::is_for(ctx, attribute_value_id).await?;
+        let func = Func::get_by_id(ctx, prototype_func_id).await?;
+        let inputs = PrototypeFunctionInputs::for_execution(&func, &prepared_args)?;
+
         let result_channel = FuncRunner::run_attribute_value(
             ctx,
             attribute_value_id,
@@ -590,8 +763,8 @@ impl AttributeValue {
             Some(value) => Some(
                 ctx.layer_db()
                     .cas()
-                    .write(
-                        Arc::new(value.into()),
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -605,8 +778,8 @@ impl AttributeValue {
             Some(value) => Some(
                 ctx.layer_db()
                     .cas()
-                    .write(
-                        Arc::new(value.into()),
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -616,7 +789,6 @@ impl AttributeValue {
             None => None,
         };
 
-        let func = Func::get_by_id(ctx, prototype_func_id).await?;
         if !func.is_intrinsic() {
             ctx.layer_db()
                 .func_run()
@@ -630,7 +802,7 @@ impl AttributeValue {
                 .await?;
         }
 
-        Ok((func_values, func, input_attribute_value_ids))
+        Ok((func_values, func, input_attribute_value_ids, inputs))
     }
 
     #[instrument(level = "debug" skip(ctx))]
@@ -699,6 +871,35 @@ impl AttributeValue {
                                 .await
                                 .map_err(Box::new)?]
                         }
+                        ValueSource::Subscription(subscription_id) => {
+                            // A subscription always yields a single array, even when it matches
+                            // zero or one components, so the func sees a stable shape.
+                            let subscription =
+                                AttributeSubscription::get_by_id(ctx, subscription_id)
+                                    .await
+                                    .map_err(Box::new)?;
+                            let mut values = vec![];
+                            for av_id in subscription
+                                .resolve(ctx, destination_component_id)
+                                .await
+                                .map_err(Box::new)?
+                            {
+                                input_attribute_value_ids.push(av_id);
+                                let attribute_value = AttributeValue::get_by_id(ctx, av_id).await?;
+                                values
+                                    .push(attribute_value.view(ctx).await?.unwrap_or(Value::Null));
+                            }
+
+                            vec![Value::Array(values)]
+                        }
+                        ValueSource::WorkspaceVariable(workspace_variable_id) => {
+                            vec![WorkspaceVariable::get_by_id(ctx, workspace_variable_id)
+                                .await
+                                .map_err(Box::new)?
+                                .value()
+                                .cloned()
+                                .unwrap_or(Value::Null)]
+                        }
                         other_source => {
                             let mut values = vec![];
 
@@ -867,12 +1068,16 @@ impl AttributeValue {
         )
     }
 
+    /// Sets the values produced by executing the prototype function. When given the
+    /// [`PrototypeFunctionInputs`] it ran with, they are stored with the values, so that the next
+    /// execution with the same inputs can be skipped.
     #[instrument(level = "debug", skip_all)]
     pub async fn set_values_from_func_run_value(
         ctx: &DalContext,
         attribute_value_id: AttributeValueId,
         func_run_value: FuncRunValue,
         func: Func,
+        inputs: Option<PrototypeFunctionInputs>,
     ) -> AttributeValueResult<()> {
         // We need to ensure the parent value tree for this value is set. But we don't want to
         // vivify the current attribute value since that would override the function which sets it
@@ -896,7 +1101,7 @@ impl AttributeValue {
 
         let unprocessed_value = func_run_value.unprocessed_value().cloned();
 
-        Self::set_real_values(ctx, attribute_value_id, func_run_value, func).await?;
+        Self::set_real_values(ctx, attribute_value_id, func_run_value, func, inputs).await?;
 
         if should_populate_nested {
             Self::populate_nested_values(ctx, attribute_value_id, unprocessed_value).await?;
@@ -913,7 +1118,7 @@ impl AttributeValue {
         // this lock is never locked for writing so is effectively a no-op here
         let read_lock = Arc::new(RwLock::new(()));
         // Don't need to pass in an Inferred Dependency Graph for one off updates, we can just calculate
-        let (execution_result, func, _) =
+        let (execution_result, func, _, inputs) =
             AttributeValue::execute_prototype_function(ctx, attribute_value_id, read_lock).await?;
 
         AttributeValue::set_values_from_func_run_value(
@@ -921,6 +1126,7 @@ impl AttributeValue {
             attribute_value_id,
             execution_result,
             func,
+            inputs,
         )
         .await?;
 
@@ -1943,7 +2149,7 @@ impl AttributeValue {
             .map_err(|_| AttributeValueError::FuncRunnerSend)?
             .map_err(Box::new)?;
 
-        Self::set_real_values(ctx, attribute_value_id, func_values, func).await?;
+        Self::set_real_values(ctx, attribute_value_id, func_values, func, None).await?;
         Ok(())
     }
 
@@ -1952,6 +2158,7 @@ impl AttributeValue {
         attribute_value_id: AttributeValueId,
         func_run_value: FuncRunValue,
         func: Func,
+        inputs: Option<PrototypeFunctionInputs>,
     ) -> AttributeValueResult<()> {
         let workspace_snapshot = ctx.workspace_snapshot()?;
         let av_node_weight = workspace_snapshot
@@ -1959,6 +2166,17 @@ impl AttributeValue {
             .await?
             .get_attribute_value_node_weight()?;
 
+        let content = |value: si_events::CasValue| match inputs {
+            Some(inputs) => {
+                ContentTypes::ComputedValue(ComputedValueContent::V1(ComputedValueContentV1 {
+                    value,
+                    code_hash: inputs.code_hash,
+                    args_hash: inputs.args_hash,
+                }))
+            }
+            None => value.into(),
+        };
+
         let content_value: Option<si_events::CasValue> =
             func_run_value.value().cloned().map(Into::into);
         let content_unprocessed_value: Option<si_events::CasValue> =
@@ -1969,7 +2187,7 @@ impl AttributeValue {
                 ctx.layer_db()
                     .cas()
                     .write(
-                        Arc::new(value.into()),
+                        Arc::new(content(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -1984,7 +2202,7 @@ impl AttributeValue {
                 ctx.layer_db()
                     .cas()
                     .write(
-                        Arc::new(value.into()),
+                        Arc::new(content(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -1995,12 +2213,31 @@ impl AttributeValue {
         };
 
         if !func.is_intrinsic() {
+            // The func run gets its own copies, so that they can be removed along with it
+            let func_run_address = |value: Option<&serde_json::Value>| {
+                value
+                    .map(|value| {
+                        ctx.layer_db()
+                            .cas()
+                            .write_func_run_data(
+                                Arc::new(ContentTypes::FuncRunData(value.clone().into())),
+                                None,
+                                ctx.events_tenancy(),
+                                ctx.events_actor(),
+                            )
+                            .map(|(address, _)| address)
+                    })
+                    .transpose()
+            };
+            let func_run_value_address = func_run_address(func_run_value.value())?;
+            let func_run_unprocessed_value_address =
+                func_run_address(func_run_value.unprocessed_value())?;
             ctx.layer_db()
                 .func_run()
                 .set_values_and_set_state_to_success(
                     func_run_value.func_run_id(),
-                    unprocessed_value_address,
-                    value_address,
+                    func_run_unprocessed_value_address,
+                    func_run_value_address,
                     ctx.events_tenancy(),
                     ctx.events_actor(),
                 )
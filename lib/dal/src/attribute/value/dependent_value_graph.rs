@@ -7,18 +7,26 @@ use telemetry::prelude::*;
 
 use crate::component::socket::ComponentOutputSocket;
 use crate::component::ControllingFuncData;
-use crate::workspace_snapshot::node_weight::NodeWeightDiscriminants;
+use crate::workspace_snapshot::content_address::ContentAddressDiscriminants;
+use crate::workspace_snapshot::node_weight::{NodeWeight, NodeWeightDiscriminants};
 use crate::workspace_snapshot::DependentValueRoot;
+use crate::workspace_variable::WorkspaceVariable;
 use crate::{
     attribute::{
-        prototype::{argument::AttributePrototypeArgument, AttributePrototype},
+        prototype::{
+            argument::{
+                subscription::{AttributeSubscription, AttributeSubscriptionId},
+                AttributePrototypeArgument,
+            },
+            AttributePrototype,
+        },
         value::ValueIsFor,
     },
     dependency_graph::DependencyGraph,
     workspace_snapshot::edge_weight::EdgeWeightKindDiscriminants,
     Component, DalContext, Secret,
 };
-use crate::{ComponentError, ComponentId, Prop, PropKind};
+use crate::{ComponentError, ComponentId, Prop, PropId, PropKind};
 
 use super::{AttributeValue, AttributeValueError, AttributeValueId, AttributeValueResult};
 
@@ -48,6 +56,16 @@ impl WorkQueueValue {
     }
 }
 
+/// The [`AttributeSubscriptions`](AttributeSubscription) in the workspace along with the lookups
+/// made while checking visited values against them. Every visited prop value is checked, so the
+/// prop paths and subscribers are cached for the lifetime of the [`DependentValueGraph`] build.
+struct SubscriptionWatchers {
+    subscriptions: Vec<AttributeSubscription>,
+    path_by_prop_id: HashMap<PropId, Vec<String>>,
+    subscribers_by_subscription_id:
+        HashMap<AttributeSubscriptionId, Vec<(AttributeValueId, ComponentId)>>,
+}
+
 impl DependentValueGraph {
     /// Construct a [`DependentValueGraph`] of all the [`AttributeValueIds`](AttributeValue) who are
     /// dependent on the initial ids provided as well as all descending dependencies.
@@ -90,6 +108,11 @@ impl DependentValueGraph {
             }
 
             let node_weight = workspace_snapshot.get_node_weight(root_ulid).await?;
+            let is_workspace_variable = matches!(
+                &node_weight,
+                NodeWeight::Content(inner) if inner.content_address_discriminants()
+                    == ContentAddressDiscriminants::WorkspaceVariable
+            );
 
             match node_weight.into() {
                 NodeWeightDiscriminants::AttributeValue => {
@@ -123,6 +146,21 @@ impl DependentValueGraph {
                             .map(|d| WorkQueueValue::Initial(*d)),
                     );
                 }
+                NodeWeightDiscriminants::Content if is_workspace_variable => {
+                    // Workspace variables behave just like secrets: the variable itself is not an
+                    // attribute value, so we start from the values that directly use it.
+                    let direct_dependents =
+                        WorkspaceVariable::direct_dependent_attribute_values(ctx, root_ulid.into())
+                            .await
+                            .map_err(Box::new)?;
+                    self.values_that_need_to_execute_from_prototype_function
+                        .extend(direct_dependents.clone());
+                    values.extend(
+                        direct_dependents
+                            .iter()
+                            .map(|d| WorkQueueValue::Initial(*d)),
+                    );
+                }
                 discrim => {
                     warn!(%discrim, %root_ulid, "skipping dependent value graph generation for unsupported node weight");
                 }
@@ -151,6 +189,14 @@ impl DependentValueGraph {
         let mut work_queue = VecDeque::from_iter(values);
         let mut seen_list = HashSet::new();
 
+        // Subscriptions are not connected to the values they read by edges, so we have to check
+        // each value we visit against them to discover the subscribers that depend on it.
+        let mut subscription_watchers = SubscriptionWatchers {
+            subscriptions: AttributeSubscription::list(ctx).await.map_err(Box::new)?,
+            path_by_prop_id: HashMap::new(),
+            subscribers_by_subscription_id: HashMap::new(),
+        };
+
         while let Some(current_attribute_value) = work_queue.pop_front() {
             let mut found_deps = false;
 
@@ -219,6 +265,26 @@ impl DependentValueGraph {
                 relevant_apas
             };
 
+            if let ValueIsFor::Prop(prop_id) = value_is_for {
+                for subscriber_value_id in Self::subscriber_values_watching_prop(
+                    ctx,
+                    &mut subscription_watchers,
+                    prop_id,
+                    current_component_id,
+                    &mut controlling_funcs_for_component,
+                )
+                .await?
+                {
+                    work_queue.push_back(WorkQueueValue::Discovered(subscriber_value_id));
+                    self.inner.id_depends_on(
+                        subscriber_value_id,
+                        current_attribute_value_controlling_value_id,
+                    );
+
+                    found_deps = true;
+                }
+            }
+
             match value_is_for {
                 ValueIsFor::Prop(prop_id) => {
                     let prop = Prop::get_by_id(ctx, prop_id).await?;
@@ -417,6 +483,79 @@ impl DependentValueGraph {
         println!("dot output stored in file (filename without extension: {filename_no_extension})");
     }
 
+    /// Find the (controlling) values of every [`AttributeSubscription`] subscriber that reads the
+    /// given prop, either as its selected value or through one of its filters. Subscribers on the
+    /// same component are skipped since a subscription never matches its own component.
+    async fn subscriber_values_watching_prop(
+        ctx: &DalContext,
+        subscription_watchers: &mut SubscriptionWatchers,
+        prop_id: PropId,
+        current_component_id: ComponentId,
+        controlling_funcs_for_component: &mut HashMap<
+            ComponentId,
+            HashMap<AttributeValueId, ControllingFuncData>,
+        >,
+    ) -> AttributeValueResult<Vec<AttributeValueId>> {
+        let SubscriptionWatchers {
+            subscriptions,
+            path_by_prop_id,
+            subscribers_by_subscription_id,
+        } = subscription_watchers;
+
+        if subscriptions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let path: &[String] = match path_by_prop_id.entry(prop_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(Prop::path_by_id(ctx, prop_id).await?.as_owned_parts())
+            }
+        };
+
+        let mut subscriber_value_ids = vec![];
+        for subscription in subscriptions
+            .iter()
+            .filter(|subscription| subscription.watches_path(path))
+        {
+            let subscribers = match subscribers_by_subscription_id.entry(subscription.id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let mut subscribers = vec![];
+                    for subscriber_value_id in
+                        AttributeSubscription::subscriber_attribute_value_ids(ctx, subscription.id)
+                            .await
+                            .map_err(Box::new)?
+                    {
+                        subscribers.push((
+                            subscriber_value_id,
+                            AttributeValue::component_id(ctx, subscriber_value_id).await?,
+                        ));
+                    }
+                    entry.insert(subscribers)
+                }
+            };
+
+            for &(subscriber_value_id, subscriber_component_id) in subscribers.iter() {
+                if subscriber_component_id == current_component_id {
+                    continue;
+                }
+
+                subscriber_value_ids.push(
+                    Self::get_controlling_attribute_value_id(
+                        ctx,
+                        subscriber_component_id,
+                        subscriber_value_id,
+                        controlling_funcs_for_component,
+                    )
+                    .await?,
+                );
+            }
+        }
+
+        Ok(subscriber_value_ids)
+    }
+
     async fn get_controlling_attribute_value_id(
         ctx: &DalContext,
         current_component_id: ComponentId,
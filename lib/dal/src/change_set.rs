@@ -17,7 +17,7 @@ use crate::billing_publish::BillingPublishError;
 use crate::slow_rt::SlowRuntimeError;
 use crate::workspace_snapshot::graph::RebaseBatch;
 use crate::{
-    action::{ActionError, ActionId},
+    action::{change_plan::ActionChangePlan, ActionError, ActionId},
     ChangeSetStatus, ComponentError, DalContext, HistoryActor, HistoryEvent, HistoryEventError,
     TransactionsError, User, UserError, UserPk, Workspace, WorkspacePk, WorkspaceSnapshot,
     WorkspaceSnapshotError, WsEvent, WsEventError,
@@ -37,6 +37,8 @@ const FIND_ANCESTORS_QUERY: &str = include_str!("queries/change_set/find_ancesto
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum ChangeSetError {
+    #[error("action error: {0}")]
+    Action(#[from] Box<ActionError>),
     #[error("billing publish error: {0}")]
     BillingPublish(#[from] Box<BillingPublishError>),
     #[error("change set not approved for apply. Current state: {0}")]
@@ -358,6 +360,12 @@ impl ChangeSet {
         billing_publish::for_change_set_status_update(ctx, self)
             .await
             .map_err(Box::new)?;
+        if matches!(
+            status,
+            ChangeSetStatus::Open | ChangeSetStatus::NeedsApproval
+        ) {
+            self.request_action_change_plans(ctx).await?;
+        }
         Ok(())
     }
 
@@ -374,6 +382,7 @@ impl ChangeSet {
             .await?;
 
         self.status = status;
+        self.request_action_change_plans(ctx).await?;
 
         Ok(())
     }
@@ -397,6 +406,7 @@ impl ChangeSet {
             .await?;
 
         self.status = status;
+        self.request_action_change_plans(ctx).await?;
 
         Ok(())
     }
@@ -871,6 +881,17 @@ impl ChangeSet {
         Ok(maybe_fake_pk)
     }
 
+    /// Asks for the change plans of the actions in this [`ChangeSet`], when it is opened or goes
+    /// up for review, which is when someone is about to look at them. Plans are computed from the
+    /// context's snapshot, so this does nothing for a context on another change set.
+    async fn request_action_change_plans(&self, ctx: &DalContext) -> ChangeSetResult<()> {
+        if ctx.change_set_id() == self.id {
+            ActionChangePlan::request(ctx).await.map_err(Box::new)?;
+        }
+
+        Ok(())
+    }
+
     fn generate_name() -> String {
         Utc::now().format("%Y-%m-%d-%H:%M").to_string()
     }
@@ -19,6 +19,9 @@ use si_events::{ulid::Ulid, ContentHash};
 use crate::action::prototype::{ActionKind, ActionPrototype, ActionPrototypeError};
 use crate::action::{Action, ActionError, ActionState};
 use crate::actor_view::ActorView;
+use crate::attribute::prototype::argument::subscription::{
+    AttributeSubscription, AttributeSubscriptionError,
+};
 use crate::attribute::prototype::argument::value_source::ValueSource;
 use crate::attribute::prototype::argument::{
     AttributePrototypeArgument, AttributePrototypeArgumentError, AttributePrototypeArgumentId,
@@ -75,6 +78,7 @@ pub mod code;
 pub mod debug;
 pub mod delete;
 pub mod diff;
+pub mod drift;
 pub mod frame;
 pub mod inferred_connection_graph;
 pub mod properties;
@@ -93,6 +97,8 @@ pub enum ComponentError {
     AttributePrototype(#[from] AttributePrototypeError),
     #[error("attribute prototype argument error: {0}")]
     AttributePrototypeArgument(#[from] AttributePrototypeArgumentError),
+    #[error("attribute subscription error: {0}")]
+    AttributeSubscription(#[from] Box<AttributeSubscriptionError>),
     #[error("attribute value error: {0}")]
     AttributeValue(#[from] AttributeValueError),
     #[error("cannot clone attributes from a component with a different schema variant id")]
@@ -968,7 +974,10 @@ impl Component {
                             }
                         }
                     }
-                    ValueSource::Secret(_) | ValueSource::StaticArgumentValue(_) => {
+                    ValueSource::Secret(_)
+                    | ValueSource::StaticArgumentValue(_)
+                    | ValueSource::Subscription(_)
+                    | ValueSource::WorkspaceVariable(_) => {
                         // Should we determine if this secret is still compatible?
                         new_value_sources.push((func_arg.id, source));
                     }
@@ -1222,7 +1231,7 @@ impl Component {
     }
 
     /// Returns all descendants (children of my children and on and on)
-    async fn get_all_descendants_for_id(
+    pub(crate) async fn get_all_descendants_for_id(
         ctx: &DalContext,
         component_id: ComponentId,
     ) -> ComponentResult<Vec<ComponentId>> {
@@ -2517,8 +2526,19 @@ impl Component {
             .publish_on_commit(ctx)
             .await?;
 
+        // Subscribers that matched this component need to drop its values. Which subscriptions
+        // could have matched it can only be worked out while it is still in the graph.
+        let affected_subscription_ids =
+            AttributeSubscription::list_ids_affected_by_component(ctx, id)
+                .await
+                .map_err(Box::new)?;
+
         ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
 
+        AttributeSubscription::enqueue_subscribers(ctx, affected_subscription_ids)
+            .await
+            .map_err(Box::new)?;
+
         Ok(())
     }
 
@@ -2636,6 +2656,14 @@ impl Component {
         ctx.add_dependent_values_and_enqueue(downstream_av_ids)
             .await?;
 
+        // Subscriptions follow the same data flow rules as connections, so their results change
+        // when a matching component enters or leaves the `to_delete` state.
+        if to_delete != original_to_delete {
+            AttributeSubscription::enqueue_subscribers_affected_by_component(ctx, component_id)
+                .await
+                .map_err(Box::new)?;
+        }
+
         // Deal with deletion actions, but only if we're transitioning from not being to_delete
         // into being to_delete.
         if to_delete && !original_to_delete {
@@ -0,0 +1,334 @@
+//! Drift is the difference between what a [`Component`] models in its domain and what its resource
+//! looks like, as last reported by a refresh.
+//!
+//! The resource payload returned by a refresh function does not have to follow the shape of the
+//! domain, so drift is only reported for values that exist in both: a value at the same path in
+//! the domain and in the payload that is not equal. Values that only exist on one side are
+//! ignored.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use si_events::FuncRunId;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    workspace_integrations::{WorkspaceIntegration, WorkspaceIntegrationsError},
+    AttributeValue, AttributeValueError, ChangeSetId, Component, ComponentError, ComponentId,
+    DalContext, TransactionsError, WorkspacePk, WsEvent, WsEventError, WsEventResult, WsPayload,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ResourceDriftError {
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] Box<AttributeValueError>),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace integrations error: {0}")]
+    WorkspaceIntegrations(#[from] WorkspaceIntegrationsError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type ResourceDriftResult<T> = Result<T, ResourceDriftError>;
+
+/// A value that differs between the domain of a [`Component`] and its resource.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftedValue {
+    /// A JSON pointer to the value, relative to the domain (e.g. `/tags/0/value`). Empty if the
+    /// domain as a whole differs.
+    pub path: String,
+    pub modeled: serde_json::Value,
+    pub actual: serde_json::Value,
+}
+
+/// The drift last detected for a [`Component`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceDrift {
+    pub component_id: ComponentId,
+    pub change_set_id: ChangeSetId,
+    pub detected_at: DateTime<Utc>,
+    /// The refresh that detected the drift.
+    pub func_run_id: Option<FuncRunId>,
+    pub drifted_values: Vec<DriftedValue>,
+}
+
+impl TryFrom<PgRow> for ResourceDrift {
+    type Error = ResourceDriftError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let drifted_values: serde_json::Value = row.try_get("drifted_values")?;
+        Ok(Self {
+            component_id: row.try_get("component_id")?,
+            change_set_id: row.try_get("change_set_id")?,
+            detected_at: row.try_get("detected_at")?,
+            func_run_id: row.try_get("func_run_id")?,
+            drifted_values: serde_json::from_value(drifted_values)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceDriftUpdatedPayload {
+    component_id: ComponentId,
+    change_set_id: ChangeSetId,
+    /// Empty if the component no longer drifts.
+    drifted_values: Vec<DriftedValue>,
+}
+
+impl ResourceDrift {
+    /// Compares the domain of the [`Component`] with the resource payload from a refresh, and
+    /// records the result. Notifies the workspace when the drift changes.
+    #[instrument(
+        name = "component.drift.detect",
+        level = "info",
+        skip(ctx, payload),
+        fields(si.component.id = %component_id)
+    )]
+    pub async fn detect(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        payload: &serde_json::Value,
+        func_run_id: Option<FuncRunId>,
+    ) -> ResourceDriftResult<Option<Self>> {
+        let component = Component::get_by_id(ctx, component_id)
+            .await
+            .map_err(Box::new)?;
+        let domain_av_id = component
+            .domain_prop_attribute_value(ctx)
+            .await
+            .map_err(Box::new)?;
+        let domain = AttributeValue::get_by_id(ctx, domain_av_id)
+            .await
+            .map_err(Box::new)?
+            .view(ctx)
+            .await
+            .map_err(Box::new)?
+            .unwrap_or(serde_json::Value::Null);
+
+        let drifted_values = Self::compare(&domain, payload);
+        let previous = Self::get_for_component(ctx, component_id).await?;
+        let changed = previous
+            .map(|drift| drift.drifted_values)
+            .unwrap_or_default()
+            != drifted_values;
+
+        let drift = if drifted_values.is_empty() {
+            Self::clear(ctx, component_id).await?;
+            None
+        } else {
+            Some(Self::record(ctx, component_id, func_run_id, &drifted_values).await?)
+        };
+
+        if changed {
+            WsEvent::resource_drift_updated(ctx, component_id, drifted_values.clone())
+                .await?
+                .publish_on_commit(ctx)
+                .await?;
+
+            if !drifted_values.is_empty() {
+                let schema_name = Component::schema_for_component_id(ctx, component_id)
+                    .await
+                    .map_err(Box::new)?
+                    .name()
+                    .to_owned();
+                let paths: Vec<&str> = drifted_values
+                    .iter()
+                    .map(|value| value.path.as_str())
+                    .collect();
+                let message = format!(
+                    "Drift detected on {} \"{}\": {} value(s) differ from the model ({})",
+                    schema_name,
+                    component.name(ctx).await.map_err(Box::new)?,
+                    drifted_values.len(),
+                    paths.join(", ")
+                );
+                WorkspaceIntegration::post_to_webhook(ctx, &message).await?;
+            }
+        }
+
+        Ok(drift)
+    }
+
+    /// The values present in both the modeled domain and the actual resource payload that are not
+    /// equal.
+    pub fn compare(modeled: &serde_json::Value, actual: &serde_json::Value) -> Vec<DriftedValue> {
+        let mut drifted_values = Vec::new();
+        compare_at(&mut String::new(), modeled, actual, &mut drifted_values);
+        drifted_values
+    }
+
+    pub async fn get_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ResourceDriftResult<Option<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM resource_drifts
+                    WHERE workspace_pk = $1 AND change_set_id = $2 AND component_id = $3",
+                &[&workspace_pk, &ctx.change_set_id(), &component_id],
+            )
+            .await?;
+
+        maybe_row.map(Self::try_from).transpose()
+    }
+
+    /// Lists the drift for every [`Component`] in the current change set, most recent first.
+    pub async fn list(ctx: &DalContext) -> ResourceDriftResult<Vec<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM resource_drifts
+                    WHERE workspace_pk = $1 AND change_set_id = $2
+                    ORDER BY detected_at DESC",
+                &[&workspace_pk, &ctx.change_set_id()],
+            )
+            .await?;
+
+        let mut drifts = Vec::with_capacity(rows.len());
+        for row in rows {
+            let drift = Self::try_from(row)?;
+            // Components can be removed after their drift was recorded
+            if Component::try_get_by_id(ctx, drift.component_id)
+                .await
+                .map_err(Box::new)?
+                .is_some()
+            {
+                drifts.push(drift);
+            }
+        }
+
+        Ok(drifts)
+    }
+
+    async fn record(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        func_run_id: Option<FuncRunId>,
+        drifted_values: &[DriftedValue],
+    ) -> ResourceDriftResult<Self> {
+        let workspace_pk: WorkspacePk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO resource_drifts
+                    (workspace_pk, change_set_id, component_id, func_run_id, drifted_values)
+                    VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT (workspace_pk, change_set_id, component_id) DO UPDATE
+                    SET detected_at = CLOCK_TIMESTAMP(),
+                        func_run_id = EXCLUDED.func_run_id,
+                        drifted_values = EXCLUDED.drifted_values
+                    RETURNING *",
+                &[
+                    &workspace_pk,
+                    &ctx.change_set_id(),
+                    &component_id,
+                    &func_run_id,
+                    &serde_json::to_value(drifted_values)?,
+                ],
+            )
+            .await?;
+
+        Self::try_from(row)
+    }
+
+    async fn clear(ctx: &DalContext, component_id: ComponentId) -> ResourceDriftResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "DELETE FROM resource_drifts
+                    WHERE workspace_pk = $1 AND change_set_id = $2 AND component_id = $3",
+                &[&workspace_pk, &ctx.change_set_id(), &component_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn compare_at(
+    path: &mut String,
+    modeled: &serde_json::Value,
+    actual: &serde_json::Value,
+    drifted_values: &mut Vec<DriftedValue>,
+) {
+    use serde_json::Value;
+
+    match (modeled, actual) {
+        // Unset values in the model don't say anything about the resource
+        (Value::Null, _) => {}
+        (Value::Object(modeled_map), Value::Object(actual_map)) => {
+            for (key, modeled_value) in modeled_map {
+                if let Some(actual_value) = actual_map.get(key) {
+                    let len = path.len();
+                    path.push('/');
+                    path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                    compare_at(path, modeled_value, actual_value, drifted_values);
+                    path.truncate(len);
+                }
+            }
+        }
+        (Value::Array(modeled_items), Value::Array(actual_items))
+            if modeled_items.len() == actual_items.len() =>
+        {
+            for (index, (modeled_item, actual_item)) in
+                modeled_items.iter().zip(actual_items).enumerate()
+            {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&index.to_string());
+                compare_at(path, modeled_item, actual_item, drifted_values);
+                path.truncate(len);
+            }
+        }
+        _ => {
+            if modeled != actual {
+                drifted_values.push(DriftedValue {
+                    path: path.clone(),
+                    modeled: modeled.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+    }
+}
+
+impl WsEvent {
+    pub async fn resource_drift_updated(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        drifted_values: Vec<DriftedValue>,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::ResourceDriftUpdated(ResourceDriftUpdatedPayload {
+                component_id,
+                change_set_id: ctx.change_set_id(),
+                drifted_values,
+            }),
+        )
+        .await
+    }
+}
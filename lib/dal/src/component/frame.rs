@@ -4,6 +4,9 @@ use itertools::Itertools;
 use telemetry::prelude::*;
 use thiserror::Error;
 
+use crate::attribute::prototype::argument::subscription::{
+    AttributeSubscription, AttributeSubscriptionError,
+};
 use crate::attribute::value::AttributeValueError;
 use crate::component::inferred_connection_graph::InferredConnection;
 use crate::diagram::SummaryDiagramInferredEdge;
@@ -24,6 +27,8 @@ use super::socket::{ComponentInputSocket, ComponentOutputSocket};
 pub enum FrameError {
     #[error("aggregation frames unsupported: {0}")]
     AggregateFramesUnsupported(ComponentId),
+    #[error("attribute subscription error: {0}")]
+    AttributeSubscription(#[from] Box<AttributeSubscriptionError>),
     #[error("attribute value error: {0}")]
     AttributeValueError(#[from] AttributeValueError),
     #[error("component error: {0}")]
@@ -345,6 +350,11 @@ impl Frame {
         )
         .await?;
 
+        // Frame ancestry may have changed the results of subscriptions.
+        AttributeSubscription::enqueue_subscribers_affected_by_component(ctx, child_id)
+            .await
+            .map_err(Box::new)?;
+
         Ok(InferredEdgeChanges {
             removed_edges: inferred_edges_to_remove,
             upserted_edges: inferred_edges_to_upsert,
@@ -439,6 +449,12 @@ impl Frame {
                 .collect_vec(),
         )
         .await?;
+
+        // Frame ancestry may have changed the results of subscriptions.
+        AttributeSubscription::enqueue_subscribers_affected_by_component(ctx, child_id)
+            .await
+            .map_err(Box::new)?;
+
         Ok(())
     }
 
@@ -28,7 +28,7 @@ use thiserror::Error;
 use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
 use tokio::time;
 use tokio_util::task::TaskTracker;
-use veritech_client::Client as VeritechClient;
+use veritech_client::{DynFunctionExecutor, FunctionExecutor};
 
 use crate::audit_logging::AuditLoggingError;
 use crate::feature_flags::FeatureFlagService;
@@ -44,7 +44,7 @@ use crate::{audit_logging, slow_rt, ChangeSetError, EncryptedSecret, Workspace,
 use crate::{
     change_set::{ChangeSet, ChangeSetId},
     job::{
-        definition::ActionJob,
+        definition::{ActionJob, ComputeActionChangePlans},
         processor::{JobQueueProcessor, JobQueueProcessorError},
         producer::{BlockingJobError, BlockingJobResult, JobProducer},
         queue::JobQueue,
@@ -72,8 +72,8 @@ pub struct ServicesContext {
     job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
     /// A Rebaser client, connected via a NATS connection.
     rebaser: RebaserClient,
-    /// A Veritech client, connected via a NATS connection.
-    veritech: VeritechClient,
+    /// A function executor, usually a Veritech client connected via a NATS connection.
+    veritech: DynFunctionExecutor,
     /// A key for re-recrypting messages to the function execution system.
     encryption_key: Arc<VeritechEncryptionKey>,
     /// The path where available packages can be found
@@ -99,7 +99,7 @@ impl ServicesContext {
         jetstream_streams: JetstreamStreams,
         job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
         rebaser: RebaserClient,
-        veritech: VeritechClient,
+        veritech: impl FunctionExecutor,
         encryption_key: Arc<VeritechEncryptionKey>,
         pkgs_path: Option<PathBuf>,
         module_index_url: Option<String>,
@@ -114,7 +114,7 @@ impl ServicesContext {
             jetstream_streams,
             job_processor,
             rebaser,
-            veritech,
+            veritech: Arc::new(veritech),
             encryption_key,
             pkgs_path,
             module_index_url,
@@ -154,8 +154,8 @@ impl ServicesContext {
         &self.rebaser
     }
 
-    /// Gets a reference to the Veritech client.
-    pub fn veritech(&self) -> &VeritechClient {
+    /// Gets a reference to the function executor, usually a Veritech client.
+    pub fn veritech(&self) -> &DynFunctionExecutor {
         &self.veritech
     }
 
@@ -446,6 +446,23 @@ impl DalContext {
         .await
     }
 
+    pub async fn run_async_rebase(
+        &self,
+        workspace_pk: WorkspacePk,
+        change_set_id: ChangeSetId,
+        updates_address: RebaseBatchAddress,
+    ) -> TransactionsResult<RequestId> {
+        self.rebaser()
+            .enqueue_updates(
+                workspace_pk,
+                change_set_id,
+                updates_address,
+                self.event_session_id,
+            )
+            .await
+            .map_err(Into::into)
+    }
+
     pub async fn run_async_rebase_from_change_set(
         &self,
         workspace_pk: WorkspacePk,
@@ -840,6 +857,17 @@ impl DalContext {
         Ok(())
     }
 
+    /// Enqueues a job that computes the change plans for the pending actions of the current
+    /// change set, once this context is committed.
+    pub async fn enqueue_compute_action_change_plans(&self) -> TransactionsResult<()> {
+        self.txns()
+            .await?
+            .job_queue
+            .enqueue_job(ComputeActionChangePlans::new(self))
+            .await;
+        Ok(())
+    }
+
     /// Add the node ids to the workspace snapshot graph and enqueue a dependent values update.
     /// This update will only be run on commit if blocking_commit is used. If commit is used, the
     /// DVU debouncer will run the job. Note that the DVU debouncer might still pick up the job
@@ -956,8 +984,8 @@ impl DalContext {
         &self.services_context.nats_conn
     }
 
-    /// Gets a reference to the DAL context's Veritech client.
-    pub fn veritech(&self) -> &VeritechClient {
+    /// Gets a reference to the DAL context's function executor, usually a Veritech client.
+    pub fn veritech(&self) -> &DynFunctionExecutor {
         &self.services_context.veritech
     }
 
@@ -5,6 +5,7 @@ use si_events::workspace_snapshot::EntityKind as EntityKindEvents;
 use crate::{
     diagram::{view::View, DiagramError},
     workspace_snapshot::EntityKindExt,
+    workspace_variable::{WorkspaceVariable, WorkspaceVariableError},
     DalContext, SchemaVariant, SchemaVariantError, WorkspaceSnapshotError,
 };
 use thiserror::Error;
@@ -18,6 +19,8 @@ pub enum EntityKindError {
     SchemaVariant(#[from] SchemaVariantError),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+    #[error("workspace variable error: {0}")]
+    WorkspaceVariable(#[from] Box<WorkspaceVariableError>),
 }
 pub type EntityKindResult<T> = Result<T, EntityKindError>;
 pub struct EntityKind;
@@ -43,26 +46,35 @@ impl EntityKind {
             // entity kinds, update this
             EntityKindEvents::Action
             | EntityKindEvents::ActionPrototype
+            | EntityKindEvents::ActionRetryPolicy
             | EntityKindEvents::ApprovalRequirementDefinition
             | EntityKindEvents::AttributePrototype
             | EntityKindEvents::AttributePrototypeArgument
+            | EntityKindEvents::AttributeSubscription
             | EntityKindEvents::AttributeValue
             | EntityKindEvents::CategoryAction
+            | EntityKindEvents::CategoryActionRetryPolicy
+            | EntityKindEvents::CategoryAttributeSubscription
             | EntityKindEvents::CategoryComponent
             | EntityKindEvents::CategoryDependentValueRoots
             | EntityKindEvents::CategoryDeprecatedActionBatch
             | EntityKindEvents::CategoryDiagramObject
             | EntityKindEvents::CategoryFunc
+            | EntityKindEvents::CategoryFuncTestCase
+            | EntityKindEvents::CategoryFuncTimeout
             | EntityKindEvents::CategoryModule
             | EntityKindEvents::CategorySchema
             | EntityKindEvents::CategorySecret
             | EntityKindEvents::CategoryView
+            | EntityKindEvents::CategoryWorkspaceVariable
             | EntityKindEvents::Component
             | EntityKindEvents::DependentValueRoot
             | EntityKindEvents::DiagramObject
             | EntityKindEvents::FinishedDependentValueRoot
             | EntityKindEvents::Func
             | EntityKindEvents::FuncArgument
+            | EntityKindEvents::FuncTestCase
+            | EntityKindEvents::FuncTimeout
             | EntityKindEvents::Geometry
             | EntityKindEvents::InputSocket
             | EntityKindEvents::JsonValue
@@ -93,6 +105,14 @@ impl EntityKind {
                     .to_owned();
                 Some(view_name)
             }
+            EntityKindEvents::WorkspaceVariable => {
+                let variable_name = WorkspaceVariable::get_by_id(ctx, id.into_inner().into())
+                    .await
+                    .map_err(Box::new)?
+                    .name()
+                    .to_owned();
+                Some(variable_name)
+            }
         };
         Ok(name)
     }
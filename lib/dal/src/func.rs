@@ -13,13 +13,15 @@ use std::string::FromUtf8Error;
 use std::sync::Arc;
 use strum::IntoEnumIterator;
 use telemetry::prelude::*;
+use test_case::FuncTestCase;
 use thiserror::Error;
+use timeout::FuncTimeout;
 use ulid::Ulid as CoreUlid;
 
 use crate::change_set::ChangeSetError;
 use crate::func::argument::FuncArgumentId;
 use crate::func::intrinsics::IntrinsicFunc;
-use crate::layer_db_types::{FuncContent, FuncContentV2};
+use crate::layer_db_types::{FuncContent, FuncContentV3};
 use crate::workspace_snapshot::edge_weight::{EdgeWeightKind, EdgeWeightKindDiscriminants};
 use crate::workspace_snapshot::node_weight::category_node_weight::CategoryNodeKind;
 use crate::workspace_snapshot::node_weight::{FuncNodeWeight, NodeWeight, NodeWeightError};
@@ -35,9 +37,14 @@ pub mod argument;
 pub mod authoring;
 pub mod backend;
 pub mod binding;
+pub mod egress_policy;
 pub mod intrinsics;
 mod kind;
+pub mod replay;
+pub mod retention;
 pub mod runner;
+pub mod test_case;
+pub mod timeout;
 
 pub use kind::FuncKind;
 
@@ -64,6 +71,10 @@ pub enum FuncError {
     FuncLocked(FuncId),
     #[error("func name already in use {0}")]
     FuncNameInUse(String),
+    #[error("func test case error: {0}")]
+    FuncTestCase(#[from] Box<test_case::FuncTestCaseError>),
+    #[error("func timeout error: {0}")]
+    FuncTimeout(#[from] Box<timeout::FuncTimeoutError>),
     #[error("func to be deleted has bindings: {0}")]
     FuncToBeDeletedHasBindings(FuncId),
     #[error("helper error: {0}")]
@@ -96,7 +107,7 @@ pub type FuncResult<T> = Result<T, FuncError>;
 
 impl From<Func> for FuncContent {
     fn from(value: Func) -> Self {
-        Self::V2(FuncContentV2 {
+        Self::V3(FuncContentV3 {
             timestamp: value.timestamp,
             display_name: value.display_name,
             description: value.description,
@@ -109,6 +120,7 @@ impl From<Func> for FuncContent {
             code_base64: value.code_base64,
             code_blake3: value.code_blake3,
             is_locked: value.is_locked,
+            always_execute: value.always_execute,
         })
     }
 }
@@ -154,10 +166,13 @@ pub struct Func {
     pub code_base64: Option<String>,
     pub code_blake3: ContentHash,
     pub is_locked: bool,
+    /// Opts the func out of skipping re-execution when its inputs are unchanged, for code that
+    /// is not deterministic.
+    pub always_execute: bool,
 }
 
 impl Func {
-    pub fn assemble(node_weight: &FuncNodeWeight, content: FuncContentV2) -> Self {
+    pub fn assemble(node_weight: &FuncNodeWeight, content: FuncContentV3) -> Self {
         Self {
             id: node_weight.id().into(),
             name: node_weight.name().to_owned(),
@@ -175,6 +190,7 @@ impl Func {
             code_base64: content.code_base64,
             code_blake3: content.code_blake3,
             is_locked: content.is_locked,
+            always_execute: content.always_execute,
         }
     }
 
@@ -226,7 +242,7 @@ impl Func {
             ContentHash::new("".as_bytes())
         };
 
-        let content = FuncContentV2 {
+        let content = FuncContentV3 {
             timestamp,
             display_name: display_name.map(Into::into),
             description: description.map(Into::into),
@@ -239,10 +255,11 @@ impl Func {
             code_base64,
             code_blake3,
             is_locked: false,
+            always_execute: false,
         };
 
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(FuncContent::V2(content.clone()).into()),
+            Arc::new(FuncContent::V3(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -340,7 +357,7 @@ impl Func {
         )?;
 
         // migrate if necessary!
-        let inner: FuncContentV2 = content.extract();
+        let inner: FuncContentV3 = content.extract();
 
         Ok(Self::assemble(func_node_weight, inner))
     }
@@ -374,6 +391,34 @@ impl Func {
         Ok(None)
     }
 
+    /// Finds the ids of every [`Func`] with the given name, since names are not unique.
+    pub async fn list_ids_by_name(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+    ) -> FuncResult<Vec<FuncId>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let func_category_id = workspace_snapshot
+            .get_category_node_or_err(None, CategoryNodeKind::Func)
+            .await?;
+        let func_indices = workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(
+                func_category_id,
+                EdgeWeightKind::new_use().into(),
+            )
+            .await?;
+        let name = name.as_ref();
+        let mut func_ids = vec![];
+        for func_index in func_indices {
+            let node_weight = workspace_snapshot.get_node_weight(func_index).await?;
+            if let NodeWeight::Func(inner_weight) = node_weight {
+                if inner_weight.name() == name {
+                    func_ids.push(inner_weight.id().into());
+                }
+            }
+        }
+        Ok(func_ids)
+    }
+
     /// Attempt to find the [`FuncId`](Func) by name and [kind](FuncKind).
     ///
     /// _Warning:_ [`Func`] names are intentionally not unique. This is a greedy algorithm!
@@ -437,9 +482,14 @@ impl Func {
                 | IntrinsicFunc::SetObject
                 | IntrinsicFunc::SetString
                 | IntrinsicFunc::Unset => false,
-                IntrinsicFunc::Identity
+                IntrinsicFunc::Arithmetic
+                | IntrinsicFunc::Coalesce
+                | IntrinsicFunc::Identity
+                | IntrinsicFunc::JoinArray
+                | IntrinsicFunc::JsonPointer
                 | IntrinsicFunc::NormalizeToArray
                 | IntrinsicFunc::ResourcePayloadToValue
+                | IntrinsicFunc::StringTemplate
                 | IntrinsicFunc::Validation => true,
             },
             None => true,
@@ -568,7 +618,13 @@ impl Func {
             return Err(FuncError::FuncToBeDeletedHasBindings(id));
         }
 
-        // Now, we can remove the func.
+        // Now, we can remove the func along with its test cases and timeout.
+        FuncTestCase::remove_for_func(ctx, id)
+            .await
+            .map_err(Box::new)?;
+        FuncTimeout::remove_for_target(ctx, timeout::FuncTimeoutTarget::Func(id))
+            .await
+            .map_err(Box::new)?;
         let workspace_snapshot = ctx.workspace_snapshot()?;
         workspace_snapshot.remove_node_by_id(id).await?;
 
@@ -577,11 +633,54 @@ impl Func {
 
     pub async fn find_intrinsic(ctx: &DalContext, intrinsic: IntrinsicFunc) -> FuncResult<FuncId> {
         let name = intrinsic.name();
+        if let Some(func_id) =
+            Self::find_id_by_name_and_kind(ctx, name, FuncKind::Intrinsic).await?
+        {
+            return Ok(func_id);
+        }
+
+        // Workspaces created before the expression intrinsics existed get them on first use.
+        if intrinsic.is_expression() {
+            Self::install_expression_intrinsics(ctx).await?;
+        }
         Self::find_id_by_name_and_kind(ctx, name, FuncKind::Intrinsic)
             .await?
             .ok_or(FuncError::IntrinsicFuncNotFound(name.to_owned()))
     }
 
+    /// Installs whichever expression intrinsics are missing from the workspace, leaving the rest
+    /// untouched.
+    pub async fn install_expression_intrinsics(ctx: &DalContext) -> FuncResult<()> {
+        let mut missing = false;
+        for intrinsic in IntrinsicFunc::iter().filter(IntrinsicFunc::is_expression) {
+            if Self::find_id_by_name_and_kind(ctx, intrinsic.name(), FuncKind::Intrinsic)
+                .await?
+                .is_none()
+            {
+                missing = true;
+                break;
+            }
+        }
+        if !missing {
+            return Ok(());
+        }
+
+        // The import finds or creates each expression intrinsic. We don't record it as an
+        // installed module, so that it can run again should one of them go missing later.
+        pkg::import_pkg_from_pkg(
+            ctx,
+            &si_pkg::SiPkg::load_from_spec(IntrinsicFunc::expression_pkg_spec()?)?,
+            Some(pkg::ImportOptions {
+                no_record: true,
+                ..Default::default()
+            }),
+        )
+        .await
+        .map_err(Box::new)?;
+
+        Ok(())
+    }
+
     /// List all [`Funcs`](Func) in the workspace
     pub async fn list_all(ctx: &DalContext) -> FuncResult<Vec<Self>> {
         let workspace_snapshot = ctx.workspace_snapshot()?;
@@ -689,7 +788,7 @@ impl Func {
     }
 
     /// Creates an exact clone of the current func that is not locked, including recreating all
-    /// [`FuncArgument`]s
+    /// [`FuncArgument`]s and [`FuncTestCase`]s, and its [`FuncTimeout`]
     pub async fn create_unlocked_func_copy(&self, ctx: &DalContext) -> FuncResult<Self> {
         let new_func = Self::new(
             ctx,
@@ -705,6 +804,16 @@ impl Func {
             self.code_base64.clone(),
         )
         .await?;
+        let new_func = if self.always_execute {
+            new_func
+                .modify(ctx, |func| {
+                    func.always_execute = true;
+                    Ok(())
+                })
+                .await?
+        } else {
+            new_func
+        };
 
         for arg in FuncArgument::list_for_func(ctx, self.id)
             .await
@@ -718,6 +827,12 @@ impl Func {
         FuncArgument::list_for_func(ctx, new_func.id)
             .await
             .map_err(Box::new)?;
+        FuncTestCase::copy_for_func(ctx, self.id, new_func.id)
+            .await
+            .map_err(Box::new)?;
+        FuncTimeout::copy_for_func(ctx, self.id, new_func.id)
+            .await
+            .map_err(Box::new)?;
         Ok(new_func)
     }
 
@@ -744,6 +859,9 @@ impl Func {
             self.code_base64.clone(),
         )
         .await?;
+        FuncTimeout::copy_for_func(ctx, self.id, duplicated_func.id)
+            .await
+            .map_err(Box::new)?;
 
         Ok(duplicated_func)
     }
@@ -788,6 +906,7 @@ impl Func {
             display_name: self.display_name.clone(),
             description: self.description.clone(),
             is_locked: self.is_locked,
+            always_execute: self.always_execute,
             bindings,
             arguments,
             types: Some(types),
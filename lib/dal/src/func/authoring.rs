@@ -43,14 +43,16 @@ use crate::attribute::prototype::AttributePrototypeError;
 use crate::attribute::value::AttributeValueError;
 use crate::func::argument::{FuncArgument, FuncArgumentError, FuncArgumentId, FuncArgumentKind};
 use crate::func::FuncKind;
+use crate::layer_db_types::ContentTypes;
 use crate::prop::PropError;
 use crate::schema::variant::authoring::{VariantAuthoringClient, VariantAuthoringError};
 use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
 use crate::socket::output::OutputSocketError;
 use crate::{
-    AttributePrototype, AttributePrototypeId, ComponentError, ComponentId, DalContext, Func,
-    FuncBackendKind, FuncBackendResponseType, FuncError, FuncId, SchemaVariant, SchemaVariantError,
-    SchemaVariantId, TransactionsError, WorkspaceSnapshotError, WsEvent, WsEventError,
+    ActionPrototypeId, AttributePrototype, AttributePrototypeId, ComponentError, ComponentId,
+    DalContext, Func, FuncBackendKind, FuncBackendResponseType, FuncError, FuncId, SchemaVariant,
+    SchemaVariantError, SchemaVariantId, TransactionsError, WorkspaceSnapshotError, WsEvent,
+    WsEventError,
 };
 
 use super::binding::attribute::AttributeBinding;
@@ -198,6 +200,21 @@ impl FuncAuthoringClient {
         Ok(func)
     }
 
+    /// Creates a new Plan Func, binds it to an existing Action Prototype and returns it
+    #[instrument(
+        name = "func.authoring.create_new_plan_func",
+        level = "info",
+        skip(ctx)
+    )]
+    pub async fn create_new_plan_func(
+        ctx: &DalContext,
+        name: Option<String>,
+        action_prototype_id: ActionPrototypeId,
+    ) -> FuncAuthoringResult<Func> {
+        let func = create::create_plan_func(ctx, name, action_prototype_id).await?;
+        Ok(func)
+    }
+
     /// Creates a new Code Gen or Qualification Func and returns it
     #[instrument(
         name = "func.authoring.create_new_leaf_func",
@@ -257,7 +274,7 @@ impl FuncAuthoringClient {
 
         let is_intrinsic = func.is_intrinsic();
         let (func_run_id, result_channel) =
-            FuncRunner::run_test(ctx, func, args, component_id).await?;
+            FuncRunner::run_test(ctx, func, args, Some(component_id)).await?;
 
         let func_run_value = result_channel
             .await
@@ -272,8 +289,8 @@ impl FuncAuthoringClient {
             Some(value) => Some(
                 ctx.layer_db()
                     .cas()
-                    .write(
-                        Arc::new(value.into()),
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -287,8 +304,8 @@ impl FuncAuthoringClient {
             Some(value) => Some(
                 ctx.layer_db()
                     .cas()
-                    .write(
-                        Arc::new(value.into()),
+                    .write_func_run_data(
+                        Arc::new(ContentTypes::FuncRunData(value)),
                         None,
                         ctx.events_tenancy(),
                         ctx.events_actor(),
@@ -573,6 +590,26 @@ impl FuncAuthoringClient {
         Ok(updated_func)
     }
 
+    /// Sets whether the [`Func`] is re-executed during
+    /// [`DependentValuesUpdate`](crate::job::definition::DependentValuesUpdate) even when its
+    /// inputs are unchanged. Opt in for funcs whose code is not deterministic.
+    /// Returns an error if the [`Func`] is currently locked
+    #[instrument(level = "info", name = "func.authoring.set_always_execute", skip(ctx))]
+    pub async fn set_always_execute(
+        ctx: &DalContext,
+        func_id: FuncId,
+        always_execute: bool,
+    ) -> FuncAuthoringResult<Func> {
+        let func = Func::get_by_id(ctx, func_id).await?;
+        func.error_if_locked()?;
+        let updated_func = Func::modify_by_id(ctx, func.id, |func| {
+            func.always_execute = always_execute;
+            Ok(())
+        })
+        .await?;
+        Ok(updated_func)
+    }
+
     /// Compiles types corresponding to "lang-js".
     pub fn compile_langjs_types() -> &'static str {
         ts_types::compile_langjs_types()
@@ -11,7 +11,8 @@ use crate::func::binding::management::ManagementBinding;
 use crate::func::binding::{AttributeArgumentBinding, AttributeFuncDestination, EventualParent};
 use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
 use crate::{
-    generate_name, DalContext, Func, FuncBackendKind, FuncBackendResponseType, SchemaVariantId,
+    generate_name, ActionPrototypeId, DalContext, Func, FuncBackendKind, FuncBackendResponseType,
+    SchemaVariantId,
 };
 
 use super::{FuncAuthoringError, FuncAuthoringResult};
@@ -23,6 +24,7 @@ static DEFAULT_QUALIFICATION_CODE: &str = include_str!("data/defaults/qualificat
 static DEFAULT_ACTION_CODE: &str = include_str!("data/defaults/action.ts");
 static DEFAULT_AUTHENTICATION_CODE: &str = include_str!("data/defaults/authentication.ts");
 static DEFAULT_MGMT_CODE: &str = include_str!("data/defaults/management.ts");
+static DEFAULT_PLAN_CODE: &str = include_str!("data/defaults/plan.ts");
 
 #[allow(dead_code)]
 static DEFAULT_VALIDATION_CODE: &str = include_str!("data/defaults/validation.ts");
@@ -91,6 +93,31 @@ pub(crate) async fn create_action_func(
     Ok(func)
 }
 
+#[instrument(
+    name = "func.authoring.create_func.create.plan",
+    level = "debug",
+    skip(ctx)
+)]
+pub(crate) async fn create_plan_func(
+    ctx: &DalContext,
+    name: Option<String>,
+    action_prototype_id: ActionPrototypeId,
+) -> FuncAuthoringResult<Func> {
+    let func = create_func_stub(
+        ctx,
+        name.clone(),
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Plan,
+        DEFAULT_PLAN_CODE,
+        DEFAULT_CODE_HANDLER,
+    )
+    .await?;
+
+    ActionBinding::create_plan_binding(ctx, func.id, action_prototype_id).await?;
+
+    Ok(func)
+}
+
 #[instrument(
     name = "func.authoring.create_func.create.leaf",
     level = "debug",
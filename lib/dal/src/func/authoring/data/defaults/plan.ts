@@ -0,0 +1,6 @@
+async function main(component: Input): Promise<Output> {
+  return {
+    action: 'noOp',
+    changes: [],
+  };
+}
@@ -17,7 +17,9 @@ pub(crate) fn compile_return_types(
     if matches!(kind, FuncBackendKind::JsAttribute)
         && !matches!(
             response_type,
-            FuncBackendResponseType::CodeGeneration | FuncBackendResponseType::Qualification
+            FuncBackendResponseType::CodeGeneration
+                | FuncBackendResponseType::Qualification
+                | FuncBackendResponseType::Plan
         )
     {
         return ""; // attribute functions have their output compiled dynamically
@@ -37,6 +39,18 @@ pub(crate) fn compile_return_types(
             "type Output = {
   format: string;
   code: string;
+}"
+        }
+        FuncBackendResponseType::Plan => {
+            "type Output = {
+  action: 'create' | 'replace' | 'updateInPlace' | 'noOp';
+  changes?: {
+    path: string;
+    before?: unknown;
+    after?: unknown;
+    forcesReplacement?: boolean;
+  }[];
+  message?: string | null;
 }"
         }
         FuncBackendResponseType::Validation => {
@@ -8,30 +8,36 @@ use strum::{AsRefStr, Display, EnumIter, EnumString};
 use telemetry::prelude::*;
 use thiserror::Error;
 use veritech_client::{
-    ActionRunResultSuccess, BeforeFunction, Client as VeritechClient, FunctionResult,
-    FunctionResultFailureErrorKind, OutputStream, ResolverFunctionResponseType,
+    ActionRunResultSuccess, BeforeFunction, DynFunctionExecutor, EgressPolicy, ExecutionPriority,
+    FunctionResult, FunctionResultFailureErrorKind, FunctionRuntime, OutputStream,
+    ResolverFunctionResponseType,
 };
 
 use crate::label_list::ToLabelList;
 use crate::workspace::WorkspaceId;
 use crate::{Func, FuncId, PropKind};
 
+pub mod arithmetic;
 pub mod array;
 pub mod boolean;
+pub mod coalesce;
 pub mod diff;
 pub mod float;
 pub mod identity;
 pub mod integer;
+pub mod join_array;
 pub mod js_action;
 pub mod js_attribute;
 pub mod js_schema_variant_definition;
 pub mod json;
+pub mod json_pointer;
 pub mod management;
 pub mod map;
 pub mod normalize_to_array;
 pub mod object;
 pub mod resource_payload_to_value;
 pub mod string;
+pub mod string_template;
 pub mod validation;
 
 #[remain::sorted]
@@ -43,10 +49,16 @@ pub enum FuncBackendError {
     DispatchMissingBase64(FuncId),
     #[error("dispatch func missing handler {0}")]
     DispatchMissingHandler(FuncId),
+    #[error("division by zero")]
+    DivisionByZero,
     #[error("function result action run error: {0:?}")]
     FunctionResultActionRun(FunctionResult<Box<ActionRunResultSuccess>>),
     #[error("invalid data - expected a valid array entry value, got: {0}")]
     InvalidArrayEntryData(serde_json::Value),
+    #[error("invalid arithmetic operand - expected a number, got: {0}")]
+    InvalidArithmeticOperand(serde_json::Value),
+    #[error("invalid json pointer (must be empty or start with '/'): {0}")]
+    InvalidJsonPointer(String),
     #[error("result failure: kind={kind}, message={message}, backend={backend}")]
     ResultFailure {
         kind: FunctionResultFailureErrorKind,
@@ -59,6 +71,8 @@ pub enum FuncBackendError {
     SerdeJson(#[from] serde_json::Error),
     #[error("unable to decode ulid")]
     Ulid(#[from] ulid::DecodeError),
+    #[error("unterminated placeholder in string template: {0}")]
+    UnterminatedTemplatePlaceholder(String),
     #[error("veritech client error: {0}")]
     VeritechClient(#[from] veritech_client::ClientError),
 }
@@ -105,6 +119,13 @@ pub enum FuncBackendKind {
     ResourcePayloadToValue,
     NormalizeToArray,
     Float,
+    Arithmetic,
+    Coalesce,
+    JoinArray,
+    JsonPointer,
+    StringTemplate,
+    /// A WebAssembly component, run by cyclone instead of lang-js.
+    Wasm,
 }
 
 impl From<FuncBackendKind> for si_events::FuncBackendKind {
@@ -135,6 +156,12 @@ impl From<FuncBackendKind> for si_events::FuncBackendKind {
                 si_events::FuncBackendKind::ResourcePayloadToValue
             }
             FuncBackendKind::NormalizeToArray => si_events::FuncBackendKind::NormalizeToArray,
+            FuncBackendKind::Arithmetic => si_events::FuncBackendKind::Arithmetic,
+            FuncBackendKind::Coalesce => si_events::FuncBackendKind::Coalesce,
+            FuncBackendKind::JoinArray => si_events::FuncBackendKind::JoinArray,
+            FuncBackendKind::JsonPointer => si_events::FuncBackendKind::JsonPointer,
+            FuncBackendKind::StringTemplate => si_events::FuncBackendKind::StringTemplate,
+            FuncBackendKind::Wasm => si_events::FuncBackendKind::Wasm,
         }
     }
 }
@@ -167,6 +194,12 @@ impl From<si_events::FuncBackendKind> for FuncBackendKind {
                 FuncBackendKind::ResourcePayloadToValue
             }
             si_events::FuncBackendKind::NormalizeToArray => FuncBackendKind::NormalizeToArray,
+            si_events::FuncBackendKind::Arithmetic => FuncBackendKind::Arithmetic,
+            si_events::FuncBackendKind::Coalesce => FuncBackendKind::Coalesce,
+            si_events::FuncBackendKind::JoinArray => FuncBackendKind::JoinArray,
+            si_events::FuncBackendKind::JsonPointer => FuncBackendKind::JsonPointer,
+            si_events::FuncBackendKind::StringTemplate => FuncBackendKind::StringTemplate,
+            si_events::FuncBackendKind::Wasm => FuncBackendKind::Wasm,
         }
     }
 }
@@ -207,6 +240,7 @@ pub enum FuncBackendResponseType {
     Void,
     Management,
     Float,
+    Plan,
 }
 
 impl From<FuncBackendResponseType> for si_events::FuncBackendResponseType {
@@ -238,6 +272,7 @@ impl From<FuncBackendResponseType> for si_events::FuncBackendResponseType {
             FuncBackendResponseType::Validation => si_events::FuncBackendResponseType::Validation,
             FuncBackendResponseType::Void => si_events::FuncBackendResponseType::Void,
             FuncBackendResponseType::Management => si_events::FuncBackendResponseType::Management,
+            FuncBackendResponseType::Plan => si_events::FuncBackendResponseType::Plan,
         }
     }
 }
@@ -271,6 +306,7 @@ impl From<si_events::FuncBackendResponseType> for FuncBackendResponseType {
             si_events::FuncBackendResponseType::Validation => FuncBackendResponseType::Validation,
             si_events::FuncBackendResponseType::Void => FuncBackendResponseType::Void,
             si_events::FuncBackendResponseType::Management => FuncBackendResponseType::Management,
+            si_events::FuncBackendResponseType::Plan => FuncBackendResponseType::Plan,
         }
     }
 }
@@ -286,6 +322,7 @@ impl From<ResolverFunctionResponseType> for FuncBackendResponseType {
             ResolverFunctionResponseType::Integer => FuncBackendResponseType::Integer,
             ResolverFunctionResponseType::Map => FuncBackendResponseType::Map,
             ResolverFunctionResponseType::Object => FuncBackendResponseType::Object,
+            ResolverFunctionResponseType::Plan => FuncBackendResponseType::Plan,
             ResolverFunctionResponseType::Qualification => FuncBackendResponseType::Qualification,
             ResolverFunctionResponseType::CodeGeneration => FuncBackendResponseType::CodeGeneration,
             ResolverFunctionResponseType::String => FuncBackendResponseType::String,
@@ -314,6 +351,7 @@ impl TryFrom<FuncBackendResponseType> for ResolverFunctionResponseType {
             FuncBackendResponseType::Identity => ResolverFunctionResponseType::Identity,
             FuncBackendResponseType::Map => ResolverFunctionResponseType::Map,
             FuncBackendResponseType::Object => ResolverFunctionResponseType::Object,
+            FuncBackendResponseType::Plan => ResolverFunctionResponseType::Plan,
             FuncBackendResponseType::Qualification => ResolverFunctionResponseType::Qualification,
             FuncBackendResponseType::CodeGeneration => ResolverFunctionResponseType::CodeGeneration,
             FuncBackendResponseType::String => ResolverFunctionResponseType::String,
@@ -339,19 +377,32 @@ impl ToLabelList for FuncBackendKind {}
 
 #[derive(Debug, Clone)]
 pub struct FuncDispatchContext {
-    pub veritech: VeritechClient,
+    pub veritech: DynFunctionExecutor,
     pub output_tx: mpsc::Sender<OutputStream>,
     pub func_run_id: FuncRunId,
     pub workspace_id: WorkspaceId,
     pub change_set_id: ChangeSetId,
+    /// Sent along with the request so cyclone kills the function after this many seconds.
+    pub timeout_secs: Option<u64>,
+    /// Sent along with the request so veritech knows which waiting requests to execute first.
+    pub priority: ExecutionPriority,
+    /// Sent along with the request so cyclone knows what to run the function's code with.
+    pub runtime: FunctionRuntime,
+    /// Sent along with the request so cyclone only lets the function reach these destinations.
+    /// Set by the [`FuncRunner`](crate::func::runner::FuncRunner) from the workspace's
+    /// [`FuncEgressPolicy`](crate::func::egress_policy::FuncEgressPolicy) for the func's kind.
+    pub egress_policy: Option<EgressPolicy>,
 }
 
 impl FuncDispatchContext {
     pub fn new(
-        veritech_client: VeritechClient,
+        veritech_client: DynFunctionExecutor,
         func_run_id: FuncRunId,
         workspace_id: WorkspaceId,
         change_set_id: ChangeSetId,
+        timeout_secs: Option<u64>,
+        priority: ExecutionPriority,
+        backend_kind: si_events::FuncBackendKind,
     ) -> (Self, mpsc::Receiver<OutputStream>) {
         let (output_tx, rx) = mpsc::channel(64);
         (
@@ -361,6 +412,13 @@ impl FuncDispatchContext {
                 func_run_id,
                 workspace_id,
                 change_set_id,
+                timeout_secs,
+                priority,
+                runtime: match backend_kind {
+                    si_events::FuncBackendKind::Wasm => FunctionRuntime::Wasm,
+                    _ => FunctionRuntime::Js,
+                },
+                egress_policy: None,
             },
             rx,
         )
@@ -369,7 +427,7 @@ impl FuncDispatchContext {
     pub fn into_inner(
         self,
     ) -> (
-        VeritechClient,
+        DynFunctionExecutor,
         mpsc::Sender<OutputStream>,
         WorkspaceId,
         ChangeSetId,
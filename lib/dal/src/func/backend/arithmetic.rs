@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+
+use crate::func::backend::{FuncBackend, FuncBackendError, FuncBackendResult};
+
+/// The operations supported by [`FuncBackendArithmetic`]. Each one folds the operands from left
+/// to right.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Deserialize, Serialize, Debug, Display, EnumString, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ArithmeticOperation {
+    Add,
+    Divide,
+    Max,
+    Min,
+    Modulo,
+    Multiply,
+    Subtract,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendArithmeticArgs {
+    pub operation: ArithmeticOperation,
+    pub operands: Option<serde_json::Value>,
+}
+
+/// Applies an [`ArithmeticOperation`] to a list of numeric operands (numeric strings are
+/// accepted too). The result stays an integer as long as every operand is an integer and the
+/// result can be represented exactly; otherwise it is a float. Null operands are skipped and no
+/// operands yield no value.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendArithmetic {
+    args: FuncBackendArithmeticArgs,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn parse(value: &serde_json::Value) -> FuncBackendResult<Option<Self>> {
+        let invalid = || FuncBackendError::InvalidArithmeticOperand(value.to_owned());
+        Ok(match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::Number(number) => Some(match number.as_i64() {
+                Some(integer) => Self::Integer(integer),
+                None => Self::Float(number.as_f64().ok_or_else(invalid)?),
+            }),
+            serde_json::Value::String(string) => Some(match string.trim().parse::<i64>() {
+                Ok(integer) => Self::Integer(integer),
+                Err(_) => Self::Float(string.trim().parse::<f64>().map_err(|_| invalid())?),
+            }),
+            _ => return Err(invalid()),
+        })
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Integer(integer) => integer as f64,
+            Self::Float(float) => float,
+        }
+    }
+
+    fn apply(self, operation: ArithmeticOperation, rhs: Self) -> FuncBackendResult<Self> {
+        if let (Self::Integer(lhs), Self::Integer(rhs)) = (self, rhs) {
+            let exact = match operation {
+                ArithmeticOperation::Add => lhs.checked_add(rhs),
+                ArithmeticOperation::Divide if rhs == 0 => {
+                    return Err(FuncBackendError::DivisionByZero)
+                }
+                ArithmeticOperation::Divide => match lhs.checked_rem(rhs) {
+                    Some(0) => lhs.checked_div(rhs),
+                    _ => None,
+                },
+                ArithmeticOperation::Max => Some(lhs.max(rhs)),
+                ArithmeticOperation::Min => Some(lhs.min(rhs)),
+                ArithmeticOperation::Modulo if rhs == 0 => {
+                    return Err(FuncBackendError::DivisionByZero)
+                }
+                ArithmeticOperation::Modulo => lhs.checked_rem(rhs),
+                ArithmeticOperation::Multiply => lhs.checked_mul(rhs),
+                ArithmeticOperation::Subtract => lhs.checked_sub(rhs),
+            };
+            if let Some(result) = exact {
+                return Ok(Self::Integer(result));
+            }
+        }
+
+        let (lhs, rhs) = (self.as_f64(), rhs.as_f64());
+        Ok(Self::Float(match operation {
+            ArithmeticOperation::Add => lhs + rhs,
+            ArithmeticOperation::Divide if rhs == 0.0 => {
+                return Err(FuncBackendError::DivisionByZero)
+            }
+            ArithmeticOperation::Divide => lhs / rhs,
+            ArithmeticOperation::Max => lhs.max(rhs),
+            ArithmeticOperation::Min => lhs.min(rhs),
+            ArithmeticOperation::Modulo if rhs == 0.0 => {
+                return Err(FuncBackendError::DivisionByZero)
+            }
+            ArithmeticOperation::Modulo => lhs % rhs,
+            ArithmeticOperation::Multiply => lhs * rhs,
+            ArithmeticOperation::Subtract => lhs - rhs,
+        }))
+    }
+
+    fn into_value(self) -> serde_json::Value {
+        match self {
+            Self::Integer(integer) => serde_json::Value::from(integer),
+            Self::Float(float) => serde_json::Value::from(float),
+        }
+    }
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendArithmetic {
+    type Args = FuncBackendArithmeticArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let operands = match self.args.operands {
+            Some(serde_json::Value::Array(operands)) => operands,
+            Some(serde_json::Value::Null) | None => vec![],
+            Some(operand) => vec![operand],
+        };
+
+        let mut result: Option<Number> = None;
+        for operand in &operands {
+            let Some(operand) = Number::parse(operand)? else {
+                continue;
+            };
+            result = Some(match result {
+                Some(lhs) => lhs.apply(self.args.operation, operand)?,
+                None => operand,
+            });
+        }
+
+        let value = result.map(Number::into_value);
+        Ok((value.clone(), value))
+    }
+}
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::func::backend::{FuncBackend, FuncBackendResult};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendCoalesceArgs {
+    pub values: Option<serde_json::Value>,
+}
+
+/// Returns the first non-null entry of the "values" argument. A single non-array value is
+/// returned as-is.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendCoalesce {
+    args: FuncBackendCoalesceArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendCoalesce {
+    type Args = FuncBackendCoalesceArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let value = match self.args.values {
+            Some(serde_json::Value::Array(entries)) => {
+                entries.into_iter().find(|entry| !entry.is_null())
+            }
+            Some(serde_json::Value::Null) | None => None,
+            Some(value) => Some(value),
+        };
+        Ok((value.clone(), value))
+    }
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::func::backend::{FuncBackend, FuncBackendResult};
+
+const DEFAULT_SEPARATOR: &str = ",";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendJoinArrayArgs {
+    pub values: Option<serde_json::Value>,
+    pub separator: Option<String>,
+}
+
+/// Joins the entries of an array into a single string. Null entries are skipped, strings are
+/// joined as-is and everything else is joined as JSON. The separator defaults to a comma.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendJoinArray {
+    args: FuncBackendJoinArrayArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendJoinArray {
+    type Args = FuncBackendJoinArrayArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let entries = match self.args.values {
+            Some(serde_json::Value::Array(entries)) => entries,
+            Some(serde_json::Value::Null) | None => vec![],
+            Some(entry) => vec![entry],
+        };
+
+        let mut parts = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match entry {
+                serde_json::Value::Null => {}
+                serde_json::Value::String(part) => parts.push(part),
+                entry => parts.push(serde_json::to_string(&entry)?),
+            }
+        }
+
+        let separator = self
+            .args
+            .separator
+            .unwrap_or_else(|| DEFAULT_SEPARATOR.to_owned());
+        let value = serde_json::Value::String(parts.join(&separator));
+        Ok((Some(value.clone()), Some(value)))
+    }
+}
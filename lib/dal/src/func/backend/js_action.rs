@@ -38,6 +38,10 @@ impl FuncDispatch for FuncBackendJsAction {
             code_base64: code_base64.into(),
             args: args.0,
             before,
+            timeout_secs: context.timeout_secs,
+            priority: context.priority,
+            runtime: context.runtime,
+            egress_policy: context.egress_policy.clone(),
         };
 
         Box::new(Self { context, request })
@@ -66,6 +70,7 @@ impl FuncDispatch for FuncBackendJsAction {
                             group: None,
                             message: message.clone(),
                             timestamp: std::cmp::max(Utc::now().timestamp(), 0) as u64,
+                            fields: None,
                         })
                         .await
                         .map_err(|_| FuncBackendError::SendError)?;
@@ -84,6 +89,7 @@ impl FuncDispatch for FuncBackendJsAction {
                         group: None,
                         message: failure.error().message.to_owned(),
                         timestamp: std::cmp::max(Utc::now().timestamp(), 0) as u64,
+                        fields: None,
                     })
                     .await
                     .map_err(|_| FuncBackendError::SendError)?;
@@ -39,6 +39,10 @@ impl FuncDispatch for FuncBackendJsAttribute {
             response_type: args.response_type,
             code_base64: code_base64.into(),
             before,
+            timeout_secs: context.timeout_secs,
+            priority: context.priority,
+            runtime: context.runtime,
+            egress_policy: context.egress_policy.clone(),
         };
 
         Box::new(Self { context, request })
@@ -68,6 +72,7 @@ impl FuncDispatch for FuncBackendJsAttribute {
                 | ResolverFunctionResponseType::Unset
                 | ResolverFunctionResponseType::Void
                 | ResolverFunctionResponseType::Management
+                | ResolverFunctionResponseType::Plan
                 | ResolverFunctionResponseType::Json => FunctionResult::Failure(failure),
                 ResolverFunctionResponseType::Qualification => {
                     FunctionResult::Success(Self::Output {
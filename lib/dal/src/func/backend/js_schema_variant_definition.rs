@@ -27,6 +27,9 @@ impl FuncDispatch for FuncBackendJsSchemaVariantDefinition {
             execution_id: context.func_run_id.to_string(),
             handler: handler.into(),
             code_base64: code_base64.to_owned(),
+            timeout_secs: context.timeout_secs,
+            priority: context.priority,
+            egress_policy: context.egress_policy.clone(),
         };
 
         Box::new(Self { context, request })
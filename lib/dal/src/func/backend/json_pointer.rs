@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::func::backend::{FuncBackend, FuncBackendError, FuncBackendResult};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendJsonPointerArgs {
+    pub value: Option<serde_json::Value>,
+    pub pointer: Option<String>,
+}
+
+/// Extracts the value found at an [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON
+/// pointer (e.g. `/tags/0/key`). Yields no value if nothing exists at the pointer.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendJsonPointer {
+    args: FuncBackendJsonPointerArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendJsonPointer {
+    type Args = FuncBackendJsonPointerArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let pointer = self.args.pointer.unwrap_or_default();
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(FuncBackendError::InvalidJsonPointer(pointer));
+        }
+
+        let value = self
+            .args
+            .value
+            .as_ref()
+            .and_then(|value| value.pointer(&pointer))
+            .cloned();
+        Ok((value.clone(), value))
+    }
+}
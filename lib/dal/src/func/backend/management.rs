@@ -46,6 +46,9 @@ impl FuncDispatch for FuncBackendManagement {
             current_view: args.current_view,
             variant_socket_map: args.variant_socket_map,
             before,
+            timeout_secs: context.timeout_secs,
+            priority: context.priority,
+            egress_policy: context.egress_policy.clone(),
         };
 
         Box::new(Self { context, request })
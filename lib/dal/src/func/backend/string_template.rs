@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::func::backend::{FuncBackend, FuncBackendError, FuncBackendResult};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendStringTemplateArgs {
+    pub template: Option<String>,
+    pub values: Option<serde_json::Value>,
+}
+
+/// Interpolates `${name}` placeholders in a template. Placeholders resolve against the "values"
+/// argument: object keys, array indices, or `${value}` when a single scalar was provided. Missing
+/// and null values render as an empty string; strings are inserted as-is and everything else is
+/// inserted as JSON.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendStringTemplate {
+    args: FuncBackendStringTemplateArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendStringTemplate {
+    type Args = FuncBackendStringTemplateArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let Some(template) = self.args.template else {
+            return Ok((None, None));
+        };
+        let values = self.args.values.unwrap_or(serde_json::Value::Null);
+
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template.as_str();
+        while let Some(start) = rest.find("${") {
+            rendered.push_str(&rest[..start]);
+            let after_open = &rest[start + 2..];
+            let end = after_open.find('}').ok_or_else(|| {
+                FuncBackendError::UnterminatedTemplatePlaceholder(template.clone())
+            })?;
+            let key = after_open[..end].trim();
+            match lookup(&values, key) {
+                None | Some(serde_json::Value::Null) => {}
+                Some(serde_json::Value::String(value)) => rendered.push_str(value),
+                Some(value) => rendered.push_str(&serde_json::to_string(value)?),
+            }
+            rest = &after_open[end + 1..];
+        }
+        rendered.push_str(rest);
+
+        let value = serde_json::Value::String(rendered);
+        Ok((Some(value.clone()), Some(value)))
+    }
+}
+
+fn lookup<'a>(values: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    match values {
+        serde_json::Value::Object(map) => map.get(key),
+        serde_json::Value::Array(array) => key.parse::<usize>().ok().and_then(|i| array.get(i)),
+        serde_json::Value::Null => None,
+        scalar => (key == "value").then_some(scalar),
+    }
+}
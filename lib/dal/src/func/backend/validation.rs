@@ -34,6 +34,9 @@ impl FuncDispatch for FuncBackendValidation {
             handler: "".to_string(),
             code_base64: "".to_string(),
             before: vec![],
+            timeout_secs: context.timeout_secs,
+            priority: context.priority,
+            egress_policy: context.egress_policy.clone(),
         };
 
         Box::new(Self { context, request })
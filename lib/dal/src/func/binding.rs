@@ -21,6 +21,8 @@ use crate::attribute::value::AttributeValueError;
 use crate::func::argument::FuncArgumentError;
 use crate::func::argument::FuncArgumentId;
 use crate::func::binding::attribute::AttributeBindingMalformedInput;
+use crate::func::intrinsics::IntrinsicFunc;
+use crate::func::timeout::FuncTimeoutError;
 use crate::func::FuncKind;
 use crate::management::prototype::ManagementPrototypeError;
 use crate::prop::PropError;
@@ -76,8 +78,12 @@ pub enum FuncBindingError {
     FuncArgument(#[from] FuncArgumentError),
     #[error("func argument missing for func arg: {0} with name: {1}")]
     FuncArgumentMissing(FuncArgumentId, String),
+    #[error("func timeout error: {0}")]
+    FuncTimeout(#[from] FuncTimeoutError),
     #[error("input socket error: {0}")]
     InputSocket(#[from] InputSocketError),
+    #[error("intrinsic func {0} requires argument \"{1}\" to be bound")]
+    IntrinsicArgumentUnbound(IntrinsicFunc, String),
     #[error("invalid attribute prototype argument source: {0}")]
     InvalidAttributePrototypeArgumentSource(AttributeFuncArgumentSource),
     #[error("invalid attribute prototype destination: {0}")]
@@ -398,7 +404,9 @@ impl FuncBinding {
     ) -> FuncBindingResult<Vec<FuncBinding>> {
         let func = Func::get_by_id(ctx, func_id).await?;
         let bindings = match func.kind {
-            FuncKind::Action => ActionBinding::assemble_action_bindings(ctx, func_id).await?,
+            FuncKind::Action | FuncKind::Plan => {
+                ActionBinding::assemble_action_bindings(ctx, func_id).await?
+            }
             FuncKind::Attribute => {
                 AttributeBinding::assemble_attribute_bindings(ctx, func_id).await?
             }
@@ -562,7 +570,12 @@ impl FuncBinding {
 
             match binding {
                 FuncBinding::Action(action) => {
-                    ActionBinding::delete_action_binding(ctx, action.action_prototype_id).await?;
+                    if action.is_plan_binding(ctx).await? {
+                        ActionBinding::delete_plan_binding(ctx, action.action_prototype_id).await?;
+                    } else {
+                        ActionBinding::delete_action_binding(ctx, action.action_prototype_id)
+                            .await?;
+                    }
                 }
                 FuncBinding::Attribute(attribute) => {
                     AttributeBinding::reset_attribute_binding(
@@ -596,7 +609,9 @@ impl FuncBinding {
     pub async fn compile_types(ctx: &DalContext, func_id: FuncId) -> FuncBindingResult<String> {
         let func = Func::get_by_id(ctx, func_id).await?;
         let types: String = match func.kind {
-            FuncKind::Action => ActionBinding::compile_action_types(ctx, func_id).await?,
+            FuncKind::Action | FuncKind::Plan => {
+                ActionBinding::compile_action_types(ctx, func_id).await?
+            }
             FuncKind::CodeGeneration | FuncKind::Qualification => {
                 LeafBinding::compile_leaf_func_types(ctx, func_id).await?
             }
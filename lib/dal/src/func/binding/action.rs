@@ -3,7 +3,10 @@ use telemetry::prelude::*;
 
 use crate::{
     action::prototype::{ActionKind, ActionPrototype},
-    func::binding::FuncBindingError,
+    func::{
+        binding::FuncBindingError,
+        timeout::{FuncTimeout, FuncTimeoutTarget},
+    },
     prop::PropPath,
     ActionPrototypeId, DalContext, Func, FuncId, Prop, SchemaVariant, SchemaVariantError,
     SchemaVariantId,
@@ -41,6 +44,58 @@ impl ActionBinding {
         Ok(bindings)
     }
 
+    /// Returns true if this binding is for the plan func of the [`ActionPrototype`], rather than
+    /// the func that runs the action. Plan func bindings are assembled the same way as action
+    /// bindings, since both are [`Use`](crate::EdgeWeightKind::Use) edges from the prototype.
+    pub async fn is_plan_binding(&self, ctx: &DalContext) -> FuncBindingResult<bool> {
+        Ok(
+            ActionPrototype::plan_func_id(ctx, self.action_prototype_id).await?
+                == Some(self.func_id),
+        )
+    }
+
+    /// Binds a plan [`Func`] to an existing [`ActionPrototype`], replacing the plan func it
+    /// already had
+    #[instrument(
+        level = "info",
+        skip(ctx),
+        name = "func.binding.action.create_plan_binding"
+    )]
+    pub async fn create_plan_binding(
+        ctx: &DalContext,
+        func_id: FuncId,
+        action_prototype_id: ActionPrototypeId,
+    ) -> FuncBindingResult<Vec<FuncBinding>> {
+        // don't add binding if parent is locked
+        let schema_variant_id =
+            ActionPrototype::schema_variant_id(ctx, action_prototype_id).await?;
+        SchemaVariant::error_if_locked(ctx, schema_variant_id).await?;
+
+        ActionPrototype::set_plan_func(ctx, action_prototype_id, func_id).await?;
+
+        FuncBinding::for_func_id(ctx, func_id).await
+    }
+
+    /// Unbinds the plan [`Func`] from an [`ActionPrototype`], leaving the prototype in place
+    #[instrument(
+        level = "info",
+        skip(ctx),
+        name = "func.binding.action.delete_plan_binding"
+    )]
+    pub async fn delete_plan_binding(
+        ctx: &DalContext,
+        action_prototype_id: ActionPrototypeId,
+    ) -> FuncBindingResult<EventualParent> {
+        // don't delete binding if parent is locked
+        let schema_variant_id =
+            ActionPrototype::schema_variant_id(ctx, action_prototype_id).await?;
+        SchemaVariant::error_if_locked(ctx, schema_variant_id).await?;
+
+        ActionPrototype::remove_plan_func(ctx, action_prototype_id).await?;
+
+        Ok(EventualParent::SchemaVariant(schema_variant_id))
+    }
+
     /// Updates the [`ActionKind`] for a given [`ActionPrototypeId`] by removing the existing [`ActionPrototype`]
     /// and creating a new one in its place
     #[instrument(
@@ -56,10 +111,16 @@ impl ActionBinding {
         let schema_variant_id =
             ActionPrototype::schema_variant_id(ctx, action_prototype_id).await?;
         let func_id = ActionPrototype::func_id(ctx, action_prototype_id).await?;
+        let plan_func_id = ActionPrototype::plan_func_id(ctx, action_prototype_id).await?;
         let func = Func::get_by_id(ctx, func_id).await?; // delete and recreate the prototype
+        let timeout = FuncTimeout::find_for_target(
+            ctx,
+            FuncTimeoutTarget::ActionPrototype(action_prototype_id),
+        )
+        .await?;
 
         ActionPrototype::remove(ctx, action_prototype_id).await?;
-        ActionPrototype::new(
+        let action_prototype = ActionPrototype::new(
             ctx,
             kind,
             func.name.to_owned(),
@@ -68,6 +129,17 @@ impl ActionBinding {
             func_id,
         )
         .await?;
+        if let Some(plan_func_id) = plan_func_id {
+            ActionPrototype::set_plan_func(ctx, action_prototype.id(), plan_func_id).await?;
+        }
+        if let Some(timeout) = timeout {
+            FuncTimeout::upsert(
+                ctx,
+                FuncTimeoutTarget::ActionPrototype(action_prototype.id()),
+                timeout.timeout_secs(),
+            )
+            .await?;
+        }
 
         FuncBinding::for_func_id(ctx, func_id).await
     }
@@ -168,14 +240,43 @@ impl ActionBinding {
         ctx: &DalContext,
         new_func_id: FuncId,
     ) -> FuncBindingResult<Vec<FuncBinding>> {
+        if self.is_plan_binding(ctx).await? {
+            ActionPrototype::set_plan_func(ctx, self.action_prototype_id, new_func_id).await?;
+            return FuncBinding::for_func_id(ctx, new_func_id).await;
+        }
+
         // cache existing config info
         let schema_variant_id = self.schema_variant_id;
+        let plan_func_id = ActionPrototype::plan_func_id(ctx, self.action_prototype_id).await?;
         let action_kind = self.kind;
+        let timeout = FuncTimeout::find_for_target(
+            ctx,
+            FuncTimeoutTarget::ActionPrototype(self.action_prototype_id),
+        )
+        .await?;
         // remove the existing action prototype and recreate it for the new func id
 
         ActionPrototype::remove(ctx, self.action_prototype_id).await?;
 
         Self::create_action_binding(ctx, new_func_id, action_kind, schema_variant_id).await?;
+        for (_, action_prototype_id) in
+            SchemaVariant::list_with_action_prototypes_for_action_func(ctx, new_func_id)
+                .await?
+                .into_iter()
+                .filter(|(variant_id, _)| *variant_id == schema_variant_id)
+        {
+            if let Some(plan_func_id) = plan_func_id {
+                ActionPrototype::set_plan_func(ctx, action_prototype_id, plan_func_id).await?;
+            }
+            if let Some(timeout) = &timeout {
+                FuncTimeout::upsert(
+                    ctx,
+                    FuncTimeoutTarget::ActionPrototype(action_prototype_id),
+                    timeout.timeout_secs(),
+                )
+                .await?;
+            }
+        }
 
         FuncBinding::for_func_id(ctx, new_func_id).await
     }
@@ -841,6 +841,54 @@ async fn validate_intrinsic_inputs(
         ));
     }
     match intrinsic_kind {
+        IntrinsicFunc::Arithmetic
+        | IntrinsicFunc::Coalesce
+        | IntrinsicFunc::JoinArray
+        | IntrinsicFunc::JsonPointer
+        | IntrinsicFunc::StringTemplate => {
+            // expressions combine any number of props, input sockets and static values, but
+            // they can only write to props and output sockets
+            if let AttributeFuncDestination::InputSocket(_) = output_location {
+                return Err(FuncBindingError::InvalidIntrinsicBinding);
+            }
+            if prototype_arguments
+                .iter()
+                .any(|arg| match arg.attribute_func_input_location {
+                    AttributeFuncArgumentSource::Prop(_)
+                    | AttributeFuncArgumentSource::InputSocket(_)
+                    | AttributeFuncArgumentSource::StaticArgument(_) => false,
+                    AttributeFuncArgumentSource::OutputSocket(_)
+                    | AttributeFuncArgumentSource::Secret(_) => true,
+                })
+            {
+                return Err(FuncBindingError::InvalidIntrinsicBinding);
+            }
+
+            let func_arguments = FuncArgument::list_for_func(ctx, func_id).await?;
+            if prototype_arguments.iter().any(|arg| {
+                !func_arguments
+                    .iter()
+                    .any(|func_arg| func_arg.id == arg.func_argument_id)
+            }) {
+                return Err(FuncBindingError::InvalidIntrinsicBinding);
+            }
+            for required_name in intrinsic_kind.required_argument_names() {
+                let bound = func_arguments
+                    .iter()
+                    .filter(|func_arg| func_arg.name == *required_name)
+                    .any(|func_arg| {
+                        prototype_arguments
+                            .iter()
+                            .any(|arg| arg.func_argument_id == func_arg.id)
+                    });
+                if !bound {
+                    return Err(FuncBindingError::IntrinsicArgumentUnbound(
+                        intrinsic_kind,
+                        required_name.to_string(),
+                    ));
+                }
+            }
+        }
         IntrinsicFunc::Identity
         | IntrinsicFunc::NormalizeToArray
         | IntrinsicFunc::ResourcePayloadToValue => {
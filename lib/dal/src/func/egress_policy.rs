@@ -0,0 +1,195 @@
+//! Egress policies restrict which network destinations a workspace's [`Funcs`](crate::Func) may
+//! reach while they run in cyclone, per [`FuncKind`]. For example, attribute and qualification
+//! funcs can be given no network access at all while actions may still call cloud APIs.
+//!
+//! A policy is an allow list of hostnames (`api.example.com`), subdomain wildcards
+//! (`*.amazonaws.com`), and CIDR blocks (`10.0.0.0/8`). A func kind without a policy may reach
+//! anything, and a policy with an empty allow list gives it no network at all. Policies are stored
+//! per workspace rather than per change set, so they cannot be loosened from within a change set.
+//!
+//! The policy is sent along with the function request, and cyclone routes the function's traffic
+//! through a proxy that enforces it. A function that tries to reach anything else fails with
+//! [`FunctionResultFailureErrorKind::EgressDenied`](veritech_client::FunctionResultFailureErrorKind),
+//! and each denied destination is added to the func run's logs. Since a command could ignore the
+//! proxy, functions held to a policy may not run commands (`siExec`) at all. WebAssembly funcs are
+//! not run by the lang server, so they are held to cyclone's own allowlist instead.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use telemetry::prelude::*;
+use thiserror::Error;
+use veritech_client::{EgressPolicy, EgressRule};
+
+use crate::{func::FuncKind, DalContext, TransactionsError, WorkspacePk};
+
+pub use si_id::FuncEgressPolicyId;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncEgressPolicyError {
+    #[error("unknown func kind: {0}")]
+    InvalidFuncKind(String),
+    #[error("invalid egress rule {0:?}: expected a hostname, a *.domain, or a CIDR block")]
+    InvalidRule(String),
+    #[error("egress policies do not apply to {0} funcs, since they do not run in cyclone")]
+    NotApplicable(FuncKind),
+    #[error("no egress policy for {0} funcs")]
+    NotFound(FuncKind),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncEgressPolicyResult<T> = Result<T, FuncEgressPolicyError>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncEgressPolicy {
+    pk: FuncEgressPolicyId,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    workspace_pk: WorkspacePk,
+    func_kind: FuncKind,
+    /// The destinations funcs of this kind may reach. Empty allows nothing.
+    allow: Vec<String>,
+}
+
+impl TryFrom<PgRow> for FuncEgressPolicy {
+    type Error = FuncEgressPolicyError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let func_kind: String = row.try_get("func_kind")?;
+        Ok(Self {
+            pk: row.try_get("pk")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            workspace_pk: row.try_get("workspace_pk")?,
+            func_kind: func_kind
+                .parse()
+                .map_err(|_| FuncEgressPolicyError::InvalidFuncKind(func_kind))?,
+            allow: row.try_get("allow")?,
+        })
+    }
+}
+
+impl From<FuncEgressPolicy> for EgressPolicy {
+    fn from(value: FuncEgressPolicy) -> Self {
+        Self { allow: value.allow }
+    }
+}
+
+impl FuncEgressPolicy {
+    pub fn pk(&self) -> FuncEgressPolicyId {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn func_kind(&self) -> FuncKind {
+        self.func_kind
+    }
+
+    pub fn allow(&self) -> &[String] {
+        &self.allow
+    }
+
+    /// Sets the allow list for funcs of the given kind in the current workspace, replacing any
+    /// existing one.
+    #[instrument(name = "func.egress_policy.set", level = "info", skip(ctx))]
+    pub async fn set(
+        ctx: &DalContext,
+        func_kind: FuncKind,
+        allow: Vec<String>,
+    ) -> FuncEgressPolicyResult<Self> {
+        if matches!(func_kind, FuncKind::Intrinsic | FuncKind::Unknown) {
+            return Err(FuncEgressPolicyError::NotApplicable(func_kind));
+        }
+        let mut normalized = Vec::with_capacity(allow.len());
+        for rule in allow {
+            if rule.parse::<EgressRule>().is_err() {
+                return Err(FuncEgressPolicyError::InvalidRule(rule));
+            }
+            let rule = rule.trim().to_lowercase();
+            if !normalized.contains(&rule) {
+                normalized.push(rule);
+            }
+        }
+
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO func_egress_policies (workspace_pk, func_kind, allow)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (workspace_pk, func_kind)
+                    DO UPDATE SET allow = EXCLUDED.allow, updated_at = CLOCK_TIMESTAMP()
+                    RETURNING *",
+                &[&workspace_pk, &func_kind.to_string(), &normalized],
+            )
+            .await?;
+
+        Self::try_from(row)
+    }
+
+    /// Finds the policy for funcs of the given kind in the current workspace, if there is one.
+    pub async fn for_func_kind(
+        ctx: &DalContext,
+        func_kind: FuncKind,
+    ) -> FuncEgressPolicyResult<Option<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM func_egress_policies WHERE workspace_pk = $1 AND func_kind = $2",
+                &[&workspace_pk, &func_kind.to_string()],
+            )
+            .await?;
+
+        maybe_row.map(Self::try_from).transpose()
+    }
+
+    /// Lists the policies for the current workspace, by func kind.
+    pub async fn list(ctx: &DalContext) -> FuncEgressPolicyResult<Vec<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM func_egress_policies WHERE workspace_pk = $1 ORDER BY func_kind",
+                &[&workspace_pk],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    /// Removes the policy for funcs of the given kind, letting them reach anything again.
+    #[instrument(name = "func.egress_policy.remove", level = "info", skip(ctx))]
+    pub async fn remove(ctx: &DalContext, func_kind: FuncKind) -> FuncEgressPolicyResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let removed = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "DELETE FROM func_egress_policies WHERE workspace_pk = $1 AND func_kind = $2
+                    RETURNING pk",
+                &[&workspace_pk, &func_kind.to_string()],
+            )
+            .await?;
+        if removed.is_none() {
+            return Err(FuncEgressPolicyError::NotFound(func_kind));
+        }
+
+        Ok(())
+    }
+}
@@ -11,7 +11,11 @@ use crate::PropKind;
 #[remain::sorted]
 #[derive(AsRefStr, Display, EnumIter, EnumString, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IntrinsicFunc {
+    Arithmetic,
+    Coalesce,
     Identity,
+    JoinArray,
+    JsonPointer,
     NormalizeToArray,
     ResourcePayloadToValue,
     SetArray,
@@ -22,6 +26,7 @@ pub enum IntrinsicFunc {
     SetMap,
     SetObject,
     SetString,
+    StringTemplate,
     Unset,
     Validation,
 }
@@ -54,6 +59,23 @@ impl IntrinsicFunc {
         builder.build().map_err(FuncError::IntrinsicSpecCreation)
     }
 
+    /// The expression intrinsics on their own, for installing them into workspaces created before
+    /// they existed.
+    pub fn expression_pkg_spec() -> FuncResult<PkgSpec> {
+        let mut builder = PkgSpec::builder();
+        builder.name("si-intrinsic-funcs");
+        builder.version("2023-05-24");
+        builder.created_at(DateTime::parse_from_rfc2822(
+            "Wed, 24 May 2023 00:00:00 PST",
+        )?);
+        builder.created_by("System Initiative");
+        for intrinsic in IntrinsicFunc::iter().filter(IntrinsicFunc::is_expression) {
+            builder.func(intrinsic.to_spec()?);
+        }
+
+        builder.build().map_err(FuncError::IntrinsicSpecCreation)
+    }
+
     pub fn to_spec(&self) -> FuncResult<FuncSpec> {
         let mut builder = FuncSpec::builder();
         builder.name(self.name());
@@ -65,8 +87,44 @@ impl IntrinsicFunc {
             .code_plaintext("");
 
         // These magic unique ids are here to keep them consistent with the intrinsic ids in the
-        // existing builtin packages (chicken/egg problem here a bit)
+        // existing builtin packages (chicken/egg problem here a bit). The expression intrinsics
+        // postdate those packages, so their ids only need to stay stable from here on.
         match self {
+            Self::Arithmetic => {
+                builder
+                    .unique_id("75e1732262700ac3745e08eb89763f419f7d7b03f3ef1005082500c2e35df863");
+                data_builder.backend_kind(FuncSpecBackendKind::Arithmetic);
+                data_builder.response_type(FuncSpecBackendResponseType::Identity);
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("operation")
+                        .kind(FuncArgumentKind::String)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("operands")
+                        .kind(FuncArgumentKind::Array)
+                        .element_kind(FuncArgumentKind::Any)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+            }
+            Self::Coalesce => {
+                builder
+                    .unique_id("301dd0b537562368be79a5b341078aa0f6b30357fcbef17c361cc024bbe0bf81");
+                data_builder.backend_kind(FuncSpecBackendKind::Coalesce);
+                data_builder.response_type(FuncSpecBackendResponseType::Identity);
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("values")
+                        .kind(FuncArgumentKind::Array)
+                        .element_kind(FuncArgumentKind::Any)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+            }
             Self::Identity => {
                 builder
                     .unique_id("c6938e12287ab65f8ba8234559178413f2e2c02c44ea08384ed6687a36ec4f50");
@@ -80,6 +138,47 @@ impl IntrinsicFunc {
                         .map_err(FuncError::IntrinsicSpecCreation)?,
                 );
             }
+            Self::JoinArray => {
+                builder
+                    .unique_id("8f1f74c6a795f6ffaaa7781b1309eb5a9b07b35cc9f5764fa37da0e5e3329d3b");
+                data_builder.backend_kind(FuncSpecBackendKind::JoinArray);
+                data_builder.response_type(FuncSpecBackendResponseType::String);
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("values")
+                        .kind(FuncArgumentKind::Array)
+                        .element_kind(FuncArgumentKind::Any)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("separator")
+                        .kind(FuncArgumentKind::String)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+            }
+            Self::JsonPointer => {
+                builder
+                    .unique_id("752ccfd16b869afcd345463d4eaa51b8abc2e36a2356446f65d9021b5da775b2");
+                data_builder.backend_kind(FuncSpecBackendKind::JsonPointer);
+                data_builder.response_type(FuncSpecBackendResponseType::Identity);
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("value")
+                        .kind(FuncArgumentKind::Any)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("pointer")
+                        .kind(FuncArgumentKind::String)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+            }
             Self::NormalizeToArray => {
                 builder
                     .unique_id("750b9044cd250a5f0e952dabe4150fa61450992e04e688be47096d50a4759d4f");
@@ -212,6 +311,26 @@ impl IntrinsicFunc {
                         .map_err(FuncError::IntrinsicSpecCreation)?,
                 );
             }
+            Self::StringTemplate => {
+                builder
+                    .unique_id("ea7de4c912465bdec1305d7b9ea8bfdc62b1b9ad3e9a908bd3aebf3c819360a9");
+                data_builder.backend_kind(FuncSpecBackendKind::StringTemplate);
+                data_builder.response_type(FuncSpecBackendResponseType::String);
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("template")
+                        .kind(FuncArgumentKind::String)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("values")
+                        .kind(FuncArgumentKind::Any)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+            }
             Self::Unset => {
                 builder
                     .unique_id("8143ff98fbe8954bb3ab89ee521335d45ba9a42b7b79289eff53b503c4392c37");
@@ -245,7 +364,11 @@ impl IntrinsicFunc {
 
     pub fn name(&self) -> &str {
         match self {
+            Self::Arithmetic => "si:arithmetic",
+            Self::Coalesce => "si:coalesce",
             Self::Identity => "si:identity",
+            Self::JoinArray => "si:joinArray",
+            Self::JsonPointer => "si:jsonPointer",
             Self::NormalizeToArray => "si:normalizeToArray",
             Self::ResourcePayloadToValue => "si:resourcePayloadToValue",
             Self::SetArray => "si:setArray",
@@ -256,14 +379,58 @@ impl IntrinsicFunc {
             Self::SetObject => "si:setObject",
             Self::SetJson => "si:setJson",
             Self::SetString => "si:setString",
+            Self::StringTemplate => "si:stringTemplate",
             Self::Unset => "si:unset",
             Self::Validation => "si:validation",
         }
     }
 
+    /// Expression intrinsics transform their inputs in-process (e.g. templating strings or doing
+    /// arithmetic) and, unlike the other intrinsics, may be bound to more than one input,
+    /// including static values.
+    pub fn is_expression(&self) -> bool {
+        match self {
+            Self::Arithmetic
+            | Self::Coalesce
+            | Self::JoinArray
+            | Self::JsonPointer
+            | Self::StringTemplate => true,
+            Self::Identity
+            | Self::NormalizeToArray
+            | Self::ResourcePayloadToValue
+            | Self::SetArray
+            | Self::SetBoolean
+            | Self::SetFloat
+            | Self::SetInteger
+            | Self::SetJson
+            | Self::SetMap
+            | Self::SetObject
+            | Self::SetString
+            | Self::Unset
+            | Self::Validation => false,
+        }
+    }
+
+    /// The names of the func arguments that must be bound for an expression intrinsic to
+    /// produce a value. Empty for every other intrinsic.
+    pub fn required_argument_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Arithmetic => &["operation", "operands"],
+            Self::Coalesce => &["values"],
+            Self::JoinArray => &["values"],
+            Self::JsonPointer => &["value", "pointer"],
+            Self::StringTemplate => &["template"],
+            _ => &[],
+        }
+    }
+
     pub fn maybe_from_str(s: impl AsRef<str>) -> Option<Self> {
         Some(match s.as_ref() {
+            "si:arithmetic" => Self::Arithmetic,
+            "si:coalesce" => Self::Coalesce,
             "si:identity" => Self::Identity,
+            "si:joinArray" => Self::JoinArray,
+            "si:jsonPointer" => Self::JsonPointer,
             "si:normalizeToArray" => Self::NormalizeToArray,
             "si:resourcePayloadToValue" => Self::ResourcePayloadToValue,
             "si:setArray" => Self::SetArray,
@@ -274,6 +441,7 @@ impl IntrinsicFunc {
             "si:setObject" => Self::SetObject,
             "si:setJson" => Self::SetJson,
             "si:setString" => Self::SetString,
+            "si:stringTemplate" => Self::StringTemplate,
             "si:unset" => Self::Unset,
             "si:validation" => Self::Validation,
             _ => {
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use si_events::FuncKind as EventFuncKind;
-use strum::{AsRefStr, Display};
+use strum::{AsRefStr, Display, EnumString};
 use telemetry::prelude::warn;
 
 use crate::func::FuncResult;
@@ -9,7 +9,9 @@ use crate::{FuncBackendKind, FuncBackendResponseType};
 /// Describes the kind of [`Func`](crate::Func).
 /// This type is postcard serialized, so cannot be "remain::sorted". New enum
 /// variants must go at the end
-#[derive(AsRefStr, Deserialize, Display, Serialize, Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[derive(
+    AsRefStr, Deserialize, Display, EnumString, Serialize, Debug, Eq, PartialEq, Clone, Copy, Hash,
+)]
 pub enum FuncKind {
     Action,
     Attribute,
@@ -20,6 +22,8 @@ pub enum FuncKind {
     SchemaVariantDefinition,
     Unknown,
     Management,
+    /// Describes the changes an action would make, without making them.
+    Plan,
 }
 
 impl From<EventFuncKind> for FuncKind {
@@ -34,6 +38,7 @@ impl From<EventFuncKind> for FuncKind {
             EventFuncKind::SchemaVariantDefinition => FuncKind::SchemaVariantDefinition,
             EventFuncKind::Unknown => FuncKind::Unknown,
             EventFuncKind::Management => FuncKind::Management,
+            EventFuncKind::Plan => FuncKind::Plan,
         }
     }
 }
@@ -50,6 +55,7 @@ impl From<FuncKind> for si_events::FuncKind {
             FuncKind::SchemaVariantDefinition => si_events::FuncKind::SchemaVariantDefinition,
             FuncKind::Unknown => si_events::FuncKind::Unknown,
             FuncKind::Management => si_events::FuncKind::Management,
+            FuncKind::Plan => si_events::FuncKind::Plan,
         }
     }
 }
@@ -61,6 +67,13 @@ impl FuncKind {
     ) -> FuncResult<FuncKind> {
         Ok(match func_backend_kind {
             FuncBackendKind::JsAttribute => match func_backend_response_type {
+                FuncBackendResponseType::CodeGeneration => FuncKind::CodeGeneration,
+                FuncBackendResponseType::Qualification => FuncKind::Qualification,
+                FuncBackendResponseType::Plan => FuncKind::Plan,
+                _ => FuncKind::Attribute,
+            },
+            FuncBackendKind::Wasm => match func_backend_response_type {
+                FuncBackendResponseType::Action => FuncKind::Action,
                 FuncBackendResponseType::CodeGeneration => FuncKind::CodeGeneration,
                 FuncBackendResponseType::Qualification => FuncKind::Qualification,
                 _ => FuncKind::Attribute,
@@ -69,18 +82,23 @@ impl FuncKind {
             FuncBackendKind::JsAuthentication => FuncKind::Authentication,
             FuncBackendKind::JsSchemaVariantDefinition => FuncKind::SchemaVariantDefinition,
             FuncBackendKind::Management => FuncKind::Management,
-            FuncBackendKind::Array
+            FuncBackendKind::Arithmetic
+            | FuncBackendKind::Array
             | FuncBackendKind::Json
             | FuncBackendKind::Boolean
+            | FuncBackendKind::Coalesce
             | FuncBackendKind::Diff
             | FuncBackendKind::Identity
             | FuncBackendKind::Float
             | FuncBackendKind::Integer
+            | FuncBackendKind::JoinArray
+            | FuncBackendKind::JsonPointer
             | FuncBackendKind::Map
             | FuncBackendKind::NormalizeToArray
             | FuncBackendKind::Object
             | FuncBackendKind::ResourcePayloadToValue
             | FuncBackendKind::String
+            | FuncBackendKind::StringTemplate
             | FuncBackendKind::Unset
             | FuncBackendKind::Validation => FuncKind::Intrinsic,
             FuncBackendKind::JsReconciliation | FuncBackendKind::JsValidation => {
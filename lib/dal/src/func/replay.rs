@@ -0,0 +1,263 @@
+//! Replaying runs a past [`FuncRun`] again with the arguments it was recorded with, either with
+//! the code it ran at the time or with the current code of its [`Func`], so that a failed refresh
+//! or a surprising attribute value can be debugged by comparing the two runs.
+//!
+//! Only the arguments and code are taken from the past run. Secrets are resolved again for the
+//! run's component, just like for any other run, since they are never stored with a run. Action
+//! funcs change the "outside world", so only refresh actions can be replayed.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use si_events::{ActionKind, CasValue, ContentHash, FuncRun, FuncRunId, FuncRunState};
+use si_layer_cache::LayerDbError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    action::prototype::{ActionPrototype, ActionPrototypeError},
+    func::runner::{FuncRunner, FuncRunnerError},
+    layer_db_types::ContentTypes,
+    DalContext, Func, FuncError, FuncId,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncRunReplayError {
+    #[error("{1:?} action func runs cannot be replayed, since they would change resources: {0}")]
+    ActionNotReplayable(FuncRunId, ActionKind),
+    #[error("action prototype error: {0}")]
+    ActionPrototype(#[from] Box<ActionPrototypeError>),
+    #[error("code not found for func run: {0}")]
+    CodeNotFound(FuncRunId),
+    #[error("func error: {0}")]
+    Func(#[from] Box<FuncError>),
+    #[error("more than one func is named {1}, so the func for func run {0} is ambiguous")]
+    FuncAmbiguous(FuncRunId, String),
+    #[error("func not found for func run {0}: {1}")]
+    FuncNotFound(FuncRunId, String),
+    #[error("func run not found: {0}")]
+    FuncRunNotFound(FuncRunId),
+    #[error("func runner error: {0}")]
+    FuncRunner(#[from] Box<FuncRunnerError>),
+    #[error("func runner has failed to send a value and exited")]
+    FuncRunnerSend,
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+}
+
+pub type FuncRunReplayResult<T> = Result<T, FuncRunReplayError>;
+
+/// Which code to replay a [`FuncRun`] with.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FuncRunReplayCode {
+    /// The code the func run was recorded with.
+    #[default]
+    Original,
+    /// The current code of the func.
+    Current,
+}
+
+/// A past [`FuncRun`] next to its replay.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunReplay {
+    pub original_func_run_id: FuncRunId,
+    pub replay_func_run_id: FuncRunId,
+    pub code: FuncRunReplayCode,
+    /// Whether the replay ran different code than the original run.
+    pub code_changed: bool,
+    pub original_state: FuncRunState,
+    pub replay_state: FuncRunState,
+    pub original_value: Option<serde_json::Value>,
+    pub replay_value: Option<serde_json::Value>,
+}
+
+impl FuncRunReplay {
+    /// Whether both runs ended the same way, with the same value.
+    pub fn results_match(&self) -> bool {
+        self.original_state == self.replay_state && self.original_value == self.replay_value
+    }
+
+    /// Replays the [`FuncRun`] and waits for the replay to finish. A replay that fails is still
+    /// returned, so that it can be compared with the original run.
+    #[instrument(name = "func.replay.replay", level = "info", skip(ctx))]
+    pub async fn replay(
+        ctx: &DalContext,
+        func_run_id: FuncRunId,
+        code: FuncRunReplayCode,
+    ) -> FuncRunReplayResult<Self> {
+        let original = ctx
+            .layer_db()
+            .func_run()
+            .read(func_run_id)
+            .await?
+            .ok_or(FuncRunReplayError::FuncRunNotFound(func_run_id))?;
+        if let Some(action_kind) = original.action_kind() {
+            if action_kind != ActionKind::Refresh {
+                return Err(FuncRunReplayError::ActionNotReplayable(
+                    func_run_id,
+                    action_kind,
+                ));
+            }
+        }
+
+        let mut func = Func::get_by_id(ctx, Self::func_id(ctx, &original).await?)
+            .await
+            .map_err(Box::new)?;
+        let function_code_cas_address = match code {
+            FuncRunReplayCode::Original => {
+                if let Some(code_base64) = Self::original_code_base64(ctx, &original).await? {
+                    func.code_base64 = Some(code_base64);
+                }
+                original.function_code_cas_address()
+            }
+            FuncRunReplayCode::Current => match func.code_base64.as_ref() {
+                Some(code_base64) => Self::write_value(ctx, code_base64.clone().into())?,
+                None => ContentHash::new("".as_bytes()),
+            },
+        };
+
+        let (replay_func_run_id, result_channel) =
+            FuncRunner::run_replay(ctx, &original, func, function_code_cas_address)
+                .await
+                .map_err(Box::new)?;
+
+        match result_channel
+            .await
+            .map_err(|_| FuncRunReplayError::FuncRunnerSend)?
+        {
+            Ok(func_run_value) => {
+                let value_address = match func_run_value.value().cloned() {
+                    Some(value) => Some(Self::write_value(ctx, value)?),
+                    None => None,
+                };
+                let unprocessed_value_address = match func_run_value.unprocessed_value().cloned() {
+                    Some(value) => Some(Self::write_value(ctx, value)?),
+                    None => None,
+                };
+                ctx.layer_db()
+                    .func_run()
+                    .set_values_and_set_state_to_success(
+                        replay_func_run_id,
+                        unprocessed_value_address,
+                        value_address,
+                        ctx.events_tenancy(),
+                        ctx.events_actor(),
+                    )
+                    .await?;
+            }
+            // The runner has already recorded the failure on the replay
+            Err(FuncRunnerError::ResultFailure { .. }) => {}
+            Err(err) => return Err(Box::new(err).into()),
+        }
+
+        Self::compare(ctx, func_run_id, replay_func_run_id, code).await
+    }
+
+    /// Compares a past [`FuncRun`] with a replay of it.
+    pub async fn compare(
+        ctx: &DalContext,
+        original_func_run_id: FuncRunId,
+        replay_func_run_id: FuncRunId,
+        code: FuncRunReplayCode,
+    ) -> FuncRunReplayResult<Self> {
+        let (original, original_value) = Self::read_with_value(ctx, original_func_run_id).await?;
+        let (replay, replay_value) = Self::read_with_value(ctx, replay_func_run_id).await?;
+
+        Ok(Self {
+            original_func_run_id,
+            replay_func_run_id,
+            code,
+            code_changed: original.function_code_cas_address()
+                != replay.function_code_cas_address(),
+            original_state: original.state(),
+            replay_state: replay.state(),
+            original_value,
+            replay_value,
+        })
+    }
+
+    async fn read_with_value(
+        ctx: &DalContext,
+        func_run_id: FuncRunId,
+    ) -> FuncRunReplayResult<(Arc<FuncRun>, Option<serde_json::Value>)> {
+        let func_run = ctx
+            .layer_db()
+            .func_run()
+            .read(func_run_id)
+            .await?
+            .ok_or(FuncRunReplayError::FuncRunNotFound(func_run_id))?;
+        let value = match func_run.result_value_cas_address() {
+            Some(address) => ctx
+                .layer_db()
+                .cas()
+                .try_read_as::<CasValue>(&address)
+                .await?
+                .map(Into::into),
+            None => None,
+        };
+
+        Ok((func_run, value))
+    }
+
+    /// The func that ran, as it is now. Action runs know their prototype; other runs can only be
+    /// matched by name, which fails if more than one func has that name rather than replaying
+    /// the wrong one.
+    async fn func_id(ctx: &DalContext, original: &FuncRun) -> FuncRunReplayResult<FuncId> {
+        if let Some(action_prototype_id) = original.action_prototype_id() {
+            return Ok(ActionPrototype::func_id(ctx, action_prototype_id)
+                .await
+                .map_err(Box::new)?);
+        }
+
+        let func_ids = Func::list_ids_by_name(ctx, original.function_name())
+            .await
+            .map_err(Box::new)?;
+        match func_ids.as_slice() {
+            [func_id] => Ok(*func_id),
+            [] => Err(FuncRunReplayError::FuncNotFound(
+                original.id(),
+                original.function_name().to_owned(),
+            )),
+            _ => Err(FuncRunReplayError::FuncAmbiguous(
+                original.id(),
+                original.function_name().to_owned(),
+            )),
+        }
+    }
+
+    /// The code the run was recorded with. Funcs without code (such as intrinsics) record an
+    /// empty address, which isn't in the CAS.
+    async fn original_code_base64(
+        ctx: &DalContext,
+        original: &FuncRun,
+    ) -> FuncRunReplayResult<Option<String>> {
+        let address = original.function_code_cas_address();
+        if address == ContentHash::new("".as_bytes()) {
+            return Ok(None);
+        }
+
+        let code: serde_json::Value = ctx
+            .layer_db()
+            .cas()
+            .try_read_as::<CasValue>(&address)
+            .await?
+            .ok_or(FuncRunReplayError::CodeNotFound(original.id()))?
+            .into();
+
+        Ok(code.as_str().map(ToOwned::to_owned))
+    }
+
+    fn write_value(ctx: &DalContext, value: serde_json::Value) -> FuncRunReplayResult<ContentHash> {
+        let (address, _) = ctx.layer_db().cas().write_func_run_data(
+            Arc::new(ContentTypes::FuncRunData(value.into())),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        Ok(address)
+    }
+}
@@ -0,0 +1,355 @@
+//! Retention for [`FuncRuns`](si_events::FuncRun), their logs, and the arguments, code, and results
+//! they reference in the content store, which would otherwise be kept forever.
+//!
+//! Each workspace may have a [`FuncRunRetentionPolicy`]; workspaces without one use
+//! [`FuncRunRetention::default`]. A run is kept while it is younger than `keep_days`, or while it is
+//! among the newest `keep_last` runs for its action, its component, or its func. Runs that have not
+//! finished are always kept, as are the newest run and the newest successful run for each attribute
+//! value, since those are looked up to show the attribute's current state.
+//!
+//! Expired runs are removed by [`collect_garbage`], which sdf runs in the background as a
+//! migration mode. The content store is shared by every workspace, so only entries written with
+//! [`write_func_run_data`](si_layer_cache::db::cas::CasDb::write_func_run_data) are considered,
+//! since nothing but func runs refers to them. Entries written by older versions may be shared with
+//! snapshots and are left alone.
+//!
+//! A run that is in flight writes its content before the func run that refers to it, so content
+//! that looks unused may be about to be used again. Rather than being removed along with the runs,
+//! unused content is marked, and [`sweep_func_run_data`] removes it on a later pass, once
+//! [`SWEEP_DELAY`] has passed, if no func run has started using it in the meantime.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::{PgError, PgRow};
+use si_events::{ContentHash, FuncRunId};
+use si_layer_cache::LayerDbError;
+use telemetry::prelude::*;
+use telemetry_utils::metric;
+use thiserror::Error;
+
+use crate::{DalContext, TransactionsError, WorkspacePk};
+
+pub use si_id::FuncRunRetentionPolicyId;
+
+/// The most func runs a single pass of [`collect_garbage`] removes from a workspace, so that one
+/// busy workspace cannot hold up the rest. Whatever is left is picked up by the next pass.
+pub const MAX_FUNC_RUNS_PER_PASS: u32 = 10_000;
+
+/// The most marked content store entries a single pass of [`sweep_func_run_data`] removes.
+pub const MAX_CAS_BLOBS_PER_SWEEP: u32 = 10_000;
+
+/// How long content store entries stay marked before [`sweep_func_run_data`] removes them, which
+/// is long enough for any run that was in flight when they were marked to have been written.
+pub const SWEEP_DELAY: Duration = Duration::hours(1);
+
+const DELETE_BATCH_SIZE: usize = 500;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncRunRetentionError {
+    #[error("keep_days must be at most {}, got {0}", i32::MAX)]
+    InvalidKeepDays(u32),
+    #[error("keep_last must be between 1 and {}, got {0}", i32::MAX)]
+    InvalidKeepLast(u32),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("no func run retention policy for this workspace")]
+    NotFound,
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncRunRetentionResult<T> = Result<T, FuncRunRetentionError>;
+
+/// How long func runs are kept.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunRetention {
+    /// How many of the newest runs to keep for each action, component, and func, however old.
+    pub keep_last: u32,
+    /// How many days to keep every run for.
+    pub keep_days: u32,
+}
+
+impl Default for FuncRunRetention {
+    fn default() -> Self {
+        Self {
+            keep_last: 50,
+            keep_days: 30,
+        }
+    }
+}
+
+impl FuncRunRetention {
+    /// Runs last updated before this time may be expired.
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::days(i64::from(self.keep_days))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunRetentionPolicy {
+    pk: FuncRunRetentionPolicyId,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    workspace_pk: WorkspacePk,
+    #[serde(flatten)]
+    retention: FuncRunRetention,
+}
+
+impl TryFrom<PgRow> for FuncRunRetentionPolicy {
+    type Error = FuncRunRetentionError;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let keep_last: i32 = row.try_get("keep_last")?;
+        let keep_days: i32 = row.try_get("keep_days")?;
+        Ok(Self {
+            pk: row.try_get("pk")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            workspace_pk: row.try_get("workspace_pk")?,
+            // Both are checked to be positive before they are stored
+            retention: FuncRunRetention {
+                keep_last: keep_last.max(1).unsigned_abs(),
+                keep_days: keep_days.max(0).unsigned_abs(),
+            },
+        })
+    }
+}
+
+impl FuncRunRetentionPolicy {
+    pub fn pk(&self) -> FuncRunRetentionPolicyId {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn retention(&self) -> FuncRunRetention {
+        self.retention
+    }
+
+    /// Sets the retention for the current workspace, replacing any existing policy. At least the
+    /// newest run has to be kept, since action history and qualifications are read from it.
+    #[instrument(name = "func.retention_policy.set", level = "info", skip(ctx))]
+    pub async fn set(
+        ctx: &DalContext,
+        retention: FuncRunRetention,
+    ) -> FuncRunRetentionResult<Self> {
+        let keep_last = i32::try_from(retention.keep_last)
+            .ok()
+            .filter(|keep_last| *keep_last >= 1)
+            .ok_or(FuncRunRetentionError::InvalidKeepLast(retention.keep_last))?;
+        let keep_days = i32::try_from(retention.keep_days)
+            .map_err(|_| FuncRunRetentionError::InvalidKeepDays(retention.keep_days))?;
+
+        let workspace_pk = ctx.workspace_pk()?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO func_run_retention_policies (workspace_pk, keep_last, keep_days)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (workspace_pk)
+                    DO UPDATE SET keep_last = EXCLUDED.keep_last,
+                                  keep_days = EXCLUDED.keep_days,
+                                  updated_at = CLOCK_TIMESTAMP()
+                    RETURNING *",
+                &[&workspace_pk, &keep_last, &keep_days],
+            )
+            .await?;
+
+        Self::try_from(row)
+    }
+
+    /// Finds the policy for the current workspace, if it has one.
+    pub async fn get(ctx: &DalContext) -> FuncRunRetentionResult<Option<Self>> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM func_run_retention_policies WHERE workspace_pk = $1",
+                &[&workspace_pk],
+            )
+            .await?;
+
+        maybe_row.map(Self::try_from).transpose()
+    }
+
+    /// Lists the policies for every workspace that has one.
+    pub async fn list_all(ctx: &DalContext) -> FuncRunRetentionResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query("SELECT * FROM func_run_retention_policies", &[])
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    /// Removes the policy for the current workspace, so it goes back to the default retention.
+    #[instrument(name = "func.retention_policy.remove", level = "info", skip(ctx))]
+    pub async fn remove(ctx: &DalContext) -> FuncRunRetentionResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        let removed = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "DELETE FROM func_run_retention_policies WHERE workspace_pk = $1 RETURNING pk",
+                &[&workspace_pk],
+            )
+            .await?;
+        if removed.is_none() {
+            return Err(FuncRunRetentionError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+/// What [`collect_garbage`] removed from a workspace, or would have removed in a dry run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FuncRunGarbage {
+    /// Expired func runs, along with their logs.
+    pub func_runs: usize,
+    /// Content store entries for arguments, code, and results that no remaining func run uses,
+    /// which are marked for [`sweep_func_run_data`] to remove.
+    pub cas_blobs: usize,
+}
+
+/// Removes a workspace's expired func runs and their logs, and marks the arguments, code, and
+/// results that no other func run uses for [`sweep_func_run_data`]. A dry run only counts what
+/// would be removed.
+#[instrument(
+    name = "func.retention.collect_garbage",
+    level = "info",
+    skip(ctx),
+    fields(si.workspace.id = %workspace_pk)
+)]
+pub async fn collect_garbage(
+    ctx: &DalContext,
+    workspace_pk: WorkspacePk,
+    retention: FuncRunRetention,
+    dry_run: bool,
+) -> FuncRunRetentionResult<FuncRunGarbage> {
+    let func_run_db = ctx.layer_db().func_run();
+    let expired = func_run_db
+        .list_expired(
+            workspace_pk,
+            retention.keep_last.max(1),
+            retention.cutoff(Utc::now()),
+            MAX_FUNC_RUNS_PER_PASS,
+        )
+        .await?;
+    let expired_ids: Vec<FuncRunId> = expired.iter().map(|func_run| func_run.id()).collect();
+
+    // Content is shared, so only what no other func run uses is orphaned. This has to be checked
+    // before the runs are gone, so that dry runs get the same answer.
+    let candidates: HashSet<ContentHash> = expired
+        .iter()
+        .flat_map(|func_run| {
+            [
+                Some(func_run.function_args_cas_address()),
+                Some(func_run.function_code_cas_address()),
+                func_run.result_value_cas_address(),
+                func_run.result_unprocessed_value_cas_address(),
+            ]
+        })
+        .flatten()
+        .collect();
+    let candidates: Vec<ContentHash> = candidates.into_iter().collect();
+    let mut orphaned = Vec::new();
+    for address in func_run_db.filter_func_run_data(&candidates).await? {
+        if !func_run_db
+            .references_cas_address(address, &expired_ids)
+            .await?
+        {
+            orphaned.push(address);
+        }
+    }
+
+    metric!(monotonic_counter.func_run_gc.expired_func_runs = expired_ids.len() as u64);
+    metric!(monotonic_counter.func_run_gc.orphaned_cas_blobs = orphaned.len() as u64);
+
+    let garbage = FuncRunGarbage {
+        func_runs: expired_ids.len(),
+        cas_blobs: orphaned.len(),
+    };
+    if dry_run {
+        info!(
+            func_runs = garbage.func_runs,
+            cas_blobs = garbage.cas_blobs,
+            "dry run: would delete expired func runs"
+        );
+        return Ok(garbage);
+    }
+
+    for batch in expired_ids.chunks(DELETE_BATCH_SIZE) {
+        ctx.layer_db()
+            .func_run_log()
+            .delete_for_func_run_ids(batch)
+            .await?;
+        func_run_db.delete_many(batch).await?;
+        metric!(monotonic_counter.func_run_gc.deleted_func_runs = batch.len() as u64);
+    }
+    for batch in orphaned.chunks(DELETE_BATCH_SIZE) {
+        func_run_db.mark_cas_candidates(batch).await?;
+    }
+
+    info!(
+        func_runs = garbage.func_runs,
+        cas_blobs = garbage.cas_blobs,
+        "deleted expired func runs"
+    );
+
+    Ok(garbage)
+}
+
+/// Removes the content store entries that [`collect_garbage`] marked before `marked_before`, unless
+/// a func run has started using them since. Returns how many were removed, or in a dry run how
+/// many are due to be checked.
+#[instrument(name = "func.retention.sweep_func_run_data", level = "info", skip(ctx))]
+pub async fn sweep_func_run_data(
+    ctx: &DalContext,
+    marked_before: DateTime<Utc>,
+    dry_run: bool,
+) -> FuncRunRetentionResult<usize> {
+    let func_run_db = ctx.layer_db().func_run();
+    let marked = func_run_db
+        .list_cas_candidates(marked_before, MAX_CAS_BLOBS_PER_SWEEP)
+        .await?;
+    if dry_run {
+        info!(
+            cas_blobs = marked.len(),
+            "dry run: would sweep func run data"
+        );
+        return Ok(marked.len());
+    }
+
+    let mut deleted = 0;
+    for address in marked {
+        if func_run_db.sweep_cas_candidate(address).await? {
+            ctx.layer_db().cas().evict_from_caches(
+                &address,
+                ctx.events_tenancy(),
+                ctx.events_actor(),
+            )?;
+            deleted += 1;
+        }
+    }
+    metric!(monotonic_counter.func_run_gc.deleted_cas_blobs = deleted as u64);
+    info!(cas_blobs = deleted, "swept func run data");
+
+    Ok(deleted)
+}
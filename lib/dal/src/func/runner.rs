@@ -3,11 +3,12 @@ use std::collections::VecDeque;
 use std::sync::Arc;
 use ulid::Ulid;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use si_events::{
-    ActionId, ActionResultState, CasValue, ContentHash, EncryptedSecretKey, FuncRun,
-    FuncRunBuilder, FuncRunBuilderError, FuncRunId, FuncRunLog, FuncRunLogId, FuncRunValue,
+    ActionId, ActionResultState, CasValue, ContentHash, EncryptedSecretKey, ExecutionPriority,
+    FuncRun, FuncRunBuilder, FuncRunBuilderError, FuncRunId, FuncRunLog, FuncRunLogId,
+    FuncRunLogLevel, FuncRunLogRecord, FuncRunValue,
 };
 use si_layer_cache::LayerDbError;
 use telemetry::prelude::*;
@@ -24,6 +25,7 @@ use crate::attribute::prototype::argument::{
     AttributePrototypeArgument, AttributePrototypeArgumentError, AttributePrototypeArgumentId,
 };
 use crate::component::socket::ComponentInputSocket;
+use crate::layer_db_types::ContentTypes;
 use crate::management::prototype::ManagementPrototypeId;
 use crate::prop::PropError;
 use crate::schema::variant::root_prop::RootPropChild;
@@ -35,6 +37,8 @@ use crate::{
     },
     attribute::value::AttributeValueError,
     func::backend::FuncBackendError,
+    func::egress_policy::{FuncEgressPolicy, FuncEgressPolicyError},
+    func::timeout::{FuncTimeout, FuncTimeoutError},
     ActionPrototypeId, AttributeValue, AttributeValueId, ChangeSet, ChangeSetError, Component,
     ComponentError, ComponentId, DalContext, EncryptedSecret, Func, FuncBackendKind, FuncError,
     FuncId, KeyPairError, Prop, PropId, SchemaVariant, SchemaVariantError, Secret, SecretError,
@@ -47,20 +51,26 @@ use super::backend::management::FuncBackendManagement;
 use super::backend::normalize_to_array::FuncBackendNormalizeToArray;
 use super::backend::resource_payload_to_value::FuncBackendResourcePayloadToValue;
 use super::backend::{
+    arithmetic::FuncBackendArithmetic,
     array::FuncBackendArray,
     boolean::FuncBackendBoolean,
+    coalesce::FuncBackendCoalesce,
     diff::FuncBackendDiff,
     identity::FuncBackendIdentity,
     integer::FuncBackendInteger,
+    join_array::FuncBackendJoinArray,
     js_action::FuncBackendJsAction,
     js_attribute::{FuncBackendJsAttribute, FuncBackendJsAttributeArgs},
     js_schema_variant_definition::FuncBackendJsSchemaVariantDefinition,
     json::FuncBackendJson,
+    json_pointer::FuncBackendJsonPointer,
     map::FuncBackendMap,
     object::FuncBackendObject,
     string::FuncBackendString,
+    string_template::FuncBackendStringTemplate,
     validation::FuncBackendValidation,
-    FuncBackend, FuncDispatch, FuncDispatchContext, InvalidResolverFunctionTypeError,
+    FuncBackend, FuncBackendResponseType, FuncDispatch, FuncDispatchContext,
+    InvalidResolverFunctionTypeError,
 };
 use super::intrinsics::IntrinsicFunc;
 
@@ -97,8 +107,12 @@ pub enum FuncRunnerError {
     Func(#[from] FuncError),
     #[error("function backend error: {0}")]
     FuncBackend(#[from] FuncBackendError),
+    #[error("func egress policy error: {0}")]
+    FuncEgressPolicy(#[from] Box<FuncEgressPolicyError>),
     #[error("func run builder error: {0}")]
     FuncRunBuilder(#[from] FuncRunBuilderError),
+    #[error("func timeout error: {0}")]
+    FuncTimeout(#[from] Box<FuncTimeoutError>),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("invalid resolver function type: {0}")]
@@ -115,6 +129,8 @@ pub enum FuncRunnerError {
     Prop(#[from] PropError),
     #[error("reconciliation funcs are no longer supported (found: {0})")]
     ReconciliationFuncsNoLongerSupported(FuncId),
+    #[error("arguments not found for func run being replayed: {0}")]
+    ReplayArgumentsNotFound(FuncRunId),
     #[error("function run result failure: kind={kind}, message={message}, backend={backend}")]
     ResultFailure {
         kind: FunctionResultFailureErrorKind,
@@ -158,6 +174,7 @@ pub struct FuncRunner {
     func: Func,
     args: serde_json::Value,
     before: Vec<BeforeFunction>,
+    timeout_secs: Option<u64>,
 }
 
 impl FuncRunner {
@@ -186,11 +203,13 @@ impl FuncRunner {
             si.workspace.id = Empty,
         )
     )]
+    /// Runs the [`Func`] with the provided arguments, outside of any prototype. When a
+    /// [`Component`] is provided, its secrets are made available to the func.
     pub async fn run_test(
         ctx: &DalContext,
         func: Func,
         args: serde_json::Value,
-        component_id: ComponentId,
+        component_id: Option<ComponentId>,
     ) -> FuncRunnerResult<(FuncRunId, FuncRunnerValueChannel)> {
         let span = current_span_for_instrument_at!("info");
 
@@ -209,17 +228,16 @@ impl FuncRunner {
             ctx: &DalContext,
             func: Func,
             args: serde_json::Value,
-            component_id: ComponentId,
+            component_id: Option<ComponentId>,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
-            let function_args: CasValue = args.clone().into();
-            let (function_args_cas_address, _) = ctx.layer_db().cas().write(
-                Arc::new(function_args.into()),
-                None,
-                ctx.events_tenancy(),
-                ctx.events_actor(),
-            )?;
-            let before = FuncRunner::before_funcs(ctx, component_id).await?;
+            // Store the code that is being tried out, so that the test run can be replayed.
+            let (function_args_cas_address, code_cas_hash) =
+                FuncRunner::write_input_cas_addresses(ctx, &func, &args)?;
+            let before = match component_id {
+                Some(component_id) => FuncRunner::before_funcs(ctx, component_id).await?,
+                None => vec![],
+            };
 
             let func_run_create_time = Utc::now();
             let func_run_inner = FuncRunBuilder::default()
@@ -229,13 +247,14 @@ impl FuncRunner {
                 .backend_response_type(func.backend_response_type.into())
                 .function_name(func.name.clone())
                 .function_kind(func.kind.into())
+                .priority(ExecutionPriority::Interactive)
                 .function_display_name(func.display_name.clone())
                 .function_description(func.description.clone())
                 .function_link(func.link.clone())
                 .function_args_cas_address(function_args_cas_address)
-                .function_code_cas_address(func.code_blake3)
+                .function_code_cas_address(code_cas_hash)
                 .attribute_value_id(None)
-                .component_id(Some(component_id))
+                .component_id(component_id)
                 .created_at(func_run_create_time)
                 .updated_at(func_run_create_time)
                 .build()?;
@@ -253,7 +272,9 @@ impl FuncRunner {
                     "si.change_set.id",
                     func_run_inner.change_set_id().array_to_str(&mut id_buf),
                 );
-                span.record("si.component.id", component_id.array_to_str(&mut id_buf));
+                if let Some(component_id) = component_id {
+                    span.record("si.component.id", component_id.array_to_str(&mut id_buf));
+                }
                 span.record(
                     "si.workspace.id",
                     func_run_inner.workspace_pk().array_to_str(&mut id_buf),
@@ -272,11 +293,16 @@ impl FuncRunner {
                 )
                 .await?;
 
+            let timeout_secs = FuncTimeout::timeout_secs_for_func(ctx, func.id)
+                .await
+                .map_err(Box::new)?;
+
             Ok(FuncRunner {
                 func_run,
                 func,
                 args,
                 before,
+                timeout_secs,
             })
         }
 
@@ -340,29 +366,8 @@ impl FuncRunner {
         ) -> FuncRunnerResult<FuncRunner> {
             let args = serde_json::Value::Null;
 
-            let function_args: CasValue = args.clone().into();
-
-            let (function_args_cas_address, _) = ctx.layer_db().cas().write(
-                Arc::new(function_args.into()),
-                None,
-                ctx.events_tenancy(),
-                ctx.events_actor(),
-            )?;
-
-            let code_cas_hash = if let Some(code) = func.code_base64.as_ref() {
-                let code_json_value: serde_json::Value = code.clone().into();
-                let code_cas_value: CasValue = code_json_value.into();
-                let (hash, _) = ctx.layer_db().cas().write(
-                    Arc::new(code_cas_value.into()),
-                    None,
-                    ctx.events_tenancy(),
-                    ctx.events_actor(),
-                )?;
-                hash
-            } else {
-                // Why are we doing this? Because the struct gods demand it. I have feelings.
-                ContentHash::new("".as_bytes())
-            };
+            let (function_args_cas_address, code_cas_hash) =
+                FuncRunner::write_input_cas_addresses(ctx, func, &args)?;
 
             let func_run_create_time = Utc::now();
             let func_run_inner = FuncRunBuilder::default()
@@ -372,6 +377,7 @@ impl FuncRunner {
                 .backend_response_type(func.backend_response_type.into())
                 .function_name(func.name.clone())
                 .function_kind(func.kind.into())
+                .priority(ExecutionPriority::Interactive)
                 .function_display_name(func.display_name.clone())
                 .function_description(func.description.clone())
                 .function_link(func.link.clone())
@@ -414,11 +420,16 @@ impl FuncRunner {
                 )
                 .await?;
 
+            let timeout_secs = FuncTimeout::timeout_secs_for_func(ctx, func.id)
+                .await
+                .map_err(Box::new)?;
+
             Ok(FuncRunner {
                 func_run,
                 func: func.clone(),
                 args,
                 before: vec![],
+                timeout_secs,
             })
         }
 
@@ -492,29 +503,8 @@ impl FuncRunner {
                 "validation_format": validation_format,
             });
 
-            let function_args: CasValue = args.clone().into();
-
-            let (function_args_cas_address, _) = ctx.layer_db().cas().write(
-                Arc::new(function_args.into()),
-                None,
-                ctx.events_tenancy(),
-                ctx.events_actor(),
-            )?;
-
-            let code_cas_hash = if let Some(code) = func.code_base64.as_ref() {
-                let code_json_value: serde_json::Value = code.clone().into();
-                let code_cas_value: CasValue = code_json_value.into();
-                let (hash, _) = ctx.layer_db().cas().write(
-                    Arc::new(code_cas_value.into()),
-                    None,
-                    ctx.events_tenancy(),
-                    ctx.events_actor(),
-                )?;
-                hash
-            } else {
-                // Why are we doing this? Because the struct gods demand it. I have feelings.
-                ContentHash::new("".as_bytes())
-            };
+            let (function_args_cas_address, code_cas_hash) =
+                FuncRunner::write_input_cas_addresses(ctx, &func, &args)?;
 
             let component_id = AttributeValue::component_id(ctx, attribute_value_id).await?;
 
@@ -526,6 +516,7 @@ impl FuncRunner {
                 .backend_response_type(func.backend_response_type.into())
                 .function_name(func.name.clone())
                 .function_kind(func.kind.into())
+                .priority(ExecutionPriority::Dvu)
                 .function_display_name(func.display_name.clone())
                 .function_description(func.description.clone())
                 .function_link(func.link.clone())
@@ -582,11 +573,16 @@ impl FuncRunner {
                 )
                 .await?;
 
+            let timeout_secs = FuncTimeout::timeout_secs_for_func(ctx, func.id)
+                .await
+                .map_err(Box::new)?;
+
             Ok(FuncRunner {
                 func_run,
                 func,
                 args,
                 before: vec![],
+                timeout_secs,
             })
         }
 
@@ -651,8 +647,6 @@ impl FuncRunner {
         ) -> FuncRunnerResult<FuncRunner> {
             let func = Func::get_by_id(ctx, func_id).await?;
 
-            let function_args: CasValue = args.clone().into();
-
             let component_id = AttributeValue::component_id(ctx, attribute_value_id).await?;
             let before = FuncRunner::before_funcs(ctx, component_id).await?;
 
@@ -666,6 +660,7 @@ impl FuncRunner {
                 .backend_response_type(func.backend_response_type.into())
                 .function_name(func.name.clone())
                 .function_kind(func.kind.into())
+                .priority(ExecutionPriority::Dvu)
                 .function_display_name(func.display_name.clone())
                 .function_description(func.description.clone())
                 .function_link(func.link.clone())
@@ -675,26 +670,8 @@ impl FuncRunner {
                 .updated_at(func_run_create_time);
 
             if !func.is_intrinsic() {
-                let (function_args_cas_address, _) = ctx.layer_db().cas().write(
-                    Arc::new(function_args.into()),
-                    None,
-                    ctx.events_tenancy(),
-                    ctx.events_actor(),
-                )?;
-
-                let code_cas_hash = if let Some(code) = func.code_base64.as_ref() {
-                    let code_json_value: serde_json::Value = code.clone().into();
-                    let code_cas_value: CasValue = code_json_value.into();
-                    let (hash, _) = ctx.layer_db().cas().write(
-                        Arc::new(code_cas_value.into()),
-                        None,
-                        ctx.events_tenancy(),
-                        ctx.events_actor(),
-                    )?;
-                    hash
-                } else {
-                    ContentHash::new("".as_bytes())
-                };
+                let (function_args_cas_address, code_cas_hash) =
+                    FuncRunner::write_input_cas_addresses(ctx, &func, &args)?;
 
                 func_run_builder.function_args_cas_address(function_args_cas_address);
                 func_run_builder.function_code_cas_address(code_cas_hash);
@@ -746,11 +723,16 @@ impl FuncRunner {
                     .await?;
             }
 
+            let timeout_secs = FuncTimeout::timeout_secs_for_func(ctx, func.id)
+                .await
+                .map_err(Box::new)?;
+
             Ok(FuncRunner {
                 func_run,
                 func,
                 args,
                 before,
+                timeout_secs,
             })
         }
 
@@ -820,28 +802,8 @@ impl FuncRunner {
         ) -> FuncRunnerResult<FuncRunner> {
             let func = Func::get_by_id(ctx, management_func_id).await?;
 
-            let function_args: CasValue = args.clone().into();
-            let (function_args_cas_address, _) = ctx.layer_db().cas().write(
-                Arc::new(function_args.into()),
-                None,
-                ctx.events_tenancy(),
-                ctx.events_actor(),
-            )?;
-
-            let code_cas_hash = if let Some(code) = func.code_base64.as_ref() {
-                let code_json_value: serde_json::Value = code.clone().into();
-                let code_cas_value: CasValue = code_json_value.into();
-                let (hash, _) = ctx.layer_db().cas().write(
-                    Arc::new(code_cas_value.into()),
-                    None,
-                    ctx.events_tenancy(),
-                    ctx.events_actor(),
-                )?;
-                hash
-            } else {
-                // Why are we doing this? Because the struct gods demand it. I have feelings.
-                ContentHash::new("".as_bytes())
-            };
+            let (function_args_cas_address, code_cas_hash) =
+                FuncRunner::write_input_cas_addresses(ctx, &func, &args)?;
 
             let before = FuncRunner::before_funcs(ctx, manager_component_id).await?;
             let manager_component = Component::get_by_id(ctx, manager_component_id).await?;
@@ -858,6 +820,7 @@ impl FuncRunner {
                 .backend_response_type(func.backend_response_type.into())
                 .function_name(func.name.clone())
                 .function_kind(func.kind.into())
+                .priority(ExecutionPriority::Interactive)
                 .prototype_id(Some(prototype_id.into()))
                 .function_display_name(func.display_name.clone())
                 .function_description(func.description.clone())
@@ -919,11 +882,16 @@ impl FuncRunner {
                 )
                 .await?;
 
+            let timeout_secs = FuncTimeout::timeout_secs_for_func(ctx, func.id)
+                .await
+                .map_err(Box::new)?;
+
             Ok(FuncRunner {
                 func_run,
                 func,
                 args,
                 before,
+                timeout_secs,
             })
         }
 
@@ -1028,35 +996,19 @@ impl FuncRunner {
                     None
                 };
 
-            let function_args: CasValue = args.clone().into();
-            let (function_args_cas_address, _) = ctx.layer_db().cas().write(
-                Arc::new(function_args.into()),
-                None,
-                ctx.events_tenancy(),
-                ctx.events_actor(),
-            )?;
-
-            let code_cas_hash = if let Some(code) = func.code_base64.as_ref() {
-                let code_json_value: serde_json::Value = code.clone().into();
-                let code_cas_value: CasValue = code_json_value.into();
-                let (hash, _) = ctx.layer_db().cas().write(
-                    Arc::new(code_cas_value.into()),
-                    None,
-                    ctx.events_tenancy(),
-                    ctx.events_actor(),
-                )?;
-                hash
-            } else {
-                // Why are we doing this? Because the struct gods demand it. I have feelings.
-                ContentHash::new("".as_bytes())
-            };
+            let (function_args_cas_address, code_cas_hash) =
+                FuncRunner::write_input_cas_addresses(ctx, &func, &args)?;
 
             let before = FuncRunner::before_funcs(ctx, component_id).await?;
             let component = Component::get_by_id(ctx, component_id).await?;
             let component_name = component.name(ctx).await?;
             let schema_name = component.schema(ctx).await?.name;
 
-            let action_kind = prototype.kind.into();
+            let action_kind: si_events::ActionKind = prototype.kind.into();
+            let priority = match action_kind {
+                si_events::ActionKind::Refresh => ExecutionPriority::Background,
+                _ => ExecutionPriority::Action,
+            };
 
             let func_run_create_time = Utc::now();
             let func_run_inner = FuncRunBuilder::default()
@@ -1066,6 +1018,7 @@ impl FuncRunner {
                 .backend_response_type(func.backend_response_type.into())
                 .function_name(func.name.clone())
                 .function_kind(func.kind.into())
+                .priority(priority)
                 .function_display_name(func.display_name.clone())
                 .function_description(func.description.clone())
                 .function_link(func.link.clone())
@@ -1131,11 +1084,17 @@ impl FuncRunner {
                 )
                 .await?;
 
+            let timeout_secs =
+                FuncTimeout::timeout_secs_for_action_prototype(ctx, action_prototype_id)
+                    .await
+                    .map_err(Box::new)?;
+
             Ok(FuncRunner {
                 func_run,
                 func,
                 args,
                 before,
+                timeout_secs,
             })
         }
 
@@ -1148,6 +1107,317 @@ impl FuncRunner {
         Ok(result_channel)
     }
 
+    #[instrument(
+        name = "func_runner.run_plan",
+        level = "info",
+        skip_all,
+        fields(
+            job.id = Empty,
+            job.invoked_name = Empty,
+            otel.kind = SpanKind::Producer.as_str(),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+            si.action.id = Empty,
+            si.action.kind = Empty,
+            si.change_set.id = Empty,
+            si.component.id = Empty,
+            si.func_run.func.backend_kind = Empty,
+            si.func_run.func.backend_response_type = Empty,
+            si.func_run.func.id = Empty,
+            si.func_run.func.kind = Empty,
+            si.func_run.func.name = Empty,
+            si.func_run.id = Empty,
+            si.workspace.id = Empty,
+        )
+    )]
+    pub async fn run_plan(
+        ctx: &DalContext,
+        action_id: ActionId,
+        action_prototype_id: ActionPrototypeId,
+        component_id: ComponentId,
+        func_id: FuncId,
+        args: serde_json::Value,
+    ) -> FuncRunnerResult<FuncRunnerValueChannel> {
+        let span = current_span_for_instrument_at!("info");
+
+        // Prepares the function for execution.
+        //
+        // Note: this function is internal so we can record early-returning errors in span metadata
+        // and in order to time the function's preparation vs. execution timings.
+        #[instrument(
+            name = "func_runner.run_plan.prepare",
+            level = "debug",
+            skip_all,
+            fields()
+        )]
+        #[inline]
+        async fn prepare(
+            ctx: &DalContext,
+            action_id: ActionId,
+            action_prototype_id: ActionPrototypeId,
+            component_id: ComponentId,
+            func_id: FuncId,
+            args: serde_json::Value,
+            span: &Span,
+        ) -> FuncRunnerResult<FuncRunner> {
+            let func = Func::get_by_id(ctx, func_id).await?;
+            let prototype = ActionPrototype::get_by_id(ctx, action_prototype_id)
+                .await
+                .map_err(Box::new)?;
+
+            let (function_args_cas_address, code_cas_hash) =
+                FuncRunner::write_input_cas_addresses(ctx, &func, &args)?;
+
+            let before = FuncRunner::before_funcs(ctx, component_id).await?;
+            let component = Component::get_by_id(ctx, component_id).await?;
+            let component_name = component.name(ctx).await?;
+            let schema_name = component.schema(ctx).await?.name;
+
+            let action_kind: si_events::ActionKind = prototype.kind.into();
+
+            let func_run_create_time = Utc::now();
+            let func_run_inner = FuncRunBuilder::default()
+                .actor(ctx.events_actor())
+                .tenancy(ctx.events_tenancy())
+                .backend_kind(func.backend_kind.into())
+                .backend_response_type(func.backend_response_type.into())
+                .function_name(func.name.clone())
+                .function_kind(func.kind.into())
+                .priority(ExecutionPriority::Background)
+                .function_display_name(func.display_name.clone())
+                .function_description(func.description.clone())
+                .function_link(func.link.clone())
+                .function_args_cas_address(function_args_cas_address)
+                .function_code_cas_address(code_cas_hash)
+                .action_or_func_id(Some(action_id.into()))
+                .prototype_id(Some(action_prototype_id.into()))
+                .action_kind(Some(action_kind))
+                .action_display_name(Some(prototype.name().clone()))
+                .attribute_value_id(None)
+                .component_id(Some(component_id))
+                .component_name(Some(component_name))
+                .schema_name(Some(schema_name))
+                .created_at(func_run_create_time)
+                .updated_at(func_run_create_time)
+                .build()?;
+
+            if !span.is_disabled() {
+                let mut id_buf = FuncRunId::array_to_str_buf();
+
+                let id = func_run_inner.id().array_to_str(&mut id_buf);
+                span.record("job.id", &id);
+                span.record("si.func_run.id", &id);
+
+                span.record("job.invoked_name", func.name.as_str());
+                span.record("si.func_run.func.name", func.name.as_str());
+
+                span.record("si.action.id", action_id.array_to_str(&mut id_buf));
+                span.record("si.action.kind", action_kind.as_ref());
+                span.record("si.func_run.func.backend_kind", func.backend_kind.as_ref());
+                span.record(
+                    "si.func_run.func.backend_response_type",
+                    func.backend_response_type.as_ref(),
+                );
+                span.record("si.func_run.func.id", func.id.array_to_str(&mut id_buf));
+                span.record("si.func_run.func.kind", func.kind.as_ref());
+
+                span.record(
+                    "si.change_set.id",
+                    func_run_inner.change_set_id().array_to_str(&mut id_buf),
+                );
+                span.record("si.component.id", component_id.array_to_str(&mut id_buf));
+                span.record(
+                    "si.workspace.id",
+                    func_run_inner.workspace_pk().array_to_str(&mut id_buf),
+                );
+            }
+
+            let func_run = Arc::new(func_run_inner);
+
+            ctx.layer_db()
+                .func_run()
+                .write(
+                    func_run.clone(),
+                    None,
+                    ctx.events_tenancy(),
+                    ctx.events_actor(),
+                )
+                .await?;
+
+            let timeout_secs =
+                FuncTimeout::timeout_secs_for_action_prototype(ctx, action_prototype_id)
+                    .await
+                    .map_err(Box::new)?;
+
+            Ok(FuncRunner {
+                func_run,
+                func,
+                args,
+                before,
+                timeout_secs,
+            })
+        }
+
+        let runner = prepare(
+            ctx,
+            action_id,
+            action_prototype_id,
+            component_id,
+            func_id,
+            args,
+            &span,
+        )
+        .await
+        .map_err(|err| span.record_err(err))?;
+
+        let result_channel = runner.execute(ctx.clone(), span).await;
+
+        Ok(result_channel)
+    }
+
+    /// Runs `func` again with the arguments recorded on a past [`FuncRun`], as a new func run.
+    /// Secrets are resolved again for the run's component rather than being read from the past
+    /// run, which never stored them. The new run isn't tied to the past run's action or attribute
+    /// value, so replaying never changes their history.
+    #[instrument(
+        name = "func_runner.run_replay",
+        level = "info",
+        skip_all,
+        fields(
+            job.id = Empty,
+            job.invoked_name = func.name.as_str(),
+            otel.kind = SpanKind::Producer.as_str(),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+            si.change_set.id = Empty,
+            si.func_run.func.backend_kind = func.backend_kind.as_ref(),
+            si.func_run.func.backend_response_type = func.backend_response_type.as_ref(),
+            si.func_run.func.id = Empty,
+            si.func_run.func.kind = func.kind.as_ref(),
+            si.func_run.func.name = func.name.as_str(),
+            si.func_run.id = Empty,
+            si.func_run.replayed_func_run.id = %original.id(),
+            si.workspace.id = Empty,
+        )
+    )]
+    pub async fn run_replay(
+        ctx: &DalContext,
+        original: &FuncRun,
+        func: Func,
+        function_code_cas_address: ContentHash,
+    ) -> FuncRunnerResult<(FuncRunId, FuncRunnerValueChannel)> {
+        let span = current_span_for_instrument_at!("info");
+
+        // Prepares the function for execution.
+        //
+        // Note: this function is internal so we can record early-returning errors in span metadata
+        // and in order to time the function's preparation vs. execution timings.
+        #[instrument(
+            name = "func_runner.run_replay.prepare",
+            level = "debug",
+            skip_all,
+            fields()
+        )]
+        #[inline]
+        async fn prepare(
+            ctx: &DalContext,
+            original: &FuncRun,
+            func: Func,
+            function_code_cas_address: ContentHash,
+            span: &Span,
+        ) -> FuncRunnerResult<FuncRunner> {
+            let args: serde_json::Value = ctx
+                .layer_db()
+                .cas()
+                .try_read_as::<CasValue>(&original.function_args_cas_address())
+                .await?
+                .ok_or(FuncRunnerError::ReplayArgumentsNotFound(original.id()))?
+                .into();
+
+            let before = match original.component_id() {
+                Some(component_id) => FuncRunner::before_funcs(ctx, component_id).await?,
+                None => vec![],
+            };
+
+            let func_run_create_time = Utc::now();
+            let func_run_inner = FuncRunBuilder::default()
+                .actor(ctx.events_actor())
+                .tenancy(ctx.events_tenancy())
+                .backend_kind(func.backend_kind.into())
+                .backend_response_type(func.backend_response_type.into())
+                .function_name(func.name.clone())
+                .function_kind(func.kind.into())
+                .priority(original.priority())
+                .function_display_name(func.display_name.clone())
+                .function_description(func.description.clone())
+                .function_link(func.link.clone())
+                .function_args_cas_address(original.function_args_cas_address())
+                .function_code_cas_address(function_code_cas_address)
+                .prototype_id(original.action_prototype_id().map(Into::into))
+                .action_kind(original.action_kind())
+                .action_display_name(original.action_display_name().map(ToOwned::to_owned))
+                .attribute_value_id(None)
+                .component_id(original.component_id())
+                .component_name(original.component_name().map(ToOwned::to_owned))
+                .schema_name(original.schema_name().map(ToOwned::to_owned))
+                .created_at(func_run_create_time)
+                .updated_at(func_run_create_time)
+                .build()?;
+
+            if !span.is_disabled() {
+                let mut id_buf = FuncRunId::array_to_str_buf();
+
+                let id = func_run_inner.id().array_to_str(&mut id_buf);
+                span.record("job.id", &id);
+                span.record("si.func_run.id", &id);
+
+                span.record("si.func_run.func.id", func.id.array_to_str(&mut id_buf));
+
+                span.record(
+                    "si.change_set.id",
+                    func_run_inner.change_set_id().array_to_str(&mut id_buf),
+                );
+                span.record(
+                    "si.workspace.id",
+                    func_run_inner.workspace_pk().array_to_str(&mut id_buf),
+                );
+            }
+
+            let func_run = Arc::new(func_run_inner);
+
+            ctx.layer_db()
+                .func_run()
+                .write(
+                    func_run.clone(),
+                    None,
+                    ctx.events_tenancy(),
+                    ctx.events_actor(),
+                )
+                .await?;
+
+            let timeout_secs = FuncTimeout::timeout_secs_for_func(ctx, func.id)
+                .await
+                .map_err(Box::new)?;
+
+            Ok(FuncRunner {
+                func_run,
+                func,
+                args,
+                before,
+                timeout_secs,
+            })
+        }
+
+        let runner = prepare(ctx, original, func, function_code_cas_address, &span)
+            .await
+            .map_err(|err| span.record_err(err))?;
+
+        let func_run_id = runner.id();
+        let result_channel = runner.execute(ctx.clone(), span).await;
+
+        Ok((func_run_id, result_channel))
+    }
+
     #[instrument(
         name = "func_runner.kill_execution",
         level = "info",
@@ -1212,12 +1482,21 @@ impl FuncRunner {
             func_run_id,
             WorkspaceId::from(Ulid::from(self.func_run.workspace_pk())),
             self.func_run.change_set_id(),
+            self.timeout_secs,
+            match self.func_run.priority() {
+                ExecutionPriority::Action => veritech_client::ExecutionPriority::Action,
+                ExecutionPriority::Background => veritech_client::ExecutionPriority::Background,
+                ExecutionPriority::Dvu => veritech_client::ExecutionPriority::Dvu,
+                ExecutionPriority::Interactive => veritech_client::ExecutionPriority::Interactive,
+            },
+            self.func_run.backend_kind(),
         );
         let (result_tx, result_rx) = oneshot::channel();
 
         let logs_task = FuncRunnerLogsTask {
             ctx: ctx.clone(),
             func_run_id,
+            func_run: self.func_run.clone(),
             output_stream_rx,
             action_id,
         };
@@ -1240,6 +1519,52 @@ impl FuncRunner {
         result_rx
     }
 
+    /// Writes the arguments and code of a [`Func`] execution to the CAS, returning
+    /// their addresses as `(function_args_cas_address, function_code_cas_address)`.
+    fn write_input_cas_addresses(
+        ctx: &DalContext,
+        func: &Func,
+        args: &serde_json::Value,
+    ) -> FuncRunnerResult<(ContentHash, ContentHash)> {
+        let function_args: CasValue = args.clone().into();
+        let (function_args_cas_address, _) = ctx.layer_db().cas().write_func_run_data(
+            Arc::new(ContentTypes::FuncRunData(function_args)),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let code_cas_hash = if let Some(code) = func.code_base64.as_ref() {
+            let code_json_value: serde_json::Value = code.clone().into();
+            let code_cas_value: CasValue = code_json_value.into();
+            let (hash, _) = ctx.layer_db().cas().write_func_run_data(
+                Arc::new(ContentTypes::FuncRunData(code_cas_value)),
+                None,
+                ctx.events_tenancy(),
+                ctx.events_actor(),
+            )?;
+            hash
+        } else {
+            ContentHash::new("".as_bytes())
+        };
+
+        Ok((function_args_cas_address, code_cas_hash))
+    }
+
+    /// Returns true if any before [`Funcs`](Func) (e.g. secret authentication funcs) would run
+    /// ahead of funcs executed for the given [`Component`].
+    pub async fn component_has_before_funcs(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> FuncRunnerResult<bool> {
+        Ok(
+            Self::ordered_before_funcs_with_secret_keys(ctx, component_id)
+                .await?
+                .iter()
+                .any(|(_, funcs)| !funcs.is_empty()),
+        )
+    }
+
     /// This _private_ method collects all [`BeforeFunctions`](BeforeFunction) for a given
     /// [`ComponentId`](Component).
     #[instrument(name = "func_runner.before_funcs", level = "debug", skip_all)]
@@ -1487,6 +1812,7 @@ impl FuncRunner {
 struct FuncRunnerLogsTask {
     ctx: DalContext,
     func_run_id: FuncRunId,
+    func_run: Arc<FuncRun>,
     output_stream_rx: mpsc::Receiver<OutputStream>,
     action_id: Option<ActionId>,
 }
@@ -1506,7 +1832,23 @@ impl FuncRunnerLogsTask {
 
     async fn try_run(mut self) -> FuncRunnerResult<()> {
         let mut func_run_log = FuncRunLog::new(self.func_run_id, self.ctx.events_tenancy());
+        let mut records = vec![];
         while let Some(item) = self.output_stream_rx.recv().await {
+            // Each line is also kept as a record, so it can be searched for across func runs
+            records.push(FuncRunLogRecord {
+                func_run_id: self.func_run_id,
+                tenancy: self.ctx.events_tenancy(),
+                seq: func_run_log.logs().len() as u32,
+                timestamp: DateTime::from_timestamp(item.timestamp as i64, 0)
+                    .unwrap_or_else(Utc::now),
+                level: FuncRunLogLevel::from_output_level(&item.level),
+                stream: item.stream.clone(),
+                message: item.message.clone(),
+                fields: item.fields,
+                func_name: self.func_run.function_name().to_owned(),
+                component_id: self.func_run.component_id(),
+            });
+
             func_run_log.push_log(si_events::OutputLine {
                 stream: item.stream,
                 execution_id: item.execution_id,
@@ -1556,6 +1898,11 @@ impl FuncRunnerLogsTask {
                 self.ctx.events_actor(),
             )
             .await?;
+        self.ctx
+            .layer_db()
+            .func_run_log()
+            .write_records(&records)
+            .await?;
 
         Ok(())
     }
@@ -1607,7 +1954,7 @@ impl FuncRunnerExecutionTask {
         }
     }
 
-    async fn try_run(self) -> FuncRunnerResult<()> {
+    async fn try_run(mut self) -> FuncRunnerResult<()> {
         let mut running_state_func_run_inner = Arc::unwrap_or_clone(self.func_run.clone());
         running_state_func_run_inner.set_state_to_running();
         let running_state_func_run = Arc::new(running_state_func_run_inner);
@@ -1623,6 +1970,12 @@ impl FuncRunnerExecutionTask {
                     self.ctx.events_actor(),
                 )
                 .await?;
+
+            self.func_dispatch_context.egress_policy =
+                FuncEgressPolicy::for_func_kind(&self.ctx, self.func.kind)
+                    .await
+                    .map_err(Box::new)?
+                    .map(Into::into);
         }
 
         let execution_result = match self.func_run.backend_kind().into() {
@@ -1742,6 +2095,51 @@ impl FuncRunnerExecutionTask {
             FuncBackendKind::NormalizeToArray => {
                 FuncBackendNormalizeToArray::create_and_execute(&self.args).await
             }
+            FuncBackendKind::Arithmetic => {
+                FuncBackendArithmetic::create_and_execute(&self.args).await
+            }
+            FuncBackendKind::Coalesce => FuncBackendCoalesce::create_and_execute(&self.args).await,
+            FuncBackendKind::JoinArray => {
+                FuncBackendJoinArray::create_and_execute(&self.args).await
+            }
+            FuncBackendKind::JsonPointer => {
+                FuncBackendJsonPointer::create_and_execute(&self.args).await
+            }
+            FuncBackendKind::StringTemplate => {
+                FuncBackendStringTemplate::create_and_execute(&self.args).await
+            }
+            // WebAssembly funcs are sent the same requests as their JavaScript counterparts, only
+            // with the runtime set from the dispatch context so cyclone knows how to run them.
+            FuncBackendKind::Wasm => match self.func.backend_response_type {
+                FuncBackendResponseType::Action => {
+                    FuncBackendJsAction::create_and_execute(
+                        self.func_dispatch_context,
+                        &self.func,
+                        &self.args,
+                        self.before,
+                    )
+                    .await
+                }
+                _ => {
+                    let args = FuncBackendJsAttributeArgs {
+                        component: ResolverFunctionComponent {
+                            data: veritech_client::ComponentView {
+                                properties: self.args.to_owned(),
+                                ..Default::default()
+                            },
+                            parents: Vec::new(),
+                        },
+                        response_type: self.func.backend_response_type.try_into()?,
+                    };
+                    FuncBackendJsAttribute::create_and_execute(
+                        self.func_dispatch_context,
+                        &self.func,
+                        &serde_json::to_value(args)?,
+                        self.before,
+                    )
+                    .await
+                }
+            },
         };
 
         match execution_result {
@@ -0,0 +1,583 @@
+//! Test cases are named inputs for a [`Func`] along with what its output should be, saved with
+//! the func so that they can be run again whenever its code changes (and exported with it in
+//! packages).
+//!
+//! A test case's arguments are either written by hand or captured from a
+//! [`Component`](crate::Component) when the test case is saved, so running a test case never
+//! depends on the state of the workspace. Test cases run through veritech like any other
+//! [`FuncRun`](si_events::FuncRun), but without the component's secrets.
+//!
+//! Every test case hangs off the test case category, and is also linked from its func by a
+//! [`FuncTestCase`](EdgeWeightKind::FuncTestCase) edge so a func's test cases can be found without
+//! going through every test case in the workspace.
+
+use std::sync::Arc;
+
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_events::{ulid::Ulid, ContentHash, FuncRunValue};
+use si_layer_cache::LayerDbError;
+use si_pkg::FuncTestCaseSpecExpectation;
+use strum::{AsRefStr, Display, EnumIter, EnumString};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    func::runner::{FuncRunner, FuncRunnerError},
+    layer_db_types::{ContentTypes, FuncTestCaseContent, FuncTestCaseContentV1},
+    workspace_snapshot::{
+        content_address::{ContentAddress, ContentAddressDiscriminants},
+        edge_weight::{EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants},
+        node_weight::{category_node_weight::CategoryNodeKind, NodeWeight, NodeWeightError},
+        WorkspaceSnapshotError,
+    },
+    DalContext, Func, FuncError, FuncId, Timestamp, TransactionsError,
+};
+
+pub use si_frontend_types::{FuncTestCaseRunResult, FuncTestReport};
+pub use si_id::FuncTestCaseId;
+
+/// How many test cases run at once, so that running every test case in a workspace doesn't crowd
+/// out everything else in veritech.
+const MAX_CONCURRENT_TEST_CASES: usize = 8;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncTestCaseError {
+    #[error("func test case {0} expects {1}, but has no expected value")]
+    ExpectedValueMissing(String, FuncTestCaseExpectation),
+    #[error("func error: {0}")]
+    Func(#[from] Box<FuncError>),
+    #[error("func runner error: {0}")]
+    FuncRunner(#[from] Box<FuncRunnerError>),
+    #[error("func runner has failed to send a value and exited")]
+    FuncRunnerSend,
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("func test case name cannot be empty")]
+    NameEmpty,
+    #[error("func {0} already has a test case named {1}")]
+    NameTaken(FuncId, String),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+pub type FuncTestCaseResult<T> = Result<T, FuncTestCaseError>;
+
+/// How the output of a [`FuncTestCase`] is checked.
+///
+/// NOTE: this is stored in the content store, so only ever add new variants to the end.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum FuncTestCaseExpectation {
+    /// The func runs without failing. Its output is not checked.
+    Succeeds,
+    /// The func fails, such as by throwing or returning the wrong type.
+    Fails,
+    /// The output is exactly the expected value.
+    Equals,
+    /// The output contains the expected value: objects may have extra keys and arrays may have
+    /// extra elements.
+    Contains,
+}
+
+impl FuncTestCaseExpectation {
+    /// Whether or not an expected value is needed to check the output.
+    pub fn needs_expected_value(&self) -> bool {
+        matches!(self, Self::Equals | Self::Contains)
+    }
+
+    fn check(&self, output: &Value, expected: Option<&Value>, failed: bool) -> bool {
+        let expected = expected.unwrap_or(&Value::Null);
+        match self {
+            Self::Succeeds => !failed,
+            Self::Fails => failed,
+            Self::Equals => !failed && output == expected,
+            Self::Contains => !failed && value_contains(output, expected),
+        }
+    }
+}
+
+impl From<FuncTestCaseSpecExpectation> for FuncTestCaseExpectation {
+    fn from(value: FuncTestCaseSpecExpectation) -> Self {
+        match value {
+            FuncTestCaseSpecExpectation::Contains => Self::Contains,
+            FuncTestCaseSpecExpectation::Equals => Self::Equals,
+            FuncTestCaseSpecExpectation::Fails => Self::Fails,
+            FuncTestCaseSpecExpectation::Succeeds => Self::Succeeds,
+        }
+    }
+}
+
+impl From<FuncTestCaseExpectation> for FuncTestCaseSpecExpectation {
+    fn from(value: FuncTestCaseExpectation) -> Self {
+        match value {
+            FuncTestCaseExpectation::Contains => Self::Contains,
+            FuncTestCaseExpectation::Equals => Self::Equals,
+            FuncTestCaseExpectation::Fails => Self::Fails,
+            FuncTestCaseExpectation::Succeeds => Self::Succeeds,
+        }
+    }
+}
+
+fn value_contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected.iter().all(|(key, value)| {
+            actual
+                .get(key)
+                .is_some_and(|actual| value_contains(actual, value))
+        }),
+        (Value::Array(actual), Value::Array(expected)) => expected
+            .iter()
+            .all(|value| actual.iter().any(|actual| value_contains(actual, value))),
+        _ => actual == expected,
+    }
+}
+
+/// Everything about a [`FuncTestCase`] that can be set when creating or updating it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCaseDefinition {
+    pub name: String,
+    pub args: Value,
+    /// The name of the component the arguments were captured from, if they were.
+    #[serde(default)]
+    pub captured_from_component: Option<String>,
+    pub expectation: FuncTestCaseExpectation,
+    #[serde(default)]
+    pub expected: Option<Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCase {
+    id: FuncTestCaseId,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    func_id: FuncId,
+    name: String,
+    args: Value,
+    captured_from_component: Option<String>,
+    expectation: FuncTestCaseExpectation,
+    expected: Option<Value>,
+}
+
+impl FuncTestCase {
+    fn assemble(id: FuncTestCaseId, inner: FuncTestCaseContentV1) -> Self {
+        Self {
+            id,
+            timestamp: inner.timestamp,
+            func_id: inner.func_id,
+            name: inner.name,
+            args: inner.args.into(),
+            captured_from_component: inner.captured_from_component,
+            expectation: inner.expectation,
+            expected: inner.expected.map(Into::into),
+        }
+    }
+
+    fn content(&self) -> FuncTestCaseContentV1 {
+        FuncTestCaseContentV1 {
+            timestamp: self.timestamp,
+            func_id: self.func_id,
+            name: self.name.clone(),
+            args: self.args.clone().into(),
+            captured_from_component: self.captured_from_component.clone(),
+            expectation: self.expectation,
+            expected: self.expected.clone().map(Into::into),
+        }
+    }
+
+    pub fn id(&self) -> FuncTestCaseId {
+        self.id
+    }
+
+    pub fn func_id(&self) -> FuncId {
+        self.func_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn args(&self) -> &Value {
+        &self.args
+    }
+
+    pub fn captured_from_component(&self) -> Option<&str> {
+        self.captured_from_component.as_deref()
+    }
+
+    pub fn expectation(&self) -> FuncTestCaseExpectation {
+        self.expectation
+    }
+
+    pub fn expected(&self) -> Option<&Value> {
+        self.expected.as_ref()
+    }
+
+    pub fn definition(&self) -> FuncTestCaseDefinition {
+        FuncTestCaseDefinition {
+            name: self.name.clone(),
+            args: self.args.clone(),
+            captured_from_component: self.captured_from_component.clone(),
+            expectation: self.expectation,
+            expected: self.expected.clone(),
+        }
+    }
+
+    pub async fn new(
+        ctx: &DalContext,
+        func_id: FuncId,
+        definition: FuncTestCaseDefinition,
+    ) -> FuncTestCaseResult<Self> {
+        // Make sure the func exists
+        Func::get_by_id(ctx, func_id).await.map_err(Box::new)?;
+        Self::validate(ctx, func_id, None, &definition).await?;
+
+        let content = FuncTestCaseContentV1 {
+            timestamp: Timestamp::now(),
+            func_id,
+            name: definition.name,
+            args: definition.args.into(),
+            captured_from_component: definition.captured_from_component,
+            expectation: definition.expectation,
+            expected: definition.expected.map(Into::into),
+        };
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(FuncTestCaseContent::V1(content.clone()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let id = workspace_snapshot.generate_ulid().await?;
+        let lineage_id = workspace_snapshot.generate_ulid().await?;
+        let node_weight =
+            NodeWeight::new_content(id, lineage_id, ContentAddress::FuncTestCase(hash));
+        workspace_snapshot.add_or_replace_node(node_weight).await?;
+
+        let category_id = workspace_snapshot
+            .get_or_create_category_node(CategoryNodeKind::FuncTestCase)
+            .await?;
+        workspace_snapshot
+            .add_edge(category_id, EdgeWeight::new(EdgeWeightKind::new_use()), id)
+            .await?;
+        workspace_snapshot
+            .add_edge(func_id, EdgeWeight::new(EdgeWeightKind::FuncTestCase), id)
+            .await?;
+
+        Ok(Self::assemble(id.into(), content))
+    }
+
+    pub async fn get_by_id(ctx: &DalContext, id: FuncTestCaseId) -> FuncTestCaseResult<Self> {
+        let ulid: Ulid = id.into();
+        let node_weight = ctx
+            .workspace_snapshot()?
+            .get_node_weight(ulid)
+            .await?
+            .get_content_node_weight_of_kind(ContentAddressDiscriminants::FuncTestCase)?;
+        let hash = node_weight.content_hash();
+
+        let content: FuncTestCaseContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&hash)
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(ulid))?;
+
+        let FuncTestCaseContent::V1(inner) = content;
+
+        Ok(Self::assemble(id, inner))
+    }
+
+    /// Lists the test cases of every [`Func`] in the workspace.
+    pub async fn list(ctx: &DalContext) -> FuncTestCaseResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let Some(category_id) = workspace_snapshot
+            .get_category_node(None, CategoryNodeKind::FuncTestCase)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut test_cases = vec![];
+        for test_case_idx in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(category_id, EdgeWeightKindDiscriminants::Use)
+            .await?
+        {
+            let id = workspace_snapshot
+                .get_node_weight(test_case_idx)
+                .await?
+                .id();
+            test_cases.push(Self::get_by_id(ctx, id.into()).await?);
+        }
+        test_cases.sort_by(|a, b| (a.func_id, &a.name).cmp(&(b.func_id, &b.name)));
+
+        Ok(test_cases)
+    }
+
+    pub async fn list_for_func(ctx: &DalContext, func_id: FuncId) -> FuncTestCaseResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        let mut test_cases = vec![];
+        for test_case_idx in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(
+                func_id,
+                EdgeWeightKindDiscriminants::FuncTestCase,
+            )
+            .await?
+        {
+            let id = workspace_snapshot
+                .get_node_weight(test_case_idx)
+                .await?
+                .id();
+            test_cases.push(Self::get_by_id(ctx, id.into()).await?);
+        }
+        test_cases.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(test_cases)
+    }
+
+    pub async fn find_by_name_for_func(
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: impl AsRef<str>,
+    ) -> FuncTestCaseResult<Option<Self>> {
+        let name = name.as_ref();
+        Ok(Self::list_for_func(ctx, func_id)
+            .await?
+            .into_iter()
+            .find(|test_case| test_case.name == name))
+    }
+
+    pub async fn update(
+        mut self,
+        ctx: &DalContext,
+        definition: FuncTestCaseDefinition,
+    ) -> FuncTestCaseResult<Self> {
+        if self.definition() == definition {
+            return Ok(self);
+        }
+        Self::validate(ctx, self.func_id, Some(self.id), &definition).await?;
+
+        self.name = definition.name;
+        self.args = definition.args;
+        self.captured_from_component = definition.captured_from_component;
+        self.expectation = definition.expectation;
+        self.expected = definition.expected;
+        self.write(ctx).await?;
+
+        Ok(self)
+    }
+
+    pub async fn remove(ctx: &DalContext, id: FuncTestCaseId) -> FuncTestCaseResult<()> {
+        ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
+
+        Ok(())
+    }
+
+    /// Removes every test case of the [`Func`], for when the func itself is removed.
+    pub async fn remove_for_func(ctx: &DalContext, func_id: FuncId) -> FuncTestCaseResult<()> {
+        for test_case in Self::list_for_func(ctx, func_id).await? {
+            Self::remove(ctx, test_case.id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies every test case of one [`Func`] to another, for when an unlocked copy of a func is
+    /// made.
+    pub async fn copy_for_func(
+        ctx: &DalContext,
+        from_func_id: FuncId,
+        to_func_id: FuncId,
+    ) -> FuncTestCaseResult<()> {
+        for test_case in Self::list_for_func(ctx, from_func_id).await? {
+            Self::new(ctx, to_func_id, test_case.definition()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs every test case in the workspace.
+    #[instrument(name = "func.test_case.run_all", level = "info", skip(ctx))]
+    pub async fn run_all(ctx: &DalContext) -> FuncTestCaseResult<FuncTestReport> {
+        Self::run(ctx, Self::list(ctx).await?).await
+    }
+
+    /// Runs every test case of the [`Func`].
+    #[instrument(name = "func.test_case.run_for_func", level = "info", skip(ctx))]
+    pub async fn run_for_func(
+        ctx: &DalContext,
+        func_id: FuncId,
+    ) -> FuncTestCaseResult<FuncTestReport> {
+        Self::run(ctx, Self::list_for_func(ctx, func_id).await?).await
+    }
+
+    /// Runs the test cases and checks their output. Up to [`MAX_CONCURRENT_TEST_CASES`] of them
+    /// run side by side in veritech, and the results keep the order of the test cases.
+    pub async fn run(
+        ctx: &DalContext,
+        test_cases: Vec<Self>,
+    ) -> FuncTestCaseResult<FuncTestReport> {
+        let results = futures::stream::iter(test_cases)
+            .map(|test_case| Self::run_one(ctx, test_case))
+            .buffered(MAX_CONCURRENT_TEST_CASES)
+            .try_collect()
+            .await?;
+
+        Ok(FuncTestReport::new(results))
+    }
+
+    async fn run_one(
+        ctx: &DalContext,
+        test_case: Self,
+    ) -> FuncTestCaseResult<FuncTestCaseRunResult> {
+        let func = Func::get_by_id(ctx, test_case.func_id)
+            .await
+            .map_err(Box::new)?;
+        let func_name = func.name.clone();
+        let is_intrinsic = func.is_intrinsic();
+        let (func_run_id, result_channel) =
+            FuncRunner::run_test(ctx, func, test_case.args.clone(), None)
+                .await
+                .map_err(Box::new)?;
+
+        let (output, failure) = match result_channel
+            .await
+            .map_err(|_| FuncTestCaseError::FuncRunnerSend)?
+        {
+            Ok(func_run_value) => {
+                if !is_intrinsic {
+                    Self::record_success(ctx, &func_run_value).await?;
+                }
+                (func_run_value.value().cloned(), None)
+            }
+            // The runner has already recorded the failure on the func run
+            Err(FuncRunnerError::ResultFailure { kind, message, .. }) => {
+                (None, Some(format!("{kind}: {message}")))
+            }
+            Err(err) => return Err(Box::new(err).into()),
+        };
+
+        let passed = test_case.expectation.check(
+            output.as_ref().unwrap_or(&Value::Null),
+            test_case.expected.as_ref(),
+            failure.is_some(),
+        );
+
+        Ok(FuncTestCaseRunResult {
+            func_test_case_id: test_case.id,
+            func_test_case_name: test_case.name,
+            func_id: test_case.func_id,
+            func_name,
+            func_run_id,
+            passed,
+            output,
+            failure,
+        })
+    }
+
+    async fn record_success(
+        ctx: &DalContext,
+        func_run_value: &FuncRunValue,
+    ) -> FuncTestCaseResult<()> {
+        let value_address = match func_run_value.value().cloned() {
+            Some(value) => Some(Self::write_value(ctx, value)?),
+            None => None,
+        };
+        let unprocessed_value_address = match func_run_value.unprocessed_value().cloned() {
+            Some(value) => Some(Self::write_value(ctx, value)?),
+            None => None,
+        };
+
+        ctx.layer_db()
+            .func_run()
+            .set_values_and_set_state_to_success(
+                func_run_value.func_run_id(),
+                unprocessed_value_address,
+                value_address,
+                ctx.events_tenancy(),
+                ctx.events_actor(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn write_value(ctx: &DalContext, value: Value) -> FuncTestCaseResult<ContentHash> {
+        let (address, _) = ctx.layer_db().cas().write_func_run_data(
+            Arc::new(ContentTypes::FuncRunData(value.into())),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        Ok(address)
+    }
+
+    async fn validate(
+        ctx: &DalContext,
+        func_id: FuncId,
+        id: Option<FuncTestCaseId>,
+        definition: &FuncTestCaseDefinition,
+    ) -> FuncTestCaseResult<()> {
+        if definition.name.trim().is_empty() {
+            return Err(FuncTestCaseError::NameEmpty);
+        }
+        if definition.expectation.needs_expected_value() && definition.expected.is_none() {
+            return Err(FuncTestCaseError::ExpectedValueMissing(
+                definition.name.clone(),
+                definition.expectation,
+            ));
+        }
+        if let Some(existing) = Self::find_by_name_for_func(ctx, func_id, &definition.name).await? {
+            if Some(existing.id) != id {
+                return Err(FuncTestCaseError::NameTaken(
+                    func_id,
+                    definition.name.clone(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn write(&mut self, ctx: &DalContext) -> FuncTestCaseResult<()> {
+        self.timestamp.updated_at = chrono::Utc::now();
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(FuncTestCaseContent::V1(self.content()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        ctx.workspace_snapshot()?
+            .update_content(self.id.into(), hash)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,392 @@
+//! Timeouts cap how long a [`Func`] may run in cyclone before its lang-js process is killed,
+//! instead of relying on cyclone's server-wide limit.
+//!
+//! A timeout targets either a single [`ActionPrototype`] or a [`Func`] of any kind. For actions,
+//! the timeout for the prototype wins over the one for its function. The timeout is sent along
+//! with the function request to veritech, and a function that runs out of time fails with
+//! [`FunctionResultFailureErrorKind::Timeout`](veritech_client::FunctionResultFailureErrorKind).
+//!
+//! Timeouts live under their own category node. Removing a target removes its timeout with
+//! [`FuncTimeout::remove_for_target`]. Timeouts for targets that are gone some other way are never
+//! returned, and are cleaned up the next time a timeout is set. Copies of a func and regenerated
+//! schema variants get new targets, so their timeouts are copied over with
+//! [`FuncTimeout::copy_for_func`] and [`FuncTimeout::restore_for_schema_variant`].
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use si_events::ulid::Ulid;
+use si_layer_cache::LayerDbError;
+use thiserror::Error;
+
+use crate::{
+    action::prototype::{ActionKind, ActionPrototype, ActionPrototypeError},
+    layer_db_types::{FuncTimeoutContent, FuncTimeoutContentV1},
+    workspace_snapshot::{
+        content_address::{ContentAddress, ContentAddressDiscriminants},
+        edge_weight::{EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants},
+        node_weight::{category_node_weight::CategoryNodeKind, NodeWeight, NodeWeightError},
+        WorkspaceSnapshotError,
+    },
+    ActionPrototypeId, DalContext, Func, FuncError, FuncId, SchemaVariantId, Timestamp,
+    TransactionsError,
+};
+
+pub use si_id::FuncTimeoutId;
+
+/// Cyclone kills every function after 32 minutes regardless, so longer timeouts would never
+/// take effect.
+pub const MAX_TIMEOUT_SECS: u64 = 32 * 60;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncTimeoutError {
+    #[error("action prototype error: {0}")]
+    ActionPrototype(#[from] Box<ActionPrototypeError>),
+    #[error("func error: {0}")]
+    Func(#[from] Box<FuncError>),
+    #[error("timeout must be between 1 and {MAX_TIMEOUT_SECS} seconds, got {0}")]
+    InvalidTimeout(u64),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+pub type FuncTimeoutResult<T> = Result<T, FuncTimeoutError>;
+
+/// What a [`FuncTimeout`] applies to.
+///
+/// NOTE: this is stored in the content store, so only ever add new variants to the end.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FuncTimeoutTarget {
+    ActionPrototype(ActionPrototypeId),
+    Func(FuncId),
+}
+
+impl From<FuncTimeoutTarget> for Ulid {
+    fn from(value: FuncTimeoutTarget) -> Self {
+        match value {
+            FuncTimeoutTarget::ActionPrototype(id) => id.into(),
+            FuncTimeoutTarget::Func(id) => id.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTimeout {
+    id: FuncTimeoutId,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    target: FuncTimeoutTarget,
+    timeout_secs: u64,
+}
+
+impl FuncTimeout {
+    fn assemble(id: FuncTimeoutId, inner: FuncTimeoutContentV1) -> Self {
+        Self {
+            id,
+            timestamp: inner.timestamp,
+            target: inner.target,
+            timeout_secs: inner.timeout_secs,
+        }
+    }
+
+    fn content(&self) -> FuncTimeoutContentV1 {
+        FuncTimeoutContentV1 {
+            timestamp: self.timestamp,
+            target: self.target,
+            timeout_secs: self.timeout_secs,
+        }
+    }
+
+    pub fn id(&self) -> FuncTimeoutId {
+        self.id
+    }
+
+    pub fn target(&self) -> FuncTimeoutTarget {
+        self.target
+    }
+
+    pub fn timeout_secs(&self) -> u64 {
+        self.timeout_secs
+    }
+
+    /// Sets the timeout for the target, replacing the existing one if there is one.
+    pub async fn upsert(
+        ctx: &DalContext,
+        target: FuncTimeoutTarget,
+        timeout_secs: u64,
+    ) -> FuncTimeoutResult<Self> {
+        if timeout_secs == 0 || timeout_secs > MAX_TIMEOUT_SECS {
+            return Err(FuncTimeoutError::InvalidTimeout(timeout_secs));
+        }
+        // Make sure the target exists
+        match target {
+            FuncTimeoutTarget::ActionPrototype(id) => {
+                ActionPrototype::get_by_id(ctx, id)
+                    .await
+                    .map_err(Box::new)?;
+            }
+            FuncTimeoutTarget::Func(id) => {
+                Func::get_by_id(ctx, id).await.map_err(Box::new)?;
+            }
+        }
+
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let mut existing = None;
+        for timeout in Self::list_including_orphans(ctx).await? {
+            if timeout.target == target {
+                existing = Some(timeout);
+            } else if !workspace_snapshot.node_exists(timeout.target).await {
+                // Clean up after targets that went away without taking their timeout along
+                Self::remove(ctx, timeout.id).await?;
+            }
+        }
+        if let Some(mut existing) = existing {
+            existing.timeout_secs = timeout_secs;
+            existing.write(ctx).await?;
+            return Ok(existing);
+        }
+
+        let content = FuncTimeoutContentV1 {
+            timestamp: Timestamp::now(),
+            target,
+            timeout_secs,
+        };
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(FuncTimeoutContent::V1(content.clone()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let id = workspace_snapshot.generate_ulid().await?;
+        let lineage_id = workspace_snapshot.generate_ulid().await?;
+        let node_weight =
+            NodeWeight::new_content(id, lineage_id, ContentAddress::FuncTimeout(hash));
+        workspace_snapshot.add_or_replace_node(node_weight).await?;
+
+        let category_id = workspace_snapshot
+            .get_or_create_category_node(CategoryNodeKind::FuncTimeout)
+            .await?;
+        workspace_snapshot
+            .add_edge(category_id, EdgeWeight::new(EdgeWeightKind::new_use()), id)
+            .await?;
+
+        Ok(Self::assemble(id.into(), content))
+    }
+
+    pub async fn get_by_id(ctx: &DalContext, id: FuncTimeoutId) -> FuncTimeoutResult<Self> {
+        let ulid: Ulid = id.into();
+        let node_weight = ctx
+            .workspace_snapshot()?
+            .get_node_weight(ulid)
+            .await?
+            .get_content_node_weight_of_kind(ContentAddressDiscriminants::FuncTimeout)?;
+        let hash = node_weight.content_hash();
+
+        let content: FuncTimeoutContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&hash)
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(ulid))?;
+
+        let FuncTimeoutContent::V1(inner) = content;
+
+        Ok(Self::assemble(id, inner))
+    }
+
+    /// Lists every timeout in the workspace whose target still exists.
+    pub async fn list(ctx: &DalContext) -> FuncTimeoutResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let mut timeouts = vec![];
+        for timeout in Self::list_including_orphans(ctx).await? {
+            if workspace_snapshot.node_exists(timeout.target).await {
+                timeouts.push(timeout);
+            }
+        }
+
+        Ok(timeouts)
+    }
+
+    /// Lists every timeout in the workspace, including ones whose target was removed without
+    /// removing its timeout (e.g. along with a regenerated schema variant).
+    async fn list_including_orphans(ctx: &DalContext) -> FuncTimeoutResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let Some(category_id) = workspace_snapshot
+            .get_category_node(None, CategoryNodeKind::FuncTimeout)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut timeouts = vec![];
+        for timeout_idx in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(category_id, EdgeWeightKindDiscriminants::Use)
+            .await?
+        {
+            let id = workspace_snapshot.get_node_weight(timeout_idx).await?.id();
+            timeouts.push(Self::get_by_id(ctx, id.into()).await?);
+        }
+        timeouts.sort_by_key(|timeout| timeout.id);
+
+        Ok(timeouts)
+    }
+
+    pub async fn find_for_target(
+        ctx: &DalContext,
+        target: FuncTimeoutTarget,
+    ) -> FuncTimeoutResult<Option<Self>> {
+        Ok(Self::list(ctx)
+            .await?
+            .into_iter()
+            .find(|timeout| timeout.target == target))
+    }
+
+    /// The timeout, in seconds, for running the [`Func`], if one is set.
+    pub async fn timeout_secs_for_func(
+        ctx: &DalContext,
+        func_id: FuncId,
+    ) -> FuncTimeoutResult<Option<u64>> {
+        Ok(Self::find_for_target(ctx, FuncTimeoutTarget::Func(func_id))
+            .await?
+            .map(|timeout| timeout.timeout_secs))
+    }
+
+    /// The timeout, in seconds, for running the [`ActionPrototype`]: the one for the prototype
+    /// if there is one, otherwise the one for its [`Func`].
+    pub async fn timeout_secs_for_action_prototype(
+        ctx: &DalContext,
+        action_prototype_id: ActionPrototypeId,
+    ) -> FuncTimeoutResult<Option<u64>> {
+        if let Some(timeout) =
+            Self::find_for_target(ctx, FuncTimeoutTarget::ActionPrototype(action_prototype_id))
+                .await?
+        {
+            return Ok(Some(timeout.timeout_secs));
+        }
+
+        let func_id = ActionPrototype::func_id(ctx, action_prototype_id)
+            .await
+            .map_err(Box::new)?;
+        Self::timeout_secs_for_func(ctx, func_id).await
+    }
+
+    /// Copies the timeout of one [`Func`] to another, for when an unlocked copy of a func is made.
+    pub async fn copy_for_func(
+        ctx: &DalContext,
+        from_func_id: FuncId,
+        to_func_id: FuncId,
+    ) -> FuncTimeoutResult<()> {
+        if let Some(timeout_secs) = Self::timeout_secs_for_func(ctx, from_func_id).await? {
+            Self::upsert(ctx, FuncTimeoutTarget::Func(to_func_id), timeout_secs).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The timeouts of the [`SchemaVariant`](crate::SchemaVariant)'s action prototypes, along
+    /// with the kind and func that identify each prototype once the variant is regenerated.
+    pub async fn list_for_schema_variant(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> FuncTimeoutResult<Vec<(ActionKind, FuncId, u64)>> {
+        let mut timeouts = vec![];
+        for prototype in ActionPrototype::for_variant(ctx, schema_variant_id)
+            .await
+            .map_err(Box::new)?
+        {
+            if let Some(timeout) =
+                Self::find_for_target(ctx, FuncTimeoutTarget::ActionPrototype(prototype.id()))
+                    .await?
+            {
+                let func_id = ActionPrototype::func_id(ctx, prototype.id())
+                    .await
+                    .map_err(Box::new)?;
+                timeouts.push((prototype.kind, func_id, timeout.timeout_secs));
+            }
+        }
+
+        Ok(timeouts)
+    }
+
+    /// Sets timeouts from [`Self::list_for_schema_variant`] on the action prototypes of the
+    /// [`SchemaVariant`](crate::SchemaVariant) with the same kind and func, for when the variant
+    /// is regenerated or copied and its prototypes are recreated.
+    pub async fn restore_for_schema_variant(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        timeouts: Vec<(ActionKind, FuncId, u64)>,
+    ) -> FuncTimeoutResult<()> {
+        if timeouts.is_empty() {
+            return Ok(());
+        }
+
+        for prototype in ActionPrototype::for_variant(ctx, schema_variant_id)
+            .await
+            .map_err(Box::new)?
+        {
+            let func_id = ActionPrototype::func_id(ctx, prototype.id())
+                .await
+                .map_err(Box::new)?;
+            if let Some((_, _, timeout_secs)) =
+                timeouts.iter().find(|(kind, timeout_func_id, _)| {
+                    *kind == prototype.kind && *timeout_func_id == func_id
+                })
+            {
+                Self::upsert(
+                    ctx,
+                    FuncTimeoutTarget::ActionPrototype(prototype.id()),
+                    *timeout_secs,
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove(ctx: &DalContext, id: FuncTimeoutId) -> FuncTimeoutResult<()> {
+        ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
+
+        Ok(())
+    }
+
+    /// Removes the timeout for the target, if there is one, for when the target is removed.
+    pub async fn remove_for_target(
+        ctx: &DalContext,
+        target: FuncTimeoutTarget,
+    ) -> FuncTimeoutResult<()> {
+        if let Some(timeout) = Self::find_for_target(ctx, target).await? {
+            Self::remove(ctx, timeout.id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn write(&mut self, ctx: &DalContext) -> FuncTimeoutResult<()> {
+        self.timestamp.updated_at = chrono::Utc::now();
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(FuncTimeoutContent::V1(self.content()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        ctx.workspace_snapshot()?
+            .update_content(self.id.into(), hash)
+            .await?;
+
+        Ok(())
+    }
+}
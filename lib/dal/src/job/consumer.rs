@@ -16,8 +16,9 @@ use crate::diagram::DiagramError;
 use crate::prop::PropError;
 use crate::validation::ValidationError;
 use crate::{
-    action::prototype::ActionPrototypeError, action::ActionError,
-    attribute::value::AttributeValueError,
+    action::concurrency_group::ActionConcurrencyGroupError,
+    action::prototype::ActionPrototypeError, action::retry_policy::ActionRetryPolicyError,
+    action::ActionError, attribute::value::AttributeValueError,
     job::definition::dependent_values_update::DependentValueUpdateError,
     job::producer::BlockingJobError, job::producer::JobProducerError, AccessBuilder,
     ActionPrototypeId, ComponentError, ComponentId, DalContext, DalContextBuilder,
@@ -31,10 +32,14 @@ use crate::{ChangeSetError, FuncError};
 pub enum JobConsumerError {
     #[error("action error: {0}")]
     Action(#[from] ActionError),
+    #[error("action concurrency group error: {0}")]
+    ActionConcurrencyGroup(#[from] ActionConcurrencyGroupError),
     #[error("action prototype error: {0}")]
     ActionPrototype(#[from] ActionPrototypeError),
     #[error("ActionProtoype {0} not found")]
     ActionPrototypeNotFound(ActionPrototypeId),
+    #[error("action retry policy error: {0}")]
+    ActionRetryPolicy(#[from] ActionRetryPolicyError),
     #[error("arg {0:?} not found at index {1}")]
     ArgNotFound(JobInfo, usize),
     #[error("attribute value error: {0}")]
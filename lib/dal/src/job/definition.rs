@@ -1,8 +1,10 @@
 mod action;
+pub mod compute_action_change_plans;
 pub mod compute_validation;
 pub mod dependent_values_update;
 
 pub use action::ActionJob;
+pub use compute_action_change_plans::ComputeActionChangePlans;
 pub use dependent_values_update::DependentValuesUpdate;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
@@ -12,11 +12,15 @@ use veritech_client::{ActionRunResultSuccess, ResourceStatus};
 
 use crate::{
     action::{
-        prototype::{ActionKind, ActionPrototype},
+        concurrency_group::ActionConcurrencyLimiter,
+        prototype::{ActionKind, ActionPrototype, ActionPrototypeError},
+        retry_policy::{ActionFailureKind, ActionRetry, ActionRetryPolicy},
         Action, ActionError, ActionId, ActionState,
     },
     billing_publish,
     change_status::ChangeStatus,
+    component::drift::ResourceDrift,
+    func::runner::FuncRunnerError,
     job::{
         consumer::{
             JobCompletionState, JobConsumer, JobConsumerError, JobConsumerMetadata,
@@ -90,20 +94,68 @@ impl JobConsumer for ActionJob {
         fields(
             id=?self.id,
             job=?self.job,
-            // TODO: determine what this field is called for retries
-            si.poopadoop.retries = Empty,
+            si.action.attempt = Empty,
         )
     )]
     async fn run(&self, ctx: &mut DalContext) -> JobConsumerResult<JobCompletionState> {
+        // Attempts that were retried automatically are counted in their retry
+        let attempt = match ActionRetry::attempts_for(ctx, self.id).await {
+            Ok(attempts) => attempts.unwrap_or(0).saturating_add(1),
+            Err(err) => {
+                error!(
+                    si.error.message = ?err,
+                    si.action.id = %self.id,
+                    "unable to read action retries"
+                );
+                1
+            }
+        };
+        Span::current().record("si.action.attempt", attempt);
+
+        match requeue_if_concurrency_group_full(ctx, self.id).await {
+            Ok(true) => return Ok(JobCompletionState::Done),
+            Ok(false) => {}
+            Err(err) => {
+                error!(
+                    si.error.message = ?err,
+                    si.action.id = %self.id,
+                    "unable to check action concurrency group"
+                );
+            }
+        }
+
         metric!(counter.action_concurrency_count = 1);
 
-        if let Err(err) = inner_run(ctx, self.id).await {
-            error!(si.error.message = ?err, si.action.id = %self.id, "unable to finish action");
-            if let Err(err) = process_failed_action(ctx, self.id).await {
-                error!(si.error.message = ?err, "failed to process action failure");
+        let failure = match inner_run(ctx, self.id).await {
+            Ok(Some(run_result)) => ActionFailureKind::from_resource_status(run_result.status),
+            Ok(None) => Some(ActionFailureKind::Other),
+            Err(err) => {
+                let failure = failure_kind(&err);
+                if failure == ActionFailureKind::Timeout {
+                    error!(
+                        si.error.message = ?err,
+                        si.action.id = %self.id,
+                        "action function exceeded its timeout and was killed"
+                    );
+                } else {
+                    error!(si.error.message = ?err, si.action.id = %self.id, "unable to finish action");
+                }
+                if let Err(err) = process_failed_action(ctx, self.id, failure).await {
+                    error!(si.error.message = ?err, "failed to process action failure");
+                }
+                Some(failure)
             }
-        }
+        };
         metric!(counter.action_concurrency_count = -1);
+
+        if let Err(err) = schedule_retry(ctx, self.id, attempt, failure).await {
+            error!(
+                si.error.message = ?err,
+                si.action.id = %self.id,
+                "unable to schedule action retry"
+            );
+        }
+
         Ok(JobCompletionState::Done)
     }
 }
@@ -231,6 +283,17 @@ async fn process_execution(
             }
 
             component.set_resource(ctx, run_result.into()).await?;
+
+            if prototype.kind == ActionKind::Refresh {
+                if let Some(payload) = &run_result.payload {
+                    // Drift is informational, so failing to detect it must not fail the refresh
+                    if let Err(err) =
+                        ResourceDrift::detect(ctx, component_id, payload, Some(func_run_id)).await
+                    {
+                        error!(si.error.message = ?err, si.component.id = %component_id, "unable to detect resource drift");
+                    }
+                }
+            }
         }
 
         // Set the resource id if we have one, even on failure. (although, why?)
@@ -324,21 +387,135 @@ async fn process_execution(
     Ok(())
 }
 
+/// The dispatcher only dispatches as many actions as their concurrency groups allow, but actions
+/// can be dispatched again by retries, or by change sets being applied at the same time. If the
+/// group is already at its limit, put the action back in the queue to be dispatched once there is
+/// room, rather than running it (or failing it).
+///
+/// Like the dispatcher, this counts the other dispatched actions of the group as well as the
+/// running ones, since they may start at any moment. When too many were dispatched, more of them
+/// may be requeued than strictly needed, but the dispatcher then only dispatches what fits.
+#[instrument(
+    name = "action_job.requeue_if_concurrency_group_full",
+    skip_all,
+    level = "info",
+    fields(si.action.id = ?action_id)
+)]
+async fn requeue_if_concurrency_group_full(
+    ctx: &mut DalContext,
+    action_id: ActionId,
+) -> JobConsumerResult<bool> {
+    ctx.update_snapshot_to_visibility().await?;
+
+    let mut concurrency_limiter = ActionConcurrencyLimiter::new(
+        ctx,
+        &[ActionState::Dispatched, ActionState::Running],
+        Some(action_id),
+    )
+    .await?;
+    if concurrency_limiter.try_acquire(ctx, action_id).await? {
+        return Ok(false);
+    }
+
+    info!(%action_id, "action concurrency group is full, requeueing action");
+    Action::set_state(ctx, action_id, ActionState::Queued).await?;
+    WsEvent::action_list_updated(ctx)
+        .await?
+        .publish_on_commit(ctx)
+        .await?;
+    ctx.commit().await?;
+
+    Ok(true)
+}
+
+fn failure_kind(err: &JobConsumerError) -> ActionFailureKind {
+    match err {
+        JobConsumerError::ActionPrototype(ActionPrototypeError::FuncRunner(
+            FuncRunnerError::ResultFailure { kind, .. },
+        )) => kind.into(),
+        _ => ActionFailureKind::Other,
+    }
+}
+
+/// If the action failed and its [`ActionRetryPolicy`] allows another attempt, records an
+/// [`ActionRetry`] for when the next attempt is due, leaving the action failed until pinga queues
+/// it again. Otherwise, forgets any earlier retries of the action.
+#[instrument(
+    name = "action_job.schedule_retry",
+    skip_all,
+    level = "info",
+    fields(
+        si.action.id = ?action_id,
+        si.action.attempt = attempt,
+        si.action.failure = ?failure,
+    )
+)]
+async fn schedule_retry(
+    ctx: &mut DalContext,
+    action_id: ActionId,
+    attempt: u32,
+    failure: Option<ActionFailureKind>,
+) -> JobConsumerResult<()> {
+    let Some(failure) = failure else {
+        // Most actions succeed on their first attempt, with no retries to forget.
+        if ActionRetry::attempts_for(ctx, action_id).await?.is_some() {
+            ctx.update_snapshot_to_visibility().await?;
+            ActionRetry::delete(ctx, action_id).await?;
+            ctx.commit_no_rebase().await?;
+        }
+        return Ok(());
+    };
+
+    ctx.update_snapshot_to_visibility().await?;
+
+    // Only retry actions that are still failed: they may have been put on hold or removed
+    let policy = if ctx.workspace_snapshot()?.node_exists(action_id).await
+        && Action::get_by_id(ctx, action_id).await?.state() == ActionState::Failed
+    {
+        ActionRetryPolicy::find_for_action(ctx, action_id).await?
+    } else {
+        None
+    };
+
+    match policy {
+        Some(policy) if policy.should_retry(attempt, failure) => {
+            let delay = policy.backoff(attempt);
+            info!(%action_id, attempt, %failure, ?delay, "scheduling retry of failed action");
+            ActionRetry::schedule(ctx, action_id, attempt, delay).await?;
+        }
+        _ => {
+            info!(%action_id, attempt, %failure, "not retrying failed action");
+            ActionRetry::delete(ctx, action_id).await?;
+        }
+    }
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
+
 #[instrument(
     name = "action_job.process_failed_action",
     skip_all,
     level = "info",
     fields(si.action.id = ?action_id))]
-async fn process_failed_action(ctx: &DalContext, action_id: ActionId) -> JobConsumerResult<()> {
-    info!(%action_id, "processing action failed");
+async fn process_failed_action(
+    ctx: &DalContext,
+    action_id: ActionId,
+    failure: ActionFailureKind,
+) -> JobConsumerResult<()> {
+    info!(%action_id, %failure, "processing action failed");
 
     Action::set_state(ctx, action_id, ActionState::Failed).await?;
 
+    let result_state = match failure {
+        ActionFailureKind::Timeout => ActionResultState::Timeout,
+        _ => ActionResultState::Failure,
+    };
     ctx.layer_db()
         .func_run()
         .set_action_result_state_for_action_id(
             action_id,
-            ActionResultState::Failure,
+            result_state,
             ctx.events_tenancy(),
             ctx.events_actor(),
         )
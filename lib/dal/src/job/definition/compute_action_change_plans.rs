@@ -0,0 +1,111 @@
+use std::convert::TryFrom;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::action::change_plan::ActionChangePlan;
+use crate::job::consumer::JobCompletionState;
+use crate::{
+    job::consumer::{
+        JobConsumer, JobConsumerError, JobConsumerMetadata, JobConsumerResult, JobInfo,
+    },
+    job::producer::{JobProducer, JobProducerResult},
+    AccessBuilder, DalContext, Visibility, WsEvent,
+};
+use crate::{ChangeSet, ChangeSetStatus};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ComputeActionChangePlansArgs;
+
+impl From<ComputeActionChangePlans> for ComputeActionChangePlansArgs {
+    fn from(_value: ComputeActionChangePlans) -> Self {
+        Self
+    }
+}
+
+/// Runs the plan funcs for the pending actions of a change set, so whoever reviews the change set
+/// can see what applying it would do.
+#[derive(Clone, Debug, Serialize)]
+pub struct ComputeActionChangePlans {
+    access_builder: AccessBuilder,
+    visibility: Visibility,
+    job: Option<JobInfo>,
+}
+
+impl ComputeActionChangePlans {
+    pub fn new(ctx: &DalContext) -> Box<Self> {
+        let access_builder = ctx.access_builder();
+        let visibility = *ctx.visibility();
+
+        Box::new(Self {
+            access_builder,
+            visibility,
+            job: None,
+        })
+    }
+}
+
+impl JobProducer for ComputeActionChangePlans {
+    fn arg(&self) -> JobProducerResult<serde_json::Value> {
+        Ok(serde_json::to_value(ComputeActionChangePlansArgs::from(
+            self.clone(),
+        ))?)
+    }
+}
+
+impl JobConsumerMetadata for ComputeActionChangePlans {
+    fn type_name(&self) -> String {
+        "ComputeActionChangePlans".to_string()
+    }
+
+    fn access_builder(&self) -> AccessBuilder {
+        self.access_builder
+    }
+
+    fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+}
+
+#[async_trait]
+impl JobConsumer for ComputeActionChangePlans {
+    #[instrument(name = "compute_action_change_plans.run", skip_all, level = "info")]
+    async fn run(&self, ctx: &mut DalContext) -> JobConsumerResult<JobCompletionState> {
+        let change_set = ChangeSet::get_by_id(ctx, ctx.change_set_id()).await?;
+
+        if !matches!(
+            change_set.status,
+            ChangeSetStatus::Open | ChangeSetStatus::NeedsApproval
+        ) || ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await?
+        {
+            info!(
+                "Change plans enqueued for a change set that is not under review. Returning early"
+            );
+            return Ok(JobCompletionState::Done);
+        }
+
+        // Plans are only stored as func runs, so there is nothing to commit to the snapshot
+        let func_run_ids = ActionChangePlan::compute_all(ctx).await?;
+        if !func_run_ids.is_empty() {
+            WsEvent::action_list_updated(ctx)
+                .await?
+                .publish_immediately(ctx)
+                .await?;
+        }
+
+        Ok(JobCompletionState::Done)
+    }
+}
+
+impl TryFrom<JobInfo> for ComputeActionChangePlans {
+    type Error = JobConsumerError;
+
+    fn try_from(job: JobInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            access_builder: job.access_builder,
+            visibility: job.visibility,
+            job: Some(job),
+        })
+    }
+}
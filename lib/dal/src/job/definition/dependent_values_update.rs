@@ -8,7 +8,6 @@ use telemetry_utils::metric;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use si_events::FuncRunValue;
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::{
@@ -18,7 +17,10 @@ use tokio::{
 use ulid::Ulid;
 
 use crate::{
-    attribute::value::{dependent_value_graph::DependentValueGraph, AttributeValueError},
+    action::change_plan::ActionChangePlan,
+    attribute::value::{
+        dependent_value_graph::DependentValueGraph, AttributeValueError, PrototypeFunctionExecution,
+    },
     job::{
         consumer::{
             JobCompletionState, JobConsumer, JobConsumerError, JobConsumerMetadata,
@@ -30,7 +32,7 @@ use crate::{
     status::{StatusMessageState, StatusUpdate, StatusUpdateError},
     workspace_snapshot::DependentValueRoot,
     AccessBuilder, AttributeValue, AttributeValueId, ChangeSet, ChangeSetError, ChangeSetStatus,
-    ComponentError, ComponentId, DalContext, Func, TransactionsError, Visibility, WorkspacePk,
+    ComponentError, ComponentId, DalContext, TransactionsError, Visibility, WorkspacePk,
     WorkspaceSnapshotError, WsEvent, WsEventError,
 };
 
@@ -356,7 +358,13 @@ impl DependentValuesUpdate {
 
                 if let Some(finished_value_id) = task_id_to_av_id.remove(&task_id) {
                     match execution_result {
-                        Ok((execution_values, func, input_attribute_value_ids)) => {
+                        // The function's inputs are unchanged and the value still holds the
+                        // result of its last run, so there is nothing to write.
+                        Ok(None) => {
+                            metric!(counter.dvu.function_execution_skipped = 1);
+                            dependency_graph.remove_value(finished_value_id);
+                        }
+                        Ok(Some((execution_values, func, input_attribute_value_ids, inputs))) => {
                             // Lock the graph for writing inside this job. The
                             // lock will be released when this guard is dropped
                             // at the end of the scope.
@@ -383,6 +391,7 @@ impl DependentValuesUpdate {
                                     finished_value_id,
                                     execution_values,
                                     func.clone(),
+                                    inputs,
                                 )
                                 .await
                                 {
@@ -474,6 +483,9 @@ impl DependentValuesUpdate {
 
         debug!("DependentValuesUpdate took: {:?}", start.elapsed());
 
+        // Values in the change set may have changed, so what its actions would do may have too
+        ActionChangePlan::request(ctx).await?;
+
         ctx.commit().await?;
         Ok(JobCompletionState::Done)
     }
@@ -513,7 +525,7 @@ async fn execution_error_detail(
 
 type PrototypeFunctionExecutionResult = (
     Ulid,
-    DependentValueUpdateResult<(FuncRunValue, Func, Vec<AttributeValueId>)>,
+    DependentValueUpdateResult<Option<PrototypeFunctionExecution>>,
     Option<serde_json::Value>,
 );
 
@@ -543,10 +555,13 @@ async fn values_from_prototype_function_execution(
         }
     }
 
-    let result =
-        AttributeValue::execute_prototype_function(&ctx, attribute_value_id, set_value_lock)
-            .await
-            .map_err(Into::into);
+    let result = AttributeValue::execute_prototype_function_unless_inputs_unchanged(
+        &ctx,
+        attribute_value_id,
+        set_value_lock,
+    )
+    .await
+    .map_err(Into::into);
 
     (task_id, result, before_value)
 }
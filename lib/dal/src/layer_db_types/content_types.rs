@@ -8,14 +8,18 @@ use strum::EnumDiscriminants;
 use thiserror::Error;
 
 use crate::action::prototype::ActionKind;
+use crate::action::retry_policy::{ActionFailureKind, ActionRetryPolicyTarget};
 use crate::approval_requirement::ApprovalRequirementApprover;
+use crate::func::test_case::FuncTestCaseExpectation;
+use crate::func::timeout::FuncTimeoutTarget;
 use crate::validation::ValidationStatus;
+use crate::workspace_variable::WorkspaceVariableKind;
 use crate::{
     action::ActionCompletionStatus, func::argument::FuncArgumentKind, prop::WidgetOptions,
     property_editor::schema::WidgetKind, socket::connection_annotation::ConnectionAnnotation,
     ActionPrototypeId, ComponentId, ComponentType, DalContext, FuncBackendKind,
-    FuncBackendResponseType, FuncId, PropId, PropKind, SchemaVariant, SchemaVariantId, SocketArity,
-    SocketKind, Timestamp, UserPk,
+    FuncBackendResponseType, FuncId, PropId, PropKind, SchemaId, SchemaVariant, SchemaVariantId,
+    SocketArity, SocketKind, Timestamp, UserPk,
 };
 
 #[remain::sorted]
@@ -62,6 +66,16 @@ pub enum ContentTypes {
     Geometry(GeometryContent),
     View(ViewContent),
     ApprovalRequirementDefinition(ApprovalRequirementDefinitionContent),
+    AttributeSubscription(AttributeSubscriptionContent),
+    WorkspaceVariable(WorkspaceVariableContent),
+    ActionRetryPolicy(ActionRetryPolicyContent),
+    FuncTimeout(FuncTimeoutContent),
+    FuncTestCase(FuncTestCaseContent),
+    /// Arguments, code, and results recorded for func runs. These are kept apart from `Any` so
+    /// that they never share an address with snapshot content, and can be removed along with the
+    /// func runs that use them.
+    FuncRunData(CasValue),
+    ComputedValue(ComputedValueContent),
 }
 
 macro_rules! impl_into_content_types {
@@ -120,6 +134,12 @@ impl_into_content_types!(ManagementPrototype);
 impl_into_content_types!(Geometry);
 impl_into_content_types!(View);
 impl_into_content_types!(ApprovalRequirementDefinition);
+impl_into_content_types!(AttributeSubscription);
+impl_into_content_types!(WorkspaceVariable);
+impl_into_content_types!(ActionRetryPolicy);
+impl_into_content_types!(FuncTimeout);
+impl_into_content_types!(FuncTestCase);
+impl_into_content_types!(ComputedValue);
 
 // Here we've broken the Foo, FooContent convention so we need to implement
 // these traits manually
@@ -134,7 +154,8 @@ impl TryFrom<ContentTypes> for CasValue {
 
     fn try_from(value: ContentTypes) -> Result<Self, Self::Error> {
         match value {
-            ContentTypes::Any(inner) => Ok(inner),
+            ContentTypes::Any(inner) | ContentTypes::FuncRunData(inner) => Ok(inner),
+            ContentTypes::ComputedValue(ComputedValueContent::V1(inner)) => Ok(inner.value),
             _ => Err("Could not convert ContentType to CasValue"),
         }
     }
@@ -143,7 +164,8 @@ impl TryFrom<ContentTypes> for CasValue {
 impl From<ContentTypes> for Option<CasValue> {
     fn from(value: ContentTypes) -> Self {
         match value {
-            ContentTypes::Any(value) => Some(value),
+            ContentTypes::Any(value) | ContentTypes::FuncRunData(value) => Some(value),
+            ContentTypes::ComputedValue(ComputedValueContent::V1(inner)) => Some(inner.value),
             _ => None,
         }
     }
@@ -295,6 +317,7 @@ pub struct GeometryContentV1 {
 pub enum FuncContent {
     V1(FuncContentV1),
     V2(FuncContentV2),
+    V3(FuncContentV3),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -329,10 +352,30 @@ pub struct FuncContentV2 {
     pub is_locked: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncContentV3 {
+    pub timestamp: Timestamp,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub hidden: bool,
+    pub builtin: bool,
+    pub backend_response_type: FuncBackendResponseType,
+    pub backend_kind: FuncBackendKind,
+    pub handler: Option<String>,
+    pub code_base64: Option<String>,
+    /// A hash of the code above
+    pub code_blake3: ContentHash,
+    pub is_locked: bool,
+    /// When set, the func is re-executed even if its inputs are unchanged (for non-deterministic
+    /// code)
+    pub always_execute: bool,
+}
+
 impl FuncContent {
-    pub fn extract(self) -> FuncContentV2 {
+    pub fn extract(self) -> FuncContentV3 {
         match self {
-            FuncContent::V1(v1) => FuncContentV2 {
+            FuncContent::V1(v1) => FuncContentV3 {
                 timestamp: v1.timestamp,
                 hidden: v1.hidden,
                 display_name: v1.display_name,
@@ -345,8 +388,24 @@ impl FuncContent {
                 handler: v1.handler,
                 code_base64: v1.code_base64,
                 code_blake3: v1.code_blake3,
+                always_execute: false,
             },
-            FuncContent::V2(v2) => v2,
+            FuncContent::V2(v2) => FuncContentV3 {
+                timestamp: v2.timestamp,
+                hidden: v2.hidden,
+                display_name: v2.display_name,
+                link: v2.link,
+                description: v2.description,
+                is_locked: v2.is_locked,
+                builtin: v2.builtin,
+                backend_response_type: v2.backend_response_type,
+                backend_kind: v2.backend_kind,
+                handler: v2.handler,
+                code_base64: v2.code_base64,
+                code_blake3: v2.code_blake3,
+                always_execute: false,
+            },
+            FuncContent::V3(v3) => v3,
         }
     }
 }
@@ -696,3 +755,95 @@ pub struct ApprovalRequirementDefinitionContentV1 {
     pub minimum: usize,
     pub approvers: HashSet<ApprovalRequirementApprover>,
 }
+
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum AttributeSubscriptionContent {
+    V1(AttributeSubscriptionContentV1),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct AttributeSubscriptionContentV1 {
+    pub timestamp: Timestamp,
+    pub filters: Vec<ComponentFilterContentV1>,
+    pub select: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub enum ComponentFilterContentV1 {
+    Schema(SchemaId),
+    DescendantOf(ComponentId),
+    DescendantOfSubscriber,
+    PropValue { path: Vec<String>, value: CasValue },
+}
+
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum WorkspaceVariableContent {
+    V1(WorkspaceVariableContentV1),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct WorkspaceVariableContentV1 {
+    pub timestamp: Timestamp,
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: WorkspaceVariableKind,
+    pub value: Option<CasValue>,
+}
+
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum ActionRetryPolicyContent {
+    V1(ActionRetryPolicyContentV1),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ActionRetryPolicyContentV1 {
+    pub timestamp: Timestamp,
+    pub target: ActionRetryPolicyTarget,
+    pub max_attempts: u32,
+    pub initial_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub retry_on: Vec<ActionFailureKind>,
+}
+
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum FuncTimeoutContent {
+    V1(FuncTimeoutContentV1),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncTimeoutContentV1 {
+    pub timestamp: Timestamp,
+    pub target: FuncTimeoutTarget,
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum FuncTestCaseContent {
+    V1(FuncTestCaseContentV1),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncTestCaseContentV1 {
+    pub timestamp: Timestamp,
+    pub func_id: FuncId,
+    pub name: String,
+    pub args: CasValue,
+    pub captured_from_component: Option<String>,
+    pub expectation: FuncTestCaseExpectation,
+    pub expected: Option<CasValue>,
+}
+
+/// An attribute value computed by its prototype function, which is read like any other value. The
+/// hashes of the code and arguments the function ran with are kept with it, so that running the
+/// function again can be skipped while they are unchanged.
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum ComputedValueContent {
+    V1(ComputedValueContentV1),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComputedValueContentV1 {
+    pub value: CasValue,
+    pub code_hash: ContentHash,
+    pub args_hash: ContentHash,
+}
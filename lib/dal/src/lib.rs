@@ -72,6 +72,7 @@ pub mod visibility;
 pub mod workspace;
 pub mod workspace_integrations;
 pub mod workspace_snapshot;
+pub mod workspace_variable;
 pub mod ws_event;
 
 pub use action::ActionPrototypeId;
@@ -137,6 +138,9 @@ pub use workspace_snapshot::{
     node_weight::NodeWeightDiscriminants,
 };
 pub use workspace_snapshot::{WorkspaceSnapshot, WorkspaceSnapshotError};
+pub use workspace_variable::{
+    WorkspaceVariable, WorkspaceVariableError, WorkspaceVariableId, WorkspaceVariableKind,
+};
 pub use ws_event::{WsEvent, WsEventError, WsEventResult, WsPayload};
 
 pub use si_runtime::{
@@ -258,6 +262,7 @@ pub fn generate_name() -> String {
 )]
 #[strum(serialize_all = "camelCase")]
 pub enum MigrationMode {
+    GarbageCollectFuncRuns,
     GarbageCollectSnapshots,
     Run,
     RunAndQuit,
@@ -287,6 +292,10 @@ impl MigrationMode {
     pub fn is_garbage_collect_snapshots(&self) -> bool {
         matches!(self, Self::GarbageCollectSnapshots)
     }
+
+    pub fn is_garbage_collect_func_runs(&self) -> bool {
+        matches!(self, Self::GarbageCollectFuncRuns)
+    }
 }
 
 #[cfg(test)]
@@ -300,6 +309,10 @@ mod tests {
 
         #[test]
         fn display() {
+            assert_eq!(
+                "garbageCollectFuncRuns",
+                MigrationMode::GarbageCollectFuncRuns.to_string()
+            );
             assert_eq!(
                 "garbageCollectSnapshots",
                 MigrationMode::GarbageCollectSnapshots.to_string()
@@ -311,6 +324,10 @@ mod tests {
 
         #[test]
         fn from_str() {
+            assert_eq!(
+                MigrationMode::GarbageCollectFuncRuns,
+                "garbageCollectFuncRuns".parse().expect("failed to parse")
+            );
             assert_eq!(
                 MigrationMode::GarbageCollectSnapshots,
                 "garbageCollectSnapshots".parse().expect("failed to parse")
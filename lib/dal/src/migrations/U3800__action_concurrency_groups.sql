@@ -0,0 +1,12 @@
+CREATE TABLE action_concurrency_groups
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident NOT NULL,
+    key                         text NOT NULL,
+    concurrency_limit           integer NOT NULL,
+    members                     jsonb NOT NULL
+);
+CREATE UNIQUE INDEX ON action_concurrency_groups (workspace_pk, key);
+CREATE INDEX ON action_concurrency_groups (workspace_pk);
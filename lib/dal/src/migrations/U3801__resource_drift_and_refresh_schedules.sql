@@ -0,0 +1,27 @@
+CREATE TABLE refresh_schedules
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident NOT NULL,
+    schema_id                   ident NULL,
+    interval_seconds            integer NOT NULL,
+    enabled                     boolean NOT NULL DEFAULT TRUE,
+    last_run_at                 timestamp with time zone NULL
+);
+-- One workspace wide schedule (without a schema), and one override per schema
+CREATE UNIQUE INDEX ON refresh_schedules (workspace_pk) WHERE schema_id IS NULL;
+CREATE UNIQUE INDEX ON refresh_schedules (workspace_pk, schema_id) WHERE schema_id IS NOT NULL;
+CREATE INDEX ON refresh_schedules (workspace_pk);
+
+CREATE TABLE resource_drifts
+(
+    pk                          ident primary key default ident_create_v1(),
+    detected_at                 timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident NOT NULL,
+    change_set_id               ident NOT NULL,
+    component_id                ident NOT NULL,
+    func_run_id                 ident NULL,
+    drifted_values              jsonb NOT NULL
+);
+CREATE UNIQUE INDEX ON resource_drifts (workspace_pk, change_set_id, component_id);
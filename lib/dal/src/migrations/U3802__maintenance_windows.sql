@@ -0,0 +1,25 @@
+CREATE TABLE maintenance_windows
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident NOT NULL,
+    name                        text NOT NULL,
+    schedule                    text NOT NULL,
+    timezone                    text NOT NULL,
+    duration_minutes            integer NOT NULL,
+    schema_id                   ident NULL,
+    label                       text NULL,
+    enabled                     boolean NOT NULL DEFAULT TRUE,
+    last_opened_at              timestamp with time zone NULL
+);
+CREATE INDEX ON maintenance_windows (workspace_pk);
+
+CREATE TABLE maintenance_window_overrides
+(
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident NOT NULL,
+    action_id                   ident NOT NULL,
+    approved_by                 ident NULL,
+    PRIMARY KEY (workspace_pk, action_id)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE func_egress_policies
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident NOT NULL,
+    func_kind                   text NOT NULL,
+    allow                       text[] NOT NULL DEFAULT '{}',
+    UNIQUE (workspace_pk, func_kind)
+);
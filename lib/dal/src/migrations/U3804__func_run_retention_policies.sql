@@ -0,0 +1,9 @@
+CREATE TABLE func_run_retention_policies
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident NOT NULL UNIQUE,
+    keep_last                   integer NOT NULL,
+    keep_days                   integer NOT NULL
+);
@@ -0,0 +1,13 @@
+CREATE TABLE action_retries
+(
+    workspace_pk                ident NOT NULL,
+    change_set_id               ident NOT NULL,
+    action_id                   ident NOT NULL,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    attempts                    integer NOT NULL,
+    -- NULL once pinga has claimed the retry and requeued the action
+    next_attempt_at             timestamp with time zone NULL,
+    PRIMARY KEY (workspace_pk, change_set_id, action_id)
+);
+CREATE INDEX ON action_retries (next_attempt_at) WHERE next_attempt_at IS NOT NULL;
@@ -0,0 +1,10 @@
+CREATE TABLE action_change_plan_requests
+(
+    workspace_pk                ident NOT NULL,
+    change_set_id               ident NOT NULL,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    -- Pushed back by every request, so that plans are computed once the change set settles
+    requested_at                timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (workspace_pk, change_set_id)
+);
+CREATE INDEX ON action_change_plan_requests (requested_at);
@@ -16,7 +16,7 @@ use crate::schema::variant::SchemaVariantError;
 use crate::{
     action::prototype::ActionPrototypeError,
     change_set::ChangeSetError,
-    func::{argument::FuncArgumentError, FuncError},
+    func::{argument::FuncArgumentError, test_case::FuncTestCaseError, FuncError},
     prop::PropError,
     socket::input::InputSocketError,
     socket::output::OutputSocketError,
@@ -62,6 +62,8 @@ pub enum PkgError {
     FuncArgument(#[from] FuncArgumentError),
     #[error("func argument for {0} not found with name {1}")]
     FuncArgumentNotFoundByName(FuncId, String),
+    #[error("func test case error: {0}")]
+    FuncTestCase(#[from] FuncTestCaseError),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("input socket error: {0}")]
@@ -156,6 +158,12 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::Management => Self::Management,
             FuncBackendKind::ResourcePayloadToValue => Self::ResourcePayloadToValue,
             FuncBackendKind::NormalizeToArray => Self::NormalizeToArray,
+            FuncBackendKind::Arithmetic => Self::Arithmetic,
+            FuncBackendKind::Coalesce => Self::Coalesce,
+            FuncBackendKind::JoinArray => Self::JoinArray,
+            FuncBackendKind::JsonPointer => Self::JsonPointer,
+            FuncBackendKind::StringTemplate => Self::StringTemplate,
+            FuncBackendKind::Wasm => Self::Wasm,
         }
     }
 }
@@ -184,6 +192,12 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::Management => Self::Management,
             FuncSpecBackendKind::ResourcePayloadToValue => Self::ResourcePayloadToValue,
             FuncSpecBackendKind::NormalizeToArray => Self::NormalizeToArray,
+            FuncSpecBackendKind::Arithmetic => Self::Arithmetic,
+            FuncSpecBackendKind::Coalesce => Self::Coalesce,
+            FuncSpecBackendKind::JoinArray => Self::JoinArray,
+            FuncSpecBackendKind::JsonPointer => Self::JsonPointer,
+            FuncSpecBackendKind::StringTemplate => Self::StringTemplate,
+            FuncSpecBackendKind::Wasm => Self::Wasm,
         }
     }
 }
@@ -201,6 +215,7 @@ impl From<FuncBackendResponseType> for FuncSpecBackendResponseType {
             FuncBackendResponseType::Json => Self::Json,
             FuncBackendResponseType::Map => Self::Map,
             FuncBackendResponseType::Object => Self::Object,
+            FuncBackendResponseType::Plan => Self::Plan,
             FuncBackendResponseType::Qualification => Self::Qualification,
             FuncBackendResponseType::Reconciliation => Self::Reconciliation,
             FuncBackendResponseType::SchemaVariantDefinition => Self::SchemaVariantDefinition,
@@ -226,6 +241,7 @@ impl From<FuncSpecBackendResponseType> for FuncBackendResponseType {
             FuncSpecBackendResponseType::Json => Self::Json,
             FuncSpecBackendResponseType::Map => Self::Map,
             FuncSpecBackendResponseType::Object => Self::Object,
+            FuncSpecBackendResponseType::Plan => Self::Plan,
             FuncSpecBackendResponseType::Qualification => Self::Qualification,
             FuncSpecBackendResponseType::Reconciliation => Self::Reconciliation,
             FuncSpecBackendResponseType::SchemaVariantDefinition => Self::SchemaVariantDefinition,
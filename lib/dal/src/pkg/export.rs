@@ -5,11 +5,11 @@ use strum::IntoEnumIterator;
 
 use si_pkg::{
     ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, AuthenticationFuncSpec,
-    ComponentSpec, EdgeSpec, FuncArgumentSpec, FuncSpec, FuncSpecData, LeafFunctionSpec,
-    ManagementFuncSpec, MapKeyFuncSpec, PkgSpec, PropSpec, PropSpecBuilder, PropSpecKind,
-    RootPropFuncSpec, SchemaSpec, SchemaSpecData, SchemaVariantSpec, SchemaVariantSpecBuilder,
-    SchemaVariantSpecData, SchemaVariantSpecPropRoot, SiPkg, SiPkgKind, SiPropFuncSpec,
-    SiPropFuncSpecKind, SocketSpec, SocketSpecData, SocketSpecKind, SpecError,
+    ComponentSpec, EdgeSpec, FuncArgumentSpec, FuncSpec, FuncSpecData, FuncTestCaseSpec,
+    LeafFunctionSpec, ManagementFuncSpec, MapKeyFuncSpec, PkgSpec, PropSpec, PropSpecBuilder,
+    PropSpecKind, RootPropFuncSpec, SchemaSpec, SchemaSpecData, SchemaVariantSpec,
+    SchemaVariantSpecBuilder, SchemaVariantSpecData, SchemaVariantSpecPropRoot, SiPkg, SiPkgKind,
+    SiPropFuncSpec, SiPropFuncSpecKind, SocketSpec, SocketSpecData, SocketSpecKind, SpecError,
 };
 use telemetry::prelude::*;
 
@@ -22,7 +22,7 @@ use crate::func::FuncKind;
 use crate::management::prototype::ManagementPrototype;
 use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
 use crate::{
-    func::{argument::FuncArgument, intrinsics::IntrinsicFunc},
+    func::{argument::FuncArgument, intrinsics::IntrinsicFunc, test_case::FuncTestCase},
     prop::PropPath,
     AttributePrototype, DalContext, Func, FuncId, Prop, PropId, PropKind, Schema, SchemaId,
     SchemaVariant, SchemaVariantId, Workspace,
@@ -864,6 +864,8 @@ impl PkgExporter {
                     // something that the user can toggle?
                     crate::attribute::prototype::argument::value_source::ValueSource::Secret(_) => {},
                     crate::attribute::prototype::argument::value_source::ValueSource::StaticArgumentValue(_) => {}, // do nothing as this is irrelevant for the schema variant!
+                    crate::attribute::prototype::argument::value_source::ValueSource::Subscription(_) => {}, // subscriptions are resolved per workspace, not part of the schema variant
+                    crate::attribute::prototype::argument::value_source::ValueSource::WorkspaceVariable(_) => {}, // workspace variables live in the workspace, not the schema variant
                 }
             }
         }
@@ -926,6 +928,18 @@ impl PkgExporter {
             );
         }
 
+        for test_case in FuncTestCase::list_for_func(ctx, func.id).await? {
+            func_spec_builder.test_case(
+                FuncTestCaseSpec::builder()
+                    .name(test_case.name())
+                    .args(test_case.args().to_owned())
+                    .expectation(test_case.expectation())
+                    .expected(test_case.expected().cloned())
+                    .unique_id(test_case.id().to_string())
+                    .build()?,
+            );
+        }
+
         let func_spec = func_spec_builder.build()?;
         // If we have data, or change set specific arguments, we're valid for this changeset
         let include_in_export = func_spec.data.is_some() || !args.is_empty();
@@ -1006,6 +1020,18 @@ impl PkgExporter {
                         func_specs.push(spec);
                     }
                 }
+                // Workspaces created before the expression intrinsics existed don't have them
+                // yet, so we install them on first use (like "si:setFloat" below).
+                IntrinsicFunc::Arithmetic
+                | IntrinsicFunc::Coalesce
+                | IntrinsicFunc::JoinArray
+                | IntrinsicFunc::JsonPointer
+                | IntrinsicFunc::StringTemplate => {
+                    let intrinsic_func_id = Func::find_intrinsic(ctx, intrinsic).await?;
+                    let intrinsic_func = Func::get_by_id(ctx, intrinsic_func_id).await?;
+                    let (spec, _) = self.add_func_to_map(ctx, &intrinsic_func).await?;
+                    func_specs.push(spec);
+                }
                 IntrinsicFunc::SetFloat => {
                     // We need to check that an intrinsic function with the name "si:setFloat" exists.
                     // If it does not exist, we import it from the intrinsic package.
@@ -1133,6 +1159,18 @@ impl PkgExporter {
                         self.func_map.insert(intrinsic_func_id, spec.clone());
                     }
                 }
+                // Workspaces created before the expression intrinsics existed don't have them
+                // yet, so we install them on first use (like "si:setFloat" below).
+                IntrinsicFunc::Arithmetic
+                | IntrinsicFunc::Coalesce
+                | IntrinsicFunc::JoinArray
+                | IntrinsicFunc::JsonPointer
+                | IntrinsicFunc::StringTemplate => {
+                    let intrinsic_func_id = Func::find_intrinsic(ctx, intrinsic).await?;
+                    let spec = intrinsic.to_spec()?;
+                    funcs.push(spec.clone());
+                    self.func_map.insert(intrinsic_func_id, spec.clone());
+                }
                 IntrinsicFunc::SetFloat => {
                     // We need to check that an intrinsic function with the name "si:setFloat" exists.
                     // If it does not exist, we import it from the intrinsic package.
@@ -2,9 +2,10 @@ use chrono::NaiveDateTime;
 use si_events::ulid::Ulid;
 use si_pkg::{
     SchemaVariantSpecPropRoot, SiPkg, SiPkgActionFunc, SiPkgAttrFuncInputView, SiPkgAuthFunc,
-    SiPkgComponent, SiPkgEdge, SiPkgError, SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData, SiPkgKind,
-    SiPkgLeafFunction, SiPkgManagementFunc, SiPkgMetadata, SiPkgProp, SiPkgPropData, SiPkgSchema,
-    SiPkgSchemaData, SiPkgSchemaVariant, SiPkgSocket, SiPkgSocketData, SocketSpecKind,
+    SiPkgComponent, SiPkgEdge, SiPkgError, SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData,
+    SiPkgFuncTestCase, SiPkgKind, SiPkgLeafFunction, SiPkgManagementFunc, SiPkgMetadata, SiPkgProp,
+    SiPkgPropData, SiPkgSchema, SiPkgSchemaData, SiPkgSchemaVariant, SiPkgSocket, SiPkgSocketData,
+    SocketSpecKind,
 };
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -18,6 +19,7 @@ use crate::attribute::prototype::argument::{
 };
 use crate::authentication_prototype::{AuthenticationPrototype, AuthenticationPrototypeId};
 use crate::func::intrinsics::IntrinsicFunc;
+use crate::func::test_case::{FuncTestCase, FuncTestCaseDefinition};
 use crate::func::FuncKind;
 use crate::management::prototype::ManagementPrototype;
 use crate::module::{Module, ModuleId};
@@ -191,6 +193,8 @@ async fn import_change_set(
                 if !args.is_empty() {
                     import_func_arguments(ctx, func.id, &args).await?;
                 }
+
+                import_func_test_cases(ctx, func.id, &func_spec.test_cases()?).await?;
             }
         };
     }
@@ -450,6 +454,39 @@ async fn import_func_arguments(
     Ok(())
 }
 
+/// Creates the test cases from the package that the [`Func`] does not already have one of the same
+/// name for.
+async fn import_func_test_cases(
+    ctx: &DalContext,
+    func_id: FuncId,
+    func_test_cases: &[SiPkgFuncTestCase<'_>],
+) -> PkgResult<()> {
+    for test_case in func_test_cases {
+        if test_case.deleted()
+            || FuncTestCase::find_by_name_for_func(ctx, func_id, test_case.name())
+                .await?
+                .is_some()
+        {
+            continue;
+        }
+
+        FuncTestCase::new(
+            ctx,
+            func_id,
+            FuncTestCaseDefinition {
+                name: test_case.name().to_owned(),
+                args: test_case.args().to_owned(),
+                captured_from_component: None,
+                expectation: test_case.expectation().into(),
+                expected: test_case.expected().cloned(),
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 async fn create_schema(
     ctx: &DalContext,
     maybe_existing_schema_id: Option<Ulid>,
@@ -1058,6 +1095,7 @@ pub async fn import_only_new_funcs(
         if let Some(intrinsic) = IntrinsicFunc::maybe_from_str(func_spec.name()) {
             if intrinsic == IntrinsicFunc::ResourcePayloadToValue
                 || intrinsic == IntrinsicFunc::NormalizeToArray
+                || intrinsic.is_expression()
             {
                 if let Some(func_id) =
                     Func::find_id_by_name_and_kind(ctx, func_spec.name(), FuncKind::Intrinsic)
@@ -1124,6 +1162,8 @@ pub async fn import_only_new_funcs(
                 }
             }
 
+            import_func_test_cases(ctx, func.id, &func_spec.test_cases()?).await?;
+
             thing_map.insert(func_spec.unique_id().into(), Thing::Func(func));
         }
     }
@@ -1870,9 +1870,14 @@ impl SchemaVariant {
             match IntrinsicFunc::maybe_from_str(&func.name) {
                 None => filtered_funcs.push(func.to_owned()),
                 Some(intrinsic) => match intrinsic {
-                    IntrinsicFunc::Identity
+                    IntrinsicFunc::Arithmetic
+                    | IntrinsicFunc::Coalesce
+                    | IntrinsicFunc::Identity
+                    | IntrinsicFunc::JoinArray
+                    | IntrinsicFunc::JsonPointer
                     | IntrinsicFunc::NormalizeToArray
                     | IntrinsicFunc::ResourcePayloadToValue
+                    | IntrinsicFunc::StringTemplate
                     | IntrinsicFunc::Unset => filtered_funcs.push(func.to_owned()),
                     IntrinsicFunc::SetArray
                     | IntrinsicFunc::SetBoolean
@@ -1976,8 +1981,13 @@ impl SchemaVariant {
                     ActionPrototype::func_id(ctx, action_prototype_node_weight.id().into())
                         .await
                         .map_err(|err| SchemaVariantError::ActionPrototype(err.to_string()))?;
+                let plan_func_id =
+                    ActionPrototype::plan_func_id(ctx, action_prototype_node_weight.id().into())
+                        .await
+                        .map_err(|err| SchemaVariantError::ActionPrototype(err.to_string()))?;
 
                 all_func_ids.insert(func_id);
+                all_func_ids.extend(plan_func_id);
             }
         }
 
@@ -2293,7 +2303,8 @@ impl SchemaVariant {
                 | EdgeWeightKindDiscriminants::ValidationOutput
                 | EdgeWeightKindDiscriminants::Manages
                 | EdgeWeightKindDiscriminants::DiagramObject
-                | EdgeWeightKindDiscriminants::ApprovalRequirementDefinition => {}
+                | EdgeWeightKindDiscriminants::ApprovalRequirementDefinition
+                | EdgeWeightKindDiscriminants::FuncTestCase => {}
             }
         }
 
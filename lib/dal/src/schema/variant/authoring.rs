@@ -25,6 +25,7 @@ use crate::attribute::prototype::AttributePrototypeError;
 use crate::func::authoring::FuncAuthoringError;
 use crate::func::intrinsics::IntrinsicFunc;
 use crate::func::runner::{FuncRunner, FuncRunnerError};
+use crate::func::timeout::{FuncTimeout, FuncTimeoutError};
 use crate::pkg::export::PkgExporter;
 use crate::pkg::import::import_only_new_funcs;
 use crate::pkg::{import_pkg_from_pkg, ImportOptions, PkgError};
@@ -66,6 +67,8 @@ pub enum VariantAuthoringError {
     FuncRun(#[from] FuncRunnerError),
     #[error("func run value sender has terminated without sending")]
     FuncRunGone,
+    #[error("func timeout error: {0}")]
+    FuncTimeout(#[from] FuncTimeoutError),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("input socket error: {0}")]
@@ -349,6 +352,9 @@ impl VariantAuthoringClient {
             )
             .await?;
 
+            let timeouts = FuncTimeout::list_for_schema_variant(ctx, schema_variant_id).await?;
+            FuncTimeout::restore_for_schema_variant(ctx, new_variant.id, timeouts).await?;
+
             for component_id in components_in_use {
                 Component::get_by_id(ctx, component_id)
                     .await?
@@ -460,6 +466,9 @@ impl VariantAuthoringClient {
             })
             .await?;
 
+        // The action prototypes are recreated below, so hold on to their timeouts
+        let timeouts = FuncTimeout::list_for_schema_variant(ctx, current_schema_variant_id).await?;
+
         // We need to clean up the old graph before we re-import the new parts!
         current_schema_variant
             .remove_external_connections(ctx)
@@ -486,6 +495,7 @@ impl VariantAuthoringClient {
                 current_schema_variant_id,
             ));
         }
+        FuncTimeout::restore_for_schema_variant(ctx, current_schema_variant_id, timeouts).await?;
 
         // Let's update the SV struct now to reflect any changes
         new_schema_variant
@@ -689,6 +699,8 @@ impl VariantAuthoringClient {
             None,
         )
         .await?;
+        let timeouts = FuncTimeout::list_for_schema_variant(ctx, locked_variant_id).await?;
+        FuncTimeout::restore_for_schema_variant(ctx, new_schema_variant.id, timeouts).await?;
 
         // need to manually modify the variant to get the new version
         // as the pkg spec used to generate this new version only has the
@@ -1,6 +1,8 @@
 use crate::{workspace::WorkspaceId, DalContext, TransactionsError};
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use si_data_pg::{PgError, PgRow};
+use telemetry::prelude::*;
 use thiserror::Error;
 
 #[remain::sorted]
@@ -35,6 +37,11 @@ impl TryFrom<PgRow> for WorkspaceIntegration {
     }
 }
 
+#[derive(Serialize)]
+struct SlackMessage<'a> {
+    text: &'a str,
+}
+
 impl WorkspaceIntegration {
     pub fn pk(&self) -> &WorkspaceIntegrationId {
         &self.pk
@@ -123,4 +130,44 @@ impl WorkspaceIntegration {
         };
         Ok(maybe_workspace_integration)
     }
+
+    /// Posts the message to the workspace's Slack webhook, if it has one. Failing to deliver the
+    /// message is logged, not returned, since the webhook is only ever a notification.
+    pub async fn post_to_webhook(
+        ctx: &DalContext,
+        message: &str,
+    ) -> WorkspaceIntegrationsResult<()> {
+        let workspace_pk = ctx.workspace_pk()?;
+        if let Some(integration) = Self::get_integrations_for_workspace_pk(ctx).await? {
+            if let Some(webhook_url) = integration.slack_webhook_url() {
+                let client = Client::new();
+                let slack_message = SlackMessage { text: message };
+
+                match client
+                    .post(webhook_url.clone())
+                    .json(&slack_message)
+                    .send()
+                    .await
+                {
+                    Ok(response) if !response.status().is_success() => {
+                        info!(
+                            "Failed to post to Slack webhook for workspace {} to URL {}, status: {}",
+                            workspace_pk,
+                            webhook_url,
+                            response.status()
+                        );
+                    }
+                    Err(err) => {
+                        info!(
+                            "Error posting to Slack webhook for workspace {} to URL {}: {}",
+                            workspace_pk, webhook_url, err
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
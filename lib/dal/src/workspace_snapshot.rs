@@ -40,7 +40,10 @@ use crate::workspace_snapshot::{
     content_address::ContentAddressDiscriminants,
     edge_weight::{EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants},
     graph::{LineageId, WorkspaceSnapshotGraphDiscriminants},
-    node_weight::{category_node_weight::CategoryNodeKind, NodeWeight},
+    node_weight::{
+        category_node_weight::{CategoryNodeKind, CategoryNodeWeight},
+        NodeWeight,
+    },
 };
 use crate::{
     workspace_snapshot::{graph::WorkspaceSnapshotGraphError, node_weight::NodeWeightError},
@@ -1049,6 +1052,32 @@ impl WorkspaceSnapshot {
             .map(|(category_node_id, _)| category_node_id))
     }
 
+    /// Finds the root's category node of the given kind, creating it with the kind's
+    /// [`well_known_id`](CategoryNodeKind::well_known_id) if the snapshot predates the kind.
+    pub async fn get_or_create_category_node(
+        &self,
+        kind: CategoryNodeKind,
+    ) -> WorkspaceSnapshotResult<Ulid> {
+        if let Some(category_id) = self.get_category_node(None, kind).await? {
+            return Ok(category_id);
+        }
+        let id = kind
+            .well_known_id()
+            .ok_or(WorkspaceSnapshotError::CategoryNodeNotFound(kind))?;
+
+        debug!(?kind, "creating category node");
+        self.add_or_replace_node(NodeWeight::Category(CategoryNodeWeight::new(id, id, kind)))
+            .await?;
+        self.add_edge(
+            self.root().await?,
+            EdgeWeight::new(EdgeWeightKind::new_use()),
+            id,
+        )
+        .await?;
+
+        Ok(id)
+    }
+
     pub async fn edges_directed(
         &self,
         id: impl Into<Ulid>,
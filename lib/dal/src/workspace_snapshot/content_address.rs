@@ -39,6 +39,11 @@ pub enum ContentAddress {
     Geometry(ContentHash),
     View(ContentHash),
     ApprovalRequirementDefinition(ContentHash),
+    AttributeSubscription(ContentHash),
+    WorkspaceVariable(ContentHash),
+    ActionRetryPolicy(ContentHash),
+    FuncTimeout(ContentHash),
+    FuncTestCase(ContentHash),
 }
 
 impl ContentAddress {
@@ -67,7 +72,12 @@ impl ContentAddress {
             | ContentAddress::ValidationOutput(id)
             | ContentAddress::View(id)
             | ContentAddress::ManagementPrototype(id)
-            | ContentAddress::ApprovalRequirementDefinition(id) => Some(*id),
+            | ContentAddress::ApprovalRequirementDefinition(id)
+            | ContentAddress::AttributeSubscription(id)
+            | ContentAddress::WorkspaceVariable(id)
+            | ContentAddress::ActionRetryPolicy(id)
+            | ContentAddress::FuncTimeout(id)
+            | ContentAddress::FuncTestCase(id) => Some(*id),
         }
         .unwrap_or_default()
     }
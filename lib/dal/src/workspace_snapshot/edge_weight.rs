@@ -71,6 +71,9 @@ pub enum EdgeWeightKind {
     DiagramObject,
     /// Indicates if there is an corresponding approval requirement definition.
     ApprovalRequirementDefinition,
+    /// From a [`Func`](crate::Func) to one of its
+    /// [`FuncTestCases`](crate::func::test_case::FuncTestCase).
+    FuncTestCase,
 }
 
 impl EdgeWeightKind {
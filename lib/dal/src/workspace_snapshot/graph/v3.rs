@@ -737,6 +737,7 @@ impl WorkspaceSnapshotGraphV3 {
                     EdgeWeightKindDiscriminants::Contain => "blue",
                     EdgeWeightKindDiscriminants::DiagramObject => "black",
                     EdgeWeightKindDiscriminants::FrameContains => "black",
+                    EdgeWeightKindDiscriminants::FuncTestCase => "black",
                     EdgeWeightKindDiscriminants::ManagementPrototype => "pink",
                     EdgeWeightKindDiscriminants::Manages => "pink",
                     EdgeWeightKindDiscriminants::Ordering => "gray",
@@ -779,6 +780,7 @@ impl WorkspaceSnapshotGraphV3 {
                             ContentAddressDiscriminants::ActionPrototype => "green",
                             ContentAddressDiscriminants::ApprovalRequirementDefinition => "black",
                             ContentAddressDiscriminants::AttributePrototype => "green",
+                            ContentAddressDiscriminants::AttributeSubscription => "green",
                             ContentAddressDiscriminants::Component => "black",
                             ContentAddressDiscriminants::DeprecatedAction => "green",
                             ContentAddressDiscriminants::DeprecatedActionBatch => "green",
@@ -800,6 +802,10 @@ impl WorkspaceSnapshotGraphV3 {
                             ContentAddressDiscriminants::ValidationOutput => "darkcyan",
                             ContentAddressDiscriminants::ValidationPrototype => "black",
                             ContentAddressDiscriminants::View => "black",
+                            ContentAddressDiscriminants::WorkspaceVariable => "purple",
+                            ContentAddressDiscriminants::ActionRetryPolicy => "green",
+                            ContentAddressDiscriminants::FuncTestCase => "green",
+                            ContentAddressDiscriminants::FuncTimeout => "green",
                         };
                         (discrim.to_string(), color)
                     }
@@ -836,6 +842,21 @@ impl WorkspaceSnapshotGraphV3 {
                         CategoryNodeKind::DiagramObject => {
                             ("Diagram Objects (Category)".into(), "black")
                         }
+                        CategoryNodeKind::WorkspaceVariable => {
+                            ("Workspace Variables (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::ActionRetryPolicy => {
+                            ("Action Retry Policies (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::FuncTimeout => {
+                            ("Func Timeouts (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::FuncTestCase => {
+                            ("Func Test Cases (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::AttributeSubscription => {
+                            ("Attribute Subscriptions (Category)".into(), "black")
+                        }
                     },
                     NodeWeight::Component(component) => (
                         "Component".to_string(),
@@ -1420,7 +1441,8 @@ impl WorkspaceSnapshotGraphV3 {
                     | EdgeWeightKind::ManagementPrototype
                     | EdgeWeightKind::Manages
                     | EdgeWeightKind::DiagramObject
-                    | EdgeWeightKind::ApprovalRequirementDefinition => {}
+                    | EdgeWeightKind::ApprovalRequirementDefinition
+                    | EdgeWeightKind::FuncTestCase => {}
                 }
             }
         }
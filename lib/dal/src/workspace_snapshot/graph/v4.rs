@@ -874,6 +874,7 @@ impl WorkspaceSnapshotGraphV4 {
                     EdgeWeightKindDiscriminants::Contain => "blue",
                     EdgeWeightKindDiscriminants::DiagramObject => "black",
                     EdgeWeightKindDiscriminants::FrameContains => "black",
+                    EdgeWeightKindDiscriminants::FuncTestCase => "black",
                     EdgeWeightKindDiscriminants::ManagementPrototype => "pink",
                     EdgeWeightKindDiscriminants::Manages => "pink",
                     EdgeWeightKindDiscriminants::Ordering => "gray",
@@ -916,6 +917,7 @@ impl WorkspaceSnapshotGraphV4 {
                             ContentAddressDiscriminants::ActionPrototype => "green",
                             ContentAddressDiscriminants::ApprovalRequirementDefinition => "black",
                             ContentAddressDiscriminants::AttributePrototype => "green",
+                            ContentAddressDiscriminants::AttributeSubscription => "green",
                             ContentAddressDiscriminants::Component => "black",
                             ContentAddressDiscriminants::DeprecatedAction => "green",
                             ContentAddressDiscriminants::DeprecatedActionBatch => "green",
@@ -937,6 +939,10 @@ impl WorkspaceSnapshotGraphV4 {
                             ContentAddressDiscriminants::ValidationOutput => "darkcyan",
                             ContentAddressDiscriminants::ValidationPrototype => "black",
                             ContentAddressDiscriminants::View => "black",
+                            ContentAddressDiscriminants::WorkspaceVariable => "purple",
+                            ContentAddressDiscriminants::ActionRetryPolicy => "green",
+                            ContentAddressDiscriminants::FuncTestCase => "green",
+                            ContentAddressDiscriminants::FuncTimeout => "green",
                         };
                         (discrim.to_string(), color)
                     }
@@ -973,6 +979,21 @@ impl WorkspaceSnapshotGraphV4 {
                         CategoryNodeKind::DiagramObject => {
                             ("Diagram Objects (Category)".into(), "black")
                         }
+                        CategoryNodeKind::WorkspaceVariable => {
+                            ("Workspace Variables (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::ActionRetryPolicy => {
+                            ("Action Retry Policies (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::FuncTimeout => {
+                            ("Func Timeouts (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::FuncTestCase => {
+                            ("Func Test Cases (Category)".into(), "black")
+                        }
+                        CategoryNodeKind::AttributeSubscription => {
+                            ("Attribute Subscriptions (Category)".into(), "black")
+                        }
                     },
                     NodeWeight::Component(component) => (
                         "Component".to_string(),
@@ -1579,7 +1600,8 @@ impl WorkspaceSnapshotGraphV4 {
                     | EdgeWeightKind::ManagementPrototype
                     | EdgeWeightKind::Manages
                     | EdgeWeightKind::DiagramObject
-                    | EdgeWeightKind::ApprovalRequirementDefinition => {}
+                    | EdgeWeightKind::ApprovalRequirementDefinition
+                    | EdgeWeightKind::FuncTestCase => {}
                 }
             }
         }
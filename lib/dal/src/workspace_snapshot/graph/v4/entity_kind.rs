@@ -44,6 +44,13 @@ impl EntityKindExt for WorkspaceSnapshotGraphV4 {
                     }
                     CategoryNodeKind::View => EntityKind::CategoryView,
                     CategoryNodeKind::DiagramObject => EntityKind::CategoryDiagramObject,
+                    CategoryNodeKind::WorkspaceVariable => EntityKind::CategoryWorkspaceVariable,
+                    CategoryNodeKind::ActionRetryPolicy => EntityKind::CategoryActionRetryPolicy,
+                    CategoryNodeKind::FuncTimeout => EntityKind::CategoryFuncTimeout,
+                    CategoryNodeKind::FuncTestCase => EntityKind::CategoryFuncTestCase,
+                    CategoryNodeKind::AttributeSubscription => {
+                        EntityKind::CategoryAttributeSubscription
+                    }
                 }
             }
             NodeWeightDiscriminants::Component => EntityKind::Component,
@@ -53,6 +60,9 @@ impl EntityKindExt for WorkspaceSnapshotGraphV4 {
             {
                 ContentAddressDiscriminants::ActionPrototype => EntityKind::ActionPrototype,
                 ContentAddressDiscriminants::AttributePrototype => EntityKind::AttributePrototype,
+                ContentAddressDiscriminants::AttributeSubscription => {
+                    EntityKind::AttributeSubscription
+                }
                 ContentAddressDiscriminants::Component => EntityKind::Component,
                 ContentAddressDiscriminants::Func => EntityKind::Func,
                 // NOTE(nick): we are treating "FuncArg" and "FuncArgument" as the same entity.
@@ -72,6 +82,10 @@ impl EntityKindExt for WorkspaceSnapshotGraphV4 {
                 ContentAddressDiscriminants::ValidationOutput => EntityKind::ValidationOutput,
                 ContentAddressDiscriminants::ValidationPrototype => EntityKind::ValidationPrototype,
                 ContentAddressDiscriminants::View => EntityKind::View,
+                ContentAddressDiscriminants::WorkspaceVariable => EntityKind::WorkspaceVariable,
+                ContentAddressDiscriminants::ActionRetryPolicy => EntityKind::ActionRetryPolicy,
+                ContentAddressDiscriminants::FuncTimeout => EntityKind::FuncTimeout,
+                ContentAddressDiscriminants::FuncTestCase => EntityKind::FuncTestCase,
                 invalid => {
                     return Err(WorkspaceSnapshotGraphError::NodeWeight(
                         NodeWeightError::InvalidContentAddressForWeightKind(
@@ -25,6 +25,45 @@ pub enum CategoryNodeKind {
     DependentValueRoots,
     View,
     DiagramObject,
+    WorkspaceVariable,
+    ActionRetryPolicy,
+    FuncTimeout,
+    FuncTestCase,
+    AttributeSubscription,
+}
+
+impl CategoryNodeKind {
+    /// The id a category node of this kind is created with when it is added to a snapshot that
+    /// predates the kind. Change sets that each create the category independently will agree on
+    /// its id, so applying them onto the same base leaves a single category node rather than two.
+    ///
+    /// Kinds that are created along with the root have no well-known id.
+    pub fn well_known_id(self) -> Option<Ulid> {
+        let id = match self {
+            Self::Action
+            | Self::Component
+            | Self::DeprecatedActionBatch
+            | Self::Func
+            | Self::Module
+            | Self::Schema
+            | Self::Secret
+            | Self::DependentValueRoots
+            | Self::View
+            | Self::DiagramObject => return None,
+            // 01JACZ6W9HXT3QK7B2M4N8PVRS
+            Self::WorkspaceVariable => 0x0192_99f3_7131_ee87_799d_62a1_2a8b_6f19,
+            // 01JAD3K8Q7R2TMX5V9WB6NPYHC
+            Self::ActionRetryPolicy => 0x0192_9a39_a2e7_c0b5_4e97_69e2_cd5b_7a2c,
+            // 01JAG9T4XK6RW2M8N3PQZB5HDF
+            Self::FuncTimeout => 0x0192_a09d_13b3_3638_2a22_a3b5_feb2_c5af,
+            // 01JAG4N7W2P8RX5T3KQB9MZDHV
+            Self::FuncTestCase => 0x0192_a04a_9f82_b231_d2e8_73ba_d34f_b63b,
+            // 01JAD4S8QY2VWN6T0M5KHX3FBE
+            Self::AttributeSubscription => 0x0192_9a4c_a2fe_16f9_5368_142c_e3d1_bd6e,
+        };
+
+        Some(ulid::Ulid(id).into())
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -105,6 +105,9 @@ impl ContentNodeWeight {
             ContentAddress::AttributePrototype(_) => {
                 ContentAddress::AttributePrototype(content_hash)
             }
+            ContentAddress::AttributeSubscription(_) => {
+                ContentAddress::AttributeSubscription(content_hash)
+            }
             ContentAddress::Component(_) => ContentAddress::Component(content_hash),
             ContentAddress::OutputSocket(_) => ContentAddress::OutputSocket(content_hash),
             ContentAddress::FuncArg(_) => ContentAddress::FuncArg(content_hash),
@@ -159,6 +162,10 @@ impl ContentNodeWeight {
                     "Content".to_string(),
                 ));
             }
+            ContentAddress::WorkspaceVariable(_) => ContentAddress::WorkspaceVariable(content_hash),
+            ContentAddress::ActionRetryPolicy(_) => ContentAddress::ActionRetryPolicy(content_hash),
+            ContentAddress::FuncTimeout(_) => ContentAddress::FuncTimeout(content_hash),
+            ContentAddress::FuncTestCase(_) => ContentAddress::FuncTestCase(content_hash),
         };
 
         self.content_address = new_address;
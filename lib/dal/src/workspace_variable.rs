@@ -0,0 +1,438 @@
+//! Workspace variables are named, typed values that live in the workspace snapshot (and therefore
+//! vary per [`ChangeSet`](crate::ChangeSet)). They can be used as a
+//! [`ValueSource`](crate::attribute::prototype::argument::value_source::ValueSource) for
+//! [`AttributePrototypeArguments`](AttributePrototypeArgument) so that values repeated across many
+//! [`Components`](crate::Component) (account ids, default regions, etc.) only need to be set once.
+//!
+//! Since variables are reachable from the root of the snapshot, they are included in workspace
+//! exports and imports alongside everything else.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_events::ulid::Ulid;
+use si_layer_cache::LayerDbError;
+use strum::{AsRefStr, Display, EnumIter, EnumString};
+use thiserror::Error;
+
+use crate::attribute::prototype::argument::{
+    AttributePrototypeArgument, AttributePrototypeArgumentError,
+};
+use crate::attribute::prototype::AttributePrototypeError;
+use crate::layer_db_types::{WorkspaceVariableContent, WorkspaceVariableContentV1};
+use crate::workspace_snapshot::content_address::{ContentAddress, ContentAddressDiscriminants};
+use crate::workspace_snapshot::edge_weight::{
+    EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants,
+};
+use crate::workspace_snapshot::node_weight::category_node_weight::CategoryNodeKind;
+use crate::workspace_snapshot::node_weight::{NodeWeight, NodeWeightError};
+use crate::workspace_snapshot::WorkspaceSnapshotError;
+use crate::{
+    AttributePrototype, AttributeValueId, ChangeSetId, DalContext, Timestamp, TransactionsError,
+    WsEvent, WsEventResult, WsPayload,
+};
+
+pub use si_id::WorkspaceVariableId;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WorkspaceVariableError {
+    #[error("attribute prototype error: {0}")]
+    AttributePrototype(#[from] AttributePrototypeError),
+    #[error("attribute prototype argument error: {0}")]
+    AttributePrototypeArgument(#[from] Box<AttributePrototypeArgumentError>),
+    #[error("workspace variable {0} is in use by {1} attribute value(s)")]
+    InUse(WorkspaceVariableId, usize),
+    #[error("invalid value for workspace variable {0} (expected {1}): {2}")]
+    InvalidValue(String, WorkspaceVariableKind, Value),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("workspace variable name cannot be empty")]
+    NameEmpty,
+    #[error("workspace variable name already in use: {0}")]
+    NameTaken(String),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+pub type WorkspaceVariableResult<T> = Result<T, WorkspaceVariableError>;
+
+impl From<AttributePrototypeArgumentError> for WorkspaceVariableError {
+    fn from(value: AttributePrototypeArgumentError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+/// The type of value a [`WorkspaceVariable`] holds.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum WorkspaceVariableKind {
+    Boolean,
+    Integer,
+    /// Any JSON value (objects and arrays included).
+    Json,
+    String,
+}
+
+impl WorkspaceVariableKind {
+    /// Whether or not the provided value can be stored in a variable of this kind.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match self {
+            Self::Boolean => value.is_boolean(),
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Json => true,
+            Self::String => value.is_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceVariable {
+    id: WorkspaceVariableId,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    name: String,
+    description: Option<String>,
+    kind: WorkspaceVariableKind,
+    value: Option<Value>,
+}
+
+impl WorkspaceVariable {
+    fn assemble(id: WorkspaceVariableId, inner: WorkspaceVariableContentV1) -> Self {
+        Self {
+            id,
+            timestamp: inner.timestamp,
+            name: inner.name,
+            description: inner.description,
+            kind: inner.kind,
+            value: inner.value.map(Into::into),
+        }
+    }
+
+    fn content(&self) -> WorkspaceVariableContentV1 {
+        WorkspaceVariableContentV1 {
+            timestamp: self.timestamp,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            kind: self.kind,
+            value: self.value.clone().map(Into::into),
+        }
+    }
+
+    pub fn id(&self) -> WorkspaceVariableId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn kind(&self) -> WorkspaceVariableKind {
+        self.kind
+    }
+
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
+    pub async fn new(
+        ctx: &DalContext,
+        name: impl Into<String>,
+        description: Option<String>,
+        kind: WorkspaceVariableKind,
+        value: Option<Value>,
+    ) -> WorkspaceVariableResult<Self> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(WorkspaceVariableError::NameEmpty);
+        }
+        if Self::find_by_name(ctx, &name).await?.is_some() {
+            return Err(WorkspaceVariableError::NameTaken(name));
+        }
+        Self::validate_value(&name, kind, value.as_ref())?;
+
+        let content = WorkspaceVariableContentV1 {
+            timestamp: Timestamp::now(),
+            name,
+            description,
+            kind,
+            value: value.map(Into::into),
+        };
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(WorkspaceVariableContent::V1(content.clone()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let id = workspace_snapshot.generate_ulid().await?;
+        let lineage_id = workspace_snapshot.generate_ulid().await?;
+        let node_weight =
+            NodeWeight::new_content(id, lineage_id, ContentAddress::WorkspaceVariable(hash));
+        workspace_snapshot.add_or_replace_node(node_weight).await?;
+
+        let category_id = workspace_snapshot
+            .get_or_create_category_node(CategoryNodeKind::WorkspaceVariable)
+            .await?;
+        workspace_snapshot
+            .add_edge(category_id, EdgeWeight::new(EdgeWeightKind::new_use()), id)
+            .await?;
+
+        Ok(Self::assemble(id.into(), content))
+    }
+
+    pub async fn get_by_id(
+        ctx: &DalContext,
+        id: WorkspaceVariableId,
+    ) -> WorkspaceVariableResult<Self> {
+        let ulid: Ulid = id.into();
+        let node_weight = ctx
+            .workspace_snapshot()?
+            .get_node_weight(ulid)
+            .await?
+            .get_content_node_weight_of_kind(ContentAddressDiscriminants::WorkspaceVariable)?;
+        let hash = node_weight.content_hash();
+
+        let content: WorkspaceVariableContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&hash)
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(ulid))?;
+
+        let WorkspaceVariableContent::V1(inner) = content;
+
+        Ok(Self::assemble(id, inner))
+    }
+
+    pub async fn list(ctx: &DalContext) -> WorkspaceVariableResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let Some(category_id) = workspace_snapshot
+            .get_category_node(None, CategoryNodeKind::WorkspaceVariable)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut variables = vec![];
+        for variable_idx in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(category_id, EdgeWeightKindDiscriminants::Use)
+            .await?
+        {
+            let id = workspace_snapshot.get_node_weight(variable_idx).await?.id();
+            variables.push(Self::get_by_id(ctx, id.into()).await?);
+        }
+        variables.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(variables)
+    }
+
+    pub async fn find_by_name(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+    ) -> WorkspaceVariableResult<Option<Self>> {
+        let name = name.as_ref();
+        Ok(Self::list(ctx)
+            .await?
+            .into_iter()
+            .find(|variable| variable.name == name))
+    }
+
+    /// Sets a new value for the [`WorkspaceVariable`] and enqueues everything that uses it into
+    /// the dependent values update.
+    pub async fn set_value(
+        mut self,
+        ctx: &DalContext,
+        value: Option<Value>,
+    ) -> WorkspaceVariableResult<Self> {
+        Self::validate_value(&self.name, self.kind, value.as_ref())?;
+        if self.value == value {
+            return Ok(self);
+        }
+
+        self.value = value;
+        self.write(ctx).await?;
+
+        ctx.add_dependent_values_and_enqueue(vec![self.id]).await?;
+
+        Ok(self)
+    }
+
+    pub async fn set_description(
+        mut self,
+        ctx: &DalContext,
+        description: Option<String>,
+    ) -> WorkspaceVariableResult<Self> {
+        if self.description == description {
+            return Ok(self);
+        }
+
+        self.description = description;
+        self.write(ctx).await?;
+
+        Ok(self)
+    }
+
+    /// Removes the [`WorkspaceVariable`]. Variables still in use by an
+    /// [`AttributePrototypeArgument`] cannot be removed, since that would leave the argument
+    /// without a value source.
+    pub async fn remove(ctx: &DalContext, id: WorkspaceVariableId) -> WorkspaceVariableResult<()> {
+        let dependents = Self::direct_dependent_attribute_values(ctx, id).await?;
+        if !dependents.is_empty() {
+            return Err(WorkspaceVariableError::InUse(id, dependents.len()));
+        }
+
+        ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
+
+        Ok(())
+    }
+
+    /// Find all [`AttributeValues`](crate::AttributeValue) that _directly_ depend on the
+    /// [`WorkspaceVariable`] corresponding to the provided [`WorkspaceVariableId`].
+    pub async fn direct_dependent_attribute_values(
+        ctx: &DalContext,
+        id: WorkspaceVariableId,
+    ) -> WorkspaceVariableResult<Vec<AttributeValueId>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let attribute_prototype_argument_indices = workspace_snapshot
+            .incoming_sources_for_edge_weight_kind(
+                id,
+                EdgeWeightKindDiscriminants::PrototypeArgumentValue,
+            )
+            .await?;
+
+        let mut attribute_value_ids = Vec::new();
+        for attribute_prototype_argument_index in attribute_prototype_argument_indices {
+            let attribute_prototype_argument_node_weight = workspace_snapshot
+                .get_node_weight(attribute_prototype_argument_index)
+                .await?
+                .get_attribute_prototype_argument_node_weight()?;
+            let attribute_prototype_id = AttributePrototypeArgument::prototype_id_for_argument_id(
+                ctx,
+                attribute_prototype_argument_node_weight.id().into(),
+            )
+            .await?;
+            attribute_value_ids.extend(
+                AttributePrototype::attribute_value_ids(ctx, attribute_prototype_id).await?,
+            );
+        }
+
+        Ok(attribute_value_ids)
+    }
+
+    fn validate_value(
+        name: &str,
+        kind: WorkspaceVariableKind,
+        value: Option<&Value>,
+    ) -> WorkspaceVariableResult<()> {
+        match value {
+            Some(value) if !kind.accepts(value) => Err(WorkspaceVariableError::InvalidValue(
+                name.to_owned(),
+                kind,
+                value.to_owned(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    async fn write(&mut self, ctx: &DalContext) -> WorkspaceVariableResult<()> {
+        self.timestamp.updated_at = chrono::Utc::now();
+
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(WorkspaceVariableContent::V1(self.content()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        ctx.workspace_snapshot()?
+            .update_content(self.id.into(), hash)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceVariableWsPayload {
+    workspace_variable: WorkspaceVariable,
+    change_set_id: ChangeSetId,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceVariableDeletedPayload {
+    workspace_variable_id: WorkspaceVariableId,
+    change_set_id: ChangeSetId,
+}
+
+impl WsEvent {
+    pub async fn workspace_variable_created(
+        ctx: &DalContext,
+        workspace_variable: WorkspaceVariable,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::WorkspaceVariableCreated(WorkspaceVariableWsPayload {
+                workspace_variable,
+                change_set_id: ctx.change_set_id(),
+            }),
+        )
+        .await
+    }
+
+    pub async fn workspace_variable_updated(
+        ctx: &DalContext,
+        workspace_variable: WorkspaceVariable,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::WorkspaceVariableUpdated(WorkspaceVariableWsPayload {
+                workspace_variable,
+                change_set_id: ctx.change_set_id(),
+            }),
+        )
+        .await
+    }
+
+    pub async fn workspace_variable_deleted(
+        ctx: &DalContext,
+        workspace_variable_id: WorkspaceVariableId,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::WorkspaceVariableDeleted(WorkspaceVariableDeletedPayload {
+                workspace_variable_id,
+                change_set_id: ctx.change_set_id(),
+            }),
+        )
+        .await
+    }
+}
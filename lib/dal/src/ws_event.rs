@@ -17,6 +17,7 @@ use crate::change_set::event::{
     ChangeSetActorPayload, ChangeSetAppliedPayload, ChangeSetMergeVotePayload,
     ChangeSetRenamePayload, ChangeSetStateChangePayload,
 };
+use crate::component::drift::ResourceDriftUpdatedPayload;
 use crate::component::{
     ComponentCreatedPayload, ComponentDeletedPayload, ComponentSetPositionPayload,
     ComponentUpdatedPayload, ComponentUpgradedPayload, ConnectionDeletedPayload,
@@ -45,6 +46,7 @@ use crate::schema::variant::{
 use crate::secret::SecretDeletedPayload;
 use crate::status::StatusUpdate;
 use crate::user::OnlinePayload;
+use crate::workspace_variable::{WorkspaceVariableDeletedPayload, WorkspaceVariableWsPayload};
 use crate::{
     user::CursorPayload, ChangeSetId, DalContext, FuncError, PropId, StandardModelError,
     TransactionsError, WorkspacePk,
@@ -129,6 +131,7 @@ pub enum WsPayload {
     ModuleImported(Vec<si_frontend_types::SchemaVariant>),
     Online(OnlinePayload),
     PromptUpdated(PromptUpdatedPayload),
+    ResourceDriftUpdated(ResourceDriftUpdatedPayload),
     ResourceRefreshed(ComponentUpdatedPayload),
     SchemaVariantCloned(SchemaVariantClonedPayload),
     SchemaVariantCreated(frontend_types::SchemaVariant),
@@ -151,6 +154,9 @@ pub enum WsPayload {
     ViewUpdated(ViewWsPayload),
     WorkspaceImportBeginApprovalProcess(WorkspaceImportApprovalActorPayload),
     WorkspaceImportCancelApprovalProcess(WorkspaceActorPayload),
+    WorkspaceVariableCreated(WorkspaceVariableWsPayload),
+    WorkspaceVariableDeleted(WorkspaceVariableDeletedPayload),
+    WorkspaceVariableUpdated(WorkspaceVariableWsPayload),
 }
 
 #[remain::sorted]
@@ -1,8 +1,27 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use dal::action::concurrency_group::{
+    ActionConcurrencyGroup, ActionConcurrencyGroupError, ActionConcurrencyGroupMember,
+    ActionConcurrencyLimiter,
+};
 use dal::action::dependency_graph::ActionDependencyGraph;
+use dal::action::maintenance_window::{
+    CronSchedule, MaintenanceWindow, MaintenanceWindowDefinition, MaintenanceWindowError,
+    MaintenanceWindowGate, MaintenanceWindowOverride,
+};
+use dal::action::plan::{ActionPlan, ActionPlanHeldAction};
+use dal::action::retry_policy::{
+    ActionFailureKind, ActionRetry, ActionRetryPolicy, ActionRetryPolicyError,
+    ActionRetryPolicyTarget, MAX_DELAY_MS,
+};
+use dal::action::selection::{ActionSelection, ActionSelectionSummary};
 use dal::component::frame::Frame;
+use dal::func::timeout::{FuncTimeout, FuncTimeoutError, FuncTimeoutTarget, MAX_TIMEOUT_SECS};
+use dal::schema::variant::authoring::VariantAuthoringClient;
 use dal::{
     action::prototype::ActionKind, action::prototype::ActionPrototype, action::Action,
-    action::ActionState, AttributeValue, Component, DalContext,
+    action::ActionError, action::ActionState, AttributeValue, Component, DalContext, Func,
 };
 use dal_test::helpers::create_component_for_default_schema_name_in_default_view;
 use dal_test::helpers::create_component_for_schema_name_with_type_on_default_view;
@@ -684,3 +703,798 @@ async fn simple_transitive_action_ordering(ctx: &mut DalContext) -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+async fn action_plan(ctx: &mut DalContext) -> Result<()> {
+    // A chain of 3 components: A->B->C, each with a create action
+    let first_component = create_component_for_schema_name_with_type_on_default_view(
+        ctx,
+        "small odd lego",
+        "first component",
+        dal::ComponentType::Component,
+    )
+    .await?;
+    let second_component = create_component_for_schema_name_with_type_on_default_view(
+        ctx,
+        "small even lego",
+        "second component",
+        dal::ComponentType::Component,
+    )
+    .await?;
+    let third_component = create_component_for_schema_name_with_type_on_default_view(
+        ctx,
+        "medium odd lego",
+        "third component",
+        dal::ComponentType::Component,
+    )
+    .await?;
+    connect_components_with_socket_names(
+        ctx,
+        first_component.id(),
+        "two",
+        second_component.id(),
+        "two",
+    )
+    .await?;
+    connect_components_with_socket_names(
+        ctx,
+        second_component.id(),
+        "one",
+        third_component.id(),
+        "one",
+    )
+    .await?;
+
+    let first_action = Action::find_for_component_id(ctx, first_component.id())
+        .await?
+        .pop()
+        .expect("first component has an action");
+    let second_action = Action::find_for_component_id(ctx, second_component.id())
+        .await?
+        .pop()
+        .expect("second component has an action");
+    let third_action = Action::find_for_component_id(ctx, third_component.id())
+        .await?
+        .pop()
+        .expect("third component has an action");
+
+    let plan = ActionPlan::for_workspace(ctx).await?;
+    assert_eq!(
+        vec![vec![first_action], vec![second_action], vec![third_action]],
+        plan.waves
+    );
+    assert!(plan.is_acyclic());
+    assert!(plan.held.is_empty());
+    assert!(plan.blocked_by_cycle.is_empty());
+
+    // The dependency of the third action explains which connection created it
+    let dependency = plan
+        .dependencies
+        .iter()
+        .find(|dependency| dependency.action_id == third_action)
+        .expect("third action has a dependency");
+    assert_eq!(second_action, dependency.depends_on);
+    let reason = dependency.reason.as_ref().expect("dependency has a reason");
+    assert_eq!(
+        vec![second_component.id(), third_component.id()],
+        reason.component_path
+    );
+    assert_eq!(1, reason.socket_edges.len());
+    let socket_edge = reason.socket_edges[0];
+    assert_eq!(second_component.id(), socket_edge.from_component_id);
+    assert_eq!(third_component.id(), socket_edge.to_component_id);
+    assert!(!socket_edge.inferred);
+
+    assert!(plan.to_dot().contains("label=\"Wave 3\""));
+    assert!(plan
+        .to_mermaid()
+        .contains(&format!("a{second_action} --> a{third_action}")));
+
+    // Putting the middle action on hold holds back everything downstream of it
+    Action::set_state(ctx, second_action, ActionState::OnHold).await?;
+    let plan = ActionPlan::for_workspace(ctx).await?;
+    assert_eq!(vec![vec![first_action]], plan.waves);
+    assert_eq!(
+        vec![
+            ActionPlanHeldAction {
+                action_id: second_action,
+                held_by: vec![],
+            },
+            ActionPlanHeldAction {
+                action_id: third_action,
+                held_by: vec![second_action],
+            },
+        ],
+        plan.held
+            .into_iter()
+            .sorted_by_key(|held| held.held_by.len())
+            .collect_vec()
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn action_selection(ctx: &mut DalContext) -> Result<()> {
+    // A chain of 3 components: A->B->C, each with a create action
+    let first_component = create_component_for_schema_name_with_type_on_default_view(
+        ctx,
+        "small odd lego",
+        "first component",
+        dal::ComponentType::Component,
+    )
+    .await?;
+    let second_component = create_component_for_schema_name_with_type_on_default_view(
+        ctx,
+        "small even lego",
+        "second component",
+        dal::ComponentType::Component,
+    )
+    .await?;
+    let third_component = create_component_for_schema_name_with_type_on_default_view(
+        ctx,
+        "medium odd lego",
+        "third component",
+        dal::ComponentType::Component,
+    )
+    .await?;
+    connect_components_with_socket_names(
+        ctx,
+        first_component.id(),
+        "two",
+        second_component.id(),
+        "two",
+    )
+    .await?;
+    connect_components_with_socket_names(
+        ctx,
+        second_component.id(),
+        "one",
+        third_component.id(),
+        "one",
+    )
+    .await?;
+
+    let first_action = Action::find_for_component_id(ctx, first_component.id())
+        .await?
+        .pop()
+        .expect("first component has an action");
+    let second_action = Action::find_for_component_id(ctx, second_component.id())
+        .await?
+        .pop()
+        .expect("second component has an action");
+    let third_action = Action::find_for_component_id(ctx, third_component.id())
+        .await?
+        .pop()
+        .expect("third component has an action");
+
+    // Running an action that (transitively) depends on a held one is rejected, and nothing changes
+    let result = ActionSelection::apply(
+        ctx,
+        &HashMap::from([
+            (first_action, ActionSelection::Hold),
+            (third_action, ActionSelection::Run),
+        ]),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(ActionError::SelectedActionDependsOnHeldAction {
+            action_id,
+            held_action_id,
+        }) if action_id == third_action && held_action_id == first_action
+    ));
+    assert_eq!(
+        ActionState::Queued,
+        Action::get_by_id(ctx, first_action).await?.state()
+    );
+
+    let summary = ActionSelection::apply(
+        ctx,
+        &HashMap::from([
+            (first_action, ActionSelection::Run),
+            (second_action, ActionSelection::Hold),
+            (third_action, ActionSelection::Drop),
+        ]),
+    )
+    .await?;
+    assert_eq!(
+        ActionSelectionSummary {
+            run: vec![first_action],
+            held: vec![second_action],
+            dropped: vec![third_action],
+        },
+        summary
+    );
+    assert_eq!(
+        ActionState::Queued,
+        Action::get_by_id(ctx, first_action).await?.state()
+    );
+    assert_eq!(
+        ActionState::OnHold,
+        Action::get_by_id(ctx, second_action).await?.state()
+    );
+    assert!(Action::find_for_component_id(ctx, third_component.id())
+        .await?
+        .is_empty());
+
+    // Only actions pending in the change set can be selected
+    assert!(matches!(
+        ActionSelection::apply(
+            ctx,
+            &HashMap::from([(third_action, ActionSelection::Run)])
+        )
+        .await,
+        Err(ActionError::ActionNotPendingInChangeSet(action_id)) if action_id == third_action
+    ));
+
+    Ok(())
+}
+
+#[test]
+async fn action_retry_policy(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    let variant_id = Component::schema_variant_id(ctx, component.id()).await?;
+    let action = Action::find_for_component_id(ctx, component.id())
+        .await?
+        .pop()
+        .expect("component has an action");
+    let prototype_id = Action::prototype_id(ctx, action).await?;
+
+    assert!(ActionRetryPolicy::find_for_action(ctx, action)
+        .await?
+        .is_none());
+
+    // Invalid policies are rejected
+    assert!(matches!(
+        ActionRetryPolicy::upsert(
+            ctx,
+            ActionRetryPolicyTarget::SchemaVariant(variant_id),
+            0,
+            1000,
+            60000,
+            vec![],
+        )
+        .await,
+        Err(ActionRetryPolicyError::InvalidMaxAttempts)
+    ));
+    assert!(matches!(
+        ActionRetryPolicy::upsert(
+            ctx,
+            ActionRetryPolicyTarget::SchemaVariant(variant_id),
+            3,
+            60000,
+            1000,
+            vec![],
+        )
+        .await,
+        Err(ActionRetryPolicyError::InvalidDelay(60000, 1000))
+    ));
+    assert!(matches!(
+        ActionRetryPolicy::upsert(
+            ctx,
+            ActionRetryPolicyTarget::SchemaVariant(variant_id),
+            3,
+            1000,
+            MAX_DELAY_MS + 1,
+            vec![],
+        )
+        .await,
+        Err(ActionRetryPolicyError::DelayTooLong(delay)) if delay == MAX_DELAY_MS + 1
+    ));
+
+    // The variant policy applies to every action of the variant
+    let variant_policy = ActionRetryPolicy::upsert(
+        ctx,
+        ActionRetryPolicyTarget::SchemaVariant(variant_id),
+        3,
+        1000,
+        60000,
+        vec![],
+    )
+    .await?;
+    assert_eq!(
+        Some(variant_policy.id()),
+        ActionRetryPolicy::find_for_action(ctx, action)
+            .await?
+            .map(|policy| policy.id())
+    );
+
+    // ... unless the prototype has its own
+    let prototype_policy = ActionRetryPolicy::upsert(
+        ctx,
+        ActionRetryPolicyTarget::ActionPrototype(prototype_id),
+        2,
+        100,
+        100,
+        vec![ActionFailureKind::ResourceError],
+    )
+    .await?;
+    assert_eq!(
+        Some(prototype_policy.id()),
+        ActionRetryPolicy::find_for_action(ctx, action)
+            .await?
+            .map(|policy| policy.id())
+    );
+
+    // Upserting for the same target replaces the policy
+    let prototype_policy = ActionRetryPolicy::upsert(
+        ctx,
+        ActionRetryPolicyTarget::ActionPrototype(prototype_id),
+        4,
+        100,
+        100,
+        vec![ActionFailureKind::ResourceError],
+    )
+    .await?;
+    assert_eq!(2, ActionRetryPolicy::list(ctx).await?.len());
+    assert_eq!(4, prototype_policy.max_attempts());
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    let policy = ActionRetryPolicy::find_for_action(ctx, action)
+        .await?
+        .expect("policy survives the commit");
+    assert_eq!(prototype_policy, policy);
+
+    assert!(policy.should_retry(1, ActionFailureKind::ResourceError));
+    assert!(policy.should_retry(3, ActionFailureKind::ResourceError));
+    assert!(!policy.should_retry(4, ActionFailureKind::ResourceError));
+    assert!(!policy.should_retry(1, ActionFailureKind::UserCodeException));
+
+    // Backoff doubles up to the max delay, and jitter never takes it below half
+    for attempt in 1..=10 {
+        let delay = variant_policy.backoff(attempt).as_millis() as u64;
+        let expected = (1000u64 << (attempt - 1)).min(60000);
+        assert!(
+            delay >= expected / 2 && delay <= expected,
+            "attempt {attempt}: {delay}ms not within {expected}ms"
+        );
+    }
+
+    ActionRetryPolicy::remove(ctx, policy.id()).await?;
+    assert_eq!(
+        Some(variant_policy.id()),
+        ActionRetryPolicy::find_for_action(ctx, action)
+            .await?
+            .map(|policy| policy.id())
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn action_retry(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    let action = Action::find_for_component_id(ctx, component.id())
+        .await?
+        .pop()
+        .expect("component has an action");
+    Action::set_state(ctx, action, ActionState::Failed).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(None, ActionRetry::attempts_for(ctx, action).await?);
+
+    // A retry that isn't due yet is not claimed
+    ActionRetry::schedule(ctx, action, 1, Duration::from_secs(3600)).await?;
+    assert_eq!(Some(1), ActionRetry::attempts_for(ctx, action).await?);
+    assert!(!ActionRetry::claim_due(ctx)
+        .await?
+        .iter()
+        .any(|retry| retry.action_id() == action));
+
+    // Once it is due, it is claimed exactly once, and remembers the attempts made so far
+    ActionRetry::schedule(ctx, action, 2, Duration::ZERO).await?;
+    let retry = ActionRetry::claim_due(ctx)
+        .await?
+        .into_iter()
+        .find(|retry| retry.action_id() == action)
+        .expect("due retry is claimed");
+    assert_eq!(2, retry.attempts());
+    assert_eq!(None, retry.next_attempt_at());
+    assert!(!ActionRetry::claim_due(ctx)
+        .await?
+        .iter()
+        .any(|retry| retry.action_id() == action));
+    assert_eq!(Some(2), ActionRetry::attempts_for(ctx, action).await?);
+
+    // Requeueing puts the failed action back in the queue, but only once
+    assert!(retry.requeue(ctx).await?);
+    assert_eq!(
+        ActionState::Queued,
+        Action::get_by_id(ctx, action).await?.state()
+    );
+    assert!(!retry.requeue(ctx).await?);
+
+    ActionRetry::delete(ctx, action).await?;
+    assert_eq!(None, ActionRetry::attempts_for(ctx, action).await?);
+
+    Ok(())
+}
+
+#[test]
+async fn func_timeout(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    let action = Action::find_for_component_id(ctx, component.id())
+        .await?
+        .pop()
+        .expect("component has an action");
+    let prototype_id = Action::prototype_id(ctx, action).await?;
+    let func_id = ActionPrototype::func_id(ctx, prototype_id).await?;
+
+    assert!(
+        FuncTimeout::timeout_secs_for_action_prototype(ctx, prototype_id)
+            .await?
+            .is_none()
+    );
+
+    // Timeouts must be positive and no longer than cyclone allows
+    assert!(matches!(
+        FuncTimeout::upsert(ctx, FuncTimeoutTarget::Func(func_id), 0).await,
+        Err(FuncTimeoutError::InvalidTimeout(0))
+    ));
+    assert!(matches!(
+        FuncTimeout::upsert(ctx, FuncTimeoutTarget::Func(func_id), MAX_TIMEOUT_SECS + 1).await,
+        Err(FuncTimeoutError::InvalidTimeout(_))
+    ));
+
+    // The timeout for the func applies to the prototype until it has one of its own
+    let func_timeout = FuncTimeout::upsert(ctx, FuncTimeoutTarget::Func(func_id), 120).await?;
+    assert_eq!(
+        Some(120),
+        FuncTimeout::timeout_secs_for_action_prototype(ctx, prototype_id).await?
+    );
+    assert_eq!(
+        Some(120),
+        FuncTimeout::timeout_secs_for_func(ctx, func_id).await?
+    );
+
+    FuncTimeout::upsert(ctx, FuncTimeoutTarget::ActionPrototype(prototype_id), 30).await?;
+    let prototype_timeout =
+        FuncTimeout::upsert(ctx, FuncTimeoutTarget::ActionPrototype(prototype_id), 45).await?;
+    assert_eq!(2, FuncTimeout::list(ctx).await?.len());
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(
+        Some(45),
+        FuncTimeout::timeout_secs_for_action_prototype(ctx, prototype_id).await?
+    );
+    assert_eq!(
+        Some(prototype_timeout.clone()),
+        FuncTimeout::find_for_target(ctx, FuncTimeoutTarget::ActionPrototype(prototype_id)).await?
+    );
+
+    FuncTimeout::remove(ctx, prototype_timeout.id()).await?;
+    assert_eq!(
+        Some(func_timeout.timeout_secs()),
+        FuncTimeout::timeout_secs_for_action_prototype(ctx, prototype_id).await?
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn func_timeout_follows_its_target(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    let action = Action::find_for_component_id(ctx, component.id())
+        .await?
+        .pop()
+        .expect("component has an action");
+    let prototype_id = Action::prototype_id(ctx, action).await?;
+    let kind = ActionPrototype::get_by_id(ctx, prototype_id).await?.kind;
+    let func_id = ActionPrototype::func_id(ctx, prototype_id).await?;
+    let schema_variant_id = ActionPrototype::schema_variant_id(ctx, prototype_id).await?;
+
+    FuncTimeout::upsert(ctx, FuncTimeoutTarget::Func(func_id), 120).await?;
+    FuncTimeout::upsert(ctx, FuncTimeoutTarget::ActionPrototype(prototype_id), 45).await?;
+
+    // Unlocking the variant recreates its action prototypes, and their timeouts come along
+    let unlocked_variant =
+        VariantAuthoringClient::create_unlocked_variant_copy(ctx, schema_variant_id).await?;
+    let unlocked_prototype = ActionPrototype::for_variant(ctx, unlocked_variant.id())
+        .await?
+        .into_iter()
+        .find(|prototype| prototype.kind == kind)
+        .expect("unlocked variant has the action");
+    assert_eq!(
+        Some(45),
+        FuncTimeout::timeout_secs_for_action_prototype(ctx, unlocked_prototype.id()).await?
+    );
+
+    // So does regenerating it
+    VariantAuthoringClient::regenerate_variant(ctx, unlocked_variant.id()).await?;
+    let regenerated_prototype = ActionPrototype::for_variant(ctx, unlocked_variant.id())
+        .await?
+        .into_iter()
+        .find(|prototype| prototype.kind == kind)
+        .expect("regenerated variant has the action");
+    assert_ne!(unlocked_prototype.id(), regenerated_prototype.id());
+    assert_eq!(
+        Some(45),
+        FuncTimeout::timeout_secs_for_action_prototype(ctx, regenerated_prototype.id()).await?
+    );
+
+    // Unlocked copies of the func keep its timeout
+    let unlocked_func = Func::get_by_id(ctx, func_id)
+        .await?
+        .create_unlocked_func_copy(ctx)
+        .await?;
+    assert_eq!(
+        Some(120),
+        FuncTimeout::timeout_secs_for_func(ctx, unlocked_func.id).await?
+    );
+
+    // Timeouts go away along with their targets
+    ActionPrototype::remove(ctx, regenerated_prototype.id()).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    let targets = FuncTimeout::list(ctx)
+        .await?
+        .into_iter()
+        .map(|timeout| timeout.target())
+        .collect_vec();
+    assert_eq!(
+        vec![
+            FuncTimeoutTarget::Func(func_id),
+            FuncTimeoutTarget::ActionPrototype(prototype_id),
+            FuncTimeoutTarget::Func(unlocked_func.id),
+        ],
+        targets
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn action_concurrency_group(ctx: &mut DalContext) -> Result<()> {
+    let first_component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "fearless").await?;
+    let second_component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "red").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let variant_id = Component::schema_variant_id(ctx, first_component.id()).await?;
+    let schema_id = Component::schema_for_component_id(ctx, first_component.id())
+        .await?
+        .id();
+    let first_action = Action::find_for_component_id(ctx, first_component.id())
+        .await?
+        .pop()
+        .expect("first component has an action");
+    let second_action = Action::find_for_component_id(ctx, second_component.id())
+        .await?
+        .pop()
+        .expect("second component has an action");
+    let prototype_id = Action::prototype_id(ctx, first_action).await?;
+
+    let mut eligible = Action::eligible_to_dispatch(ctx).await?;
+    eligible.sort();
+    let mut both = vec![first_action, second_action];
+    both.sort();
+    assert_eq!(both, eligible);
+
+    assert!(matches!(
+        ActionConcurrencyGroup::new(ctx, "swifty".into(), 0, vec![]).await,
+        Err(ActionConcurrencyGroupError::InvalidLimit)
+    ));
+
+    // Only one swifty action at a time
+    let mut schema_group = ActionConcurrencyGroup::new(
+        ctx,
+        "swifty".into(),
+        1,
+        vec![ActionConcurrencyGroupMember::Schema { schema_id }],
+    )
+    .await?;
+    assert!(matches!(
+        ActionConcurrencyGroup::new(ctx, "swifty".into(), 1, vec![]).await,
+        Err(ActionConcurrencyGroupError::KeyTaken(_))
+    ));
+    let eligible = Action::eligible_to_dispatch(ctx).await?;
+    assert_eq!(1, eligible.len());
+
+    // Once one is in flight, the other stays queued
+    let dispatched = eligible[0];
+    Action::set_state(ctx, dispatched, ActionState::Dispatched).await?;
+    assert!(Action::eligible_to_dispatch(ctx).await?.is_empty());
+    let waiting = if dispatched == first_action {
+        second_action
+    } else {
+        first_action
+    };
+    assert_eq!(
+        ActionState::Queued,
+        Action::get_by_id(ctx, waiting).await?.state()
+    );
+
+    // An action that was dispatched anyway has to wait for the one dispatched before it, even
+    // though that one isn't running yet
+    let mut concurrency_limiter = ActionConcurrencyLimiter::new(
+        ctx,
+        &[ActionState::Dispatched, ActionState::Running],
+        Some(waiting),
+    )
+    .await?;
+    assert!(!concurrency_limiter.try_acquire(ctx, waiting).await?);
+
+    schema_group
+        .update(
+            ctx,
+            2,
+            vec![ActionConcurrencyGroupMember::Schema { schema_id }],
+        )
+        .await?;
+    assert_eq!(vec![waiting], Action::eligible_to_dispatch(ctx).await?);
+
+    // A group for the prototype wins over the group for the schema, and the category is the
+    // least specific
+    schema_group
+        .update(
+            ctx,
+            1,
+            vec![ActionConcurrencyGroupMember::Schema { schema_id }],
+        )
+        .await?;
+    let category = dal::SchemaVariant::get_by_id(ctx, variant_id)
+        .await?
+        .category()
+        .to_owned();
+    ActionConcurrencyGroup::new(
+        ctx,
+        "by category".into(),
+        5,
+        vec![ActionConcurrencyGroupMember::Category { category }],
+    )
+    .await?;
+    assert!(Action::eligible_to_dispatch(ctx).await?.is_empty());
+    ActionConcurrencyGroup::new(
+        ctx,
+        "swifty create".into(),
+        2,
+        vec![ActionConcurrencyGroupMember::ActionPrototype {
+            action_prototype_id: prototype_id,
+        }],
+    )
+    .await?;
+    assert_eq!(vec![waiting], Action::eligible_to_dispatch(ctx).await?);
+
+    ActionConcurrencyGroup::delete(ctx, schema_group.pk()).await?;
+    assert_eq!(2, ActionConcurrencyGroup::list(ctx).await?.len());
+
+    Ok(())
+}
+
+#[test]
+async fn maintenance_window_holds_actions_on_head(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "midnights")
+            .await?;
+    let schema_id = Component::schema_for_component_id(ctx, component.id())
+        .await?
+        .id();
+    let definition = |schedule: &str| MaintenanceWindowDefinition {
+        name: "leap day".into(),
+        schedule: schedule.into(),
+        timezone: "Europe/Lisbon".into(),
+        duration_minutes: 1,
+        schema_id: Some(schema_id),
+        label: None,
+        enabled: true,
+    };
+
+    assert!(matches!(
+        MaintenanceWindow::new(ctx, definition("0 0 30 2")).await,
+        Err(MaintenanceWindowError::InvalidSchedule(_, _))
+    ));
+    assert!(matches!(
+        MaintenanceWindow::new(
+            ctx,
+            MaintenanceWindowDefinition {
+                timezone: "Mars/Olympus_Mons".into(),
+                ..definition("0 0 29 2 *")
+            }
+        )
+        .await,
+        Err(MaintenanceWindowError::InvalidTimezone(_))
+    ));
+
+    // Only open for the first minute of leap days, so closed while the test runs
+    let mut window = MaintenanceWindow::new(ctx, definition("0 0 29 2 *")).await?;
+    assert!(!window.status(ctx).await?.is_open());
+
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    let action_id = Action::find_for_component_id(ctx, component.id())
+        .await?
+        .pop()
+        .expect("component has an action");
+    assert_eq!(
+        ActionState::Queued,
+        Action::get_by_id(ctx, action_id).await?.state()
+    );
+    assert!(!Action::eligible_to_dispatch(ctx)
+        .await?
+        .contains(&action_id));
+    let hold = MaintenanceWindowGate::new(ctx)
+        .await?
+        .hold_for(ctx, action_id)
+        .await?
+        .expect("action is held for the window");
+    assert_eq!(vec![window.pk()], hold.window_ids);
+    assert!(hold.next_open_at.is_some());
+
+    // An override lets it go while the window is closed
+    MaintenanceWindowOverride::new(ctx, action_id).await?;
+    assert!(Action::eligible_to_dispatch(ctx)
+        .await?
+        .contains(&action_id));
+    MaintenanceWindowOverride::delete(ctx, action_id).await?;
+    assert!(!Action::eligible_to_dispatch(ctx)
+        .await?
+        .contains(&action_id));
+
+    // The override is used up by dispatching the action, so a second dispatch is held again
+    MaintenanceWindowOverride::new(ctx, action_id).await?;
+    assert!(Action::eligible_to_dispatch(ctx)
+        .await?
+        .contains(&action_id));
+    Action::dispatch_action(ctx, action_id).await?;
+    assert!(MaintenanceWindowOverride::list_action_ids(ctx)
+        .await?
+        .is_empty());
+    Action::set_state(ctx, action_id, ActionState::Queued).await?;
+    assert!(!Action::eligible_to_dispatch(ctx)
+        .await?
+        .contains(&action_id));
+
+    // As does opening the window
+    window.update(ctx, definition("* * * * *")).await?;
+    assert!(window.status(ctx).await?.is_open());
+    assert!(Action::eligible_to_dispatch(ctx)
+        .await?
+        .contains(&action_id));
+
+    Ok(())
+}
+
+#[test]
+async fn maintenance_window_cron_schedule(_ctx: &DalContext) -> Result<()> {
+    let at = |date: &str| {
+        chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").expect("valid date")
+    };
+
+    // Saturdays at 02:00, or the first of the month
+    let schedule: CronSchedule = "0 2 1 * 6".parse()?;
+    assert!(schedule.matches(at("2026-10-17 02:00")));
+    assert!(schedule.matches(at("2026-10-01 02:00")));
+    assert!(!schedule.matches(at("2026-10-18 02:00")));
+    assert_eq!(
+        Some(at("2026-10-24 02:00")),
+        schedule.next_match_after(at("2026-10-19 12:00"))
+    );
+    assert_eq!(
+        Some(at("2026-10-17 02:00")),
+        schedule.last_match_within(at("2026-10-17 03:59"), 120)
+    );
+    assert_eq!(
+        None,
+        schedule.last_match_within(at("2026-10-17 04:00"), 120)
+    );
+
+    // Every 15 minutes during working hours on weekdays, with Sunday as 7
+    let schedule: CronSchedule = "*/15 9-17 * * 1-5,7".parse()?;
+    assert!(schedule.matches(at("2026-10-19 09:45")));
+    assert!(schedule.matches(at("2026-10-18 17:00")));
+    assert!(!schedule.matches(at("2026-10-17 09:45")));
+    assert!(!schedule.matches(at("2026-10-19 09:50")));
+
+    assert!("0 2 * *".parse::<CronSchedule>().is_err());
+    assert!("60 2 * * *".parse::<CronSchedule>().is_err());
+    assert!("*/0 2 * * *".parse::<CronSchedule>().is_err());
+
+    Ok(())
+}
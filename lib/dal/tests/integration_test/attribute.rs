@@ -1,2 +1,3 @@
 mod prototype;
+mod subscription;
 mod value;
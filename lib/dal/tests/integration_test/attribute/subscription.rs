@@ -0,0 +1,239 @@
+use dal::attribute::prototype::argument::subscription::{AttributeSubscription, ComponentFilter};
+use dal::func::argument::FuncArgument;
+use dal::func::intrinsics::IntrinsicFunc;
+use dal::{
+    AttributePrototype, AttributePrototypeArgument, AttributeValue, ComponentType, DalContext, Func,
+};
+use dal_test::expected::{ExpectComponent, ExpectComponentProp, ExpectSchema};
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::{test, Result};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+/// Subscribes "/root/domain/parrot_names" on the pet shop to the name of every component matching
+/// the filters.
+async fn subscribe_parrot_names(
+    ctx: &mut DalContext,
+    pet_shop: ExpectComponent,
+    filters: Vec<ComponentFilter>,
+) -> Result<ExpectComponentProp> {
+    let identity_func_id = Func::find_intrinsic(ctx, IntrinsicFunc::Identity).await?;
+    let identity_arg = FuncArgument::find_by_name_for_func(ctx, "identity", identity_func_id)
+        .await?
+        .expect("identity func argument exists");
+    let prototype = AttributePrototype::new(ctx, identity_func_id).await?;
+    AttributePrototypeArgument::new(ctx, prototype.id(), identity_arg.id)
+        .await?
+        .set_value_from_subscription(
+            ctx,
+            filters,
+            vec!["root".to_string(), "si".to_string(), "name".to_string()],
+        )
+        .await?;
+    let parrot_names = pet_shop.prop(ctx, ["root", "domain", "parrot_names"]).await;
+    let parrot_names_av_id = parrot_names.attribute_value(ctx).await.id();
+    AttributeValue::set_component_prototype_id(ctx, parrot_names_av_id, prototype.id(), None)
+        .await?;
+    ctx.add_dependent_values_and_enqueue(vec![parrot_names_av_id])
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    Ok(parrot_names)
+}
+
+#[test]
+async fn subscription_collects_values_from_matching_components(ctx: &mut DalContext) -> Result<()> {
+    let pirate_schema = ExpectSchema::find(ctx, "pirate").await;
+    let pet_shop = ExpectComponent::create_named(ctx, "pet_shop", "Petopia").await;
+    let first_pirate = ExpectComponent::create_named(ctx, "pirate", "Long John Silver").await;
+
+    // Subscribe to the name of every pirate in the workspace.
+    let parrot_names = subscribe_parrot_names(
+        ctx,
+        pet_shop,
+        vec![ComponentFilter::Schema(pirate_schema.id())],
+    )
+    .await?;
+
+    assert_eq!(json!(["Long John Silver"]), parrot_names.get(ctx).await);
+
+    // A new matching component is picked up without any connection being made.
+    let second_pirate = ExpectComponent::create_named(ctx, "pirate", "Anne Bonny").await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(
+        json!(["Long John Silver", "Anne Bonny"]),
+        parrot_names.get(ctx).await
+    );
+
+    // Changing the selected value on a matching component flows through as well.
+    first_pirate
+        .prop(ctx, ["root", "si", "name"])
+        .await
+        .set(ctx, "Captain Flint")
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let mut names: Vec<String> = serde_json::from_value(parrot_names.get(ctx).await)?;
+    names.sort();
+    assert_eq!(vec!["Anne Bonny", "Captain Flint"], names);
+
+    // Components slated for deletion stop contributing.
+    second_pirate
+        .component(ctx)
+        .await
+        .set_to_delete(ctx, true)
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(json!(["Captain Flint"]), parrot_names.get(ctx).await);
+
+    Ok(())
+}
+
+#[test]
+async fn subscription_filters_by_frame(ctx: &mut DalContext) -> Result<()> {
+    let pirate_schema = ExpectSchema::find(ctx, "pirate").await;
+    let pet_shop = ExpectComponent::create_named(ctx, "pet_shop", "Petopia").await;
+    let ship = ExpectComponent::create_named(ctx, "small odd lego", "Hispaniola").await;
+    ship.set_type(ctx, ComponentType::ConfigurationFrameDown)
+        .await;
+    let crew = ExpectComponent::create_named(ctx, "pirate", "Israel Hands").await;
+    crew.upsert_parent(ctx, ship.id()).await;
+    let castaway = ExpectComponent::create_named(ctx, "pirate", "Ben Gunn").await;
+
+    // Subscribe to the name of every pirate aboard the ship.
+    let parrot_names = subscribe_parrot_names(
+        ctx,
+        pet_shop,
+        vec![
+            ComponentFilter::Schema(pirate_schema.id()),
+            ComponentFilter::DescendantOf(ship.id()),
+        ],
+    )
+    .await?;
+
+    assert_eq!(json!(["Israel Hands"]), parrot_names.get(ctx).await);
+
+    // Coming aboard changes what matches, even though no value changed.
+    castaway.upsert_parent(ctx, ship.id()).await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(
+        json!(["Israel Hands", "Ben Gunn"]),
+        parrot_names.get(ctx).await
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn subscription_filters_by_prop_value(ctx: &mut DalContext) -> Result<()> {
+    let pirate_schema = ExpectSchema::find(ctx, "pirate").await;
+    let pet_shop = ExpectComponent::create_named(ctx, "pet_shop", "Petopia").await;
+    let first_pirate = ExpectComponent::create_named(ctx, "pirate", "Long John Silver").await;
+    first_pirate
+        .prop(ctx, ["root", "domain", "working_eyes"])
+        .await
+        .set(ctx, 2)
+        .await;
+    let second_pirate = ExpectComponent::create_named(ctx, "pirate", "Blind Pew").await;
+    second_pirate
+        .prop(ctx, ["root", "domain", "working_eyes"])
+        .await
+        .set(ctx, 0)
+        .await;
+
+    // Subscribe to the name of every pirate that can see.
+    let parrot_names = subscribe_parrot_names(
+        ctx,
+        pet_shop,
+        vec![
+            ComponentFilter::Schema(pirate_schema.id()),
+            ComponentFilter::PropValue {
+                path: vec![
+                    "root".to_string(),
+                    "domain".to_string(),
+                    "working_eyes".to_string(),
+                ],
+                value: json!(2),
+            },
+        ],
+    )
+    .await?;
+
+    assert_eq!(json!(["Long John Silver"]), parrot_names.get(ctx).await);
+
+    // Changing the value a filter reads changes what matches.
+    second_pirate
+        .prop(ctx, ["root", "domain", "working_eyes"])
+        .await
+        .set(ctx, 2)
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(
+        json!(["Long John Silver", "Blind Pew"]),
+        parrot_names.get(ctx).await
+    );
+
+    first_pirate
+        .prop(ctx, ["root", "domain", "working_eyes"])
+        .await
+        .set(ctx, 1)
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(json!(["Blind Pew"]), parrot_names.get(ctx).await);
+
+    Ok(())
+}
+
+#[test]
+async fn only_subscriptions_that_could_match_are_affected_by_a_component(
+    ctx: &mut DalContext,
+) -> Result<()> {
+    let pirate_schema = ExpectSchema::find(ctx, "pirate").await;
+    let pet_shop = ExpectComponent::create_named(ctx, "pet_shop", "Petopia").await;
+    let ship = ExpectComponent::create_named(ctx, "small odd lego", "Hispaniola").await;
+    ship.set_type(ctx, ComponentType::ConfigurationFrameDown)
+        .await;
+    let dinghy = ExpectComponent::create_named(ctx, "small odd lego", "Jolly Boat").await;
+    let pirate = ExpectComponent::create_named(ctx, "pirate", "Israel Hands").await;
+    pirate.upsert_parent(ctx, ship.id()).await;
+
+    subscribe_parrot_names(
+        ctx,
+        pet_shop,
+        vec![
+            ComponentFilter::Schema(pirate_schema.id()),
+            ComponentFilter::DescendantOf(ship.id()),
+        ],
+    )
+    .await?;
+    let subscription_ids: Vec<_> = AttributeSubscription::list(ctx)
+        .await?
+        .into_iter()
+        .map(|subscription| subscription.id())
+        .collect();
+    assert_eq!(1, subscription_ids.len());
+
+    // The frame filter could match anywhere the pirate is moved to, so only the schema decides.
+    assert_eq!(
+        subscription_ids,
+        AttributeSubscription::list_ids_affected_by_component(ctx, pirate.id()).await?
+    );
+    assert!(
+        AttributeSubscription::list_ids_affected_by_component(ctx, dinghy.id())
+            .await?
+            .is_empty()
+    );
+
+    // Moving the ship moves the pirate aboard it.
+    assert_eq!(
+        subscription_ids,
+        AttributeSubscription::list_ids_affected_by_component(ctx, ship.id()).await?
+    );
+
+    Ok(())
+}
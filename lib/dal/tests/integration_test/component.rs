@@ -18,6 +18,7 @@ use serde_json::json;
 mod autoconnect;
 mod debug;
 mod delete;
+mod drift;
 mod get_code;
 mod get_diff;
 mod paste;
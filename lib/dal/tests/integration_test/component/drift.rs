@@ -0,0 +1,185 @@
+use dal::action::prototype::{ActionKind, ActionPrototype};
+use dal::action::refresh_schedule::{RefreshSchedule, RefreshScheduleError};
+use dal::action::Action;
+use dal::component::drift::{DriftedValue, ResourceDrift};
+use dal::component::resource::ResourceData;
+use dal::{Component, DalContext};
+use dal_test::helpers::{
+    create_component_for_default_schema_name_in_default_view, ChangeSetTestHelpers,
+};
+use dal_test::{test, Result};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use veritech_client::ResourceStatus;
+
+#[test]
+async fn compare(_ctx: &mut DalContext) -> Result<()> {
+    let modeled = json!({
+        "name": "fearless",
+        "size": 3,
+        "region": null,
+        "tags": [{ "key": "owner", "value": "taylor" }],
+        "ports": [80, 443],
+    });
+
+    // Values missing on either side, unset values and arrays that changed length are ignored
+    let actual = json!({
+        "name": "fearless",
+        "size": 3,
+        "region": "us-east-1",
+        "tags": [{ "key": "owner", "value": "taylor" }],
+        "ports": [80],
+        "arn": "arn:aws:swifty:fearless",
+    });
+    assert!(ResourceDrift::compare(&modeled, &actual).is_empty());
+
+    let actual = json!({
+        "name": "fearless",
+        "size": 5,
+        "tags": [{ "key": "owner", "value": "travis" }],
+        "ports": [80, 8443],
+    });
+    assert_eq!(
+        vec![
+            DriftedValue {
+                path: "/ports/1".to_string(),
+                modeled: json!(443),
+                actual: json!(8443),
+            },
+            DriftedValue {
+                path: "/size".to_string(),
+                modeled: json!(3),
+                actual: json!(5),
+            },
+            DriftedValue {
+                path: "/tags/0/value".to_string(),
+                modeled: json!("taylor"),
+                actual: json!("travis"),
+            },
+        ],
+        ResourceDrift::compare(&modeled, &actual)
+    );
+
+    assert_eq!(
+        vec![DriftedValue {
+            path: "".to_string(),
+            modeled: modeled.clone(),
+            actual: json!("fearless"),
+        }],
+        ResourceDrift::compare(&modeled, &json!("fearless"))
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn detect(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "fearless").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let domain = component.view(ctx).await?.expect("component has a view")["domain"].clone();
+    let mut actual = domain.clone();
+    actual["name"] = json!("red");
+
+    let drift = ResourceDrift::detect(ctx, component.id(), &actual, None)
+        .await?
+        .expect("drift detected");
+    assert_eq!(
+        vec![DriftedValue {
+            path: "/name".to_string(),
+            modeled: json!("fearless"),
+            actual: json!("red"),
+        }],
+        drift.drifted_values
+    );
+    assert_eq!(
+        Some(drift),
+        ResourceDrift::get_for_component(ctx, component.id()).await?
+    );
+    assert_eq!(1, ResourceDrift::list(ctx).await?.len());
+
+    // Once the resource matches the model again, the drift is cleared
+    assert!(ResourceDrift::detect(ctx, component.id(), &domain, None)
+        .await?
+        .is_none());
+    assert!(ResourceDrift::get_for_component(ctx, component.id())
+        .await?
+        .is_none());
+    assert!(ResourceDrift::list(ctx).await?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+async fn refresh_schedule(ctx: &mut DalContext) -> Result<()> {
+    let with_resource =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "fearless").await?;
+    let without_resource =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "red").await?;
+    with_resource
+        .set_resource(
+            ctx,
+            ResourceData::new(ResourceStatus::Ok, Some(json!({ "name": "fearless" }))),
+        )
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    let schema_id = Component::schema_for_component_id(ctx, with_resource.id())
+        .await?
+        .id();
+
+    assert!(matches!(
+        RefreshSchedule::new(ctx, None, 60, true).await,
+        Err(RefreshScheduleError::IntervalTooShort(60))
+    ));
+    let mut workspace_schedule = RefreshSchedule::new(ctx, None, 3600, true).await?;
+    assert!(matches!(
+        RefreshSchedule::new(ctx, None, 600, true).await,
+        Err(RefreshScheduleError::ScheduleExists(None))
+    ));
+    workspace_schedule.update(ctx, 1800, true).await?;
+    assert_eq!(1800, workspace_schedule.interval_seconds());
+    assert_eq!(
+        workspace_schedule,
+        RefreshSchedule::get_by_pk(ctx, workspace_schedule.pk()).await?
+    );
+
+    // Only components with a resource are refreshed, and only once
+    assert_eq!(1, workspace_schedule.enqueue_refreshes(ctx).await?);
+    let refresh_actions = refresh_action_count(ctx, &with_resource).await?;
+    assert_eq!(1, refresh_actions);
+    assert_eq!(0, refresh_action_count(ctx, &without_resource).await?);
+    assert_eq!(0, workspace_schedule.enqueue_refreshes(ctx).await?);
+
+    // A schema schedule overrides the workspace schedule for its components
+    let schema_schedule = RefreshSchedule::new(ctx, Some(schema_id), 7200, true).await?;
+    assert_eq!(
+        vec![workspace_schedule.clone(), schema_schedule.clone()],
+        RefreshSchedule::list(ctx).await?
+    );
+    for action_id in Action::find_for_component_id(ctx, with_resource.id()).await? {
+        Action::remove_by_id(ctx, action_id).await?;
+    }
+    assert_eq!(0, workspace_schedule.enqueue_refreshes(ctx).await?);
+    assert_eq!(1, schema_schedule.enqueue_refreshes(ctx).await?);
+
+    RefreshSchedule::delete(ctx, schema_schedule.pk()).await?;
+    assert!(matches!(
+        RefreshSchedule::get_by_pk(ctx, schema_schedule.pk()).await,
+        Err(RefreshScheduleError::NotFound(_))
+    ));
+    assert_eq!(vec![workspace_schedule], RefreshSchedule::list(ctx).await?);
+
+    Ok(())
+}
+
+async fn refresh_action_count(ctx: &DalContext, component: &Component) -> Result<usize> {
+    let mut count = 0;
+    for action_id in Action::find_for_component_id(ctx, component.id()).await? {
+        let prototype_id = Action::prototype_id(ctx, action_id).await?;
+        if ActionPrototype::get_by_id(ctx, prototype_id).await?.kind == ActionKind::Refresh {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
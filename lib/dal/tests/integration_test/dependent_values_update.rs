@@ -1,12 +1,15 @@
 use dal::component::resource::ResourceData;
+use dal::func::authoring::FuncAuthoringClient;
+use dal::func::binding::AttributeFuncDestination;
 use dal::{
-    AttributeValue, Component, DalContext, InputSocket, OutputSocket, Schema, SchemaVariant,
+    AttributeValue, AttributeValueId, Component, DalContext, InputSocket, OutputSocket, Schema,
+    SchemaVariant,
 };
-use dal_test::expected::{self, ExpectComponent};
+use dal_test::expected::{self, ExpectComponent, ExpectSchema};
 use dal_test::helpers::{
     create_named_component_for_schema_variant_on_default_view, ChangeSetTestHelpers,
 };
-use dal_test::test;
+use dal_test::{test, Result};
 use serde_json::json;
 use veritech_client::ResourceStatus;
 
@@ -515,3 +518,84 @@ async fn component_concurrency_limit(ctx: &mut DalContext) {
         )
     }
 }
+
+#[test]
+async fn unchanged_inputs_skip_execution(ctx: &mut DalContext) -> Result<()> {
+    let variant = ExpectSchema::find(ctx, "swifty")
+        .await
+        .default_variant(ctx)
+        .await
+        .create_unlocked_copy(ctx)
+        .await;
+    let domain_name_prop_id = variant.prop(ctx, ["root", "domain", "name"]).await.id();
+
+    let func = FuncAuthoringClient::create_new_attribute_func(
+        ctx,
+        Some("constant name".to_string()),
+        None,
+        AttributeFuncDestination::Prop(domain_name_prop_id),
+        vec![],
+    )
+    .await?;
+    FuncAuthoringClient::save_code(
+        ctx,
+        func.id,
+        "async function main(): Promise<Output> {\n  return \"taylor\";\n}\n".to_string(),
+    )
+    .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let component = variant
+        .create_named_component_on_default_view(ctx, "taylor")
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let name = component.prop(ctx, ["root", "domain", "name"]).await;
+    let name_av_id = name.attribute_value(ctx).await.id();
+    assert_eq!(json!("taylor"), name.get(ctx).await);
+    let runs = func_run_count(ctx, name_av_id).await?;
+    assert_eq!(1, runs);
+
+    // Nothing feeding the func changed, so it is not run again
+    ctx.add_dependent_values_and_enqueue(vec![name_av_id])
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(json!("taylor"), name.get(ctx).await);
+    assert_eq!(runs, func_run_count(ctx, name_av_id).await?);
+
+    // Changing the code does
+    FuncAuthoringClient::save_code(
+        ctx,
+        func.id,
+        "async function main(): Promise<Output> {\n  return \"swift\";\n}\n".to_string(),
+    )
+    .await?;
+    ctx.add_dependent_values_and_enqueue(vec![name_av_id])
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(json!("swift"), name.get(ctx).await);
+    assert_eq!(runs + 1, func_run_count(ctx, name_av_id).await?);
+
+    // Opting out re-executes the func regardless
+    let runs = runs + 1;
+    FuncAuthoringClient::set_always_execute(ctx, func.id, true).await?;
+    ctx.add_dependent_values_and_enqueue(vec![name_av_id])
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(json!("swift"), name.get(ctx).await);
+    assert_eq!(runs + 1, func_run_count(ctx, name_av_id).await?);
+
+    Ok(())
+}
+
+async fn func_run_count(ctx: &DalContext, attribute_value_id: AttributeValueId) -> Result<usize> {
+    Ok(ctx
+        .layer_db()
+        .func_run()
+        .read_many_for_workspace(ctx.workspace_pk()?)
+        .await?
+        .unwrap_or_default()
+        .iter()
+        .filter(|func_run| func_run.attribute_value_id() == Some(attribute_value_id))
+        .count())
+}
@@ -3,19 +3,25 @@ use std::io::Read;
 
 use chrono::{DateTime, TimeZone, Utc};
 use dal::action::prototype::ActionKind;
+use dal::action::retry_policy::{ActionFailureKind, ActionRetryPolicyTarget};
 use dal::action::ActionCompletionStatus;
 use dal::approval_requirement::ApprovalRequirementApprover;
 use dal::func::argument::FuncArgumentKind;
+use dal::func::test_case::FuncTestCaseExpectation;
+use dal::func::timeout::FuncTimeoutTarget;
 use dal::func::FuncKind;
 use dal::layer_db_types::{
-    ApprovalRequirementDefinitionContent, AttributePrototypeContent, AttributePrototypeContentV1,
-    ComponentContent, ContentTypes, ContentTypesDiscriminants, DeprecatedActionBatchContent,
-    DeprecatedActionContent, DeprecatedActionPrototypeContent, DeprecatedActionRunnerContent,
+    ActionRetryPolicyContent, ApprovalRequirementDefinitionContent, AttributePrototypeContent,
+    AttributePrototypeContentV1, AttributeSubscriptionContent, ComponentContent,
+    ComponentFilterContentV1, ComputedValueContent, ComputedValueContentV1, ContentTypes,
+    ContentTypesDiscriminants, DeprecatedActionBatchContent, DeprecatedActionContent,
+    DeprecatedActionPrototypeContent, DeprecatedActionRunnerContent,
     DeprecatedActionRunnerContentV1, FuncArgumentContent, FuncArgumentContentV1, FuncContent,
-    FuncContentV2, GeometryContent, InputSocketContent, InputSocketContentV2,
-    ManagementPrototypeContent, ManagementPrototypeContentV1, ModuleContent, OutputSocketContent,
-    PropContent, PropContentV1, SchemaContent, SchemaVariantContent, SecretContent,
-    StaticArgumentValueContent, ValidationContent, ViewContent,
+    FuncContentV2, FuncTestCaseContent, FuncTimeoutContent, GeometryContent, InputSocketContent,
+    InputSocketContentV2, ManagementPrototypeContent, ManagementPrototypeContentV1, ModuleContent,
+    OutputSocketContent, PropContent, PropContentV1, SchemaContent, SchemaVariantContent,
+    SecretContent, StaticArgumentValueContent, ValidationContent, ViewContent,
+    WorkspaceVariableContent,
 };
 use dal::prop::WidgetOption;
 use dal::socket::connection_annotation::ConnectionAnnotation;
@@ -30,7 +36,7 @@ use dal::workspace_snapshot::node_weight::{
 use dal::{
     ComponentType, ContentHash, DalContext, EdgeWeight, EdgeWeightKind,
     EdgeWeightKindDiscriminants, NodeWeightDiscriminants, SocketArity, SocketKind, Timestamp,
-    WorkspaceSnapshotGraph, WorkspaceSnapshotGraphVCurrent,
+    WorkspaceSnapshotGraph, WorkspaceSnapshotGraphVCurrent, WorkspaceVariableKind,
 };
 use dal::{PropKind, Ulid};
 use dal_test::test;
@@ -41,7 +47,7 @@ use strum::IntoEnumIterator;
 const CURRENT_SERIALIZED_GRAPH_DIR_PATH: &str = "./lib/dal/tests";
 const CURRENT_SERIALIZED_GRAPH_FILENAME: &str = "serialization-test-data-2024-11-21.snapshot";
 const CURRENT_SERIALIZED_CONTENT_FILENAME: &str =
-    "serialization-test-content-store-data-2025-03-25.bin";
+    "serialization-test-content-store-data-2026-10-19.bin";
 
 // If you're modifying this, you probably just added a new node or edge weight. Before you replace
 // the snapshot with one that includes the new weights, ensure that your current code passes the
@@ -228,6 +234,7 @@ fn make_me_one_with_everything(graph: &mut WorkspaceSnapshotGraphVCurrent) {
             EdgeWeightKindDiscriminants::ApprovalRequirementDefinition => {
                 EdgeWeightKind::ApprovalRequirementDefinition
             }
+            EdgeWeightKindDiscriminants::FuncTestCase => EdgeWeightKind::FuncTestCase,
         };
 
         let edge_weight = EdgeWeight::new(edge_weight_kind);
@@ -499,6 +506,73 @@ fn make_me_one_with_everything_content_types_edition() -> Vec<ContentTypes> {
                 )
                 .into()
             }
+            ContentTypesDiscriminants::AttributeSubscription => {
+                AttributeSubscriptionContent::V1(
+                    dal::layer_db_types::AttributeSubscriptionContentV1 {
+                        timestamp,
+                        filters: vec![
+                            ComponentFilterContentV1::Schema(make_static_ulid()),
+                            ComponentFilterContentV1::DescendantOfSubscriber,
+                            ComponentFilterContentV1::PropValue {
+                                path: vec!["root".into(), "si".into(), "name".into()],
+                                value: cas_value.clone(),
+                            },
+                        ],
+                        select: vec!["root".into(), "domain".into(), "subnetId".into()],
+                    },
+                )
+                .into()
+            }
+            ContentTypesDiscriminants::WorkspaceVariable => {
+                WorkspaceVariableContent::V1(dal::layer_db_types::WorkspaceVariableContentV1 {
+                    timestamp,
+                    name: "region".into(),
+                    description: Some("where the servers are".into()),
+                    kind: WorkspaceVariableKind::Json,
+                    value: Some(cas_value.clone()),
+                })
+                .into()
+            }
+            ContentTypesDiscriminants::ActionRetryPolicy => {
+                ActionRetryPolicyContent::V1(dal::layer_db_types::ActionRetryPolicyContentV1 {
+                    timestamp,
+                    target: ActionRetryPolicyTarget::SchemaVariant(make_static_ulid()),
+                    max_attempts: 3,
+                    initial_delay_ms: 1000,
+                    max_delay_ms: 60000,
+                    retry_on: vec![ActionFailureKind::Other],
+                })
+                .into()
+            }
+            ContentTypesDiscriminants::FuncTimeout => {
+                FuncTimeoutContent::V1(dal::layer_db_types::FuncTimeoutContentV1 {
+                    timestamp,
+                    target: FuncTimeoutTarget::Func(make_static_ulid()),
+                    timeout_secs: 60,
+                })
+                .into()
+            }
+            ContentTypesDiscriminants::FuncTestCase => {
+                FuncTestCaseContent::V1(dal::layer_db_types::FuncTestCaseContentV1 {
+                    timestamp,
+                    func_id: make_static_ulid(),
+                    name: "does it blend".into(),
+                    args: cas_value.clone(),
+                    captured_from_component: Some("blender".into()),
+                    expectation: FuncTestCaseExpectation::Contains,
+                    expected: Some(cas_value.clone()),
+                })
+                .into()
+            }
+            ContentTypesDiscriminants::FuncRunData => ContentTypes::FuncRunData(cas_value.clone()),
+            ContentTypesDiscriminants::ComputedValue => {
+                ComputedValueContent::V1(ComputedValueContentV1 {
+                    value: cas_value.clone(),
+                    code_hash: ContentHash::from(0xc0de_u128),
+                    args_hash: ContentHash::from(0xa495_u128),
+                })
+                .into()
+            }
         });
     }
 
@@ -6,6 +6,13 @@ use pretty_assertions_sorted::assert_eq;
 
 mod argument;
 mod authoring;
+mod egress_policy;
+mod in_process_veritech;
+mod intrinsics;
+mod replay;
+mod retention;
+mod test_case;
+mod wasm;
 
 #[test]
 async fn summary(ctx: &mut DalContext) {
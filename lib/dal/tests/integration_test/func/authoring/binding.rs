@@ -877,6 +877,21 @@ async fn return_the_right_bindings(ctx: &mut DalContext, nw: &WorkspaceSignup) {
                                         // should only be one input right now
                                         assert_eq!(maybe_valid_args.len(), 1);
                                     }
+                                    // expressions may combine several inputs, but never
+                                    // read from output sockets or secrets
+                                    IntrinsicFunc::Arithmetic
+                                    | IntrinsicFunc::Coalesce
+                                    | IntrinsicFunc::JoinArray
+                                    | IntrinsicFunc::JsonPointer
+                                    | IntrinsicFunc::StringTemplate => {
+                                        assert!(attribute_binding.argument_bindings.iter().all(
+                                            |arg| !matches!(
+                                                arg.attribute_func_input_location,
+                                                AttributeFuncArgumentSource::OutputSocket(_)
+                                                    | AttributeFuncArgumentSource::Secret(_)
+                                            )
+                                        ));
+                                    }
                                     IntrinsicFunc::Unset => {
                                         assert!(attribute_binding.argument_bindings.is_empty());
                                     }
@@ -937,6 +952,21 @@ async fn return_the_right_bindings(ctx: &mut DalContext, nw: &WorkspaceSignup) {
                                         // should only be one or zero input right now
                                         assert!(maybe_valid_args.len() < 2);
                                     }
+                                    // expressions may combine several inputs, but never
+                                    // read from output sockets or secrets
+                                    IntrinsicFunc::Arithmetic
+                                    | IntrinsicFunc::Coalesce
+                                    | IntrinsicFunc::JoinArray
+                                    | IntrinsicFunc::JsonPointer
+                                    | IntrinsicFunc::StringTemplate => {
+                                        assert!(attribute_binding.argument_bindings.iter().all(
+                                            |arg| !matches!(
+                                                arg.attribute_func_input_location,
+                                                AttributeFuncArgumentSource::OutputSocket(_)
+                                                    | AttributeFuncArgumentSource::Secret(_)
+                                            )
+                                        ));
+                                    }
                                     // unset has no args
                                     IntrinsicFunc::Unset => {
                                         assert!(attribute_binding.argument_bindings.is_empty());
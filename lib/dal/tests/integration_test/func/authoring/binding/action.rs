@@ -4,6 +4,7 @@ use dal::action::{Action, ActionId};
 use dal::func::authoring::FuncAuthoringClient;
 use dal::func::binding::action::ActionBinding;
 use dal::func::binding::FuncBinding;
+use dal::func::FuncKind;
 use dal::schema::variant::authoring::VariantAuthoringClient;
 use dal::{DalContext, Func, SchemaVariant};
 use dal_test::helpers::{
@@ -267,3 +268,86 @@ async fn detach_attach_then_delete_action_func_while_enqueued(ctx: &mut DalConte
         queued.len()  // actual
     );
 }
+
+#[test]
+async fn attach_and_detach_plan_func(ctx: &mut DalContext) {
+    let schema_variant_id = SchemaVariant::default_id_for_schema_name(ctx, "fallout")
+        .await
+        .expect("unable to get default schema variant");
+    let schema_variant_id =
+        VariantAuthoringClient::create_unlocked_variant_copy(ctx, schema_variant_id)
+            .await
+            .expect("can create unlocked copy")
+            .id();
+    let action_prototype = ActionPrototype::for_variant(ctx, schema_variant_id)
+        .await
+        .expect("could not list action prototypes")
+        .into_iter()
+        .find(|prototype| prototype.kind == ActionKind::Create)
+        .expect("no create action prototype found");
+    let action_func_id = ActionPrototype::func_id(ctx, action_prototype.id())
+        .await
+        .expect("could not get action func id");
+
+    // Attach a plan func to the create action and commit.
+    let plan_func = FuncAuthoringClient::create_new_plan_func(
+        ctx,
+        Some("planCreateFallout".to_owned()),
+        action_prototype.id(),
+    )
+    .await
+    .expect("could not create plan func");
+    assert_eq!(FuncKind::Plan, plan_func.kind);
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    // The plan func is bound alongside the action func, not in place of it.
+    assert_eq!(
+        Some(plan_func.id),
+        ActionPrototype::plan_func_id(ctx, action_prototype.id())
+            .await
+            .expect("could not get plan func id")
+    );
+    assert_eq!(
+        action_func_id,
+        ActionPrototype::func_id(ctx, action_prototype.id())
+            .await
+            .expect("could not get action func id")
+    );
+    let bindings = FuncBinding::for_func_id(ctx, plan_func.id)
+        .await
+        .expect("could not get bindings");
+    assert_eq!(1, bindings.len());
+    let FuncBinding::Action(binding) = bindings.first().expect("has a binding") else {
+        panic!("plan func binding is not an action binding");
+    };
+    assert_eq!(action_prototype.id(), binding.action_prototype_id);
+    assert!(binding
+        .is_plan_binding(ctx)
+        .await
+        .expect("could not check binding"));
+
+    // Detaching the plan func leaves the action in place.
+    ActionBinding::delete_plan_binding(ctx, action_prototype.id())
+        .await
+        .expect("could not delete plan binding");
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    assert_eq!(
+        None,
+        ActionPrototype::plan_func_id(ctx, action_prototype.id())
+            .await
+            .expect("could not get plan func id")
+    );
+    assert_eq!(
+        action_func_id,
+        ActionPrototype::func_id(ctx, action_prototype.id())
+            .await
+            .expect("could not get action func id")
+    );
+}
@@ -1,12 +1,11 @@
 use dal::func::authoring::FuncAuthoringClient;
 use dal::{DalContext, Func};
 use dal_test::helpers::{
-    create_component_for_default_schema_name_in_default_view, ChangeSetTestHelpers,
+    create_component_for_default_schema_name_in_default_view, wait_for_func_run_with_success_state,
+    ChangeSetTestHelpers,
 };
 use dal_test::test;
-use si_events::{FuncRun, FuncRunId, FuncRunState};
-use std::sync::Arc;
-use std::time::Duration;
+use si_events::ExecutionPriority;
 
 #[test]
 async fn test_execute_action_func(ctx: &mut DalContext) {
@@ -41,7 +40,9 @@ async fn test_execute_action_func(ctx: &mut DalContext) {
         .expect("could not commit and update snapshot to visibility");
 
     // Check the results.
-    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id).await;
+    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id)
+        .await
+        .expect("func run did not succeed");
     assert_eq!(
         func.name.as_str(),       // expected
         func_run.function_name()  // actual
@@ -81,7 +82,9 @@ async fn test_execute_attribute_func(ctx: &mut DalContext) {
         .expect("could not commit and update snapshot to visibility");
 
     // Check the results.
-    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id).await;
+    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id)
+        .await
+        .expect("func run did not succeed");
     assert_eq!(
         func.name.as_str(),       // expected
         func_run.function_name()  // actual
@@ -121,7 +124,9 @@ async fn test_execute_code_generation_func(ctx: &mut DalContext) {
         .expect("could not commit and update snapshot to visibility");
 
     // Check the results.
-    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id).await;
+    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id)
+        .await
+        .expect("func run did not succeed");
     assert_eq!(
         func.name.as_str(),       // expected
         func_run.function_name()  // actual
@@ -161,7 +166,9 @@ async fn test_execute_qualification_func(ctx: &mut DalContext) {
         .expect("could not commit and update snapshot to visibility");
 
     // Check the results.
-    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id).await;
+    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id)
+        .await
+        .expect("func run did not succeed");
     assert_eq!(
         func.name.as_str(),       // expected
         func_run.function_name()  // actual
@@ -213,31 +220,15 @@ async fn test_execute_with_modified_code(ctx: &mut DalContext) {
         .expect("could not commit and update snapshot to visibility");
 
     // Check the results.
-    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id).await;
+    let func_run = wait_for_func_run_with_success_state(ctx, func_run_id)
+        .await
+        .expect("func run did not succeed");
     assert_eq!(
         func.name.as_str(),       // expected
         func_run.function_name()  // actual
     );
-}
-
-async fn wait_for_func_run_with_success_state(ctx: &DalContext, func_run_id: FuncRunId) -> FuncRun {
-    let seconds = 15;
-
-    for _ in 0..(seconds * 10) {
-        let func_run = ctx
-            .layer_db()
-            .func_run()
-            .read(func_run_id)
-            .await
-            .expect("could not read func run")
-            .expect("func run not found");
-
-        if func_run.state() == FuncRunState::Success {
-            return Arc::unwrap_or_clone(func_run);
-        }
-
-        tokio::time::sleep(Duration::from_millis(100)).await;
-    }
-
-    panic!("timed out waiting for func run");
+    assert_eq!(
+        ExecutionPriority::Interactive, // expected
+        func_run.priority()             // actual
+    );
 }
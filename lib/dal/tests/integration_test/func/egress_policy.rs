@@ -0,0 +1,130 @@
+use base64::{engine::general_purpose, Engine};
+use dal::func::{
+    egress_policy::{FuncEgressPolicy, FuncEgressPolicyError},
+    runner::FuncRunner,
+    FuncKind,
+};
+use dal::{DalContext, Func, FuncBackendKind, FuncBackendResponseType};
+use dal_test::{test, CannedResponse, InProcessVeritech, RecordedRequest};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use veritech_client::EgressPolicy;
+
+#[test]
+async fn set_list_and_remove(ctx: &DalContext) {
+    let policy = FuncEgressPolicy::set(
+        ctx,
+        FuncKind::Action,
+        vec![
+            "*.AmazonAWS.com".to_string(),
+            "10.0.0.0/8".to_string(),
+            "*.amazonaws.com".to_string(),
+        ],
+    )
+    .await
+    .expect("could not set policy");
+    assert_eq!(
+        vec!["*.amazonaws.com".to_string(), "10.0.0.0/8".to_string()],
+        policy.allow()
+    );
+
+    FuncEgressPolicy::set(ctx, FuncKind::Attribute, vec![])
+        .await
+        .expect("could not set policy");
+    // Setting it again replaces it
+    let policy = FuncEgressPolicy::set(ctx, FuncKind::Action, vec!["api.example.com".to_string()])
+        .await
+        .expect("could not set policy");
+    assert_eq!(vec!["api.example.com".to_string()], policy.allow());
+
+    let policies = FuncEgressPolicy::list(ctx)
+        .await
+        .expect("could not list policies");
+    assert_eq!(
+        vec![FuncKind::Action, FuncKind::Attribute],
+        policies
+            .iter()
+            .map(FuncEgressPolicy::func_kind)
+            .collect::<Vec<_>>()
+    );
+
+    FuncEgressPolicy::remove(ctx, FuncKind::Action)
+        .await
+        .expect("could not remove policy");
+    assert!(FuncEgressPolicy::for_func_kind(ctx, FuncKind::Action)
+        .await
+        .expect("could not get policy")
+        .is_none());
+    assert!(matches!(
+        FuncEgressPolicy::remove(ctx, FuncKind::Action).await,
+        Err(FuncEgressPolicyError::NotFound(FuncKind::Action))
+    ));
+}
+
+#[test]
+async fn rejects_invalid_rules(ctx: &DalContext) {
+    for rule in ["", "10.0.0.0/33", "https://example.com", "*.", "bad host"] {
+        assert!(
+            matches!(
+                FuncEgressPolicy::set(ctx, FuncKind::Action, vec![rule.to_string()]).await,
+                Err(FuncEgressPolicyError::InvalidRule(_))
+            ),
+            "accepted {rule:?}"
+        );
+    }
+    assert!(matches!(
+        FuncEgressPolicy::set(ctx, FuncKind::Intrinsic, vec![]).await,
+        Err(FuncEgressPolicyError::NotApplicable(FuncKind::Intrinsic))
+    ));
+}
+
+#[test(in_process_veritech)]
+async fn sends_policy_for_func_kind(ctx: &DalContext, veritech: &InProcessVeritech) {
+    let func = Func::new(
+        ctx,
+        "test:egressName",
+        None::<String>,
+        None::<String>,
+        None::<String>,
+        false,
+        false,
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::String,
+        Some("main"),
+        Some(general_purpose::STANDARD_NO_PAD.encode("function main() { return 'pluto'; }")),
+    )
+    .await
+    .expect("could not create func");
+    veritech.respond_to_code_hash(
+        func.code_blake3,
+        CannedResponse::resolver_value(json!("pluto")),
+    );
+
+    let run = |func: Func| async move {
+        let (_, result_channel) = FuncRunner::run_test(ctx, func, json!({}), None)
+            .await
+            .expect("could not run func");
+        result_channel
+            .await
+            .expect("could not receive result")
+            .expect("func failed");
+    };
+
+    // Without a policy, the func may reach anything
+    run(func.clone()).await;
+    FuncEgressPolicy::set(ctx, FuncKind::Attribute, vec![])
+        .await
+        .expect("could not set policy");
+    FuncEgressPolicy::set(ctx, FuncKind::Action, vec!["*.amazonaws.com".to_string()])
+        .await
+        .expect("could not set policy");
+    run(func).await;
+
+    match veritech.requests().as_slice() {
+        [RecordedRequest::ResolverFunction(before), RecordedRequest::ResolverFunction(after)] => {
+            assert_eq!(None, before.egress_policy);
+            assert_eq!(Some(EgressPolicy { allow: vec![] }), after.egress_policy);
+        }
+        requests => panic!("unexpected requests: {requests:?}"),
+    }
+}
@@ -0,0 +1,77 @@
+use base64::{engine::general_purpose, Engine};
+use dal::func::runner::FuncRunner;
+use dal::{DalContext, Func, FuncBackendKind, FuncBackendResponseType};
+use dal_test::{test, CannedResponse, InProcessVeritech, RecordedRequest};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test(in_process_veritech)]
+async fn answers_with_canned_responses(ctx: &DalContext, veritech: &InProcessVeritech) {
+    let galaxies = json!([{ "sun": "pluto-sun", "planets": 3 }]);
+    veritech
+        .respond_to_func(
+            ctx,
+            "test:falloutEntriesToGalaxies",
+            CannedResponse::resolver_value(galaxies.clone()),
+        )
+        .await
+        .expect("could not set up canned response");
+
+    let func_id = Func::find_id_by_name(ctx, "test:falloutEntriesToGalaxies")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+    let func = Func::get_by_id(ctx, func_id)
+        .await
+        .expect("could not get func");
+    let args = json!({ "entries": [{ "si": { "name": "pluto" }, "domain": { "rads": 3 } }] });
+    let (_, result_channel) = FuncRunner::run_test(ctx, func, args.clone(), None)
+        .await
+        .expect("could not run func");
+    let value = result_channel
+        .await
+        .expect("could not receive result")
+        .expect("func failed");
+
+    assert_eq!(Some(&galaxies), value.value());
+    match veritech.requests().as_slice() {
+        [RecordedRequest::ResolverFunction(request)] => {
+            assert_eq!("falloutEntriesToGalaxies", request.handler);
+            assert_eq!(args, request.component.data.properties);
+        }
+        requests => panic!("unexpected requests: {requests:?}"),
+    }
+}
+
+#[test(in_process_veritech)]
+async fn evaluates_simple_funcs_natively(ctx: &DalContext, veritech: &InProcessVeritech) {
+    let code_base64 = general_purpose::STANDARD_NO_PAD
+        .encode("function main(input) {\n  return input.si.name;\n}\n");
+    let func = Func::new(
+        ctx,
+        "test:nameOfInput",
+        None::<String>,
+        None::<String>,
+        None::<String>,
+        false,
+        false,
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::String,
+        Some("main"),
+        Some(code_base64),
+    )
+    .await
+    .expect("could not create func");
+
+    let (_, result_channel) =
+        FuncRunner::run_test(ctx, func, json!({ "si": { "name": "pluto" } }), None)
+            .await
+            .expect("could not run func");
+    let value = result_channel
+        .await
+        .expect("could not receive result")
+        .expect("func failed");
+
+    assert_eq!(Some(&json!("pluto")), value.value());
+    assert_eq!(1, veritech.requests().len());
+}
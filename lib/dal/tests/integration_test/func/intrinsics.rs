@@ -0,0 +1,231 @@
+use dal::func::argument::FuncArgument;
+use dal::func::backend::arithmetic::FuncBackendArithmetic;
+use dal::func::backend::coalesce::FuncBackendCoalesce;
+use dal::func::backend::join_array::FuncBackendJoinArray;
+use dal::func::backend::json_pointer::FuncBackendJsonPointer;
+use dal::func::backend::string_template::FuncBackendStringTemplate;
+use dal::func::backend::{FuncBackend, FuncBackendError};
+use dal::func::binding::attribute::AttributeBinding;
+use dal::func::binding::{
+    AttributeArgumentBinding, AttributeFuncArgumentSource, AttributeFuncDestination,
+    FuncBindingError,
+};
+use dal::func::intrinsics::IntrinsicFunc;
+use dal::{DalContext, Func, FuncId};
+use dal_test::expected::ExpectSchema;
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::{test, Result};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test]
+async fn expression_intrinsics_evaluate(_ctx: &DalContext) -> Result<()> {
+    let (_, value) = FuncBackendStringTemplate::create_and_execute(&json!({
+        "template": "arn:aws:s3:::${bucket}/${ key }${missing}",
+        "values": { "bucket": "poop", "key": 1 },
+    }))
+    .await?;
+    assert_eq!(Some(json!("arn:aws:s3:::poop/1")), value);
+
+    let (_, value) = FuncBackendStringTemplate::create_and_execute(&json!({
+        "template": "${0}-${1}",
+        "values": ["a", "b"],
+    }))
+    .await?;
+    assert_eq!(Some(json!("a-b")), value);
+
+    let result = FuncBackendStringTemplate::create_and_execute(&json!({
+        "template": "${oops",
+        "values": "canoe",
+    }))
+    .await;
+    assert!(matches!(
+        result,
+        Err(FuncBackendError::UnterminatedTemplatePlaceholder(_))
+    ));
+
+    let (_, value) = FuncBackendJsonPointer::create_and_execute(&json!({
+        "value": { "tags": [{ "key": "Name", "value": "bigpoop" }] },
+        "pointer": "/tags/0/value",
+    }))
+    .await?;
+    assert_eq!(Some(json!("bigpoop")), value);
+
+    let (_, value) = FuncBackendJsonPointer::create_and_execute(&json!({
+        "value": { "tags": [] },
+        "pointer": "/tags/0/value",
+    }))
+    .await?;
+    assert_eq!(None, value);
+
+    let (_, value) = FuncBackendJoinArray::create_and_execute(&json!({
+        "values": ["a", null, 1, true],
+        "separator": " | ",
+    }))
+    .await?;
+    assert_eq!(Some(json!("a | 1 | true")), value);
+
+    let (_, value) =
+        FuncBackendJoinArray::create_and_execute(&json!({ "values": ["a", "b"] })).await?;
+    assert_eq!(Some(json!("a,b")), value);
+
+    let (_, value) =
+        FuncBackendCoalesce::create_and_execute(&json!({ "values": [null, "fallback", "later"] }))
+            .await?;
+    assert_eq!(Some(json!("fallback")), value);
+
+    let (_, value) = FuncBackendArithmetic::create_and_execute(&json!({
+        "operation": "add",
+        "operands": [1, "2", null, 3],
+    }))
+    .await?;
+    assert_eq!(Some(json!(6)), value);
+
+    let (_, value) = FuncBackendArithmetic::create_and_execute(&json!({
+        "operation": "divide",
+        "operands": [7, 2],
+    }))
+    .await?;
+    assert_eq!(Some(json!(3.5)), value);
+
+    let (_, value) = FuncBackendArithmetic::create_and_execute(&json!({
+        "operation": "max",
+        "operands": [7, 2.5, 11],
+    }))
+    .await?;
+    assert_eq!(Some(json!(11.0)), value);
+
+    let result = FuncBackendArithmetic::create_and_execute(&json!({
+        "operation": "modulo",
+        "operands": [7, 0],
+    }))
+    .await;
+    assert!(matches!(result, Err(FuncBackendError::DivisionByZero)));
+
+    let result = FuncBackendArithmetic::create_and_execute(&json!({
+        "operation": "add",
+        "operands": [1, { "not": "a number" }],
+    }))
+    .await;
+    assert!(matches!(
+        result,
+        Err(FuncBackendError::InvalidArithmeticOperand(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+async fn string_template_binding(ctx: &mut DalContext) -> Result<()> {
+    let variant = ExpectSchema::find(ctx, "swifty")
+        .await
+        .default_variant(ctx)
+        .await
+        .create_unlocked_copy(ctx)
+        .await;
+    let name_prop_id = variant.prop(ctx, ["root", "si", "name"]).await.id();
+    let domain_name_prop_id = variant.prop(ctx, ["root", "domain", "name"]).await.id();
+
+    let func_id = Func::find_intrinsic(ctx, IntrinsicFunc::StringTemplate).await?;
+    let template_arg = argument_binding(
+        ctx,
+        func_id,
+        "template",
+        AttributeFuncArgumentSource::StaticArgument(json!("app-${value}")),
+    )
+    .await?;
+    let values_arg = argument_binding(
+        ctx,
+        func_id,
+        "values",
+        AttributeFuncArgumentSource::Prop(name_prop_id),
+    )
+    .await?;
+
+    // The template itself must be bound.
+    let result = AttributeBinding::upsert_attribute_binding(
+        ctx,
+        func_id,
+        None,
+        AttributeFuncDestination::Prop(domain_name_prop_id),
+        vec![values_arg.clone()],
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(FuncBindingError::IntrinsicArgumentUnbound(
+            IntrinsicFunc::StringTemplate,
+            _
+        ))
+    ));
+
+    AttributeBinding::upsert_attribute_binding(
+        ctx,
+        func_id,
+        None,
+        AttributeFuncDestination::Prop(domain_name_prop_id),
+        vec![template_arg, values_arg],
+    )
+    .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let component = variant
+        .create_named_component_on_default_view(ctx, "taylor")
+        .await;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(
+        json!("app-taylor"),
+        component
+            .prop(ctx, ["root", "domain", "name"])
+            .await
+            .get(ctx)
+            .await
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn expression_intrinsics_are_installed_on_first_use(ctx: &mut DalContext) -> Result<()> {
+    // Stand in for a workspace created before the expression intrinsics existed.
+    let func_id = Func::find_intrinsic(ctx, IntrinsicFunc::Coalesce).await?;
+    Func::delete_by_id(ctx, func_id).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let func_id = Func::find_intrinsic(ctx, IntrinsicFunc::Coalesce).await?;
+    let arguments = FuncArgument::list_for_func(ctx, func_id).await?;
+    assert_eq!(
+        vec!["values"],
+        arguments
+            .iter()
+            .map(|argument| argument.name.as_str())
+            .collect::<Vec<_>>()
+    );
+
+    // The others were left alone.
+    let string_templates = Func::list_all(ctx)
+        .await?
+        .into_iter()
+        .filter(|func| func.name == IntrinsicFunc::StringTemplate.name())
+        .count();
+    assert_eq!(1, string_templates);
+
+    Ok(())
+}
+
+async fn argument_binding(
+    ctx: &DalContext,
+    func_id: FuncId,
+    name: &str,
+    source: AttributeFuncArgumentSource,
+) -> Result<AttributeArgumentBinding> {
+    let func_argument = FuncArgument::find_by_name_for_func(ctx, name, func_id)
+        .await?
+        .expect("func argument exists");
+    Ok(AttributeArgumentBinding {
+        func_argument_id: func_argument.id,
+        attribute_prototype_argument_id: None,
+        attribute_func_input_location: source,
+    })
+}
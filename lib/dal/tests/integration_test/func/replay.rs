@@ -0,0 +1,74 @@
+use dal::func::authoring::FuncAuthoringClient;
+use dal::func::replay::{FuncRunReplay, FuncRunReplayCode, FuncRunReplayError};
+use dal::{DalContext, Func};
+use dal_test::helpers::{
+    create_component_for_default_schema_name_in_default_view, wait_for_func_run_with_success_state,
+    ChangeSetTestHelpers,
+};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use si_events::FuncRunState;
+
+#[test]
+async fn replay_attribute_func_run(ctx: &mut DalContext) {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "Replayed")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let func_id = Func::find_id_by_name(ctx, "test:falloutEntriesToGalaxies")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+
+    // Run with code that differs from the func's current code.
+    let modified_code =
+        "async function falloutEntriesToGalaxies(input: Input): Promise<Output> { return [\"replay me\"]; }";
+    let func_run_id = FuncAuthoringClient::test_execute_func(
+        ctx,
+        func_id,
+        serde_json::json!({ "entries": [] }),
+        Some(modified_code.to_string()),
+        component.id(),
+    )
+    .await
+    .expect("could not perform test execution for func");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    wait_for_func_run_with_success_state(ctx, func_run_id)
+        .await
+        .expect("func run did not succeed");
+
+    // Replaying with the original code gets the same result.
+    let replay = FuncRunReplay::replay(ctx, func_run_id, FuncRunReplayCode::Original)
+        .await
+        .expect("could not replay func run");
+    assert_eq!(func_run_id, replay.original_func_run_id);
+    assert_ne!(func_run_id, replay.replay_func_run_id);
+    assert_eq!(FuncRunState::Success, replay.replay_state);
+    assert!(!replay.code_changed);
+    assert_eq!(Some(serde_json::json!(["replay me"])), replay.replay_value);
+    assert!(replay.results_match());
+
+    // Replaying with the current code runs different code.
+    let replay = FuncRunReplay::replay(ctx, func_run_id, FuncRunReplayCode::Current)
+        .await
+        .expect("could not replay func run");
+    assert!(replay.code_changed);
+    assert_ne!(Some(serde_json::json!(["replay me"])), replay.replay_value);
+    assert!(!replay.results_match());
+
+    // Once an unlocked copy shares the func's name, the run can't tell which one it was.
+    FuncAuthoringClient::create_unlocked_func_copy(ctx, func_id, None)
+        .await
+        .expect("could not create unlocked func copy");
+    let result = FuncRunReplay::replay(ctx, func_run_id, FuncRunReplayCode::Original).await;
+    assert!(matches!(
+        result,
+        Err(FuncRunReplayError::FuncAmbiguous(id, _)) if id == func_run_id
+    ));
+}
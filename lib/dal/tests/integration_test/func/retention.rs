@@ -0,0 +1,329 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use dal::func::retention::{
+    collect_garbage, sweep_func_run_data, FuncRunGarbage, FuncRunRetention, FuncRunRetentionError,
+    FuncRunRetentionPolicy, SWEEP_DELAY,
+};
+use dal::layer_db_types::ContentTypes;
+use dal::DalContext;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use si_events::{
+    ComponentId, ContentHash, ExecutionPriority, FuncBackendKind, FuncBackendResponseType,
+    FuncKind, FuncRun, FuncRunBuilder, FuncRunState,
+};
+
+#[test]
+async fn set_get_and_remove(ctx: &DalContext) {
+    assert!(FuncRunRetentionPolicy::get(ctx)
+        .await
+        .expect("could not get policy")
+        .is_none());
+
+    let retention = FuncRunRetention {
+        keep_last: 5,
+        keep_days: 7,
+    };
+    let policy = FuncRunRetentionPolicy::set(ctx, retention)
+        .await
+        .expect("could not set policy");
+    assert_eq!(retention, policy.retention());
+
+    // Setting it again replaces it
+    let retention = FuncRunRetention {
+        keep_last: 1,
+        keep_days: 0,
+    };
+    FuncRunRetentionPolicy::set(ctx, retention)
+        .await
+        .expect("could not set policy");
+    let policy = FuncRunRetentionPolicy::get(ctx)
+        .await
+        .expect("could not get policy")
+        .expect("no policy");
+    assert_eq!(retention, policy.retention());
+
+    // The newest run always has to be kept
+    let result = FuncRunRetentionPolicy::set(
+        ctx,
+        FuncRunRetention {
+            keep_last: 0,
+            keep_days: 7,
+        },
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(FuncRunRetentionError::InvalidKeepLast(0))
+    ));
+
+    FuncRunRetentionPolicy::remove(ctx)
+        .await
+        .expect("could not remove policy");
+    assert!(FuncRunRetentionPolicy::get(ctx)
+        .await
+        .expect("could not get policy")
+        .is_none());
+    assert!(matches!(
+        FuncRunRetentionPolicy::remove(ctx).await,
+        Err(FuncRunRetentionError::NotFound)
+    ));
+}
+
+#[test]
+async fn collect_garbage_removes_expired_runs(ctx: &DalContext) {
+    let component_id = ComponentId::new();
+    let args_address = write_func_run_data(ctx, json!({ "name": "retention" })).await;
+    // Content written by older versions may be shared with snapshots, so it is never removed
+    let shared_address = write_cas(ctx, ContentTypes::Any(json!("shared").into())).await;
+    let mut func_runs = Vec::new();
+    for days_ago in [10, 9, 8] {
+        let code_address = write_func_run_data(
+            ctx,
+            json!(format!("code for {component_id} from {days_ago} days ago")),
+        )
+        .await;
+        let result_address = write_func_run_data(
+            ctx,
+            json!(format!(
+                "result for {component_id} from {days_ago} days ago"
+            )),
+        )
+        .await;
+        let mut func_run = create_func_run(ctx, component_id, args_address, code_address, days_ago);
+        func_run.set_result_value_cas_address(Some(result_address));
+        func_run.set_result_unprocessed_value_cas_address(Some(shared_address));
+        ctx.layer_db()
+            .func_run()
+            .write(
+                Arc::new(func_run.clone()),
+                None,
+                ctx.events_tenancy(),
+                ctx.events_actor(),
+            )
+            .await
+            .expect("could not write func run");
+        func_runs.push(func_run);
+    }
+    let workspace_pk = ctx.workspace_pk().expect("no workspace");
+    let retention = FuncRunRetention {
+        keep_last: 1,
+        keep_days: 1,
+    };
+
+    // The two older runs are expired, and their code and results are marked. The args are still
+    // used by the newest run.
+    let expected = FuncRunGarbage {
+        func_runs: 2,
+        cas_blobs: 4,
+    };
+    let garbage = collect_garbage(ctx, workspace_pk, retention, true)
+        .await
+        .expect("could not collect garbage");
+    assert_eq!(expected, garbage);
+    for func_run in &func_runs {
+        assert!(ctx
+            .layer_db()
+            .func_run()
+            .read(func_run.id())
+            .await
+            .expect("could not read func run")
+            .is_some());
+    }
+
+    let garbage = collect_garbage(ctx, workspace_pk, retention, false)
+        .await
+        .expect("could not collect garbage");
+    assert_eq!(expected, garbage);
+    for func_run in &func_runs[..2] {
+        assert!(ctx
+            .layer_db()
+            .func_run()
+            .read(func_run.id())
+            .await
+            .expect("could not read func run")
+            .is_none());
+    }
+    assert!(ctx
+        .layer_db()
+        .func_run()
+        .read(func_runs[2].id())
+        .await
+        .expect("could not read func run")
+        .is_some());
+
+    // Marked content is kept until it has been marked for long enough
+    let orphaned: Vec<ContentHash> = func_runs[..2]
+        .iter()
+        .flat_map(|func_run| {
+            [
+                Some(func_run.function_code_cas_address()),
+                func_run.result_value_cas_address(),
+            ]
+        })
+        .flatten()
+        .collect();
+    sweep_func_run_data(ctx, Utc::now() - SWEEP_DELAY, false)
+        .await
+        .expect("could not sweep func run data");
+    for address in &orphaned {
+        assert!(ctx
+            .layer_db()
+            .cas()
+            .read(address)
+            .await
+            .expect("could not read cas")
+            .is_some());
+    }
+
+    sweep_func_run_data(ctx, Utc::now(), false)
+        .await
+        .expect("could not sweep func run data");
+    for address in &orphaned {
+        assert!(ctx
+            .layer_db()
+            .cas()
+            .read(address)
+            .await
+            .expect("could not read cas")
+            .is_none());
+    }
+    for address in [args_address, shared_address] {
+        assert!(ctx
+            .layer_db()
+            .cas()
+            .read(&address)
+            .await
+            .expect("could not read cas")
+            .is_some());
+    }
+
+    // Nothing is left to collect
+    let garbage = collect_garbage(ctx, workspace_pk, retention, false)
+        .await
+        .expect("could not collect garbage");
+    assert_eq!(FuncRunGarbage::default(), garbage);
+}
+
+#[test]
+async fn sweep_keeps_content_used_again(ctx: &DalContext) {
+    let component_id = ComponentId::new();
+    let args_address = write_func_run_data(ctx, json!({ "name": "retention" })).await;
+    let code_address =
+        write_func_run_data(ctx, json!(format!("code for {component_id} used again"))).await;
+    let expired = create_func_run(ctx, component_id, args_address, code_address, 10);
+    ctx.layer_db()
+        .func_run()
+        .write(
+            Arc::new(expired.clone()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )
+        .await
+        .expect("could not write func run");
+    let workspace_pk = ctx.workspace_pk().expect("no workspace");
+    let retention = FuncRunRetention {
+        keep_last: 1,
+        keep_days: 1,
+    };
+
+    let garbage = collect_garbage(ctx, workspace_pk, retention, false)
+        .await
+        .expect("could not collect garbage");
+    assert_eq!(
+        FuncRunGarbage {
+            func_runs: 1,
+            cas_blobs: 2,
+        },
+        garbage
+    );
+
+    // A run that was in flight while the garbage was collected records its func run once its
+    // content has been marked
+    let func_run = create_func_run(ctx, component_id, args_address, code_address, 0);
+    ctx.layer_db()
+        .func_run()
+        .write(
+            Arc::new(func_run),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )
+        .await
+        .expect("could not write func run");
+
+    sweep_func_run_data(ctx, Utc::now(), false)
+        .await
+        .expect("could not sweep func run data");
+    for address in [args_address, code_address] {
+        assert!(ctx
+            .layer_db()
+            .cas()
+            .cache
+            .pg()
+            .get(&address.to_string())
+            .await
+            .expect("could not read cas")
+            .is_some());
+    }
+}
+
+async fn write_func_run_data(ctx: &DalContext, value: serde_json::Value) -> ContentHash {
+    let (address, status) = ctx
+        .layer_db()
+        .cas()
+        .write_func_run_data(
+            Arc::new(ContentTypes::FuncRunData(value.into())),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )
+        .expect("could not write to cas");
+    status.get_status().await.expect("could not persist cas");
+    address
+}
+
+async fn write_cas(ctx: &DalContext, value: ContentTypes) -> ContentHash {
+    let (address, status) = ctx
+        .layer_db()
+        .cas()
+        .write(
+            Arc::new(value),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )
+        .expect("could not write to cas");
+    status.get_status().await.expect("could not persist cas");
+    address
+}
+
+fn create_func_run(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    args_address: ContentHash,
+    code_address: ContentHash,
+    days_ago: i64,
+) -> FuncRun {
+    let updated_at = Utc::now() - Duration::days(days_ago);
+    FuncRunBuilder::default()
+        .actor(ctx.events_actor())
+        .tenancy(ctx.events_tenancy())
+        .state(FuncRunState::Success)
+        .component_id(Some(component_id))
+        .attribute_value_id(None)
+        .backend_kind(FuncBackendKind::JsAttribute)
+        .backend_response_type(FuncBackendResponseType::Object)
+        .function_name("retention".to_string())
+        .function_kind(FuncKind::Attribute)
+        .priority(ExecutionPriority::Dvu)
+        .function_args_cas_address(args_address)
+        .function_code_cas_address(code_address)
+        .created_at(updated_at)
+        .updated_at(updated_at)
+        .build()
+        .expect("could not build func run")
+}
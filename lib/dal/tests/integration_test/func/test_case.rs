@@ -0,0 +1,163 @@
+use dal::func::authoring::FuncAuthoringClient;
+use dal::func::binding::FuncBinding;
+use dal::func::test_case::{
+    FuncTestCase, FuncTestCaseDefinition, FuncTestCaseError, FuncTestCaseExpectation,
+};
+use dal::{DalContext, Func};
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+fn definition(
+    name: &str,
+    expectation: FuncTestCaseExpectation,
+    expected: Option<serde_json::Value>,
+) -> FuncTestCaseDefinition {
+    FuncTestCaseDefinition {
+        name: name.to_string(),
+        args: json!({ "entries": [{ "si": { "name": "pluto" }, "domain": { "rads": 3 } }] }),
+        captured_from_component: None,
+        expectation,
+        expected,
+    }
+}
+
+#[test]
+async fn create_and_run_test_cases(ctx: &mut DalContext) {
+    let func_id = Func::find_id_by_name(ctx, "test:falloutEntriesToGalaxies")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+
+    FuncTestCase::new(
+        ctx,
+        func_id,
+        definition(
+            "one galaxy",
+            FuncTestCaseExpectation::Equals,
+            Some(json!([{ "sun": "pluto-sun", "planets": 3 }])),
+        ),
+    )
+    .await
+    .expect("could not create test case");
+    FuncTestCase::new(
+        ctx,
+        func_id,
+        definition(
+            "has the sun",
+            FuncTestCaseExpectation::Contains,
+            Some(json!([{ "sun": "pluto-sun" }])),
+        ),
+    )
+    .await
+    .expect("could not create test case");
+    let regression = FuncTestCase::new(
+        ctx,
+        func_id,
+        definition(
+            "no galaxies",
+            FuncTestCaseExpectation::Equals,
+            Some(json!([])),
+        ),
+    )
+    .await
+    .expect("could not create test case");
+
+    // Names are unique per func and comparisons need something to compare against.
+    assert!(matches!(
+        FuncTestCase::new(
+            ctx,
+            func_id,
+            definition("one galaxy", FuncTestCaseExpectation::Succeeds, None),
+        )
+        .await,
+        Err(FuncTestCaseError::NameTaken(_, _))
+    ));
+    assert!(matches!(
+        FuncTestCase::new(
+            ctx,
+            func_id,
+            definition("another", FuncTestCaseExpectation::Equals, None),
+        )
+        .await,
+        Err(FuncTestCaseError::ExpectedValueMissing(_, _))
+    ));
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let report = FuncTestCase::run_for_func(ctx, func_id)
+        .await
+        .expect("could not run test cases");
+    assert_eq!(2, report.passed);
+    assert_eq!(1, report.failed);
+    assert!(!report.all_passed());
+
+    let failed = report
+        .results
+        .iter()
+        .find(|result| !result.passed)
+        .expect("no failed result");
+    assert_eq!(regression.id(), failed.func_test_case_id);
+    assert_eq!(
+        Some(json!([{ "sun": "pluto-sun", "planets": 3 }])),
+        failed.output
+    );
+
+    // Fixing the expectation makes everything pass.
+    regression
+        .update(
+            ctx,
+            definition("no galaxies", FuncTestCaseExpectation::Succeeds, None),
+        )
+        .await
+        .expect("could not update test case");
+    let report = FuncTestCase::run_all(ctx)
+        .await
+        .expect("could not run test cases");
+    assert_eq!(3, report.passed);
+    assert!(report.all_passed());
+}
+
+#[test]
+async fn test_cases_follow_unlocked_copies(ctx: &mut DalContext) {
+    let func_id = Func::find_id_by_name(ctx, "test:falloutEntriesToGalaxies")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+    FuncTestCase::new(
+        ctx,
+        func_id,
+        definition("runs", FuncTestCaseExpectation::Succeeds, None),
+    )
+    .await
+    .expect("could not create test case");
+
+    let unlocked_func = FuncAuthoringClient::create_unlocked_func_copy(ctx, func_id, None)
+        .await
+        .expect("could not create unlocked copy");
+    let test_cases = FuncTestCase::list_for_func(ctx, unlocked_func.id)
+        .await
+        .expect("could not list test cases");
+    assert_eq!(
+        vec!["runs"],
+        test_cases
+            .iter()
+            .map(|test_case| test_case.name())
+            .collect::<Vec<_>>()
+    );
+
+    // Deleting the func deletes its test cases.
+    FuncBinding::delete_all_bindings_for_func_id(ctx, unlocked_func.id)
+        .await
+        .expect("could not delete bindings");
+    Func::delete_by_id(ctx, unlocked_func.id)
+        .await
+        .expect("could not delete func");
+    assert!(FuncTestCase::list_for_func(ctx, unlocked_func.id)
+        .await
+        .expect("could not list test cases")
+        .is_empty());
+}
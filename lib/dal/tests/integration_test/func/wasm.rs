@@ -0,0 +1,79 @@
+use base64::{engine::general_purpose, Engine};
+use dal::func::{runner::FuncRunner, FuncKind};
+use dal::{DalContext, Func, FuncBackendKind, FuncBackendResponseType};
+use dal_test::{test, CannedResponse, InProcessVeritech, RecordedRequest};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use veritech_client::FunctionRuntime;
+
+// Only the start of a component, which is enough for requests that never reach cyclone
+const COMPONENT_PREAMBLE: &[u8] = b"\0asm\x0d\0\x01\0";
+
+async fn create_wasm_func(
+    ctx: &DalContext,
+    name: &str,
+    response_type: FuncBackendResponseType,
+) -> Func {
+    Func::new(
+        ctx,
+        name,
+        None::<String>,
+        None::<String>,
+        None::<String>,
+        false,
+        false,
+        FuncBackendKind::Wasm,
+        response_type,
+        Some("run"),
+        Some(general_purpose::STANDARD_NO_PAD.encode(COMPONENT_PREAMBLE)),
+    )
+    .await
+    .expect("could not create func")
+}
+
+#[test]
+async fn kind_follows_response_type(ctx: &DalContext) {
+    let attribute =
+        create_wasm_func(ctx, "test:wasmAttribute", FuncBackendResponseType::String).await;
+    let qualification = create_wasm_func(
+        ctx,
+        "test:wasmQualification",
+        FuncBackendResponseType::Qualification,
+    )
+    .await;
+    let action = create_wasm_func(ctx, "test:wasmAction", FuncBackendResponseType::Action).await;
+
+    assert_eq!(FuncKind::Attribute, attribute.kind);
+    assert_eq!(FuncKind::Qualification, qualification.kind);
+    assert_eq!(FuncKind::Action, action.kind);
+}
+
+#[test(in_process_veritech)]
+async fn dispatches_attribute_funcs_with_wasm_runtime(
+    ctx: &DalContext,
+    veritech: &InProcessVeritech,
+) {
+    let func = create_wasm_func(ctx, "test:wasmName", FuncBackendResponseType::String).await;
+    veritech.respond_to_code_hash(
+        func.code_blake3,
+        CannedResponse::resolver_value(json!("pluto")),
+    );
+
+    let args = json!({ "si": { "name": "pluto" } });
+    let (_, result_channel) = FuncRunner::run_test(ctx, func, args.clone(), None)
+        .await
+        .expect("could not run func");
+    let value = result_channel
+        .await
+        .expect("could not receive result")
+        .expect("func failed");
+
+    assert_eq!(Some(&json!("pluto")), value.value());
+    match veritech.requests().as_slice() {
+        [RecordedRequest::ResolverFunction(request)] => {
+            assert_eq!(FunctionRuntime::Wasm, request.runtime);
+            assert_eq!(args, request.component.data.properties);
+        }
+        requests => panic!("unexpected requests: {requests:?}"),
+    }
+}
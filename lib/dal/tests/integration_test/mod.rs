@@ -27,3 +27,4 @@ mod secret;
 mod validations;
 mod view;
 mod workspace;
+mod workspace_variable;
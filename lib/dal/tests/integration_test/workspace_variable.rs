@@ -0,0 +1,109 @@
+use dal::func::argument::FuncArgument;
+use dal::func::intrinsics::IntrinsicFunc;
+use dal::{
+    AttributePrototype, AttributePrototypeArgument, AttributeValue, DalContext, Func,
+    WorkspaceVariable, WorkspaceVariableError, WorkspaceVariableKind,
+};
+use dal_test::expected::ExpectComponent;
+use dal_test::helpers::ChangeSetTestHelpers;
+use dal_test::{test, Result};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test]
+async fn workspace_variable_flows_into_attribute_values(ctx: &mut DalContext) -> Result<()> {
+    let region = WorkspaceVariable::new(
+        ctx,
+        "region",
+        Some("where the servers are".to_string()),
+        WorkspaceVariableKind::String,
+        Some(json!("us-east-1")),
+    )
+    .await?;
+    let component = ExpectComponent::create_named(ctx, "swifty", "taylor").await;
+
+    // Point "/root/domain/name" at the variable rather than "/root/si/name".
+    let identity_func_id = Func::find_intrinsic(ctx, IntrinsicFunc::Identity).await?;
+    let identity_arg = FuncArgument::find_by_name_for_func(ctx, "identity", identity_func_id)
+        .await?
+        .expect("identity func argument exists");
+    let prototype = AttributePrototype::new(ctx, identity_func_id).await?;
+    AttributePrototypeArgument::new(ctx, prototype.id(), identity_arg.id)
+        .await?
+        .set_value_from_workspace_variable_id(ctx, region.id())
+        .await?;
+    let name = component.prop(ctx, ["root", "domain", "name"]).await;
+    let name_av_id = name.attribute_value(ctx).await.id();
+    AttributeValue::set_component_prototype_id(ctx, name_av_id, prototype.id(), None).await?;
+    ctx.add_dependent_values_and_enqueue(vec![name_av_id])
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(json!("us-east-1"), name.get(ctx).await);
+
+    // Changing the variable recomputes everything using it.
+    WorkspaceVariable::get_by_id(ctx, region.id())
+        .await?
+        .set_value(ctx, Some(json!("eu-west-2")))
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(json!("eu-west-2"), name.get(ctx).await);
+    assert_eq!(
+        vec![name_av_id],
+        WorkspaceVariable::direct_dependent_attribute_values(ctx, region.id()).await?
+    );
+
+    // Variables in use cannot be removed.
+    let result = WorkspaceVariable::remove(ctx, region.id()).await;
+    assert!(matches!(result, Err(WorkspaceVariableError::InUse(_, 1))));
+
+    Ok(())
+}
+
+#[test]
+async fn workspace_variable_validation(ctx: &mut DalContext) -> Result<()> {
+    let account_id = WorkspaceVariable::new(
+        ctx,
+        "account_id",
+        None,
+        WorkspaceVariableKind::Integer,
+        Some(json!(123456789012_u64)),
+    )
+    .await?;
+
+    let result =
+        WorkspaceVariable::new(ctx, "account_id", None, WorkspaceVariableKind::Json, None).await;
+    assert!(matches!(result, Err(WorkspaceVariableError::NameTaken(_))));
+
+    let result = account_id
+        .clone()
+        .set_value(ctx, Some(json!("not a number")))
+        .await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceVariableError::InvalidValue(
+            _,
+            WorkspaceVariableKind::Integer,
+            _
+        ))
+    ));
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let variables = WorkspaceVariable::list(ctx).await?;
+    assert_eq!(
+        vec![("account_id", Some(&json!(123456789012_u64)))],
+        variables
+            .iter()
+            .map(|variable| (variable.name(), variable.value()))
+            .collect::<Vec<_>>()
+    );
+
+    WorkspaceVariable::remove(ctx, account_id.id()).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert!(WorkspaceVariable::list(ctx).await?.is_empty());
+
+    Ok(())
+}
@@ -1,8 +1,94 @@
 use serde::Deserialize;
 
+use crate::{generic, rule_err, Failure};
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
 pub struct Validator {
-    pub matches: Vec<crate::Validator>,
+    matches: Vec<Match>,
+    #[serde(flatten)]
+    pub base: generic::Validator<serde_json::Value, Flags>,
+}
+
+impl Validator {
+    pub(crate) fn validate(self, value: &Option<serde_json::Value>) -> Result<(), Failure> {
+        self.base.validate_presence(value)?;
+        if let Some(value) = value {
+            self.base.validate_value(value)?;
+
+            let mut failures = vec![];
+            let mut matched = 0;
+            for alternative in self.matches {
+                match alternative.schema.check(&Some(value.clone())) {
+                    Ok(()) => matched += 1,
+                    Err(failure) => failures.push(failure),
+                }
+            }
+
+            let failure = match self.base.flags.extra_flags.r#match.unwrap_or_default() {
+                Mode::All if !failures.is_empty() => Some(rule_err(
+                    "alternatives.all",
+                    "does not match all of the required types",
+                )),
+                // A single alternative fails just like the schema itself would
+                Mode::Any if matched == 0 && failures.len() == 1 => {
+                    return Err(failures.remove(0));
+                }
+                Mode::Any | Mode::One if matched == 0 => Some(rule_err(
+                    "alternatives.match",
+                    "does not match any of the allowed types",
+                )),
+                Mode::One if matched > 1 => Some(rule_err(
+                    "alternatives.one",
+                    "matches more than one allowed type",
+                )),
+                Mode::All | Mode::Any | Mode::One => None,
+            };
+            if let Some(failure) = failure {
+                return Err(failure.into());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push(
+            match self.base.flags.extra_flags.r#match.unwrap_or_default() {
+                Mode::All => "alternatives.all",
+                Mode::Any => "alternatives.match",
+                Mode::One => "alternatives.one",
+            },
+        );
+        for alternative in &self.matches {
+            rule_names.extend(alternative.schema.rule_names());
+        }
+        rule_names
+    }
+}
+
+// Conditional alternatives (ref/is/then/otherwise) are not supported.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct Match {
+    schema: crate::Validator,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    // How many of the alternatives must match (defaults to any)
+    r#match: Option<Mode>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+enum Mode {
+    All,
+    #[default]
+    Any,
+    One,
 }
@@ -0,0 +1,219 @@
+use serde::Deserialize;
+
+use crate::{generic, require, rule_err, Args, Failure, StringOrNumber};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Validator {
+    #[serde(default)]
+    rules: Vec<Rule>,
+    // Each item must match one of these
+    items: Option<Vec<crate::Validator>>,
+    #[serde(flatten)]
+    pub base: generic::Validator<serde_json::Value, Flags>,
+}
+
+impl Validator {
+    pub(crate) fn validate(self, value: &Option<serde_json::Value>) -> Result<(), Failure> {
+        self.base.validate_presence(value)?;
+        if let Some(value) = value {
+            let array = match value {
+                serde_json::Value::Array(array) => array,
+                _ => return Err(rule_err("array.base", "must be an array").into()),
+            };
+
+            self.base.validate_value(value)?;
+            if let Some(items) = self.items {
+                for (index, item) in array.iter().enumerate() {
+                    Self::validate_item(&items, index, item)?;
+                }
+            }
+            for rule in self.rules {
+                rule.validate(array)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_item(
+        items: &[crate::Validator],
+        index: usize,
+        item: &serde_json::Value,
+    ) -> Result<(), Failure> {
+        let item = Some(item.clone());
+        match items {
+            // A single item schema reports its own error
+            [schema] => schema
+                .clone()
+                .check_nested(StringOrNumber::Number(index), &item),
+            _ => {
+                if items
+                    .iter()
+                    .any(|schema| schema.clone().check(&item).is_ok())
+                {
+                    Ok(())
+                } else {
+                    let mut failure: Failure =
+                        rule_err("array.includes", "does not match any of the allowed types")
+                            .into();
+                    failure.path.push(StringOrNumber::Number(index));
+                    Err(failure)
+                }
+            }
+        }
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push("array.base");
+        if let Some(items) = &self.items {
+            if items.len() > 1 {
+                rule_names.push("array.includes");
+            }
+            for schema in items {
+                rule_names.extend(schema.rule_names());
+            }
+        }
+        rule_names.extend(self.rules.iter().map(Rule::rule_name));
+        rule_names
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+#[serde(tag = "name")]
+enum Rule {
+    // Has(Args<Schema>),
+    Length(Args<Limit>),
+    Max(Args<Limit>),
+    Min(Args<Limit>),
+    // Ordered,
+    // Sort(Args<Options<SortOptions>>),
+    Unique(Unique),
+}
+
+impl Rule {
+    fn validate(self, array: &[serde_json::Value]) -> Result<(), Failure> {
+        match self {
+            Self::Length(rule) => require(
+                array.len() == rule.args.limit,
+                "array.length",
+                format!("must contain {} items", rule.args.limit),
+            )?,
+            Self::Max(rule) => require(
+                array.len() <= rule.args.limit,
+                "array.max",
+                format!(
+                    "must contain less than or equal to {} items",
+                    rule.args.limit
+                ),
+            )?,
+            Self::Min(rule) => require(
+                array.len() >= rule.args.limit,
+                "array.min",
+                format!("must contain at least {} items", rule.args.limit),
+            )?,
+            Self::Unique(rule) => rule.validate(array)?,
+        }
+        Ok(())
+    }
+
+    fn rule_name(&self) -> &'static str {
+        match self {
+            Self::Length(_) => "array.length",
+            Self::Max(_) => "array.max",
+            Self::Min(_) => "array.min",
+            Self::Unique(_) => "array.unique",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct Limit {
+    limit: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct Unique {
+    #[serde(default)]
+    args: UniqueArgs,
+}
+
+impl Unique {
+    fn validate(self, array: &[serde_json::Value]) -> Result<(), Failure> {
+        let separator = self.args.options.separator.unwrap_or(".".into());
+        let path: Option<Vec<&str>> = self
+            .args
+            .comparator
+            .as_deref()
+            .map(|comparator| comparator.split(separator.as_str()).collect());
+
+        let mut seen: Vec<&serde_json::Value> = vec![];
+        for (index, item) in array.iter().enumerate() {
+            // Compare the whole item, or the value at the comparator path in it
+            let compared = match &path {
+                Some(path) => path
+                    .iter()
+                    .try_fold(item, |value, key| value.get(key))
+                    .filter(|value| !value.is_null()),
+                None => Some(item),
+            };
+            let Some(compared) = compared else {
+                if self.args.options.ignore_undefined {
+                    continue;
+                }
+                // Missing values compare equal to each other
+                if seen.contains(&&serde_json::Value::Null) {
+                    return Err(Self::duplicate(index));
+                }
+                seen.push(&serde_json::Value::Null);
+                continue;
+            };
+            if seen.contains(&compared) {
+                return Err(Self::duplicate(index));
+            }
+            seen.push(compared);
+        }
+        Ok(())
+    }
+
+    fn duplicate(index: usize) -> Failure {
+        let mut failure: Failure = rule_err("array.unique", "contains a duplicate value").into();
+        failure.path.push(StringOrNumber::Number(index));
+        failure
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct UniqueArgs {
+    // Path to the value to compare items by (comparator functions are not supported)
+    comparator: Option<String>,
+    #[serde(default)]
+    options: UniqueOptions,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct UniqueOptions {
+    // Skip items whose compared value is missing, instead of treating them as duplicates
+    #[serde(default)]
+    ignore_undefined: bool,
+    separator: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    // sparse: Option<bool>,
+    // single: Option<bool>,
+}
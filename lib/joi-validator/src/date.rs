@@ -1,31 +1,200 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
 use serde::Deserialize;
 
-use crate::{generic, Args, Limit};
+use crate::{generic, require, rule_err, Args};
 
-pub type Validator = generic::Validator<DateTime<Utc>, Rule, Flags>;
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Validator {
+    #[serde(default)]
+    rules: Vec<Rule>,
+    #[serde(flatten)]
+    pub base: generic::Validator<DateTime<Utc>, Flags>,
+}
+
+impl Validator {
+    pub fn validate(self, value: &Option<serde_json::Value>) -> Result<(), (String, String)> {
+        self.base.validate_presence(value)?;
+        if let Some(value) = value {
+            let format = self.base.flags.extra_flags.format;
+            let value = parse(value, format).ok_or_else(|| match format {
+                Some(format) => rule_err(
+                    "date.format",
+                    format!("must be in {} format", format.description()),
+                ),
+                None => rule_err("date.base", "must be a valid date"),
+            })?;
+
+            // Now that we have the date, validate it
+            self.base.validate_value(&value)?;
+            for rule in self.rules {
+                rule.validate(&value)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push(match self.base.flags.extra_flags.format {
+            Some(_) => "date.format",
+            None => "date.base",
+        });
+        rule_names.extend(self.rules.iter().map(Rule::rule_name));
+        rule_names
+    }
+}
+
+// Parses a date the way Joi does: numbers (and numeric strings) are timestamps, and other strings
+// must be dates. The "iso" format only accepts ISO 8601 strings.
+fn parse(value: &serde_json::Value, format: Option<Format>) -> Option<DateTime<Utc>> {
+    let timestamp = match value {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(string) => string.trim().parse::<f64>().ok(),
+        _ => return None,
+    };
+    match (timestamp, format) {
+        (Some(_), Some(Format::Iso)) => None,
+        (Some(seconds), Some(Format::Unix)) => from_millis(seconds * 1000.0),
+        (Some(millis), Some(Format::Javascript) | None) => from_millis(millis),
+        (None, Some(Format::Javascript | Format::Unix)) => None,
+        (None, Some(Format::Iso) | None) => value.as_str().and_then(parse_iso),
+    }
+}
+
+fn from_millis(millis: f64) -> Option<DateTime<Utc>> {
+    if !millis.is_finite() {
+        return None;
+    }
+    Utc.timestamp_millis_opt(millis.round() as i64).single()
+}
+
+fn parse_iso(string: &str) -> Option<DateTime<Utc>> {
+    let string = string.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(string) {
+        return Some(date.with_timezone(&Utc));
+    }
+    // Dates and times without an offset are UTC
+    if let Ok(date) = NaiveDateTime::parse_from_str(string, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(date.and_utc());
+    }
+    NaiveDate::parse_from_str(string, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
 #[serde(tag = "name")]
-pub enum Rule {
-    Greater(Args<Limit<DateTime<Utc>>>),
-    Less(Args<Limit<DateTime<Utc>>>),
-    Max(Args<Limit<DateTime<Utc>>>),
-    Min(Args<Limit<DateTime<Utc>>>),
+enum Rule {
+    Greater(Args<Limit>),
+    Less(Args<Limit>),
+    Max(Args<Limit>),
+    Min(Args<Limit>),
+}
+
+impl Rule {
+    fn validate(&self, value: &DateTime<Utc>) -> Result<(), (String, String)> {
+        match self {
+            Rule::Greater(rule) => require(
+                value > &rule.args.date.resolve(),
+                "date.greater",
+                format!("must be greater than {}", rule.args.date),
+            ),
+            Rule::Less(rule) => require(
+                value < &rule.args.date.resolve(),
+                "date.less",
+                format!("must be less than {}", rule.args.date),
+            ),
+            Rule::Max(rule) => require(
+                value <= &rule.args.date.resolve(),
+                "date.max",
+                format!("must be less than or equal to {}", rule.args.date),
+            ),
+            Rule::Min(rule) => require(
+                value >= &rule.args.date.resolve(),
+                "date.min",
+                format!("must be greater than or equal to {}", rule.args.date),
+            ),
+        }
+    }
+
+    fn rule_name(&self) -> &'static str {
+        match self {
+            Rule::Greater(_) => "date.greater",
+            Rule::Less(_) => "date.less",
+            Rule::Max(_) => "date.max",
+            Rule::Min(_) => "date.min",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
-pub struct Flags {
-    pub format: Option<Format>,
+struct Limit {
+    date: LimitDate,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum LimitDate {
+    Now(Now),
+    Date(DateTime<Utc>),
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub enum Format {
+enum Now {
+    Now,
+}
+
+impl LimitDate {
+    fn resolve(&self) -> DateTime<Utc> {
+        match self {
+            LimitDate::Now(Now::Now) => Utc::now(),
+            LimitDate::Date(date) => *date,
+        }
+    }
+}
+
+impl std::fmt::Display for LimitDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitDate::Now(Now::Now) => write!(f, "\"now\""),
+            LimitDate::Date(date) => write!(
+                f,
+                "\"{}\"",
+                date.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    format: Option<Format>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Format {
     Iso,        // Joi.date().iso()
     Javascript, // Joi.date().timestamp()
+    Unix,       // Joi.date().timestamp("unix")
+}
+
+impl Format {
+    fn description(self) -> &'static str {
+        match self {
+            Format::Iso => "ISO 8601 date",
+            Format::Javascript => "timestamp or number of milliseconds",
+            Format::Unix => "timestamp or number of seconds",
+        }
+    }
 }
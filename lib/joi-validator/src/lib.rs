@@ -28,12 +28,13 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::serde_as;
 
-// These are commented out until we decide to support them in any way.
-// pub mod alternatives;
-// pub mod date;
+mod alternatives;
+mod array;
 mod boolean;
+mod date;
 pub mod generic;
 mod number;
+mod object;
 mod string;
 #[cfg(test)]
 mod test;
@@ -50,10 +51,12 @@ pub enum Error {
 #[serde(deny_unknown_fields)]
 #[serde(tag = "type")]
 pub enum Validator {
-    // Alternatives(alternatives::Validator),
+    Alternatives(alternatives::Validator),
+    Array(array::Validator),
     Boolean(boolean::Validator),
-    // Date(date::Validator),
+    Date(date::Validator),
     Number(number::Validator),
+    Object(object::Validator),
     String(string::Validator),
 }
 
@@ -65,34 +68,69 @@ impl Validator {
         value: &Option<serde_json::Value>,
     ) -> ValidateResponse<Option<serde_json::Value>> {
         let label = self.take_label().unwrap_or("value".into());
-        let result = match self {
-            Validator::Boolean(validator) => validator.validate(value),
-            Validator::Number(validator) => validator.validate(value),
-            Validator::String(validator) => validator.validate(value),
-        };
         ValidateResponse {
             value: value.clone(),
-            error: match result {
+            error: match self.check(value) {
                 Ok(()) => None,
-                Err((r#type, message)) => Some(ValidateError {
-                    _original: value.clone(),
-                    details: vec![ValidateErrorDetails {
-                        message: format!("{} {}", Self::to_json_string(&label), message),
-                        r#type,
-                        path: vec![],
-                        context: ValidateContext {
-                            label,
-                            value: value.clone(),
-                            extra: None,
-                        },
-                    }],
-                }),
+                Err(failure) => {
+                    let label = failure
+                        .label
+                        .or_else(|| path_label(&failure.path))
+                        .unwrap_or(label);
+                    Some(ValidateError {
+                        _original: value.clone(),
+                        details: vec![ValidateErrorDetails {
+                            message: format!(
+                                "{} {}",
+                                Self::to_json_string(&label),
+                                failure.message
+                            ),
+                            r#type: failure.r#type,
+                            path: failure.path,
+                            context: ValidateContext {
+                                label,
+                                value: value.clone(),
+                                extra: None,
+                            },
+                        }],
+                    })
+                }
             },
             warning: None,
             artifacts: None,
         }
     }
 
+    // Validates a value without producing a Joi response, so validators can be nested.
+    fn check(self, value: &Option<serde_json::Value>) -> Result<(), Failure> {
+        match self {
+            Validator::Alternatives(validator) => validator.validate(value),
+            Validator::Array(validator) => validator.validate(value),
+            Validator::Boolean(validator) => validator.validate(value).map_err(Into::into),
+            Validator::Date(validator) => validator.validate(value).map_err(Into::into),
+            Validator::Number(validator) => validator.validate(value).map_err(Into::into),
+            Validator::Object(validator) => validator.validate(value),
+            Validator::String(validator) => validator.validate(value).map_err(Into::into),
+        }
+    }
+
+    // Validates a value found at the given key or index of the value being validated. Errors
+    // coming from this validator itself (rather than from further down) use its label.
+    fn check_nested(
+        mut self,
+        segment: StringOrNumber,
+        value: &Option<serde_json::Value>,
+    ) -> Result<(), Failure> {
+        let label = self.take_label();
+        self.check(value).map_err(|mut failure| {
+            if failure.path.is_empty() && failure.label.is_none() {
+                failure.label = label;
+            }
+            failure.path.insert(0, segment);
+            failure
+        })
+    }
+
     // Outputs a JSON string, with quotes and escapes
     fn to_json_string(string: &str) -> String {
         serde_json::Value::from(string).to_string()
@@ -100,21 +138,71 @@ impl Validator {
 
     fn take_label(&mut self) -> Option<String> {
         match self {
+            Validator::Alternatives(validator) => validator.base.flags.label.take(),
+            Validator::Array(validator) => validator.base.flags.label.take(),
             Validator::Boolean(validator) => validator.base.flags.label.take(),
+            Validator::Date(validator) => validator.base.flags.label.take(),
             Validator::Number(validator) => validator.base.flags.label.take(),
+            Validator::Object(validator) => validator.base.flags.label.take(),
             Validator::String(validator) => validator.base.flags.label.take(),
         }
     }
 
     pub fn rule_names(&self) -> Vec<&'static str> {
         match self {
+            Validator::Alternatives(validator) => validator.rule_names(),
+            Validator::Array(validator) => validator.rule_names(),
             Validator::Boolean(validator) => validator.rule_names(),
+            Validator::Date(validator) => validator.rule_names(),
             Validator::Number(validator) => validator.rule_names(),
+            Validator::Object(validator) => validator.rule_names(),
             Validator::String(validator) => validator.rule_names(),
         }
     }
 }
 
+// A validation failure, possibly for a value nested inside the one being validated.
+#[derive(Debug, Clone)]
+pub(crate) struct Failure {
+    r#type: String,
+    message: String,
+    // Where the failing value is, relative to the value being validated
+    path: Vec<StringOrNumber>,
+    // Label of the nested validator that failed, if it has one
+    label: Option<String>,
+}
+
+impl From<(String, String)> for Failure {
+    fn from((r#type, message): (String, String)) -> Self {
+        Self {
+            r#type,
+            message,
+            path: vec![],
+            label: None,
+        }
+    }
+}
+
+// Labels a nested value by its path the way Joi does, e.g. "tags[0].key"
+fn path_label(path: &[StringOrNumber]) -> Option<String> {
+    if path.is_empty() {
+        return None;
+    }
+    let mut label = String::new();
+    for segment in path {
+        match segment {
+            StringOrNumber::String(key) => {
+                if !label.is_empty() {
+                    label.push('.');
+                }
+                label.push_str(key);
+            }
+            StringOrNumber::Number(index) => label.push_str(&format!("[{index}]")),
+        }
+    }
+    Some(label)
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
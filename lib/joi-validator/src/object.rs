@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::{generic, rule_err, Failure, StringOrNumber};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Validator {
+    #[serde(default)]
+    rules: Vec<Rule>,
+    // Schemas for known keys. If unset, any keys are allowed.
+    keys: Option<BTreeMap<String, crate::Validator>>,
+    #[serde(flatten)]
+    pub base: generic::Validator<serde_json::Value, Flags>,
+}
+
+impl Validator {
+    pub(crate) fn validate(self, value: &Option<serde_json::Value>) -> Result<(), Failure> {
+        self.base.validate_presence(value)?;
+        if let Some(value) = value {
+            let object = match value {
+                serde_json::Value::Object(object) => object,
+                _ => return Err(rule_err("object.base", "must be of type object").into()),
+            };
+
+            self.base.validate_value(value)?;
+            if let Some(keys) = self.keys {
+                // Check the known keys first (missing keys are validated as undefined, so
+                // "required" is enforced by the key's own schema)
+                for (key, schema) in &keys {
+                    schema.clone().check_nested(
+                        StringOrNumber::String(key.clone()),
+                        &object.get(key).cloned(),
+                    )?;
+                }
+                if !self.base.flags.extra_flags.unknown {
+                    if let Some(key) = object.keys().find(|key| !keys.contains_key(*key)) {
+                        let mut failure: Failure =
+                            rule_err("object.unknown", "is not allowed").into();
+                        failure.path.push(StringOrNumber::String(key.clone()));
+                        return Err(failure);
+                    }
+                }
+            }
+            for rule in self.rules {
+                rule.validate(object)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push("object.base");
+        if let Some(keys) = &self.keys {
+            if !self.base.flags.extra_flags.unknown {
+                rule_names.push("object.unknown");
+            }
+            for schema in keys.values() {
+                rule_names.extend(schema.rule_names());
+            }
+        }
+        rule_names
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+#[serde(tag = "name")]
+enum Rule {}
+
+impl Rule {
+    fn validate(
+        &self,
+        _object: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), Failure> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    // Set true to allow keys that have no schema
+    #[serde(default)]
+    unknown: bool,
+}
@@ -29,6 +29,32 @@ pub(crate) fn invalid(json: &str, value: impl Into<serde_json::Value>) -> Result
     invalid_opt(json, Some(value.into()))
 }
 
+pub(crate) fn invalid_with_message(
+    json: &str,
+    value: impl Into<serde_json::Value>,
+    message: &str,
+) -> Result<()> {
+    let validator: Validator = serde_json::from_str(json)?;
+    let value = Some(value.into());
+    match validator.validate(&value).error {
+        Some(error)
+            if error
+                .details
+                .iter()
+                .any(|details| details.message == message) =>
+        {
+            Ok(())
+        }
+        Some(error) => Err(eyre!(
+            "expected {:?} on {:?}, got: {:?}",
+            message,
+            value,
+            error
+        )),
+        None => Err(eyre!("expected validation error on {:?}", value)),
+    }
+}
+
 pub(crate) fn unsupported(json: &str) -> Result<()> {
     match serde_json::from_str::<Validator>(json) {
         Ok(_) => Err(eyre!("expected schema to be unsupported: {:?}", json)),
@@ -53,7 +79,8 @@ mod any {
     fn unsupported_types() -> Result<()> {
         unsupported(r#"{ "type": "any" }"#)?;
         unsupported(r#"{ "type": "alternatives" }"#)?;
-        unsupported(r#"{ "type": "date" }"#)?;
+        unsupported(r#"{ "type": "link" }"#)?;
+        unsupported(r#"{ "type": "symbol" }"#)?;
         Ok(())
     }
 }
@@ -861,3 +888,361 @@ mod string {
         Ok(())
     }
 }
+
+mod date {
+    use super::*;
+
+    #[test]
+    fn type_date() -> Result<()> {
+        let joi = r#"{ "type": "date" }"#;
+        valid_opt(joi, None)?;
+        valid(joi, "2024-02-29")?;
+        valid(joi, "2024-02-29T12:34:56Z")?;
+        valid(joi, "2024-02-29T12:34:56.789+02:00")?;
+        valid(joi, "2024-02-29T12:34:56")?;
+        valid(joi, 0)?;
+        valid(joi, 1709210096000i64)?;
+        valid(joi, "1709210096000")?;
+        invalid(joi, "")?;
+        invalid(joi, "a")?;
+        invalid(joi, "2024-02-30")?;
+        invalid(joi, true)?;
+        invalid(joi, json!([]))?;
+        invalid(joi, json!({}))?;
+        invalid(joi, json!(null))?;
+        Ok(())
+    }
+
+    #[test]
+    fn formats() -> Result<()> {
+        let joi = r#"{ "type": "date", "flags": { "format": "iso" } }"#;
+        valid(joi, "2024-02-29")?;
+        valid(joi, "2024-02-29T12:34:56Z")?;
+        invalid(joi, 1709210096000i64)?;
+        invalid(joi, "1709210096000")?;
+        invalid_with_message(
+            joi,
+            "yesterday",
+            r#""value" must be in ISO 8601 date format"#,
+        )?;
+
+        let joi = r#"{ "type": "date", "flags": { "format": "javascript" } }"#;
+        valid(joi, 1709210096000i64)?;
+        valid(joi, "1709210096000")?;
+        invalid(joi, "2024-02-29")?;
+
+        let joi = r#"{ "type": "date", "flags": { "format": "unix", "presence": "required" } }"#;
+        valid(joi, 1709210096)?;
+        valid(joi, 1709210096.5)?;
+        invalid(joi, "2024-02-29")?;
+        invalid_opt(joi, None)?;
+
+        unsupported(r#"{ "type": "date", "flags": { "format": "rfc2822" } }"#)?;
+        Ok(())
+    }
+
+    #[test]
+    fn limits() -> Result<()> {
+        let joi = r#"{ "type": "date", "rules": [
+            { "name": "min", "args": { "date": "2024-01-01T00:00:00.000Z" } },
+            { "name": "less", "args": { "date": "2025-01-01T00:00:00.000Z" } }
+        ] }"#;
+        invalid(joi, "2023-12-31T23:59:59Z")?;
+        valid(joi, "2024-01-01")?;
+        valid(joi, "2024-12-31T23:59:59Z")?;
+        invalid(joi, "2025-01-01")?;
+        invalid_with_message(
+            joi,
+            "2023-06-01",
+            r#""value" must be greater than or equal to "2024-01-01T00:00:00.000Z""#,
+        )?;
+
+        let joi = r#"{ "type": "date", "rules": [
+            { "name": "greater", "args": { "date": "2024-01-01T00:00:00.000Z" } },
+            { "name": "max", "args": { "date": "now" } }
+        ] }"#;
+        invalid(joi, "2024-01-01")?;
+        valid(joi, "2024-01-02")?;
+        invalid(joi, "2999-01-01")?;
+
+        unsupported(r#"{ "type": "date", "rules": [ { "name": "min" } ] }"#)?;
+        unsupported(
+            r#"{ "type": "date", "rules": [ { "name": "min", "args": { "date": "soon" } } ] }"#,
+        )?;
+        unsupported(
+            r#"{ "type": "date", "rules": [ { "name": "min", "args": { "limit": 2 } } ] }"#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn valid_values() -> Result<()> {
+        let joi = r#"{ "type": "date", "flags": { "only": true }, "allow": [ "2024-01-01T00:00:00.000Z" ] }"#;
+        valid(joi, "2024-01-01")?;
+        valid(joi, 1704067200000i64)?;
+        invalid(joi, "2024-01-02")?;
+
+        let joi = r#"{ "type": "date", "invalid": [ "2024-01-01T00:00:00.000Z" ] }"#;
+        invalid(joi, "2024-01-01")?;
+        valid(joi, "2024-01-02")?;
+        Ok(())
+    }
+}
+
+mod alternatives {
+    use super::*;
+
+    #[test]
+    fn any() -> Result<()> {
+        let joi = r#"{ "type": "alternatives", "matches": [
+            { "schema": { "type": "number", "rules": [ { "name": "integer" } ] } },
+            { "schema": { "type": "string", "rules": [ { "name": "max", "args": { "limit": 3 } } ] } }
+        ] }"#;
+        valid_opt(joi, None)?;
+        valid(joi, 1)?;
+        valid(joi, "abc")?;
+        invalid(joi, "abcd")?;
+        invalid(joi, true)?;
+        invalid_with_message(
+            joi,
+            json!({}),
+            r#""value" does not match any of the allowed types"#,
+        )?;
+
+        // A single alternative reports its own error
+        let joi =
+            r#"{ "type": "alternatives", "matches": [ { "schema": { "type": "string" } } ] }"#;
+        invalid_with_message(joi, 1, r#""value" must be a string"#)?;
+        Ok(())
+    }
+
+    #[test]
+    fn one_and_all() -> Result<()> {
+        let joi = r#"{ "type": "alternatives", "flags": { "match": "one" }, "matches": [
+            { "schema": { "type": "number" } },
+            { "schema": { "type": "string" } }
+        ] }"#;
+        valid(joi, 1)?;
+        invalid(joi, true)?;
+        // "1" is both a string and a number
+        invalid_with_message(joi, "1", r#""value" matches more than one allowed type"#)?;
+
+        let joi = r#"{ "type": "alternatives", "flags": { "match": "all" }, "matches": [
+            { "schema": { "type": "number" } },
+            { "schema": { "type": "string" } }
+        ] }"#;
+        valid(joi, "1")?;
+        invalid(joi, 1)?;
+        invalid(joi, "a")?;
+        Ok(())
+    }
+
+    #[test]
+    fn required() -> Result<()> {
+        let joi = r#"{ "type": "alternatives", "flags": { "presence": "required" }, "matches": [
+            { "schema": { "type": "number" } }
+        ] }"#;
+        valid(joi, 1)?;
+        invalid_opt(joi, None)?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_matches() -> Result<()> {
+        unsupported(r#"{ "type": "alternatives", "matches": [ { "type": "string" } ] }"#)?;
+        unsupported(
+            r#"{ "type": "alternatives", "matches": [ { "ref": { "path": ["a"] }, "is": { "type": "string" }, "then": { "type": "string" } } ] }"#,
+        )?;
+        unsupported(
+            r#"{ "type": "alternatives", "matches": [ { "schema": { "type": "any" } } ] }"#,
+        )?;
+        unsupported(r#"{ "type": "alternatives", "flags": { "match": "some" }, "matches": [] }"#)?;
+        Ok(())
+    }
+}
+
+mod object {
+    use super::*;
+
+    #[test]
+    fn type_object() -> Result<()> {
+        let joi = r#"{ "type": "object" }"#;
+        valid_opt(joi, None)?;
+        valid(joi, json!({}))?;
+        valid(joi, json!({ "a": 1, "b": [true] }))?;
+        invalid(joi, json!([]))?;
+        invalid(joi, "a")?;
+        invalid(joi, 1)?;
+        invalid(joi, json!(null))?;
+        Ok(())
+    }
+
+    #[test]
+    fn keys() -> Result<()> {
+        let joi = r#"{ "type": "object", "keys": {
+            "name": { "type": "string", "flags": { "presence": "required" } },
+            "port": { "type": "number", "rules": [ { "name": "integer" } ] }
+        } }"#;
+        valid(joi, json!({ "name": "a" }))?;
+        valid(joi, json!({ "name": "a", "port": 80 }))?;
+        invalid_with_message(joi, json!({}), r#""name" is required"#)?;
+        invalid_with_message(
+            joi,
+            json!({ "name": "a", "port": 80.5 }),
+            r#""port" must be an integer"#,
+        )?;
+        invalid_with_message(
+            joi,
+            json!({ "name": "a", "extra": true }),
+            r#""extra" is not allowed"#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn unknown() -> Result<()> {
+        let joi = r#"{ "type": "object", "flags": { "unknown": true }, "keys": {
+            "name": { "type": "string" }
+        } }"#;
+        valid(joi, json!({ "name": "a", "extra": true }))?;
+        invalid(joi, json!({ "name": 1, "extra": true }))?;
+
+        // No keys at all means nothing is allowed
+        let joi = r#"{ "type": "object", "keys": {} }"#;
+        valid(joi, json!({}))?;
+        invalid(joi, json!({ "extra": true }))?;
+        Ok(())
+    }
+
+    #[test]
+    fn nested() -> Result<()> {
+        let joi = r#"{ "type": "object", "keys": {
+            "tags": { "type": "array", "items": [ { "type": "object", "keys": {
+                "key": { "type": "string", "flags": { "presence": "required" } },
+                "value": { "type": "string", "flags": { "label": "Tag Value" } }
+            } } ] }
+        } }"#;
+        valid(joi, json!({ "tags": [{ "key": "a", "value": "b" }] }))?;
+        invalid_with_message(
+            joi,
+            json!({ "tags": [{ "key": "a" }, {}] }),
+            r#""tags[1].key" is required"#,
+        )?;
+        invalid_with_message(
+            joi,
+            json!({ "tags": [{ "key": "a", "value": 1 }] }),
+            r#""Tag Value" must be a string"#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_basics() -> Result<()> {
+        unsupported(
+            r#"{ "type": "object", "rules": [ { "name": "min", "args": { "limit": 1 } } ] }"#,
+        )?;
+        unsupported(r#"{ "type": "object", "keys": { "a": { "type": "any" } } }"#)?;
+        unsupported(r#"{ "type": "object", "patterns": [] }"#)?;
+        unsupported(r#"{ "type": "object", "flags": { "extra": true } }"#)?;
+        Ok(())
+    }
+}
+
+mod array {
+    use super::*;
+
+    #[test]
+    fn type_array() -> Result<()> {
+        let joi = r#"{ "type": "array" }"#;
+        valid_opt(joi, None)?;
+        valid(joi, json!([]))?;
+        valid(joi, json!([1, "a", {}]))?;
+        invalid(joi, json!({}))?;
+        invalid(joi, "a")?;
+        invalid(joi, json!(null))?;
+        Ok(())
+    }
+
+    #[test]
+    fn items() -> Result<()> {
+        let joi = r#"{ "type": "array", "items": [ { "type": "string" } ] }"#;
+        valid(joi, json!([]))?;
+        valid(joi, json!(["a", "b"]))?;
+        invalid_with_message(joi, json!(["a", 1]), r#""[1]" must be a string"#)?;
+
+        let joi =
+            r#"{ "type": "array", "items": [ { "type": "string" }, { "type": "boolean" } ] }"#;
+        valid(joi, json!(["a", true]))?;
+        invalid_with_message(
+            joi,
+            json!(["a", true, {}]),
+            r#""[2]" does not match any of the allowed types"#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn limits() -> Result<()> {
+        let joi = r#"{ "type": "array", "rules": [
+            { "name": "min", "args": { "limit": 1 } },
+            { "name": "max", "args": { "limit": 2 } }
+        ] }"#;
+        invalid(joi, json!([]))?;
+        valid(joi, json!([1]))?;
+        valid(joi, json!([1, 2]))?;
+        invalid(joi, json!([1, 2, 3]))?;
+
+        let joi =
+            r#"{ "type": "array", "rules": [ { "name": "length", "args": { "limit": 2 } } ] }"#;
+        invalid(joi, json!([1]))?;
+        valid(joi, json!([1, 2]))?;
+        invalid_with_message(joi, json!([1, 2, 3]), r#""value" must contain 2 items"#)?;
+
+        unsupported(r#"{ "type": "array", "rules": [ { "name": "min" } ] }"#)?;
+        unsupported(
+            r#"{ "type": "array", "rules": [ { "name": "max", "args": { "limit": -1 } } ] }"#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn unique() -> Result<()> {
+        let joi = r#"{ "type": "array", "rules": [ { "name": "unique" } ] }"#;
+        valid(joi, json!([1, "1", { "a": 1 }, { "a": 2 }]))?;
+        invalid_with_message(
+            joi,
+            json!([{ "a": 1 }, 2, { "a": 1 }]),
+            r#""[2]" contains a duplicate value"#,
+        )?;
+
+        let joi = r#"{ "type": "array", "rules": [
+            { "name": "unique", "args": { "comparator": "spec.name", "options": {} } }
+        ] }"#;
+        valid(
+            joi,
+            json!([{ "spec": { "name": "a" } }, { "spec": { "name": "b" } }]),
+        )?;
+        invalid(
+            joi,
+            json!([{ "spec": { "name": "a" } }, { "spec": { "name": "a" }, "x": 1 }]),
+        )?;
+        invalid(joi, json!([{}, {}]))?;
+
+        let joi = r#"{ "type": "array", "rules": [
+            { "name": "unique", "args": { "comparator": "name", "options": { "ignoreUndefined": true } } }
+        ] }"#;
+        valid(joi, json!([{}, {}, { "name": "a" }]))?;
+        invalid(joi, json!([{ "name": "a" }, { "name": "a" }]))?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_basics() -> Result<()> {
+        unsupported(r#"{ "type": "array", "ordered": [ { "type": "string" } ] }"#)?;
+        unsupported(r#"{ "type": "array", "items": [ { "type": "any" } ] }"#)?;
+        unsupported(r#"{ "type": "array", "rules": [ { "name": "has" } ] }"#)?;
+        unsupported(r#"{ "type": "array", "flags": { "sparse": true } }"#)?;
+        unsupported(r#"{ "type": "array", "flags": { "single": true } }"#)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Enqueues the change plans of change sets once they have settled. Plans are requested on every
+//! dependent values update, which is debounced by the requests table rather than in memory, so
+//! every pinga instance runs the scheduler; claiming a request is atomic, so only one instance
+//! enqueues the plans for it.
+
+use std::time::Duration;
+
+use dal::{
+    action::{change_plan::ActionChangePlanRequest, ActionError},
+    DalContextBuilder, TransactionsError,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+/// How often to check for change sets that have settled.
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+enum ChangePlanSchedulerError {
+    #[error("action error: {0}")]
+    Action(#[from] ActionError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+type ChangePlanSchedulerResult<T> = Result<T, ChangePlanSchedulerError>;
+
+pub(crate) async fn run(ctx_builder: DalContextBuilder, shutdown_token: CancellationToken) {
+    let mut interval = tokio::time::interval(TICK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(err) = enqueue_due_plans(&ctx_builder).await {
+                    error!(si.error.message = ?err, "unable to enqueue due change plans");
+                }
+            }
+            _ = shutdown_token.cancelled() => {
+                debug!("change plan scheduler received cancellation");
+                break;
+            }
+        }
+    }
+}
+
+#[instrument(
+    name = "pinga.change_plan_scheduler.enqueue_due_plans",
+    level = "debug",
+    skip_all
+)]
+async fn enqueue_due_plans(ctx_builder: &DalContextBuilder) -> ChangePlanSchedulerResult<()> {
+    let ctx = ctx_builder.build_default(None).await?;
+    let requests = ActionChangePlanRequest::claim_due(&ctx).await?;
+    ctx.commit_no_rebase().await?;
+
+    for request in requests {
+        // One workspace failing shouldn't hold back the others
+        if let Err(err) = enqueue(ctx_builder, &request).await {
+            error!(
+                si.error.message = ?err,
+                si.workspace.id = %request.workspace_pk(),
+                si.change_set.id = %request.change_set_id(),
+                "unable to enqueue change plans"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn enqueue(
+    ctx_builder: &DalContextBuilder,
+    request: &ActionChangePlanRequest,
+) -> ChangePlanSchedulerResult<()> {
+    let ctx = ctx_builder
+        .build_for_change_set_as_system(request.workspace_pk(), request.change_set_id(), None)
+        .await?;
+    // The plan funcs run in whichever pinga instance picks up the job
+    ctx.enqueue_compute_action_change_plans().await?;
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 mod app_state;
+mod change_plan_scheduler;
 mod config;
 mod handlers;
+mod maintenance_scheduler;
+mod refresh_scheduler;
+mod retry_scheduler;
 pub mod server;
 
 use std::io;
@@ -0,0 +1,98 @@
+//! Wakes up action dispatch on HEAD when a [`MaintenanceWindow`] opens, since actions held for
+//! the window would otherwise wait for the next unrelated change to HEAD. Every pinga instance
+//! runs the scheduler; claiming a window opening is atomic, so each opening only wakes dispatch
+//! once.
+
+use std::time::Duration;
+
+use dal::{
+    action::maintenance_window::{MaintenanceWindow, MaintenanceWindowError},
+    DalContextBuilder, TransactionsError, Workspace, WorkspaceError,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+/// How often to check for windows that have opened. Schedules have a granularity of a minute,
+/// so this bounds how late dispatch is woken up.
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+enum MaintenanceSchedulerError {
+    #[error("maintenance window error: {0}")]
+    MaintenanceWindow(#[from] MaintenanceWindowError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace error: {0}")]
+    Workspace(#[from] WorkspaceError),
+}
+
+type MaintenanceSchedulerResult<T> = Result<T, MaintenanceSchedulerError>;
+
+pub(crate) async fn run(ctx_builder: DalContextBuilder, shutdown_token: CancellationToken) {
+    let mut interval = tokio::time::interval(TICK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(err) = wake_opened_windows(&ctx_builder).await {
+                    error!(si.error.message = ?err, "unable to check for opened maintenance windows");
+                }
+            }
+            _ = shutdown_token.cancelled() => {
+                debug!("maintenance scheduler received cancellation");
+                break;
+            }
+        }
+    }
+}
+
+#[instrument(
+    name = "pinga.maintenance_scheduler.wake_opened_windows",
+    level = "debug",
+    skip_all
+)]
+async fn wake_opened_windows(ctx_builder: &DalContextBuilder) -> MaintenanceSchedulerResult<()> {
+    let ctx = ctx_builder.build_default(None).await?;
+    let windows = MaintenanceWindow::claim_opened(&ctx).await?;
+    ctx.commit_no_rebase().await?;
+
+    for window in windows {
+        // One workspace failing shouldn't hold back the others
+        if let Err(err) = wake_dispatch(ctx_builder, &window).await {
+            error!(
+                si.error.message = ?err,
+                si.workspace.id = %window.workspace_pk(),
+                "unable to wake action dispatch for maintenance window"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn wake_dispatch(
+    ctx_builder: &DalContextBuilder,
+    window: &MaintenanceWindow,
+) -> MaintenanceSchedulerResult<()> {
+    let ctx = ctx_builder.build_default(None).await?;
+    let workspace = Workspace::get_by_pk(&ctx, window.workspace_pk()).await?;
+
+    let ctx = ctx_builder
+        .build_for_change_set_as_system(
+            window.workspace_pk(),
+            workspace.default_change_set_id(),
+            None,
+        )
+        .await?;
+    info!(
+        si.workspace.id = %window.workspace_pk(),
+        window = %window.pk(),
+        "maintenance window opened, waking action dispatch"
+    );
+    MaintenanceWindow::wake_dispatch(&ctx).await?;
+
+    Ok(())
+}
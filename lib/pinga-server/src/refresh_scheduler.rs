@@ -0,0 +1,95 @@
+//! Periodically enqueues the refresh actions for [`RefreshSchedules`](RefreshSchedule) that are
+//! due. Every pinga instance runs the scheduler; claiming a schedule is atomic, so each schedule
+//! is only run by one of them.
+
+use std::time::Duration;
+
+use dal::{
+    action::refresh_schedule::{RefreshSchedule, RefreshScheduleError},
+    DalContextBuilder, TransactionsError, Workspace, WorkspaceError,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+/// How often to check for schedules that are due. Schedules can't run more often than every
+/// [`MIN_INTERVAL_SECONDS`](dal::action::refresh_schedule::MIN_INTERVAL_SECONDS), so this only
+/// bounds how late a schedule can run.
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+enum RefreshSchedulerError {
+    #[error("refresh schedule error: {0}")]
+    RefreshSchedule(#[from] RefreshScheduleError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace error: {0}")]
+    Workspace(#[from] WorkspaceError),
+}
+
+type RefreshSchedulerResult<T> = Result<T, RefreshSchedulerError>;
+
+pub(crate) async fn run(ctx_builder: DalContextBuilder, shutdown_token: CancellationToken) {
+    let mut interval = tokio::time::interval(TICK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(err) = run_due_schedules(&ctx_builder).await {
+                    error!(si.error.message = ?err, "unable to run due refresh schedules");
+                }
+            }
+            _ = shutdown_token.cancelled() => {
+                debug!("refresh scheduler received cancellation");
+                break;
+            }
+        }
+    }
+}
+
+#[instrument(
+    name = "pinga.refresh_scheduler.run_due_schedules",
+    level = "debug",
+    skip_all
+)]
+async fn run_due_schedules(ctx_builder: &DalContextBuilder) -> RefreshSchedulerResult<()> {
+    let ctx = ctx_builder.build_default(None).await?;
+    let schedules = RefreshSchedule::claim_due(&ctx).await?;
+    ctx.commit_no_rebase().await?;
+
+    for schedule in schedules {
+        // One workspace failing shouldn't hold back the others
+        if let Err(err) = run_schedule(ctx_builder, &schedule).await {
+            error!(
+                si.error.message = ?err,
+                si.workspace.id = %schedule.workspace_pk(),
+                "unable to run refresh schedule"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_schedule(
+    ctx_builder: &DalContextBuilder,
+    schedule: &RefreshSchedule,
+) -> RefreshSchedulerResult<()> {
+    let ctx = ctx_builder.build_default(None).await?;
+    let workspace = Workspace::get_by_pk(&ctx, schedule.workspace_pk()).await?;
+
+    let ctx = ctx_builder
+        .build_for_change_set_as_system(
+            schedule.workspace_pk(),
+            workspace.default_change_set_id(),
+            None,
+        )
+        .await?;
+    if schedule.enqueue_refreshes(&ctx).await? > 0 {
+        ctx.commit().await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+//! Queues failed actions again once their [`ActionRetry`] is due, so that the dispatcher runs
+//! the next attempt. Every pinga instance runs the scheduler; claiming a retry is atomic, so each
+//! retry is only queued once.
+
+use std::time::Duration;
+
+use dal::{
+    action::retry_policy::{ActionRetry, ActionRetryPolicyError},
+    DalContextBuilder, TransactionsError,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+/// How often to check for retries that are due. Retry delays start in the milliseconds, so this
+/// bounds how late a short retry runs.
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+enum RetrySchedulerError {
+    #[error("action retry policy error: {0}")]
+    ActionRetryPolicy(#[from] ActionRetryPolicyError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+type RetrySchedulerResult<T> = Result<T, RetrySchedulerError>;
+
+pub(crate) async fn run(ctx_builder: DalContextBuilder, shutdown_token: CancellationToken) {
+    let mut interval = tokio::time::interval(TICK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(err) = requeue_due_retries(&ctx_builder).await {
+                    error!(si.error.message = ?err, "unable to requeue due action retries");
+                }
+            }
+            _ = shutdown_token.cancelled() => {
+                debug!("retry scheduler received cancellation");
+                break;
+            }
+        }
+    }
+}
+
+#[instrument(
+    name = "pinga.retry_scheduler.requeue_due_retries",
+    level = "debug",
+    skip_all
+)]
+async fn requeue_due_retries(ctx_builder: &DalContextBuilder) -> RetrySchedulerResult<()> {
+    let ctx = ctx_builder.build_default(None).await?;
+    let retries = ActionRetry::claim_due(&ctx).await?;
+    ctx.commit_no_rebase().await?;
+
+    for retry in retries {
+        // One workspace failing shouldn't hold back the others
+        if let Err(err) = requeue(ctx_builder, &retry).await {
+            error!(
+                si.error.message = ?err,
+                si.workspace.id = %retry.workspace_pk(),
+                si.action.id = %retry.action_id(),
+                "unable to requeue action for retry"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn requeue(ctx_builder: &DalContextBuilder, retry: &ActionRetry) -> RetrySchedulerResult<()> {
+    let ctx = ctx_builder
+        .build_for_change_set_as_system(retry.workspace_pk(), retry.change_set_id(), None)
+        .await?;
+    if retry.requeue(&ctx).await? {
+        info!(
+            si.workspace.id = %retry.workspace_pk(),
+            si.action.id = %retry.action_id(),
+            attempts = retry.attempts(),
+            "requeueing failed action for retry"
+        );
+        // Committing rebases the change set, which dispatches the action
+        ctx.commit().await?;
+    } else {
+        ctx.commit_no_rebase().await?;
+    }
+
+    Ok(())
+}
@@ -34,7 +34,10 @@ use telemetry_utils::metric;
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use veritech_client::Client as VeritechClient;
 
-use crate::{app_state::AppState, handlers, Config, ServerError, ServerResult};
+use crate::{
+    app_state::AppState, change_plan_scheduler, handlers, maintenance_scheduler, refresh_scheduler,
+    retry_scheduler, Config, ServerError, ServerResult,
+};
 
 const CONSUMER_NAME: &str = "pinga-server";
 
@@ -163,6 +166,23 @@ impl Server {
 
         let ctx_builder = DalContext::builder(services_context, false);
 
+        tokio::spawn(refresh_scheduler::run(
+            ctx_builder.clone(),
+            shutdown_token.clone(),
+        ));
+        tokio::spawn(maintenance_scheduler::run(
+            ctx_builder.clone(),
+            shutdown_token.clone(),
+        ));
+        tokio::spawn(retry_scheduler::run(
+            ctx_builder.clone(),
+            shutdown_token.clone(),
+        ));
+        tokio::spawn(change_plan_scheduler::run(
+            ctx_builder.clone(),
+            shutdown_token.clone(),
+        ));
+
         let state = AppState::new(metadata.clone(), concurrency_limit, ctx_builder);
 
         let app = ServiceBuilder::new()
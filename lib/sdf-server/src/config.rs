@@ -124,6 +124,9 @@ pub struct Config {
     #[builder(default)]
     migration_mode: MigrationMode,
 
+    #[builder(default)]
+    func_run_gc_dry_run: bool,
+
     #[builder(default)]
     crypto: VeritechCryptoConfig,
 
@@ -182,6 +185,12 @@ impl Config {
         &self.migration_mode
     }
 
+    /// Whether func run garbage collection only reports what it would delete.
+    #[must_use]
+    pub fn func_run_gc_dry_run(&self) -> bool {
+        self.func_run_gc_dry_run
+    }
+
     /// Gets a reference to the config's nats.
     #[must_use]
     pub fn nats(&self) -> &NatsConfig {
@@ -301,6 +310,8 @@ pub struct ConfigFile {
     #[serde(default)]
     pub migration_mode: MigrationMode,
     #[serde(default)]
+    pub func_run_gc_dry_run: bool,
+    #[serde(default)]
     pub dev_mode: bool,
     #[serde(default)]
     pub jwt_signing_public_key: JwtConfig,
@@ -343,6 +354,7 @@ impl Default for ConfigFile {
             pg: Default::default(),
             nats: Default::default(),
             migration_mode: Default::default(),
+            func_run_gc_dry_run: false,
             jwt_signing_public_key: Default::default(),
             jwt_secondary_signing_public_key: Default::default(),
             crypto: Default::default(),
@@ -380,6 +392,7 @@ impl TryFrom<ConfigFile> for Config {
             nats: value.nats,
             incoming_stream: IncomingStream::default(), // TODO this OK?
             migration_mode: value.migration_mode,
+            func_run_gc_dry_run: value.func_run_gc_dry_run,
             jwt_signing_public_key: value.jwt_signing_public_key,
             jwt_secondary_signing_public_key: value.jwt_secondary_signing_public_key,
             crypto: value.crypto,
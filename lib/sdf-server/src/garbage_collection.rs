@@ -1,6 +1,13 @@
-use std::{collections::HashSet, future::IntoFuture as _};
+use std::{
+    collections::{HashMap, HashSet},
+    future::IntoFuture as _,
+};
 
-use dal::{ChangeSetStatus, ServicesContext, TransactionsError, WorkspaceSnapshotAddress};
+use chrono::Utc;
+use dal::{
+    func::retention::{self, FuncRunGarbage, FuncRunRetentionError, FuncRunRetentionPolicy},
+    ChangeSetStatus, ServicesContext, TransactionsError, WorkspaceSnapshotAddress,
+};
 use si_data_pg::{PgError, PgPoolError};
 use si_layer_cache::LayerDbError;
 use strum::IntoEnumIterator;
@@ -13,6 +20,8 @@ use crate::{init, Config};
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum GarbageCollectorError {
+    #[error("Func run retention error: {0}")]
+    FuncRunRetention(#[from] FuncRunRetentionError),
     #[error("error while initializing: {0}")]
     Init(#[from] init::InitError),
     #[error("Layer DB error: {0}")]
@@ -157,3 +166,100 @@ impl SnapshotGarbageCollector {
         Ok(())
     }
 }
+
+pub struct FuncRunGarbageCollector {
+    services_context: ServicesContext,
+    dry_run: bool,
+}
+
+impl FuncRunGarbageCollector {
+    #[instrument(name = "sdf.func_run_garbage_collector.new", level = "info", skip_all)]
+    pub async fn new(
+        config: Config,
+        task_tracker: &TaskTracker,
+        task_token: CancellationToken,
+    ) -> Result<Self> {
+        let dry_run = config.func_run_gc_dry_run();
+        let (services_context, layer_db_graceful_shutdown) =
+            init::services_context_from_config(&config, task_token).await?;
+
+        task_tracker.spawn(layer_db_graceful_shutdown.into_future());
+
+        Ok(Self {
+            services_context,
+            dry_run,
+        })
+    }
+
+    /// Removes the func runs, logs, and content that each workspace's retention policy no longer
+    /// keeps. In a dry run, only logs what would have been removed.
+    #[instrument(
+        name = "sdf.func_run_garbage_collector.garbage_collect_func_runs",
+        level = "info",
+        skip_all,
+        fields(dry_run = self.dry_run)
+    )]
+    pub async fn garbage_collect_func_runs(self) -> Result<()> {
+        let span = current_span_for_instrument_at!("info");
+
+        let dal_context = self.services_context.clone().into_builder(true);
+        let ctx = dal_context
+            .build_default(None)
+            .await
+            .map_err(|err| span.record_err(err))?;
+        let ctx = &ctx;
+
+        let policies: HashMap<_, _> = FuncRunRetentionPolicy::list_all(ctx)
+            .await?
+            .into_iter()
+            .map(|policy| (policy.workspace_pk(), policy.retention()))
+            .collect();
+        info!(
+            "Found {} workspace(s) with a func run retention policy.",
+            policies.len()
+        );
+
+        let workspace_pks = ctx.layer_db().func_run().list_workspace_ids().await?;
+        info!("Found {} workspace(s) with func runs.", workspace_pks.len());
+
+        let mut total = FuncRunGarbage::default();
+        for workspace_pk in workspace_pks {
+            let retention = policies.get(&workspace_pk).copied().unwrap_or_default();
+            // One workspace failing shouldn't stop the rest from being collected
+            match retention::collect_garbage(ctx, workspace_pk, retention, self.dry_run).await {
+                Ok(garbage) => {
+                    total.func_runs += garbage.func_runs;
+                    total.cas_blobs += garbage.cas_blobs;
+                }
+                Err(err) => {
+                    error!(
+                        si.error.message = ?err,
+                        si.workspace.id = %workspace_pk,
+                        "failed to garbage collect func runs"
+                    );
+                }
+            }
+        }
+
+        // Content marked on earlier passes is only removed once runs that were in flight when it
+        // was marked have had time to be written
+        let swept =
+            retention::sweep_func_run_data(ctx, Utc::now() - retention::SWEEP_DELAY, self.dry_run)
+                .await?;
+
+        if self.dry_run {
+            info!(
+                "Would have deleted {} func run(s), marked {} content store entries, and swept {} marked entries.",
+                total.func_runs, total.cas_blobs, swept
+            );
+        } else {
+            info!(
+                "Deleted {} func run(s), marked {} content store entries, and swept {} marked entries.",
+                total.func_runs, total.cas_blobs, swept
+            );
+        }
+
+        span.record_ok();
+        Ok(())
+    }
+}
@@ -44,7 +44,7 @@ pub use self::{
         Config, ConfigBuilder, ConfigError, ConfigFile, IncomingStream, MigrationMode,
         StandardConfig, StandardConfigFile, WorkspacePermissions, WorkspacePermissionsMode,
     },
-    garbage_collection::SnapshotGarbageCollector,
+    garbage_collection::{FuncRunGarbageCollector, SnapshotGarbageCollector},
     migrations::Migrator,
     nats_multiplexer::CRDT_MULTIPLEXER_SUBJECT,
     server::{Server, ServerMetadata, ServerSocket},
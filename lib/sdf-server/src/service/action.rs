@@ -16,8 +16,10 @@ use super::ApiError;
 use crate::AppState;
 
 mod cancel;
+mod change_plans;
 mod history;
 pub mod list_actions;
+mod plan;
 mod put_on_hold;
 mod retry;
 
@@ -48,6 +50,8 @@ pub enum ActionError {
     InvalidUserSystemInit,
     #[error("layer db error: {0}")]
     LayerDb(#[from] LayerDbError),
+    #[error(transparent)]
+    MaintenanceWindow(#[from] dal::action::maintenance_window::MaintenanceWindowError),
     #[error("no schema found for component {0}")]
     NoSchemaForComponent(ComponentId),
     #[error("no schema variant found for component {0}")]
@@ -83,6 +87,8 @@ impl IntoResponse for ActionError {
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/list", get(list_actions::list_actions))
+        .route("/plan", get(plan::plan))
+        .route("/change_plans", get(change_plans::change_plans))
         .route("/put_on_hold", post(put_on_hold::put_on_hold))
         .route("/cancel", post(cancel::cancel))
         .route("/retry", post(retry::retry))
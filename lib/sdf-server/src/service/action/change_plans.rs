@@ -0,0 +1,25 @@
+use axum::{extract::Query, Json};
+use dal::{action::change_plan::ActionChangePlan, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ActionResult;
+use crate::extract::{v1::AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionChangePlansRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ActionChangePlansResponse = Vec<ActionChangePlan>;
+
+pub async fn change_plans(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ActionChangePlansRequest>,
+) -> ActionResult<Json<ActionChangePlansResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    Ok(Json(ActionChangePlan::list(&ctx).await?))
+}
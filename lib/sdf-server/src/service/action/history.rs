@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use axum::{extract::Query, Json};
 use chrono::{DateTime, Utc};
 use dal::Visibility;
@@ -26,6 +28,9 @@ pub struct ActionHistoryView {
     pub originating_change_set_name: String,
     pub updated_at: DateTime<Utc>,
     pub result: ActionResultState,
+    /// Which run of the action this was, starting at 1. Actions that are retried, automatically
+    /// or by hand, have one entry per attempt.
+    pub attempt: u32,
 }
 
 impl TryFrom<FuncRun> for ActionHistoryView {
@@ -87,6 +92,7 @@ impl TryFrom<FuncRun> for ActionHistoryView {
                 ActionError::ActionHistoryFieldMissing("action_result_state".to_string())
             })?,
             updated_at: func_run.updated_at(),
+            attempt: 1,
         })
     }
 }
@@ -107,7 +113,7 @@ pub async fn history(
 ) -> ActionResult<Json<ActionHistoryResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
-    let mut result = Vec::new();
+    let mut result: Vec<ActionHistoryView> = Vec::new();
     if let Some(action_history_list) = ctx
         .layer_db()
         .func_run()
@@ -119,5 +125,13 @@ pub async fn history(
         }
     }
 
+    // The history is newest first, so count the attempts from the end
+    let mut attempts: HashMap<ActionId, u32> = HashMap::new();
+    for view in result.iter_mut().rev() {
+        let attempt = attempts.entry(view.id).or_default();
+        *attempt += 1;
+        view.attempt = *attempt;
+    }
+
     Ok(Json(result))
 }
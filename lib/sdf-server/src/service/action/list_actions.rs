@@ -1,6 +1,7 @@
 use axum::extract::Query;
 use axum::Json;
 use dal::action::dependency_graph::ActionDependencyGraph;
+use dal::action::maintenance_window::{MaintenanceWindowGate, MaintenanceWindowHold};
 use dal::action::prototype::{ActionKind, ActionPrototype};
 use dal::action::{Action, ActionState};
 use dal::Func;
@@ -31,6 +32,8 @@ pub struct ActionView {
     // includes action ids that impact this status
     // this occurs when ancestors of this action are on hold or have failed
     pub hold_status_influenced_by: Vec<ActionId>,
+    // set when a queued action on HEAD is waiting for a maintenance window to open
+    pub maintenance_window_hold: Option<MaintenanceWindowHold>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -57,6 +60,7 @@ pub async fn list_actions(
     if !action_graph.is_acyclic() {
         warn!("action graph for {:?} has a cycle", request.visibility);
     }
+    let maintenance_window_gate = MaintenanceWindowGate::new(&ctx).await?;
 
     for action_id in action_ids {
         let action = Action::get_by_id(&ctx, action_id).await?;
@@ -71,6 +75,10 @@ pub async fn list_actions(
             .get_last_run_for_action_id(ctx.events_tenancy().workspace_pk, action.id())
             .await?
             .map(|f| f.id());
+        let maintenance_window_hold = match action.state() {
+            ActionState::Queued => maintenance_window_gate.hold_for(&ctx, action_id).await?,
+            _ => None,
+        };
 
         let action_view = ActionView {
             id: action_id,
@@ -90,6 +98,7 @@ pub async fn list_actions(
                 action_id,
             )
             .await?,
+            maintenance_window_hold,
         };
         queued.push(action_view);
     }
@@ -0,0 +1,37 @@
+use axum::{extract::Query, Json};
+use dal::{action::plan::ActionPlan, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ActionResult;
+use crate::extract::{v1::AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlanRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlanResponse {
+    pub plan: ActionPlan,
+    pub dot: String,
+    pub mermaid: String,
+}
+
+pub async fn plan(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ActionPlanRequest>,
+) -> ActionResult<Json<ActionPlanResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let plan = ActionPlan::for_workspace(&ctx).await?;
+
+    Ok(Json(ActionPlanResponse {
+        dot: plan.to_dot(),
+        mermaid: plan.to_mermaid(),
+        plan,
+    }))
+}
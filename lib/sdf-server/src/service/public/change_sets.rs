@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use axum::{
     extract::Host,
     http::StatusCode,
@@ -9,6 +11,7 @@ use axum::{
 use dal::{
     action::{
         prototype::{ActionKind, ActionPrototype},
+        selection::{ActionSelection, ActionSelectionSummary},
         Action, ActionState,
     },
     change_set::ChangeSet,
@@ -36,6 +39,7 @@ pub fn routes(state: AppState) -> Router<AppState> {
         Router::new()
             .nest("/components", super::components::routes())
             .nest("/management", super::management::routes())
+            .route("/action_selection", post(action_selection))
             .route("/request_approval", post(request_approval))
             .route(
                 "/force_apply",
@@ -158,6 +162,48 @@ struct MergeStatusResponseActionComponent {
     name: String,
 }
 
+// Decide what happens to the change set's pending actions when it is applied
+async fn action_selection(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Json(payload): Json<ActionSelectionRequest>,
+) -> Result<Json<ActionSelectionSummary>> {
+    let summary = ActionSelection::apply(ctx, &payload.action_selection).await?;
+
+    tracker.track(
+        ctx,
+        "select_actions_for_apply",
+        json!({
+            "change_set": ctx.change_set_id(),
+            "run": summary.run.len(),
+            "held": summary.held.len(),
+            "dropped": summary.dropped.len(),
+        }),
+    );
+
+    if !summary.is_empty() {
+        ctx.write_audit_log(
+            AuditLogKind::ApplyChangeSetActionSelection {
+                run: summary.run.clone(),
+                held: summary.held.clone(),
+                dropped: summary.dropped.clone(),
+            },
+            ctx.change_set()?.name.clone(),
+        )
+        .await?;
+    }
+
+    ctx.commit().await?;
+
+    Ok(Json(summary))
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ActionSelectionRequest {
+    action_selection: HashMap<ActionId, ActionSelection>,
+}
+
 async fn force_apply(
     ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
     tracker: PosthogEventTracker,
@@ -223,19 +269,14 @@ async fn request_approval(
         .into_frontend_type(ctx)
         .await?;
 
-    let change_set_url = format!(
-        "https://{}/w/{}/{}",
-        host_name,
-        ctx.workspace_pk()?,
-        change_set_id
-    );
+    let change_set_url = format!("https://{}/w/{}/{}", host_name, workspace_pk, change_set_id);
     let message = format!(
         "{} requested an approval of change set {}: {}",
         user.email(),
         change_set_view.name.clone(),
         change_set_url
     );
-    post_to_webhook(ctx, workspace_pk, message.as_str()).await?;
+    post_to_webhook(ctx, message.as_str()).await?;
 
     ctx.write_audit_log(
         AuditLogKind::RequestChangeSetApproval {
@@ -286,6 +327,13 @@ pub enum ChangeSetsError {
 
 impl IntoResponse for ChangeSetsError {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+        let status_code = match &self {
+            Self::Action(
+                dal::action::ActionError::ActionNotPendingInChangeSet(_)
+                | dal::action::ActionError::SelectedActionDependsOnHeldAction { .. },
+            ) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status_code, self.to_string()).into_response()
     }
 }
@@ -12,19 +12,29 @@ use crate::{
     AppState,
 };
 
+pub mod action_concurrency_group;
+pub mod action_retry_policy;
 pub mod admin;
 pub mod approval_requirement_definition;
 pub mod audit_log;
 pub mod change_set;
 pub mod fs;
 pub mod func;
+pub mod func_egress_policy;
+pub mod func_run_log;
+pub mod func_run_retention_policy;
+pub mod func_timeout;
 pub mod index;
 pub mod integrations;
+pub mod maintenance_window;
 pub mod management;
 pub mod module;
+pub mod refresh_schedule;
+pub mod resource_drift;
 pub mod variant;
 pub mod view;
 pub mod workspace;
+pub mod workspace_variable;
 pub mod ws;
 
 pub fn routes(state: AppState) -> Router<AppState> {
@@ -40,12 +50,16 @@ fn workspace_routes(state: AppState) -> Router<AppState> {
         .nest(
             "/change-sets/:change_set_id",
             change_set::change_set_routes(state.clone())
+                .nest("/action-retry-policies", action_retry_policy::v2_routes())
                 .nest("/audit-logs", audit_log::v2_routes())
                 .nest("/funcs", func::v2_routes())
+                .nest("/func-timeouts", func_timeout::v2_routes())
                 .nest("/modules", module::v2_routes())
+                .nest("/resource-drift", resource_drift::v2_routes())
                 .nest("/schema-variants", variant::v2_routes())
                 .nest("/management", management::v2_routes())
                 .nest("/views", view::v2_routes())
+                .nest("/workspace-variables", workspace_variable::v2_routes())
                 .nest(
                     "/approval-requirement-definitions",
                     approval_requirement_definition::v2_routes(),
@@ -53,8 +67,23 @@ fn workspace_routes(state: AppState) -> Router<AppState> {
                 .route_layer(middleware::from_extractor::<TargetChangeSetIdFromPath>()),
         )
         .nest("/fs", fs::fs_routes(state.clone()))
+        .nest(
+            "/func-egress-policies",
+            func_egress_policy::v2_routes(state.clone()),
+        )
+        .nest("/func-run-logs", func_run_log::v2_routes())
+        .nest(
+            "/func-run-retention-policy",
+            func_run_retention_policy::v2_routes(state.clone()),
+        )
+        .nest(
+            "/action-concurrency-groups",
+            action_concurrency_group::v2_routes(),
+        )
         .nest("/index", index::v2_workspace_routes())
         .nest("/integrations", integrations::v2_routes())
+        .nest("/maintenance-windows", maintenance_window::v2_routes(state))
+        .nest("/refresh-schedules", refresh_schedule::v2_routes())
         // TODO: We eventually want things like the bifrost WS to live here, but the current setup throws an internal server error if we try to use it here.
         // .nest("/ws", ws::router(state))
         .route_layer(middleware::from_extractor::<TargetWorkspaceIdFromPath>())
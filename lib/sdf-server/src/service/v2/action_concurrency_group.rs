@@ -0,0 +1,54 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, put},
+    Router,
+};
+use dal::{action::concurrency_group::ActionConcurrencyGroupError, TransactionsError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, service::ApiError};
+
+mod create;
+mod list;
+mod remove;
+mod update;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ActionConcurrencyGroupAPIError {
+    #[error("action concurrency group error: {0}")]
+    ActionConcurrencyGroup(#[from] ActionConcurrencyGroupError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ActionConcurrencyGroupAPIResult<T> = Result<T, ActionConcurrencyGroupAPIError>;
+
+impl IntoResponse for ActionConcurrencyGroupAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::ActionConcurrencyGroup(
+                ActionConcurrencyGroupError::InvalidLimit | ActionConcurrencyGroupError::KeyEmpty,
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::ActionConcurrencyGroup(ActionConcurrencyGroupError::KeyTaken(_)) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
+            Self::ActionConcurrencyGroup(ActionConcurrencyGroupError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list::list).post(create::create))
+        .route(
+            "/:action_concurrency_group_id",
+            put(update::update).delete(remove::remove),
+        )
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Host, OriginalUri},
+    Json,
+};
+use dal::action::concurrency_group::{ActionConcurrencyGroup, ActionConcurrencyGroupMember};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::ActionConcurrencyGroupAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub key: String,
+    pub concurrency_limit: i32,
+    pub members: Vec<ActionConcurrencyGroupMember>,
+}
+
+pub async fn create(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Json(Request {
+        key,
+        concurrency_limit,
+        members,
+    }): Json<Request>,
+) -> ActionConcurrencyGroupAPIResult<Json<ActionConcurrencyGroup>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let group = ActionConcurrencyGroup::new(&ctx, key, concurrency_limit, members).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "create_action_concurrency_group",
+        serde_json::json!({
+            "how": "/action_concurrency_group/create",
+            "action_concurrency_group_id": group.pk(),
+            "action_concurrency_group_key": group.key(),
+            "concurrency_limit": group.concurrency_limit(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(group))
+}
@@ -0,0 +1,15 @@
+use axum::Json;
+use dal::action::concurrency_group::ActionConcurrencyGroup;
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::ActionConcurrencyGroupAPIResult;
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> ActionConcurrencyGroupAPIResult<Json<Vec<ActionConcurrencyGroup>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    Ok(Json(ActionConcurrencyGroup::list(&ctx).await?))
+}
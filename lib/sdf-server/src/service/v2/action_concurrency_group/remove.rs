@@ -0,0 +1,47 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    action::concurrency_group::{ActionConcurrencyGroup, ActionConcurrencyGroupId},
+    WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::ActionConcurrencyGroupAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, action_concurrency_group_id)): Path<(
+        WorkspacePk,
+        ActionConcurrencyGroupId,
+    )>,
+) -> ActionConcurrencyGroupAPIResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let group = ActionConcurrencyGroup::get_by_pk(&ctx, action_concurrency_group_id).await?;
+    ActionConcurrencyGroup::delete(&ctx, action_concurrency_group_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_action_concurrency_group",
+        serde_json::json!({
+            "how": "/action_concurrency_group/remove",
+            "action_concurrency_group_id": action_concurrency_group_id,
+            "action_concurrency_group_key": group.key(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    action::concurrency_group::{
+        ActionConcurrencyGroup, ActionConcurrencyGroupId, ActionConcurrencyGroupMember,
+    },
+    WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::ActionConcurrencyGroupAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub concurrency_limit: i32,
+    pub members: Vec<ActionConcurrencyGroupMember>,
+}
+
+pub async fn update(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, action_concurrency_group_id)): Path<(
+        WorkspacePk,
+        ActionConcurrencyGroupId,
+    )>,
+    Json(Request {
+        concurrency_limit,
+        members,
+    }): Json<Request>,
+) -> ActionConcurrencyGroupAPIResult<Json<ActionConcurrencyGroup>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut group = ActionConcurrencyGroup::get_by_pk(&ctx, action_concurrency_group_id).await?;
+    group.update(&ctx, concurrency_limit, members).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_action_concurrency_group",
+        serde_json::json!({
+            "how": "/action_concurrency_group/update",
+            "action_concurrency_group_id": group.pk(),
+            "action_concurrency_group_key": group.key(),
+            "concurrency_limit": group.concurrency_limit(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(group))
+}
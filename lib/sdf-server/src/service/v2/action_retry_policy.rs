@@ -0,0 +1,47 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+    Router,
+};
+use dal::{action::retry_policy::ActionRetryPolicyError, ChangeSetError, TransactionsError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, service::ApiError};
+
+mod list;
+mod remove;
+mod upsert;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ActionRetryPolicyAPIError {
+    #[error("action retry policy error: {0}")]
+    ActionRetryPolicy(#[from] ActionRetryPolicyError),
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ActionRetryPolicyAPIResult<T> = Result<T, ActionRetryPolicyAPIError>;
+
+impl IntoResponse for ActionRetryPolicyAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::ActionRetryPolicy(
+                ActionRetryPolicyError::InvalidMaxAttempts
+                | ActionRetryPolicyError::InvalidDelay(..),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list::list).put(upsert::upsert))
+        .route("/:action_retry_policy_id", delete(remove::remove))
+}
@@ -0,0 +1,18 @@
+use axum::{extract::Path, Json};
+use dal::{action::retry_policy::ActionRetryPolicy, ChangeSetId, WorkspacePk};
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::ActionRetryPolicyAPIResult;
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> ActionRetryPolicyAPIResult<Json<Vec<ActionRetryPolicy>>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(ActionRetryPolicy::list(&ctx).await?))
+}
@@ -0,0 +1,53 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    action::retry_policy::{ActionRetryPolicy, ActionRetryPolicyId},
+    ChangeSet, ChangeSetId, WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::AccessBuilder},
+    track,
+};
+
+use super::ActionRetryPolicyAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, action_retry_policy_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        ActionRetryPolicyId,
+    )>,
+) -> ActionRetryPolicyAPIResult<ForceChangeSetResponse<()>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let policy = ActionRetryPolicy::get_by_id(&ctx, action_retry_policy_id).await?;
+    ActionRetryPolicy::remove(&ctx, action_retry_policy_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_action_retry_policy",
+        serde_json::json!({
+            "how": "/action_retry_policy/remove",
+            "action_retry_policy_id": action_retry_policy_id,
+            "target": policy.target(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::empty(force_change_set_id))
+}
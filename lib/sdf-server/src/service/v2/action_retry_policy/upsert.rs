@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    action::retry_policy::{ActionFailureKind, ActionRetryPolicy, ActionRetryPolicyTarget},
+    ChangeSet, ChangeSetId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::AccessBuilder},
+    track,
+};
+
+use super::ActionRetryPolicyAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub target: ActionRetryPolicyTarget,
+    pub max_attempts: u32,
+    pub initial_delay_ms: u64,
+    pub max_delay_ms: u64,
+    #[serde(default)]
+    pub retry_on: Vec<ActionFailureKind>,
+}
+
+pub async fn upsert(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(Request {
+        target,
+        max_attempts,
+        initial_delay_ms,
+        max_delay_ms,
+        retry_on,
+    }): Json<Request>,
+) -> ActionRetryPolicyAPIResult<ForceChangeSetResponse<ActionRetryPolicy>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let policy = ActionRetryPolicy::upsert(
+        &ctx,
+        target,
+        max_attempts,
+        initial_delay_ms,
+        max_delay_ms,
+        retry_on,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "upsert_action_retry_policy",
+        serde_json::json!({
+            "how": "/action_retry_policy/upsert",
+            "action_retry_policy_id": policy.id(),
+            "target": policy.target(),
+            "max_attempts": policy.max_attempts(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, policy))
+}
@@ -8,16 +8,14 @@ use axum::{
 };
 use dal::{
     workspace_integrations::WorkspaceIntegration, ChangeSetId, DalContext, HistoryEventError,
-    WorkspacePk, WsEventError,
+    WsEventError,
 };
-use reqwest::Client;
-use serde::Serialize;
 use si_data_spicedb::SpiceDbError;
-use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::{middleware::WorkspacePermissionLayer, service::ApiError, AppState};
 
+mod action_selection;
 mod apply;
 mod approval_status;
 mod approve;
@@ -31,6 +29,8 @@ mod request_approval;
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error("action error: {0}")]
+    Action(#[from] dal::action::ActionError),
     #[error("change set error: {0}")]
     ChangeSet(#[from] dal::ChangeSetError),
     #[error("change set apply error: {0}")]
@@ -64,6 +64,10 @@ pub enum Error {
 impl IntoResponse for Error {
     fn into_response(self) -> axum::response::Response {
         let status_code = match &self {
+            Self::Action(
+                dal::action::ActionError::ActionNotPendingInChangeSet(_)
+                | dal::action::ActionError::SelectedActionDependsOnHeldAction { .. },
+            ) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::ChangeSetApply(_) => StatusCode::CONFLICT,
             Self::DvuRootsNotEmpty(_) => StatusCode::PRECONDITION_FAILED,
             Self::Transactions(dal::TransactionsError::BadWorkspaceAndChangeSet) => {
@@ -80,45 +84,8 @@ pub type ChangeSetAPIError = Error;
 
 type Result<T> = result::Result<T, Error>;
 
-#[derive(Serialize)]
-struct SlackMessage<'a> {
-    text: &'a str,
-}
-
-pub async fn post_to_webhook(
-    ctx: &DalContext,
-    workspace_id: WorkspacePk,
-    message: &str,
-) -> Result<()> {
-    if let Some(integration) = WorkspaceIntegration::get_integrations_for_workspace_pk(ctx).await? {
-        if let Some(webhook_url) = integration.slack_webhook_url() {
-            let client = Client::new();
-            let slack_message = SlackMessage { text: message };
-
-            match client
-                .post(webhook_url.clone())
-                .json(&slack_message)
-                .send()
-                .await
-            {
-                Ok(response) if !response.status().is_success() => {
-                    info!(
-                        "Failed to post to Slack webhook for workspace {} to URL {}, status: {}",
-                        workspace_id,
-                        webhook_url,
-                        response.status()
-                    );
-                }
-                Err(err) => {
-                    info!(
-                        "Error posting to Slack webhook for workspace {} to URL {}: {}",
-                        workspace_id, webhook_url, err
-                    );
-                }
-                _ => {}
-            }
-        }
-    }
+pub async fn post_to_webhook(ctx: &DalContext, message: &str) -> Result<()> {
+    WorkspaceIntegration::post_to_webhook(ctx, message).await?;
 
     Ok(())
 }
@@ -129,6 +96,10 @@ pub fn change_sets_routes() -> Router<AppState> {
 
 pub fn change_set_routes(state: AppState) -> Router<AppState> {
     Router::new()
+        .route(
+            "/action_selection",
+            post(action_selection::action_selection),
+        )
         .route("/apply", post(apply::apply))
         .route("/approval_status", get(approval_status::approval_status))
         .route("/approve", post(approve::approve))
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    action::{
+        selection::{ActionSelection, ActionSelectionSummary},
+        ActionId,
+    },
+    ChangeSet, ChangeSetId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+use si_events::audit_log::AuditLogKind;
+
+use super::Result;
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// What to do with each pending action once the change set is applied. Actions that are not
+    /// listed are left as they are.
+    pub action_selection: HashMap<ActionId, ActionSelection>,
+}
+
+/// Records what happens to the change set's pending actions when it is applied. This changes the
+/// change set like any other edit, so approvers see the selection and it can't change after they
+/// have approved.
+pub async fn action_selection(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(request): Json<Request>,
+) -> Result<()> {
+    let ctx = builder
+        .build(request_ctx.build(change_set_id.into()))
+        .await?;
+
+    let summary = ActionSelection::apply(&ctx, &request.action_selection).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "select_actions_for_apply",
+        serde_json::json!({
+            "change_set": change_set_id,
+            "run": summary.run.len(),
+            "held": summary.held.len(),
+            "dropped": summary.dropped.len(),
+        }),
+    );
+
+    if !summary.is_empty() {
+        let change_set = ChangeSet::get_by_id(&ctx, change_set_id).await?;
+        let ActionSelectionSummary { run, held, dropped } = summary;
+        ctx.write_audit_log(
+            AuditLogKind::ApplyChangeSetActionSelection { run, held, dropped },
+            change_set.name,
+        )
+        .await?;
+    }
+
+    ctx.commit().await?;
+
+    Ok(())
+}
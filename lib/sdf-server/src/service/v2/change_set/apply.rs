@@ -59,7 +59,7 @@ pub async fn apply(
             "{} applied change set {} to HEAD: {}",
             actor, change_set_view.name, change_set_url
         );
-        post_to_webhook(&ctx, workspace_pk, message.as_str()).await?;
+        post_to_webhook(&ctx, message.as_str()).await?;
 
         // WS Event fires from the dal
         ctx.commit().await?;
@@ -128,7 +128,7 @@ pub async fn approve(
         change_set_view.name.clone(),
         change_set_url
     );
-    post_to_webhook(&ctx, workspace_pk, message.as_str()).await?;
+    post_to_webhook(&ctx, message.as_str()).await?;
 
     ctx.commit().await?;
 
@@ -49,7 +49,7 @@ pub async fn request_approval(
         change_set_view.name.clone(),
         change_set_url
     );
-    post_to_webhook(&ctx, workspace_pk, message.as_str()).await?;
+    post_to_webhook(&ctx, message.as_str()).await?;
 
     ctx.write_audit_log(
         AuditLogKind::RequestChangeSetApproval {
@@ -54,7 +54,7 @@ pub async fn get_bindings_for_func_and_schema_variant(
     let func = dal::Func::get_by_id(ctx, func_id).await?;
 
     Ok(match func.kind {
-        dal::func::FuncKind::Action => {
+        dal::func::FuncKind::Action | dal::func::FuncKind::Plan => {
             ActionBinding::assemble_action_bindings(ctx, func_id).await?
         }
         dal::func::FuncKind::Intrinsic => {
@@ -8,8 +8,12 @@ use axum::{
 use dal::{
     attribute::{prototype::argument::AttributePrototypeArgumentError, value::AttributeValueError},
     func::{
-        argument::FuncArgumentError, authoring::FuncAuthoringError, binding::FuncBindingError,
+        argument::FuncArgumentError,
+        authoring::FuncAuthoringError,
+        binding::FuncBindingError,
+        replay::FuncRunReplayError,
         runner::FuncRunnerError,
+        test_case::{FuncTestCaseError, FuncTestCaseId},
     },
     workspace_snapshot::graph::WorkspaceSnapshotGraphError,
     ChangeSetError, ComponentError, DalContext, Func, FuncError, FuncId, SchemaVariantError,
@@ -32,7 +36,9 @@ pub mod execute_func;
 pub mod get_code;
 pub mod get_func_run;
 pub mod list_funcs;
+pub mod replay_func_run;
 pub mod save_code;
+pub mod test_case;
 pub mod test_execute;
 pub mod update_func;
 
@@ -65,6 +71,12 @@ pub enum FuncAPIError {
     FuncNameReserved(String),
     #[error("The function does not exist")]
     FuncNotFound(FuncId),
+    #[error("func run replay error: {0}")]
+    FuncRunReplay(#[from] FuncRunReplayError),
+    #[error("func test case error: {0}")]
+    FuncTestCase(#[from] FuncTestCaseError),
+    #[error("func test case {0} does not belong to func {1}")]
+    FuncTestCaseNotForFunc(FuncTestCaseId, FuncId),
     #[error("hyper error: {0}")]
     Http(#[from] axum::http::Error),
     #[error("layer db error: {0}")]
@@ -120,6 +132,10 @@ impl IntoResponse for FuncAPIError {
                     }
                     FunctionResultFailureErrorKind::InvalidReturnType
                     | FunctionResultFailureErrorKind::KilledExecution
+                    | FunctionResultFailureErrorKind::Timeout
+                    | FunctionResultFailureErrorKind::CpuLimitExceeded
+                    | FunctionResultFailureErrorKind::EgressDenied
+                    | FunctionResultFailureErrorKind::MemoryLimitExceeded
                     | FunctionResultFailureErrorKind::ActionFieldWrongType => {
                         (StatusCode::UNPROCESSABLE_ENTITY, Some(message))
                     }
@@ -145,12 +161,19 @@ impl IntoResponse for FuncAPIError {
             | Self::MissingPrototypeId
             | Self::MissingSchemaVariantAndFunc
             | Self::Func(FuncError::FuncLocked(_))
+            | Self::FuncTestCase(
+                FuncTestCaseError::ExpectedValueMissing(_, _)
+                | FuncTestCaseError::NameEmpty
+                | FuncTestCaseError::NameTaken(_, _),
+            )
             | Self::SchemaVariant(dal::SchemaVariantError::SchemaVariantLocked(_)) => {
                 (StatusCode::BAD_REQUEST, None)
             }
 
             // Return 404 when the func is not found
             Self::FuncNotFound(_) |
+            Self::FuncTestCaseNotForFunc(_, _) |
+            Self::FuncRunReplay(FuncRunReplayError::FuncRunNotFound(_)) |
             // When a graph node cannot be found for a schema variant, it is not found
             Self::SchemaVariant(dal::SchemaVariantError::NotFound(_)) => (StatusCode::NOT_FOUND, None),
 
@@ -172,6 +195,11 @@ impl IntoResponse for FuncAPIError {
                 ),
             )) => (StatusCode::UNPROCESSABLE_ENTITY, None),
 
+            // Replaying would change resources in the "outside world"
+            Self::FuncRunReplay(FuncRunReplayError::ActionNotReplayable(_, _)) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, None)
+            }
+
             // Return 422 if the error is related to the user code being invalid
             Self::FuncAuthoring(FuncAuthoringError::AttributeValue(AttributeValueError::FuncRunner(err))) =>
                 func_runner_err_to_status_and_message(*err),
@@ -191,6 +219,10 @@ pub fn v2_routes() -> Router<AppState> {
         .route("/", get(list_funcs::list_funcs))
         .route("/code", get(get_code::get_code)) // accepts a list of func_ids
         .route("/runs/:func_run_id", get(get_func_run::get_func_run)) // accepts a list of func_ids
+        .route(
+            "/runs/:func_run_id/replay",
+            post(replay_func_run::replay_func_run),
+        )
         .route("/", post(create_func::create_func))
         .route("/:func_id", put(update_func::update_func)) // only save the func's metadata
         .route("/:func_id/code", put(save_code::save_code)) // only saves func code
@@ -232,6 +264,31 @@ pub fn v2_routes() -> Router<AppState> {
             "/:func_id/arguments/:func_argument_id",
             delete(argument::delete_argument::delete_func_argument),
         )
+        // Func Test Cases
+        .route(
+            "/test_cases/run",
+            post(test_case::run_test_cases::run_all_func_test_cases),
+        )
+        .route(
+            "/:func_id/test_cases",
+            get(test_case::list_test_cases::list_func_test_cases),
+        )
+        .route(
+            "/:func_id/test_cases",
+            post(test_case::create_test_case::create_func_test_case),
+        )
+        .route(
+            "/:func_id/test_cases/run",
+            post(test_case::run_test_cases::run_func_test_cases),
+        )
+        .route(
+            "/:func_id/test_cases/:func_test_case_id",
+            put(test_case::update_test_case::update_func_test_case),
+        )
+        .route(
+            "/:func_id/test_cases/:func_test_case_id",
+            delete(test_case::delete_test_case::delete_func_test_case),
+        )
 }
 
 // helper to assemble the front end struct to return the code and types so SDF can decide when these events need to fire
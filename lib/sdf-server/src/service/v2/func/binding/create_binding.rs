@@ -3,6 +3,7 @@ use axum::{
     Json,
 };
 use dal::{
+    action::prototype::ActionPrototype,
     func::binding::{
         action::ActionBinding, attribute::AttributeBinding, authentication::AuthBinding,
         leaf::LeafBinding, management::ManagementBinding, AttributeArgumentBinding, EventualParent,
@@ -261,6 +262,46 @@ pub async fn create_binding(
                 }
             }
         }
+        dal::func::FuncKind::Plan => {
+            for binding in request.bindings {
+                if let frontend_types::FuncBinding::Action {
+                    action_prototype_id: Some(action_prototype_id),
+                    func_id: Some(func_id),
+                    ..
+                } = binding
+                {
+                    let action_prototype_id = action_prototype_id.into_raw_id().into();
+                    ActionBinding::create_plan_binding(&ctx, func_id, action_prototype_id).await?;
+                    let schema_variant_id =
+                        ActionPrototype::schema_variant_id(&ctx, action_prototype_id).await?;
+                    let action_kind = ActionPrototype::get_by_id(&ctx, action_prototype_id)
+                        .await?
+                        .kind;
+                    let schema =
+                        SchemaVariant::schema_id_for_schema_variant_id(&ctx, schema_variant_id)
+                            .await?;
+                    let schema_variant = SchemaVariant::get_by_id(&ctx, schema_variant_id).await?;
+                    let func = Func::get_by_id(&ctx, func_id).await?;
+                    ctx.write_audit_log(
+                        AuditLogKind::AttachActionFunc {
+                            func_id: func.id,
+                            func_display_name: func.display_name.clone(),
+                            schema_variant_id: Some(schema_variant_id),
+                            component_id: None,
+                            action_kind: Some(action_kind.into()),
+                        },
+                        func.name.clone(),
+                    )
+                    .await?;
+                    WsEvent::schema_variant_updated(&ctx, schema, schema_variant)
+                        .await?
+                        .publish_on_commit(&ctx)
+                        .await?;
+                } else {
+                    return Err(FuncAPIError::MissingActionPrototype);
+                }
+            }
+        }
         dal::func::FuncKind::CodeGeneration | dal::func::FuncKind::Qualification => {
             for binding in request.bindings {
                 if let frontend_types::FuncBinding::CodeGeneration {
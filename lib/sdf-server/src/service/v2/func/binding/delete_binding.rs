@@ -97,6 +97,18 @@ pub async fn delete_binding(
                 )
                 .await?
             }
+            FuncKind::Plan => {
+                let frontend_types::FuncBinding::Action {
+                    action_prototype_id: Some(action_prototype_id),
+                    ..
+                } = binding
+                else {
+                    return Err(FuncAPIError::MissingActionPrototype);
+                };
+
+                ActionBinding::delete_plan_binding(&ctx, action_prototype_id.into_raw_id().into())
+                    .await?
+            }
             FuncKind::Management => {
                 let frontend_types::FuncBinding::Management {
                     management_prototype_id: Some(management_prototype_id),
@@ -3,6 +3,7 @@ use axum::{
     Json,
 };
 use dal::{
+    action::prototype::ActionPrototype,
     func::{
         authoring::FuncAuthoringClient,
         binding::{
@@ -373,6 +374,48 @@ pub async fn create_func(
                 return Err(FuncAPIError::WrongFunctionKindForBinding);
             }
         }
+        FuncKind::Plan => {
+            if let FuncBinding::Action {
+                action_prototype_id: Some(action_prototype_id),
+                ..
+            } = request.binding
+            {
+                let action_prototype_id = action_prototype_id.into_raw_id().into();
+                let func = FuncAuthoringClient::create_new_plan_func(
+                    &ctx,
+                    request.name,
+                    action_prototype_id,
+                )
+                .await?;
+                let schema_variant_id =
+                    ActionPrototype::schema_variant_id(&ctx, action_prototype_id).await?;
+                let action_kind = ActionPrototype::get_by_id(&ctx, action_prototype_id)
+                    .await?
+                    .kind;
+                ctx.write_audit_log(
+                    AuditLogKind::CreateFunc {
+                        func_display_name: func.display_name.clone(),
+                        func_kind: func.kind.into(),
+                    },
+                    func.name.clone(),
+                )
+                .await?;
+                ctx.write_audit_log(
+                    AuditLogKind::AttachActionFunc {
+                        func_id: func.id,
+                        func_display_name: func.display_name.clone(),
+                        schema_variant_id: Some(schema_variant_id),
+                        component_id: None,
+                        action_kind: Some(action_kind.into()),
+                    },
+                    func.name.clone(),
+                )
+                .await?;
+                func
+            } else {
+                return Err(FuncAPIError::MissingActionPrototype);
+            }
+        }
         FuncKind::Unknown | FuncKind::SchemaVariantDefinition | FuncKind::Intrinsic => {
             return Err(FuncAPIError::WrongFunctionKindForBinding)
         }
@@ -5,8 +5,8 @@ use dal::{ContentHash, DalContext, WorkspacePk};
 use serde::{Deserialize, Serialize};
 use si_events::{
     ActionId, ActionKind, ActionPrototypeId, ActionResultState, Actor, AttributeValueId, CasValue,
-    ChangeSetId, ComponentId, FuncBackendKind, FuncBackendResponseType, FuncKind, FuncRun,
-    FuncRunId, FuncRunLog, FuncRunLogId, FuncRunState, OutputLine,
+    ChangeSetId, ComponentId, ExecutionPriority, FuncBackendKind, FuncBackendResponseType,
+    FuncKind, FuncRun, FuncRunId, FuncRunLog, FuncRunLogId, FuncRunState, OutputLine,
 };
 use std::sync::Arc;
 
@@ -80,6 +80,7 @@ pub struct FuncRunView {
     action_originating_change_set_id: Option<ChangeSetId>,
     action_originating_change_set_name: Option<String>,
     action_result_state: Option<ActionResultState>,
+    priority: ExecutionPriority,
     backend_kind: FuncBackendKind,
     backend_response_type: FuncBackendResponseType,
     function_name: String,
@@ -124,6 +125,7 @@ impl FuncRunView {
                 .action_originating_change_set_name()
                 .map(|v| v.to_string()),
             action_result_state: func_run.action_result_state(),
+            priority: func_run.priority(),
             backend_kind: func_run.backend_kind(),
             backend_response_type: func_run.backend_response_type(),
             function_name: func_run.function_name().to_string(),
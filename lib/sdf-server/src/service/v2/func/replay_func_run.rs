@@ -0,0 +1,87 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::replay::{FuncRunReplay, FuncRunReplayCode},
+    ChangeSetId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+use si_events::FuncRunId;
+
+use super::{
+    get_func_run::{get_func_run_view, FuncRunView},
+    FuncAPIResult,
+};
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFuncRunRequest {
+    #[serde(default)]
+    pub code: FuncRunReplayCode,
+}
+
+/// The past run and its replay, for showing side by side.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFuncRunResponse {
+    pub replay: FuncRunReplay,
+    pub results_match: bool,
+    pub original_func_run: FuncRunView,
+    pub replay_func_run: FuncRunView,
+}
+
+pub async fn replay_func_run(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_run_id)): Path<(WorkspacePk, ChangeSetId, FuncRunId)>,
+    Json(request): Json<ReplayFuncRunRequest>,
+) -> FuncAPIResult<Json<ReplayFuncRunResponse>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let replay = FuncRunReplay::replay(&ctx, func_run_id, request.code).await?;
+
+    let original_func_run = ctx.layer_db().func_run().try_read(func_run_id).await?;
+    let replay_func_run = ctx
+        .layer_db()
+        .func_run()
+        .try_read(replay.replay_func_run_id)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "replay_func_run",
+        serde_json::json!({
+            "how": "/func/replay_func_run",
+            "func_run_id": func_run_id,
+            "replay_func_run_id": replay.replay_func_run_id,
+            "func_name": original_func_run.function_name(),
+            "code": request.code,
+            "results_match": replay.results_match(),
+        }),
+    );
+
+    let response = ReplayFuncRunResponse {
+        results_match: replay.results_match(),
+        original_func_run: get_func_run_view(&ctx, &original_func_run).await?,
+        replay_func_run: get_func_run_view(&ctx, &replay_func_run).await?,
+        replay,
+    };
+
+    ctx.commit().await?;
+
+    Ok(Json(response))
+}
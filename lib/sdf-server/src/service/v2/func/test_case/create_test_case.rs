@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestCaseDefinition},
+    ChangeSet, ChangeSetId, Func, FuncId, WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    service::{force_change_set_response::ForceChangeSetResponse, v2::func::FuncAPIResult},
+    track,
+};
+
+pub async fn create_func_test_case(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+    Json(request): Json<FuncTestCaseDefinition>,
+) -> FuncAPIResult<ForceChangeSetResponse<FuncTestCase>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let func = Func::get_by_id(&ctx, func_id).await?;
+    let test_case = FuncTestCase::new(&ctx, func_id, request).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "create_func_test_case",
+        serde_json::json!({
+            "how": "/func/create_func_test_case",
+            "func_id": func_id,
+            "func_name": func.name.clone(),
+            "func_kind": func.kind.clone(),
+            "expectation": test_case.expectation(),
+            "captured_from_component": test_case.captured_from_component().is_some(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, test_case))
+}
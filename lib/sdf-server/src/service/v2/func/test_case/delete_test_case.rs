@@ -0,0 +1,63 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestCaseId},
+    ChangeSet, ChangeSetId, Func, FuncId, WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    service::{
+        force_change_set_response::ForceChangeSetResponse,
+        v2::func::{FuncAPIError, FuncAPIResult},
+    },
+    track,
+};
+
+pub async fn delete_func_test_case(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id, func_test_case_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        FuncId,
+        FuncTestCaseId,
+    )>,
+) -> FuncAPIResult<ForceChangeSetResponse<()>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let func = Func::get_by_id(&ctx, func_id).await?;
+    let test_case = FuncTestCase::get_by_id(&ctx, func_test_case_id).await?;
+    if test_case.func_id() != func_id {
+        return Err(FuncAPIError::FuncTestCaseNotForFunc(
+            func_test_case_id,
+            func_id,
+        ));
+    }
+    FuncTestCase::remove(&ctx, func_test_case_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "delete_func_test_case",
+        serde_json::json!({
+            "how": "/func/delete_func_test_case",
+            "func_id": func_id,
+            "func_name": func.name.clone(),
+            "func_kind": func.kind.clone(),
+            "func_test_case_id": func_test_case_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, ()))
+}
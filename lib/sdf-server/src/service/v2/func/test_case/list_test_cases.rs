@@ -0,0 +1,18 @@
+use axum::{extract::Path, Json};
+use dal::{func::test_case::FuncTestCase, ChangeSetId, FuncId, WorkspacePk};
+
+use crate::{
+    extract::HandlerContext, service::v2::func::FuncAPIResult, service::v2::AccessBuilder,
+};
+
+pub async fn list_func_test_cases(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+) -> FuncAPIResult<Json<Vec<FuncTestCase>>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(FuncTestCase::list_for_func(&ctx, func_id).await?))
+}
@@ -0,0 +1,82 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestReport},
+    ChangeSetId, FuncId, WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::func::FuncAPIResult,
+    service::v2::AccessBuilder,
+    track,
+};
+
+/// Runs the test cases of every func in the change set. This is what CI calls to catch
+/// regressions before applying.
+pub async fn run_all_func_test_cases(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> FuncAPIResult<Json<FuncTestReport>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let report = FuncTestCase::run_all(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "run_all_func_test_cases",
+        serde_json::json!({
+            "how": "/func/run_all_func_test_cases",
+            "passed": report.passed,
+            "failed": report.failed,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(report))
+}
+
+pub async fn run_func_test_cases(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id)): Path<(WorkspacePk, ChangeSetId, FuncId)>,
+) -> FuncAPIResult<Json<FuncTestReport>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let report = FuncTestCase::run_for_func(&ctx, func_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "run_func_test_cases",
+        serde_json::json!({
+            "how": "/func/run_func_test_cases",
+            "func_id": func_id,
+            "passed": report.passed,
+            "failed": report.failed,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(report))
+}
@@ -0,0 +1,68 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::test_case::{FuncTestCase, FuncTestCaseDefinition, FuncTestCaseId},
+    ChangeSet, ChangeSetId, Func, FuncId, WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    service::{
+        force_change_set_response::ForceChangeSetResponse,
+        v2::func::{FuncAPIError, FuncAPIResult},
+    },
+    track,
+};
+
+pub async fn update_func_test_case(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_id, func_test_case_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        FuncId,
+        FuncTestCaseId,
+    )>,
+    Json(request): Json<FuncTestCaseDefinition>,
+) -> FuncAPIResult<ForceChangeSetResponse<FuncTestCase>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let func = Func::get_by_id(&ctx, func_id).await?;
+    let test_case = FuncTestCase::get_by_id(&ctx, func_test_case_id).await?;
+    if test_case.func_id() != func_id {
+        return Err(FuncAPIError::FuncTestCaseNotForFunc(
+            func_test_case_id,
+            func_id,
+        ));
+    }
+    let test_case = test_case.update(&ctx, request).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_func_test_case",
+        serde_json::json!({
+            "how": "/func/update_func_test_case",
+            "func_id": func_id,
+            "func_name": func.name.clone(),
+            "func_kind": func.kind.clone(),
+            "func_test_case_id": func_test_case_id,
+            "expectation": test_case.expectation(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, test_case))
+}
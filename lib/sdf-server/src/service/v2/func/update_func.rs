@@ -23,6 +23,8 @@ use ulid::Ulid;
 pub struct UpdateFuncRequest {
     pub display_name: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub always_execute: Option<bool>,
     client_ulid: Ulid,
 }
 
@@ -40,11 +42,14 @@ pub async fn update_func(
         .await?;
     let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
     let old_func = Func::get_by_id(&ctx, func_id).await?;
-    let updated_func =
+    let mut updated_func =
         FuncAuthoringClient::update_func(&ctx, func_id, request.display_name, request.description)
-            .await?
-            .into_frontend_type(&ctx)
             .await?;
+    if let Some(always_execute) = request.always_execute {
+        updated_func =
+            FuncAuthoringClient::set_always_execute(&ctx, func_id, always_execute).await?;
+    }
+    let updated_func = updated_func.into_frontend_type(&ctx).await?;
 
     WsEvent::func_updated(&ctx, updated_func.clone(), Some(request.client_ulid))
         .await?
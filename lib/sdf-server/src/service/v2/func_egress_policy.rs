@@ -0,0 +1,54 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, put},
+    Router,
+};
+use dal::{func::egress_policy::FuncEgressPolicyError, TransactionsError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, middleware::WorkspacePermissionLayer, service::ApiError};
+
+mod list;
+mod remove;
+mod set;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum FuncEgressPolicyAPIError {
+    #[error("func egress policy error: {0}")]
+    FuncEgressPolicy(#[from] FuncEgressPolicyError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncEgressPolicyAPIResult<T> = Result<T, FuncEgressPolicyAPIError>;
+
+impl IntoResponse for FuncEgressPolicyAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::FuncEgressPolicy(
+                FuncEgressPolicyError::InvalidRule(_) | FuncEgressPolicyError::NotApplicable(_),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::FuncEgressPolicy(FuncEgressPolicyError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes(state: AppState) -> Router<AppState> {
+    Router::new().route("/", get(list::list)).route(
+        "/:func_kind",
+        // Loosening a policy lets funcs reach more of the network, so only managers may change them
+        put(set::set)
+            .delete(remove::remove)
+            .route_layer(WorkspacePermissionLayer::new(
+                state,
+                permissions::Permission::Manage,
+            )),
+    )
+}
@@ -0,0 +1,15 @@
+use axum::Json;
+use dal::func::egress_policy::FuncEgressPolicy;
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::FuncEgressPolicyAPIResult;
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> FuncEgressPolicyAPIResult<Json<Vec<FuncEgressPolicy>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    Ok(Json(FuncEgressPolicy::list(&ctx).await?))
+}
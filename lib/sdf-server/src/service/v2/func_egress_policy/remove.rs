@@ -0,0 +1,42 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    func::{egress_policy::FuncEgressPolicy, FuncKind},
+    WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::FuncEgressPolicyAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, func_kind)): Path<(WorkspacePk, FuncKind)>,
+) -> FuncEgressPolicyAPIResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    FuncEgressPolicy::remove(&ctx, func_kind).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_func_egress_policy",
+        serde_json::json!({
+            "how": "/func_egress_policy/remove",
+            "func_kind": func_kind,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
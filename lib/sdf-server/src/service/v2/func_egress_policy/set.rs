@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::{egress_policy::FuncEgressPolicy, FuncKind},
+    WorkspacePk,
+};
+use serde::Deserialize;
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::FuncEgressPolicyAPIResult;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFuncEgressPolicyRequest {
+    /// Hostnames, `*.`-prefixed domains, and CIDR blocks. Empty means no network at all.
+    pub allow: Vec<String>,
+}
+
+pub async fn set(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, func_kind)): Path<(WorkspacePk, FuncKind)>,
+    Json(request): Json<SetFuncEgressPolicyRequest>,
+) -> FuncEgressPolicyAPIResult<Json<FuncEgressPolicy>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let policy = FuncEgressPolicy::set(&ctx, func_kind, request.allow).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "set_func_egress_policy",
+        serde_json::json!({
+            "how": "/func_egress_policy/set",
+            "func_kind": func_kind,
+            "allow": policy.allow(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(policy))
+}
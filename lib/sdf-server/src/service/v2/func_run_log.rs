@@ -0,0 +1,33 @@
+use axum::{
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use dal::TransactionsError;
+use si_layer_cache::LayerDbError;
+use thiserror::Error;
+
+use crate::{service::ApiError, AppState};
+
+mod search;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum FuncRunLogAPIError {
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncRunLogAPIResult<T> = Result<T, FuncRunLogAPIError>;
+
+impl IntoResponse for FuncRunLogAPIError {
+    fn into_response(self) -> Response {
+        ApiError::new(ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new().route("/", get(search::search))
+}
@@ -0,0 +1,51 @@
+use axum::{extract::Query, Json};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use si_events::{ChangeSetId, ComponentId, FuncRunLogLevel, FuncRunLogRecord};
+use si_layer_cache::db::func_run_log::FuncRunLogSearch;
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::FuncRunLogAPIResult;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFuncRunLogsRequest {
+    /// Only lines at this level or above.
+    level: Option<FuncRunLogLevel>,
+    /// Only lines whose message or fields contain this text, ignoring case.
+    text: Option<String>,
+    component_id: Option<ComponentId>,
+    func_name: Option<String>,
+    change_set_id: Option<ChangeSetId>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    limit: Option<u32>,
+}
+
+/// Searches the log lines of every func run in the workspace, most recent first.
+pub async fn search(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<SearchFuncRunLogsRequest>,
+) -> FuncRunLogAPIResult<Json<Vec<FuncRunLogRecord>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let search = FuncRunLogSearch {
+        min_level: request.level,
+        text: request.text.filter(|text| !text.is_empty()),
+        component_id: request.component_id,
+        func_name: request.func_name,
+        change_set_id: request.change_set_id,
+        since: request.since,
+        until: request.until,
+        limit: request.limit,
+    };
+    let records = ctx
+        .layer_db()
+        .func_run_log()
+        .search(ctx.workspace_pk()?, &search)
+        .await?;
+
+    Ok(Json(records))
+}
@@ -0,0 +1,55 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, put},
+    Router,
+};
+use dal::{func::retention::FuncRunRetentionError, TransactionsError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, middleware::WorkspacePermissionLayer, service::ApiError};
+
+mod get;
+mod remove;
+mod set;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum FuncRunRetentionPolicyAPIError {
+    #[error("func run retention error: {0}")]
+    FuncRunRetention(#[from] FuncRunRetentionError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncRunRetentionPolicyAPIResult<T> = Result<T, FuncRunRetentionPolicyAPIError>;
+
+impl IntoResponse for FuncRunRetentionPolicyAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::FuncRunRetention(
+                FuncRunRetentionError::InvalidKeepDays(_)
+                | FuncRunRetentionError::InvalidKeepLast(_),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::FuncRunRetention(FuncRunRetentionError::NotFound) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes(state: AppState) -> Router<AppState> {
+    Router::new().route("/", get(get::get)).route(
+        "/",
+        // Shortening retention deletes history for good, so only managers may change it
+        put(set::set)
+            .delete(remove::remove)
+            .route_layer(WorkspacePermissionLayer::new(
+                state,
+                permissions::Permission::Manage,
+            )),
+    )
+}
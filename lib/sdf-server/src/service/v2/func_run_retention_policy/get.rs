@@ -0,0 +1,34 @@
+use axum::Json;
+use dal::func::retention::{FuncRunRetention, FuncRunRetentionPolicy};
+use serde::Serialize;
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::FuncRunRetentionPolicyAPIResult;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFuncRunRetentionPolicyResponse {
+    /// The workspace's own policy, if it has one.
+    policy: Option<FuncRunRetentionPolicy>,
+    /// The retention that applies to the workspace, which is the default without a policy.
+    retention: FuncRunRetention,
+}
+
+pub async fn get(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> FuncRunRetentionPolicyAPIResult<Json<GetFuncRunRetentionPolicyResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let policy = FuncRunRetentionPolicy::get(&ctx).await?;
+    let retention = policy
+        .as_ref()
+        .map(FuncRunRetentionPolicy::retention)
+        .unwrap_or_default();
+
+    Ok(Json(GetFuncRunRetentionPolicyResponse {
+        policy,
+        retention,
+    }))
+}
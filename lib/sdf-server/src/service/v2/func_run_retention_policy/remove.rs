@@ -0,0 +1,37 @@
+use axum::extract::{Host, OriginalUri};
+use dal::func::retention::FuncRunRetentionPolicy;
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::FuncRunRetentionPolicyAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+) -> FuncRunRetentionPolicyAPIResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    FuncRunRetentionPolicy::remove(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_func_run_retention_policy",
+        serde_json::json!({
+            "how": "/func_run_retention_policy/remove",
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use axum::{
+    extract::{Host, OriginalUri},
+    Json,
+};
+use dal::func::retention::{FuncRunRetention, FuncRunRetentionPolicy};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::FuncRunRetentionPolicyAPIResult;
+
+pub async fn set(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Json(request): Json<FuncRunRetention>,
+) -> FuncRunRetentionPolicyAPIResult<Json<FuncRunRetentionPolicy>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let policy = FuncRunRetentionPolicy::set(&ctx, request).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "set_func_run_retention_policy",
+        serde_json::json!({
+            "how": "/func_run_retention_policy/set",
+            "keep_last": request.keep_last,
+            "keep_days": request.keep_days,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(policy))
+}
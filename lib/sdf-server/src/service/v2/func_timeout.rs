@@ -0,0 +1,46 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+    Router,
+};
+use dal::{func::timeout::FuncTimeoutError, ChangeSetError, TransactionsError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, service::ApiError};
+
+mod list;
+mod remove;
+mod upsert;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum FuncTimeoutAPIError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("func timeout error: {0}")]
+    FuncTimeout(#[from] FuncTimeoutError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncTimeoutAPIResult<T> = Result<T, FuncTimeoutAPIError>;
+
+impl IntoResponse for FuncTimeoutAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::FuncTimeout(FuncTimeoutError::InvalidTimeout(_)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list::list).put(upsert::upsert))
+        .route("/:func_timeout_id", delete(remove::remove))
+}
@@ -0,0 +1,18 @@
+use axum::{extract::Path, Json};
+use dal::{func::timeout::FuncTimeout, ChangeSetId, WorkspacePk};
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::FuncTimeoutAPIResult;
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> FuncTimeoutAPIResult<Json<Vec<FuncTimeout>>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(FuncTimeout::list(&ctx).await?))
+}
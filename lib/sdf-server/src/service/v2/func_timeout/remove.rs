@@ -0,0 +1,53 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    func::timeout::{FuncTimeout, FuncTimeoutId},
+    ChangeSet, ChangeSetId, WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::AccessBuilder},
+    track,
+};
+
+use super::FuncTimeoutAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, func_timeout_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        FuncTimeoutId,
+    )>,
+) -> FuncTimeoutAPIResult<ForceChangeSetResponse<()>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let timeout = FuncTimeout::get_by_id(&ctx, func_timeout_id).await?;
+    FuncTimeout::remove(&ctx, func_timeout_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_func_timeout",
+        serde_json::json!({
+            "how": "/func_timeout/remove",
+            "func_timeout_id": func_timeout_id,
+            "target": timeout.target(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::empty(force_change_set_id))
+}
@@ -0,0 +1,64 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    func::timeout::{FuncTimeout, FuncTimeoutTarget},
+    ChangeSet, ChangeSetId, WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::AccessBuilder},
+    track,
+};
+
+use super::FuncTimeoutAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub target: FuncTimeoutTarget,
+    pub timeout_secs: u64,
+}
+
+pub async fn upsert(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(Request {
+        target,
+        timeout_secs,
+    }): Json<Request>,
+) -> FuncTimeoutAPIResult<ForceChangeSetResponse<FuncTimeout>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let timeout = FuncTimeout::upsert(&ctx, target, timeout_secs).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "upsert_func_timeout",
+        serde_json::json!({
+            "how": "/func_timeout/upsert",
+            "func_timeout_id": timeout.id(),
+            "target": timeout.target(),
+            "timeout_secs": timeout.timeout_secs(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, timeout))
+}
@@ -0,0 +1,78 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Router,
+};
+use dal::{action::maintenance_window::MaintenanceWindowError, TransactionsError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, middleware::WorkspacePermissionLayer, service::ApiError};
+
+mod create;
+mod list;
+mod override_hold;
+mod remove;
+mod update;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum MaintenanceWindowAPIError {
+    #[error("maintenance window error: {0}")]
+    MaintenanceWindow(#[from] MaintenanceWindowError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type MaintenanceWindowAPIResult<T> = Result<T, MaintenanceWindowAPIError>;
+
+impl IntoResponse for MaintenanceWindowAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::MaintenanceWindow(
+                MaintenanceWindowError::InvalidDuration(_)
+                | MaintenanceWindowError::InvalidSchedule(_, _)
+                | MaintenanceWindowError::InvalidTimezone(_)
+                | MaintenanceWindowError::NameEmpty,
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::MaintenanceWindow(MaintenanceWindowError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes(state: AppState) -> Router<AppState> {
+    // Anyone may see the windows, but only approvers may change them, since a window holds
+    // actions back the same way an override releases them
+    Router::new()
+        .route("/", get(list::list))
+        .route(
+            "/",
+            post(create::create).route_layer(WorkspacePermissionLayer::new(
+                state.clone(),
+                permissions::Permission::Approve,
+            )),
+        )
+        .route(
+            "/overrides/:action_id",
+            post(override_hold::create)
+                .delete(override_hold::remove)
+                .route_layer(WorkspacePermissionLayer::new(
+                    state.clone(),
+                    permissions::Permission::Approve,
+                )),
+        )
+        .route(
+            "/:maintenance_window_id",
+            put(update::update)
+                .delete(remove::remove)
+                .route_layer(WorkspacePermissionLayer::new(
+                    state,
+                    permissions::Permission::Approve,
+                )),
+        )
+}
@@ -0,0 +1,30 @@
+use axum::Json;
+use dal::action::maintenance_window::{MaintenanceWindow, MaintenanceWindowStatus};
+use serde::Serialize;
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::MaintenanceWindowAPIResult;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindowView {
+    #[serde(flatten)]
+    pub window: MaintenanceWindow,
+    pub status: MaintenanceWindowStatus,
+}
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> MaintenanceWindowAPIResult<Json<Vec<MaintenanceWindowView>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut views = Vec::new();
+    for window in MaintenanceWindow::list(&ctx).await? {
+        let status = window.status(&ctx).await?;
+        views.push(MaintenanceWindowView { window, status });
+    }
+
+    Ok(Json(views))
+}
@@ -0,0 +1,65 @@
+//! Overriding a hold lets an action be dispatched while the maintenance windows covering it are
+//! closed. These routes require the approval permission.
+
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    action::{
+        maintenance_window::{MaintenanceWindow, MaintenanceWindowOverride},
+        ActionId,
+    },
+    WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::MaintenanceWindowAPIResult;
+
+pub async fn create(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, action_id)): Path<(WorkspacePk, ActionId)>,
+) -> MaintenanceWindowAPIResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    MaintenanceWindowOverride::new(&ctx, action_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "override_maintenance_window_hold",
+        serde_json::json!({
+            "how": "/maintenance_window/override_hold",
+            "action_id": action_id,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    // The override has to be committed before the rebaser looks for actions to dispatch
+    MaintenanceWindow::wake_dispatch(&ctx).await?;
+
+    Ok(())
+}
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, action_id)): Path<(WorkspacePk, ActionId)>,
+) -> MaintenanceWindowAPIResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    MaintenanceWindowOverride::delete(&ctx, action_id).await?;
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    action::maintenance_window::{MaintenanceWindow, MaintenanceWindowId},
+    WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::MaintenanceWindowAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, maintenance_window_id)): Path<(WorkspacePk, MaintenanceWindowId)>,
+) -> MaintenanceWindowAPIResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    MaintenanceWindow::get_by_pk(&ctx, maintenance_window_id).await?;
+    MaintenanceWindow::delete(&ctx, maintenance_window_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_maintenance_window",
+        serde_json::json!({
+            "how": "/maintenance_window/remove",
+            "maintenance_window_id": maintenance_window_id,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    // Removing a window can let held actions go
+    MaintenanceWindow::wake_dispatch(&ctx).await?;
+
+    Ok(())
+}
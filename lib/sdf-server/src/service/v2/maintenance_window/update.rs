@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    action::maintenance_window::{
+        MaintenanceWindow, MaintenanceWindowDefinition, MaintenanceWindowId,
+    },
+    WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::MaintenanceWindowAPIResult;
+
+pub async fn update(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, maintenance_window_id)): Path<(WorkspacePk, MaintenanceWindowId)>,
+    Json(definition): Json<MaintenanceWindowDefinition>,
+) -> MaintenanceWindowAPIResult<Json<MaintenanceWindow>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut window = MaintenanceWindow::get_by_pk(&ctx, maintenance_window_id).await?;
+    window.update(&ctx, definition).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_maintenance_window",
+        serde_json::json!({
+            "how": "/maintenance_window/update",
+            "maintenance_window_id": window.pk(),
+            "schedule": window.schedule(),
+            "timezone": window.timezone(),
+            "duration_minutes": window.duration_minutes(),
+            "schema_id": window.schema_id(),
+            "label": window.label(),
+            "enabled": window.enabled(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(window))
+}
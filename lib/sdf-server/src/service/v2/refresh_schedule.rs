@@ -0,0 +1,54 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, put},
+    Router,
+};
+use dal::{action::refresh_schedule::RefreshScheduleError, TransactionsError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, service::ApiError};
+
+mod create;
+mod list;
+mod remove;
+mod update;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum RefreshScheduleAPIError {
+    #[error("refresh schedule error: {0}")]
+    RefreshSchedule(#[from] RefreshScheduleError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type RefreshScheduleAPIResult<T> = Result<T, RefreshScheduleAPIError>;
+
+impl IntoResponse for RefreshScheduleAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::RefreshSchedule(RefreshScheduleError::IntervalTooShort(_)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            Self::RefreshSchedule(RefreshScheduleError::ScheduleExists(_)) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
+            Self::RefreshSchedule(RefreshScheduleError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list::list).post(create::create))
+        .route(
+            "/:refresh_schedule_id",
+            put(update::update).delete(remove::remove),
+        )
+}
@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Host, OriginalUri},
+    Json,
+};
+use dal::{action::refresh_schedule::RefreshSchedule, SchemaId};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::RefreshScheduleAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Leave empty for the schedule covering the whole workspace.
+    pub schema_id: Option<SchemaId>,
+    pub interval_seconds: i32,
+    pub enabled: bool,
+}
+
+pub async fn create(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Json(Request {
+        schema_id,
+        interval_seconds,
+        enabled,
+    }): Json<Request>,
+) -> RefreshScheduleAPIResult<Json<RefreshSchedule>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let schedule = RefreshSchedule::new(&ctx, schema_id, interval_seconds, enabled).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "create_refresh_schedule",
+        serde_json::json!({
+            "how": "/refresh_schedule/create",
+            "refresh_schedule_id": schedule.pk(),
+            "schema_id": schedule.schema_id(),
+            "interval_seconds": schedule.interval_seconds(),
+            "enabled": schedule.enabled(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(schedule))
+}
@@ -0,0 +1,15 @@
+use axum::Json;
+use dal::action::refresh_schedule::RefreshSchedule;
+
+use crate::{extract::HandlerContext, service::v2::AccessBuilder};
+
+use super::RefreshScheduleAPIResult;
+
+pub async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> RefreshScheduleAPIResult<Json<Vec<RefreshSchedule>>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    Ok(Json(RefreshSchedule::list(&ctx).await?))
+}
@@ -0,0 +1,44 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{
+    action::refresh_schedule::{RefreshSchedule, RefreshScheduleId},
+    WorkspacePk,
+};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::RefreshScheduleAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, refresh_schedule_id)): Path<(WorkspacePk, RefreshScheduleId)>,
+) -> RefreshScheduleAPIResult<()> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let schedule = RefreshSchedule::get_by_pk(&ctx, refresh_schedule_id).await?;
+    RefreshSchedule::delete(&ctx, refresh_schedule_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_refresh_schedule",
+        serde_json::json!({
+            "how": "/refresh_schedule/remove",
+            "refresh_schedule_id": refresh_schedule_id,
+            "schema_id": schedule.schema_id(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{
+    action::refresh_schedule::{RefreshSchedule, RefreshScheduleId},
+    WorkspacePk,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::v2::AccessBuilder,
+    track,
+};
+
+use super::RefreshScheduleAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub interval_seconds: i32,
+    pub enabled: bool,
+}
+
+pub async fn update(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, refresh_schedule_id)): Path<(WorkspacePk, RefreshScheduleId)>,
+    Json(Request {
+        interval_seconds,
+        enabled,
+    }): Json<Request>,
+) -> RefreshScheduleAPIResult<Json<RefreshSchedule>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut schedule = RefreshSchedule::get_by_pk(&ctx, refresh_schedule_id).await?;
+    schedule.update(&ctx, interval_seconds, enabled).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_refresh_schedule",
+        serde_json::json!({
+            "how": "/refresh_schedule/update",
+            "refresh_schedule_id": schedule.pk(),
+            "schema_id": schedule.schema_id(),
+            "interval_seconds": schedule.interval_seconds(),
+            "enabled": schedule.enabled(),
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(schedule))
+}
@@ -0,0 +1,51 @@
+use axum::{
+    extract::Path,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use dal::{
+    component::drift::{ResourceDrift, ResourceDriftError},
+    ChangeSetId, TransactionsError, WorkspacePk,
+};
+use thiserror::Error;
+
+use crate::{
+    app_state::AppState,
+    extract::HandlerContext,
+    service::{v2::AccessBuilder, ApiError},
+};
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ResourceDriftAPIError {
+    #[error("resource drift error: {0}")]
+    ResourceDrift(#[from] ResourceDriftError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ResourceDriftAPIResult<T> = Result<T, ResourceDriftAPIError>;
+
+impl IntoResponse for ResourceDriftAPIError {
+    fn into_response(self) -> Response {
+        ApiError::new(ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new().route("/", get(list))
+}
+
+/// The drift report: every component in the change set whose resource differs from its model.
+async fn list(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> ResourceDriftAPIResult<Json<Vec<ResourceDrift>>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(ResourceDrift::list(&ctx).await?))
+}
@@ -0,0 +1,55 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, put},
+    Router,
+};
+use dal::{ChangeSetError, TransactionsError, WorkspaceVariableError, WsEventError};
+use thiserror::Error;
+
+use crate::{app_state::AppState, service::ApiError};
+
+mod create;
+mod list;
+mod remove;
+mod update;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum WorkspaceVariableAPIError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("workspace variable error: {0}")]
+    WorkspaceVariable(#[from] WorkspaceVariableError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type WorkspaceVariableAPIResult<T> = Result<T, WorkspaceVariableAPIError>;
+
+impl IntoResponse for WorkspaceVariableAPIError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = match self {
+            Self::WorkspaceVariable(
+                WorkspaceVariableError::NameEmpty | WorkspaceVariableError::InvalidValue(..),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::WorkspaceVariable(
+                WorkspaceVariableError::NameTaken(_) | WorkspaceVariableError::InUse(..),
+            ) => (StatusCode::CONFLICT, self.to_string()),
+            _ => (ApiError::DEFAULT_ERROR_STATUS_CODE, self.to_string()),
+        };
+
+        ApiError::new(status_code, error_message).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list::list).post(create::create))
+        .route(
+            "/:workspace_variable_id",
+            put(update::update).delete(remove::remove),
+        )
+}
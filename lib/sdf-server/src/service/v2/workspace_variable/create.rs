@@ -0,0 +1,82 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{ChangeSet, ChangeSetId, WorkspacePk, WorkspaceVariable, WorkspaceVariableKind, WsEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_events::audit_log::AuditLogKind;
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::AccessBuilder},
+    track,
+};
+
+use super::WorkspaceVariableAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: WorkspaceVariableKind,
+    pub value: Option<Value>,
+}
+
+pub async fn create(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(Request {
+        name,
+        description,
+        kind,
+        value,
+    }): Json<Request>,
+) -> WorkspaceVariableAPIResult<ForceChangeSetResponse<WorkspaceVariable>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let workspace_variable = WorkspaceVariable::new(&ctx, name, description, kind, value).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "create_workspace_variable",
+        serde_json::json!({
+            "how": "/workspace_variable/create",
+            "workspace_variable_id": workspace_variable.id(),
+            "workspace_variable_name": workspace_variable.name(),
+            "workspace_variable_kind": workspace_variable.kind(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.write_audit_log(
+        AuditLogKind::CreateWorkspaceVariable {
+            workspace_variable_id: workspace_variable.id(),
+        },
+        workspace_variable.name().to_owned(),
+    )
+    .await?;
+    WsEvent::workspace_variable_created(&ctx, workspace_variable.clone())
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(
+        force_change_set_id,
+        workspace_variable,
+    ))
+}
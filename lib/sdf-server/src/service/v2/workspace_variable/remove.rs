@@ -0,0 +1,64 @@
+use axum::extract::{Host, OriginalUri, Path};
+use dal::{ChangeSet, ChangeSetId, WorkspacePk, WorkspaceVariable, WorkspaceVariableId, WsEvent};
+use si_events::audit_log::AuditLogKind;
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::AccessBuilder},
+    track,
+};
+
+use super::WorkspaceVariableAPIResult;
+
+pub async fn remove(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, workspace_variable_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        WorkspaceVariableId,
+    )>,
+) -> WorkspaceVariableAPIResult<ForceChangeSetResponse<()>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let workspace_variable = WorkspaceVariable::get_by_id(&ctx, workspace_variable_id).await?;
+    WorkspaceVariable::remove(&ctx, workspace_variable_id).await?;
+
+    WsEvent::workspace_variable_deleted(&ctx, workspace_variable_id)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.write_audit_log(
+        AuditLogKind::DeleteWorkspaceVariable {
+            workspace_variable_id,
+        },
+        workspace_variable.name().to_owned(),
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_workspace_variable",
+        serde_json::json!({
+            "how": "/workspace_variable/remove",
+            "workspace_variable_id": workspace_variable_id,
+            "workspace_variable_name": workspace_variable.name(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::empty(force_change_set_id))
+}
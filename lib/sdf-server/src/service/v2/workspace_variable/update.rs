@@ -0,0 +1,85 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    Json,
+};
+use dal::{ChangeSet, ChangeSetId, WorkspacePk, WorkspaceVariable, WorkspaceVariableId, WsEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_events::audit_log::AuditLogKind;
+
+use crate::{
+    extract::{HandlerContext, PosthogClient},
+    service::{force_change_set_response::ForceChangeSetResponse, v2::AccessBuilder},
+    track,
+};
+
+use super::WorkspaceVariableAPIResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub description: Option<String>,
+    pub value: Option<Value>,
+}
+
+pub async fn update(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, workspace_variable_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        WorkspaceVariableId,
+    )>,
+    Json(Request { description, value }): Json<Request>,
+) -> WorkspaceVariableAPIResult<ForceChangeSetResponse<WorkspaceVariable>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    // Changing the value enqueues every attribute value using the variable into the dependent
+    // values update.
+    let workspace_variable = WorkspaceVariable::get_by_id(&ctx, workspace_variable_id)
+        .await?
+        .set_description(&ctx, description)
+        .await?
+        .set_value(&ctx, value)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_workspace_variable",
+        serde_json::json!({
+            "how": "/workspace_variable/update",
+            "workspace_variable_id": workspace_variable.id(),
+            "workspace_variable_name": workspace_variable.name(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.write_audit_log(
+        AuditLogKind::UpdateWorkspaceVariable {
+            workspace_variable_id,
+        },
+        workspace_variable.name().to_owned(),
+    )
+    .await?;
+    WsEvent::workspace_variable_updated(&ctx, workspace_variable.clone())
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(
+        force_change_set_id,
+        workspace_variable,
+    ))
+}
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
-use si_id::{ApprovalRequirementDefinitionId, EntityId, ManagementPrototypeId, UserPk};
+use si_id::{
+    ApprovalRequirementDefinitionId, EntityId, ManagementPrototypeId, UserPk, WorkspaceVariableId,
+};
 use strum::{Display, EnumDiscriminants};
 
 use crate::{
-    func_run::FuncArgumentKind, ActionKind, ActionPrototypeId, Actor, AttributeValueId,
+    func_run::FuncArgumentKind, ActionId, ActionKind, ActionPrototypeId, Actor, AttributeValueId,
     AuthenticationMethod, ChangeSetId, ChangeSetStatus, ComponentId, FuncArgumentId, FuncId,
     FuncKind, FuncRunId, InputSocketId, OutputSocketId, PropId, SchemaId, SchemaVariantId,
     SecretId, ViewId, WorkspacePk,
@@ -44,6 +46,11 @@ pub enum AuditLogKindV1 {
         user_id: UserPk,
     },
     ApplyChangeSet,
+    ApplyChangeSetActionSelection {
+        run: Vec<ActionId>,
+        held: Vec<ActionId>,
+        dropped: Vec<ActionId>,
+    },
     ApproveChangeSetApply {
         from_status: ChangeSetStatus,
     },
@@ -149,6 +156,9 @@ pub enum AuditLogKindV1 {
     CreateView {
         view_id: ViewId,
     },
+    CreateWorkspaceVariable {
+        workspace_variable_id: WorkspaceVariableId,
+    },
     DeleteApprovalRequirementDefinition {
         approval_requirement_definition_id: ApprovalRequirementDefinitionId,
         entity_name: Option<String>,
@@ -195,6 +205,9 @@ pub enum AuditLogKindV1 {
     DeleteView {
         view_id: ViewId,
     },
+    DeleteWorkspaceVariable {
+        workspace_variable_id: WorkspaceVariableId,
+    },
     DetachFunc {
         func_id: FuncId,
         func_display_name: Option<String>,
@@ -405,6 +418,9 @@ pub enum AuditLogKindV1 {
         view_id: ViewId,
         old_name: String,
     },
+    UpdateWorkspaceVariable {
+        workspace_variable_id: WorkspaceVariableId,
+    },
     UpgradeComponent {
         name: String,
         component_id: ComponentId,
@@ -454,6 +470,12 @@ pub enum AuditLogMetadataV1 {
     #[serde(rename_all = "camelCase")]
     ApplyChangeSet,
     #[serde(rename_all = "camelCase")]
+    ApplyChangeSetActionSelection {
+        run: Vec<ActionId>,
+        held: Vec<ActionId>,
+        dropped: Vec<ActionId>,
+    },
+    #[serde(rename_all = "camelCase")]
     ApproveChangeSetApply { from_status: ChangeSetStatus },
     #[serde(rename_all = "camelCase")]
     AttachActionFunc {
@@ -570,6 +592,10 @@ pub enum AuditLogMetadataV1 {
     #[serde(rename_all = "camelCase")]
     CreateView { view_id: ViewId },
     #[serde(rename_all = "camelCase")]
+    CreateWorkspaceVariable {
+        workspace_variable_id: WorkspaceVariableId,
+    },
+    #[serde(rename_all = "camelCase")]
     DeleteApprovalRequirementDefinition {
         individual_approvers: Vec<UserPk>,
         approval_requirement_definition_id: ApprovalRequirementDefinitionId,
@@ -618,6 +644,10 @@ pub enum AuditLogMetadataV1 {
     #[serde(rename_all = "camelCase")]
     DeleteView { view_id: ViewId },
     #[serde(rename_all = "camelCase")]
+    DeleteWorkspaceVariable {
+        workspace_variable_id: WorkspaceVariableId,
+    },
+    #[serde(rename_all = "camelCase")]
     DetachFunc {
         func_id: FuncId,
         func_display_name: Option<String>,
@@ -843,6 +873,10 @@ pub enum AuditLogMetadataV1 {
     #[serde(rename_all = "camelCase")]
     UpdateView { view_id: ViewId, old_name: String },
     #[serde(rename_all = "camelCase")]
+    UpdateWorkspaceVariable {
+        workspace_variable_id: WorkspaceVariableId,
+    },
+    #[serde(rename_all = "camelCase")]
     UpgradeComponent {
         name: String,
         component_id: ComponentId,
@@ -870,6 +904,9 @@ impl AuditLogMetadataV1 {
             MetadataDiscrim::AddAction => ("Enqueued", Some("Action")),
             MetadataDiscrim::AddApprover => ("User Added", Some("Approval Requirement Definition")),
             MetadataDiscrim::ApplyChangeSet => ("Applied", Some("Change Set")),
+            MetadataDiscrim::ApplyChangeSetActionSelection => {
+                ("Selected Actions to Run on Apply", Some("Change Set"))
+            }
             MetadataDiscrim::ApproveChangeSetApply => {
                 ("Approved Request to Apply", Some("Change Set"))
             }
@@ -894,6 +931,7 @@ impl AuditLogMetadataV1 {
             MetadataDiscrim::CreateSchemaVariant => ("Created", Some("Schema Variant")),
             MetadataDiscrim::CreateSecret => ("Created", Some("Secret")),
             MetadataDiscrim::CreateView => ("Created", Some("View")),
+            MetadataDiscrim::CreateWorkspaceVariable => ("Created", Some("Workspace Variable")),
             MetadataDiscrim::DeleteApprovalRequirementDefinition => {
                 ("Deleted", Some("Approval Requirement Definition"))
             }
@@ -904,6 +942,7 @@ impl AuditLogMetadataV1 {
             MetadataDiscrim::DeleteSchemaVariant => ("Deleted", Some("Schema Variant")),
             MetadataDiscrim::DeleteSecret => ("Deleted", Some("Secret")),
             MetadataDiscrim::DeleteView => ("Deleted", Some("View")),
+            MetadataDiscrim::DeleteWorkspaceVariable => ("Deleted", Some("Workspace Variable")),
             MetadataDiscrim::DetachFunc => ("Detached", Some("Function")),
             MetadataDiscrim::ExecuteFunc => ("Executed", Some("Function")),
             MetadataDiscrim::ExportWorkspace => ("Exported", Some("Workspace")),
@@ -943,6 +982,7 @@ impl AuditLogMetadataV1 {
             MetadataDiscrim::UpdateSecret => ("Updated", Some("Secret")),
             MetadataDiscrim::UpdateSchemaVariant => ("Updated", Some("Schema Variant")),
             MetadataDiscrim::UpdateView => ("Updated", Some("View")),
+            MetadataDiscrim::UpdateWorkspaceVariable => ("Updated", Some("Workspace Variable")),
             MetadataDiscrim::UpgradeComponent => ("Upgraded", Some("Component")),
             MetadataDiscrim::WithdrawRequestForChangeSetApply => {
                 ("Withdrew Request to Apply", Some("Change Set"))
@@ -985,6 +1025,9 @@ impl From<Kind> for Metadata {
                 entity_id,
             },
             Kind::ApplyChangeSet => Self::ApplyChangeSet,
+            Kind::ApplyChangeSetActionSelection { run, held, dropped } => {
+                Self::ApplyChangeSetActionSelection { run, held, dropped }
+            }
             Kind::ApproveChangeSetApply { from_status } => {
                 Self::ApproveChangeSetApply { from_status }
             }
@@ -1165,6 +1208,11 @@ impl From<Kind> for Metadata {
             },
             Kind::CreateSecret { name, secret_id } => Self::CreateSecret { name, secret_id },
             Kind::CreateView { view_id } => Self::CreateView { view_id },
+            Kind::CreateWorkspaceVariable {
+                workspace_variable_id,
+            } => Self::CreateWorkspaceVariable {
+                workspace_variable_id,
+            },
             Kind::DeleteApprovalRequirementDefinition {
                 individual_approvers,
                 approval_requirement_definition_id,
@@ -1237,6 +1285,11 @@ impl From<Kind> for Metadata {
             },
             Kind::DeleteSecret { name, secret_id } => Self::DeleteSecret { name, secret_id },
             Kind::DeleteView { view_id } => Self::DeleteView { view_id },
+            Kind::DeleteWorkspaceVariable {
+                workspace_variable_id,
+            } => Self::DeleteWorkspaceVariable {
+                workspace_variable_id,
+            },
             Kind::DetachFunc {
                 func_id,
                 func_display_name,
@@ -1582,6 +1635,11 @@ impl From<Kind> for Metadata {
             },
             Kind::UpdateSecret { name, secret_id } => Self::UpdateSecret { name, secret_id },
             Kind::UpdateView { view_id, old_name } => Self::UpdateView { view_id, old_name },
+            Kind::UpdateWorkspaceVariable {
+                workspace_variable_id,
+            } => Self::UpdateWorkspaceVariable {
+                workspace_variable_id,
+            },
             Kind::UpgradeComponent {
                 name,
                 component_id,
@@ -42,6 +42,7 @@ pub enum FuncKind {
     SchemaVariantDefinition,
     Unknown,
     Management,
+    Plan,
 }
 
 /// Describes the kind of [`FuncArgument`](crate::FuncArgument).
@@ -98,6 +99,13 @@ pub enum FuncBackendKind {
     ResourcePayloadToValue,
     NormalizeToArray,
     Float,
+    Arithmetic,
+    Coalesce,
+    JoinArray,
+    JsonPointer,
+    StringTemplate,
+    /// A WebAssembly component, run by cyclone instead of lang-js.
+    Wasm,
 }
 
 // NOTE(nick,zack): do not add "remain::sorted" for postcard de/ser. We need the order to be
@@ -136,6 +144,7 @@ pub enum FuncBackendResponseType {
     Void,
     Management,
     Float,
+    Plan,
 }
 
 #[remain::sorted]
@@ -160,6 +169,25 @@ pub enum ActionResultState {
     Success,
     Failure,
     Unknown,
+    /// The action function ran for longer than its timeout and was killed.
+    Timeout,
+}
+
+/// How urgently a [`FuncRun`] should be executed, relative to other runs waiting for an
+/// execution slot.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Display, EnumString, Hash,
+)]
+pub enum ExecutionPriority {
+    /// Action functions being dispatched from the action queue.
+    Action,
+    /// Work that nobody is waiting on, like refreshing resources or computing action plans.
+    Background,
+    /// Attribute and validation functions run as part of a dependent values update.
+    Dvu,
+    /// Work a user is actively waiting on, like a test run in the func editor.
+    Interactive,
 }
 
 #[derive(Debug, Clone, Builder, Serialize, Deserialize)]
@@ -196,6 +224,7 @@ pub struct FuncRun {
     #[builder(default)]
     function_display_name: Option<String>,
     function_kind: FuncKind,
+    priority: ExecutionPriority,
     #[builder(default)]
     function_description: Option<String>,
     #[builder(default)]
@@ -365,6 +394,11 @@ impl FuncRun {
         self.function_kind
     }
 
+    /// The [`ExecutionPriority`] the run is dispatched with, chosen by whoever started the run.
+    pub fn priority(&self) -> ExecutionPriority {
+        self.priority
+    }
+
     pub fn function_args_cas_address(&self) -> ContentHash {
         self.function_args_cas_address
     }
@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
 
-use crate::{FuncRunId, Tenancy};
+use crate::{ComponentId, FuncRunId, Tenancy};
 
 pub use si_id::FuncRunLogId;
 
@@ -77,3 +78,72 @@ impl FuncRunLog {
         self.finalized = true;
     }
 }
+
+/// How important a [`FuncRunLogRecord`] is, from least to most.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Display,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum FuncRunLogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl FuncRunLogLevel {
+    /// Maps the free-form level of an [`OutputLine`] onto a level, treating anything unknown as
+    /// [`Info`](Self::Info).
+    pub fn from_output_level(level: &str) -> Self {
+        match level.to_lowercase().as_str() {
+            "trace" | "debug" => Self::Debug,
+            "warn" | "warning" => Self::Warn,
+            "error" | "fatal" => Self::Error,
+            _ => Self::Info,
+        }
+    }
+
+    /// This level and every level more important than it.
+    pub fn and_above(self) -> Vec<Self> {
+        [Self::Debug, Self::Info, Self::Warn, Self::Error]
+            .into_iter()
+            .filter(|level| *level >= self)
+            .collect()
+    }
+}
+
+/// One line of a func run's logs, kept alongside the [`FuncRunLog`] so that lines can be searched
+/// across a workspace's func runs.
+///
+/// Unlike [`OutputLine`], a record has a normalized level and may carry the key/value fields of a
+/// structured log line. Records are stored as JSON, not postcard, so they can grow new fields.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunLogRecord {
+    pub func_run_id: FuncRunId,
+    pub tenancy: Tenancy,
+    /// The line's position within the func run's logs.
+    pub seq: u32,
+    pub timestamp: DateTime<Utc>,
+    pub level: FuncRunLogLevel,
+    pub stream: String,
+    pub message: String,
+    /// The key/value fields of a structured log line, if it was one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<serde_json::Map<String, serde_json::Value>>,
+    pub func_name: String,
+    pub component_id: Option<ComponentId>,
+}
@@ -43,11 +43,11 @@ pub use crate::{
     func_execution::*,
     func_run::{
         ActionId, ActionKind, ActionPrototypeId, ActionResultState, AttributePrototypeArgumentId,
-        AttributePrototypeId, AttributeValueId, ComponentId, FuncArgumentKind, FuncBackendKind,
-        FuncBackendResponseType, FuncKind, FuncRun, FuncRunBuilder, FuncRunBuilderError, FuncRunId,
-        FuncRunState, FuncRunValue, ManagementPrototypeId, ViewId,
+        AttributePrototypeId, AttributeValueId, ComponentId, ExecutionPriority, FuncArgumentKind,
+        FuncBackendKind, FuncBackendResponseType, FuncKind, FuncRun, FuncRunBuilder,
+        FuncRunBuilderError, FuncRunId, FuncRunState, FuncRunValue, ManagementPrototypeId, ViewId,
     },
-    func_run_log::{FuncRunLog, FuncRunLogId, OutputLine},
+    func_run_log::{FuncRunLog, FuncRunLogId, FuncRunLogLevel, FuncRunLogRecord, OutputLine},
     resource_metadata::{ResourceMetadata, ResourceStatus},
     schema::SchemaId,
     schema_variant::{PropId, SchemaVariantId},
@@ -20,20 +20,27 @@ pub struct Change {
 pub enum EntityKind {
     Action,
     ActionPrototype,
+    ActionRetryPolicy,
     ApprovalRequirementDefinition,
     AttributePrototype,
     AttributePrototypeArgument,
+    AttributeSubscription,
     AttributeValue,
     CategoryAction,
+    CategoryActionRetryPolicy,
+    CategoryAttributeSubscription,
     CategoryComponent,
     CategoryDependentValueRoots,
     CategoryDeprecatedActionBatch,
     CategoryDiagramObject,
     CategoryFunc,
+    CategoryFuncTestCase,
+    CategoryFuncTimeout,
     CategoryModule,
     CategorySchema,
     CategorySecret,
     CategoryView,
+    CategoryWorkspaceVariable,
     Component,
     DependentValueRoot,
     DiagramObject,
@@ -41,6 +48,8 @@ pub enum EntityKind {
     FinishedDependentValueRoot,
     Func,
     FuncArgument,
+    FuncTestCase,
+    FuncTimeout,
     Geometry,
     InputSocket,
     JsonValue,
@@ -58,4 +67,5 @@ pub enum EntityKind {
     ValidationOutput,
     ValidationPrototype,
     View,
+    WorkspaceVariable,
 }
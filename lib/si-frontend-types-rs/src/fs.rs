@@ -83,6 +83,7 @@ pub fn kind_to_string(kind: FuncKind) -> String {
         FuncKind::SchemaVariantDefinition => "asset-definition",
         FuncKind::Unknown => "unknown",
         FuncKind::Management => "management",
+        FuncKind::Plan => "plan",
     }
     .into()
 }
@@ -98,6 +99,7 @@ pub fn kind_pluralized_to_string(kind: FuncKind) -> String {
         FuncKind::SchemaVariantDefinition => "asset-definitions",
         FuncKind::Unknown => "unknowns",
         FuncKind::Management => "managements",
+        FuncKind::Plan => "plans",
     }
     .into()
 }
@@ -113,6 +115,7 @@ pub fn kind_from_string(s: &str) -> Option<FuncKind> {
         "asset-definition" => FuncKind::SchemaVariantDefinition,
         "unknown" => FuncKind::Unknown,
         "management" => FuncKind::Management,
+        "plan" => FuncKind::Plan,
         _ => return None,
     })
 }
@@ -4,9 +4,10 @@ use strum::{AsRefStr, Display, EnumIter, EnumString};
 pub use si_events::FuncKind;
 use si_events::{
     ActionKind, ActionPrototypeId, AttributePrototypeArgumentId, AttributePrototypeId, ComponentId,
-    FuncArgumentId, FuncBackendKind, FuncId, InputSocketId, ManagementPrototypeId, OutputSocketId,
-    PropId, SchemaVariantId, Timestamp,
+    FuncArgumentId, FuncBackendKind, FuncId, FuncRunId, InputSocketId, ManagementPrototypeId,
+    OutputSocketId, PropId, SchemaVariantId, Timestamp,
 };
+use si_id::FuncTestCaseId;
 
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -32,6 +33,7 @@ pub struct FuncSummary {
     pub display_name: Option<String>,
     pub description: Option<String>,
     pub is_locked: bool,
+    pub always_execute: bool,
     pub arguments: Vec<FuncArgument>,
     pub bindings: Vec<FuncBinding>,
     pub types: Option<String>,
@@ -256,3 +258,42 @@ pub enum FuncArgumentKind {
     Object,
     String,
 }
+
+/// The outcome of running a single func test case.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestCaseRunResult {
+    pub func_test_case_id: FuncTestCaseId,
+    pub func_test_case_name: String,
+    pub func_id: FuncId,
+    pub func_name: String,
+    pub func_run_id: FuncRunId,
+    pub passed: bool,
+    pub output: Option<serde_json::Value>,
+    /// Why the func failed, if it did.
+    pub failure: Option<String>,
+}
+
+/// The outcome of running a set of func test cases.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncTestReport {
+    pub passed: usize,
+    pub failed: usize,
+    pub results: Vec<FuncTestCaseRunResult>,
+}
+
+impl FuncTestReport {
+    pub fn new(results: Vec<FuncTestCaseRunResult>) -> Self {
+        let passed = results.iter().filter(|result| result.passed).count();
+        Self {
+            passed,
+            failed: results.len() - passed,
+            results,
+        }
+    }
+
+    pub fn all_passed(&self) -> bool {
+        self.failed == 0
+    }
+}
@@ -30,7 +30,7 @@ pub use crate::{
     conflict::ConflictWithHead,
     func::{
         AttributeArgumentBinding, FuncArgument, FuncArgumentKind, FuncBinding, FuncBindings,
-        FuncCode, FuncKind, FuncSummary, LeafInputLocation,
+        FuncCode, FuncKind, FuncSummary, FuncTestCaseRunResult, FuncTestReport, LeafInputLocation,
     },
     materialized_view::MaterializedView,
     module::{
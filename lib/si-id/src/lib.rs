@@ -34,10 +34,12 @@ pub use ::ulid as ulid_upstream;
 
 // Please keep these alphabetically sorted!
 id!(ActionPrototypeId);
+id!(ActionRetryPolicyId);
 id!(ActivityId);
 id!(ApprovalRequirementDefinitionId);
 id!(AttributePrototypeArgumentId);
 id!(AttributePrototypeId);
+id!(AttributeSubscriptionId);
 id!(AuthenticationPrototypeId);
 id!(AuthTokenId);
 id!(DeprecatedVectorClockId);
@@ -46,6 +48,8 @@ id!(EntityId);
 id!(FuncArgumentId);
 id!(FuncExecutionPk);
 id!(FuncRunLogId);
+id!(FuncTestCaseId);
+id!(FuncTimeoutId);
 id!(GeometryId);
 id!(HistoryEventPk);
 id!(InputSocketId);
@@ -64,15 +68,21 @@ id!(VectorClockActorId);
 id!(VectorClockChangeSetId);
 id!(ViewId);
 id!(WorkspaceSnapshotNodeId);
+id!(WorkspaceVariableId);
 
 // Please keep these alphabetically sorted!
+id_with_pg_types!(ActionConcurrencyGroupId);
 id_with_pg_types!(ActionId);
 id_with_pg_types!(CachedModuleId);
 id_with_pg_types!(ChangeSetId);
 id_with_pg_types!(ChangeSetApprovalId);
 id_with_pg_types!(ComponentId);
+id_with_pg_types!(FuncEgressPolicyId);
 id_with_pg_types!(FuncId);
 id_with_pg_types!(FuncRunId);
+id_with_pg_types!(FuncRunRetentionPolicyId);
+id_with_pg_types!(MaintenanceWindowId);
+id_with_pg_types!(RefreshScheduleId);
 id_with_pg_types!(UserPk);
 id_with_pg_types!(WorkspaceIntegrationId);
 
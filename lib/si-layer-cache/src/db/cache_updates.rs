@@ -170,6 +170,9 @@ where
                         .insert_from_cache_updates(event.key, serialized_value);
                 }
             }
+            crate::event::LayeredEventKind::CasEvict => {
+                self.cas_cache.evict_from_cache_updates(event.key);
+            }
             crate::event::LayeredEventKind::EncryptedSecretInsertion => {
                 if !self.encrypted_secret_cache.contains(&event.key) {
                     let serialized_value =
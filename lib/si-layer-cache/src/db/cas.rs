@@ -19,6 +19,9 @@ use super::serialize;
 pub const DBNAME: &str = "cas";
 pub const CACHE_NAME: &str = "cas";
 pub const PARTITION_KEY: &str = "cas";
+/// The sort key of the arguments, code, and results written for func runs. Nothing but func runs
+/// refers to these, so they can be removed once no func run uses them.
+pub const FUNC_RUN_DATA_SORT_KEY: &str = "func_run_data";
 
 #[derive(Debug, Clone)]
 pub struct CasDb<V>
@@ -47,6 +50,30 @@ where
         web_events: Option<Vec<WebEvent>>,
         tenancy: Tenancy,
         actor: Actor,
+    ) -> LayerDbResult<(ContentHash, PersisterStatusReader)> {
+        self.write_with_sort_key(value, PARTITION_KEY, web_events, tenancy, actor)
+    }
+
+    /// Writes a func run's arguments, code, or results, which func run retention removes once no
+    /// func run uses them. They must never be written by [`Self::write`] as well.
+    #[instrument(name = "cas.write_func_run_data", level = "debug", skip_all)]
+    pub fn write_func_run_data(
+        &self,
+        value: Arc<V>,
+        web_events: Option<Vec<WebEvent>>,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<(ContentHash, PersisterStatusReader)> {
+        self.write_with_sort_key(value, FUNC_RUN_DATA_SORT_KEY, web_events, tenancy, actor)
+    }
+
+    fn write_with_sort_key(
+        &self,
+        value: Arc<V>,
+        sort_key: &str,
+        web_events: Option<Vec<WebEvent>>,
+        tenancy: Tenancy,
+        actor: Actor,
     ) -> LayerDbResult<(ContentHash, PersisterStatusReader)> {
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
         let key = ContentHash::new(&postcard_value);
@@ -60,7 +87,7 @@ where
             Arc::new(DBNAME.to_string()),
             cache_key,
             Arc::new(postcard_value),
-            Arc::new("cas".to_string()),
+            Arc::new(sort_key.to_string()),
             web_events,
             tenancy,
             actor,
@@ -70,6 +97,41 @@ where
         Ok((key, reader))
     }
 
+    /// Drops a value from the caches of every service, once its durable copy has been removed
+    /// from pg. The durable copy is left alone, since only the caller knows whether it is still
+    /// safe to remove.
+    #[instrument(
+        name = "cas.evict_from_caches",
+        level = "debug",
+        skip_all,
+        fields(
+            si.cas.address = %key,
+        )
+    )]
+    pub fn evict_from_caches(
+        &self,
+        key: &ContentHash,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::CasEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new("cas".to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_event(event)?;
+
+        Ok(reader)
+    }
+
     pub async fn read(&self, key: &ContentHash) -> LayerDbResult<Option<Arc<V>>> {
         self.cache.get(key.to_string().into()).await
     }
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use si_events::{
     ActionId, ActionResultState, Actor, AttributeValueId, ChangeSetId, ComponentId, ContentHash,
     FuncId, FuncRun, FuncRunId, Tenancy, WebEvent, WorkspacePk,
@@ -23,6 +24,12 @@ pub const DBNAME: &str = "func_runs";
 pub const CACHE_NAME: &str = DBNAME;
 pub const PARTITION_KEY: &str = "workspace_id";
 
+/// Matches func runs that use the content store address in `$1` for anything.
+const CAS_ADDRESS_MATCHES: &str = "json_value -> 'function_args_cas_address' = $1
+    OR json_value -> 'function_code_cas_address' = $1
+    OR json_value -> 'result_value_cas_address' = $1
+    OR json_value -> 'result_unprocessed_value_cas_address' = $1";
+
 #[derive(Debug, Clone)]
 pub struct FuncRunDb {
     pub cache: Arc<LayerCache<Arc<FuncRun>>>,
@@ -31,8 +38,10 @@ pub struct FuncRunDb {
     get_last_qualification_for_attribute_value_id: String,
     list_action_history: String,
     get_last_action_by_action_id: String,
+    get_last_plan_by_action_id: String,
     list_management_history: String,
     get_last_management_by_func_and_component_id: String,
+    list_expired_for_workspace_id: String,
 }
 
 impl FuncRunDb {
@@ -61,6 +70,13 @@ impl FuncRunDb {
                   ORDER BY updated_at DESC
                   LIMIT 1",
             ),
+            get_last_plan_by_action_id: format!(
+                "
+                SELECT value FROM {DBNAME}
+                  WHERE function_kind = 'Plan' AND workspace_id = $1 AND action_id = $2
+                  ORDER BY updated_at DESC
+                  LIMIT 1",
+            ),
             list_management_history: format!(
                 r#"
                 SELECT value FROM {DBNAME}
@@ -76,6 +92,44 @@ impl FuncRunDb {
                 LIMIT 1
             "#
             ),
+            // Ranks each run within its action (or management func and component), component,
+            // func, and attribute value, newest first. A run is expired once it is older than the
+            // cutoff, has finished, and is outside the newest `$3` of every group it belongs to.
+            // The newest run and the newest successful run for an attribute value are always kept,
+            // since they are looked up to show the attribute's current state.
+            list_expired_for_workspace_id: format!(
+                r#"
+                SELECT value FROM (
+                    SELECT value, state, updated_at,
+                        CASE WHEN action_id IS NULL THEN NULL ELSE ROW_NUMBER() OVER (
+                            PARTITION BY action_id, component_id, function_kind ORDER BY updated_at DESC
+                        ) END AS action_rank,
+                        CASE WHEN component_id IS NULL THEN NULL ELSE ROW_NUMBER() OVER (
+                            PARTITION BY component_id ORDER BY updated_at DESC
+                        ) END AS component_rank,
+                        ROW_NUMBER() OVER (
+                            PARTITION BY json_value ->> 'function_name' ORDER BY updated_at DESC
+                        ) AS func_rank,
+                        CASE WHEN attribute_value_id IS NULL THEN NULL ELSE ROW_NUMBER() OVER (
+                            PARTITION BY attribute_value_id ORDER BY updated_at DESC
+                        ) END AS attribute_value_rank,
+                        CASE WHEN attribute_value_id IS NULL OR state <> 'Success' THEN NULL ELSE ROW_NUMBER() OVER (
+                            PARTITION BY attribute_value_id, state = 'Success' ORDER BY updated_at DESC
+                        ) END AS attribute_value_success_rank
+                    FROM {DBNAME}
+                    WHERE workspace_id = $1
+                ) AS ranked
+                WHERE updated_at < $2
+                    AND state IN ('Success', 'Failure', 'Killed')
+                    AND (action_rank IS NULL OR action_rank > $3)
+                    AND (component_rank IS NULL OR component_rank > $3)
+                    AND func_rank > $3
+                    AND (attribute_value_rank IS NULL OR attribute_value_rank > 1)
+                    AND (attribute_value_success_rank IS NULL OR attribute_value_success_rank > 1)
+                ORDER BY updated_at
+                LIMIT $4
+            "#
+            ),
         }
     }
 
@@ -127,6 +181,31 @@ impl FuncRunDb {
         Ok(maybe_func)
     }
 
+    /// Returns the most recent plan [`FuncRun`] for the given action, if any.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_last_plan_run_for_action_id(
+        &self,
+        workspace_pk: WorkspacePk,
+        action_id: ActionId,
+    ) -> LayerDbResult<Option<FuncRun>> {
+        let maybe_row = self
+            .cache
+            .pg()
+            .query_opt(
+                &self.get_last_plan_by_action_id,
+                &[&workspace_pk, &action_id],
+            )
+            .await?;
+
+        let maybe_func = if let Some(row) = maybe_row {
+            Some(serialize::from_bytes(row.get("value"))?)
+        } else {
+            None
+        };
+
+        Ok(maybe_func)
+    }
+
     pub async fn list_management_history(
         &self,
         workspace_pk: WorkspacePk,
@@ -369,6 +448,191 @@ impl FuncRunDb {
             .ok_or_else(|| LayerDbError::MissingFuncRun(key))
     }
 
+    /// Lists the workspaces that have any func runs.
+    pub async fn list_workspace_ids(&self) -> LayerDbResult<Vec<WorkspacePk>> {
+        let rows = self
+            .cache
+            .pg()
+            .query(&format!("SELECT DISTINCT workspace_id FROM {DBNAME}"), &[])
+            .await?
+            .unwrap_or_default();
+        let mut workspace_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            let workspace_id: String = row.get("workspace_id");
+            // Skip anything that isn't a workspace id rather than failing the whole listing
+            if let Ok(workspace_id) = workspace_id.parse() {
+                workspace_ids.push(workspace_id);
+            }
+        }
+        Ok(workspace_ids)
+    }
+
+    /// Lists up to `limit` of a workspace's func runs that are older than `cutoff` and are not
+    /// among the newest `keep_last` runs for their action, component, or func, oldest first.
+    ///
+    /// Runs that have not finished are never expired, and neither are the newest run and the
+    /// newest successful run for each attribute value.
+    pub async fn list_expired(
+        &self,
+        workspace_id: WorkspacePk,
+        keep_last: u32,
+        cutoff: DateTime<Utc>,
+        limit: u32,
+    ) -> LayerDbResult<Vec<FuncRun>> {
+        let rows = self
+            .cache
+            .pg()
+            .query(
+                &self.list_expired_for_workspace_id,
+                &[
+                    &workspace_id,
+                    &cutoff,
+                    &i64::from(keep_last),
+                    &i64::from(limit),
+                ],
+            )
+            .await?
+            .unwrap_or_default();
+        let mut func_runs = Vec::with_capacity(rows.len());
+        for row in rows {
+            func_runs.push(serialize::from_bytes(row.get("value"))?);
+        }
+        Ok(func_runs)
+    }
+
+    /// Deletes the given func runs from the database. Their logs and the values they reference are
+    /// left alone.
+    pub async fn delete_many(&self, func_run_ids: &[FuncRunId]) -> LayerDbResult<()> {
+        let keys: Vec<String> = func_run_ids.iter().map(ToString::to_string).collect();
+        self.cache
+            .pg()
+            .insert_raw(
+                &format!("DELETE FROM {DBNAME} WHERE key = ANY($1)"),
+                &[&keys],
+            )
+            .await?;
+        for key in &keys {
+            self.cache.remove_from_memory(key);
+        }
+        Ok(())
+    }
+
+    /// Whether any func run, other than the ones being ignored, uses the given address for its
+    /// arguments, code, or results.
+    pub async fn references_cas_address(
+        &self,
+        address: ContentHash,
+        ignoring: &[FuncRunId],
+    ) -> LayerDbResult<bool> {
+        // Addresses are stored as byte arrays in the JSON, so compare against the same encoding
+        let address = serde_json::to_value(address)?;
+        let ignoring: Vec<String> = ignoring.iter().map(ToString::to_string).collect();
+        let maybe_row = self
+            .cache
+            .pg()
+            .query_opt(
+                &format!(
+                    "SELECT key FROM {DBNAME}
+                       WHERE ({CAS_ADDRESS_MATCHES})
+                         AND NOT key = ANY($2)
+                       LIMIT 1"
+                ),
+                &[&address, &ignoring],
+            )
+            .await?;
+        Ok(maybe_row.is_some())
+    }
+
+    /// Which of the given content store addresses hold func run data, which nothing but func runs
+    /// refers to. See [`CasDb::write_func_run_data`](super::cas::CasDb::write_func_run_data).
+    pub async fn filter_func_run_data(
+        &self,
+        addresses: &[ContentHash],
+    ) -> LayerDbResult<Vec<ContentHash>> {
+        let keys: Vec<String> = addresses.iter().map(ToString::to_string).collect();
+        let rows = self
+            .cache
+            .pg()
+            .query(
+                &format!(
+                    "SELECT key FROM {cas} WHERE key = ANY($1) AND sort_key = $2",
+                    cas = super::cas::DBNAME,
+                ),
+                &[&keys, &super::cas::FUNC_RUN_DATA_SORT_KEY],
+            )
+            .await?
+            .unwrap_or_default();
+        rows.into_iter()
+            .map(|row| Ok(row.get::<_, String>("key").parse()?))
+            .collect()
+    }
+
+    /// Marks content store entries that no func run uses any more, to be removed by a later
+    /// [`Self::sweep_cas_candidate`]. Entries that are already marked keep their first mark.
+    pub async fn mark_cas_candidates(&self, addresses: &[ContentHash]) -> LayerDbResult<()> {
+        let keys: Vec<String> = addresses.iter().map(ToString::to_string).collect();
+        self.cache
+            .pg()
+            .insert_raw(
+                "INSERT INTO func_run_gc_candidates (key)
+                    SELECT * FROM UNNEST($1::text[])
+                    ON CONFLICT DO NOTHING",
+                &[&keys],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Lists the content store entries marked before the given time, oldest mark first.
+    pub async fn list_cas_candidates(
+        &self,
+        marked_before: DateTime<Utc>,
+        limit: u32,
+    ) -> LayerDbResult<Vec<ContentHash>> {
+        let rows = self
+            .cache
+            .pg()
+            .query(
+                "SELECT key FROM func_run_gc_candidates
+                    WHERE marked_at < $1
+                    ORDER BY marked_at
+                    LIMIT $2",
+                &[&marked_before, &i64::from(limit)],
+            )
+            .await?
+            .unwrap_or_default();
+        rows.into_iter()
+            .map(|row| Ok(row.get::<_, String>("key").parse()?))
+            .collect()
+    }
+
+    /// Unmarks a content store entry, and removes its durable copy unless a func run has started
+    /// using it again since it was marked. The check and the delete are one statement, so a run
+    /// written at the same time keeps the entry. Returns whether the entry was removed.
+    pub async fn sweep_cas_candidate(&self, address: ContentHash) -> LayerDbResult<bool> {
+        let key = address.to_string();
+        let address = serde_json::to_value(address)?;
+        let maybe_row = self
+            .cache
+            .pg()
+            .query_opt(
+                &format!(
+                    "WITH candidate AS (
+                       DELETE FROM func_run_gc_candidates WHERE key = $2 RETURNING key
+                     )
+                     DELETE FROM {cas}
+                       WHERE key IN (SELECT key FROM candidate)
+                         AND sort_key = $3
+                         AND NOT EXISTS (SELECT 1 FROM {DBNAME} WHERE {CAS_ADDRESS_MATCHES})
+                     RETURNING key",
+                    cas = super::cas::DBNAME,
+                ),
+                &[&address, &key, &super::cas::FUNC_RUN_DATA_SORT_KEY],
+            )
+            .await?;
+        Ok(maybe_row.is_some())
+    }
+
     // NOTE(nick): this is just to test that things are working. We probably want some customization
     // for where clauses, etc. in the real version. This should be a step closer to how we'll query
     // for history though.
@@ -1,6 +1,11 @@
 use std::sync::Arc;
 
-use si_events::{Actor, FuncRunId, FuncRunLog, Tenancy, WebEvent};
+use chrono::{DateTime, Utc};
+use si_data_pg::postgres_types::ToSql;
+use si_events::{
+    Actor, ChangeSetId, ComponentId, FuncRunId, FuncRunLog, FuncRunLogLevel, FuncRunLogRecord,
+    Tenancy, WebEvent, WorkspacePk,
+};
 
 use crate::{
     error::LayerDbResult,
@@ -14,6 +19,29 @@ use super::serialize;
 pub const DBNAME: &str = "func_run_logs";
 pub const CACHE_NAME: &str = DBNAME;
 pub const PARTITION_KEY: &str = "workspace_id";
+pub const RECORDS_TABLE_NAME: &str = "func_run_log_records";
+
+/// How many records a search returns when it does not ask for a number.
+pub const DEFAULT_SEARCH_LIMIT: u32 = 100;
+/// The most records a single search can return.
+pub const MAX_SEARCH_LIMIT: u32 = 1000;
+
+/// Filters for [`FuncRunLogDb::search`]. Every filter that is set has to match.
+#[derive(Debug, Clone, Default)]
+pub struct FuncRunLogSearch {
+    /// Only records at this level or above.
+    pub min_level: Option<FuncRunLogLevel>,
+    /// Only records whose message or fields contain this text, ignoring case.
+    pub text: Option<String>,
+    pub component_id: Option<ComponentId>,
+    pub func_name: Option<String>,
+    pub change_set_id: Option<ChangeSetId>,
+    /// Only records logged at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only records logged before this time.
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+}
 
 #[derive(Debug, Clone)]
 pub struct FuncRunLogDb {
@@ -121,4 +149,185 @@ impl FuncRunLogDb {
             .await?;
         Ok(())
     }
+
+    /// Deletes the logs and log records of the given func runs.
+    pub async fn delete_for_func_run_ids(&self, func_run_ids: &[FuncRunId]) -> LayerDbResult<()> {
+        let func_run_ids: Vec<String> = func_run_ids.iter().map(ToString::to_string).collect();
+        let deleted_rows = self
+            .cache
+            .pg()
+            .query(
+                &format!("DELETE FROM {DBNAME} WHERE func_run_id = ANY($1) RETURNING key"),
+                &[&func_run_ids],
+            )
+            .await?
+            .unwrap_or_default();
+        for row in deleted_rows {
+            self.cache.remove_from_memory(row.get("key"));
+        }
+        self.cache
+            .pg()
+            .insert_raw(
+                &format!("DELETE FROM {RECORDS_TABLE_NAME} WHERE func_run_id = ANY($1)"),
+                &[&func_run_ids],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Stores the records of a func run's log lines, so they can be found with
+    /// [`search`](Self::search). They are written with a single statement, once the func run's
+    /// logs are finalized. Writing a record that already exists does nothing.
+    pub async fn write_records(&self, records: &[FuncRunLogRecord]) -> LayerDbResult<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let mut func_run_ids = Vec::with_capacity(records.len());
+        let mut seqs = Vec::with_capacity(records.len());
+        let mut workspace_ids = Vec::with_capacity(records.len());
+        let mut change_set_ids = Vec::with_capacity(records.len());
+        let mut timestamps = Vec::with_capacity(records.len());
+        let mut levels = Vec::with_capacity(records.len());
+        let mut func_names = Vec::with_capacity(records.len());
+        let mut component_ids = Vec::with_capacity(records.len());
+        let mut messages = Vec::with_capacity(records.len());
+        let mut json_values = Vec::with_capacity(records.len());
+        for record in records {
+            func_run_ids.push(record.func_run_id.to_string());
+            seqs.push(i64::from(record.seq));
+            workspace_ids.push(record.tenancy.workspace_pk.to_string());
+            change_set_ids.push(record.tenancy.change_set_id.to_string());
+            timestamps.push(record.timestamp);
+            levels.push(record.level.to_string());
+            func_names.push(record.func_name.as_str());
+            component_ids.push(record.component_id.map(|v| v.to_string()));
+            messages.push(record.message.as_str());
+            json_values.push(serde_json::to_value(record)?);
+        }
+
+        self.cache
+            .pg()
+            .insert_raw(
+                &format!(
+                    "INSERT INTO {RECORDS_TABLE_NAME} (
+                    func_run_id,
+                    seq,
+                    workspace_id,
+                    change_set_id,
+                    timestamp,
+                    level,
+                    func_name,
+                    component_id,
+                    message,
+                    json_value
+                ) SELECT * FROM UNNEST(
+                    $1::text[],
+                    $2::bigint[],
+                    $3::text[],
+                    $4::text[],
+                    $5::timestamptz[],
+                    $6::text[],
+                    $7::text[],
+                    $8::text[],
+                    $9::text[],
+                    $10::jsonb[]
+                ) ON CONFLICT (func_run_id, seq) DO NOTHING;"
+                ),
+                &[
+                    &func_run_ids,
+                    &seqs,
+                    &workspace_ids,
+                    &change_set_ids,
+                    &timestamps,
+                    &levels,
+                    &func_names,
+                    &component_ids,
+                    &messages,
+                    &json_values,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Finds the log records across all of a workspace's func runs that match the search, most
+    /// recent first.
+    pub async fn search(
+        &self,
+        workspace_pk: WorkspacePk,
+        search: &FuncRunLogSearch,
+    ) -> LayerDbResult<Vec<FuncRunLogRecord>> {
+        let workspace_id = workspace_pk.to_string();
+        let levels: Option<Vec<String>> = search
+            .min_level
+            .map(|level| level.and_above().iter().map(ToString::to_string).collect());
+        let text = search.text.as_deref().map(|text| {
+            let escaped = text
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{escaped}%")
+        });
+        let component_id = search.component_id.map(|v| v.to_string());
+        let change_set_id = search.change_set_id.map(|v| v.to_string());
+        let limit = i64::from(
+            search
+                .limit
+                .unwrap_or(DEFAULT_SEARCH_LIMIT)
+                .min(MAX_SEARCH_LIMIT),
+        );
+
+        let mut query =
+            format!("SELECT json_value FROM {RECORDS_TABLE_NAME} WHERE workspace_id = $1");
+        let mut params: Vec<&(dyn ToSql + Sync)> = vec![&workspace_id];
+        if let Some(levels) = &levels {
+            params.push(levels);
+            query.push_str(&format!(" AND level = ANY(${})", params.len()));
+        }
+        if let Some(text) = &text {
+            params.push(text);
+            query.push_str(&format!(
+                " AND (message ILIKE ${n} ESCAPE '\\' OR json_value->>'fields' ILIKE ${n} ESCAPE '\\')",
+                n = params.len()
+            ));
+        }
+        if let Some(component_id) = &component_id {
+            params.push(component_id);
+            query.push_str(&format!(" AND component_id = ${}", params.len()));
+        }
+        if let Some(func_name) = &search.func_name {
+            params.push(func_name);
+            query.push_str(&format!(" AND func_name = ${}", params.len()));
+        }
+        if let Some(change_set_id) = &change_set_id {
+            params.push(change_set_id);
+            query.push_str(&format!(" AND change_set_id = ${}", params.len()));
+        }
+        if let Some(since) = &search.since {
+            params.push(since);
+            query.push_str(&format!(" AND timestamp >= ${}", params.len()));
+        }
+        if let Some(until) = &search.until {
+            params.push(until);
+            query.push_str(&format!(" AND timestamp < ${}", params.len()));
+        }
+        params.push(&limit);
+        query.push_str(&format!(
+            " ORDER BY timestamp DESC, seq DESC LIMIT ${}",
+            params.len()
+        ));
+
+        let rows = self
+            .cache
+            .pg()
+            .query(&query, &params)
+            .await?
+            .unwrap_or_default();
+        let mut records = Vec::with_capacity(rows.len());
+        for row in rows {
+            records.push(serde_json::from_value(row.get("json_value"))?);
+        }
+        Ok(records)
+    }
 }
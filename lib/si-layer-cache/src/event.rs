@@ -67,6 +67,7 @@ pub use si_id::LayeredEventId;
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum LayeredEventKind {
+    CasEvict,
     CasInsertion,
     EncryptedSecretInsertion,
     FuncRunLogWrite,
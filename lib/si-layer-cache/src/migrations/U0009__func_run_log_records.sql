@@ -0,0 +1,18 @@
+CREATE TABLE func_run_log_records
+(
+    func_run_id   text                     NOT NULL,
+    seq           bigint                   NOT NULL,
+    workspace_id  text                     NOT NULL,
+    change_set_id text                     NOT NULL,
+    timestamp     timestamp with time zone NOT NULL,
+    level         text                     NOT NULL,
+    func_name     text                     NOT NULL,
+    component_id  text,
+    message       text                     NOT NULL,
+    json_value    jsonb                    NOT NULL,
+    PRIMARY KEY (func_run_id, seq)
+);
+
+CREATE INDEX IF NOT EXISTS func_run_log_records_by_workspace_id ON func_run_log_records (workspace_id, timestamp DESC);
+CREATE INDEX IF NOT EXISTS func_run_log_records_by_component_id ON func_run_log_records (component_id, workspace_id, timestamp DESC);
+CREATE INDEX IF NOT EXISTS func_run_log_records_by_func_name ON func_run_log_records (func_name, workspace_id, timestamp DESC);
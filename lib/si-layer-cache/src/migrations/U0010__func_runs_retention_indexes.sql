@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS func_runs_workspace_id_and_updated_at ON func_runs (workspace_id, updated_at DESC);
+CREATE INDEX IF NOT EXISTS func_runs_by_function_args_cas_address ON func_runs ((json_value -> 'function_args_cas_address'));
+CREATE INDEX IF NOT EXISTS func_runs_by_function_code_cas_address ON func_runs ((json_value -> 'function_code_cas_address'));
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS func_runs_by_result_value_cas_address ON func_runs ((json_value -> 'result_value_cas_address'));
+CREATE INDEX IF NOT EXISTS func_runs_by_result_unprocessed_value_cas_address ON func_runs ((json_value -> 'result_unprocessed_value_cas_address'));
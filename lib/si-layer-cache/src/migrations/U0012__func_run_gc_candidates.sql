@@ -0,0 +1,7 @@
+CREATE TABLE func_run_gc_candidates
+(
+    key       text                     NOT NULL PRIMARY KEY,
+    marked_at timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX IF NOT EXISTS func_run_gc_candidates_by_marked_at ON func_run_gc_candidates (marked_at);
@@ -267,6 +267,11 @@ impl PersistEventTask {
 
     #[instrument(level = "debug", skip_all)]
     pub async fn evict_from_pg(&self, event: Arc<LayeredEvent>) -> LayerDbResult<()> {
+        // Content is only removed from pg by whoever decided it was no longer used, since it may
+        // have been written again in the meantime; this eviction is just for the caches.
+        if matches!(event.event_kind, LayeredEventKind::CasEvict) {
+            return Ok(());
+        }
         let pg_layer = PgLayer::new(self.pg_pool.clone(), event.payload.db_name.as_ref());
         pg_layer.delete(&event.payload.key).await?;
         Ok(())
@@ -323,7 +328,8 @@ impl PersistEventTask {
     pub async fn write_to_pg(&self, event: Arc<LayeredEvent>) -> LayerDbResult<()> {
         let pg_layer = PgLayer::new(self.pg_pool.clone(), event.payload.db_name.as_ref());
         match event.event_kind {
-            LayeredEventKind::CasInsertion
+            LayeredEventKind::CasEvict
+            | LayeredEventKind::CasInsertion
             | LayeredEventKind::EncryptedSecretInsertion
             | LayeredEventKind::Raw
             | LayeredEventKind::RebaseBatchEvict
@@ -264,6 +264,101 @@ async fn writes_are_gossiped() {
     assert_eq!(cas_value.as_ref(), &in_pg);
 }
 
+#[tokio::test]
+async fn evictions_are_gossiped() {
+    let token = CancellationToken::new();
+
+    let db = setup_pg_db("cas_evictions_are_gossiped").await;
+
+    let compute_executor = setup_compute_executor();
+
+    let (ldb_slash, _): (TestLayerDb, _) = LayerDb::from_services(
+        db.clone(),
+        setup_nats_client(Some("cas_evictions_are_gossiped".to_string())).await,
+        compute_executor.clone(),
+        CacheConfig::default(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb_slash.pg_migrate().await.expect("migrate layerdb");
+
+    let (ldb_axl, _): (TestLayerDb, _) = LayerDb::from_services(
+        db,
+        setup_nats_client(Some("cas_evictions_are_gossiped".to_string())).await,
+        compute_executor,
+        CacheConfig::default(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb_axl.pg_migrate().await.expect("migrate layerdb");
+
+    let (tenancy, actor) = (
+        Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+        Actor::User(UserPk::new()),
+    );
+    let cas_value: Arc<CasValue> = Arc::new(serde_json::json!("velvet revolver").into());
+    let (cas_pk, status) = ldb_slash
+        .cas()
+        .write(cas_value.clone(), None, tenancy, actor)
+        .expect("failed to write to layerdb");
+    assert!(
+        matches!(
+            status.get_status().await.expect("failed to get status"),
+            PersistStatus::Finished
+        ),
+        "persister failed"
+    );
+    let cas_pk_str: Arc<str> = cas_pk.to_string().into();
+    assert_eq!(
+        Some(cas_value),
+        ldb_axl
+            .cas()
+            .read(&cas_pk)
+            .await
+            .expect("failed to read from layerdb")
+    );
+
+    let status = ldb_slash
+        .cas()
+        .evict_from_caches(&cas_pk, tenancy, actor)
+        .expect("failed to evict");
+    assert!(
+        matches!(
+            status.get_status().await.expect("failed to get status"),
+            PersistStatus::Finished
+        ),
+        "persister failed"
+    );
+    assert!(!ldb_slash.cas().cache.contains(&cas_pk_str));
+
+    let max_check_count = 10;
+
+    let mut memory_check_count = 0;
+    while memory_check_count <= max_check_count {
+        if !ldb_axl.cas().cache.contains(&cas_pk_str) {
+            break;
+        }
+        memory_check_count += 1;
+        tokio::time::sleep_until(Instant::now() + Duration::from_millis(1)).await;
+    }
+    assert_ne!(
+        max_check_count, memory_check_count,
+        "value was not evicted from the remote memory cache within 10ms"
+    );
+
+    // Evicting only clears the caches
+    assert!(ldb_axl
+        .cas()
+        .cache
+        .pg()
+        .get(&cas_pk_str)
+        .await
+        .expect("error getting data from pg")
+        .is_some());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn stress_test() {
     let token = CancellationToken::new();
@@ -1,11 +1,12 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use si_layer_cache::hybrid_cache::CacheConfig;
 use std::collections::HashSet;
 use std::{sync::Arc, time::Duration};
 
 use si_events::{
-    Actor, ChangeSetId, ContentHash, FuncBackendKind, FuncBackendResponseType, FuncKind, FuncRun,
-    FuncRunBuilder, FuncRunId, Tenancy, UserPk, WorkspacePk,
+    Actor, ChangeSetId, ComponentId, ContentHash, ExecutionPriority, FuncBackendKind,
+    FuncBackendResponseType, FuncKind, FuncRun, FuncRunBuilder, FuncRunId, FuncRunState, Tenancy,
+    UserPk, WorkspacePk,
 };
 use si_layer_cache::db::serialize;
 use si_layer_cache::LayerDb;
@@ -229,6 +230,297 @@ async fn write_and_read_many_for_workspace_id() {
     );
 }
 
+#[tokio::test]
+async fn list_expired_and_delete_many() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        setup_pg_db("func_run_list_expired_and_delete_many").await,
+        setup_nats_client(Some("func_run_list_expired_and_delete_many".to_string())).await,
+        setup_compute_executor(),
+        CacheConfig::default(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate ldb");
+
+    let (tenancy, actor) = (
+        Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+        Actor::User(UserPk::new()),
+    );
+    let first_component_id = ComponentId::new();
+    let second_component_id = ComponentId::new();
+    let days_ago = |days| Utc::now() - chrono::Duration::days(days);
+    let run = |function_name: &str,
+               component_id: Option<ComponentId>,
+               state: FuncRunState,
+               updated_at: DateTime<Utc>| {
+        FuncRunBuilder::default()
+            .actor(actor)
+            .tenancy(tenancy)
+            .state(state)
+            .component_id(component_id)
+            .attribute_value_id(None)
+            .backend_kind(FuncBackendKind::JsAttribute)
+            .backend_response_type(FuncBackendResponseType::Object)
+            .function_name(function_name.to_string())
+            .function_kind(FuncKind::Attribute)
+            .priority(ExecutionPriority::Dvu)
+            .function_args_cas_address(ContentHash::new(function_name.as_bytes()))
+            .function_code_cas_address(ContentHash::new(updated_at.to_rfc3339().as_bytes()))
+            .created_at(updated_at)
+            .updated_at(updated_at)
+            .build()
+            .expect("could not build func run")
+    };
+
+    let still_running = run(
+        "gimme",
+        Some(first_component_id),
+        FuncRunState::Running,
+        days_ago(11),
+    );
+    let oldest = run(
+        "gimme",
+        Some(first_component_id),
+        FuncRunState::Success,
+        days_ago(10),
+    );
+    let older = run(
+        "gimme",
+        Some(first_component_id),
+        FuncRunState::Failure,
+        days_ago(9),
+    );
+    let newest = run(
+        "gimme",
+        Some(first_component_id),
+        FuncRunState::Success,
+        days_ago(8),
+    );
+    let only_for_its_component = run(
+        "gimme",
+        Some(second_component_id),
+        FuncRunState::Success,
+        days_ago(10),
+    );
+    let only_for_its_func = run("shelter", None, FuncRunState::Success, days_ago(10));
+    for func_run in [
+        &still_running,
+        &oldest,
+        &older,
+        &newest,
+        &only_for_its_component,
+        &only_for_its_func,
+    ] {
+        ldb.func_run()
+            .write(Arc::new(func_run.clone()), None, tenancy, actor)
+            .await
+            .expect("failed to write to layerdb");
+    }
+
+    assert_eq!(
+        vec![tenancy.workspace_pk],
+        ldb.func_run()
+            .list_workspace_ids()
+            .await
+            .expect("failed to list workspace ids")
+    );
+
+    let expired = ldb
+        .func_run()
+        .list_expired(tenancy.workspace_pk, 1, days_ago(1), 100)
+        .await
+        .expect("failed to list expired func runs");
+    assert_eq!(
+        vec![oldest.id(), older.id()],
+        expired.iter().map(|v| v.id()).collect::<Vec<_>>()
+    );
+
+    // Nothing is expired while it is younger than the cutoff, or among the runs to keep
+    assert!(ldb
+        .func_run()
+        .list_expired(tenancy.workspace_pk, 1, days_ago(12), 100)
+        .await
+        .expect("failed to list expired func runs")
+        .is_empty());
+    assert!(ldb
+        .func_run()
+        .list_expired(tenancy.workspace_pk, 3, days_ago(1), 100)
+        .await
+        .expect("failed to list expired func runs")
+        .is_empty());
+
+    assert!(ldb
+        .func_run()
+        .references_cas_address(oldest.function_code_cas_address(), &[])
+        .await
+        .expect("failed to check references"));
+    assert!(!ldb
+        .func_run()
+        .references_cas_address(oldest.function_code_cas_address(), &[oldest.id()])
+        .await
+        .expect("failed to check references"));
+
+    ldb.func_run()
+        .delete_many(&[oldest.id(), older.id()])
+        .await
+        .expect("failed to delete func runs");
+
+    assert!(ldb
+        .func_run()
+        .list_expired(tenancy.workspace_pk, 1, days_ago(1), 100)
+        .await
+        .expect("failed to list expired func runs")
+        .is_empty());
+    assert!(!ldb
+        .func_run()
+        .references_cas_address(oldest.function_code_cas_address(), &[])
+        .await
+        .expect("failed to check references"));
+    // The args are the same for every run of the func, so they are still in use
+    assert!(ldb
+        .func_run()
+        .references_cas_address(oldest.function_args_cas_address(), &[])
+        .await
+        .expect("failed to check references"));
+}
+
+#[tokio::test]
+async fn mark_and_sweep_cas_candidates() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        setup_pg_db("func_run_mark_and_sweep_cas_candidates").await,
+        setup_nats_client(Some("func_run_mark_and_sweep_cas_candidates".to_string())).await,
+        setup_compute_executor(),
+        CacheConfig::default(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate ldb");
+
+    let (tenancy, actor) = (
+        Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+        Actor::User(UserPk::new()),
+    );
+    let (address, status) = ldb
+        .cas()
+        .write_func_run_data(
+            Arc::new("war never changes".to_string()),
+            None,
+            tenancy,
+            actor,
+        )
+        .expect("failed to write to cas");
+    status.get_status().await.expect("failed to persist cas");
+    let (shared_address, status) = ldb
+        .cas()
+        .write(Arc::new("shared".to_string()), None, tenancy, actor)
+        .expect("failed to write to cas");
+    status.get_status().await.expect("failed to persist cas");
+
+    // Only content written for func runs is func run data
+    assert_eq!(
+        vec![address],
+        ldb.func_run()
+            .filter_func_run_data(&[address, shared_address])
+            .await
+            .expect("failed to filter func run data")
+    );
+
+    let marked_before = Utc::now();
+    ldb.func_run()
+        .mark_cas_candidates(&[address])
+        .await
+        .expect("failed to mark cas candidates");
+    assert!(ldb
+        .func_run()
+        .list_cas_candidates(marked_before, 100)
+        .await
+        .expect("failed to list cas candidates")
+        .is_empty());
+    assert_eq!(
+        vec![address],
+        ldb.func_run()
+            .list_cas_candidates(Utc::now(), 100)
+            .await
+            .expect("failed to list cas candidates")
+    );
+
+    // A run started using the content after it was marked, so it stays and is unmarked
+    let mut func_run = create_func_run(actor, tenancy, "ron perlman");
+    func_run.set_result_value_cas_address(Some(address));
+    ldb.func_run()
+        .write(Arc::new(func_run.clone()), None, tenancy, actor)
+        .await
+        .expect("failed to write to layerdb");
+    assert!(!ldb
+        .func_run()
+        .sweep_cas_candidate(address)
+        .await
+        .expect("failed to sweep cas candidate"));
+    assert!(ldb
+        .cas()
+        .cache
+        .pg()
+        .get(&address.to_string())
+        .await
+        .expect("failed to read cas from pg")
+        .is_some());
+    assert!(ldb
+        .func_run()
+        .list_cas_candidates(Utc::now(), 100)
+        .await
+        .expect("failed to list cas candidates")
+        .is_empty());
+
+    // Content that is not marked is never swept
+    ldb.func_run()
+        .delete_many(&[func_run.id()])
+        .await
+        .expect("failed to delete func runs");
+    assert!(!ldb
+        .func_run()
+        .sweep_cas_candidate(address)
+        .await
+        .expect("failed to sweep cas candidate"));
+
+    ldb.func_run()
+        .mark_cas_candidates(&[address, shared_address])
+        .await
+        .expect("failed to mark cas candidates");
+    assert!(ldb
+        .func_run()
+        .sweep_cas_candidate(address)
+        .await
+        .expect("failed to sweep cas candidate"));
+    assert!(ldb
+        .cas()
+        .cache
+        .pg()
+        .get(&address.to_string())
+        .await
+        .expect("failed to read cas from pg")
+        .is_none());
+    // Nor is content that isn't func run data
+    assert!(!ldb
+        .func_run()
+        .sweep_cas_candidate(shared_address)
+        .await
+        .expect("failed to sweep cas candidate"));
+    assert!(ldb
+        .cas()
+        .cache
+        .pg()
+        .get(&shared_address.to_string())
+        .await
+        .expect("failed to read cas from pg")
+        .is_some());
+}
+
 fn create_func_run(actor: Actor, tenancy: Tenancy, function_name: impl Into<String>) -> FuncRun {
     let func_run_create_time = Utc::now();
     FuncRunBuilder::default()
@@ -240,6 +532,7 @@ fn create_func_run(actor: Actor, tenancy: Tenancy, function_name: impl Into<Stri
         .backend_response_type(FuncBackendResponseType::Action)
         .function_name(function_name.into())
         .function_kind(FuncKind::Action)
+        .priority(ExecutionPriority::Action)
         .function_args_cas_address(ContentHash::default())
         .function_code_cas_address(ContentHash::default())
         .created_at(func_run_create_time)
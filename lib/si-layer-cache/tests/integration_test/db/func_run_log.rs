@@ -1,9 +1,11 @@
 use std::{sync::Arc, time::Duration};
 
+use chrono::Utc;
 use si_events::{
-    Actor, ChangeSetId, FuncRunId, FuncRunLog, OutputLine, Tenancy, UserPk, WorkspacePk,
+    Actor, ChangeSetId, ComponentId, FuncRunId, FuncRunLog, FuncRunLogLevel, FuncRunLogRecord,
+    OutputLine, Tenancy, UserPk, WorkspacePk,
 };
-use si_layer_cache::LayerDb;
+use si_layer_cache::{db::func_run_log::FuncRunLogSearch, LayerDb};
 use si_layer_cache::{db::serialize, hybrid_cache::CacheConfig};
 use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
@@ -227,3 +229,137 @@ async fn write_and_get_for_func_run_id() {
 
     assert_eq!(value.id(), read_value.id());
 }
+
+#[tokio::test]
+async fn write_and_search_records() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        setup_pg_db("func_run_log_write_and_search_records").await,
+        setup_nats_client(Some("func_run_log_write_and_search_records".to_string())).await,
+        setup_compute_executor(),
+        CacheConfig::default(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate ldb");
+
+    let tenancy = Tenancy::new(WorkspacePk::new(), ChangeSetId::new());
+    let refresh_component_id = ComponentId::new();
+    let refresh_run_id = FuncRunId::new();
+    let other_run_id = FuncRunId::new();
+    let record =
+        |func_run_id, seq, level, message: &str, func_name: &str, component_id| FuncRunLogRecord {
+            func_run_id,
+            tenancy,
+            seq,
+            timestamp: Utc::now(),
+            level,
+            stream: "stdout".to_string(),
+            message: message.to_string(),
+            fields: None,
+            func_name: func_name.to_string(),
+            component_id,
+        };
+    let mut forbidden = record(
+        refresh_run_id,
+        1,
+        FuncRunLogLevel::Error,
+        "request failed",
+        "awsEc2Refresh",
+        Some(refresh_component_id),
+    );
+    forbidden.fields = Some(serde_json::Map::from_iter([(
+        "status".to_string(),
+        serde_json::json!(403),
+    )]));
+    let records = vec![
+        record(
+            refresh_run_id,
+            0,
+            FuncRunLogLevel::Info,
+            "refreshing instance",
+            "awsEc2Refresh",
+            Some(refresh_component_id),
+        ),
+        forbidden.clone(),
+        record(
+            other_run_id,
+            0,
+            FuncRunLogLevel::Warn,
+            "100% of quota used",
+            "awsQuotaCheck",
+            None,
+        ),
+    ];
+    ldb.func_run_log()
+        .write_records(&records)
+        .await
+        .expect("failed to write records");
+    // Writing a record twice keeps the first one
+    ldb.func_run_log()
+        .write_records(&records[..1])
+        .await
+        .expect("failed to write record again");
+
+    let search = |search: FuncRunLogSearch| {
+        let ldb = ldb.clone();
+        async move {
+            ldb.func_run_log()
+                .search(tenancy.workspace_pk, &search)
+                .await
+                .expect("failed to search records")
+        }
+    };
+
+    assert_eq!(3, search(FuncRunLogSearch::default()).await.len());
+
+    let found = search(FuncRunLogSearch {
+        min_level: Some(FuncRunLogLevel::Warn),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(2, found.len());
+    assert!(found
+        .iter()
+        .all(|record| record.level >= FuncRunLogLevel::Warn));
+
+    let found = search(FuncRunLogSearch {
+        text: Some("403".to_string()),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(vec![forbidden], found);
+
+    // Wildcards in the text are matched literally
+    let found = search(FuncRunLogSearch {
+        text: Some("100%".to_string()),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(1, found.len());
+    assert_eq!(other_run_id, found[0].func_run_id);
+
+    let found = search(FuncRunLogSearch {
+        component_id: Some(refresh_component_id),
+        func_name: Some("awsEc2Refresh".to_string()),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(2, found.len());
+
+    let found = search(FuncRunLogSearch {
+        since: Some(Utc::now()),
+        ..Default::default()
+    })
+    .await;
+    assert!(found.is_empty());
+
+    let found = ldb
+        .func_run_log()
+        .search(WorkspacePk::new(), &FuncRunLogSearch::default())
+        .await
+        .expect("failed to search records");
+    assert!(found.is_empty());
+}
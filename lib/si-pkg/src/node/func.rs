@@ -162,6 +162,9 @@ impl NodeChild for FuncSpec {
             .arguments
             .iter()
             .map(|arg| Box::new(arg.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>)
+            .chain(self.test_cases.iter().map(|test_case| {
+                Box::new(test_case.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>
+            }))
             .collect();
 
         NodeWithChildren::new(
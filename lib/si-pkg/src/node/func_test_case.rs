@@ -0,0 +1,106 @@
+use super::{read_common_fields, write_common_fields, PkgNode};
+use crate::spec::{FuncTestCaseSpec, FuncTestCaseSpecExpectation};
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NameStr, NodeChild, NodeKind,
+    NodeWithChildren, ReadBytes, WriteBytes,
+};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+const KEY_NAME_STR: &str = "name";
+const KEY_ARGS_STR: &str = "args";
+const KEY_EXPECTATION_STR: &str = "expectation";
+const KEY_EXPECTED_STR: &str = "expected";
+
+#[derive(Clone, Debug)]
+pub struct FuncTestCaseNode {
+    pub name: String,
+    pub args: serde_json::Value,
+    pub expectation: FuncTestCaseSpecExpectation,
+    pub expected: Option<serde_json::Value>,
+    pub unique_id: Option<String>,
+    pub deleted: bool,
+}
+
+impl NameStr for FuncTestCaseNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl WriteBytes for FuncTestCaseNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_NAME_STR, &self.name)?;
+        write_key_value_line(
+            writer,
+            KEY_ARGS_STR,
+            serde_json::to_string(&self.args).map_err(GraphError::parse)?,
+        )?;
+        write_key_value_line(writer, KEY_EXPECTATION_STR, self.expectation)?;
+        write_key_value_line(
+            writer,
+            KEY_EXPECTED_STR,
+            match &self.expected {
+                Some(expected) => serde_json::to_string(expected).map_err(GraphError::parse)?,
+                None => "".to_string(),
+            },
+        )?;
+
+        write_common_fields(writer, self.unique_id.as_deref(), self.deleted)?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for FuncTestCaseNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Option<Self>, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let name = read_key_value_line(reader, KEY_NAME_STR)?;
+
+        let args_str = read_key_value_line(reader, KEY_ARGS_STR)?;
+        let args = serde_json::from_str(&args_str).map_err(GraphError::parse)?;
+
+        let expectation_str = read_key_value_line(reader, KEY_EXPECTATION_STR)?;
+        let expectation =
+            FuncTestCaseSpecExpectation::from_str(&expectation_str).map_err(GraphError::parse)?;
+
+        let expected_str = read_key_value_line(reader, KEY_EXPECTED_STR)?;
+        let expected = if expected_str.is_empty() {
+            None
+        } else {
+            Some(serde_json::from_str(&expected_str).map_err(GraphError::parse)?)
+        };
+
+        let (unique_id, deleted) = read_common_fields(reader)?;
+
+        Ok(Some(Self {
+            name,
+            args,
+            expectation,
+            expected,
+            unique_id,
+            deleted,
+        }))
+    }
+}
+
+impl NodeChild for FuncTestCaseSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Leaf,
+            Self::NodeType::FuncTestCase(FuncTestCaseNode {
+                name: self.name.to_string(),
+                args: self.args.to_owned(),
+                expectation: self.expectation,
+                expected: self.expected.to_owned(),
+                unique_id: self.unique_id.to_owned(),
+                deleted: self.deleted,
+            }),
+            vec![],
+        )
+    }
+}
@@ -23,6 +23,7 @@ mod component_child;
 mod edge;
 mod func;
 mod func_argument;
+mod func_test_case;
 mod leaf_function;
 mod management_func;
 mod map_key_func;
@@ -50,6 +51,7 @@ pub(crate) use self::{
     edge::EdgeNode,
     func::FuncNode,
     func_argument::FuncArgumentNode,
+    func_test_case::FuncTestCaseNode,
     leaf_function::LeafFunctionNode,
     map_key_func::MapKeyFuncNode,
     package::PackageNode,
@@ -77,6 +79,7 @@ const NODE_KIND_COMPONENT_CHILD: &str = "component_child";
 const NODE_KIND_EDGE: &str = "edge";
 const NODE_KIND_FUNC: &str = "func";
 const NODE_KIND_FUNC_ARGUMENT: &str = "func_argument";
+const NODE_KIND_FUNC_TEST_CASE: &str = "func_test_case";
 const NODE_KIND_LEAF_FUNCTION: &str = "leaf_function";
 const NODE_KIND_MANAGEMENT_FUNC: &str = "management_func";
 const NODE_KIND_MAP_KEY_FUNC: &str = "map_key_func";
@@ -150,6 +153,7 @@ pub enum PkgNode {
     Edge(EdgeNode),
     Func(FuncNode),
     FuncArgument(FuncArgumentNode),
+    FuncTestCase(FuncTestCaseNode),
     LeafFunction(LeafFunctionNode),
     ManagementFunc(ManagementFuncNode),
     MapKeyFunc(MapKeyFuncNode),
@@ -179,6 +183,7 @@ impl PkgNode {
     pub const NODE_KIND_EDGE_STR: &'static str = NODE_KIND_EDGE;
     pub const FUNC_KIND_STR: &'static str = NODE_KIND_FUNC;
     pub const FUNC_ARGUMENT_KIND_STR: &'static str = NODE_KIND_FUNC_ARGUMENT;
+    pub const FUNC_TEST_CASE_KIND_STR: &'static str = NODE_KIND_FUNC_TEST_CASE;
     pub const LEAF_FUNCTION_KIND_STR: &'static str = NODE_KIND_LEAF_FUNCTION;
     pub const MANAGEMENT_FUNC_KIND_STR: &'static str = NODE_KIND_MANAGEMENT_FUNC;
     pub const MAP_KEY_FUNC_KIND_STR: &'static str = NODE_KIND_MAP_KEY_FUNC;
@@ -207,6 +212,7 @@ impl PkgNode {
             Self::Edge(_) => NODE_KIND_EDGE,
             Self::Func(_) => NODE_KIND_FUNC,
             Self::FuncArgument(_) => NODE_KIND_FUNC_ARGUMENT,
+            Self::FuncTestCase(_) => NODE_KIND_FUNC_TEST_CASE,
             Self::LeafFunction(_) => NODE_KIND_LEAF_FUNCTION,
             Self::ManagementFunc(_) => NODE_KIND_MANAGEMENT_FUNC,
             Self::MapKeyFunc(_) => NODE_KIND_MAP_KEY_FUNC,
@@ -247,6 +253,7 @@ impl NameStr for PkgNode {
             Self::Edge(_) => NODE_KIND_EDGE,
             Self::Func(node) => node.name(),
             Self::FuncArgument(node) => node.name(),
+            Self::FuncTestCase(node) => node.name(),
             Self::LeafFunction(_) => NODE_KIND_LEAF_FUNCTION,
             Self::ManagementFunc(_) => NODE_KIND_MANAGEMENT_FUNC,
             Self::MapKeyFunc(_) => NODE_KIND_MAP_KEY_FUNC,
@@ -283,6 +290,7 @@ impl WriteBytes for PkgNode {
             Self::Edge(node) => node.write_bytes(writer)?,
             Self::Func(node) => node.write_bytes(writer)?,
             Self::FuncArgument(node) => node.write_bytes(writer)?,
+            Self::FuncTestCase(node) => node.write_bytes(writer)?,
             Self::LeafFunction(node) => node.write_bytes(writer)?,
             Self::ManagementFunc(node) => node.write_bytes(writer)?,
             Self::MapKeyFunc(node) => node.write_bytes(writer)?,
@@ -335,6 +343,9 @@ impl ReadBytes for PkgNode {
             NODE_KIND_FUNC_ARGUMENT => {
                 FuncArgumentNode::read_bytes(reader)?.map(Self::FuncArgument)
             }
+            NODE_KIND_FUNC_TEST_CASE => {
+                FuncTestCaseNode::read_bytes(reader)?.map(Self::FuncTestCase)
+            }
             NODE_KIND_LEAF_FUNCTION => {
                 LeafFunctionNode::read_bytes(reader)?.map(Self::LeafFunction)
             }
@@ -7,7 +7,7 @@ use crate::{
     node::PkgNode,
     spec::{
         FuncArgumentKind, FuncArgumentSpec, FuncSpec, FuncSpecBackendKind,
-        FuncSpecBackendResponseType, FuncSpecData,
+        FuncSpecBackendResponseType, FuncSpecData, FuncTestCaseSpec, FuncTestCaseSpecExpectation,
     },
 };
 
@@ -96,6 +96,96 @@ impl<'a> TryFrom<SiPkgFuncArgument<'a>> for FuncArgumentSpec {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct SiPkgFuncTestCase<'a> {
+    name: String,
+    args: serde_json::Value,
+    expectation: FuncTestCaseSpecExpectation,
+    expected: Option<serde_json::Value>,
+    unique_id: Option<String>,
+    deleted: bool,
+
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgFuncTestCase<'a> {
+    fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::FuncTestCase(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::FUNC_TEST_CASE_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: node.name,
+            args: node.args,
+            expectation: node.expectation,
+            expected: node.expected,
+            unique_id: node.unique_id,
+            deleted: node.deleted,
+
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn args(&self) -> &serde_json::Value {
+        &self.args
+    }
+
+    pub fn expectation(&self) -> FuncTestCaseSpecExpectation {
+        self.expectation
+    }
+
+    pub fn expected(&self) -> Option<&serde_json::Value> {
+        self.expected.as_ref()
+    }
+
+    pub fn unique_id(&self) -> Option<&str> {
+        self.unique_id.as_deref()
+    }
+
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgFuncTestCase<'a>> for FuncTestCaseSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgFuncTestCase<'a>) -> Result<Self, Self::Error> {
+        Ok(FuncTestCaseSpec::builder()
+            .name(value.name)
+            .args(value.args)
+            .expectation(value.expectation)
+            .expected(value.expected)
+            .unique_id(value.unique_id)
+            .deleted(value.deleted)
+            .build()?)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SiPkgFuncData {
     name: String,
@@ -203,12 +293,29 @@ impl<'a> SiPkgFunc<'a> {
             .graph
             .neighbors_directed(self.source.node_idx, Outgoing)
         {
-            arguments.push(SiPkgFuncArgument::from_graph(self.source.graph, idx)?);
+            if let PkgNode::FuncArgument(_) = self.source.graph[idx].inner() {
+                arguments.push(SiPkgFuncArgument::from_graph(self.source.graph, idx)?);
+            }
         }
 
         Ok(arguments)
     }
 
+    pub fn test_cases(&self) -> PkgResult<Vec<SiPkgFuncTestCase>> {
+        let mut test_cases = vec![];
+        for idx in self
+            .source
+            .graph
+            .neighbors_directed(self.source.node_idx, Outgoing)
+        {
+            if let PkgNode::FuncTestCase(_) = self.source.graph[idx].inner() {
+                test_cases.push(SiPkgFuncTestCase::from_graph(self.source.graph, idx)?);
+            }
+        }
+
+        Ok(test_cases)
+    }
+
     pub fn name(&self) -> &str {
         self.name.as_ref()
     }
@@ -320,6 +427,10 @@ impl<'a> TryFrom<SiPkgFunc<'a>> for FuncSpec {
             builder.argument(argument.try_into()?);
         }
 
+        for test_case in value.test_cases()? {
+            builder.test_case(test_case.try_into()?);
+        }
+
         Ok(builder.build()?)
     }
 }
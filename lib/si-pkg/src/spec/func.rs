@@ -62,20 +62,77 @@ impl FuncArgumentSpec {
     }
 }
 
+#[remain::sorted]
+#[derive(
+    Deserialize,
+    Serialize,
+    AsRefStr,
+    Display,
+    EnumIter,
+    EnumString,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum FuncTestCaseSpecExpectation {
+    Contains,
+    Equals,
+    Fails,
+    Succeeds,
+}
+
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct FuncTestCaseSpec {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into))]
+    pub args: serde_json::Value,
+    #[builder(setter(into))]
+    pub expectation: FuncTestCaseSpecExpectation,
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub expected: Option<serde_json::Value>,
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub unique_id: Option<String>,
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+impl FuncTestCaseSpec {
+    pub fn builder() -> FuncTestCaseSpecBuilder {
+        FuncTestCaseSpecBuilder::default()
+    }
+
+    pub fn anonymize(&mut self) {
+        self.unique_id = None;
+    }
+}
+
 #[remain::sorted]
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, AsRefStr, Display, EnumIter, EnumString)]
 #[serde(rename_all = "camelCase")]
 pub enum FuncSpecBackendKind {
+    Arithmetic,
     Array,
     Boolean,
+    Coalesce,
     Diff,
     Float,
     Identity,
     Integer,
+    JoinArray,
     JsAction,
     JsAttribute,
     JsAuthentication,
     Json,
+    JsonPointer,
     // NOTE(nick): this is deprecated, but keeping it for now in case something from the module
     // index needs it.
     JsReconciliation,
@@ -87,8 +144,10 @@ pub enum FuncSpecBackendKind {
     Object,
     ResourcePayloadToValue,
     String,
+    StringTemplate,
     Unset,
     Validation,
+    Wasm,
 }
 
 #[remain::sorted]
@@ -106,6 +165,7 @@ pub enum FuncSpecBackendResponseType {
     Management,
     Map,
     Object,
+    Plan,
     Qualification,
     // NOTE(nick): this is deprecated, but keeping it for now in case something from the module
     // index needs it.
@@ -183,6 +243,9 @@ pub struct FuncSpec {
 
     #[builder(setter(each(name = "argument"), into), default)]
     pub arguments: Vec<FuncArgumentSpec>,
+    #[builder(setter(each(name = "test_case"), into), default)]
+    #[serde(default)]
+    pub test_cases: Vec<FuncTestCaseSpec>,
 }
 
 impl FuncSpecBuilder {
@@ -208,5 +271,6 @@ impl FuncSpec {
     pub fn anonymize(&mut self) {
         self.unique_id = String::new();
         self.arguments.iter_mut().for_each(|f| f.anonymize());
+        self.test_cases.iter_mut().for_each(|f| f.anonymize());
     }
 }
@@ -1,5 +1,6 @@
 //! Cyclone implementations of [`Instance`][`super::Instance`].
 
+pub use cgroup::CgroupLimits;
 pub use local_http::{
     LocalHttpInstance, LocalHttpInstanceError, LocalHttpInstanceSpec, LocalHttpInstanceSpecBuilder,
     LocalHttpSocketStrategy,
@@ -9,5 +10,6 @@ pub use local_uds::{
     LocalUdsRuntimeStrategy, LocalUdsSocketStrategy,
 };
 
+mod cgroup;
 mod local_http;
 mod local_uds;
@@ -0,0 +1,176 @@
+//! Resource limits for locally spawned Cyclone processes, enforced with cgroup v2.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io,
+    path::PathBuf,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::time;
+use tracing::{debug, warn};
+
+/// The `cpu.max` period, in microseconds (the kernel's default).
+const CPU_MAX_PERIOD_USECS: u64 = 100_000;
+const REMOVE_ATTEMPTS: usize = 10;
+const REMOVE_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Resource limits applied to each Cyclone instance (and the lang server processes it spawns) by
+/// putting it into its own cgroup.
+///
+/// The `parent` cgroup is created if needed, but the `memory`, `cpu`, and `pids` controllers must
+/// already be delegated to it, i.e. enabled in the `cgroup.subtree_control` of its own parent.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CgroupLimits {
+    /// The cgroup under which every instance gets its own cgroup.
+    #[serde(default = "default_parent")]
+    pub parent: PathBuf,
+    /// Written to `memory.max`, in bytes.
+    #[serde(default)]
+    pub memory_max_bytes: Option<u64>,
+    /// Written to `cpu.max`, in thousandths of a CPU.
+    #[serde(default)]
+    pub cpu_max_millicores: Option<u64>,
+    /// Written to `pids.max`.
+    #[serde(default)]
+    pub pids_max: Option<u64>,
+}
+
+impl Default for CgroupLimits {
+    fn default() -> Self {
+        Self {
+            parent: default_parent(),
+            memory_max_bytes: None,
+            cpu_max_millicores: None,
+            pids_max: None,
+        }
+    }
+}
+
+fn default_parent() -> PathBuf {
+    PathBuf::from("/sys/fs/cgroup/veritech")
+}
+
+/// A cgroup owned by a single instance, which is removed along with it.
+#[derive(Debug)]
+pub(crate) struct Cgroup {
+    path: PathBuf,
+}
+
+impl Cgroup {
+    /// Creates a cgroup called `name` under the configured parent and applies the limits to it.
+    pub(crate) fn create(limits: &CgroupLimits, name: &str) -> io::Result<Self> {
+        fs::create_dir_all(&limits.parent)?;
+        let controllers: Vec<_> = [
+            limits.memory_max_bytes.map(|_| "+memory"),
+            limits.cpu_max_millicores.map(|_| "+cpu"),
+            limits.pids_max.map(|_| "+pids"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !controllers.is_empty() {
+            fs::write(
+                limits.parent.join("cgroup.subtree_control"),
+                controllers.join(" "),
+            )?;
+        }
+
+        let path = limits.parent.join(name);
+        fs::create_dir(&path)?;
+        // From here on, dropping the cgroup removes its directory again if a limit can't be set
+        let cgroup = Self { path };
+
+        if let Some(bytes) = limits.memory_max_bytes {
+            cgroup.write("memory.max", bytes.to_string())?;
+            // Without this, a process over the limit is swapped out rather than OOM-killed. Not
+            // every host has swap accounting, so this is best effort.
+            if let Err(err) = cgroup.write("memory.swap.max", "0") {
+                debug!(error = ?err, "could not disable swap for cgroup");
+            }
+        }
+        if let Some(millicores) = limits.cpu_max_millicores {
+            cgroup.write("cpu.max", cpu_max(millicores))?;
+        }
+        if let Some(pids) = limits.pids_max {
+            cgroup.write("pids.max", pids.to_string())?;
+        }
+
+        Ok(cgroup)
+    }
+
+    /// Opens the cgroup's `cgroup.procs` file. Writing `0` to it moves the writing process into
+    /// the cgroup, which lets a child join it between `fork` and `exec`, before it can spawn
+    /// processes of its own.
+    pub(crate) fn procs(&self) -> io::Result<File> {
+        OpenOptions::new()
+            .write(true)
+            .open(self.path.join("cgroup.procs"))
+    }
+
+    /// Kills anything still running in the cgroup and removes it.
+    pub(crate) async fn remove(self) -> io::Result<()> {
+        if let Err(err) = self.write("cgroup.kill", "1") {
+            debug!(error = ?err, "could not kill remaining processes in cgroup");
+        }
+
+        // The cgroup can only be removed once the kernel is done reaping its processes
+        let mut attempt = 1;
+        loop {
+            match fs::remove_dir(&self.path) {
+                Ok(()) => return Ok(()),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+                Err(err) if attempt >= REMOVE_ATTEMPTS => return Err(err),
+                Err(_) => {
+                    attempt += 1;
+                    time::sleep(REMOVE_BACKOFF).await;
+                }
+            }
+        }
+    }
+
+    fn write(&self, file: &str, contents: impl AsRef<[u8]>) -> io::Result<()> {
+        fs::write(self.path.join(file), contents)
+    }
+}
+
+/// The contents of `cpu.max` for a limit in thousandths of a CPU: the quota of CPU time per
+/// period, both in microseconds. The kernel rejects quotas under a millisecond.
+fn cpu_max(millicores: u64) -> String {
+    let quota = (millicores * CPU_MAX_PERIOD_USECS / 1000).max(1000);
+    format!("{quota} {CPU_MAX_PERIOD_USECS}")
+}
+
+impl Drop for Cgroup {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_dir(&self.path) {
+            if err.kind() != io::ErrorKind::NotFound {
+                warn!(error = ?err, path = ?self.path, "failed to remove cgroup");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_max_for_whole_cpus() {
+        assert_eq!("100000 100000", cpu_max(1000));
+        assert_eq!("200000 100000", cpu_max(2000));
+    }
+
+    #[test]
+    fn cpu_max_for_fractions_of_a_cpu() {
+        assert_eq!("50000 100000", cpu_max(500));
+        assert_eq!("25000 100000", cpu_max(250));
+    }
+
+    #[test]
+    fn cpu_max_has_a_minimum_quota() {
+        assert_eq!("1000 100000", cpu_max(1));
+        assert_eq!("1000 100000", cpu_max(0));
+    }
+}
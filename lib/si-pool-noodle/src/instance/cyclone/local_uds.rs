@@ -1,7 +1,7 @@
 #[cfg(target_os = "linux")]
 use si_firecracker::{errors::FirecrackerJailError, firecracker::FirecrackerJail};
 use std::{
-    io,
+    io::{self, Write},
     path::{Path, PathBuf},
     result,
     sync::Arc,
@@ -38,6 +38,7 @@ use tokio::{
 };
 use tracing::{debug, trace};
 
+use super::cgroup::{Cgroup, CgroupLimits};
 use crate::instance::{Instance, Spec, SpecBuilder};
 
 /// Error type for [`LocalUdsInstance`].
@@ -47,6 +48,9 @@ pub enum LocalUdsInstanceError {
     /// Spec builder error.
     #[error(transparent)]
     Builder(#[from] LocalUdsInstanceSpecBuilderError),
+    /// Failed to set up or tear down the cgroup of a child process.
+    #[error("failed to manage cgroup for cyclone child process: {0}")]
+    Cgroup(#[source] io::Error),
     /// Error when waiting for child process to shutdown.
     #[error(transparent)]
     ChildShutdown(#[from] ShutdownError),
@@ -263,6 +267,16 @@ pub struct LocalUdsInstanceSpec {
     #[builder(setter(into), default = "Some(1)")]
     limit_requests: Option<u32>,
 
+    /// Has a spawned Cyclone server serve executions from this many warm lang server processes.
+    /// Only pays off when the server handles more than one request, see `limit_requests`.
+    #[builder(setter(into), default)]
+    warm_workers: Option<usize>,
+
+    /// Puts each spawned Cyclone server into its own cgroup with these resource limits. Only
+    /// applies to the `LocalProcess` runtime strategy on Linux.
+    #[builder(setter(into), default)]
+    cgroup_limits: Option<CgroupLimits>,
+
     /// Enables the `ping` execution endpoint for a spawned Cyclone server.
     #[builder(private, setter(name = "_ping"), default = "false")]
     ping: bool,
@@ -522,6 +536,7 @@ struct LocalProcessRuntime {
     cmd: Command,
     child: Option<Child>,
     socket: PathBuf,
+    cgroup: Option<Cgroup>,
 }
 
 impl LocalProcessRuntime {
@@ -541,6 +556,9 @@ impl LocalProcessRuntime {
         if let Some(limit_requests) = spec.limit_requests {
             cmd.arg("--limit-requests").arg(limit_requests.to_string());
         }
+        if let Some(warm_workers) = spec.warm_workers {
+            cmd.arg("--warm-workers").arg(warm_workers.to_string());
+        }
         if let Some(timeout) = spec.watch_timeout {
             cmd.arg("--watch-timeout")
                 .arg(timeout.as_secs().to_string());
@@ -555,10 +573,31 @@ impl LocalProcessRuntime {
             cmd.arg("--enable-action-run");
         }
 
+        let cgroup = match &spec.cgroup_limits {
+            Some(limits) => {
+                let name: String = thread_rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(10)
+                    .map(char::from)
+                    .collect();
+                let cgroup = Cgroup::create(limits, &format!("cyclone-{name}"))
+                    .map_err(LocalUdsInstanceError::Cgroup)?;
+                let procs = cgroup.procs().map_err(LocalUdsInstanceError::Cgroup)?;
+                // SAFETY: the closure only issues a single `write` to an already open file, which
+                // is safe to do between `fork` and `exec`.
+                unsafe {
+                    cmd.pre_exec(move || (&procs).write_all(b"0"));
+                }
+                Some(cgroup)
+            }
+            None => None,
+        };
+
         Ok(Box::new(LocalProcessRuntime {
             cmd,
             child: None,
             socket: socket.to_path_buf(),
+            cgroup,
         }))
     }
 }
@@ -581,13 +620,16 @@ impl LocalInstanceRuntime for LocalProcessRuntime {
         Ok(())
     }
     async fn terminate(&mut self) -> result::Result<(), LocalUdsInstanceError> {
-        match self.child.as_mut() {
-            Some(c) => {
-                process::child_shutdown(c, Some(process::Signal::SIGTERM), None).await?;
-                Ok(())
-            }
-            None => Ok(()),
+        if let Some(c) = self.child.as_mut() {
+            process::child_shutdown(c, Some(process::Signal::SIGTERM), None).await?;
+        }
+        if let Some(cgroup) = self.cgroup.take() {
+            cgroup
+                .remove()
+                .await
+                .map_err(LocalUdsInstanceError::Cgroup)?;
         }
+        Ok(())
     }
 }
 
@@ -614,6 +656,10 @@ impl LocalDockerRuntime {
             cmd.push(String::from("--limit-requests"));
             cmd.push(limit_requests.to_string())
         }
+        if let Some(warm_workers) = spec.warm_workers {
+            cmd.push(String::from("--warm-workers"));
+            cmd.push(warm_workers.to_string());
+        }
         if let Some(timeout) = spec.watch_timeout {
             cmd.push(String::from("--watch-timeout"));
             cmd.push(timeout.as_secs().to_string());
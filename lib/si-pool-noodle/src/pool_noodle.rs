@@ -39,10 +39,12 @@
 //! =:----------::::::::::::::::::::::::::*#***########%%%%%*::::::------
 
 use crate::lifeguard::LifeGuard;
+use crate::scaling::{ScaleDecision, Scaler, ScalingStats};
 use crate::task::{PoolNoodleTask, PoolNoodleTaskType};
 use crossbeam_queue::ArrayQueue;
 use std::fmt::Display;
 use std::result;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use telemetry_utils::metric;
 use tokio::sync::Mutex;
@@ -51,7 +53,7 @@ use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 
 use tokio::sync::mpsc::{self, Receiver, Sender};
-use tokio::time::{timeout, Duration};
+use tokio::time::{self, timeout, Duration, Instant};
 use tracing::{debug, info, warn};
 
 use crate::errors::PoolNoodleError;
@@ -70,6 +72,9 @@ pub struct PoolNoodleConfig<S> {
     pub pool_size: u32,
     /// Number of attempts to get from the pool before giving up with 10 ms between attempts
     pub retry_limit: u32,
+    /// Resizes the pool between a minimum and `pool_size` as demand changes. Without it, the pool
+    /// always keeps `pool_size` instances around.
+    pub scaling: Option<PoolNoodleScalingConfig>,
     /// Shuts down the pool management tasks
     pub shutdown_token: CancellationToken,
     /// The spec for the type of instance to manage
@@ -86,12 +91,43 @@ where
             max_concurrency: 1000,
             pool_size: 100,
             retry_limit: 120, // * 100ms between tries, we will try for 2 minutes before giving up
+            scaling: None,
             shutdown_token: CancellationToken::new(),
             spec: S::default(),
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Configuration for adaptively sizing a pool. The pool starts out with `pool_size` instances,
+/// grows back towards it when requests wait for an instance, and shrinks towards `min_size` while
+/// instances sit unused.
+pub struct PoolNoodleScalingConfig {
+    /// Fewest instances to keep around, no matter how idle the pool is
+    pub min_size: u32,
+    /// How often to decide whether to resize the pool
+    pub interval: Duration,
+    /// Grow the pool when requests waited at least this long for an instance, on average, over an
+    /// interval
+    pub scale_up_wait: Duration,
+    /// Count an interval as idle when at least this percentage of the pool was ready throughout
+    pub scale_down_idle_percent: u32,
+    /// Shrink the pool only after this many idle intervals in a row
+    pub scale_down_after: u32,
+}
+
+impl Default for PoolNoodleScalingConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 1,
+            interval: Duration::from_secs(10),
+            scale_up_wait: Duration::from_millis(100),
+            scale_down_idle_percent: 50,
+            scale_down_after: 6,
+        }
+    }
+}
+
 /// Pool Noodle is a tool for ensuring that we maintain a bare minimum number of Firecracker Jails
 /// for function execution. We wrap it in an Arc Mutex so we can update the queues it manages
 /// across threads.
@@ -164,6 +200,12 @@ where
             }
         });
 
+        if let Some(scaling) = self.inner().scaling.clone() {
+            let inner = self.inner();
+            metric!(counter.pool_noodle.size = i64::from(inner.pool_size));
+            tokio::spawn(async move { inner.scale(scaling).await });
+        }
+
         Ok(())
     }
 
@@ -177,12 +219,14 @@ where
     pub async fn get(&self) -> Result<LifeGuard<I, E, S>, E> {
         metric!(counter.pool_noodle.get_requests = 1);
         let inner = self.inner();
+        let started = Instant::now();
 
         let max_retries = self.inner().retry_limit; // Set the maximum number of retries
         let mut retries = 0;
         loop {
             if retries >= max_retries {
                 metric!(counter.pool_noodle.get_requests = -1);
+                inner.stats.record_wait(started.elapsed());
                 return Err(PoolNoodleError::ExecutionPoolStarved);
             }
             if let Some(mut instance) = inner.ready_queue.pop() {
@@ -192,6 +236,8 @@ where
                     Ok(_) => {
                         metric!(counter.pool_noodle.get_requests = -1);
                         metric!(counter.pool_noodle.active = 1);
+                        inner.stats.record_wait(started.elapsed());
+                        inner.stats.record_ready(inner.ready_len());
                         return Ok(LifeGuard::new(
                             Some(instance),
                             inner.queue_tx.clone(),
@@ -251,10 +297,19 @@ where
     pool_size: u32,
     ready_queue: ArrayQueue<I>,
     retry_limit: u32,
+    scaling: Option<PoolNoodleScalingConfig>,
     shutdown_token: CancellationToken,
     spec: S,
     queue_rx: Mutex<Receiver<PoolNoodleTaskType<I, S>>>,
     queue_tx: Sender<PoolNoodleTaskType<I, S>>,
+    // The number of instances the pool currently aims to keep around, which is `pool_size` unless
+    // the pool scales
+    size: AtomicU32,
+    // Instances that should leave the pool the next time they are cleaned
+    retirements: AtomicU32,
+    // Ids of instances that have left the pool, to reuse when it grows again
+    free_ids: std::sync::Mutex<Vec<u32>>,
+    stats: ScalingStats,
 }
 
 impl<I, E, S> PoolNoodleInner<I, S>
@@ -275,13 +330,112 @@ where
             pool_size: config.pool_size,
             ready_queue: ArrayQueue::new(config.pool_size as usize),
             retry_limit: config.retry_limit,
+            scaling: config.scaling,
             shutdown_token: config.shutdown_token,
             spec: config.spec,
             queue_rx: queue_rx.into(),
             queue_tx,
+            size: AtomicU32::new(config.pool_size),
+            retirements: AtomicU32::new(0),
+            free_ids: Default::default(),
+            stats: ScalingStats::new(0),
         }
     }
 
+    fn ready_len(&self) -> u32 {
+        u32::try_from(self.ready_queue.len()).unwrap_or(u32::MAX)
+    }
+
+    async fn scale(self: Arc<Self>, config: PoolNoodleScalingConfig) {
+        let mut interval = time::interval(config.interval);
+        let mut scaler = Scaler::new(config, self.pool_size);
+        // The first tick completes immediately, and there is nothing to go on yet
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_token.cancelled() => {
+                    debug!("scaling loop received cancellation");
+                    break;
+                }
+                _ = interval.tick() => {
+                    let sample = self.stats.take(self.ready_len());
+                    let size = self.size.load(Ordering::SeqCst);
+                    match scaler.evaluate(size, sample) {
+                        Some(ScaleDecision::Up(by)) => {
+                            info!(size, by, ?sample, "PoolNoodle: scaling up");
+                            self.scale_up(by).await;
+                        }
+                        Some(ScaleDecision::Down(by)) => {
+                            info!(size, by, ?sample, "PoolNoodle: scaling down");
+                            self.scale_down(by).await;
+                        }
+                        None => {}
+                    }
+                }
+            }
+        }
+    }
+
+    async fn scale_up(&self, by: u32) {
+        self.size.fetch_add(by, Ordering::SeqCst);
+        metric!(monotonic_counter.pool_noodle.scale_up = 1);
+        metric!(counter.pool_noodle.size = i64::from(by));
+
+        // Instances that were due to leave the pool can simply stay
+        let kept = self
+            .retirements
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |retirements| {
+                Some(retirements.saturating_sub(by))
+            })
+            .map_or(0, |retirements| retirements.min(by));
+
+        for _ in kept..by {
+            let id = self
+                .free_ids
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .pop();
+            match id {
+                Some(id) => self.push_clean_task_to_work_queue(id).await,
+                None => break,
+            }
+        }
+    }
+
+    async fn scale_down(&self, by: u32) {
+        self.size.fetch_sub(by, Ordering::SeqCst);
+        self.retirements.fetch_add(by, Ordering::SeqCst);
+        metric!(monotonic_counter.pool_noodle.scale_down = 1);
+        metric!(counter.pool_noodle.size = -i64::from(by));
+
+        // Ready instances leave right away, the rest once they are done being used
+        for _ in 0..by {
+            let Some(instance) = self.ready_queue.pop() else {
+                break;
+            };
+            metric!(counter.pool_noodle.ready = -1);
+            let id = instance.id();
+            let task = PoolNoodleTaskType::Drop(PoolNoodleTask::new(
+                Some(instance),
+                id,
+                self.spec.clone(),
+            ));
+            if self.queue_tx.send(task).await.is_err() {
+                warn!("failed to push instance to drop: {}", id);
+            }
+            metric!(counter.pool_noodle.task.drop = 1);
+        }
+    }
+
+    fn take_retirement(&self) -> bool {
+        self.retirements
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |retirements| {
+                retirements.checked_sub(1)
+            })
+            .is_ok()
+    }
+
     async fn handle_task(self: Arc<Self>, task_type: PoolNoodleTaskType<I, S>) {
         match task_type {
             PoolNoodleTaskType::Clean(task) => self.handle_clean(task).await,
@@ -300,7 +454,15 @@ where
         loop {
             match task.clean().await {
                 Ok(_) => {
-                    self.push_prepare_task_to_work_queue(id).await;
+                    if self.take_retirement() {
+                        debug!("PoolNoodle: retiring instance: {}", id);
+                        self.free_ids
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner)
+                            .push(id);
+                    } else {
+                        self.push_prepare_task_to_work_queue(id).await;
+                    }
                     break;
                 }
                 Err(e) => {
@@ -460,6 +622,7 @@ mod tests {
             max_concurrency: 10,
             pool_size: 3,
             retry_limit: 3,
+            scaling: None,
             shutdown_token: shutdown_token.clone(),
             spec,
         };
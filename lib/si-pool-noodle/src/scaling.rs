@@ -0,0 +1,199 @@
+use std::{
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    time::Duration,
+};
+
+use crate::pool_noodle::PoolNoodleScalingConfig;
+
+/// Accumulates what requests to the pool saw between two scaling decisions.
+#[derive(Debug, Default)]
+pub(crate) struct ScalingStats {
+    requests: AtomicU64,
+    wait_micros: AtomicU64,
+    min_ready: AtomicU32,
+}
+
+impl ScalingStats {
+    pub(crate) fn new(ready: u32) -> Self {
+        Self {
+            min_ready: AtomicU32::new(ready),
+            ..Default::default()
+        }
+    }
+
+    /// Records how long a request waited for an instance, whether or not it got one.
+    pub(crate) fn record_wait(&self, wait: Duration) {
+        let micros = u64::try_from(wait.as_micros()).unwrap_or(u64::MAX);
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.wait_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    /// Records how many instances were left ready after a request took one.
+    pub(crate) fn record_ready(&self, ready: u32) {
+        self.min_ready.fetch_min(ready, Ordering::Relaxed);
+    }
+
+    /// Takes a sample of the interval that just ended and starts a new one.
+    pub(crate) fn take(&self, ready: u32) -> ScalingSample {
+        let requests = self.requests.swap(0, Ordering::Relaxed);
+        let wait_micros = self.wait_micros.swap(0, Ordering::Relaxed);
+        let min_ready = self.min_ready.swap(ready, Ordering::Relaxed).min(ready);
+
+        ScalingSample {
+            requests,
+            average_wait: Duration::from_micros(wait_micros.checked_div(requests).unwrap_or(0)),
+            min_ready,
+        }
+    }
+}
+
+/// What the pool looked like over one scaling interval.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ScalingSample {
+    /// How many requests were made for an instance.
+    pub(crate) requests: u64,
+    /// How long those requests waited for an instance, on average.
+    pub(crate) average_wait: Duration,
+    /// The fewest instances that sat ready at any point.
+    pub(crate) min_ready: u32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ScaleDecision {
+    Down(u32),
+    Up(u32),
+}
+
+/// Decides when to resize the pool. Growing happens as soon as requests wait too long, while
+/// shrinking needs several idle intervals in a row, so that a pool at the edge of either threshold
+/// doesn't flap between sizes.
+#[derive(Debug)]
+pub(crate) struct Scaler {
+    config: PoolNoodleScalingConfig,
+    min_size: u32,
+    max_size: u32,
+    idle_intervals: u32,
+}
+
+impl Scaler {
+    pub(crate) fn new(config: PoolNoodleScalingConfig, max_size: u32) -> Self {
+        Self {
+            min_size: config.min_size.clamp(1, max_size.max(1)),
+            max_size,
+            config,
+            idle_intervals: 0,
+        }
+    }
+
+    pub(crate) fn evaluate(&mut self, size: u32, sample: ScalingSample) -> Option<ScaleDecision> {
+        // Grow or shrink by a quarter of the pool at a time
+        let step = (size / 4).max(1);
+
+        if sample.requests > 0 && sample.average_wait >= self.config.scale_up_wait {
+            self.idle_intervals = 0;
+            let by = step.min(self.max_size.saturating_sub(size));
+            return (by > 0).then_some(ScaleDecision::Up(by));
+        }
+
+        let idle_floor = (size * self.config.scale_down_idle_percent / 100).max(1);
+        if sample.min_ready < idle_floor {
+            self.idle_intervals = 0;
+            return None;
+        }
+
+        self.idle_intervals += 1;
+        if self.idle_intervals < self.config.scale_down_after {
+            return None;
+        }
+        self.idle_intervals = 0;
+        let by = step.min(size.saturating_sub(self.min_size));
+        (by > 0).then_some(ScaleDecision::Down(by))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaler() -> Scaler {
+        Scaler::new(
+            PoolNoodleScalingConfig {
+                min_size: 4,
+                interval: Duration::from_secs(10),
+                scale_up_wait: Duration::from_millis(100),
+                scale_down_idle_percent: 50,
+                scale_down_after: 3,
+            },
+            20,
+        )
+    }
+
+    fn waiting() -> ScalingSample {
+        ScalingSample {
+            requests: 10,
+            average_wait: Duration::from_millis(250),
+            min_ready: 0,
+        }
+    }
+
+    fn idle(min_ready: u32) -> ScalingSample {
+        ScalingSample {
+            requests: 0,
+            average_wait: Duration::ZERO,
+            min_ready,
+        }
+    }
+
+    #[test]
+    fn scales_up_when_requests_wait_up_to_the_max() {
+        let mut scaler = scaler();
+
+        assert_eq!(Some(ScaleDecision::Up(2)), scaler.evaluate(8, waiting()));
+        assert_eq!(Some(ScaleDecision::Up(2)), scaler.evaluate(18, waiting()));
+        assert_eq!(None, scaler.evaluate(20, waiting()));
+    }
+
+    #[test]
+    fn scales_down_only_after_consecutive_idle_intervals() {
+        let mut scaler = scaler();
+
+        assert_eq!(None, scaler.evaluate(16, idle(10)));
+        assert_eq!(None, scaler.evaluate(16, idle(10)));
+        // A busy interval resets the count
+        assert_eq!(None, scaler.evaluate(16, idle(2)));
+        assert_eq!(None, scaler.evaluate(16, idle(10)));
+        assert_eq!(None, scaler.evaluate(16, idle(10)));
+        assert_eq!(Some(ScaleDecision::Down(4)), scaler.evaluate(16, idle(10)));
+    }
+
+    #[test]
+    fn never_scales_down_below_the_min() {
+        let mut scaler = scaler();
+
+        for _ in 0..2 {
+            assert_eq!(None, scaler.evaluate(5, idle(5)));
+        }
+        assert_eq!(Some(ScaleDecision::Down(1)), scaler.evaluate(5, idle(5)));
+        for _ in 0..3 {
+            assert_eq!(None, scaler.evaluate(4, idle(4)));
+        }
+    }
+
+    #[test]
+    fn samples_reset_between_intervals() {
+        let stats = ScalingStats::new(8);
+        stats.record_wait(Duration::from_millis(100));
+        stats.record_wait(Duration::from_millis(300));
+        stats.record_ready(3);
+
+        assert_eq!(
+            ScalingSample {
+                requests: 2,
+                average_wait: Duration::from_millis(200),
+                min_ready: 3,
+            },
+            stats.take(6),
+        );
+        assert_eq!(idle(6), stats.take(7));
+    }
+}
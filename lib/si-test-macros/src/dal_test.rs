@@ -20,17 +20,35 @@ use crate::{
     path_as_string, Args,
 };
 
+/// The test attribute argument which runs functions with `dal_test::InProcessVeritech` rather
+/// than starting a Veritech server.
+const IN_PROCESS_VERITECH: &str = "in_process_veritech";
+
 pub(crate) fn expand(item: ItemFn, args: Args) -> TokenStream {
-    let fn_setup = fn_setup(item.sig.inputs.iter());
+    let fn_setup = fn_setup(item.sig.inputs.iter(), &args);
 
     expand_test(item, args, fn_setup)
 }
 
-fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> DalTestFnSetup {
+fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>, args: &Args) -> DalTestFnSetup {
     let mut expander = DalTestFnSetupExpander::new();
 
+    let mut in_process_veritech = false;
+    for var in &args.vars {
+        if var == IN_PROCESS_VERITECH {
+            in_process_veritech = true;
+        } else {
+            panic!("unexpected test attribute argument: {var}");
+        }
+    }
+
     expander.setup_start_forklift_server();
-    expander.setup_start_veritech_server();
+    if in_process_veritech {
+        // Must come before anything that creates a services context, so they all use it
+        expander.setup_in_process_veritech();
+    } else {
+        expander.setup_start_veritech_server();
+    }
     expander.setup_start_pinga_server();
     expander.setup_start_rebaser_server();
 
@@ -99,6 +117,11 @@ fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> DalTestFnSetup {
                                 let var = var.as_ref();
                                 expander.push_arg(parse_quote! {#var});
                             }
+                            "InProcessVeritech" => {
+                                let var = in_process_veritech_arg(&expander);
+                                let var = var.as_ref();
+                                expander.push_arg(parse_quote! {#var.clone()});
+                            }
                             _ => panic!("unexpected argument type: {type_path:?}"),
                         };
                     }
@@ -155,6 +178,11 @@ fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> DalTestFnSetup {
                                     let var = var.as_ref();
                                     expander.push_arg(parse_quote! {#var});
                                 }
+                                "InProcessVeritech" => {
+                                    let var = in_process_veritech_arg(&expander);
+                                    let var = var.as_ref();
+                                    expander.push_arg(parse_quote! {&#var});
+                                }
                                 _ => panic!("unexpected argument reference type: {type_ref:?}"),
                             }
                         }
@@ -174,6 +202,15 @@ fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> DalTestFnSetup {
     expander.finish()
 }
 
+fn in_process_veritech_arg(expander: &DalTestFnSetupExpander) -> Rc<Ident> {
+    match expander.in_process_veritech() {
+        Some(ident) => ident.clone(),
+        None => panic!(
+            "an `InProcessVeritech` argument requires the `{IN_PROCESS_VERITECH}` test attribute argument"
+        ),
+    }
+}
+
 struct DalTestFnSetup {
     code: TokenStream,
     fn_args: Punctuated<Expr, Comma>,
@@ -200,6 +237,7 @@ struct DalTestFnSetupExpander {
     start_forklift_server: Option<()>,
     veritech_server: Option<Rc<Ident>>,
     start_veritech_server: Option<()>,
+    in_process_veritech: Option<Rc<Ident>>,
     services_context: Option<Rc<Ident>>,
     dal_context_builder: Option<Rc<Ident>>,
     workspace_signup: Option<(Rc<Ident>, Rc<Ident>)>,
@@ -227,6 +265,7 @@ impl DalTestFnSetupExpander {
             start_forklift_server: None,
             veritech_server: None,
             start_veritech_server: None,
+            in_process_veritech: None,
             services_context: None,
             dal_context_builder: None,
             workspace_signup: None,
@@ -349,6 +388,14 @@ impl FnSetupExpander for DalTestFnSetupExpander {
         self.start_veritech_server = value;
     }
 
+    fn in_process_veritech(&self) -> Option<&Rc<Ident>> {
+        self.in_process_veritech.as_ref()
+    }
+
+    fn set_in_process_veritech(&mut self, value: Option<Rc<Ident>>) {
+        self.in_process_veritech = value;
+    }
+
     fn services_context(&self) -> Option<&Rc<Ident>> {
         self.services_context.as_ref()
     }
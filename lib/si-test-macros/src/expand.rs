@@ -198,6 +198,9 @@ pub(crate) trait FnSetupExpander {
     fn start_veritech_server(&self) -> Option<()>;
     fn set_start_veritech_server(&mut self, value: Option<()>);
 
+    fn in_process_veritech(&self) -> Option<&Rc<Ident>>;
+    fn set_in_process_veritech(&mut self, value: Option<Rc<Ident>>);
+
     fn services_context(&self) -> Option<&Rc<Ident>>;
     fn set_services_context(&mut self, value: Option<Rc<Ident>>);
 
@@ -276,24 +279,15 @@ pub(crate) trait FnSetupExpander {
             return ident.clone();
         }
 
-        let test_context = self.setup_test_context();
-        let test_context = test_context.as_ref();
-
         let cancellation_token = self.setup_cancellation_token();
         let cancellation_token = cancellation_token.as_ref();
 
-        let task_tracker = self.setup_task_tracker();
-        let task_tracker = task_tracker.as_ref();
+        let create_services_context = self.expand_create_services_context();
 
         let var = Ident::new("pinga_server", Span::call_site());
         self.code_extend(quote! {
             let #var = {
-                let s_ctx = #test_context
-                    .create_services_context(
-                        #cancellation_token.clone(),
-                        #task_tracker.clone(),
-                    )
-                    .await;
+                let s_ctx = #create_services_context;
                 ::dal_test::pinga_server(
                     s_ctx,
                     #cancellation_token.clone(),
@@ -324,24 +318,15 @@ pub(crate) trait FnSetupExpander {
             return ident.clone();
         }
 
-        let test_context = self.setup_test_context();
-        let test_context = test_context.as_ref();
-
         let cancellation_token = self.setup_cancellation_token();
         let cancellation_token = cancellation_token.as_ref();
 
-        let task_tracker = self.setup_task_tracker();
-        let task_tracker = task_tracker.as_ref();
+        let create_services_context = self.expand_create_services_context();
         let var = Ident::new("rebaser_server", Span::call_site());
 
         self.code_extend(quote! {
             let #var = {
-                let s_ctx = #test_context
-                    .create_services_context(
-                        #cancellation_token.clone(),
-                        #task_tracker.clone(),
-                    )
-                    .await;
+                let s_ctx = #create_services_context;
                 ::dal_test::rebaser_server(
                     s_ctx,
                     #cancellation_token.clone(),
@@ -443,11 +428,23 @@ pub(crate) trait FnSetupExpander {
         self.set_start_forklift_server(Some(()));
     }
 
-    fn setup_services_context(&mut self) -> Rc<Ident> {
-        if let Some(ident) = self.services_context() {
+    fn setup_in_process_veritech(&mut self) -> Rc<Ident> {
+        if let Some(ident) = self.in_process_veritech() {
             return ident.clone();
         }
 
+        let var = Ident::new("in_process_veritech", Span::call_site());
+        self.code_extend(quote! {
+            let #var = ::dal_test::InProcessVeritech::new();
+        });
+        self.set_in_process_veritech(Some(Rc::new(var)));
+
+        self.in_process_veritech().unwrap().clone()
+    }
+
+    /// Expands to an expression creating a services context, which executes functions with the
+    /// in-process veritech stand-in if the test has set one up.
+    fn expand_create_services_context(&mut self) -> TokenStream {
         let test_context = self.setup_test_context();
         let test_context = test_context.as_ref();
 
@@ -457,11 +454,37 @@ pub(crate) trait FnSetupExpander {
         let task_tracker = self.setup_task_tracker();
         let task_tracker = task_tracker.as_ref();
 
+        match self.in_process_veritech() {
+            Some(in_process_veritech) => {
+                let in_process_veritech = in_process_veritech.as_ref();
+                quote! {
+                    #test_context
+                        .create_services_context_with_veritech(
+                            #cancellation_token.clone(),
+                            #task_tracker.clone(),
+                            #in_process_veritech.clone(),
+                        )
+                        .await
+                }
+            }
+            None => quote! {
+                #test_context
+                    .create_services_context(#cancellation_token.clone(), #task_tracker.clone())
+                    .await
+            },
+        }
+    }
+
+    fn setup_services_context(&mut self) -> Rc<Ident> {
+        if let Some(ident) = self.services_context() {
+            return ident.clone();
+        }
+
+        let create_services_context = self.expand_create_services_context();
+
         let var = Ident::new("services_context", Span::call_site());
         self.code_extend(quote! {
-            let #var = #test_context
-                .create_services_context(#cancellation_token.clone(), #task_tracker.clone())
-                .await;
+            let #var = #create_services_context;
         });
         self.set_services_context(Some(Rc::new(var)));
 
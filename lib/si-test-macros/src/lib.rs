@@ -20,8 +20,6 @@ const SPAN_EVENTS_ENV_VAR: &str = "SI_TEST_LOG_SPAN_EVENTS";
 const RT_DEFAULT_WORKER_THREADS: usize = 2;
 const RT_DEFAULT_THREAD_STACK_SIZE: usize = 2 * 1024 * 1024 * 3;
 
-#[allow(dead_code)] // We aren't current using args on the macro, but when we do we can drop this
-                    // line
 struct Args {
     pub(crate) vars: HashSet<Ident>,
 }
@@ -165,6 +163,23 @@ fn path_as_string(path: &Path) -> String {
 /// * `nw: &WorkspaceSignup`: a reference to the full "new-workspace" data structure,
 ///    created for this test
 ///
+/// # Running Functions In-Process
+///
+/// By default, functions are run by a Veritech server started for the test. With the
+/// `in_process_veritech` argument, they are run by a `dal_test::InProcessVeritech` stand-in
+/// instead, which can be taken as an `InProcessVeritech` or `&InProcessVeritech` test argument
+/// to set up canned responses and to assert on the requests it received:
+///
+/// ```ignore
+/// use dal::DalContext;
+/// use dal_test::{test, InProcessVeritech};
+///
+/// #[test(in_process_veritech)]
+/// async fn runs_in_process(ctx: &DalContext, veritech: &InProcessVeritech) {
+///     // ...
+/// }
+/// ```
+///
 /// # Customized Tokio Runtime
 ///
 /// The attribute uses a similar strategy to the stock `#[tokio::test]` attribute, except that this
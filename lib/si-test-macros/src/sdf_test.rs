@@ -231,6 +231,7 @@ struct SdfTestFnSetupExpander {
     start_forklift_server: Option<()>,
     veritech_server: Option<Rc<Ident>>,
     start_veritech_server: Option<()>,
+    in_process_veritech: Option<Rc<Ident>>,
     services_context: Option<Rc<Ident>>,
     dal_context_builder: Option<Rc<Ident>>,
     workspace_signup: Option<(Rc<Ident>, Rc<Ident>)>,
@@ -266,6 +267,7 @@ impl SdfTestFnSetupExpander {
             start_forklift_server: None,
             veritech_server: None,
             start_veritech_server: None,
+            in_process_veritech: None,
             services_context: None,
             dal_context_builder: None,
             workspace_signup: None,
@@ -576,6 +578,14 @@ impl FnSetupExpander for SdfTestFnSetupExpander {
         self.start_veritech_server = value;
     }
 
+    fn in_process_veritech(&self) -> Option<&Rc<Ident>> {
+        self.in_process_veritech.as_ref()
+    }
+
+    fn set_in_process_veritech(&mut self, value: Option<Rc<Ident>>) {
+        self.in_process_veritech = value;
+    }
+
     fn services_context(&self) -> Option<&Rc<Ident>> {
         self.services_context.as_ref()
     }
@@ -1,3 +1,6 @@
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
 use cyclone_core::CycloneRequestable;
 use futures::{StreamExt, TryStreamExt};
 use nats_subscriber::{Subscriber, SubscriberError};
@@ -15,8 +18,9 @@ use veritech_core::{
 
 pub use cyclone_core::{
     ActionRunRequest, ActionRunResultSuccess, BeforeFunction, ComponentKind, ComponentView,
-    ComponentViewWithGeometry, FunctionResult, FunctionResultFailure,
-    FunctionResultFailureErrorKind, KillExecutionRequest, ManagementFuncStatus, ManagementRequest,
+    ComponentViewWithGeometry, EgressPolicy, EgressRule, ExecutionPriority, FunctionResult,
+    FunctionResultFailure, FunctionResultFailureError, FunctionResultFailureErrorKind,
+    FunctionRuntime, KillExecutionRequest, ManagementFuncStatus, ManagementRequest,
     ManagementResultSuccess, OutputStream, ResolverFunctionComponent, ResolverFunctionRequest,
     ResolverFunctionResponseType, ResolverFunctionResultSuccess, ResourceStatus,
     SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess, SensitiveContainer,
@@ -45,6 +49,62 @@ pub enum ClientError {
 
 pub type ClientResult<T> = Result<T, ClientError>;
 
+/// A shareable, type-erased [`FunctionExecutor`].
+pub type DynFunctionExecutor = Arc<dyn FunctionExecutor>;
+
+/// Executes functions on behalf of callers, which is what [`Client`] does by sending requests to
+/// veritech over NATS.
+///
+/// Callers that hold onto a [`DynFunctionExecutor`] rather than a [`Client`] can be handed another
+/// implementation, such as an in-process stand-in for tests.
+#[async_trait]
+pub trait FunctionExecutor: fmt::Debug + Send + Sync + 'static {
+    async fn execute_action_run(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ActionRunRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ActionRunResultSuccess>>;
+
+    async fn execute_resolver_function(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ResolverFunctionRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ResolverFunctionResultSuccess>>;
+
+    async fn execute_schema_variant_definition(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &SchemaVariantDefinitionRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<SchemaVariantDefinitionResultSuccess>>;
+
+    async fn execute_validation(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ValidationRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ValidationResultSuccess>>;
+
+    async fn execute_management(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ManagementRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ManagementResultSuccess>>;
+
+    async fn kill_execution(
+        &self,
+        request: &KillExecutionRequest,
+    ) -> ClientResult<FunctionResult<()>>;
+}
+
 /// This _private_ enum helps dictate what NATS technology should be used in communicating with veritech.
 enum RequestMode {
     /// Publish messages using core NATS to communicate with veritech.
@@ -346,6 +406,74 @@ impl Client {
     }
 }
 
+#[async_trait]
+impl FunctionExecutor for Client {
+    async fn execute_action_run(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ActionRunRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ActionRunResultSuccess>> {
+        Client::execute_action_run(self, output_tx, request, workspace_id, change_set_id).await
+    }
+
+    async fn execute_resolver_function(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ResolverFunctionRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ResolverFunctionResultSuccess>> {
+        Client::execute_resolver_function(self, output_tx, request, workspace_id, change_set_id)
+            .await
+    }
+
+    async fn execute_schema_variant_definition(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &SchemaVariantDefinitionRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<SchemaVariantDefinitionResultSuccess>> {
+        Client::execute_schema_variant_definition(
+            self,
+            output_tx,
+            request,
+            workspace_id,
+            change_set_id,
+        )
+        .await
+    }
+
+    async fn execute_validation(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ValidationRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ValidationResultSuccess>> {
+        Client::execute_validation(self, output_tx, request, workspace_id, change_set_id).await
+    }
+
+    async fn execute_management(
+        &self,
+        output_tx: mpsc::Sender<OutputStream>,
+        request: &ManagementRequest,
+        workspace_id: &str,
+        change_set_id: &str,
+    ) -> ClientResult<FunctionResult<ManagementResultSuccess>> {
+        Client::execute_management(self, output_tx, request, workspace_id, change_set_id).await
+    }
+
+    async fn kill_execution(
+        &self,
+        request: &KillExecutionRequest,
+    ) -> ClientResult<FunctionResult<()>> {
+        Client::kill_execution(self, request).await
+    }
+}
+
 async fn forward_output_task(
     mut output_subscriber: Subscriber<OutputStream>,
     output_tx: mpsc::Sender<OutputStream>,
@@ -125,6 +125,9 @@ async fn executes_simple_management_function() {
              }",
         ),
         before: vec![],
+        timeout_secs: None,
+        priority: Default::default(),
+        egress_policy: None,
     };
 
     let result = client
@@ -164,6 +167,10 @@ async fn executes_simple_action_run() {
         args: serde_json::json!({ "foo": "bar", "baz": "foo" }),
         code_base64: base64_encode("function numberOfInputs(input) { return { status: 'ok', payload: Object.keys(input)?.length ?? 0 } }"),
         before: vec![],
+        timeout_secs: None,
+        priority: Default::default(),
+        runtime: Default::default(),
+        egress_policy: None,
     };
 
     let result = client
@@ -215,6 +222,10 @@ async fn executes_simple_resolver_function() {
             "function numberOfInputs(input) { return Object.keys(input)?.length ?? 0; }",
         ),
         before: vec![],
+        timeout_secs: None,
+        priority: Default::default(),
+        runtime: Default::default(),
+        egress_policy: None,
     };
 
     let result = client
@@ -285,6 +296,10 @@ async fn type_checks_resolve_function() {
             response_type,
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            runtime: Default::default(),
+            egress_policy: None,
         };
 
         let result = client
@@ -350,6 +365,10 @@ async fn type_checks_resolve_function() {
             response_type: response_type.clone(),
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
             before: vec![],
+            timeout_secs: None,
+            priority: Default::default(),
+            runtime: Default::default(),
+            egress_policy: None,
         };
 
         let result = client
@@ -395,6 +414,9 @@ async fn executes_simple_validation() {
         validation_format: r#"{"type":"number","flags":{"presence":"required"},"rules":[{"name":"integer"},{"name":"min","args":{"limit":33}},{"name":"max","args":{"limit":33}}]}"#.to_string(),
         code_base64: "".to_string(),
         before: vec![],
+        timeout_secs: None,
+        priority: Default::default(),
+        egress_policy: None,
     };
 
     let result = client
@@ -440,6 +462,9 @@ async fn executes_simple_schema_variant_definition() {
                     };
                 }",
         ),
+        timeout_secs: None,
+        priority: Default::default(),
+        egress_policy: None,
     };
 
     let result = client
@@ -8,9 +8,10 @@ use si_pool_noodle::{
 };
 use tokio::sync::oneshot;
 use tokio::sync::Mutex;
+use tokio_util::task::TaskTracker;
 use veritech_core::ExecutionId;
 
-use crate::server::ServerMetadata;
+use crate::{fair_queue::FairQueue, server::ServerMetadata};
 
 /// Application state.
 #[derive(Clone, Debug)]
@@ -20,11 +21,15 @@ pub struct AppState {
     // NOTE(nick,fletcher,scott): this implements clone and the inner bits are wrapped in an Arc.
     // If that changes, then I hope you read this comment before that happens.
     pub cyclone_pool: PoolNoodle<LocalUdsInstance, LocalUdsInstanceSpec>,
+    pub fair_queue: FairQueue,
     pub decryption_key: Arc<VeritechDecryptionKey>,
     // TODO(nick,fletcher,scott): make this mutable at runtime.
     pub cyclone_client_execution_timeout: Duration,
     pub nats: NatsClient,
     pub kill_senders: Arc<Mutex<HashMap<ExecutionId, oneshot::Sender<()>>>>,
+    /// Tracks requests that have been taken off the work queue, whether they are waiting their turn
+    /// or running, so that shutdown can wait for them.
+    pub tasks: TaskTracker,
 }
 
 impl AppState {
@@ -32,18 +37,22 @@ impl AppState {
     pub fn new(
         metadata: Arc<ServerMetadata>,
         cyclone_pool: PoolNoodle<LocalUdsInstance, LocalUdsInstanceSpec>,
+        fair_queue: FairQueue,
         decryption_key: Arc<VeritechDecryptionKey>,
         cyclone_client_execution_timeout: Duration,
         nats: NatsClient,
         kill_senders: Arc<Mutex<HashMap<ExecutionId, oneshot::Sender<()>>>>,
+        tasks: TaskTracker,
     ) -> Self {
         Self {
             metadata,
             cyclone_pool,
+            fair_queue,
             decryption_key,
             cyclone_client_execution_timeout,
             nats,
             kill_senders,
+            tasks,
         }
     }
 
@@ -1,6 +1,7 @@
 use si_crypto::VeritechCryptoConfig;
 use si_std::CanonicalFileError;
 use std::{
+    collections::HashMap,
     env,
     net::{SocketAddr, ToSocketAddrs},
     path::{Path, PathBuf},
@@ -14,9 +15,10 @@ use serde::{Deserialize, Serialize};
 use si_data_nats::NatsConfig;
 use si_pool_noodle::{
     instance::cyclone::{
-        LocalHttpInstance, LocalHttpInstanceSpec, LocalHttpSocketStrategy, LocalUdsInstance,
-        LocalUdsInstanceSpec, LocalUdsRuntimeStrategy, LocalUdsSocketStrategy,
+        CgroupLimits, LocalHttpInstance, LocalHttpInstanceSpec, LocalHttpSocketStrategy,
+        LocalUdsInstance, LocalUdsInstanceSpec, LocalUdsRuntimeStrategy, LocalUdsSocketStrategy,
     },
+    pool_noodle::PoolNoodleScalingConfig,
     Instance,
 };
 use telemetry::prelude::*;
@@ -54,6 +56,8 @@ pub enum ConfigError {
     Settings(#[from] si_settings::SettingsError),
     #[error("failed to resolve socket addrs")]
     SocketAddrResolve(#[source] std::io::Error),
+    #[error("warm workers need cyclone to serve more than one request, but limit_requets is 1")]
+    WarmWorkersWithSingleRequest,
 }
 
 impl ConfigError {
@@ -83,6 +87,12 @@ pub struct Config {
     #[builder(default = "default_veritech_requests_concurrency_limit()")]
     veritech_requests_concurrency_limit: usize,
 
+    #[builder(default)]
+    fair_queue: FairQueueConfig,
+
+    #[builder(default)]
+    pool_scaling: Option<PoolScalingConfig>,
+
     #[builder(default = "random_instance_id()")]
     instance_id: String,
 
@@ -142,6 +152,16 @@ impl Config {
         self.veritech_requests_concurrency_limit
     }
 
+    /// Gets a reference to the config's per-workspace fair queue settings.
+    pub fn fair_queue(&self) -> &FairQueueConfig {
+        &self.fair_queue
+    }
+
+    /// Gets a reference to the config's cyclone pool scaling settings, if the pool should scale.
+    pub fn pool_scaling(&self) -> Option<&PoolScalingConfig> {
+        self.pool_scaling.as_ref()
+    }
+
     /// Gets the config's instance ID.
     pub fn instance_id(&self) -> &str {
         self.instance_id.as_ref()
@@ -176,6 +196,10 @@ pub struct ConfigFile {
     cyclone_client_execution_timeout_secs: u64,
     #[serde(default = "default_veritech_requests_concurrency_limit")]
     veritech_requests_concurrency_limit: usize,
+    #[serde(default)]
+    pub fair_queue: FairQueueConfig,
+    #[serde(default)]
+    pub pool_scaling: Option<PoolScalingConfig>,
     #[serde(default = "random_instance_id")]
     instance_id: String,
     #[serde(default = "default_heartbeat_app")]
@@ -201,6 +225,8 @@ impl ConfigFile {
             healthcheck_pool: default_healthcheck_pool(),
             cyclone_client_execution_timeout_secs: default_cyclone_client_execution_timeout_secs(),
             veritech_requests_concurrency_limit: default_veritech_requests_concurrency_limit(),
+            fair_queue: Default::default(),
+            pool_scaling: Default::default(),
             instance_id: random_instance_id(),
             heartbeat_app: default_heartbeat_app(),
             heartbeat_app_sleep_secs: default_heartbeat_app_sleep_secs(),
@@ -216,6 +242,8 @@ impl ConfigFile {
             healthcheck_pool: default_healthcheck_pool(),
             cyclone_client_execution_timeout_secs: default_cyclone_client_execution_timeout_secs(),
             veritech_requests_concurrency_limit: default_veritech_requests_concurrency_limit(),
+            fair_queue: Default::default(),
+            pool_scaling: Default::default(),
             instance_id: random_instance_id(),
             heartbeat_app: default_heartbeat_app(),
             heartbeat_app_sleep_secs: default_heartbeat_app_sleep_secs(),
@@ -242,6 +270,8 @@ impl TryFrom<ConfigFile> for Config {
             value.cyclone_client_execution_timeout_secs,
         ));
         config.veritech_requests_concurrency_limit(value.veritech_requests_concurrency_limit);
+        config.fair_queue(value.fair_queue);
+        config.pool_scaling(value.pool_scaling);
         config.instance_id(value.instance_id);
 
         config.heartbeat_app(value.heartbeat_app);
@@ -254,6 +284,94 @@ impl TryFrom<ConfigFile> for Config {
     }
 }
 
+/// Weights for sharing cyclone instances between workspaces. A workspace with a weight of 2 is
+/// granted twice as many instances as a workspace with a weight of 1 while both have requests
+/// waiting. Requests with a higher priority are always granted instances first, unless a lower
+/// priority request has been waiting for longer than `max_wait_secs`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FairQueueConfig {
+    #[serde(default = "default_fair_queue_weight")]
+    pub default_weight: u32,
+    /// Weights by workspace id, for workspaces that should not get the default weight.
+    #[serde(default)]
+    pub workspace_weights: HashMap<String, u32>,
+    /// How long a request may wait behind higher priority requests before it is granted the next
+    /// instance anyway.
+    #[serde(default = "default_fair_queue_max_wait_secs")]
+    pub max_wait_secs: u64,
+}
+
+impl FairQueueConfig {
+    /// Gets how long a request may wait behind higher priority requests.
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_secs(self.max_wait_secs)
+    }
+
+    /// Gets the weight of the workspace, which is never less than 1.
+    pub fn weight_for(&self, workspace_id: &str) -> u32 {
+        self.workspace_weights
+            .get(workspace_id)
+            .copied()
+            .unwrap_or(self.default_weight)
+            .max(1)
+    }
+}
+
+impl Default for FairQueueConfig {
+    fn default() -> Self {
+        Self {
+            default_weight: default_fair_queue_weight(),
+            workspace_weights: HashMap::new(),
+            max_wait_secs: default_fair_queue_max_wait_secs(),
+        }
+    }
+}
+
+/// Bounds and thresholds for resizing the cyclone pool with demand. The pool starts out with the
+/// cyclone `pool_size` instances, which is also the most it grows to, and shrinks towards
+/// `min_size` while instances sit unused.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PoolScalingConfig {
+    #[serde(default = "default_pool_scaling_min_size")]
+    pub min_size: u32,
+    /// How often to decide whether to resize the pool.
+    #[serde(default = "default_pool_scaling_interval_secs")]
+    pub interval_secs: u64,
+    /// Grow the pool when requests waited at least this long for an instance, on average.
+    #[serde(default = "default_pool_scaling_scale_up_wait_ms")]
+    pub scale_up_wait_ms: u64,
+    /// Count an interval as idle when at least this percentage of the pool was ready throughout.
+    #[serde(default = "default_pool_scaling_scale_down_idle_percent")]
+    pub scale_down_idle_percent: u32,
+    /// Shrink the pool only after this many idle intervals in a row.
+    #[serde(default = "default_pool_scaling_scale_down_after")]
+    pub scale_down_after: u32,
+}
+
+impl Default for PoolScalingConfig {
+    fn default() -> Self {
+        Self {
+            min_size: default_pool_scaling_min_size(),
+            interval_secs: default_pool_scaling_interval_secs(),
+            scale_up_wait_ms: default_pool_scaling_scale_up_wait_ms(),
+            scale_down_idle_percent: default_pool_scaling_scale_down_idle_percent(),
+            scale_down_after: default_pool_scaling_scale_down_after(),
+        }
+    }
+}
+
+impl From<PoolScalingConfig> for PoolNoodleScalingConfig {
+    fn from(value: PoolScalingConfig) -> Self {
+        Self {
+            min_size: value.min_size,
+            interval: Duration::from_secs(value.interval_secs),
+            scale_up_wait: Duration::from_millis(value.scale_up_wait_ms),
+            scale_down_idle_percent: value.scale_down_idle_percent,
+            scale_down_after: value.scale_down_after,
+        }
+    }
+}
+
 #[remain::sorted]
 #[derive(Clone, Debug)]
 pub enum CycloneSpec {
@@ -328,6 +446,10 @@ pub enum CycloneConfig {
         watch_timeout: Option<Duration>,
         #[serde(default = "default_limit_requests")]
         limit_requets: Option<u32>,
+        #[serde(default)]
+        warm_workers: Option<usize>,
+        #[serde(default)]
+        cgroup_limits: Option<CgroupLimits>,
         #[serde(default = "default_enable_endpoint")]
         ping: bool,
         #[serde(default = "default_enable_endpoint")]
@@ -367,6 +489,8 @@ impl CycloneConfig {
             runtime_strategy: default_runtime_strategy(),
             watch_timeout: Default::default(),
             limit_requets: default_limit_requests(),
+            warm_workers: Default::default(),
+            cgroup_limits: Default::default(),
             ping: default_enable_endpoint(),
             resolver: default_enable_endpoint(),
             action: default_enable_endpoint(),
@@ -478,6 +602,8 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 runtime_strategy,
                 watch_timeout,
                 limit_requets,
+                warm_workers,
+                cgroup_limits,
                 ping,
                 resolver,
                 action,
@@ -485,6 +611,12 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 connect_timeout,
                 create_firecracker_setup_scripts,
             } => {
+                // A cyclone that exits after one request would throw its warm workers away
+                // before any of them is reused
+                if warm_workers.is_some() && limit_requets == Some(1) {
+                    return Err(ConfigError::WarmWorkersWithSingleRequest);
+                }
+
                 let mut builder = LocalUdsInstance::spec();
 
                 //we only need these if running local process. Maybe the builder should handle
@@ -505,6 +637,8 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                     builder.watch_timeout(watch_timeout);
                 }
                 builder.limit_requests(limit_requets);
+                builder.warm_workers(warm_workers);
+                builder.cgroup_limits(cgroup_limits);
                 if ping {
                     builder.ping();
                 }
@@ -614,6 +748,34 @@ fn default_veritech_requests_concurrency_limit() -> usize {
     DEFAULT_VERITECH_REQUESTS_CONCURRENCY_LIMIT
 }
 
+fn default_fair_queue_weight() -> u32 {
+    1
+}
+
+fn default_fair_queue_max_wait_secs() -> u64 {
+    30
+}
+
+fn default_pool_scaling_min_size() -> u32 {
+    10
+}
+
+fn default_pool_scaling_interval_secs() -> u64 {
+    10
+}
+
+fn default_pool_scaling_scale_up_wait_ms() -> u64 {
+    100
+}
+
+fn default_pool_scaling_scale_down_idle_percent() -> u32 {
+    50
+}
+
+fn default_pool_scaling_scale_down_after() -> u32 {
+    6
+}
+
 fn default_heartbeat_app() -> bool {
     true
 }
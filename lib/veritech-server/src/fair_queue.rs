@@ -0,0 +1,530 @@
+//! Fair scheduling of executions across workspaces.
+//!
+//! Requests are pulled off of the work queue in the order they were published, so a single
+//! workspace publishing thousands of requests at once (e.g. a large dependent values update) would
+//! otherwise hold every cyclone instance until it is done. Instead, each request waits in a
+//! sub-queue for its workspace until it is granted a slot, and slots are handed out with deficit
+//! round-robin: each workspace with waiting requests is granted up to its weight in slots before
+//! moving on to the next one.
+//!
+//! Messages are acked as soon as their request is in line, so that a workspace's backlog waits here
+//! rather than in the handlers that pull other workspaces' requests off of the work queue.
+//!
+//! Requests are also split up by their [`ExecutionPriority`], so that a user waiting on a test run
+//! is not stuck behind background work. Slots go to the highest priority with waiting requests,
+//! except when a lower priority request has waited for longer than the configured maximum, in
+//! which case the priority with the longest waiting request goes first.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use si_pool_noodle::ExecutionPriority;
+use telemetry::prelude::*;
+use telemetry_utils::metric;
+use tokio::sync::oneshot;
+
+use crate::config::FairQueueConfig;
+
+/// Grants execution slots to requests, by priority and fairly across workspaces.
+#[derive(Clone)]
+pub struct FairQueue {
+    inner: Arc<Mutex<Inner>>,
+    config: Arc<FairQueueConfig>,
+}
+
+impl fmt::Debug for FairQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FairQueue")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Inner {
+    available_slots: usize,
+    // One level per priority, highest priority first.
+    levels: Vec<PriorityLevel>,
+}
+
+struct PriorityLevel {
+    priority: ExecutionPriority,
+    queues: HashMap<String, WorkspaceQueue>,
+    // Workspaces with waiting requests, in the order they will be granted slots.
+    ready: VecDeque<String>,
+}
+
+#[derive(Default)]
+struct WorkspaceQueue {
+    waiters: VecDeque<Waiter>,
+    deficit: u32,
+}
+
+struct Waiter {
+    enqueued_at: Instant,
+    // The permit itself is sent, so that a request that goes away after being granted its slot
+    // gives it back by dropping the permit still in the channel.
+    sender: oneshot::Sender<FairQueuePermit>,
+}
+
+impl FairQueue {
+    /// Creates a new [`FairQueue`] that allows up to `slots` executions at a time.
+    pub fn new(slots: usize, config: FairQueueConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                available_slots: slots,
+                levels: ExecutionPriority::HIGHEST_FIRST
+                    .into_iter()
+                    .map(PriorityLevel::new)
+                    .collect(),
+            })),
+            config: Arc::new(config),
+        }
+    }
+
+    /// Waits until the workspace is granted an execution slot for a request with the given
+    /// priority. The slot is held until the returned [`FairQueuePermit`] is dropped.
+    pub async fn acquire(
+        &self,
+        workspace_id: &str,
+        priority: ExecutionPriority,
+    ) -> FairQueuePermit {
+        self.enqueue(workspace_id, priority).granted().await
+    }
+
+    /// Takes the request's place in line right away, without waiting for its slot. The place is
+    /// given up if the returned [`FairQueueEntry`] is dropped before its slot is granted.
+    pub fn enqueue(&self, workspace_id: &str, priority: ExecutionPriority) -> FairQueueEntry {
+        let enqueued_at = Instant::now();
+        let mut inner = self.lock();
+        // Only skip the line when nobody is in it
+        let state = if inner.available_slots > 0 && inner.levels.iter().all(PriorityLevel::is_empty)
+        {
+            inner.available_slots -= 1;
+            EntryState::Granted(FairQueuePermit::new(self.clone()))
+        } else {
+            let (sender, receiver) = oneshot::channel();
+            inner.level_mut(priority).enqueue(
+                workspace_id,
+                Waiter {
+                    enqueued_at,
+                    sender,
+                },
+            );
+            metric!(counter.veritech.fair_queue.depth = 1);
+            metric!(
+                counter.veritech.fair_queue.depth_by_priority = 1,
+                label = priority.as_ref()
+            );
+            EntryState::Waiting(receiver)
+        };
+
+        FairQueueEntry {
+            fair_queue: self.clone(),
+            enqueued_at,
+            state,
+        }
+    }
+
+    fn release(&self) {
+        let mut inner = self.lock();
+        let max_wait = self.config.max_wait();
+        loop {
+            // A level that has been passed over for too long goes ahead of everything else, so
+            // that a steady stream of higher priority requests can't starve it.
+            let starved = inner
+                .levels
+                .iter()
+                .enumerate()
+                .filter_map(|(index, level)| level.oldest_enqueued_at().map(|at| (index, at)))
+                .filter(|(_, enqueued_at)| enqueued_at.elapsed() >= max_wait)
+                .min_by_key(|(_, enqueued_at)| *enqueued_at)
+                .map(|(index, _)| index);
+            let next = starved.or_else(|| inner.levels.iter().position(|level| !level.is_empty()));
+
+            let Some(index) = next else {
+                // Nobody is waiting, so the slot goes back for the next request to take
+                inner.available_slots += 1;
+                return;
+            };
+
+            if starved.is_some() && index > 0 {
+                metric!(
+                    counter.veritech.fair_queue.starvation_grants = 1,
+                    label = inner.levels[index].priority.as_ref()
+                );
+            }
+
+            // Only returns false once the level has nothing left in it, so this always ends.
+            if inner.levels[index].grant(self) {
+                return;
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // A panic while holding the lock leaves the queues consistent, since every critical
+        // section finishes its bookkeeping before anything that could panic.
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Inner {
+    fn level_mut(&mut self, priority: ExecutionPriority) -> &mut PriorityLevel {
+        let index = self
+            .levels
+            .iter()
+            .position(|level| level.priority == priority)
+            .unwrap_or(self.levels.len() - 1);
+        &mut self.levels[index]
+    }
+}
+
+impl PriorityLevel {
+    fn new(priority: ExecutionPriority) -> Self {
+        Self {
+            priority,
+            queues: HashMap::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ready.is_empty()
+    }
+
+    fn oldest_enqueued_at(&self) -> Option<Instant> {
+        self.queues
+            .values()
+            .filter_map(|queue| queue.waiters.front())
+            .map(|waiter| waiter.enqueued_at)
+            .min()
+    }
+
+    fn enqueue(&mut self, workspace_id: &str, waiter: Waiter) {
+        let queue = self.queues.entry(workspace_id.to_owned()).or_default();
+        queue.waiters.push_back(waiter);
+        if queue.waiters.len() == 1 {
+            self.ready.push_back(workspace_id.to_owned());
+        }
+    }
+
+    /// Grants a slot to the next waiting request, returning false if there was nobody left at this
+    /// level to take it.
+    fn grant(&mut self, fair_queue: &FairQueue) -> bool {
+        while let Some(workspace_id) = self.ready.front().cloned() {
+            let weight = fair_queue.config.weight_for(&workspace_id);
+            let Some(queue) = self.queues.get_mut(&workspace_id) else {
+                self.ready.pop_front();
+                continue;
+            };
+
+            if queue.deficit == 0 {
+                queue.deficit = weight;
+            }
+
+            let Some(waiter) = queue.waiters.pop_front() else {
+                self.queues.remove(&workspace_id);
+                self.ready.pop_front();
+                continue;
+            };
+            metric!(counter.veritech.fair_queue.depth = -1);
+            metric!(
+                counter.veritech.fair_queue.depth_by_priority = -1,
+                label = self.priority.as_ref()
+            );
+
+            // The waiting request may have gone away (e.g. during shutdown), in which case the
+            // slot goes to the next one without counting against the workspace. The permit that
+            // comes back must not release the slot, both because it is being handed out right here
+            // and because the lock is held.
+            let granted = match waiter.sender.send(FairQueuePermit::new(fair_queue.clone())) {
+                Ok(()) => true,
+                Err(permit) => {
+                    permit.disarm();
+                    false
+                }
+            };
+            if granted {
+                queue.deficit -= 1;
+                trace!(
+                    si.workspace.id = %workspace_id,
+                    priority = %self.priority,
+                    waited_ms = waiter.enqueued_at.elapsed().as_millis() as u64,
+                    "granted execution slot",
+                );
+            }
+
+            if queue.waiters.is_empty() {
+                self.queues.remove(&workspace_id);
+                self.ready.pop_front();
+            } else if queue.deficit == 0 {
+                self.ready.rotate_left(1);
+            }
+
+            if granted {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// A request's place in line in the [`FairQueue`].
+#[must_use]
+pub struct FairQueueEntry {
+    fair_queue: FairQueue,
+    enqueued_at: Instant,
+    state: EntryState,
+}
+
+enum EntryState {
+    Granted(FairQueuePermit),
+    Waiting(oneshot::Receiver<FairQueuePermit>),
+}
+
+impl FairQueueEntry {
+    /// Waits until the request is granted its execution slot.
+    pub async fn granted(self) -> FairQueuePermit {
+        let permit = match self.state {
+            EntryState::Granted(permit) => permit,
+            // The sender is only dropped without sending when the queue itself is dropped, which
+            // can't happen while this entry holds on to it.
+            EntryState::Waiting(receiver) => receiver
+                .await
+                .unwrap_or_else(|_| FairQueuePermit::new(self.fair_queue.clone())),
+        };
+
+        metric!(
+            histogram.veritech.fair_queue.wait_time_ms =
+                self.enqueued_at.elapsed().as_millis() as u64
+        );
+
+        permit
+    }
+}
+
+impl fmt::Debug for FairQueueEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FairQueueEntry")
+            .field("enqueued_at", &self.enqueued_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An execution slot granted by the [`FairQueue`]. The slot is given to the next waiting request
+/// when this is dropped.
+#[must_use]
+pub struct FairQueuePermit {
+    queue: Option<FairQueue>,
+}
+
+impl FairQueuePermit {
+    fn new(queue: FairQueue) -> Self {
+        Self { queue: Some(queue) }
+    }
+
+    /// Drops the permit without giving its slot to anyone.
+    fn disarm(mut self) {
+        self.queue = None;
+    }
+}
+
+impl fmt::Debug for FairQueuePermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FairQueuePermit").finish_non_exhaustive()
+    }
+}
+
+impl Drop for FairQueuePermit {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{future::BoxFuture, FutureExt as _};
+
+    use super::*;
+
+    fn fair_queue(slots: usize) -> FairQueue {
+        FairQueue::new(
+            slots,
+            FairQueueConfig {
+                default_weight: 1,
+                workspace_weights: HashMap::from([("heavy".to_string(), 2)]),
+                max_wait_secs: 60,
+            },
+        )
+    }
+
+    /// Starts acquiring a slot, returning the request once it is waiting in line.
+    fn enqueue<'a>(
+        fair_queue: &'a FairQueue,
+        workspace_id: &'a str,
+        priority: ExecutionPriority,
+    ) -> BoxFuture<'a, FairQueuePermit> {
+        let mut request = fair_queue.acquire(workspace_id, priority).boxed();
+        assert!(
+            (&mut request).now_or_never().is_none(),
+            "request should wait in line"
+        );
+        request
+    }
+
+    /// Releases the permit, returning the labels of the waiting requests in the order they are
+    /// granted slots.
+    fn grant_order(
+        permit: FairQueuePermit,
+        mut waiting: Vec<(&str, BoxFuture<'_, FairQueuePermit>)>,
+    ) -> Vec<String> {
+        let mut order = Vec::new();
+        let mut permit = permit;
+        while !waiting.is_empty() {
+            drop(permit);
+            let mut granted: Vec<_> = waiting
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, (_, request))| {
+                    request.now_or_never().map(|permit| (index, permit))
+                })
+                .collect();
+            assert_eq!(1, granted.len(), "exactly one request should be granted");
+            let (index, next) = granted.remove(0);
+            order.push(waiting.remove(index).0.to_string());
+            permit = next;
+        }
+        order
+    }
+
+    #[tokio::test]
+    async fn skips_the_line_when_nobody_is_waiting() {
+        let fair_queue = fair_queue(2);
+
+        let first = fair_queue
+            .acquire("workspace", ExecutionPriority::Dvu)
+            .now_or_never();
+        let second = fair_queue
+            .acquire("workspace", ExecutionPriority::Dvu)
+            .now_or_never();
+        assert!(first.is_some());
+        assert!(second.is_some());
+
+        let third = enqueue(&fair_queue, "workspace", ExecutionPriority::Interactive);
+        drop(first);
+        assert!(third.now_or_never().is_some());
+    }
+
+    #[tokio::test]
+    async fn grants_slots_by_workspace_weight() {
+        let fair_queue = fair_queue(1);
+        let permit = fair_queue.acquire("held", ExecutionPriority::Dvu).await;
+
+        let dvu = ExecutionPriority::Dvu;
+        let waiting = vec![
+            ("heavy-1", enqueue(&fair_queue, "heavy", dvu)),
+            ("heavy-2", enqueue(&fair_queue, "heavy", dvu)),
+            ("heavy-3", enqueue(&fair_queue, "heavy", dvu)),
+            ("light-1", enqueue(&fair_queue, "light", dvu)),
+            ("light-2", enqueue(&fair_queue, "light", dvu)),
+        ];
+
+        assert_eq!(
+            vec!["heavy-1", "heavy-2", "light-1", "heavy-3", "light-2"],
+            grant_order(permit, waiting)
+        );
+    }
+
+    #[tokio::test]
+    async fn grants_slots_by_priority() {
+        let fair_queue = fair_queue(1);
+        let permit = fair_queue.acquire("held", ExecutionPriority::Dvu).await;
+
+        let waiting = vec![
+            (
+                "background",
+                enqueue(&fair_queue, "workspace", ExecutionPriority::Background),
+            ),
+            (
+                "dvu",
+                enqueue(&fair_queue, "workspace", ExecutionPriority::Dvu),
+            ),
+            (
+                "interactive",
+                enqueue(&fair_queue, "workspace", ExecutionPriority::Interactive),
+            ),
+        ];
+
+        assert_eq!(
+            vec!["interactive", "dvu", "background"],
+            grant_order(permit, waiting)
+        );
+    }
+
+    #[tokio::test]
+    async fn backlog_beyond_the_concurrency_limit_does_not_hold_up_other_workspaces() {
+        // Stands in for the limit on how many messages are handled at once, which requests only
+        // hold while they take their place in line
+        let concurrency_limit = 4;
+        let handler_slots = tokio::sync::Semaphore::new(concurrency_limit);
+        let fair_queue = fair_queue(1);
+        let permit = fair_queue.acquire("held", ExecutionPriority::Dvu).await;
+
+        let mut waiting = Vec::new();
+        let backlog: Vec<String> = (1..=concurrency_limit * 3)
+            .map(|index| format!("busy-{index}"))
+            .collect();
+        for (label, workspace_id) in backlog
+            .iter()
+            .map(|label| (label.as_str(), "busy"))
+            .chain([("light", "light")])
+        {
+            let _handler_slot = handler_slots
+                .try_acquire()
+                .expect("waiting requests should not hold handler slots");
+            let entry = fair_queue.enqueue(workspace_id, ExecutionPriority::Dvu);
+            waiting.push((label, entry.granted().boxed()));
+        }
+
+        let order = grant_order(permit, waiting);
+        assert_eq!(vec!["busy-1", "light", "busy-2"], order[..3]);
+    }
+
+    #[tokio::test]
+    async fn cancelled_request_does_not_take_a_slot() {
+        let fair_queue = fair_queue(1);
+        let permit = fair_queue.acquire("held", ExecutionPriority::Dvu).await;
+
+        let cancelled = enqueue(&fair_queue, "workspace", ExecutionPriority::Dvu);
+        let waiting = enqueue(&fair_queue, "workspace", ExecutionPriority::Dvu);
+        drop(cancelled);
+        drop(permit);
+
+        assert!(waiting.now_or_never().is_some());
+    }
+
+    #[tokio::test]
+    async fn request_cancelled_after_its_grant_gives_the_slot_back() {
+        let fair_queue = fair_queue(1);
+        let permit = fair_queue.acquire("held", ExecutionPriority::Dvu).await;
+
+        // Granted the slot, but gone before it got to take the permit
+        let cancelled = enqueue(&fair_queue, "workspace", ExecutionPriority::Dvu);
+        drop(permit);
+        drop(cancelled);
+
+        assert!(fair_queue
+            .acquire("workspace", ExecutionPriority::Dvu)
+            .now_or_never()
+            .is_some());
+    }
+}
@@ -113,7 +113,7 @@ pub async fn process_request_inner(
 
     // Based on whether or not there is a prefix, we need to determine how many parts there are
     // before the exact subject part we are interested in.
-    if state.nats_subject_has_prefix() {
+    let workspace_id = if state.nats_subject_has_prefix() {
         match (
             parts.next(),
             parts.next(),
@@ -124,6 +124,7 @@ pub async fn process_request_inner(
             (Some(_), Some(_), Some(_), Some(workspace_id), Some(change_set_id)) => {
                 span.record("si.workspace.id", workspace_id);
                 span.record("si.change_set.id", change_set_id);
+                workspace_id
             }
             _ => return Err(HandlerError::InvalidIncomingSubject(subject)),
         }
@@ -132,10 +133,11 @@ pub async fn process_request_inner(
             (Some(_), Some(_), Some(workspace_id), Some(change_set_id)) => {
                 span.record("si.workspace.id", workspace_id);
                 span.record("si.change_set.id", change_set_id);
+                workspace_id
             }
             _ => return Err(HandlerError::InvalidIncomingSubject(subject)),
         }
-    }
+    };
 
     let (Some(request_subject), None) = (parts.next(), parts.next()) else {
         return Err(HandlerError::InvalidIncomingSubject(subject));
@@ -152,19 +154,19 @@ pub async fn process_request_inner(
 
     match veritech_request {
         VeritechRequest::ActionRun(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, workspace_id, reply_subject)
         }
         VeritechRequest::Management(request) => {
-            dispatch_request(state, *request, reply_subject).await?
+            dispatch_request(state, *request, workspace_id, reply_subject)
         }
         VeritechRequest::Resolver(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, workspace_id, reply_subject)
         }
         VeritechRequest::SchemaVariantDefinition(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, workspace_id, reply_subject)
         }
         VeritechRequest::Validation(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, workspace_id, reply_subject)
         }
         // Kill requests do not get handled here
         VeritechRequest::KillExecution(_) => {
@@ -175,7 +177,54 @@ pub async fn process_request_inner(
     Ok(())
 }
 
-async fn dispatch_request<Request>(
+/// Takes the request's place in line for its workspace and hands it off to run once it is granted
+/// a slot. The message is acked as soon as the request is in line, so that requests waiting their
+/// turn don't hold the handler slots that other workspaces' requests need to get in line at all.
+fn dispatch_request<Request>(
+    state: AppState,
+    request: Request,
+    workspace_id: &str,
+    reply_mailbox: Subject,
+) where
+    Request: CycloneRequestable + DecryptRequest + Serialize + Clone + Send + Sync + 'static,
+    Request::Response: Serialize + DeserializeOwned + std::fmt::Debug + std::marker::Unpin,
+    HandlerError: From<ExecutionError<<Request as CycloneRequestable>::Response>>,
+{
+    let entry = state.fair_queue.enqueue(workspace_id, request.priority());
+    let tasks = state.tasks.clone();
+
+    tasks.spawn(
+        async move {
+            // Hold the slot until the execution is done
+            let _permit = entry.granted().await;
+
+            let execution_id = request.execution_id().to_owned();
+            if let Err(err) = execute_request(state.clone(), request, reply_mailbox.clone()).await
+            {
+                error!(si.error.message = ?err, si.func_run.id = %execution_id, "failed to process request");
+                // The message has already been acked, so let the requester know rather than leave
+                // it waiting
+                let func_res_failure = FunctionResultFailure::new_for_veritech_server_error(
+                    execution_id,
+                    "failed to run function",
+                    timestamp(),
+                );
+                let publisher = Publisher::new(&state.nats, &reply_mailbox);
+                if let Err(err) = publisher
+                    .publish_result(&si_pool_noodle::FunctionResult::Failure::<Request>(
+                        func_res_failure,
+                    ))
+                    .await
+                {
+                    error!(si.error.message = ?err, "failed to publish errored result");
+                }
+            }
+        }
+        .instrument(Span::current()),
+    );
+}
+
+async fn execute_request<Request>(
     state: AppState,
     mut request: Request,
     reply_mailbox: Subject,
@@ -186,6 +235,7 @@ where
     HandlerError: From<ExecutionError<<Request as CycloneRequestable>::Response>>,
 {
     let span = current_span_for_instrument_at!("info");
+
     let mut client = match state.cyclone_pool.get().await {
         Ok(client) => client,
         Err(err) => {
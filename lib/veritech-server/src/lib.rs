@@ -1,5 +1,6 @@
 mod app_state;
 mod config;
+mod fair_queue;
 mod handlers;
 mod heartbeat;
 mod publisher;
@@ -17,7 +18,8 @@ pub(crate) use crate::publisher::{Publisher, PublisherError};
 pub use crate::{
     config::{
         detect_and_configure_development, Config, ConfigBuilder, ConfigError, ConfigFile,
-        CycloneSpec, CycloneStream, StandardConfig, StandardConfigFile,
+        CycloneSpec, CycloneStream, FairQueueConfig, PoolScalingConfig, StandardConfig,
+        StandardConfigFile,
     },
     server::Server,
 };
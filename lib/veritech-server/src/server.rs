@@ -30,12 +30,13 @@ use si_pool_noodle::{
 use telemetry::prelude::*;
 use telemetry_utils::metric;
 use tokio::sync::{oneshot, Mutex};
-use tokio_util::sync::CancellationToken;
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use veritech_core::{incoming_subject, veritech_work_queue, ExecutionId, GetNatsSubjectFor};
 
 use crate::{
     app_state::{AppState, KillAppState},
     config::CycloneSpec,
+    fair_queue::FairQueue,
     handlers,
     heartbeat::HeartbeatApp,
     Config, ServerError, ServerResult,
@@ -63,6 +64,7 @@ pub struct Server {
     metadata: Arc<ServerMetadata>,
     inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
     kill_inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
+    tasks: TaskTracker,
     shutdown_token: CancellationToken,
 }
 
@@ -126,6 +128,7 @@ impl Server {
                 let pool_config = PoolNoodleConfig {
                     check_health: config.healthcheck_pool(),
                     pool_size: spec.pool_size,
+                    scaling: config.pool_scaling().cloned().map(Into::into),
                     shutdown_token: token.clone(),
                     spec: spec.clone(),
                     ..Default::default()
@@ -146,14 +149,22 @@ impl Server {
                 metric!(counter.veritech.handlers_doing_work = 0);
                 metric!(counter.veritech.pool_exhausted = 0);
 
+                // Only as many requests as there are instances get to run at once, with the rest
+                // waiting their workspace's turn.
+                let fair_queue =
+                    FairQueue::new(spec.pool_size as usize, config.fair_queue().clone());
+                let tasks = TaskTracker::new();
+
                 let inner_future = Self::build_app(
                     metadata.clone(),
                     config.veritech_requests_concurrency_limit(),
                     cyclone_pool,
+                    fair_queue,
                     Arc::new(decryption_key),
                     config.cyclone_client_execution_timeout(),
                     nats.clone(),
                     kill_senders.clone(),
+                    tasks.clone(),
                     token.clone(),
                 )
                 .await?;
@@ -183,6 +194,7 @@ impl Server {
                         metadata,
                         inner: inner_future,
                         kill_inner: kill_inner_future,
+                        tasks,
                         shutdown_token: token,
                     },
                     maybe_heartbeat_app,
@@ -212,6 +224,10 @@ impl Server {
         }
         kill_inner_result?.map_err(ServerError::Naxum)?;
 
+        // Requests that were taken off the work queue have been acked, so they are finished here
+        self.tasks.close();
+        self.tasks.wait().await;
+
         info!("veritech main loop shutdown complete");
         Ok(())
     }
@@ -221,10 +237,12 @@ impl Server {
         metadata: Arc<ServerMetadata>,
         concurrency_limit: usize,
         cyclone_pool: PoolNoodle<LocalUdsInstance, LocalUdsInstanceSpec>,
+        fair_queue: FairQueue,
         decryption_key: Arc<VeritechDecryptionKey>,
         cyclone_client_execution_timeout: Duration,
         nats: NatsClient,
         kill_senders: Arc<Mutex<HashMap<ExecutionId, oneshot::Sender<()>>>>,
+        tasks: TaskTracker,
         token: CancellationToken,
     ) -> ServerResult<Box<dyn Future<Output = io::Result<()>> + Unpin + Send>> {
         let connection_metadata = nats.metadata_clone();
@@ -245,10 +263,12 @@ impl Server {
         let state = AppState::new(
             metadata,
             cyclone_pool,
+            fair_queue,
             decryption_key,
             cyclone_client_execution_timeout,
             nats,
             kill_senders,
+            tasks,
         );
 
         let app = ServiceBuilder::new()